@@ -102,6 +102,9 @@ pub fn register_commands(builder: tauri::Builder<tauri::Wry>) -> tauri::Builder<
         start_clipboard_monitor,
         stop_clipboard_monitor,
         get_clipboard_content_type,
+        get_clipboard_history,
+        clear_clipboard_history,
+        restore_clipboard_item,
         move_and_link,
         create_links_only,
         cancel_move_operation,
@@ -117,6 +120,7 @@ pub fn register_commands(builder: tauri::Builder<tauri::Wry>) -> tauri::Builder<
         read_file_binary_raw,
         read_app_data_file_binary,
         read_file_as_base64,
+        read_file_base64_chunk,
         save_uploaded_file,
         copy_file_to_app_data,
         copy_directory_in_app_data,
@@ -142,6 +146,8 @@ pub fn register_commands(builder: tauri::Builder<tauri::Wry>) -> tauri::Builder<
         stop_directory_cleanup,
         scan_content_duplicates,
         stop_dedup_scan,
+        pause_dedup_scan,
+        resume_dedup_scan,
         read_file_content_for_diff,
         delete_duplicate_files,
         // Skill 管理命令
@@ -170,6 +176,13 @@ pub fn register_commands(builder: tauri::Builder<tauri::Wry>) -> tauri::Builder<
         stop_llm_inspector,
         get_inspector_status,
         update_inspector_target,
+        update_inspector_tls,
+        inspector_list_records,
+        inspector_get_record,
+        inspector_read_full_response_body,
+        inspector_replay_record,
+        inspector_export_har,
+        inspector_get_usage_summary,
         // Git分析器命令
         git_load_repository,
         git_load_repository_stream,
@@ -177,11 +190,15 @@ pub fn register_commands(builder: tauri::Builder<tauri::Wry>) -> tauri::Builder<
         git_get_branches,
         git_get_incremental_commits,
         git_load_incremental_stream,
+        git_search_commits,
         git_get_commit_detail,
         git_cherry_pick,
         git_revert,
         git_export_commits,
         git_format_log,
+        git_blame,
+        git_diff_range,
+        git_file_history,
         git_update_commit_message,
         git_enrich_commits_stream,
         git_cancel_enrich,
@@ -197,6 +214,8 @@ pub fn register_commands(builder: tauri::Builder<tauri::Wry>) -> tauri::Builder<
         git_pull,
         // OCR命令
         native_ocr,
+        native_ocr_batch,
+        ocr_list_available_languages,
         // 外部播放器透明弹幕覆盖层命令 (Windows)
         #[cfg(windows)]
         find_player_windows,
@@ -242,6 +261,8 @@ pub fn register_commands(builder: tauri::Builder<tauri::Wry>) -> tauri::Builder<
         set_window_position,
         set_window_shadow,
         ensure_window_visible,
+        set_window_always_on_top,
+        set_window_opacity,
         // 窗口配置管理命令
         save_window_config,
         apply_window_config,
@@ -267,6 +288,7 @@ pub fn register_commands(builder: tauri::Builder<tauri::Wry>) -> tauri::Builder<
         list_config_files,
         export_all_configs_to_zip,
         import_all_configs_from_zip,
+        preview_config_import,
         // 资产管理命令
         check_asset_manager_document_converter,
         detect_asset_manager_document_converters,
@@ -275,10 +297,15 @@ pub fn register_commands(builder: tauri::Builder<tauri::Wry>) -> tauri::Builder<
         get_asset_base_path,
         import_asset_from_path,
         import_asset_from_bytes,
+        import_asset_from_url,
         get_asset_base64,
         get_asset_binary,
         read_text_file,
         list_all_assets,
+        create_collection,
+        add_assets_to_collection,
+        list_collections,
+        list_collection_assets,
         rebuild_hash_index,
         find_duplicate_files,
         delete_asset,
@@ -287,9 +314,13 @@ pub fn register_commands(builder: tauri::Builder<tauri::Wry>) -> tauri::Builder<
         list_assets_paginated,
         get_asset_stats,
         rebuild_catalog_index,
+        verify_catalog_index,
+        migrate_asset_storage,
         // 资产来源管理命令
         remove_asset_source,
         add_asset_source,
+        add_asset_tags,
+        remove_asset_tags,
         remove_asset_completely,
         remove_assets_completely,
         find_asset_by_hash,
@@ -304,6 +335,7 @@ pub fn register_commands(builder: tauri::Builder<tauri::Wry>) -> tauri::Builder<
         batch_delete_agent_assets,
         list_agent_assets,
         delete_all_agent_assets,
+        cleanup_orphan_agent_assets,
         get_agent_asset_path,
         // 插件管理命令
         uninstall_plugin,
@@ -322,6 +354,7 @@ pub fn register_commands(builder: tauri::Builder<tauri::Wry>) -> tauri::Builder<
         // 原生插件命令
         native_plugin::load_native_plugin,
         native_plugin::unload_native_plugin,
+        native_plugin::reload_native_plugin,
         native_plugin::call_native_plugin_method,
         // 窗口特效命令
         apply_window_effect,
@@ -330,6 +363,8 @@ pub fn register_commands(builder: tauri::Builder<tauri::Wry>) -> tauri::Builder<
         check_command_version,
         check_ffmpeg_availability,
         process_media,
+        process_media_batch,
+        extract_video_frame,
         kill_ffmpeg_process,
         get_media_metadata,
         get_full_media_info,
@@ -348,8 +383,7 @@ pub fn register_commands(builder: tauri::Builder<tauri::Wry>) -> tauri::Builder<
         search_llm_data_stream,
         cancel_llm_chat_search,
         search_media_generator_data,
-        // 基于 rdev 的拖拽会话命令 (仅在非 macOS 上注册)
-        #[cfg(not(target_os = "macos"))]
+        // 拖拽会话命令 (非 macOS 基于 rdev，macOS 基于 NSEvent 轮询)
         start_drag_session,
         // 知识库命令
         crate::recall::recall_initialize,
@@ -370,7 +404,10 @@ pub fn register_commands(builder: tauri::Builder<tauri::Wry>) -> tauri::Builder<
         crate::recall::recall_retrieval_cache_clear,
         crate::recall::recall_retrieval_cache_stats,
         crate::recall::recall_search,
+        crate::recall::recall_search_paginated,
         crate::recall::recall_upsert_entry,
+        crate::recall::recall_list_entry_versions,
+        crate::recall::recall_restore_entry_version,
         crate::recall::recall_delete_entry,
         crate::recall::recall_batch_delete_entries,
         crate::recall::recall_batch_patch_entries,
@@ -387,12 +424,17 @@ pub fn register_commands(builder: tauri::Builder<tauri::Wry>) -> tauri::Builder<
         crate::recall::recall_sync_tag_vectors,
         crate::recall::recall_rebuild_tag_pool_index,
         crate::recall::recall_list_all_tags,
+        crate::recall::recall_rename_tag,
+        crate::recall::recall_merge_tags,
+        crate::recall::recall_get_reference_graph,
         crate::recall::recall_list_tag_pool_models,
         crate::recall::recall_clear_tag_pool,
         crate::recall::recall_clear_other_tag_pools,
         crate::recall::recall_flush_all_tag_pools,
         crate::recall::recall_clone_base,
+        crate::recall::recall_convert_entry_storage,
         crate::recall::recall_export_base,
+        crate::recall::recall_export_markdown,
         crate::recall::recall_export_backup,
         crate::recall::recall_export_backups,
         crate::recall::recall_cancel_backup_operation,
@@ -18,6 +18,7 @@
 //! - 保存资产文件到 Agent 专属目录
 //! - 删除 Agent 资产
 //! - 列出 Agent 的所有资产
+//! - 清理已删除 Agent 残留的孤儿资产
 //!
 //! 资产存储路径：`appdata://llm-chat/agents/{agent_id}/assets/{filename}`
 
@@ -26,6 +27,7 @@ use image::ImageFormat;
 use lofty::file::TaggedFileExt;
 use lofty::probe::Probe;
 use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
 use std::fs;
 use std::path::{Path, PathBuf};
 use tauri::AppHandle;
@@ -48,6 +50,27 @@ pub struct AgentAssetInfo {
     pub thumbnail_path: Option<String>,
 }
 
+/// 孤儿资产清理结果
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OrphanAssetCleanupResult {
+    /// 清理的资产目录数量
+    pub removed_count: usize,
+    /// 释放的空间（字节）
+    pub freed_bytes: u64,
+    /// 被清理的 Agent ID
+    pub removed_agent_ids: Vec<String>,
+    /// 清理失败的条目
+    pub errors: Vec<String>,
+}
+
+/// 获取所有 Agent 目录的根路径
+fn get_agents_root_dir(app: &AppHandle) -> PathBuf {
+    crate::get_app_data_dir(app.config())
+        .join("agent-manager")
+        .join("agents")
+}
+
 /// 获取 Agent 资产目录的基础路径
 ///
 /// 返回 `{app_data_dir}/agent-manager/agents/{agent_id}/assets` 路径
@@ -546,6 +569,120 @@ pub async fn delete_all_agent_assets(app: AppHandle, agent_id: String) -> Result
     Ok(())
 }
 
+/// 在 Agent 根目录中查找不属于任何现存 Agent 的资产目录
+///
+/// 返回 (agent_id, assets 目录) 列表。符号链接、隐藏目录以及规范化后
+/// 不在根目录内的路径都会被跳过，避免误删目录外的内容
+fn find_orphan_asset_dirs(
+    agents_root: &Path,
+    valid_agent_ids: &HashSet<String>,
+) -> Result<Vec<(String, PathBuf)>, String> {
+    let canonical_root = agents_root
+        .canonicalize()
+        .map_err(|e| format!("解析 Agent 目录失败: {}", e))?;
+
+    let mut orphans = Vec::new();
+    let entries =
+        fs::read_dir(&canonical_root).map_err(|e| format!("读取 Agent 目录失败: {}", e))?;
+    for entry in entries.flatten() {
+        let Ok(file_type) = entry.file_type() else {
+            continue;
+        };
+        if !file_type.is_dir() || file_type.is_symlink() {
+            continue;
+        }
+
+        let agent_id = entry.file_name().to_string_lossy().to_string();
+        if agent_id.starts_with('.') || valid_agent_ids.contains(&agent_id) {
+            continue;
+        }
+
+        let assets_dir = entry.path().join("assets");
+        let Ok(metadata) = fs::symlink_metadata(&assets_dir) else {
+            continue;
+        };
+        if !metadata.is_dir() {
+            continue;
+        }
+
+        // 规范化后必须仍位于根目录下的 {agent_id}/assets
+        match assets_dir.canonicalize() {
+            Ok(canonical) if canonical == canonical_root.join(&agent_id).join("assets") => {
+                orphans.push((agent_id, canonical));
+            }
+            _ => log::warn!("跳过可疑的资产目录: {}", assets_dir.display()),
+        }
+    }
+
+    orphans.sort();
+    Ok(orphans)
+}
+
+/// 清理孤儿资产
+///
+/// 扫描 Agent 资产根目录，把不属于 `valid_agent_ids` 中任何 Agent 的 assets 目录移入回收站。
+///
+/// # 参数
+/// - `app`: Tauri 应用句柄
+/// - `valid_agent_ids`: 当前仍存在的 Agent ID 列表，不能为空（防止在 Agent 尚未加载时误删全部资产）
+///
+/// # 返回
+/// 返回清理数量与释放的空间
+#[tauri::command]
+pub async fn cleanup_orphan_agent_assets(
+    app: AppHandle,
+    valid_agent_ids: Vec<String>,
+) -> Result<OrphanAssetCleanupResult, String> {
+    if valid_agent_ids.is_empty() {
+        return Err("有效 Agent 列表为空，已中止清理以防误删".to_string());
+    }
+
+    let agents_root = get_agents_root_dir(&app);
+    let mut result = OrphanAssetCleanupResult {
+        removed_count: 0,
+        freed_bytes: 0,
+        removed_agent_ids: Vec::new(),
+        errors: Vec::new(),
+    };
+
+    if !agents_root.exists() {
+        return Ok(result);
+    }
+
+    let valid_agent_ids: HashSet<String> = valid_agent_ids.into_iter().collect();
+    let orphans = tokio::task::spawn_blocking(move || {
+        find_orphan_asset_dirs(&agents_root, &valid_agent_ids).map(|orphans| {
+            orphans
+                .into_iter()
+                .map(|(agent_id, dir)| {
+                    let size = super::file_operations::calculate_dir_size_fast(&dir);
+                    (agent_id, dir, size)
+                })
+                .collect::<Vec<_>>()
+        })
+    })
+    .await
+    .map_err(|e| format!("扫描孤儿资产失败: {}", e))??;
+
+    for (agent_id, assets_dir, size) in orphans {
+        match trash::delete(&assets_dir) {
+            Ok(()) => {
+                log::info!("已清理孤儿资产: {} ({} 字节)", agent_id, size);
+                result.removed_count += 1;
+                result.freed_bytes += size;
+                // Agent 目录已空时一并移除
+                if let Some(agent_dir) = assets_dir.parent() {
+                    let _ = fs::remove_dir(agent_dir);
+                }
+                result.removed_agent_ids.push(agent_id);
+            }
+            Err(e) => result.errors.push(format!("{}: {}", agent_id, e)),
+        }
+    }
+
+    Ok(result)
+}
+
 /// 获取 Agent 资产的完整路径
 ///
 /// 返回资产文件在系统中的完整路径，用于前端通过 convertFileSrc 转换。
@@ -650,4 +787,20 @@ mod tests {
             "application/octet-stream"
         );
     }
+
+    #[test]
+    fn test_find_orphan_asset_dirs() {
+        let root = tempfile::tempdir().unwrap();
+        for id in ["alive", "gone", ".hidden"] {
+            fs::create_dir_all(root.path().join(id).join("assets")).unwrap();
+        }
+        // 没有 assets 目录的 Agent 不算孤儿
+        fs::create_dir_all(root.path().join("empty")).unwrap();
+
+        let valid: HashSet<String> = ["alive".to_string()].into_iter().collect();
+        let orphans = find_orphan_asset_dirs(root.path(), &valid).unwrap();
+        let ids: Vec<&str> = orphans.iter().map(|(id, _)| id.as_str()).collect();
+        assert_eq!(ids, vec!["gone"]);
+        assert!(orphans[0].1.ends_with("gone/assets"));
+    }
 }
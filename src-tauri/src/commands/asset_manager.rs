@@ -12,19 +12,21 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use crate::error::{AppError, AppResult, ResultExt};
 use crate::utils::mime;
 use base64::{engine::general_purpose, Engine as _};
 use chrono::Utc;
 use content_inspector::{inspect, ContentType};
 use lofty::file::TaggedFileExt;
 use lofty::probe::Probe;
+use rayon::prelude::*;
 use serde::{Deserialize, Serialize};
-use sha2::{Digest, Sha256};
-use std::collections::{HashMap, HashSet};
+use std::collections::{BTreeMap, HashMap, HashSet};
 use std::fs;
 use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
 use std::sync::{Arc, RwLock};
-use std::time::Duration;
+use std::time::{Duration, SystemTime};
 use tauri::{AppHandle, Emitter};
 use uuid::Uuid;
 
@@ -40,6 +42,12 @@ pub struct AssetCatalog {
     pub base_dir: Arc<RwLock<Option<PathBuf>>>,
     /// 是否有未保存的更改
     pub is_dirty: Arc<RwLock<bool>>,
+    /// 内存索引最近一次与磁盘 Catalog 文件同步时，该文件的修改时间
+    disk_mtime: Arc<RwLock<Option<SystemTime>>>,
+    /// 哈希 -> 资产 ID 的全局查找表，供导入去重使用
+    ///
+    /// 只在条目整体替换、新增或哈希变化时维护；被删除的 ID 在查找时按 `entries` 过滤。
+    hash_lookup: Arc<RwLock<HashMap<String, Vec<String>>>>,
 }
 
 impl AssetCatalog {
@@ -48,6 +56,8 @@ impl AssetCatalog {
             entries: Arc::new(RwLock::new(HashMap::new())),
             base_dir: Arc::new(RwLock::new(None)),
             is_dirty: Arc::new(RwLock::new(false)),
+            disk_mtime: Arc::new(RwLock::new(None)),
+            hash_lookup: Arc::new(RwLock::new(HashMap::new())),
         }
     }
 
@@ -67,27 +77,25 @@ impl AssetCatalog {
             return Ok(());
         }
 
-        use std::io::{BufRead, BufReader};
-        let file =
-            fs::File::open(&catalog_path).map_err(|e| format!("无法打开 Catalog 文件: {}", e))?;
-        let reader = BufReader::new(file);
+        let entries = load_catalog_file(&catalog_path)?;
 
-        let mut entries = HashMap::new();
-        for line in reader.lines() {
-            let line_content = line.map_err(|e| format!("读取 Catalog 文件行失败: {}", e))?;
-            if line_content.trim().is_empty() {
-                continue;
-            }
-            if let Ok(mut entry) = serde_json::from_str::<CatalogEntry>(&line_content) {
-                entry.migrate_if_needed();
-                entries.insert(entry.id.clone(), entry);
-            }
+        let legacy_count = entries
+            .values()
+            .filter(|entry| entry.sha256.is_some() && entry.blake3.is_none())
+            .count();
+        if legacy_count > 0 {
+            log::warn!(
+                "[AssetCatalog] 有 {} 条资产仍使用旧版 SHA-256 哈希，需要重建哈希索引以迁移到 BLAKE3，否则这些资产无法参与去重",
+                legacy_count
+            );
         }
 
         {
             let mut entries_lock = self.entries.write().map_err(|e| e.to_string())?;
             *entries_lock = entries;
         }
+        self.rebuild_hash_lookup()?;
+        self.sync_disk_mtime(&catalog_path);
 
         log::info!(
             "[AssetCatalog] 内存索引初始化完成，共加载 {} 条资产",
@@ -96,10 +104,134 @@ impl AssetCatalog {
         Ok(())
     }
 
+    /// 记录磁盘 Catalog 文件当前的修改时间，表示内存与磁盘已一致
+    fn sync_disk_mtime(&self, catalog_path: &Path) {
+        Self::record_disk_mtime(&self.disk_mtime, catalog_path);
+    }
+
+    fn record_disk_mtime(disk_mtime_arc: &Arc<RwLock<Option<SystemTime>>>, catalog_path: &Path) {
+        if let Ok(mut disk_mtime) = disk_mtime_arc.write() {
+            *disk_mtime = read_file_mtime(catalog_path);
+        }
+    }
+
+    /// 如果磁盘上的 Catalog 文件在外部被修改（mtime 变化），则重新加载内存索引
+    ///
+    /// 内存中存在尚未落盘的修改时以内存为准，不做重载。
+    pub fn reload_if_changed(&self) -> Result<(), String> {
+        if self.is_dirty.read().map(|dirty| *dirty).unwrap_or(true) {
+            return Ok(());
+        }
+
+        let base_dir = {
+            let base_dir_lock = self.base_dir.read().map_err(|e| e.to_string())?;
+            match base_dir_lock.clone() {
+                Some(dir) => dir,
+                None => return Ok(()),
+            }
+        };
+        let catalog_path = get_catalog_path(&base_dir)?;
+
+        let current_mtime = read_file_mtime(&catalog_path);
+        let known_mtime = *self.disk_mtime.read().map_err(|e| e.to_string())?;
+        if current_mtime.is_none() || current_mtime == known_mtime {
+            return Ok(());
+        }
+
+        let entries = load_catalog_file(&catalog_path)?;
+        let count = entries.len();
+        {
+            let mut entries_lock = self.entries.write().map_err(|e| e.to_string())?;
+            *entries_lock = entries;
+        }
+        self.rebuild_hash_lookup()?;
+        self.sync_disk_mtime(&catalog_path);
+
+        log::info!(
+            "[AssetCatalog] 检测到 Catalog 文件在外部被修改，已重新加载 {} 条资产",
+            count
+        );
+        Ok(())
+    }
+
+    /// 立即将内存索引写入磁盘（不等待防抖）
+    pub fn flush(&self) -> Result<(), String> {
+        {
+            let mut dirty = self.is_dirty.write().map_err(|e| e.to_string())?;
+            *dirty = true;
+        }
+        Self::save_internal(
+            self.entries.clone(),
+            self.base_dir.clone(),
+            self.is_dirty.clone(),
+            self.disk_mtime.clone(),
+        )
+    }
+
     pub fn get_count(&self) -> usize {
         self.entries.read().map(|e| e.len()).unwrap_or(0)
     }
 
+    /// 按当前内存条目重建哈希查找表，条目被整体替换或哈希被重算后调用
+    fn rebuild_hash_lookup(&self) -> Result<(), String> {
+        let lookup = {
+            let entries = self.entries.read().map_err(|e| e.to_string())?;
+            build_hash_lookup(entries.values())
+        };
+        *self.hash_lookup.write().map_err(|e| e.to_string())? = lookup;
+        Ok(())
+    }
+
+    /// 将新增条目的哈希登记到查找表
+    fn register_hashes(&self, entry: &CatalogEntry) -> Result<(), String> {
+        let mut lookup = self.hash_lookup.write().map_err(|e| e.to_string())?;
+        add_to_hash_lookup(&mut lookup, entry);
+        Ok(())
+    }
+
+    /// 在全局 Catalog 中按哈希查找可复用的资产（跨月份、跨类型）
+    ///
+    /// Catalog 中记录了文件但磁盘上已不存在（例如被手动删除）的条目会被移除并落盘，
+    /// 以便后续导入能正常重新入库。
+    fn find_duplicate(
+        &self,
+        app: &AppHandle,
+        base_dir: &Path,
+        file_hash: &str,
+    ) -> Result<Option<Asset>, String> {
+        let (found, stale_ids) = {
+            let entries = self.entries.read().map_err(|e| e.to_string())?;
+            let lookup = self.hash_lookup.read().map_err(|e| e.to_string())?;
+            find_duplicate_in_catalog(base_dir, file_hash, &entries, &lookup)
+        };
+
+        if !stale_ids.is_empty() {
+            log::warn!(
+                "[AssetCatalog] 哈希 {} 对应的 {} 个资产文件已丢失，已从 Catalog 中移除: {:?}",
+                file_hash,
+                stale_ids.len(),
+                stale_ids
+            );
+            {
+                let mut entries = self.entries.write().map_err(|e| e.to_string())?;
+                for id in &stale_ids {
+                    entries.remove(id);
+                }
+            }
+            if let Some(ids) = self
+                .hash_lookup
+                .write()
+                .map_err(|e| e.to_string())?
+                .get_mut(file_hash)
+            {
+                ids.retain(|id| !stale_ids.contains(id));
+            }
+            self.mark_dirty(app);
+        }
+
+        Ok(found)
+    }
+
     /// 标记为脏并调度保存
     pub fn mark_dirty(&self, _app: &AppHandle) {
         {
@@ -115,13 +247,14 @@ impl AssetCatalog {
         let entries_arc = self.entries.clone();
         let base_dir_arc = self.base_dir.clone();
         let dirty_arc = self.is_dirty.clone();
+        let disk_mtime_arc = self.disk_mtime.clone();
 
         tauri::async_runtime::spawn(async move {
             // 等待 2 秒防抖
             tokio::time::sleep(Duration::from_secs(2)).await;
 
             // 执行保存
-            let result = Self::save_internal(entries_arc, base_dir_arc, dirty_arc);
+            let result = Self::save_internal(entries_arc, base_dir_arc, dirty_arc, disk_mtime_arc);
             if let Err(e) = result {
                 log::error!("[AssetCatalog] 异步保存失败: {}", e);
             }
@@ -132,6 +265,7 @@ impl AssetCatalog {
         entries_arc: Arc<RwLock<HashMap<String, CatalogEntry>>>,
         base_dir_arc: Arc<RwLock<Option<PathBuf>>>,
         dirty_arc: Arc<RwLock<bool>>,
+        disk_mtime_arc: Arc<RwLock<Option<SystemTime>>>,
     ) -> Result<(), String> {
         // 重置脏标记
         {
@@ -173,12 +307,86 @@ impl AssetCatalog {
 
         fs::rename(&temp_catalog_path, &catalog_path)
             .map_err(|e| format!("重命名临时 Catalog 文件失败: {}", e))?;
+        Self::record_disk_mtime(&disk_mtime_arc, &catalog_path);
 
         log::debug!("[AssetCatalog] 目录索引已同步到磁盘");
         Ok(())
     }
 }
 
+/// 坏行数超过该值时提示用户重建 Catalog 索引
+const CATALOG_CORRUPTED_LINES_THRESHOLD: usize = 5;
+
+/// Catalog 文件的解析结果
+struct CatalogParseResult {
+    entries: HashMap<String, CatalogEntry>,
+    /// 非空行总数
+    total_lines: usize,
+    /// 无法解析的行号（从 1 开始）
+    corrupted_lines: Vec<usize>,
+}
+
+/// 逐行解析 Catalog 内容（JSONL），坏行（如崩溃留下的半行 JSON）跳过并记录行号
+fn parse_catalog_lines<R: std::io::BufRead>(reader: R) -> Result<CatalogParseResult, String> {
+    let mut result = CatalogParseResult {
+        entries: HashMap::new(),
+        total_lines: 0,
+        corrupted_lines: Vec::new(),
+    };
+
+    for (index, line) in reader.lines().enumerate() {
+        let line_content = match line {
+            Ok(content) => content,
+            // 非 UTF-8 等无法读取的行同样视为坏行
+            Err(e) if e.kind() == std::io::ErrorKind::InvalidData => {
+                result.total_lines += 1;
+                result.corrupted_lines.push(index + 1);
+                continue;
+            }
+            Err(e) => return Err(format!("读取 Catalog 文件行失败: {}", e)),
+        };
+        if line_content.trim().is_empty() {
+            continue;
+        }
+        result.total_lines += 1;
+        match serde_json::from_str::<CatalogEntry>(&line_content) {
+            Ok(mut entry) => {
+                entry.migrate_if_needed();
+                result.entries.insert(entry.id.clone(), entry);
+            }
+            Err(e) => {
+                log::warn!(
+                    "[AssetCatalog] 跳过无法解析的 Catalog 第 {} 行: {}",
+                    index + 1,
+                    e
+                );
+                result.corrupted_lines.push(index + 1);
+            }
+        }
+    }
+
+    Ok(result)
+}
+
+/// 从磁盘读取 Catalog 文件（JSONL），跳过无法解析的行
+fn load_catalog_file(catalog_path: &Path) -> Result<HashMap<String, CatalogEntry>, String> {
+    let file = fs::File::open(catalog_path).map_err(|e| format!("无法打开 Catalog 文件: {}", e))?;
+    let result = parse_catalog_lines(std::io::BufReader::new(file))?;
+
+    if result.corrupted_lines.len() > CATALOG_CORRUPTED_LINES_THRESHOLD {
+        log::warn!(
+            "[AssetCatalog] Catalog 文件中有 {} 行损坏，建议重建目录索引",
+            result.corrupted_lines.len()
+        );
+    }
+
+    Ok(result.entries)
+}
+
+fn read_file_mtime(path: &Path) -> Option<SystemTime> {
+    fs::metadata(path).and_then(|m| m.modified()).ok()
+}
+
 impl Default for AssetCatalog {
     fn default() -> Self {
         Self::new()
@@ -215,6 +423,23 @@ pub struct ListAssetsPaginatedPayload {
     pub search_query: Option<String>,
     #[serde(default)]
     pub show_duplicates_only: bool,
+    /// 按标签筛选，为空时不筛选
+    #[serde(default)]
+    pub filter_tags: Vec<String>,
+    /// 多个标签的匹配方式，默认需全部命中
+    #[serde(default)]
+    pub tag_match_mode: TagMatchMode,
+}
+
+/// 多标签筛选的匹配方式
+#[derive(Debug, Clone, Copy, Default, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum TagMatchMode {
+    /// 资产需包含所有指定标签 (AND)
+    #[default]
+    All,
+    /// 资产包含任一指定标签即可 (OR)
+    Any,
 }
 
 #[derive(Debug, Clone, Serialize)]
@@ -240,6 +465,8 @@ pub struct AssetStats {
     pub type_counts: HashMap<AssetType, u64>,
     pub source_module_counts: HashMap<String, u64>,
     pub origin_counts: HashMap<AssetOriginType, u64>,
+    /// 按创建时间（UTC 年-月，如 "2025-10"）聚合的资产数量
+    pub monthly_counts: BTreeMap<String, u64>,
 }
 
 /// 资产的来源类型
@@ -300,18 +527,55 @@ pub struct AssetMetadata {
     pub height: Option<u32>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub duration: Option<f64>,
+    /// 音视频主流的编解码器名称（来自 ffprobe）
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub codec: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub audio_waveform: Option<Vec<u8>>,
+    /// 文件 BLAKE3 哈希，用于去重
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub sha256: Option<String>,
-    /// 入库前原始文件的 SHA-256。用于记录转换类资产（如 DOC -> DOCX）的源文件哈希，
+    pub blake3: Option<String>,
+    /// 入库前原始文件的 BLAKE3。用于记录转换类资产（如 DOC -> DOCX）的源文件哈希，
     /// 让后续导入可以在转换前完成去重。
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub original_sha256: Option<String>,
+    pub original_blake3: Option<String>,
     /// 衍生数据映射表，key 为类型 (e.g., "transcription", "ocr")
     #[serde(skip_serializing_if = "Option::is_none")]
     pub derived: Option<HashMap<String, DerivedDataInfo>>,
+    /// 图片 EXIF 信息
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub exif: Option<ImageExif>,
+}
+
+/// 从图片 EXIF 中提取的信息
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct ImageExif {
+    /// 方向标签 (1-8)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub orientation: Option<u16>,
+    /// 拍摄时间 (RFC 3339, UTC)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub taken_at: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub camera_make: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub camera_model: Option<String>,
+    /// 纬度，南纬为负
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub gps_latitude: Option<f64>,
+    /// 经度，西经为负
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub gps_longitude: Option<f64>,
+}
+
+impl ImageExif {
+    /// 方向标签为 5-8 时图片需要旋转 90°/270°，显示宽高与像素宽高互换
+    fn swaps_dimensions(&self) -> bool {
+        matches!(self.orientation, Some(5..=8))
+    }
 }
+
 /// 资产对象
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -328,6 +592,9 @@ pub struct Asset {
     pub created_at: String,
     pub source_module: String, // Note: This might become redundant, but keep for now for backwards compat
     pub origins: Vec<AssetOrigin>,
+    /// 用户标签
+    #[serde(default)]
+    pub tags: Vec<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub metadata: Option<AssetMetadata>,
 }
@@ -366,12 +633,115 @@ pub struct AssetImportOptions {
     /// 来源模块 ID
     #[serde(skip_serializing_if = "Option::is_none")]
     pub source_module: Option<String>,
+    /// 缩略图输出格式，默认 JPEG
+    #[serde(default)]
+    pub thumbnail_format: ThumbnailFormat,
+    /// 缩略图质量 (1-100)，仅对 JPEG 生效
+    ///
+    /// image 库自带的 WebP 编码器只提供无损模式，WebP 缩略图同时指定质量时
+    /// 由 [`AssetImportOptions::validate`] 拒绝导入
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub thumbnail_quality: Option<u8>,
+    /// 缩略图边长（像素），默认 400
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub thumbnail_size: Option<u32>,
 }
 
 fn default_true() -> bool {
     true
 }
 
+impl AssetImportOptions {
+    /// 校验选项组合，在导入开始前调用
+    fn validate(&self) -> Result<(), String> {
+        if self.thumbnail_format == ThumbnailFormat::Webp && self.thumbnail_quality.is_some() {
+            return Err(
+                "WebP 缩略图使用无损编码，不支持 thumbnailQuality，请移除该选项或改用 JPEG".into(),
+            );
+        }
+        Ok(())
+    }
+
+    fn thumbnail_options(&self) -> ThumbnailOptions {
+        ThumbnailOptions {
+            format: self.thumbnail_format,
+            quality: self
+                .thumbnail_quality
+                .unwrap_or(DEFAULT_THUMBNAIL_QUALITY)
+                .clamp(1, 100),
+            size: self
+                .thumbnail_size
+                .unwrap_or(DEFAULT_THUMBNAIL_SIZE)
+                .clamp(MIN_THUMBNAIL_SIZE, MAX_THUMBNAIL_SIZE),
+        }
+    }
+}
+
+/// 缩略图输出格式
+#[derive(Debug, Clone, Copy, Default, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum ThumbnailFormat {
+    /// 不支持透明度，Alpha 通道会被丢弃
+    #[default]
+    Jpeg,
+    /// 保留透明度
+    Webp,
+}
+
+impl ThumbnailFormat {
+    fn extension(self) -> &'static str {
+        match self {
+            ThumbnailFormat::Jpeg => "jpg",
+            ThumbnailFormat::Webp => "webp",
+        }
+    }
+}
+
+/// 缩略图可能使用的扩展名，查找时按此顺序探测
+const THUMBNAIL_EXTENSIONS: [&str; 2] = ["webp", "jpg"];
+const DEFAULT_THUMBNAIL_SIZE: u32 = 400;
+const MIN_THUMBNAIL_SIZE: u32 = 32;
+const MAX_THUMBNAIL_SIZE: u32 = 2048;
+const DEFAULT_THUMBNAIL_QUALITY: u8 = 75;
+
+/// 缩略图生成参数
+#[derive(Debug, Clone, Copy)]
+struct ThumbnailOptions {
+    format: ThumbnailFormat,
+    quality: u8,
+    size: u32,
+}
+
+/// 查找资产已存在的缩略图，返回相对路径
+fn find_thumbnail_relative(base_dir: &Path, asset_id: &str) -> Option<String> {
+    THUMBNAIL_EXTENSIONS
+        .iter()
+        .map(|ext| format!(".thumbnails/{}.{}", asset_id, ext))
+        .find(|relative| base_dir.join(relative).exists())
+}
+
+/// 删除资产的所有格式的缩略图
+///
+/// `permanent` 为 false 时移入回收站，为 true 时直接删除并返回释放的字节数
+fn remove_thumbnails(base_dir: &Path, asset_id: &str, permanent: bool) -> u64 {
+    let mut freed_bytes = 0;
+    for ext in THUMBNAIL_EXTENSIONS {
+        let thumbnail_path = base_dir.join(format!(".thumbnails/{}.{}", asset_id, ext));
+        if !thumbnail_path.exists() {
+            continue;
+        }
+        if permanent {
+            let size = fs::metadata(&thumbnail_path).map(|m| m.len()).unwrap_or(0);
+            if fs::remove_file(&thumbnail_path).is_ok() {
+                freed_bytes += size;
+            }
+        } else {
+            let _ = trash::delete(&thumbnail_path);
+        }
+    }
+    freed_bytes
+}
+
 impl Default for AssetImportOptions {
     fn default() -> Self {
         Self {
@@ -380,6 +750,9 @@ impl Default for AssetImportOptions {
             origin: None,
             subfolder: None,
             source_module: None,
+            thumbnail_format: ThumbnailFormat::default(),
+            thumbnail_quality: None,
+            thumbnail_size: None,
         }
     }
 }
@@ -441,15 +814,21 @@ fn determine_asset_type(mime: &str, path: Option<&Path>) -> AssetType {
     }
 }
 
-/// 计算文件的 SHA-256 哈希值
+/// 计算文件的 BLAKE3 哈希值（流式读取，不把整个文件载入内存）
 fn calculate_file_hash(path: &Path) -> Result<String, String> {
-    let bytes = fs::read(path).map_err(|e| format!("读取文件失败: {}", e))?;
+    let file = fs::File::open(path).map_err(|e| format!("读取文件失败: {}", e))?;
 
-    let mut hasher = Sha256::new();
-    hasher.update(&bytes);
-    let result = hasher.finalize();
+    let mut hasher = blake3::Hasher::new();
+    hasher
+        .update_reader(file)
+        .map_err(|e| format!("读取文件失败: {}", e))?;
 
-    Ok(format!("{:x}", result))
+    Ok(hasher.finalize().to_hex().to_string())
+}
+
+/// 计算内存数据的 BLAKE3 哈希值
+fn calculate_bytes_hash(bytes: &[u8]) -> String {
+    blake3::hash(bytes).to_hex().to_string()
 }
 
 fn build_import_origin(
@@ -594,271 +973,342 @@ pub fn get_asset_base_path(app: AppHandle) -> Result<String, String> {
     Ok(assets_dir.to_string_lossy().to_string())
 }
 
-/// 从文件路径导入资产
-#[tauri::command]
-pub async fn import_asset_from_path(
-    app: AppHandle,
-    catalog: tauri::State<'_, AssetCatalog>,
-    original_path: String,
-    options: Option<AssetImportOptions>,
-) -> Result<AssetImportResult, String> {
-    let opts = options.unwrap_or_default();
-    let source_path = PathBuf::from(&original_path);
+/// 导入内容：磁盘上已准备好的文件，或内存中的字节
+enum ImportPayload<'a> {
+    File(&'a Path),
+    Bytes(&'a [u8]),
+}
 
-    if !source_path.exists() {
-        return Err(format!("文件不存在: {}", original_path));
+/// 一次导入的公共参数，由各导入命令准备好后交给 `import_asset_internal`
+struct ImportRequest<'a> {
+    payload: ImportPayload<'a>,
+    /// 用于推断 MIME 和扩展名的路径（字节导入时即原始文件名）
+    name_path: &'a Path,
+    original_name: String,
+    /// 入库文件的哈希，未启用去重时为 None
+    file_hash: Option<String>,
+    /// 发生格式转换时，转换前原始文件的哈希
+    original_blake3: Option<String>,
+    /// 是否需要用 file_hash 再检查一次重复
+    check_duplicate: bool,
+    /// 未显式指定 origin 时使用的默认来源
+    default_origin_type: AssetOriginType,
+    default_source: String,
+    /// 发送导入进度事件所用的原始路径，为 None 时不发送
+    progress_key: Option<&'a str>,
+    converted_name: Option<String>,
+}
+
+/// 命中重复资产：合并来源并通知前端
+fn reuse_duplicate_asset(
+    app: &AppHandle,
+    catalog: &AssetCatalog,
+    base_dir: &Path,
+    existing_asset: Asset,
+    new_origin: AssetOrigin,
+) -> Result<Asset, String> {
+    let existing_asset =
+        add_origin_to_existing_asset(app, catalog, base_dir, existing_asset, new_origin)?;
+
+    if let Err(e) = app.emit("asset-imported", &existing_asset) {
+        log::error!("发出 asset-imported 事件失败 (重复资产): {}", e);
     }
 
-    if !source_path.is_file() {
-        return Err(format!("路径不是文件: {}", original_path));
+    Ok(existing_asset)
+}
+
+/// 导入流程的公共部分：去重、生成路径、写入文件、提取元数据、生成缩略图、更新索引和 Catalog
+///
+/// 各导入命令只负责准备字节或路径，元数据字段的增减都只需在这里修改
+async fn import_asset_internal(
+    app: &AppHandle,
+    catalog: &AssetCatalog,
+    base_dir: &Path,
+    opts: &AssetImportOptions,
+    request: ImportRequest<'_>,
+) -> Result<Asset, String> {
+    let thumbnail_options = opts.thumbnail_options();
+    let emit_progress = |phase: &str, converted_name: Option<String>| {
+        if let Some(key) = request.progress_key {
+            emit_import_progress(app, key, phase, None, converted_name);
+        }
+    };
+
+    if request.check_duplicate {
+        if let Some(hash) = &request.file_hash {
+            if let Some(existing_asset) = catalog.find_duplicate(app, base_dir, hash)? {
+                let new_origin = build_import_origin(
+                    opts,
+                    request.default_origin_type,
+                    request.default_source,
+                    "unknown",
+                );
+                return reuse_duplicate_asset(app, catalog, base_dir, existing_asset, new_origin);
+            }
+        }
     }
 
-    let base_path = get_asset_base_path(app.clone())?;
-    let base_dir = PathBuf::from(&base_path);
+    let source_path = match request.payload {
+        ImportPayload::File(path) => Some(path),
+        ImportPayload::Bytes(_) => None,
+    };
+    let mime_type = mime::guess_mime_type(request.name_path);
+    let asset_type = determine_asset_type(&mime_type, source_path);
 
-    let original_mime_type = mime::guess_mime_type(&source_path);
-    let original_asset_type = determine_asset_type(&original_mime_type, Some(&source_path));
+    // 阶段: copying — 写入资产库
+    emit_progress("copying", request.converted_name.clone());
 
-    // 先对原始文件计算哈希。对于需要转换的文档，这一步可以在启动转换器前命中去重。
-    let pre_conversion_hash = if opts.enable_deduplication {
-        emit_import_progress(
-            &app,
-            &original_path,
-            "hashing",
-            Some("计算原始文件哈希".to_string()),
-            None,
-        );
-        let hash = calculate_file_hash(&source_path)?;
-        let duplicate = {
-            let entries = catalog.entries.read().map_err(|e| e.to_string())?;
-            check_duplicate_in_current_month(&base_dir, &original_asset_type, &hash, &entries)?
-        };
+    let (uuid, relative_path) =
+        generate_asset_path(&asset_type, request.name_path, opts.subfolder.as_ref());
+    let target_path = base_dir.join(&relative_path);
 
-        if let Some(existing_asset) = duplicate {
-            let new_origin = build_import_origin(
-                &opts,
-                AssetOriginType::Local,
-                original_path.clone(),
-                "unknown",
-            );
-            let existing_asset = add_origin_to_existing_asset(
-                &app,
-                &catalog,
-                &base_dir,
-                existing_asset,
-                new_origin,
-            )?;
-
-            if let Err(e) = app.emit("asset-imported", &existing_asset) {
-                log::error!("发出 asset-imported 事件失败 (重复资产): {}", e);
-            }
+    if let Some(parent) = target_path.parent() {
+        fs::create_dir_all(parent).map_err(|e| format!("创建目标目录失败: {}", e))?;
+    }
 
-            return Ok(AssetImportResult {
-                asset: existing_asset,
-                warnings: Vec::new(),
-            });
+    let file_size = match request.payload {
+        ImportPayload::File(path) => {
+            fs::copy(path, &target_path).map_err(|e| format!("复制文件失败: {}", e))?
+        }
+        ImportPayload::Bytes(bytes) => {
+            fs::write(&target_path, bytes).map_err(|e| format!("写入文件失败: {}", e))?;
+            bytes.len() as u64
         }
+    };
 
-        Some(hash)
-    } else {
-        None
+    let mut asset_metadata = AssetMetadata {
+        width: None,
+        height: None,
+        duration: None,
+        codec: None,
+        audio_waveform: None,
+        blake3: request.file_hash.clone(),
+        original_blake3: request.original_blake3,
+        derived: None,
+        exif: None,
     };
 
-    // 阶段: preparing — 准备导入源（可能触发文档格式转换）
-    emit_import_progress(&app, &original_path, "preparing", None, None);
-    let prepared_source =
-        document_converter::prepare_import_source(&app, &source_path, &original_path, &base_dir)
-            .await?;
-    let import_warnings: Vec<AssetImportWarning> = prepared_source
-        .warnings
-        .iter()
-        .map(|w| AssetImportWarning {
-            code: w.code.clone(),
-            title: w.title.clone(),
-            message: w.message.clone(),
-            source_path: w.source_path.clone(),
-        })
-        .collect();
-    let source_path = prepared_source.path.as_path();
+    if matches!(asset_type, AssetType::Image) {
+        fill_image_metadata(&target_path, &mut asset_metadata);
+    }
 
-    // 如果发生了转换，通知前端新文件名
-    let converted_name = if prepared_source.cleanup_dir.is_some() {
-        source_path
-            .file_name()
-            .map(|n| n.to_string_lossy().to_string())
+    // 音视频的时长、分辨率与缩略图都依赖 FFmpeg，不可用时静默跳过
+    let ffmpeg_path = if matches!(asset_type, AssetType::Audio | AssetType::Video) {
+        resolve_available_ffmpeg(app).await
     } else {
         None
     };
+    if let Some(ffmpeg_path) = &ffmpeg_path {
+        fill_media_metadata(ffmpeg_path, &target_path, &asset_type, &mut asset_metadata).await;
+    }
 
-    let metadata = source_path
-        .metadata()
-        .map_err(|e| format!("读取文件元数据失败: {}", e))?;
-    let file_size = metadata.len();
-
-    let mime_type = mime::guess_mime_type(source_path);
-    let asset_type = determine_asset_type(&mime_type, Some(source_path));
+    let thumbnail_path = if opts.generate_thumbnail
+        && (matches!(asset_type, AssetType::Image) || matches!(asset_type, AssetType::Audio))
+    {
+        // 阶段: thumbnailing — 生成缩略图
+        emit_progress("thumbnailing", request.converted_name);
+        generate_thumbnail(
+            &target_path,
+            base_dir,
+            &uuid,
+            &asset_type,
+            &thumbnail_options,
+        )?
+    } else if opts.generate_thumbnail && matches!(asset_type, AssetType::Video) {
+        match &ffmpeg_path {
+            Some(ffmpeg_path) => {
+                emit_progress("thumbnailing", request.converted_name);
+                generate_video_thumbnail(
+                    ffmpeg_path,
+                    &target_path,
+                    base_dir,
+                    &uuid,
+                    thumbnail_options.size,
+                )
+                .await
+            }
+            None => None,
+        }
+    } else {
+        None
+    };
 
-    // 计算入库文件哈希（如果启用去重）并检查转换后的重复项。
-    let file_hash = if opts.enable_deduplication {
-        let hash = if prepared_source.cleanup_dir.is_some() {
-            emit_import_progress(
-                &app,
-                &original_path,
-                "hashing",
-                Some("计算转换后文件哈希".to_string()),
-                converted_name.clone(),
-            );
-            calculate_file_hash(source_path)?
-        } else {
-            pre_conversion_hash
-                .clone()
-                .ok_or_else(|| "原始文件哈希缺失".to_string())?
-        };
-
-        let duplicate = if prepared_source.cleanup_dir.is_some() {
-            // 如果是转换后的文件，再用转换后哈希兜底检查一次旧数据或同内容转换结果。
-            let entries = catalog.entries.read().map_err(|e| e.to_string())?;
-            check_duplicate_in_current_month(&base_dir, &asset_type, &hash, &entries)?
-        } else {
-            None
-        };
-
-        if let Some(existing_asset) = duplicate {
-            let new_origin = build_import_origin(
-                &opts,
-                AssetOriginType::Local,
-                original_path.clone(),
-                "unknown",
-            );
-            let existing_asset = add_origin_to_existing_asset(
-                &app,
-                &catalog,
-                &base_dir,
-                existing_asset,
-                new_origin,
-            )?;
-
-            if let Err(e) = app.emit("asset-imported", &existing_asset) {
-                log::error!("发出 asset-imported 事件失败 (重复资产): {}", e);
-            }
-
-            return Ok(AssetImportResult {
-                asset: existing_asset,
-                warnings: import_warnings,
-            });
-        }
-
-        Some(hash)
-    } else {
-        None
-    };
-
-    // 阶段: copying — 复制文件到资产库
-    emit_import_progress(
-        &app,
-        &original_path,
-        "copying",
-        None,
-        converted_name.clone(),
+    let origin = build_import_origin(
+        opts,
+        request.default_origin_type,
+        request.default_source,
+        "unknown",
     );
-
-    let (uuid, relative_path) =
-        generate_asset_path(&asset_type, source_path, opts.subfolder.as_ref());
-    let target_path = base_dir.join(&relative_path);
-
-    if let Some(parent) = target_path.parent() {
-        fs::create_dir_all(parent).map_err(|e| format!("创建目标目录失败: {}", e))?;
-    }
-
-    fs::copy(source_path, &target_path).map_err(|e| format!("复制文件失败: {}", e))?;
-
-    let original_name = source_path
-        .file_name()
-        .ok_or_else(|| "无法获取文件名".to_string())?
-        .to_string_lossy()
-        .to_string();
-
-    let mut asset_metadata = AssetMetadata {
-        width: None,
-        height: None,
-        duration: None,
-        audio_waveform: None,
-        sha256: file_hash.clone(),
-        original_sha256: if prepared_source.cleanup_dir.is_some() {
-            pre_conversion_hash.clone()
-        } else {
-            None
-        },
-        derived: None,
-    };
-
-    if matches!(asset_type, AssetType::Image) {
-        if let Ok(img) = image::open(&target_path) {
-            asset_metadata.width = Some(img.width());
-            asset_metadata.height = Some(img.height());
-        }
-    }
-
-    let thumbnail_path = if opts.generate_thumbnail
-        && (matches!(asset_type, AssetType::Image) || matches!(asset_type, AssetType::Audio))
-    {
-        // 阶段: thumbnailing — 生成缩略图
-        emit_import_progress(&app, &original_path, "thumbnailing", None, converted_name);
-        generate_thumbnail(&target_path, &base_dir, &uuid, &asset_type)?
-    } else {
-        None
-    };
-
     let source_module = opts
         .source_module
         .clone()
         .unwrap_or_else(|| "unknown".to_string());
 
-    let origin = opts.origin.unwrap_or_else(|| AssetOrigin {
-        origin_type: AssetOriginType::Local,
-        source: original_path.clone(),
-        source_module: source_module.clone(),
-    });
-
     let asset = Asset {
         id: uuid.clone(),
         asset_type: asset_type.clone(),
         mime_type,
-        name: original_name,
+        name: request.original_name,
         path: relative_path.clone(),
         thumbnail_path,
         size: file_size,
-        created_at: Utc::now().to_rfc3339(),
+        created_at: exif_taken_at(&asset_metadata).unwrap_or_else(|| Utc::now().to_rfc3339()),
         source_module,
         origins: vec![origin],
+        tags: Vec::new(),
         metadata: Some(asset_metadata),
     };
 
     // 更新月度索引
-    if let Some(hash) = file_hash {
+    if let Some(hash) = request.file_hash {
         let filename = target_path
             .file_name()
             .ok_or("无法获取文件名")?
             .to_string_lossy()
             .to_string();
 
-        if let Err(e) = update_month_index(&base_dir, &asset_type, &hash, &filename) {
+        if let Err(e) = update_month_index(base_dir, &asset_type, &hash, &filename) {
             log::error!("更新月度索引失败: {}", e);
         }
     }
 
     // 更新内存 Catalog
     let catalog_entry = convert_asset_to_catalog_entry(&asset);
+    catalog.register_hashes(&catalog_entry)?;
     {
         let mut entries = catalog.entries.write().map_err(|e| e.to_string())?;
         entries.insert(catalog_entry.id.clone(), catalog_entry);
     }
-    catalog.mark_dirty(&app);
+    catalog.mark_dirty(app);
 
     if let Err(e) = app.emit("asset-imported", &asset) {
         log::error!("发出 asset-imported 事件失败: {}", e);
     }
 
+    Ok(asset)
+}
+
+/// 从文件路径导入资产
+#[tauri::command]
+pub async fn import_asset_from_path(
+    app: AppHandle,
+    catalog: tauri::State<'_, AssetCatalog>,
+    original_path: String,
+    options: Option<AssetImportOptions>,
+) -> Result<AssetImportResult, String> {
+    let opts = options.unwrap_or_default();
+    opts.validate()?;
+    let source_path = PathBuf::from(&original_path);
+
+    if !source_path.exists() {
+        return Err(format!("文件不存在: {}", original_path));
+    }
+
+    if !source_path.is_file() {
+        return Err(format!("路径不是文件: {}", original_path));
+    }
+
+    let base_path = get_asset_base_path(app.clone())?;
+    let base_dir = PathBuf::from(&base_path);
+
+    // 先对原始文件计算哈希。对于需要转换的文档，这一步可以在启动转换器前命中去重。
+    let pre_conversion_hash = if opts.enable_deduplication {
+        emit_import_progress(
+            &app,
+            &original_path,
+            "hashing",
+            Some("计算原始文件哈希".to_string()),
+            None,
+        );
+        let hash = calculate_file_hash(&source_path)?;
+
+        if let Some(existing_asset) = catalog.find_duplicate(&app, &base_dir, &hash)? {
+            let new_origin = build_import_origin(
+                &opts,
+                AssetOriginType::Local,
+                original_path.clone(),
+                "unknown",
+            );
+            let asset =
+                reuse_duplicate_asset(&app, &catalog, &base_dir, existing_asset, new_origin)?;
+            return Ok(AssetImportResult {
+                asset,
+                warnings: Vec::new(),
+            });
+        }
+
+        Some(hash)
+    } else {
+        None
+    };
+
+    // 阶段: preparing — 准备导入源（可能触发文档格式转换）
+    emit_import_progress(&app, &original_path, "preparing", None, None);
+    let prepared_source =
+        document_converter::prepare_import_source(&app, &source_path, &original_path, &base_dir)
+            .await?;
+    let import_warnings: Vec<AssetImportWarning> = prepared_source
+        .warnings
+        .iter()
+        .map(|w| AssetImportWarning {
+            code: w.code.clone(),
+            title: w.title.clone(),
+            message: w.message.clone(),
+            source_path: w.source_path.clone(),
+        })
+        .collect();
+    let source_path = prepared_source.path.as_path();
+    let converted = prepared_source.cleanup_dir.is_some();
+
+    // 如果发生了转换，通知前端新文件名
+    let converted_name = if converted {
+        source_path
+            .file_name()
+            .map(|n| n.to_string_lossy().to_string())
+    } else {
+        None
+    };
+
+    // 计算入库文件哈希（如果启用去重）。转换后的文件需要重新计算并再检查一次重复。
+    let file_hash = if opts.enable_deduplication && converted {
+        emit_import_progress(
+            &app,
+            &original_path,
+            "hashing",
+            Some("计算转换后文件哈希".to_string()),
+            converted_name.clone(),
+        );
+        Some(calculate_file_hash(source_path)?)
+    } else {
+        pre_conversion_hash.clone()
+    };
+
+    let original_name = source_path
+        .file_name()
+        .ok_or_else(|| "无法获取文件名".to_string())?
+        .to_string_lossy()
+        .to_string();
+
+    let asset = import_asset_internal(
+        &app,
+        &catalog,
+        &base_dir,
+        &opts,
+        ImportRequest {
+            payload: ImportPayload::File(source_path),
+            name_path: source_path,
+            original_name,
+            file_hash,
+            original_blake3: if converted { pre_conversion_hash } else { None },
+            check_duplicate: converted,
+            default_origin_type: AssetOriginType::Local,
+            default_source: original_path.clone(),
+            progress_key: Some(&original_path),
+            converted_name,
+        },
+    )
+    .await?;
+
     Ok(AssetImportResult {
         asset,
         warnings: import_warnings,
@@ -875,147 +1325,315 @@ pub async fn import_asset_from_bytes(
     options: Option<AssetImportOptions>,
 ) -> Result<Asset, String> {
     let opts = options.unwrap_or_default();
-
-    let temp_path = PathBuf::from(&original_name);
-    let mime_type = mime::guess_mime_type(&temp_path);
-    let asset_type = determine_asset_type(&mime_type, None);
-
+    opts.validate()?;
     let base_path = get_asset_base_path(app.clone())?;
     let base_dir = PathBuf::from(&base_path);
+    let name_path = PathBuf::from(&original_name);
 
-    // 计算文件哈希（如果启用去重）并检查是否重复
+    // 计算文件哈希（如果启用去重），重复检查交给公共流程
     let file_hash = if opts.enable_deduplication {
-        let mut hasher = Sha256::new();
-        hasher.update(&bytes);
-        let hash = format!("{:x}", hasher.finalize());
-
-        let duplicate = {
-            let entries = catalog.entries.read().map_err(|e| e.to_string())?;
-            check_duplicate_in_current_month(&base_dir, &asset_type, &hash, &entries)?
-        };
-
-        if let Some(mut existing_asset) = duplicate {
-            let new_origin = opts.origin.unwrap_or_else(|| AssetOrigin {
-                origin_type: AssetOriginType::Clipboard,
-                source: "clipboard".to_string(),
-                source_module: opts.source_module.unwrap_or_else(|| "unknown".to_string()),
-            });
+        Some(calculate_bytes_hash(&bytes))
+    } else {
+        None
+    };
 
-            if !existing_asset
-                .origins
-                .iter()
-                .any(|o| o.source_module == new_origin.source_module)
-            {
-                existing_asset.origins.push(new_origin.clone());
+    import_asset_internal(
+        &app,
+        &catalog,
+        &base_dir,
+        &opts,
+        ImportRequest {
+            payload: ImportPayload::Bytes(&bytes),
+            name_path: &name_path,
+            original_name: original_name.clone(),
+            file_hash,
+            original_blake3: None,
+            check_duplicate: true,
+            default_origin_type: AssetOriginType::Clipboard,
+            default_source: "clipboard".to_string(),
+            progress_key: None,
+            converted_name: None,
+        },
+    )
+    .await
+}
 
-                {
-                    let mut entries = catalog.entries.write().map_err(|e| e.to_string())?;
-                    if let Some(entry) = entries.get_mut(&existing_asset.id) {
-                        entry.origins.push(new_origin);
+/// URL 导入的下载超时
+const URL_IMPORT_TIMEOUT: Duration = Duration::from_secs(120);
+/// URL 导入允许的最大下载体积（512MB）
+const URL_IMPORT_MAX_BYTES: u64 = 512 * 1024 * 1024;
+/// URL 导入最多跟随的重定向次数
+const URL_IMPORT_MAX_REDIRECTS: usize = 10;
+
+/// 从 Content-Disposition 中取出文件名，优先使用 RFC 5987 的 `filename*`
+fn filename_from_content_disposition(header: &str) -> Option<String> {
+    let mut plain = None;
+    for part in header.split(';').map(str::trim) {
+        let Some((key, value)) = part.split_once('=') else {
+            continue;
+        };
+        let value = value.trim().trim_matches('"');
+        match key.trim().to_ascii_lowercase().as_str() {
+            "filename*" => {
+                // 形如 UTF-8''%E5%9B%BE.png
+                let encoded = value.rsplit("''").next().unwrap_or(value);
+                if let Ok(decoded) = urlencoding::decode(encoded) {
+                    if !decoded.is_empty() {
+                        return Some(decoded.into_owned());
                     }
                 }
-                catalog.mark_dirty(&app);
             }
-
-            if let Err(e) = app.emit("asset-imported", &existing_asset) {
-                log::error!("发出 asset-imported 事件失败 (重复资产): {}", e);
-            }
-
-            return Ok(existing_asset);
+            "filename" if !value.is_empty() => plain = Some(value.to_string()),
+            _ => {}
         }
+    }
+    plain
+}
 
-        Some(hash)
-    } else {
-        None
+/// 常见 Content-Type 对应的扩展名，仅在文件名和魔数都无法给出扩展名时使用
+fn extension_from_content_type(content_type: &str) -> Option<&'static str> {
+    let essence = content_type
+        .split(';')
+        .next()
+        .unwrap_or_default()
+        .trim()
+        .to_ascii_lowercase();
+    let ext = match essence.as_str() {
+        "image/jpeg" => "jpg",
+        "image/png" => "png",
+        "image/gif" => "gif",
+        "image/webp" => "webp",
+        "image/svg+xml" => "svg",
+        "image/avif" => "avif",
+        "audio/mpeg" => "mp3",
+        "audio/wav" | "audio/x-wav" => "wav",
+        "audio/ogg" => "ogg",
+        "video/mp4" => "mp4",
+        "video/webm" => "webm",
+        "application/pdf" => "pdf",
+        "application/json" => "json",
+        "text/plain" => "txt",
+        "text/markdown" => "md",
+        "text/html" => "html",
+        _ => return None,
     };
+    Some(ext)
+}
 
-    let (uuid, relative_path) =
-        generate_asset_path(&asset_type, &temp_path, opts.subfolder.as_ref());
-    let target_path = base_dir.join(&relative_path);
+/// 推断下载文件的文件名
+///
+/// 依次尝试 Content-Disposition、最终 URL 的最后一段路径；缺少扩展名时
+/// 先用魔数检测，再回退到 Content-Type
+fn infer_download_file_name(
+    final_url: &url::Url,
+    content_disposition: Option<&str>,
+    content_type: Option<&str>,
+    bytes: &[u8],
+) -> String {
+    let name = content_disposition
+        .and_then(filename_from_content_disposition)
+        .or_else(|| {
+            final_url
+                .path_segments()
+                .and_then(|mut segments| segments.next_back())
+                .filter(|segment| !segment.is_empty())
+                .map(|segment| {
+                    urlencoding::decode(segment)
+                        .map(|s| s.into_owned())
+                        .unwrap_or_else(|_| segment.to_string())
+                })
+        })
+        // 去掉路径分隔符，避免文件名被当成子路径
+        .map(|name| name.replace(['/', '\\'], "_"))
+        .unwrap_or_else(|| "download".to_string());
 
-    if let Some(parent) = target_path.parent() {
-        fs::create_dir_all(parent).map_err(|e| format!("创建目标目录失败: {}", e))?;
+    if Path::new(&name).extension().is_some() {
+        return name;
     }
 
-    fs::write(&target_path, &bytes).map_err(|e| format!("写入文件失败: {}", e))?;
+    let ext = infer::get(bytes)
+        .map(|kind| kind.extension())
+        .or_else(|| content_type.and_then(extension_from_content_type));
+    match ext {
+        Some(ext) => format!("{}.{}", name, ext),
+        None => name,
+    }
+}
 
-    let file_size = bytes.len() as u64;
+/// 下载 URL 内容，限制超时与体积
+async fn download_url_bytes(url: &url::Url) -> Result<(Vec<u8>, String), String> {
+    use tauri_plugin_http::reqwest;
+
+    let client = reqwest::Client::builder()
+        .timeout(URL_IMPORT_TIMEOUT)
+        .redirect(reqwest::redirect::Policy::limited(URL_IMPORT_MAX_REDIRECTS))
+        .build()
+        .map_err(|e| format!("创建 HTTP 客户端失败: {}", e))?;
+
+    let mut response = client.get(url.clone()).send().await.map_err(|e| {
+        if e.is_timeout() {
+            format!("下载超时（{} 秒）: {}", URL_IMPORT_TIMEOUT.as_secs(), url)
+        } else if e.is_redirect() {
+            format!("重定向次数过多: {}", url)
+        } else {
+            format!("下载失败: {}", e)
+        }
+    })?;
 
-    let mut asset_metadata = AssetMetadata {
-        width: None,
-        height: None,
-        duration: None,
-        audio_waveform: None,
-        sha256: file_hash.clone(),
-        original_sha256: None,
-        derived: None,
-    };
+    let status = response.status();
+    if !status.is_success() {
+        return Err(format!("下载失败: 服务器返回 HTTP {}", status));
+    }
 
-    if matches!(asset_type, AssetType::Image) {
-        if let Ok(img) = image::load_from_memory(&bytes) {
-            asset_metadata.width = Some(img.width());
-            asset_metadata.height = Some(img.height());
+    if let Some(length) = response.content_length() {
+        if length > URL_IMPORT_MAX_BYTES {
+            return Err(format!(
+                "文件过大（{} MB），超过 URL 导入上限 {} MB",
+                length / 1024 / 1024,
+                URL_IMPORT_MAX_BYTES / 1024 / 1024
+            ));
         }
     }
 
-    let thumbnail_path = if opts.generate_thumbnail
-        && (matches!(asset_type, AssetType::Image) || matches!(asset_type, AssetType::Audio))
+    let header_str = |name: reqwest::header::HeaderName| {
+        response
+            .headers()
+            .get(name)
+            .and_then(|v| v.to_str().ok())
+            .map(str::to_string)
+    };
+    let content_disposition = header_str(reqwest::header::CONTENT_DISPOSITION);
+    let content_type = header_str(reqwest::header::CONTENT_TYPE);
+    let final_url = response.url().clone();
+
+    // 服务器可能不给或谎报 Content-Length，边读边检查体积
+    let mut bytes = Vec::new();
+    while let Some(chunk) = response
+        .chunk()
+        .await
+        .map_err(|e| format!("读取下载内容失败: {}", e))?
     {
-        generate_thumbnail(&target_path, &base_dir, &uuid, &asset_type)?
+        if bytes.len() as u64 + chunk.len() as u64 > URL_IMPORT_MAX_BYTES {
+            return Err(format!(
+                "文件超过 URL 导入上限 {} MB",
+                URL_IMPORT_MAX_BYTES / 1024 / 1024
+            ));
+        }
+        bytes.extend_from_slice(&chunk);
+    }
+
+    let file_name = infer_download_file_name(
+        &final_url,
+        content_disposition.as_deref(),
+        content_type.as_deref(),
+        &bytes,
+    );
+    Ok((bytes, file_name))
+}
+
+/// 从网络 URL 下载并导入资产，来源自动记为 Network + 原始 URL
+#[tauri::command]
+pub async fn import_asset_from_url(
+    app: AppHandle,
+    catalog: tauri::State<'_, AssetCatalog>,
+    url: String,
+    options: Option<AssetImportOptions>,
+) -> Result<Asset, String> {
+    let opts = options.unwrap_or_default();
+    opts.validate()?;
+    let parsed = url::Url::parse(url.trim()).map_err(|e| format!("无效的 URL: {}", e))?;
+    if !matches!(parsed.scheme(), "http" | "https") {
+        return Err(format!("仅支持 http/https 链接: {}", url));
+    }
+
+    emit_import_progress(&app, &url, "downloading", None, None);
+    let (bytes, original_name) = download_url_bytes(&parsed).await?;
+
+    let base_path = get_asset_base_path(app.clone())?;
+    let base_dir = PathBuf::from(&base_path);
+    let name_path = PathBuf::from(&original_name);
+
+    let file_hash = if opts.enable_deduplication {
+        Some(calculate_bytes_hash(&bytes))
     } else {
         None
     };
 
-    let source_module = opts
-        .source_module
-        .clone()
-        .unwrap_or_else(|| "unknown".to_string());
+    import_asset_internal(
+        &app,
+        &catalog,
+        &base_dir,
+        &opts,
+        ImportRequest {
+            payload: ImportPayload::Bytes(&bytes),
+            name_path: &name_path,
+            original_name: original_name.clone(),
+            file_hash,
+            original_blake3: None,
+            check_duplicate: true,
+            default_origin_type: AssetOriginType::Network,
+            default_source: url.clone(),
+            progress_key: Some(&url),
+            converted_name: None,
+        },
+    )
+    .await
+}
 
-    let origin = opts.origin.unwrap_or_else(|| AssetOrigin {
-        origin_type: AssetOriginType::Clipboard,
-        source: "clipboard".to_string(),
-        source_module: source_module.clone(),
-    });
+#[cfg(test)]
+mod import_options_tests {
+    use super::{AssetImportOptions, ThumbnailFormat};
 
-    let asset = Asset {
-        id: uuid.clone(),
-        asset_type: asset_type.clone(),
-        mime_type,
-        name: original_name,
-        path: relative_path.clone(),
-        thumbnail_path,
-        size: file_size,
-        created_at: Utc::now().to_rfc3339(),
-        source_module,
-        origins: vec![origin],
-        metadata: Some(asset_metadata),
-    };
+    #[test]
+    fn rejects_quality_for_webp_thumbnails() {
+        let mut opts = AssetImportOptions {
+            thumbnail_quality: Some(80),
+            ..Default::default()
+        };
+        assert!(opts.validate().is_ok());
 
-    if let Some(hash) = file_hash {
-        let filename = target_path
-            .file_name()
-            .ok_or("无法获取文件名")?
-            .to_string_lossy()
-            .to_string();
+        opts.thumbnail_format = ThumbnailFormat::Webp;
+        assert!(opts.validate().is_err());
 
-        if let Err(e) = update_month_index(&base_dir, &asset_type, &hash, &filename) {
-            log::error!("更新月度索引失败: {}", e);
-        }
+        opts.thumbnail_quality = None;
+        assert!(opts.validate().is_ok());
     }
+}
 
-    // 更新内存 Catalog
-    let catalog_entry = convert_asset_to_catalog_entry(&asset);
-    {
-        let mut entries = catalog.entries.write().map_err(|e| e.to_string())?;
-        entries.insert(catalog_entry.id.clone(), catalog_entry);
-    }
-    catalog.mark_dirty(&app);
+#[cfg(test)]
+mod url_import_tests {
+    use super::{filename_from_content_disposition, infer_download_file_name};
 
-    if let Err(e) = app.emit("asset-imported", &asset) {
-        log::error!("发出 asset-imported 事件失败: {}", e);
+    #[test]
+    fn prefers_encoded_content_disposition_name() {
+        assert_eq!(
+            filename_from_content_disposition(
+                "attachment; filename=\"a.png\"; filename*=UTF-8''%E5%9B%BE.png"
+            )
+            .as_deref(),
+            Some("图.png")
+        );
+        assert_eq!(
+            filename_from_content_disposition("inline; filename=\"report.pdf\"").as_deref(),
+            Some("report.pdf")
+        );
     }
 
-    Ok(asset)
+    #[test]
+    fn infers_extension_from_content_when_url_has_none() {
+        let url = url::Url::parse("https://example.com/images/avatar?size=64").unwrap();
+        let png = [0x89, b'P', b'N', b'G', 0x0D, 0x0A, 0x1A, 0x0A, 0, 0, 0, 0];
+        assert_eq!(
+            infer_download_file_name(&url, None, None, &png),
+            "avatar.png"
+        );
+
+        let url = url::Url::parse("https://example.com/").unwrap();
+        assert_eq!(
+            infer_download_file_name(&url, None, Some("text/plain; charset=utf-8"), b"hi"),
+            "download.txt"
+        );
+    }
 }
 
 /// 知识库备份导入使用的资产结果。`created` 用于在库提交失败时只清理本次新增资产。
@@ -1063,7 +1681,7 @@ pub(crate) fn get_asset_for_backup(
     Ok(Some((convert_entry_to_asset(entry, &base_dir), file_path)))
 }
 
-/// 从知识库备份恢复原始资产。这里按整个 Catalog 的 SHA-256 去重，而不是仅检查当月索引。
+/// 从知识库备份恢复原始资产。这里按整个 Catalog 的 BLAKE3 去重，而不是仅检查当月索引。
 pub(crate) fn import_backup_asset(
     app: &AppHandle,
     catalog: &AssetCatalog,
@@ -1071,9 +1689,7 @@ pub(crate) fn import_backup_asset(
     original_name: &str,
     original_mime_type: &str,
 ) -> Result<BackupAssetImport, String> {
-    let mut hasher = Sha256::new();
-    hasher.update(bytes);
-    let file_hash = format!("{:x}", hasher.finalize());
+    let file_hash = calculate_bytes_hash(bytes);
     let base_dir = PathBuf::from(get_asset_base_path(app.clone())?);
     let origin = AssetOrigin {
         origin_type: AssetOriginType::Local,
@@ -1081,14 +1697,8 @@ pub(crate) fn import_backup_asset(
         source_module: "knowledge-base".to_string(),
     };
 
-    let existing = {
-        let entries = catalog.entries.read().map_err(|e| e.to_string())?;
-        entries
-            .values()
-            .find(|entry| entry.sha256.as_deref() == Some(file_hash.as_str()))
-            .cloned()
-    };
-    if let Some(existing) = existing {
+    // 与普通导入共用哈希查找表，文件已被手动删除的条目会在这里被清理
+    if let Some(existing) = catalog.find_duplicate(app, &base_dir, &file_hash)? {
         let asset = {
             let mut entries = catalog.entries.write().map_err(|e| e.to_string())?;
             let entry = entries
@@ -1122,10 +1732,12 @@ pub(crate) fn import_backup_asset(
         width: None,
         height: None,
         duration: None,
+        codec: None,
         audio_waveform: None,
-        sha256: Some(file_hash.clone()),
-        original_sha256: None,
+        blake3: Some(file_hash.clone()),
+        original_blake3: None,
         derived: None,
+        exif: None,
     };
     if matches!(asset_type, AssetType::Image) {
         if let Ok(image) = image::load_from_memory(bytes) {
@@ -1145,6 +1757,7 @@ pub(crate) fn import_backup_asset(
         created_at: Utc::now().to_rfc3339(),
         source_module: "knowledge-base".to_string(),
         origins: vec![origin],
+        tags: Vec::new(),
         metadata: Some(metadata),
     };
 
@@ -1153,9 +1766,11 @@ pub(crate) fn import_backup_asset(
             log::warn!("[KB_BACKUP] 更新资产月度索引失败: {}", error);
         }
     }
+    let catalog_entry = convert_asset_to_catalog_entry(&asset);
+    catalog.register_hashes(&catalog_entry)?;
     {
         let mut entries = catalog.entries.write().map_err(|e| e.to_string())?;
-        entries.insert(uuid, convert_asset_to_catalog_entry(&asset));
+        entries.insert(uuid, catalog_entry);
     }
     catalog.mark_dirty(app);
     let _ = app.emit("asset-imported", &asset);
@@ -1187,6 +1802,149 @@ mod backup_asset_tests {
     }
 }
 
+#[cfg(test)]
+mod duplicate_group_tests {
+    use super::{group_duplicate_entries, CatalogEntry};
+
+    fn entry(id: &str, hash: &str, size: u64) -> CatalogEntry {
+        serde_json::from_value(serde_json::json!({
+            "id": id,
+            "path": format!("images/2024-01/{}.png", id),
+            "name": format!("{}.png", id),
+            "size": size,
+            "mimeType": "image/png",
+            "assetType": "image",
+            "createdAt": format!("2024-01-01T00:00:0{}Z", id.len()),
+            "blake3": hash,
+        }))
+        .expect("catalog entry should deserialize")
+    }
+
+    #[test]
+    fn groups_catalog_entries_by_hash_with_asset_ids() {
+        let entries = [
+            entry("a", "h1", 10),
+            entry("bb", "h1", 10),
+            entry("c", "h2", 5),
+        ];
+
+        let result = group_duplicate_entries(entries.iter());
+        assert_eq!(result.total_groups, 1);
+        assert_eq!(result.total_files, 2);
+        assert_eq!(result.wasted_space, 10);
+        assert_eq!(result.duplicates[0].asset_ids, vec!["a", "bb"]);
+    }
+}
+
+#[cfg(test)]
+mod tag_tests {
+    use super::normalize_tags;
+
+    #[test]
+    fn normalize_tags_trims_and_deduplicates() {
+        let tags = vec![
+            " cat ".to_string(),
+            "dog".to_string(),
+            "".to_string(),
+            "cat".to_string(),
+        ];
+        assert_eq!(normalize_tags(tags), vec!["cat", "dog"]);
+    }
+}
+
+#[cfg(test)]
+mod exif_tests {
+    use super::parse_exif_datetime;
+
+    #[test]
+    fn converts_exif_datetime_with_offset_to_utc() {
+        assert_eq!(
+            parse_exif_datetime("2024:05:01 08:30:00", Some("+08:00")),
+            Some("2024-05-01T00:30:00+00:00".to_string())
+        );
+        assert_eq!(parse_exif_datetime("0000:00:00 00:00:00", None), None);
+    }
+}
+
+#[cfg(test)]
+mod catalog_parse_tests {
+    use super::parse_catalog_lines;
+
+    #[test]
+    fn skips_truncated_lines_and_reports_line_numbers() {
+        let content = concat!(
+            r#"{"id":"a","path":"images/2024-01/a.png","name":"a.png","size":1,"mimeType":"image/png","assetType":"image","createdAt":"2024-01-01T00:00:00Z"}"#,
+            "\n\n",
+            r#"{"id":"b","path":"images/2024-01/b.png","na"#,
+            "\n",
+        );
+
+        let result = parse_catalog_lines(content.as_bytes()).unwrap();
+        assert_eq!(result.total_lines, 2);
+        assert!(result.entries.contains_key("a"));
+        assert_eq!(result.corrupted_lines, vec![3]);
+    }
+}
+
+#[cfg(test)]
+mod catalog_dedup_tests {
+    use super::{build_hash_lookup, find_duplicate_in_catalog, CatalogEntry};
+    use std::collections::HashMap;
+    use std::fs;
+    use tempfile::tempdir;
+
+    fn entry(id: &str, path: &str, created_at: &str, hash: &str) -> CatalogEntry {
+        serde_json::from_value(serde_json::json!({
+            "id": id,
+            "path": path,
+            "name": format!("{}.png", id),
+            "size": 4,
+            "mimeType": "image/png",
+            "assetType": "image",
+            "createdAt": created_at,
+            "blake3": hash,
+        }))
+        .expect("catalog entry should deserialize")
+    }
+
+    #[test]
+    fn finds_duplicates_from_previous_months_and_reports_missing_files() {
+        let dir = tempdir().expect("temporary asset dir should be created");
+        let old_month = dir.path().join("images/2024-01");
+        fs::create_dir_all(&old_month).unwrap();
+        fs::write(old_month.join("kept.png"), b"data").unwrap();
+
+        let mut entries = HashMap::new();
+        entries.insert(
+            "missing".to_string(),
+            entry(
+                "missing",
+                "images/2023-12/missing.png",
+                "2023-12-01T00:00:00Z",
+                "h1",
+            ),
+        );
+        entries.insert(
+            "kept".to_string(),
+            entry(
+                "kept",
+                "images/2024-01/kept.png",
+                "2024-01-01T00:00:00Z",
+                "h1",
+            ),
+        );
+
+        let lookup = build_hash_lookup(entries.values());
+        let (found, stale) = find_duplicate_in_catalog(dir.path(), "h1", &entries, &lookup);
+        assert_eq!(found.map(|asset| asset.id), Some("kept".to_string()));
+        assert_eq!(stale, vec!["missing".to_string()]);
+
+        let (found, stale) = find_duplicate_in_catalog(dir.path(), "other", &entries, &lookup);
+        assert!(found.is_none());
+        assert!(stale.is_empty());
+    }
+}
+
 /// 清理由失败的知识库导入新建的资产；已存在并复用的资产不会传入这里。
 pub(crate) fn remove_backup_asset(
     app: &AppHandle,
@@ -1199,14 +1957,25 @@ pub(crate) fn remove_backup_asset(
         entries.remove(asset_id)
     };
     if let Some(entry) = entry {
-        delete_asset_files(&base_dir, &entry.id, &entry.path)?;
+        delete_asset_files(&base_dir, &entry.id, &entry.path, false)?;
         catalog.mark_dirty(app);
     }
     Ok(())
 }
 
+/// 月度哈希索引文件名（BLAKE3）
+const MONTH_INDEX_FILE: &str = ".index.blake3.json";
+/// 旧版 SHA-256 月度索引文件名，仅在迁移时识别并清理
+const LEGACY_MONTH_INDEX_FILE: &str = ".index.json";
+
+/// 判断是否为月度索引文件（包括旧版），扫描资产时需要跳过
+fn is_month_index_file(path: &Path) -> bool {
+    path.file_name()
+        .is_some_and(|name| name == MONTH_INDEX_FILE || name == LEGACY_MONTH_INDEX_FILE)
+}
+
 /// 月度哈希索引结构
-/// 存储格式: { "sha256_hash": ["uuid1.ext", "uuid2.ext"] }
+/// 存储格式: { "blake3_hash": ["uuid1.ext", "uuid2.ext"] }
 #[derive(Debug, Clone, Serialize, Deserialize)]
 struct MonthHashIndex {
     #[serde(flatten)]
@@ -1242,10 +2011,6 @@ impl MonthHashIndex {
         fs::write(index_path, content).map_err(|e| format!("写入索引文件失败: {}", e))
     }
 
-    fn get(&self, hash: &str) -> Option<&Vec<String>> {
-        self.entries.get(hash)
-    }
-
     fn insert(&mut self, hash: String, filename: String) {
         let filenames = self.entries.entry(hash).or_default();
         if !filenames.iter().any(|existing| existing == &filename) {
@@ -1255,19 +2020,73 @@ impl MonthHashIndex {
 }
 
 fn catalog_entry_matches_hash(entry: &CatalogEntry, file_hash: &str) -> bool {
-    entry.sha256.as_deref() == Some(file_hash)
-        || entry.original_sha256.as_deref() == Some(file_hash)
+    entry.blake3.as_deref() == Some(file_hash)
+        || entry.original_blake3.as_deref() == Some(file_hash)
+}
+
+/// 将条目的当前哈希与原始文件哈希登记到查找表
+fn add_to_hash_lookup(lookup: &mut HashMap<String, Vec<String>>, entry: &CatalogEntry) {
+    for hash in [entry.blake3.as_ref(), entry.original_blake3.as_ref()]
+        .into_iter()
+        .flatten()
+    {
+        let ids = lookup.entry(hash.clone()).or_default();
+        if !ids.contains(&entry.id) {
+            ids.push(entry.id.clone());
+        }
+    }
+}
+
+/// 由 Catalog 条目构建哈希查找表
+fn build_hash_lookup<'a>(
+    entries: impl IntoIterator<Item = &'a CatalogEntry>,
+) -> HashMap<String, Vec<String>> {
+    let mut lookup = HashMap::new();
+    for entry in entries {
+        add_to_hash_lookup(&mut lookup, entry);
+    }
+    lookup
 }
 
-/// 检查当月目录中是否已存在相同哈希的文件（使用索引优化）
-/// 返回已存在的 Asset 信息（如果找到）
-/// `catalog_entries` 为内存中的 Catalog 条目映射，避免文件 IO
-fn check_duplicate_in_current_month(
+/// 通过哈希查找表在 Catalog 条目中查找与哈希匹配且文件仍然存在的资产
+///
+/// 返回命中的资产（优先最早入库的条目），以及哈希匹配但文件已丢失的条目 ID。
+fn find_duplicate_in_catalog(
     base_dir: &Path,
-    asset_type: &AssetType,
     file_hash: &str,
     catalog_entries: &HashMap<String, CatalogEntry>,
-) -> Result<Option<Asset>, String> {
+    hash_lookup: &HashMap<String, Vec<String>>,
+) -> (Option<Asset>, Vec<String>) {
+    let mut candidates: Vec<&CatalogEntry> = hash_lookup
+        .get(file_hash)
+        .into_iter()
+        .flatten()
+        .filter_map(|id| catalog_entries.get(id))
+        .filter(|entry| catalog_entry_matches_hash(entry, file_hash))
+        .collect();
+    candidates.sort_by(|a, b| a.created_at.cmp(&b.created_at).then(a.id.cmp(&b.id)));
+
+    let mut stale_ids = Vec::new();
+    for entry in candidates {
+        if base_dir.join(&entry.path).is_file() {
+            return (
+                Some(convert_entry_to_asset(entry.clone(), base_dir)),
+                stale_ids,
+            );
+        }
+        stale_ids.push(entry.id.clone());
+    }
+
+    (None, stale_ids)
+}
+
+/// 更新月度哈希索引
+fn update_month_index(
+    base_dir: &Path,
+    asset_type: &AssetType,
+    file_hash: &str,
+    filename: &str,
+) -> Result<(), String> {
     let now = Utc::now();
     let year_month = now.format("%Y-%m").to_string();
 
@@ -1280,132 +2099,378 @@ fn check_duplicate_in_current_month(
     };
 
     let month_dir = base_dir.join(type_dir).join(&year_month);
+    let index_path = month_dir.join(MONTH_INDEX_FILE);
 
-    if !month_dir.exists() {
-        return Ok(None);
-    }
+    // 读取现有索引
+    let mut index = MonthHashIndex::from_file(&index_path)?;
 
-    let index_path = month_dir.join(".index.json");
-    let index = MonthHashIndex::from_file(&index_path)?;
+    // 添加新条目
+    index.insert(file_hash.to_string(), filename.to_string());
 
-    if let Some(filenames) = index.get(file_hash) {
-        if let Some(filename) = filenames.first() {
-            let file_path = month_dir.join(filename);
+    // 保存索引
+    index.save(&index_path)?;
 
-            if !file_path.exists() {
-                return Ok(None);
-            }
+    Ok(())
+}
 
-            let asset_id = file_path
-                .file_stem()
-                .and_then(|s| s.to_str())
-                .ok_or("无法解析文件名")?;
+/// 生成缩略图
+fn generate_thumbnail(
+    source_path: &Path,
+    base_dir: &Path,
+    uuid: &str,
+    asset_type: &AssetType,
+    options: &ThumbnailOptions,
+) -> Result<Option<String>, String> {
+    match asset_type {
+        AssetType::Image => generate_image_thumbnail(source_path, base_dir, uuid, options),
+        AssetType::Audio => generate_audio_thumbnail(source_path, base_dir, uuid, options),
+        _ => Ok(None),
+    }
+}
 
-            // 从内存 Catalog 中查找
-            if let Some(entry) = catalog_entries.get(asset_id) {
-                return Ok(Some(convert_entry_to_asset(entry.clone(), base_dir)));
-            }
+/// 生成图片缩略图
+fn generate_image_thumbnail(
+    source_path: &Path,
+    base_dir: &Path,
+    uuid: &str,
+    options: &ThumbnailOptions,
+) -> Result<Option<String>, String> {
+    let img = match image::open(source_path) {
+        Ok(img) => img,
+        Err(_) => return Ok(None),
+    };
 
-            log::warn!(
-                "在内存 Catalog 中未找到资产 {}，使用文件系统信息重建",
-                asset_id
-            );
-            return Ok(Some(build_asset_from_path(&file_path, base_dir)?));
-        }
+    if img.width() <= options.size && img.height() <= options.size {
+        return Ok(None);
     }
 
-    let relative_prefix = format!("{}/{}/", type_dir, year_month);
-    if let Some(entry) = catalog_entries.values().find(|entry| {
-        entry.asset_type == *asset_type
-            && entry.path.starts_with(&relative_prefix)
-            && catalog_entry_matches_hash(entry, file_hash)
-    }) {
-        return Ok(Some(convert_entry_to_asset(entry.clone(), base_dir)));
+    // 按 EXIF 方向摆正后再裁剪，避免竖拍照片的缩略图横躺
+    let orientation = read_image_exif(source_path).and_then(|exif| exif.orientation);
+    let img = apply_exif_orientation(img, orientation);
+
+    save_thumbnail_image(&img, base_dir, uuid, options)
+        .map(Some)
+        .map_err(|e| format!("保存缩略图失败: {}", e))
+}
+
+/// 读取图片宽高与 EXIF 信息，宽高为按 EXIF 方向摆正后的显示尺寸
+fn fill_image_metadata(path: &Path, metadata: &mut AssetMetadata) {
+    let exif = read_image_exif(path);
+
+    if let Ok((width, height)) = image::image_dimensions(path) {
+        let swaps = exif.as_ref().is_some_and(|exif| exif.swaps_dimensions());
+        let (width, height) = if swaps {
+            (height, width)
+        } else {
+            (width, height)
+        };
+        metadata.width = Some(width);
+        metadata.height = Some(height);
     }
 
-    Ok(None)
+    metadata.exif = exif;
 }
 
-/// 更新月度哈希索引
-fn update_month_index(
-    base_dir: &Path,
-    asset_type: &AssetType,
-    file_hash: &str,
-    filename: &str,
-) -> Result<(), String> {
-    let now = Utc::now();
-    let year_month = now.format("%Y-%m").to_string();
+/// EXIF 中记录的拍摄时间，用于优先作为资产的创建时间
+fn exif_taken_at(metadata: &AssetMetadata) -> Option<String> {
+    metadata.exif.as_ref()?.taken_at.clone()
+}
 
-    let type_dir = match asset_type {
-        AssetType::Image => "images",
-        AssetType::Audio => "audio",
-        AssetType::Video => "videos",
-        AssetType::Document => "documents",
-        AssetType::Other => "other",
+/// 使用 kamadak-exif 解析图片的 EXIF，没有 EXIF 或解析失败时返回 None
+fn read_image_exif(path: &Path) -> Option<ImageExif> {
+    let file = fs::File::open(path).ok()?;
+    let exif = exif::Reader::new()
+        .read_from_container(&mut std::io::BufReader::new(file))
+        .ok()?;
+
+    let orientation = exif
+        .get_field(exif::Tag::Orientation, exif::In::PRIMARY)
+        .and_then(|field| field.value.get_uint(0))
+        .and_then(|value| u16::try_from(value).ok())
+        .filter(|value| (1..=8).contains(value));
+
+    let taken_at = exif_ascii(&exif, exif::Tag::DateTimeOriginal)
+        .or_else(|| exif_ascii(&exif, exif::Tag::DateTime))
+        .and_then(|value| {
+            let offset = exif_ascii(&exif, exif::Tag::OffsetTimeOriginal);
+            parse_exif_datetime(&value, offset.as_deref())
+        });
+
+    let info = ImageExif {
+        orientation,
+        taken_at,
+        camera_make: exif_ascii(&exif, exif::Tag::Make),
+        camera_model: exif_ascii(&exif, exif::Tag::Model),
+        gps_latitude: exif_gps_coordinate(
+            &exif,
+            exif::Tag::GPSLatitude,
+            exif::Tag::GPSLatitudeRef,
+            "S",
+        ),
+        gps_longitude: exif_gps_coordinate(
+            &exif,
+            exif::Tag::GPSLongitude,
+            exif::Tag::GPSLongitudeRef,
+            "W",
+        ),
     };
 
-    let month_dir = base_dir.join(type_dir).join(&year_month);
-    let index_path = month_dir.join(".index.json");
+    if info == ImageExif::default() {
+        None
+    } else {
+        Some(info)
+    }
+}
+
+fn exif_ascii(exif: &exif::Exif, tag: exif::Tag) -> Option<String> {
+    match &exif.get_field(tag, exif::In::PRIMARY)?.value {
+        exif::Value::Ascii(parts) => parts
+            .first()
+            .map(|part| {
+                String::from_utf8_lossy(part)
+                    .trim_end_matches('\0')
+                    .trim()
+                    .to_string()
+            })
+            .filter(|value| !value.is_empty()),
+        _ => None,
+    }
+}
+
+/// 将 EXIF 中以度、分、秒表示的 GPS 坐标转换为十进制度数
+fn exif_gps_coordinate(
+    exif: &exif::Exif,
+    tag: exif::Tag,
+    ref_tag: exif::Tag,
+    negative_ref: &str,
+) -> Option<f64> {
+    let exif::Value::Rational(parts) = &exif.get_field(tag, exif::In::PRIMARY)?.value else {
+        return None;
+    };
+    if parts.len() < 3 {
+        return None;
+    }
 
-    // 读取现有索引
-    let mut index = MonthHashIndex::from_file(&index_path)?;
+    let value = parts[0].to_f64() + parts[1].to_f64() / 60.0 + parts[2].to_f64() / 3600.0;
+    if !value.is_finite() {
+        return None;
+    }
 
-    // 添加新条目
-    index.insert(file_hash.to_string(), filename.to_string());
+    let is_negative = exif_ascii(exif, ref_tag)
+        .is_some_and(|reference| reference.eq_ignore_ascii_case(negative_ref));
+    Some(if is_negative { -value } else { value })
+}
 
-    // 保存索引
-    index.save(&index_path)?;
+/// 解析 EXIF 时间（"YYYY:MM:DD HH:MM:SS"）为 UTC 的 RFC 3339 字符串
+///
+/// EXIF 时间本身不带时区，有 OffsetTimeOriginal 时使用它，否则按本机时区解释
+fn parse_exif_datetime(value: &str, offset: Option<&str>) -> Option<String> {
+    let naive = chrono::NaiveDateTime::parse_from_str(value.trim(), "%Y:%m:%d %H:%M:%S").ok()?;
+
+    let datetime = match offset.and_then(|o| o.trim().parse::<chrono::FixedOffset>().ok()) {
+        Some(offset) => naive
+            .and_local_timezone(offset)
+            .single()?
+            .with_timezone(&Utc),
+        None => naive
+            .and_local_timezone(chrono::Local)
+            .earliest()?
+            .with_timezone(&Utc),
+    };
 
-    Ok(())
+    Some(datetime.to_rfc3339())
 }
 
-/// 生成缩略图
-fn generate_thumbnail(
-    source_path: &Path,
-    base_dir: &Path,
-    uuid: &str,
-    asset_type: &AssetType,
-) -> Result<Option<String>, String> {
-    match asset_type {
-        AssetType::Image => generate_image_thumbnail(source_path, base_dir, uuid),
-        AssetType::Audio => generate_audio_thumbnail(source_path, base_dir, uuid),
-        _ => Ok(None),
+/// 按 EXIF 方向标签旋转/翻转图片
+fn apply_exif_orientation(
+    img: image::DynamicImage,
+    orientation: Option<u16>,
+) -> image::DynamicImage {
+    match orientation {
+        Some(2) => img.fliph(),
+        Some(3) => img.rotate180(),
+        Some(4) => img.flipv(),
+        Some(5) => img.rotate90().fliph(),
+        Some(6) => img.rotate90(),
+        Some(7) => img.rotate270().fliph(),
+        Some(8) => img.rotate270(),
+        _ => img,
     }
 }
 
-/// 生成图片缩略图
-fn generate_image_thumbnail(
-    source_path: &Path,
+/// 将图片缩放裁剪为正方形缩略图并按配置的格式写入 .thumbnails 目录，返回相对路径
+fn save_thumbnail_image(
+    img: &image::DynamicImage,
     base_dir: &Path,
     uuid: &str,
-) -> Result<Option<String>, String> {
-    let img = match image::open(source_path) {
-        Ok(img) => img,
-        Err(_) => return Ok(None),
-    };
+    options: &ThumbnailOptions,
+) -> Result<String, String> {
+    // 使用 resize_to_fill 替代 thumbnail
+    // 它会自动按比例缩放并从中间裁剪，确保输出正好是 size x size
+    let thumbnail = img.resize_to_fill(
+        options.size,
+        options.size,
+        image::imageops::FilterType::Lanczos3,
+    );
 
-    if img.width() <= 400 && img.height() <= 400 {
-        return Ok(None);
+    let thumbnail_relative = format!(".thumbnails/{}.{}", uuid, options.format.extension());
+    let thumbnail_path = base_dir.join(&thumbnail_relative);
+
+    if let Some(parent) = thumbnail_path.parent() {
+        fs::create_dir_all(parent).map_err(|e| format!("创建缩略图目录失败: {}", e))?;
     }
 
-    // 使用 resize_to_fill 替代 thumbnail
-    // 它会自动按比例缩放并从中间裁剪，确保输出正好是 400x400
-    let thumbnail = img.resize_to_fill(400, 400, image::imageops::FilterType::Lanczos3);
+    let file = fs::File::create(&thumbnail_path).map_err(|e| e.to_string())?;
+    let mut writer = std::io::BufWriter::new(file);
+
+    match options.format {
+        ThumbnailFormat::Jpeg => {
+            // 将图像转换为 RGB8 格式以去除 Alpha 通道，因为 JPEG 不支持透明度
+            let encoder =
+                image::codecs::jpeg::JpegEncoder::new_with_quality(&mut writer, options.quality);
+            thumbnail
+                .to_rgb8()
+                .write_with_encoder(encoder)
+                .map_err(|e| e.to_string())?;
+        }
+        ThumbnailFormat::Webp => {
+            // image 的 WebP 编码器仅支持无损模式，指定质量的请求已在 AssetImportOptions::validate 中拒绝
+            let encoder = image::codecs::webp::WebPEncoder::new_lossless(&mut writer);
+            thumbnail
+                .to_rgba8()
+                .write_with_encoder(encoder)
+                .map_err(|e| e.to_string())?;
+        }
+    }
+
+    Ok(thumbnail_relative)
+}
+
+/// 读取设置中配置的 FFmpeg 路径，并确认其可用
+///
+/// 未配置时使用 PATH 中的 `ffmpeg`；不可用时返回 None，调用方应静默跳过音视频相关处理
+async fn resolve_available_ffmpeg(app: &AppHandle) -> Option<String> {
+    let config_path = crate::get_app_data_dir(app.config())
+        .join("app-settings")
+        .join("settings.json");
+
+    let ffmpeg_path = fs::read_to_string(&config_path)
+        .ok()
+        .and_then(|content| serde_json::from_str::<serde_json::Value>(&content).ok())
+        .and_then(|config| {
+            config
+                .get("environment")?
+                .get("ffmpegPath")?
+                .as_str()
+                .map(|s| s.trim().to_string())
+        })
+        .filter(|s| !s.is_empty())
+        .unwrap_or_else(|| "ffmpeg".to_string());
+
+    if super::ffmpeg_processor::is_ffmpeg_available(&ffmpeg_path).await {
+        Some(ffmpeg_path)
+    } else {
+        None
+    }
+}
 
+/// 生成视频缩略图
+///
+/// 使用 FFmpeg 抽取第 1 秒的一帧，缩放裁剪为 size x size 的 JPEG。任何失败都返回 None
+async fn generate_video_thumbnail(
+    ffmpeg_path: &str,
+    source_path: &Path,
+    base_dir: &Path,
+    uuid: &str,
+    size: u32,
+) -> Option<String> {
     let thumbnail_relative = format!(".thumbnails/{}.jpg", uuid);
     let thumbnail_path = base_dir.join(&thumbnail_relative);
 
     if let Some(parent) = thumbnail_path.parent() {
-        fs::create_dir_all(parent).map_err(|e| format!("创建缩略图目录失败: {}", e))?;
+        fs::create_dir_all(parent).ok()?;
+    }
+
+    // 与图片缩略图保持一致：按比例放大到覆盖 size x size 后居中裁剪
+    let filter = format!(
+        "scale={0}:{0}:force_original_aspect_ratio=increase,crop={0}:{0}",
+        size
+    );
+    let mut command = tokio::process::Command::new(ffmpeg_path);
+    command
+        .args(["-y", "-v", "error", "-ss", "1", "-i"])
+        .arg(source_path)
+        .args(["-frames:v", "1", "-vf", &filter, "-q:v", "3"])
+        .arg(&thumbnail_path);
+    crate::utils::hide_child_process_window(&mut command);
+
+    match command.output().await {
+        Ok(output) if output.status.success() && thumbnail_path.exists() => {
+            Some(thumbnail_relative)
+        }
+        Ok(output) => {
+            log::debug!(
+                "[AssetThumbnail] FFmpeg 抽帧失败 {}: {}",
+                source_path.display(),
+                String::from_utf8_lossy(&output.stderr).trim()
+            );
+            None
+        }
+        Err(e) => {
+            log::debug!("[AssetThumbnail] 无法启动 FFmpeg: {}", e);
+            None
+        }
     }
+}
 
-    // 将图像转换为 RGB8 格式以去除 Alpha 通道，因为 JPEG 不支持透明度
-    thumbnail
-        .to_rgb8()
-        .save_with_format(&thumbnail_path, image::ImageFormat::Jpeg)
-        .map_err(|e| format!("保存缩略图失败: {}", e))?;
+/// 使用 ffprobe 解析音视频的时长、分辨率与编解码器并写入元数据
+///
+/// ffprobe 与 FFmpeg 位于同一目录；解析失败时保持元数据不变
+async fn fill_media_metadata(
+    ffmpeg_path: &str,
+    source_path: &Path,
+    asset_type: &AssetType,
+    metadata: &mut AssetMetadata,
+) {
+    let probe = match super::ffmpeg_processor::get_full_media_info(
+        ffmpeg_path.to_string(),
+        source_path.to_string_lossy().to_string(),
+        None,
+    )
+    .await
+    {
+        Ok(probe) => probe,
+        Err(e) => {
+            log::debug!(
+                "[AssetMetadata] ffprobe 解析失败 {}: {}",
+                source_path.display(),
+                e
+            );
+            return;
+        }
+    };
 
-    Ok(Some(thumbnail_relative))
+    let stream_type = match asset_type {
+        AssetType::Video => "video",
+        AssetType::Audio => "audio",
+        _ => return,
+    };
+    let stream = probe.streams.iter().find(|s| s.codec_type == stream_type);
+
+    metadata.duration = probe
+        .format
+        .duration
+        .parse::<f64>()
+        .ok()
+        .or_else(|| stream.and_then(|s| s.duration.as_deref()?.parse::<f64>().ok()));
+
+    if let Some(stream) = stream {
+        metadata.codec = stream.codec_name.clone();
+        if matches!(asset_type, AssetType::Video) {
+            metadata.width = stream.width;
+            metadata.height = stream.height;
+        }
+    }
 }
 
 /// 保存前端生成的缩略图
@@ -1429,6 +2494,9 @@ pub async fn save_asset_thumbnail(
         .decode(base64_str)
         .map_err(|e| format!("Base64 解码失败: {}", e))?;
 
+    // 前端提交的缩略图统一存为 .jpg，先清理其他格式的旧缩略图以免被优先匹配
+    remove_thumbnails(&base_dir, &asset_id, false);
+
     let thumbnail_relative = format!(".thumbnails/{}.jpg", asset_id);
     let thumbnail_path = base_dir.join(&thumbnail_relative);
 
@@ -1451,6 +2519,7 @@ fn generate_audio_thumbnail(
     source_path: &Path,
     base_dir: &Path,
     uuid: &str,
+    options: &ThumbnailOptions,
 ) -> Result<Option<String>, String> {
     // 使用 Probe 读取文件，这样更健壮
     let tagged_file = match Probe::open(source_path) {
@@ -1488,65 +2557,47 @@ fn generate_audio_thumbnail(
 
     // 生成缩略图
     // 如果图片已经很小了，没必要生成缩略图
-    if img.width() <= 400 && img.height() <= 400 {
+    if img.width() <= options.size && img.height() <= options.size {
         return Ok(None);
     }
 
-    // 使用 resize_to_fill 确保音频封面也是规整的正方形且不模糊
-    let thumbnail = img.resize_to_fill(400, 400, image::imageops::FilterType::Lanczos3);
-
-    let thumbnail_relative = format!(".thumbnails/{}.jpg", uuid);
-    let thumbnail_path = base_dir.join(&thumbnail_relative);
-
-    if let Some(parent) = thumbnail_path.parent() {
-        fs::create_dir_all(parent).map_err(|e| format!("创建缩略图目录失败: {}", e))?;
-    }
-
-    // 保存
-    thumbnail
-        .to_rgb8()
-        .save_with_format(&thumbnail_path, image::ImageFormat::Jpeg)
-        .map_err(|e| format!("保存音频封面失败: {}", e))?;
-
-    Ok(Some(thumbnail_relative))
+    // 与图片缩略图一致，确保音频封面也是规整的正方形且不模糊
+    save_thumbnail_image(&img, base_dir, uuid, options)
+        .map(Some)
+        .map_err(|e| format!("保存音频封面失败: {}", e))
 }
 
-/// 根据相对路径读取资产的二进制数据
-/// 注意：该方法返回 Vec<u8>，在 Tauri IPC 传输中会被序列化为 JSON 数字数组，对于大文件非常低效。
-#[tauri::command]
-pub fn get_asset_binary(app: AppHandle, relative_path: String) -> Result<Vec<u8>, String> {
+/// 将资产相对路径解析为存在的绝对路径，拒绝越出资产根目录的路径
+fn resolve_existing_asset_file(app: AppHandle, relative_path: &str) -> AppResult<PathBuf> {
     let base_path = get_asset_base_path(app)?;
     let base_dir = PathBuf::from(&base_path);
-    let file_path = base_dir.join(&relative_path);
+    let file_path = base_dir.join(relative_path);
 
     if !file_path.starts_with(&base_dir) {
-        return Err("非法的文件路径".to_string());
+        return Err(AppError::PermissionDenied("非法的文件路径".to_string()));
     }
 
     if !file_path.exists() {
-        return Err(format!("文件不存在: {}", relative_path));
+        return Err(AppError::NotFound(format!("文件不存在: {}", relative_path)));
     }
 
-    fs::read(&file_path).map_err(|e| format!("读取文件失败: {}", e))
+    Ok(file_path)
+}
+
+/// 根据相对路径读取资产的二进制数据
+/// 注意：该方法返回 Vec<u8>，在 Tauri IPC 传输中会被序列化为 JSON 数字数组，对于大文件非常低效。
+#[tauri::command]
+pub fn get_asset_binary(app: AppHandle, relative_path: String) -> AppResult<Vec<u8>> {
+    let file_path = resolve_existing_asset_file(app, &relative_path)?;
+    fs::read(&file_path).context("读取文件失败")
 }
 
 /// 根据相对路径读取资产并转换为 Base64 字符串
 /// 该方法在 Rust 侧完成 Base64 转换，避免了前端处理大文件时的阻塞。
 #[tauri::command]
-pub fn get_asset_base64(app: AppHandle, relative_path: String) -> Result<String, String> {
-    let base_path = get_asset_base_path(app)?;
-    let base_dir = PathBuf::from(&base_path);
-    let file_path = base_dir.join(&relative_path);
-
-    if !file_path.starts_with(&base_dir) {
-        return Err("非法的文件路径".to_string());
-    }
-
-    if !file_path.exists() {
-        return Err(format!("文件不存在: {}", relative_path));
-    }
-
-    let bytes = fs::read(&file_path).map_err(|e| format!("读取文件失败: {}", e))?;
+pub fn get_asset_base64(app: AppHandle, relative_path: String) -> AppResult<String> {
+    let file_path = resolve_existing_asset_file(app, &relative_path)?;
+    let bytes = fs::read(&file_path).context("读取文件失败")?;
     Ok(general_purpose::STANDARD.encode(bytes))
 }
 
@@ -1584,7 +2635,7 @@ pub fn list_all_assets(app: AppHandle) -> Result<Vec<Asset>, String> {
                 }
 
                 // 跳过索引文件
-                if file_path.file_name() == Some(".index.json".as_ref()) {
+                if is_month_index_file(&file_path) {
                     continue;
                 }
 
@@ -1620,19 +2671,23 @@ fn build_asset_from_path(file_path: &Path, base_dir: &Path) -> Result<Asset, Str
         width: None,
         height: None,
         duration: None,
+        codec: None,
         audio_waveform: None,
-        sha256: file_hash,
-        original_sha256: None,
+        blake3: file_hash,
+        original_blake3: None,
         derived: None,
+        exif: None,
     };
 
+    let thumbnail_path = find_thumbnail_relative(base_dir, &uuid);
+
     Ok(Asset {
         id: uuid,
         asset_type,
         mime_type,
         name: file_path.file_name().unwrap().to_string_lossy().to_string(),
         path: relative_path.to_string_lossy().replace("\\", "/"),
-        thumbnail_path: None, // TODO: 检查缩略图是否存在
+        thumbnail_path,
         size: metadata.len(),
         created_at: metadata
             .created()
@@ -1640,32 +2695,63 @@ fn build_asset_from_path(file_path: &Path, base_dir: &Path) -> Result<Asset, Str
             .unwrap_or_default(),
         source_module: "unknown".to_string(), // 从文件系统重建时无法确定来源模块
         origins: vec![],                      // 无法从文件系统确定来源
+        tags: Vec::new(),
         metadata: Some(asset_metadata),
     })
 }
-/// 根据相对路径读取文本文件内容
-///
-/// 该函数会自动检测文件是否为文本文件，并尝试以 UTF-8 编码读取
-#[tauri::command]
-pub fn read_text_file(app: AppHandle, relative_path: String) -> Result<String, String> {
-    let base_path = get_asset_base_path(app)?;
-    let base_dir = PathBuf::from(&base_path);
-    let file_path = base_dir.join(&relative_path);
 
-    if !file_path.starts_with(&base_dir) {
-        return Err("非法的文件路径".to_string());
+#[cfg(test)]
+mod thumbnail_lookup_tests {
+    use super::{build_asset_from_path, find_thumbnail_relative};
+    use std::fs;
+
+    #[test]
+    fn detects_thumbnail_in_any_supported_format() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::create_dir_all(dir.path().join(".thumbnails")).unwrap();
+        assert_eq!(find_thumbnail_relative(dir.path(), "a"), None);
+
+        fs::write(dir.path().join(".thumbnails/a.jpg"), b"jpg").unwrap();
+        assert_eq!(
+            find_thumbnail_relative(dir.path(), "a").as_deref(),
+            Some(".thumbnails/a.jpg")
+        );
+
+        // 同时存在时优先使用新格式
+        fs::write(dir.path().join(".thumbnails/a.webp"), b"webp").unwrap();
+        assert_eq!(
+            find_thumbnail_relative(dir.path(), "a").as_deref(),
+            Some(".thumbnails/a.webp")
+        );
     }
 
-    if !file_path.exists() {
-        return Err(format!("文件不存在: {}", relative_path));
+    #[test]
+    fn rebuilt_asset_carries_existing_thumbnail() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::create_dir_all(dir.path().join("images/2025-01")).unwrap();
+        fs::create_dir_all(dir.path().join(".thumbnails")).unwrap();
+        let file_path = dir.path().join("images/2025-01/abc.png");
+        fs::write(&file_path, b"not really a png").unwrap();
+        fs::write(dir.path().join(".thumbnails/abc.jpg"), b"jpg").unwrap();
+
+        let asset = build_asset_from_path(&file_path, dir.path()).unwrap();
+        assert_eq!(asset.thumbnail_path.as_deref(), Some(".thumbnails/abc.jpg"));
     }
+}
+
+/// 根据相对路径读取文本文件内容
+///
+/// 该函数会自动检测文件是否为文本文件，并尝试以 UTF-8 编码读取
+#[tauri::command]
+pub fn read_text_file(app: AppHandle, relative_path: String) -> AppResult<String> {
+    let file_path = resolve_existing_asset_file(app, &relative_path)?;
 
     // 检测是否为文本文件
     if !is_text_file(&file_path) {
-        return Err("文件不是有效的文本文件".to_string());
+        return Err(AppError::InvalidInput("文件不是有效的文本文件".to_string()));
     }
 
-    fs::read_to_string(&file_path).map_err(|e| format!("读取文本文件失败: {}", e))
+    fs::read_to_string(&file_path).context("读取文本文件失败")
 }
 
 /// 资产导入进度事件 payload
@@ -1716,15 +2802,23 @@ pub struct RebuildIndexProgress {
 ///
 /// 该函数会扫描所有资产目录，计算每个文件的哈希值，并更新对应月份的索引文件
 /// 同时通过事件系统向前端报告进度
+///
+/// 这也是 SHA-256 -> BLAKE3 的一次性迁移入口：重算后会回写 Catalog 中的哈希并删除旧版索引文件
 #[tauri::command]
-pub async fn rebuild_hash_index(app: AppHandle) -> Result<String, String> {
+pub async fn rebuild_hash_index(
+    app: AppHandle,
+    catalog: tauri::State<'_, AssetCatalog>,
+) -> Result<String, String> {
     let base_path = get_asset_base_path(app.clone())?;
     let base_dir = PathBuf::from(&base_path);
+    // 资产 ID -> 重算后的哈希，处理完成后统一回写 Catalog
+    let mut recomputed_hashes: HashMap<String, String> = HashMap::new();
 
     let asset_type_dirs = ["images", "audio", "videos", "documents", "other"];
 
-    // 第一步：统计总文件数
-    let mut total_files = 0usize;
+    // 第一步：收集所有月份目录及其中的待哈希文件
+    let mut month_dirs: Vec<(&str, PathBuf)> = Vec::new();
+    let mut pending_files: Vec<(usize, PathBuf)> = Vec::new();
     for type_dir_str in &asset_type_dirs {
         let type_dir = base_dir.join(type_dir_str);
         if !type_dir.exists() || !type_dir.is_dir() {
@@ -1740,94 +2834,103 @@ pub async fn rebuild_hash_index(app: AppHandle) -> Result<String, String> {
                 continue;
             }
 
+            let month_idx = month_dirs.len();
             for file_entry in fs::read_dir(&year_month_path)
                 .map_err(|e| e.to_string())?
                 .flatten()
             {
                 let file_path = file_entry.path();
-                if file_path.is_file() && file_path.file_name() != Some(".index.json".as_ref()) {
-                    total_files += 1;
+                // 跳过索引文件本身和非文件项
+                if file_path.is_file() && !is_month_index_file(&file_path) {
+                    pending_files.push((month_idx, file_path));
                 }
             }
+            month_dirs.push((type_dir_str, year_month_path));
         }
     }
 
-    // 第二步：处理文件并报告进度
-    let mut current_processed = 0usize;
-    let mut errors = Vec::new();
-
-    for type_dir_str in &asset_type_dirs {
-        let type_dir = base_dir.join(type_dir_str);
-        if !type_dir.exists() || !type_dir.is_dir() {
-            continue;
-        }
-
-        // 遍历年-月目录
-        for year_month_entry in fs::read_dir(&type_dir)
-            .map_err(|e| e.to_string())?
-            .flatten()
-        {
-            let year_month_path = year_month_entry.path();
-            if !year_month_path.is_dir() {
-                continue;
-            }
-
-            // 为当前月份目录创建全新的索引
-            let index_path = year_month_path.join(".index.json");
-            let mut new_index = MonthHashIndex::new();
-
-            // 遍历该月份目录下的所有文件
-            for file_entry in fs::read_dir(&year_month_path)
-                .map_err(|e| e.to_string())?
-                .flatten()
-            {
-                let file_path = file_entry.path();
-
-                // 跳过索引文件本身和非文件项
-                if !file_path.is_file() || file_path.file_name() == Some(".index.json".as_ref()) {
-                    continue;
-                }
-
-                current_processed += 1;
-
-                // 发送进度事件
+    // 第二步：并行计算哈希，按完成数节流发送进度事件（最多约 200 次）
+    let total_files = pending_files.len();
+    let emit_step = (total_files / 200).max(1);
+    let processed = AtomicUsize::new(0);
+    let hash_results: Vec<Result<String, String>> = pending_files
+        .par_iter()
+        .map(|(month_idx, file_path)| {
+            let result = calculate_file_hash(file_path);
+            let done = processed.fetch_add(1, Ordering::Relaxed) + 1;
+            if done % emit_step == 0 || done == total_files {
                 let progress = RebuildIndexProgress {
-                    current: current_processed,
+                    current: done,
                     total: total_files,
-                    current_type: type_dir_str.to_string(),
+                    current_type: month_dirs[*month_idx].0.to_string(),
                 };
                 let _ = app.emit("rebuild-index-progress", &progress);
+            }
+            result
+        })
+        .collect();
 
-                // 计算文件哈希
-                match calculate_file_hash(&file_path) {
-                    Ok(hash) => {
-                        if let Some(filename) = file_path.file_name() {
-                            let filename_str = filename.to_string_lossy().to_string();
-                            new_index.insert(hash, filename_str);
-                        }
-                    }
-                    Err(e) => {
-                        errors.push(format!(
-                            "计算文件 {} 的哈希失败: {}",
-                            file_path.display(),
-                            e
-                        ));
-                    }
+    // 第三步：按原始遍历顺序填充各月份索引，保证与串行版本的内容一致
+    let mut month_indexes: Vec<MonthHashIndex> =
+        month_dirs.iter().map(|_| MonthHashIndex::new()).collect();
+    let mut errors = Vec::new();
+
+    for ((month_idx, file_path), result) in pending_files.iter().zip(hash_results) {
+        match result {
+            Ok(hash) => {
+                if let Some(asset_id) = file_path.file_stem().and_then(|s| s.to_str()) {
+                    recomputed_hashes.insert(asset_id.to_string(), hash.clone());
+                }
+                if let Some(filename) = file_path.file_name() {
+                    let filename_str = filename.to_string_lossy().to_string();
+                    month_indexes[*month_idx].insert(hash, filename_str);
                 }
             }
+            Err(e) => {
+                errors.push(format!(
+                    "计算文件 {} 的哈希失败: {}",
+                    file_path.display(),
+                    e
+                ));
+            }
+        }
+    }
+
+    for ((_, year_month_path), new_index) in month_dirs.iter().zip(month_indexes) {
+        // 保存全新的索引
+        let index_path = year_month_path.join(MONTH_INDEX_FILE);
+        if let Err(e) = new_index.save(&index_path) {
+            errors.push(format!("保存索引文件 {} 失败: {}", index_path.display(), e));
+        }
+
+        // 清理旧版 SHA-256 索引，避免新旧哈希混用
+        let legacy_index_path = year_month_path.join(LEGACY_MONTH_INDEX_FILE);
+        if legacy_index_path.exists() {
+            if let Err(e) = fs::remove_file(&legacy_index_path) {
+                errors.push(format!(
+                    "删除旧版索引文件 {} 失败: {}",
+                    legacy_index_path.display(),
+                    e
+                ));
+            }
+        }
+    }
 
-            // 保存全新的索引
-            if let Err(e) = new_index.save(&index_path) {
-                errors.push(format!("保存索引文件 {} 失败: {}", index_path.display(), e));
+    // 将重算的哈希回写到 Catalog，并清除旧版 SHA-256 标记（转换类资产的原始文件哈希无法重算，直接丢弃）
+    {
+        let mut entries = catalog.entries.write().map_err(|e| e.to_string())?;
+        for entry in entries.values_mut() {
+            if let Some(hash) = recomputed_hashes.get(&entry.id) {
+                entry.blake3 = Some(hash.clone());
             }
+            entry.sha256 = None;
         }
     }
+    catalog.rebuild_hash_lookup()?;
+    catalog.mark_dirty(&app);
 
     // 构建结果消息
-    let mut result = format!(
-        "索引重建完成！共处理和索引了 {} 个文件。",
-        current_processed
-    );
+    let mut result = format!("索引重建完成！共处理和索引了 {} 个文件。", total_files);
 
     if !errors.is_empty() {
         result.push_str(&format!("\n\n遇到 {} 个错误：\n", errors.len()));
@@ -1850,6 +2953,8 @@ pub struct DuplicateFileInfo {
     pub hash: String,
     /// 重复文件的相对路径列表
     pub files: Vec<String>,
+    /// 重复文件对应的资产 ID，与 files 一一对应
+    pub asset_ids: Vec<String>,
     /// 文件总大小（单个文件大小）
     pub size: u64,
     /// 重复文件数量
@@ -1870,17 +2975,24 @@ pub struct DuplicateFilesResult {
     pub wasted_space: u64,
 }
 
-/// 删除资产文件（移动到回收站）
+/// 删除资产文件（默认移动到回收站，`permanent` 为 true 时彻底删除）
 ///
 /// 该函数会：
-/// 1. 将文件移动到系统回收站
+/// 1. 将文件移动到系统回收站，或直接删除
 /// 2. 删除缩略图（如果存在）
 /// 3. 从哈希索引中移除该文件
 /// 4. 从内存 Catalog 中移除
 ///
 /// 注意：此函数也被其他命令内部调用，因此物理删除逻辑独立于 Catalog 操作。
 /// 调用方负责在调用前从内存 Catalog 中移除条目。
-fn delete_asset_files(base_dir: &Path, asset_id: &str, relative_path: &str) -> Result<(), String> {
+///
+/// 返回实际释放的磁盘字节数，移入回收站时不释放空间，返回 0。
+fn delete_asset_files(
+    base_dir: &Path,
+    asset_id: &str,
+    relative_path: &str,
+    permanent: bool,
+) -> Result<u64, String> {
     let file_path = base_dir.join(relative_path);
 
     if !file_path.starts_with(base_dir) {
@@ -1891,18 +3003,22 @@ fn delete_asset_files(base_dir: &Path, asset_id: &str, relative_path: &str) -> R
         return Err(format!("文件不存在: {}", relative_path));
     }
 
-    // 1. 移动文件到回收站
-    trash::delete(&file_path).map_err(|e| format!("删除文件失败: {}", e))?;
+    // 1. 移动文件到回收站，或彻底删除
+    let mut freed_bytes = 0;
+    if permanent {
+        let size = fs::metadata(&file_path).map(|m| m.len()).unwrap_or(0);
+        fs::remove_file(&file_path).map_err(|e| format!("彻底删除文件失败: {}", e))?;
+        freed_bytes += size;
+    } else {
+        trash::delete(&file_path).map_err(|e| format!("删除文件失败: {}", e))?;
+    }
 
     // 2. 删除缩略图（如果存在）
-    let thumbnail_path = base_dir.join(format!(".thumbnails/{}.jpg", asset_id));
-    if thumbnail_path.exists() {
-        let _ = trash::delete(&thumbnail_path);
-    }
+    freed_bytes += remove_thumbnails(base_dir, asset_id, permanent);
 
     // 3. 从月度哈希索引中移除该文件
     if let Some(parent_dir) = file_path.parent() {
-        let index_path = parent_dir.join(".index.json");
+        let index_path = parent_dir.join(MONTH_INDEX_FILE);
         if index_path.exists() {
             if let Ok(mut index) = MonthHashIndex::from_file(&index_path) {
                 if let Some(filename) = file_path.file_name() {
@@ -1923,21 +3039,48 @@ fn delete_asset_files(base_dir: &Path, asset_id: &str, relative_path: &str) -> R
         }
     }
 
-    Ok(())
+    Ok(freed_bytes)
+}
+
+/// 单个资产删除结果
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AssetDeleteResult {
+    /// 实际释放的磁盘字节数（移入回收站时为 0）
+    pub freed_bytes: u64,
+}
+
+/// 批量资产删除结果
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BatchAssetDeleteResult {
+    /// 删除失败的资产 ID
+    pub failed_ids: Vec<String>,
+    /// 实际释放的磁盘字节数（移入回收站时为 0）
+    pub freed_bytes: u64,
 }
 
+/// 删除资产文件
+///
+/// `permanent` 为 true 时跳过回收站直接删除，前端需在二次确认后才传入 true
 #[tauri::command]
 pub async fn delete_asset(
     app: AppHandle,
     catalog: tauri::State<'_, AssetCatalog>,
     asset_id: String,
     relative_path: String,
-) -> Result<(), String> {
+    permanent: Option<bool>,
+) -> Result<AssetDeleteResult, String> {
     let base_path = get_asset_base_path(app.clone())?;
     let base_dir = PathBuf::from(&base_path);
 
     // 物理删除文件
-    delete_asset_files(&base_dir, &asset_id, &relative_path)?;
+    let freed_bytes = delete_asset_files(
+        &base_dir,
+        &asset_id,
+        &relative_path,
+        permanent.unwrap_or(false),
+    )?;
 
     // 从内存 Catalog 中移除
     {
@@ -1945,115 +3088,309 @@ pub async fn delete_asset(
         entries.remove(&asset_id);
     }
     catalog.mark_dirty(&app);
+    remove_assets_from_all_collections(&base_dir, std::slice::from_ref(&asset_id));
 
-    Ok(())
+    Ok(AssetDeleteResult { freed_bytes })
 }
 
 /// 查找所有重复的文件
 ///
-/// 直接从索引文件读取哈希值，找出具有相同哈希值的文件组
+/// 直接按内存 Catalog 中记录的 BLAKE3 哈希分组，找出具有相同哈希值的资产组
 #[tauri::command]
-pub async fn find_duplicate_files(app: AppHandle) -> Result<DuplicateFilesResult, String> {
-    let base_path = get_asset_base_path(app)?;
-    let base_dir = PathBuf::from(&base_path);
+pub async fn find_duplicate_files(
+    catalog: tauri::State<'_, AssetCatalog>,
+) -> Result<DuplicateFilesResult, String> {
+    let entries = catalog.entries.read().map_err(|e| e.to_string())?;
+    Ok(group_duplicate_entries(entries.values()))
+}
 
-    let asset_type_dirs = ["images", "audio", "videos", "documents", "other"];
+/// 将 Catalog 条目按哈希分组，统计重复组与可节省的空间
+fn group_duplicate_entries<'a>(
+    entries: impl Iterator<Item = &'a CatalogEntry>,
+) -> DuplicateFilesResult {
+    let mut hash_map: HashMap<&str, Vec<&CatalogEntry>> = HashMap::new();
+    for entry in entries {
+        if let Some(hash) = entry.blake3.as_deref().filter(|hash| !hash.is_empty()) {
+            hash_map.entry(hash).or_default().push(entry);
+        }
+    }
 
-    // 使用 HashMap 存储哈希值到文件路径列表的映射
-    // HashMap<hash, Vec<(relative_path, type_dir, year_month, filename)>>
-    let mut hash_map: HashMap<String, Vec<(String, String, String, String)>> = HashMap::new();
+    // 筛选出重复的文件组（哈希值对应的文件数 > 1）
+    let mut duplicates = Vec::new();
+    let mut total_files = 0;
+    let mut wasted_space = 0u64;
 
-    for type_dir_str in &asset_type_dirs {
-        let type_dir = base_dir.join(type_dir_str);
-        if !type_dir.exists() || !type_dir.is_dir() {
+    for (hash, mut group) in hash_map {
+        if group.len() < 2 {
+            continue;
+        }
+        // 按入库时间排序，保证结果稳定
+        group.sort_by(|a, b| a.created_at.cmp(&b.created_at).then(a.id.cmp(&b.id)));
+
+        let file_size = group[0].size;
+        let count = group.len();
+
+        // 计算浪费的空间（重复文件数 - 1）* 文件大小
+        wasted_space += file_size * (count as u64 - 1);
+        total_files += count;
+
+        duplicates.push(DuplicateFileInfo {
+            hash: hash.to_string(),
+            files: group.iter().map(|entry| entry.path.clone()).collect(),
+            asset_ids: group.iter().map(|entry| entry.id.clone()).collect(),
+            size: file_size,
+            count,
+        });
+    }
+
+    // 按重复文件数量降序排序
+    duplicates.sort_by(|a, b| b.count.cmp(&a.count).then_with(|| a.hash.cmp(&b.hash)));
+
+    DuplicateFilesResult {
+        total_groups: duplicates.len(),
+        total_files,
+        wasted_space,
+        duplicates,
+    }
+}
+
+/// 资产存储目录中需要迁移的子目录
+const ASSET_STORAGE_DIRS: [&str; 7] = [
+    "images",
+    "audio",
+    "videos",
+    "documents",
+    "other",
+    ".thumbnails",
+    ".catalog",
+];
+
+/// 资产存储迁移进度信息
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AssetMigrationProgress {
+    /// 当前阶段: "copying" | "cleaning"
+    pub phase: String,
+    pub current: usize,
+    pub total: usize,
+    pub current_file: String,
+}
+
+/// 资产存储迁移结果
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AssetMigrationResult {
+    /// 迁移的文件数
+    pub migrated_files: usize,
+    /// 迁移的总字节数
+    pub migrated_bytes: u64,
+    /// 迁移完成后未能从旧目录删除的文件数
+    pub cleanup_failures: usize,
+}
+
+/// 收集资产存储目录下所有需要迁移的文件（相对路径）
+fn collect_asset_storage_files(base_dir: &Path) -> Vec<PathBuf> {
+    let mut files = Vec::new();
+    for dir_name in ASSET_STORAGE_DIRS {
+        let dir = base_dir.join(dir_name);
+        if !dir.is_dir() {
             continue;
         }
-
-        // 遍历年-月目录
-        for year_month_entry in fs::read_dir(&type_dir)
-            .map_err(|e| e.to_string())?
+        for entry in walkdir::WalkDir::new(&dir)
+            .follow_links(false)
+            .into_iter()
             .flatten()
         {
-            let year_month_path = year_month_entry.path();
-            if !year_month_path.is_dir() {
-                continue;
+            if entry.file_type().is_file() {
+                if let Ok(relative) = entry.path().strip_prefix(base_dir) {
+                    files.push(relative.to_path_buf());
+                }
             }
+        }
+    }
+    files
+}
 
-            let year_month = year_month_path
-                .file_name()
-                .and_then(|n| n.to_str())
-                .unwrap_or("")
-                .to_string();
-
-            // 读取该月份的索引文件
-            let index_path = year_month_path.join(".index.json");
-            if !index_path.exists() {
-                continue;
-            }
+/// 将资产存储目录整体迁移到新位置
+///
+/// 先把所有文件复制到新目录并逐个校验大小，全部成功后才更新配置并切换内存 Catalog，
+/// 最后删除旧目录中的文件。复制阶段任何失败都会清理已复制到新目录的文件，旧目录保持不变。
+#[tauri::command]
+pub async fn migrate_asset_storage(
+    app: AppHandle,
+    catalog: tauri::State<'_, AssetCatalog>,
+    from: String,
+    to: String,
+) -> Result<AssetMigrationResult, String> {
+    let from_dir = PathBuf::from(&from);
+    let to_dir = PathBuf::from(&to);
+
+    if !from_dir.is_dir() {
+        return Err(format!("源目录不存在: {}", from));
+    }
+    fs::create_dir_all(&to_dir).map_err(|e| format!("无法创建目标目录 '{}': {}", to, e))?;
+
+    let from_canonical = from_dir
+        .canonicalize()
+        .map_err(|e| format!("无法解析源目录: {}", e))?;
+    let to_canonical = to_dir
+        .canonicalize()
+        .map_err(|e| format!("无法解析目标目录: {}", e))?;
+    // 迁移完成后会删除源目录中的文件，只允许从当前资产根目录迁出
+    let current_canonical = PathBuf::from(get_asset_base_path(app.clone())?)
+        .canonicalize()
+        .map_err(|e| format!("无法解析当前资产目录: {}", e))?;
+    if from_canonical != current_canonical {
+        return Err(format!(
+            "源目录不是当前资产存储目录: {}",
+            from_canonical.display()
+        ));
+    }
+    if from_canonical == to_canonical {
+        return Err("源目录与目标目录相同".to_string());
+    }
+    if to_canonical.starts_with(&from_canonical) || from_canonical.starts_with(&to_canonical) {
+        return Err("源目录与目标目录不能互相嵌套".to_string());
+    }
+    if ASSET_STORAGE_DIRS
+        .iter()
+        .any(|dir_name| to_dir.join(dir_name).exists())
+    {
+        return Err(format!(
+            "目标目录中已存在资产数据，请选择一个空目录: {}",
+            to
+        ));
+    }
 
-            let index = MonthHashIndex::from_file(&index_path)?;
+    // 确保内存中尚未落盘的修改先写入旧目录的 Catalog
+    catalog.flush()?;
 
-            // 遍历索引中的每个条目
-            for (hash, filenames) in index.entries.iter() {
-                for filename in filenames {
-                    let relative_path = format!("{}/{}/{}", type_dir_str, year_month, filename);
+    let files = collect_asset_storage_files(&from_dir);
+    let total = files.len();
+    let mut copied: Vec<PathBuf> = Vec::with_capacity(total);
+    let mut migrated_bytes = 0u64;
 
-                    hash_map.entry(hash.clone()).or_default().push((
-                        relative_path,
-                        type_dir_str.to_string(),
-                        year_month.clone(),
-                        filename.clone(),
-                    ));
-                }
-            }
+    let emit_progress = |phase: &str, current: usize, file: &Path| {
+        let progress = AssetMigrationProgress {
+            phase: phase.to_string(),
+            current,
+            total,
+            current_file: file.to_string_lossy().replace('\\', "/"),
+        };
+        if let Err(e) = app.emit("asset-migration-progress", &progress) {
+            log::error!("发出 asset-migration-progress 事件失败: {}", e);
         }
-    }
+    };
 
-    // 筛选出重复的文件组（哈希值对应的文件数 > 1）
-    let mut duplicates = Vec::new();
-    let mut total_files = 0;
-    let mut wasted_space = 0u64;
+    // 第一步：复制并校验，失败时回滚目标目录
+    let copy_result = (|| -> Result<(), String> {
+        for (index, relative) in files.iter().enumerate() {
+            emit_progress("copying", index + 1, relative);
 
-    for (hash, files_info) in hash_map.iter() {
-        if files_info.len() > 1 {
-            // 获取第一个文件的大小作为参考
-            let first_file = &files_info[0];
-            let first_file_path = base_dir
-                .join(&first_file.1)
-                .join(&first_file.2)
-                .join(&first_file.3);
+            let source = from_dir.join(relative);
+            let target = to_dir.join(relative);
+            if let Some(parent) = target.parent() {
+                fs::create_dir_all(parent).map_err(|e| format!("创建目录失败: {}", e))?;
+            }
 
-            let file_size = first_file_path.metadata().map(|m| m.len()).unwrap_or(0);
+            let expected = fs::metadata(&source)
+                .map_err(|e| format!("读取文件信息失败 '{}': {}", relative.display(), e))?
+                .len();
+            fs::copy(&source, &target)
+                .map_err(|e| format!("复制文件失败 '{}': {}", relative.display(), e))?;
+            copied.push(relative.clone());
+
+            let actual = fs::metadata(&target).map(|m| m.len()).unwrap_or(u64::MAX);
+            if actual != expected {
+                return Err(format!(
+                    "文件校验失败 '{}': 期望 {} 字节，实际 {} 字节",
+                    relative.display(),
+                    expected,
+                    actual
+                ));
+            }
+            migrated_bytes += expected;
+        }
+        Ok(())
+    })();
 
-            let count = files_info.len();
+    if let Err(e) = copy_result {
+        log::error!("[AssetMigration] 迁移失败，正在清理目标目录: {}", e);
+        for relative in &copied {
+            let _ = fs::remove_file(to_dir.join(relative));
+        }
+        for dir_name in ASSET_STORAGE_DIRS {
+            let _ = remove_empty_dirs(&to_dir.join(dir_name));
+        }
+        return Err(e);
+    }
 
-            // 计算浪费的空间（重复文件数 - 1）* 文件大小
-            wasted_space += file_size * (count as u64 - 1);
-            total_files += count;
+    // 第二步：更新配置并将内存 Catalog 切换到新目录
+    let config_path = crate::get_app_data_dir(app.config())
+        .join("app-settings")
+        .join("settings.json");
+    let mut config: serde_json::Value = fs::read_to_string(&config_path)
+        .ok()
+        .and_then(|content| serde_json::from_str(&content).ok())
+        .unwrap_or_else(|| serde_json::json!({}));
+    config["customAssetPath"] = serde_json::Value::String(to.clone());
+    if let Some(parent) = config_path.parent() {
+        fs::create_dir_all(parent).map_err(|e| format!("创建配置目录失败: {}", e))?;
+    }
+    let content =
+        serde_json::to_string_pretty(&config).map_err(|e| format!("序列化配置失败: {}", e))?;
+    fs::write(&config_path, content).map_err(|e| format!("写入配置文件失败: {}", e))?;
 
-            duplicates.push(DuplicateFileInfo {
-                hash: hash.clone(),
-                files: files_info
-                    .iter()
-                    .map(|(path, _, _, _)| path.clone())
-                    .collect(),
-                size: file_size,
-                count,
-            });
+    catalog.initialize(&app)?;
+
+    // 第三步：删除旧目录中已迁移的文件，失败只记录不影响结果
+    let mut cleanup_failures = 0;
+    for (index, relative) in files.iter().enumerate() {
+        emit_progress("cleaning", index + 1, relative);
+        if let Err(e) = fs::remove_file(from_dir.join(relative)) {
+            log::warn!(
+                "[AssetMigration] 删除旧文件失败 {}: {}",
+                relative.display(),
+                e
+            );
+            cleanup_failures += 1;
         }
     }
+    for dir_name in ASSET_STORAGE_DIRS {
+        let _ = remove_empty_dirs(&from_dir.join(dir_name));
+    }
 
-    // 按重复文件数量降序排序
-    duplicates.sort_by(|a, b| b.count.cmp(&a.count));
+    log::info!(
+        "[AssetMigration] 资产存储已从 {} 迁移到 {}，共 {} 个文件",
+        from,
+        to,
+        total
+    );
 
-    Ok(DuplicateFilesResult {
-        total_groups: duplicates.len(),
-        total_files,
-        wasted_space,
-        duplicates,
+    Ok(AssetMigrationResult {
+        migrated_files: total,
+        migrated_bytes,
+        cleanup_failures,
     })
 }
 
+/// 自底向上删除空目录
+fn remove_empty_dirs(dir: &Path) -> std::io::Result<()> {
+    if !dir.is_dir() {
+        return Ok(());
+    }
+    for entry in fs::read_dir(dir)?.flatten() {
+        let path = entry.path();
+        if path.is_dir() {
+            remove_empty_dirs(&path)?;
+        }
+    }
+    if fs::read_dir(dir)?.next().is_none() {
+        fs::remove_dir(dir)?;
+    }
+    Ok(())
+}
+
 // --- 新增的懒加载相关命令 ---
 
 /// Catalog 索引中的单个条目结构
@@ -2069,9 +3406,12 @@ pub(crate) struct CatalogEntry {
     created_at: String,
     #[serde(default = "default_origins")]
     origins: Vec<AssetOrigin>,
-    sha256: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    tags: Vec<String>,
+    #[serde(default)]
+    blake3: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
-    original_sha256: Option<String>,
+    original_blake3: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     derived: Option<HashMap<String, DerivedDataInfo>>,
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -2082,12 +3422,19 @@ pub(crate) struct CatalogEntry {
     height: Option<u32>,
     #[serde(skip_serializing_if = "Option::is_none")]
     duration: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    codec: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    exif: Option<ImageExif>,
 
     // 旧版本字段，仅用于向后兼容
     #[serde(skip_serializing)]
     source_module: Option<String>,
     #[serde(skip_serializing)]
     origin_type: Option<AssetOriginType>,
+    /// 旧版 SHA-256 哈希，存在说明该条目尚未迁移到 BLAKE3，迁移完成后清除
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    sha256: Option<String>,
 }
 
 /// 为旧数据提供默认的空 origins 数组
@@ -2139,11 +3486,12 @@ fn convert_asset_to_catalog_entry(asset: &Asset) -> CatalogEntry {
         asset_type: asset.asset_type.clone(),
         created_at: asset.created_at.clone(),
         origins: asset.origins.clone(),
-        sha256: asset.metadata.as_ref().and_then(|m| m.sha256.clone()),
-        original_sha256: asset
+        tags: asset.tags.clone(),
+        blake3: asset.metadata.as_ref().and_then(|m| m.blake3.clone()),
+        original_blake3: asset
             .metadata
             .as_ref()
-            .and_then(|m| m.original_sha256.clone()),
+            .and_then(|m| m.original_blake3.clone()),
         derived: asset.metadata.as_ref().and_then(|m| m.derived.clone()),
         audio_waveform: asset
             .metadata
@@ -2152,16 +3500,18 @@ fn convert_asset_to_catalog_entry(asset: &Asset) -> CatalogEntry {
         width: asset.metadata.as_ref().and_then(|m| m.width),
         height: asset.metadata.as_ref().and_then(|m| m.height),
         duration: asset.metadata.as_ref().and_then(|m| m.duration),
+        codec: asset.metadata.as_ref().and_then(|m| m.codec.clone()),
+        exif: asset.metadata.as_ref().and_then(|m| m.exif.clone()),
         // 旧字段设为 None，仅用于反序列化兼容
         source_module: None,
         origin_type: None,
+        sha256: None,
     }
 }
 
 /// 将 CatalogEntry 转换为 Asset
 fn convert_entry_to_asset(entry: CatalogEntry, base_dir: &Path) -> Asset {
-    let thumbnail_relative = format!(".thumbnails/{}.jpg", entry.id);
-    let thumbnail_path = base_dir.join(&thumbnail_relative);
+    let thumbnail_path = find_thumbnail_relative(base_dir, &entry.id);
 
     let source_module = entry
         .origins
@@ -2175,22 +3525,21 @@ fn convert_entry_to_asset(entry: CatalogEntry, base_dir: &Path) -> Asset {
         mime_type: entry.mime_type,
         name: entry.name,
         path: entry.path,
-        thumbnail_path: if thumbnail_path.exists() {
-            Some(thumbnail_relative)
-        } else {
-            None
-        },
+        thumbnail_path,
         size: entry.size,
         created_at: entry.created_at,
         source_module, // For backward compatibility, use the first one
         origins: entry.origins,
+        tags: entry.tags,
         metadata: Some(AssetMetadata {
+            exif: entry.exif,
             width: entry.width,
             height: entry.height,
             duration: entry.duration,
+            codec: entry.codec,
             audio_waveform: entry.audio_waveform.clone(),
-            sha256: entry.sha256,
-            original_sha256: entry.original_sha256,
+            blake3: entry.blake3,
+            original_blake3: entry.original_blake3,
             derived: entry.derived,
         }),
     }
@@ -2205,6 +3554,7 @@ pub async fn list_assets_paginated(
     let base_path = get_asset_base_path(app)?;
     let base_dir = PathBuf::from(&base_path);
 
+    catalog.reload_if_changed()?;
     let entries_lock = catalog.entries.read().map_err(|e| e.to_string())?;
     let all_entries: Vec<&CatalogEntry> = entries_lock.values().collect();
 
@@ -2212,7 +3562,7 @@ pub async fn list_assets_paginated(
     let duplicate_hashes: HashSet<String> = if payload.show_duplicates_only {
         let mut hash_counts = HashMap::new();
         for entry in &all_entries {
-            if let Some(hash) = &entry.sha256 {
+            if let Some(hash) = &entry.blake3 {
                 if !hash.is_empty() {
                     *hash_counts.entry(hash.clone()).or_insert(0) += 1;
                 }
@@ -2260,16 +3610,24 @@ pub async fn list_assets_paginated(
                 _ => true,
             };
 
+            let tags_match =
+                entry_matches_tags(entry, &payload.filter_tags, payload.tag_match_mode);
+
             let duplicates_match = if payload.show_duplicates_only {
                 entry
-                    .sha256
+                    .blake3
                     .as_ref()
                     .is_some_and(|hash| !hash.is_empty() && duplicate_hashes.contains(hash))
             } else {
                 true
             };
 
-            type_match && origin_match && source_module_match && search_match && duplicates_match
+            type_match
+                && origin_match
+                && source_module_match
+                && search_match
+                && tags_match
+                && duplicates_match
         })
         .collect();
 
@@ -2324,6 +3682,7 @@ pub async fn list_assets_paginated(
 pub async fn get_asset_stats(
     catalog: tauri::State<'_, AssetCatalog>,
 ) -> Result<AssetStats, String> {
+    catalog.reload_if_changed()?;
     let entries = catalog.entries.read().map_err(|e| e.to_string())?;
     let mut stats = AssetStats::default();
 
@@ -2344,11 +3703,61 @@ pub async fn get_asset_stats(
                 .entry(origin.origin_type.clone())
                 .or_insert(0) += 1;
         }
+        if let Ok(created_at) = chrono::DateTime::parse_from_rfc3339(&entry.created_at) {
+            let month = created_at.with_timezone(&Utc).format("%Y-%m").to_string();
+            *stats.monthly_counts.entry(month).or_insert(0) += 1;
+        }
     }
 
     Ok(stats)
 }
 
+/// Catalog 索引校验结果
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CatalogVerifyResult {
+    /// 非空行总数
+    total_lines: usize,
+    /// 可正常解析的条目数
+    valid_entries: usize,
+    /// 损坏的行数
+    corrupted_lines: usize,
+    /// 损坏行的行号（从 1 开始）
+    corrupted_line_numbers: Vec<usize>,
+    /// 损坏行数超过阈值，建议重建目录索引
+    needs_rebuild: bool,
+}
+
+/// 校验磁盘上的 Catalog 索引文件，统计损坏的行
+#[tauri::command]
+pub async fn verify_catalog_index(app: AppHandle) -> Result<CatalogVerifyResult, String> {
+    let base_path = get_asset_base_path(app)?;
+    let catalog_path = get_catalog_path(&PathBuf::from(&base_path))?;
+
+    if !catalog_path.exists() {
+        return Ok(CatalogVerifyResult {
+            total_lines: 0,
+            valid_entries: 0,
+            corrupted_lines: 0,
+            corrupted_line_numbers: Vec::new(),
+            needs_rebuild: false,
+        });
+    }
+
+    let file =
+        fs::File::open(&catalog_path).map_err(|e| format!("无法打开 Catalog 文件: {}", e))?;
+    let result = parse_catalog_lines(std::io::BufReader::new(file))?;
+    let corrupted_lines = result.corrupted_lines.len();
+
+    Ok(CatalogVerifyResult {
+        total_lines: result.total_lines,
+        valid_entries: result.entries.len(),
+        corrupted_lines,
+        corrupted_line_numbers: result.corrupted_lines,
+        needs_rebuild: corrupted_lines > CATALOG_CORRUPTED_LINES_THRESHOLD,
+    })
+}
+
 #[tauri::command]
 pub async fn rebuild_catalog_index(
     app: AppHandle,
@@ -2386,9 +3795,7 @@ pub async fn rebuild_catalog_index(
                 if let Ok(file_entries) = fs::read_dir(&year_month_path) {
                     for file_entry in file_entries.flatten() {
                         let file_path = file_entry.path();
-                        if file_path.is_file()
-                            && file_path.file_name() != Some(".index.json".as_ref())
-                        {
+                        if file_path.is_file() && !is_month_index_file(&file_path) {
                             all_file_paths.push((file_path, type_dir_str.to_string()));
                         }
                     }
@@ -2399,9 +3806,14 @@ pub async fn rebuild_catalog_index(
 
     let total_files = all_file_paths.len();
 
+    // FFmpeg 可用时为音视频补全时长等元数据，并为缺少缩略图的视频补生成，不可用则跳过
+    let ffmpeg_path = resolve_available_ffmpeg(&app).await;
+
     // 第三步：处理文件并构建新的内存索引，同时写入磁盘和报告进度
-    let file =
-        fs::File::create(&catalog_path).map_err(|e| format!("无法创建 Catalog 文件: {}", e))?;
+    // 先写入临时文件，完成后再替换，避免重建过程中被按 mtime 重载读到半截文件
+    let temp_catalog_path = catalog_path.with_extension("jsonl.tmp");
+    let file = fs::File::create(&temp_catalog_path)
+        .map_err(|e| format!("无法创建临时 Catalog 文件: {}", e))?;
     let mut writer = BufWriter::new(file);
     let mut new_entries: HashMap<String, CatalogEntry> = HashMap::new();
 
@@ -2412,6 +3824,7 @@ pub async fn rebuild_catalog_index(
             // 尝试从旧数据中恢复元数据
             if let Some(old_entry) = existing_metadata.get(&asset.id) {
                 asset.origins = old_entry.origins.clone();
+                asset.tags = old_entry.tags.clone();
                 if let Some(first_origin) = asset.origins.first() {
                     asset.source_module = first_origin.source_module.clone();
                 }
@@ -2419,12 +3832,36 @@ pub async fn rebuild_catalog_index(
                     metadata.width = old_entry.width;
                     metadata.height = old_entry.height;
                     metadata.duration = old_entry.duration;
+                    metadata.codec = old_entry.codec.clone();
+                    metadata.exif = old_entry.exif.clone();
                     metadata.audio_waveform = old_entry.audio_waveform.clone();
-                    metadata.original_sha256 = old_entry.original_sha256.clone();
+                    metadata.original_blake3 = old_entry.original_blake3.clone();
                     metadata.derived = old_entry.derived.clone();
                 }
             }
 
+            if let Some(ffmpeg_path) = &ffmpeg_path {
+                if let Some(metadata) = asset.metadata.as_mut() {
+                    let is_media = matches!(asset.asset_type, AssetType::Audio | AssetType::Video);
+                    if is_media && metadata.duration.is_none() {
+                        fill_media_metadata(ffmpeg_path, file_path, &asset.asset_type, metadata)
+                            .await;
+                    }
+                }
+
+                let thumbnail_exists = find_thumbnail_relative(&base_dir, &asset.id).is_some();
+                if matches!(asset.asset_type, AssetType::Video) && !thumbnail_exists {
+                    generate_video_thumbnail(
+                        ffmpeg_path,
+                        file_path,
+                        &base_dir,
+                        &asset.id,
+                        DEFAULT_THUMBNAIL_SIZE,
+                    )
+                    .await;
+                }
+            }
+
             let entry = convert_asset_to_catalog_entry(&asset);
             let line = serde_json::to_string(&entry)
                 .map_err(|e| format!("序列化 Catalog 条目失败: {}", e))?;
@@ -2449,12 +3886,17 @@ pub async fn rebuild_catalog_index(
     writer
         .flush()
         .map_err(|e| format!("刷新 Catalog 文件缓冲区失败: {}", e))?;
+    drop(writer);
+    fs::rename(&temp_catalog_path, &catalog_path)
+        .map_err(|e| format!("重命名临时 Catalog 文件失败: {}", e))?;
 
     // 第四步：将重建结果同步到内存（替换整个内存索引）
     {
         let mut entries = catalog.entries.write().map_err(|e| e.to_string())?;
         *entries = new_entries;
     }
+    catalog.rebuild_hash_lookup()?;
+    catalog.sync_disk_mtime(&catalog_path);
     // 磁盘已经直接写入，无需 mark_dirty
 
     log::info!(
@@ -2511,9 +3953,10 @@ pub async fn remove_asset_source(
     catalog.mark_dirty(&app);
 
     if let Some((id, path)) = asset_to_delete {
-        if let Err(e) = delete_asset_files(&base_dir, &id, &path) {
+        if let Err(e) = delete_asset_files(&base_dir, &id, &path, false) {
             log::error!("在移除来源期间删除资产文件失败: {}", e);
         }
+        remove_assets_from_all_collections(&base_dir, &[id]);
     }
 
     Ok(result)
@@ -2550,13 +3993,91 @@ pub async fn add_asset_source(
     Ok(updated)
 }
 
+/// 判断条目是否满足标签筛选条件，未指定标签时总是满足
+fn entry_matches_tags(entry: &CatalogEntry, filter_tags: &[String], mode: TagMatchMode) -> bool {
+    if filter_tags.is_empty() {
+        return true;
+    }
+    let has_tag = |tag: &String| entry.tags.iter().any(|t| t == tag);
+    match mode {
+        TagMatchMode::All => filter_tags.iter().all(has_tag),
+        TagMatchMode::Any => filter_tags.iter().any(has_tag),
+    }
+}
+
+/// 规范化标签：去除首尾空白，丢弃空标签并去重（保持原有顺序）
+fn normalize_tags(tags: Vec<String>) -> Vec<String> {
+    let mut normalized: Vec<String> = Vec::new();
+    for tag in tags {
+        let tag = tag.trim().to_string();
+        if !tag.is_empty() && !normalized.contains(&tag) {
+            normalized.push(tag);
+        }
+    }
+    normalized
+}
+
+/// 为资产添加标签，已存在的标签会被忽略
+#[tauri::command]
+pub async fn add_asset_tags(
+    app: AppHandle,
+    catalog: tauri::State<'_, AssetCatalog>,
+    asset_id: String,
+    tags: Vec<String>,
+) -> Result<Asset, String> {
+    let base_path = get_asset_base_path(app.clone())?;
+    let base_dir = PathBuf::from(&base_path);
+
+    let mut entries = catalog.entries.write().map_err(|e| e.to_string())?;
+    let entry = entries
+        .get_mut(&asset_id)
+        .ok_or_else(|| format!("找不到 ID 为 '{}' 的资产", asset_id))?;
+
+    let mut merged = std::mem::take(&mut entry.tags);
+    merged.extend(tags);
+    entry.tags = normalize_tags(merged);
+
+    let updated = convert_entry_to_asset(entry.clone(), &base_dir);
+    drop(entries);
+    catalog.mark_dirty(&app);
+
+    Ok(updated)
+}
+
+/// 从资产中移除标签
+#[tauri::command]
+pub async fn remove_asset_tags(
+    app: AppHandle,
+    catalog: tauri::State<'_, AssetCatalog>,
+    asset_id: String,
+    tags: Vec<String>,
+) -> Result<Asset, String> {
+    let base_path = get_asset_base_path(app.clone())?;
+    let base_dir = PathBuf::from(&base_path);
+
+    let to_remove = normalize_tags(tags);
+    let mut entries = catalog.entries.write().map_err(|e| e.to_string())?;
+    let entry = entries
+        .get_mut(&asset_id)
+        .ok_or_else(|| format!("找不到 ID 为 '{}' 的资产", asset_id))?;
+
+    entry.tags.retain(|tag| !to_remove.contains(tag));
+
+    let updated = convert_entry_to_asset(entry.clone(), &base_dir);
+    drop(entries);
+    catalog.mark_dirty(&app);
+
+    Ok(updated)
+}
+
 /// 完全删除资产（移除所有来源并删除文件）
 #[tauri::command]
 pub async fn remove_asset_completely(
     app: AppHandle,
     catalog: tauri::State<'_, AssetCatalog>,
     asset_id: String,
-) -> Result<(), String> {
+    permanent: Option<bool>,
+) -> Result<AssetDeleteResult, String> {
     let base_path = get_asset_base_path(app.clone())?;
     let base_dir = PathBuf::from(&base_path);
 
@@ -2569,18 +4090,28 @@ pub async fn remove_asset_completely(
     };
     catalog.mark_dirty(&app);
 
-    delete_asset_files(&base_dir, &asset_id, &asset_path)?;
+    remove_assets_from_all_collections(&base_dir, std::slice::from_ref(&asset_id));
 
-    Ok(())
+    let freed_bytes = delete_asset_files(
+        &base_dir,
+        &asset_id,
+        &asset_path,
+        permanent.unwrap_or(false),
+    )?;
+
+    Ok(AssetDeleteResult { freed_bytes })
 }
 
 /// 批量完全删除资产（移除所有来源并删除文件）
+///
+/// `permanent` 为 true 时跳过回收站直接删除，返回失败的 ID 与实际释放的字节数
 #[tauri::command]
 pub async fn remove_assets_completely(
     app: AppHandle,
     catalog: tauri::State<'_, AssetCatalog>,
     asset_ids: Vec<String>,
-) -> Result<Vec<String>, String> {
+    permanent: Option<bool>,
+) -> Result<BatchAssetDeleteResult, String> {
     let base_path = get_asset_base_path(app.clone())?;
     let base_dir = PathBuf::from(&base_path);
 
@@ -2596,21 +4127,291 @@ pub async fn remove_assets_completely(
         }
     }
     catalog.mark_dirty(&app);
+    let removed_ids: Vec<String> = assets_to_delete.iter().map(|(id, _)| id.clone()).collect();
+    remove_assets_from_all_collections(&base_dir, &removed_ids);
 
+    let permanent = permanent.unwrap_or(false);
     let mut failed_ids = Vec::new();
+    let mut freed_bytes = 0;
     for (id, path) in assets_to_delete {
-        if let Err(e) = delete_asset_files(&base_dir, &id, &path) {
-            log::error!("删除资产 {} 失败: {}", id, e);
-            failed_ids.push(id);
+        match delete_asset_files(&base_dir, &id, &path, permanent) {
+            Ok(bytes) => freed_bytes += bytes,
+            Err(e) => {
+                log::error!("删除资产 {} 失败: {}", id, e);
+                failed_ids.push(id);
+            }
+        }
+    }
+
+    Ok(BatchAssetDeleteResult {
+        failed_ids,
+        freed_bytes,
+    })
+}
+
+/// 资产集合（相册），成员关系独立存放在 `.catalog/collections.json`
+///
+/// 集合只保存资产 ID，一个资产可以同时属于多个集合
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AssetCollection {
+    pub id: String,
+    pub name: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    pub created_at: String,
+    pub updated_at: String,
+    #[serde(default)]
+    pub asset_ids: Vec<String>,
+}
+
+/// collections.json 的根结构
+#[derive(Debug, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct CollectionsFile {
+    #[serde(default)]
+    collections: Vec<AssetCollection>,
+}
+
+/// 串行化 collections.json 的读改写，避免并发命令互相覆盖
+static COLLECTIONS_LOCK: std::sync::Mutex<()> = std::sync::Mutex::new(());
+
+fn get_collections_path(base_dir: &Path) -> PathBuf {
+    base_dir.join(".catalog").join("collections.json")
+}
+
+fn load_collections(base_dir: &Path) -> AppResult<CollectionsFile> {
+    let path = get_collections_path(base_dir);
+    match fs::read_to_string(&path) {
+        Ok(content) => serde_json::from_str(&content).context("解析集合文件失败"),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(CollectionsFile::default()),
+        Err(e) => Err(AppError::from(e).context("读取集合文件失败")),
+    }
+}
+
+/// 先写临时文件再替换，避免写到一半时崩溃损坏集合数据
+fn save_collections(base_dir: &Path, file: &CollectionsFile) -> AppResult<()> {
+    let path = get_collections_path(base_dir);
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).context("创建 Catalog 目录失败")?;
+    }
+    let temp_path = path.with_extension("json.tmp");
+    let content = serde_json::to_string_pretty(file)?;
+    fs::write(&temp_path, content).context("写入集合文件失败")?;
+    fs::rename(&temp_path, &path).context("替换集合文件失败")?;
+    Ok(())
+}
+
+/// 在持锁状态下读改写集合文件，`update` 返回 true 时才落盘
+fn update_collections<T>(
+    base_dir: &Path,
+    update: impl FnOnce(&mut CollectionsFile) -> AppResult<(T, bool)>,
+) -> AppResult<T> {
+    let _guard = COLLECTIONS_LOCK
+        .lock()
+        .map_err(|e| AppError::Other(e.to_string()))?;
+    let mut file = load_collections(base_dir)?;
+    let (result, changed) = update(&mut file)?;
+    if changed {
+        save_collections(base_dir, &file)?;
+    }
+    Ok(result)
+}
+
+/// 资产被删除后从所有集合中移除其引用；失败只记录日志，不影响删除本身
+fn remove_assets_from_all_collections(base_dir: &Path, asset_ids: &[String]) {
+    if asset_ids.is_empty() || !get_collections_path(base_dir).exists() {
+        return;
+    }
+    let removed: HashSet<&str> = asset_ids.iter().map(String::as_str).collect();
+    let result = update_collections(base_dir, |file| {
+        let now = Utc::now().to_rfc3339();
+        let mut changed = false;
+        for collection in file.collections.iter_mut() {
+            let before = collection.asset_ids.len();
+            collection
+                .asset_ids
+                .retain(|id| !removed.contains(id.as_str()));
+            if collection.asset_ids.len() != before {
+                collection.updated_at = now.clone();
+                changed = true;
+            }
+        }
+        Ok(((), changed))
+    });
+    if let Err(e) = result {
+        log::warn!("[AssetCollections] 从集合中移除已删除资产失败: {}", e);
+    }
+}
+
+/// 创建资产集合
+#[tauri::command]
+pub fn create_collection(
+    app: AppHandle,
+    name: String,
+    description: Option<String>,
+) -> AppResult<AssetCollection> {
+    let name = name.trim().to_string();
+    if name.is_empty() {
+        return Err(AppError::InvalidInput("集合名称不能为空".to_string()));
+    }
+    let base_dir = PathBuf::from(get_asset_base_path(app)?);
+
+    update_collections(&base_dir, |file| {
+        if file.collections.iter().any(|c| c.name == name) {
+            return Err(AppError::AlreadyExists(format!("集合 '{}' 已存在", name)));
+        }
+        let now = Utc::now().to_rfc3339();
+        let collection = AssetCollection {
+            id: Uuid::new_v4().to_string(),
+            name,
+            description: description
+                .map(|d| d.trim().to_string())
+                .filter(|d| !d.is_empty()),
+            created_at: now.clone(),
+            updated_at: now,
+            asset_ids: Vec::new(),
+        };
+        file.collections.push(collection.clone());
+        Ok((collection, true))
+    })
+}
+
+/// 把资产加入集合，已在集合中的资产与 Catalog 中不存在的 ID 会被忽略
+#[tauri::command]
+pub fn add_assets_to_collection(
+    app: AppHandle,
+    catalog: tauri::State<'_, AssetCatalog>,
+    collection_id: String,
+    asset_ids: Vec<String>,
+) -> AppResult<AssetCollection> {
+    let base_dir = PathBuf::from(get_asset_base_path(app)?);
+    let known_ids: Vec<String> = {
+        let entries = catalog
+            .entries
+            .read()
+            .map_err(|e| AppError::Other(e.to_string()))?;
+        asset_ids
+            .into_iter()
+            .filter(|id| entries.contains_key(id))
+            .collect()
+    };
+
+    update_collections(&base_dir, |file| {
+        let collection = file
+            .collections
+            .iter_mut()
+            .find(|c| c.id == collection_id)
+            .ok_or_else(|| AppError::NotFound(format!("找不到集合: {}", collection_id)))?;
+
+        let mut existing: HashSet<String> = collection.asset_ids.iter().cloned().collect();
+        let mut changed = false;
+        for id in known_ids {
+            if existing.insert(id.clone()) {
+                collection.asset_ids.push(id);
+                changed = true;
+            }
+        }
+        if changed {
+            collection.updated_at = Utc::now().to_rfc3339();
+        }
+        Ok((collection.clone(), changed))
+    })
+}
+
+/// 列出所有资产集合（按创建时间排序）
+#[tauri::command]
+pub fn list_collections(app: AppHandle) -> AppResult<Vec<AssetCollection>> {
+    let base_dir = PathBuf::from(get_asset_base_path(app)?);
+    let _guard = COLLECTIONS_LOCK
+        .lock()
+        .map_err(|e| AppError::Other(e.to_string()))?;
+    let mut collections = load_collections(&base_dir)?.collections;
+    collections.sort_by(|a, b| a.created_at.cmp(&b.created_at));
+    Ok(collections)
+}
+
+/// 列出集合中的资产，按加入集合的顺序返回，已不存在的资产会被跳过
+#[tauri::command]
+pub fn list_collection_assets(
+    app: AppHandle,
+    catalog: tauri::State<'_, AssetCatalog>,
+    collection_id: String,
+) -> AppResult<Vec<Asset>> {
+    let base_dir = PathBuf::from(get_asset_base_path(app)?);
+    let asset_ids = {
+        let _guard = COLLECTIONS_LOCK
+            .lock()
+            .map_err(|e| AppError::Other(e.to_string()))?;
+        load_collections(&base_dir)?
+            .collections
+            .into_iter()
+            .find(|c| c.id == collection_id)
+            .ok_or_else(|| AppError::NotFound(format!("找不到集合: {}", collection_id)))?
+            .asset_ids
+    };
+
+    let entries = catalog
+        .entries
+        .read()
+        .map_err(|e| AppError::Other(e.to_string()))?;
+    Ok(asset_ids
+        .iter()
+        .filter_map(|id| entries.get(id))
+        .map(|entry| convert_entry_to_asset(entry.clone(), &base_dir))
+        .collect())
+}
+
+#[cfg(test)]
+mod collection_tests {
+    use super::{
+        load_collections, remove_assets_from_all_collections, save_collections, AssetCollection,
+        CollectionsFile,
+    };
+
+    fn collection(id: &str, asset_ids: &[&str]) -> AssetCollection {
+        AssetCollection {
+            id: id.to_string(),
+            name: id.to_string(),
+            description: None,
+            created_at: "2025-01-01T00:00:00Z".to_string(),
+            updated_at: "2025-01-01T00:00:00Z".to_string(),
+            asset_ids: asset_ids.iter().map(|s| s.to_string()).collect(),
         }
     }
 
-    Ok(failed_ids)
+    #[test]
+    fn missing_file_loads_as_empty() {
+        let dir = tempfile::tempdir().unwrap();
+        assert!(load_collections(dir.path()).unwrap().collections.is_empty());
+    }
+
+    #[test]
+    fn deleted_assets_are_removed_from_every_collection() {
+        let dir = tempfile::tempdir().unwrap();
+        let file = CollectionsFile {
+            collections: vec![
+                collection("project-a", &["1", "2", "3"]),
+                collection("stickers", &["2", "4"]),
+                collection("untouched", &["5"]),
+            ],
+        };
+        save_collections(dir.path(), &file).unwrap();
+
+        remove_assets_from_all_collections(dir.path(), &["2".to_string(), "3".to_string()]);
+
+        let loaded = load_collections(dir.path()).unwrap().collections;
+        assert_eq!(loaded[0].asset_ids, vec!["1"]);
+        assert_eq!(loaded[1].asset_ids, vec!["4"]);
+        assert_eq!(loaded[2].asset_ids, vec!["5"]);
+        assert_eq!(loaded[2].updated_at, "2025-01-01T00:00:00Z");
+        assert_ne!(loaded[0].updated_at, "2025-01-01T00:00:00Z");
+    }
 }
 
-/// 根据哈希值在当月索引中查找资产
+/// 根据哈希值在全局 Catalog 中查找资产
 ///
-/// 仅在当前月份的目录中查找，以支持按月滚动的清理策略。
+/// 跨月份查找，文件已丢失的失效条目会被自动清理。
 /// 如果提供了 source_to_add，且找到了资产，会将该来源添加到资产中。
 #[tauri::command]
 pub async fn find_asset_by_hash(
@@ -2622,42 +4423,28 @@ pub async fn find_asset_by_hash(
     let base_path = get_asset_base_path(app.clone())?;
     let base_dir = PathBuf::from(&base_path);
 
-    let asset_types = [
-        AssetType::Image,
-        AssetType::Audio,
-        AssetType::Video,
-        AssetType::Document,
-        AssetType::Other,
-    ];
-
-    for asset_type in &asset_types {
-        let found = {
-            let entries = catalog.entries.read().map_err(|e| e.to_string())?;
-            check_duplicate_in_current_month(&base_dir, asset_type, &hash, &entries)?
-        };
+    let Some(asset) = catalog.find_duplicate(&app, &base_dir, &hash)? else {
+        return Ok(None);
+    };
 
-        if let Some(asset) = found {
-            if let Some(origin) = source_to_add {
-                let mut entries = catalog.entries.write().map_err(|e| e.to_string())?;
-                if let Some(entry) = entries.get_mut(&asset.id) {
-                    if !entry
-                        .origins
-                        .iter()
-                        .any(|o| o.source_module == origin.source_module)
-                    {
-                        entry.origins.push(origin);
-                    }
-                    let updated = convert_entry_to_asset(entry.clone(), &base_dir);
-                    drop(entries);
-                    catalog.mark_dirty(&app);
-                    return Ok(Some(updated));
-                }
+    if let Some(origin) = source_to_add {
+        let mut entries = catalog.entries.write().map_err(|e| e.to_string())?;
+        if let Some(entry) = entries.get_mut(&asset.id) {
+            if !entry
+                .origins
+                .iter()
+                .any(|o| o.source_module == origin.source_module)
+            {
+                entry.origins.push(origin);
             }
-            return Ok(Some(asset));
+            let updated = convert_entry_to_asset(entry.clone(), &base_dir);
+            drop(entries);
+            catalog.mark_dirty(&app);
+            return Ok(Some(updated));
         }
     }
 
-    Ok(None)
+    Ok(Some(asset))
 }
 
 /// 更新资产的衍生数据信息
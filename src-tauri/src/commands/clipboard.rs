@@ -12,16 +12,127 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use base64::{engine::general_purpose, Engine as _};
+use serde::Serialize;
+use std::collections::hash_map::DefaultHasher;
+use std::collections::VecDeque;
+use std::hash::{Hash, Hasher};
+use std::io::Cursor;
+use std::path::PathBuf;
 use std::sync::{atomic, Arc, Mutex};
 use std::thread;
 use std::time::Duration;
+use tauri::image::Image;
 use tauri::{AppHandle, Emitter, State};
 use tauri_plugin_clipboard_manager::ClipboardExt;
 
+/// 文本历史上限
+const TEXT_HISTORY_LIMIT: usize = 50;
+/// 图片历史上限
+const IMAGE_HISTORY_LIMIT: usize = 20;
+/// 图片缩略图最大边长
+const THUMBNAIL_SIZE: u32 = 160;
+
+/// 剪贴板历史项类型
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ClipboardItemKind {
+    Text,
+    Image,
+}
+
+/// 剪贴板历史项
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ClipboardHistoryItem {
+    pub id: String,
+    pub kind: ClipboardItemKind,
+    /// 文本内容（仅文本项）
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub text: Option<String>,
+    /// 缩略图 data URL（仅图片项）
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub thumbnail: Option<String>,
+    /// 原图临时文件路径（仅图片项）
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub image_path: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub width: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub height: Option<u32>,
+    /// 复制时间（毫秒时间戳）
+    pub timestamp: i64,
+    /// 内容哈希，用于去重
+    #[serde(skip)]
+    hash: u64,
+}
+
+/// 带上限的剪贴板历史，文本与图片分开存放，最新的在队首
+#[derive(Default)]
+pub struct ClipboardHistory {
+    texts: VecDeque<ClipboardHistoryItem>,
+    images: VecDeque<ClipboardHistoryItem>,
+}
+
+impl ClipboardHistory {
+    /// 记录一项；相同内容已存在时移到队首。返回被挤出的旧项
+    fn push(&mut self, item: ClipboardHistoryItem) -> Vec<ClipboardHistoryItem> {
+        let (queue, limit) = match item.kind {
+            ClipboardItemKind::Text => (&mut self.texts, TEXT_HISTORY_LIMIT),
+            ClipboardItemKind::Image => (&mut self.images, IMAGE_HISTORY_LIMIT),
+        };
+
+        let mut evicted = Vec::new();
+        if let Some(pos) = queue.iter().position(|existing| existing.hash == item.hash) {
+            if let Some(old) = queue.remove(pos) {
+                evicted.push(old);
+            }
+        }
+        queue.push_front(item);
+        while queue.len() > limit {
+            if let Some(old) = queue.pop_back() {
+                evicted.push(old);
+            }
+        }
+        evicted
+    }
+
+    /// 最新的图片哈希，用于判断剪贴板图片是否变化
+    fn latest_image_hash(&self) -> Option<u64> {
+        self.images.front().map(|item| item.hash)
+    }
+
+    fn find(&self, id: &str) -> Option<&ClipboardHistoryItem> {
+        self.texts
+            .iter()
+            .chain(self.images.iter())
+            .find(|item| item.id == id)
+    }
+
+    /// 按时间倒序列出全部历史
+    fn list(&self) -> Vec<ClipboardHistoryItem> {
+        let mut items: Vec<ClipboardHistoryItem> = self
+            .texts
+            .iter()
+            .chain(self.images.iter())
+            .cloned()
+            .collect();
+        items.sort_by_key(|item| std::cmp::Reverse(item.timestamp));
+        items
+    }
+
+    fn clear(&mut self) -> Vec<ClipboardHistoryItem> {
+        self.texts.drain(..).chain(self.images.drain(..)).collect()
+    }
+}
+
 // 定义一个结构体来管理监听状态
 pub struct ClipboardMonitorState {
     pub should_run: Arc<atomic::AtomicBool>,
     pub last_content: Arc<Mutex<String>>,
+    pub history: Arc<Mutex<ClipboardHistory>>,
+    /// 是否跳过被密码管理器等标记为敏感的内容
+    pub skip_sensitive: Arc<atomic::AtomicBool>,
 }
 
 impl ClipboardMonitorState {
@@ -29,51 +140,525 @@ impl ClipboardMonitorState {
         Self {
             should_run: Arc::new(atomic::AtomicBool::new(false)),
             last_content: Arc::new(Mutex::new(String::new())),
+            history: Arc::new(Mutex::new(ClipboardHistory::default())),
+            skip_sensitive: Arc::new(atomic::AtomicBool::new(true)),
+        }
+    }
+}
+
+fn hash_bytes(bytes: &[u8]) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    bytes.hash(&mut hasher);
+    hasher.finish()
+}
+
+/// 图片历史的临时文件目录
+fn clipboard_temp_dir() -> PathBuf {
+    std::env::temp_dir().join("aiohub-clipboard")
+}
+
+/// 删除历史项关联的临时文件
+fn remove_item_files(items: &[ClipboardHistoryItem]) {
+    for path in items.iter().filter_map(|item| item.image_path.as_ref()) {
+        let _ = std::fs::remove_file(path);
+    }
+}
+
+/// 清理临时目录中不属于当前历史的图片文件
+///
+/// 历史只保存在内存中，上次运行（或异常退出）留下的文件都会在监听启动时被清掉
+fn remove_orphan_temp_files(history: &Mutex<ClipboardHistory>) {
+    let Ok(entries) = std::fs::read_dir(clipboard_temp_dir()) else {
+        return;
+    };
+    let kept: std::collections::HashSet<PathBuf> = match history.lock() {
+        Ok(history) => history
+            .images
+            .iter()
+            .filter_map(|item| item.image_path.as_ref().map(PathBuf::from))
+            .collect(),
+        Err(_) => return,
+    };
+    for entry in entries.flatten() {
+        let path = entry.path();
+        if path.is_file() && !kept.contains(&path) {
+            let _ = std::fs::remove_file(&path);
         }
     }
 }
 
+/// 剪贴板序列号，内容每次变化都会递增，用于在读取图片前低成本地判断是否有变化
+///
+/// 仅 Windows 提供；其他平台返回 None，只能每次读取图片后比较哈希
+#[cfg(target_os = "windows")]
+fn clipboard_sequence_number() -> Option<u32> {
+    use windows::Win32::System::DataExchange::GetClipboardSequenceNumber;
+
+    match unsafe { GetClipboardSequenceNumber() } {
+        0 => None,
+        sequence => Some(sequence),
+    }
+}
+
+#[cfg(not(target_os = "windows"))]
+fn clipboard_sequence_number() -> Option<u32> {
+    None
+}
+
+/// 当前剪贴板内容是否被标记为敏感（不应被记录）
+///
+/// Windows 上密码管理器会放置 `ExcludeClipboardContentFromMonitorProcessing`
+/// 或 `Clipboard Viewer Ignore` 格式；其他平台暂无法检测
+#[cfg(target_os = "windows")]
+fn is_sensitive_clipboard() -> bool {
+    use windows::core::HSTRING;
+    use windows::Win32::System::DataExchange::{
+        IsClipboardFormatAvailable, RegisterClipboardFormatW,
+    };
+
+    [
+        "ExcludeClipboardContentFromMonitorProcessing",
+        "Clipboard Viewer Ignore",
+    ]
+    .iter()
+    .any(|name| unsafe {
+        let format = RegisterClipboardFormatW(&HSTRING::from(*name));
+        format != 0 && IsClipboardFormatAvailable(format).is_ok()
+    })
+}
+
+#[cfg(not(target_os = "windows"))]
+fn is_sensitive_clipboard() -> bool {
+    false
+}
+
+fn new_text_item(text: String) -> ClipboardHistoryItem {
+    ClipboardHistoryItem {
+        id: uuid::Uuid::new_v4().to_string(),
+        kind: ClipboardItemKind::Text,
+        hash: hash_bytes(text.as_bytes()),
+        text: Some(text),
+        thumbnail: None,
+        image_path: None,
+        width: None,
+        height: None,
+        timestamp: chrono::Utc::now().timestamp_millis(),
+    }
+}
+
+/// 把剪贴板图片保存为临时 PNG 并生成缩略图
+fn new_image_item(
+    rgba: &[u8],
+    width: u32,
+    height: u32,
+    hash: u64,
+) -> Result<ClipboardHistoryItem, String> {
+    let image = image::RgbaImage::from_raw(width, height, rgba.to_vec())
+        .ok_or_else(|| "剪贴板图片数据无效".to_string())?;
+
+    let id = uuid::Uuid::new_v4().to_string();
+    let dir = clipboard_temp_dir();
+    std::fs::create_dir_all(&dir).map_err(|e| format!("创建临时目录失败: {}", e))?;
+    let image_path = dir.join(format!("{}.png", id));
+    image
+        .save_with_format(&image_path, image::ImageFormat::Png)
+        .map_err(|e| {
+            let _ = std::fs::remove_file(&image_path);
+            format!("保存剪贴板图片失败: {}", e)
+        })?;
+
+    let thumbnail =
+        image::DynamicImage::ImageRgba8(image).thumbnail(THUMBNAIL_SIZE, THUMBNAIL_SIZE);
+    let mut buffer = Cursor::new(Vec::new());
+    thumbnail
+        .write_to(&mut buffer, image::ImageFormat::Png)
+        .map_err(|e| {
+            let _ = std::fs::remove_file(&image_path);
+            format!("生成缩略图失败: {}", e)
+        })?;
+
+    Ok(ClipboardHistoryItem {
+        id,
+        kind: ClipboardItemKind::Image,
+        text: None,
+        thumbnail: Some(format!(
+            "data:image/png;base64,{}",
+            general_purpose::STANDARD.encode(buffer.into_inner())
+        )),
+        image_path: Some(image_path.to_string_lossy().to_string()),
+        width: Some(width),
+        height: Some(height),
+        timestamp: chrono::Utc::now().timestamp_millis(),
+        hash,
+    })
+}
+
+/// 记录到历史并清理被挤出项的临时文件
+fn record_history(history: &Mutex<ClipboardHistory>, item: ClipboardHistoryItem) {
+    if let Ok(mut history) = history.lock() {
+        let evicted = history.push(item);
+        remove_item_files(&evicted);
+    }
+}
+
 // Tauri 命令：启动剪贴板监听
+// 监听期间会把文本与图片记录到历史；`skip_sensitive` 默认为 true
 #[tauri::command]
-pub fn start_clipboard_monitor(app_handle: AppHandle, state: State<ClipboardMonitorState>) {
+pub fn start_clipboard_monitor(
+    app_handle: AppHandle,
+    state: State<ClipboardMonitorState>,
+    skip_sensitive: Option<bool>,
+) {
     let should_run = state.should_run.clone();
     let last_content = state.last_content.clone();
-    should_run.store(true, atomic::Ordering::SeqCst);
+    let history = state.history.clone();
+    let skip_sensitive_flag = state.skip_sensitive.clone();
+    if let Some(skip) = skip_sensitive {
+        skip_sensitive_flag.store(skip, atomic::Ordering::SeqCst);
+    }
+    // 已在运行时只更新设置
+    if should_run.swap(true, atomic::Ordering::SeqCst) {
+        return;
+    }
 
     thread::spawn(move || {
-        let mut last_clipboard_text = last_content.lock().unwrap().clone();
+        remove_orphan_temp_files(&history);
+        // 上次检查图片时的剪贴板序列号，未变化时不再读取整张图片
+        let mut last_image_sequence: Option<u32> = None;
         while should_run.load(atomic::Ordering::SeqCst) {
             let clipboard_manager = app_handle.clipboard();
-            if let Ok(current_content) = clipboard_manager.read_text() {
-                if !current_content.is_empty() && current_content != last_clipboard_text {
-                    last_clipboard_text = current_content.clone();
+            let sensitive =
+                skip_sensitive_flag.load(atomic::Ordering::SeqCst) && is_sensitive_clipboard();
+            // 与共享的 last_content 比较，restore_clipboard_item 写回的内容不会被重复记录
+            let last_clipboard_text = last_content.lock().unwrap().clone();
+            match clipboard_manager.read_text() {
+                // 文本未变化
+                Ok(current_content)
+                    if !current_content.is_empty() && current_content == last_clipboard_text => {}
+                Ok(current_content) if !current_content.is_empty() => {
                     *last_content.lock().unwrap() = current_content.clone();
+                    if sensitive {
+                        log::debug!("剪贴板内容被标记为敏感，跳过记录");
+                    } else {
+                        record_history(&history, new_text_item(current_content.clone()));
+                    }
                     // 发送事件到前端
                     app_handle
                         .emit("clipboard-changed", current_content)
                         .unwrap();
                 }
+                _ if !sensitive => {
+                    // 没有文本时检查图片；能拿到序列号时先比较，避免每轮都读取并哈希整张图片
+                    let sequence = clipboard_sequence_number();
+                    let unchanged = sequence.is_some() && sequence == last_image_sequence;
+                    last_image_sequence = sequence;
+                    let image = if unchanged {
+                        None
+                    } else {
+                        clipboard_manager.read_image().ok()
+                    };
+                    if let Some(image) = image {
+                        let hash = hash_bytes(image.rgba());
+                        let latest = history.lock().ok().and_then(|h| h.latest_image_hash());
+                        if latest != Some(hash) {
+                            match new_image_item(image.rgba(), image.width(), image.height(), hash)
+                            {
+                                Ok(item) => record_history(&history, item),
+                                Err(e) => log::warn!("记录剪贴板图片失败: {}", e),
+                            }
+                        }
+                    }
+                }
+                _ => {}
             }
             thread::sleep(Duration::from_millis(500)); // 每500毫秒检查一次
         }
     });
 }
 
+// Tauri 命令：获取剪贴板历史（按时间倒序）
+#[tauri::command]
+pub fn get_clipboard_history(
+    state: State<ClipboardMonitorState>,
+) -> Result<Vec<ClipboardHistoryItem>, String> {
+    let history = state
+        .history
+        .lock()
+        .map_err(|e| format!("读取剪贴板历史失败: {}", e))?;
+    Ok(history.list())
+}
+
+// Tauri 命令：清空剪贴板历史，并删除图片临时文件
+#[tauri::command]
+pub fn clear_clipboard_history(state: State<ClipboardMonitorState>) -> Result<(), String> {
+    let removed = state
+        .history
+        .lock()
+        .map_err(|e| format!("清空剪贴板历史失败: {}", e))?
+        .clear();
+    remove_item_files(&removed);
+    Ok(())
+}
+
+// Tauri 命令：把历史项重新写回剪贴板
+#[tauri::command]
+pub fn restore_clipboard_item(
+    app_handle: AppHandle,
+    state: State<ClipboardMonitorState>,
+    id: String,
+) -> Result<(), String> {
+    let item = state
+        .history
+        .lock()
+        .map_err(|e| format!("读取剪贴板历史失败: {}", e))?
+        .find(&id)
+        .cloned()
+        .ok_or_else(|| format!("剪贴板历史项不存在: {}", id))?;
+
+    let clipboard = app_handle.clipboard();
+    match item.kind {
+        ClipboardItemKind::Text => {
+            let text = item.text.clone().unwrap_or_default();
+            clipboard
+                .write_text(text.clone())
+                .map_err(|e| format!("写入剪贴板失败: {}", e))?;
+            // 提前同步，避免监听线程把它当作新内容
+            if let Ok(mut last) = state.last_content.lock() {
+                *last = text;
+            }
+        }
+        ClipboardItemKind::Image => {
+            let path = item
+                .image_path
+                .as_deref()
+                .ok_or_else(|| "图片历史项缺少文件".to_string())?;
+            let image = image::open(path)
+                .map_err(|e| format!("读取历史图片失败: {}", e))?
+                .to_rgba8();
+            let (width, height) = image.dimensions();
+            clipboard
+                .write_image(&Image::new_owned(image.into_raw(), width, height))
+                .map_err(|e| format!("写入剪贴板失败: {}", e))?;
+        }
+    }
+
+    // 恢复的项移到队首
+    let mut restored = item;
+    restored.timestamp = chrono::Utc::now().timestamp_millis();
+    if let Ok(mut history) = state.history.lock() {
+        let evicted = history.push(restored);
+        // 被替换的是同一项，保留其图片文件
+        let evicted: Vec<_> = evicted.into_iter().filter(|old| old.id != id).collect();
+        remove_item_files(&evicted);
+    }
+    Ok(())
+}
+
 // Tauri 命令：停止剪贴板监听
 #[tauri::command]
 pub fn stop_clipboard_monitor(state: State<ClipboardMonitorState>) {
     state.should_run.store(false, atomic::Ordering::SeqCst);
 }
 
+/// 剪贴板内容类型
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ClipboardContentKind {
+    Empty,
+    Text,
+    Image,
+    Files,
+    Html,
+    Rtf,
+}
+
+/// 剪贴板内容的结构化描述
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ClipboardContentInfo {
+    pub kind: ClipboardContentKind,
+    /// 文本的细分格式：json / base64 / text
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub text_format: Option<String>,
+    /// 纯文本内容；HTML / RTF 时为纯文本回退
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub text: Option<String>,
+    /// 文本长度（字符数）
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub text_length: Option<usize>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub html: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub width: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub height: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub files: Option<Vec<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub file_count: Option<usize>,
+}
+
+impl ClipboardContentInfo {
+    fn new(kind: ClipboardContentKind) -> Self {
+        Self {
+            kind,
+            text_format: None,
+            text: None,
+            text_length: None,
+            html: None,
+            width: None,
+            height: None,
+            files: None,
+            file_count: None,
+        }
+    }
+
+    fn with_text(mut self, text: String) -> Self {
+        self.text_format = Some(classify_text(&text).to_string());
+        self.text_length = Some(text.chars().count());
+        self.text = Some(text);
+        self
+    }
+}
+
+/// 粗略判断文本的格式
+fn classify_text(content: &str) -> &'static str {
+    let content = content.trim();
+    if content.starts_with('{') && content.ends_with('}') {
+        "json"
+    } else if content.len() > 100 && content.ends_with("==") && content.contains('/') {
+        "base64"
+    } else {
+        "text"
+    }
+}
+
+/// 从 HTML 中提取纯文本（去标签、解码常见实体、合并空白）
+fn html_to_plain_text(html: &str) -> String {
+    lazy_static::lazy_static! {
+        static ref SKIP_RE: regex::Regex =
+            regex::Regex::new(r"(?is)<(script|style|head)[^>]*>.*?</(script|style|head)>").unwrap();
+        static ref BREAK_RE: regex::Regex =
+            regex::Regex::new(r"(?i)<br\s*/?>|</(p|div|li|tr|h[1-6])>").unwrap();
+        static ref TAG_RE: regex::Regex = regex::Regex::new(r"<[^>]*>").unwrap();
+    }
+
+    let text = SKIP_RE.replace_all(html, "");
+    let text = BREAK_RE.replace_all(&text, "\n");
+    let text = TAG_RE.replace_all(&text, "");
+    let text = text
+        .replace("&nbsp;", " ")
+        .replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&#39;", "'")
+        .replace("&amp;", "&");
+
+    text.lines()
+        .map(|line| line.split_whitespace().collect::<Vec<_>>().join(" "))
+        .filter(|line| !line.is_empty())
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// 剪贴板中是否有 RTF 内容（仅 Windows 可检测）
+#[cfg(target_os = "windows")]
+fn clipboard_has_rtf() -> bool {
+    use windows::core::HSTRING;
+    use windows::Win32::System::DataExchange::{
+        IsClipboardFormatAvailable, RegisterClipboardFormatW,
+    };
+
+    unsafe {
+        let format = RegisterClipboardFormatW(&HSTRING::from("Rich Text Format"));
+        format != 0 && IsClipboardFormatAvailable(format).is_ok()
+    }
+}
+
+#[cfg(not(target_os = "windows"))]
+fn clipboard_has_rtf() -> bool {
+    false
+}
+
 // Tauri 命令：获取剪贴板内容类型
+// 按 文件列表 > 图片 > HTML > RTF > 文本 的优先级识别当前剪贴板内容
 #[tauri::command]
-pub fn get_clipboard_content_type(state: State<ClipboardMonitorState>) -> String {
-    let content = state.last_content.lock().unwrap();
-    if content.starts_with("{") && content.ends_with("}") {
-        "json".to_string()
-    } else if content.len() > 100 && content.ends_with("==") && content.contains("/") {
-        "base64".to_string()
-    } else {
-        "text".to_string()
+pub fn get_clipboard_content_type() -> Result<ClipboardContentInfo, String> {
+    let mut clipboard = arboard::Clipboard::new().map_err(|e| format!("打开剪贴板失败: {}", e))?;
+
+    if let Ok(files) = clipboard.get().file_list() {
+        if !files.is_empty() {
+            let mut info = ClipboardContentInfo::new(ClipboardContentKind::Files);
+            info.file_count = Some(files.len());
+            info.files = Some(
+                files
+                    .iter()
+                    .map(|path| path.to_string_lossy().to_string())
+                    .collect(),
+            );
+            return Ok(info);
+        }
+    }
+
+    if let Ok(image) = clipboard.get_image() {
+        let mut info = ClipboardContentInfo::new(ClipboardContentKind::Image);
+        info.width = Some(image.width as u32);
+        info.height = Some(image.height as u32);
+        return Ok(info);
+    }
+
+    let text = clipboard.get_text().ok().filter(|text| !text.is_empty());
+
+    if let Ok(html) = clipboard.get().html() {
+        if !html.trim().is_empty() {
+            let fallback = text.unwrap_or_else(|| html_to_plain_text(&html));
+            let mut info =
+                ClipboardContentInfo::new(ClipboardContentKind::Html).with_text(fallback);
+            info.html = Some(html);
+            return Ok(info);
+        }
+    }
+
+    match text {
+        Some(text) if clipboard_has_rtf() => {
+            Ok(ClipboardContentInfo::new(ClipboardContentKind::Rtf).with_text(text))
+        }
+        Some(text) => Ok(ClipboardContentInfo::new(ClipboardContentKind::Text).with_text(text)),
+        None => Ok(ClipboardContentInfo::new(ClipboardContentKind::Empty)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_clipboard_history_ring_buffer() {
+        let mut history = ClipboardHistory::default();
+        for i in 0..TEXT_HISTORY_LIMIT + 5 {
+            history.push(new_text_item(format!("text-{}", i)));
+        }
+        assert_eq!(history.texts.len(), TEXT_HISTORY_LIMIT);
+        assert_eq!(history.texts[0].text.as_deref(), Some("text-54"));
+
+        // 重复内容移到队首而不是新增
+        let evicted = history.push(new_text_item("text-10".to_string()));
+        assert_eq!(evicted.len(), 1);
+        assert_eq!(history.texts.len(), TEXT_HISTORY_LIMIT);
+        assert_eq!(history.texts[0].text.as_deref(), Some("text-10"));
+
+        let id = history.texts[0].id.clone();
+        assert!(history.find(&id).is_some());
+        assert_eq!(history.clear().len(), TEXT_HISTORY_LIMIT);
+        assert!(history.list().is_empty());
+    }
+
+    #[test]
+    fn test_clipboard_text_helpers() {
+        assert_eq!(classify_text(" {\"a\": 1} "), "json");
+        assert_eq!(classify_text("hello"), "text");
+
+        let html = "<html><head><style>p{}</style></head><body><p>Hello&nbsp;<b>world</b></p><div>a &amp; b</div></body></html>";
+        assert_eq!(html_to_plain_text(html), "Hello world\na & b");
     }
 }
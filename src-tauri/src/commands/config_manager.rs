@@ -14,7 +14,7 @@
 
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
-use std::collections::HashMap;
+use std::collections::BTreeMap;
 use std::fs;
 use std::io::{Read, Write};
 use std::path::{Path, PathBuf};
@@ -80,55 +80,138 @@ fn should_exclude_file(file_name: &str) -> bool {
     file_name.ends_with(".log") || file_name == ".DS_Store" || file_name == "Thumbs.db"
 }
 
-/// 获取配置文件列表
-#[tauri::command]
-pub async fn list_config_files(app: AppHandle) -> Result<HashMap<String, Vec<String>>, String> {
-    let app_data_dir = get_app_data_dir(&app)?;
-    let mut result: HashMap<String, Vec<String>> = HashMap::new();
-
-    // 扫描应用数据目录下的所有子目录
-    if let Ok(entries) = fs::read_dir(&app_data_dir) {
-        for entry in entries.flatten() {
-            if let Ok(file_type) = entry.file_type() {
-                if file_type.is_dir() {
-                    let dir_name = entry.file_name();
-                    let dir_name_str = dir_name.to_string_lossy();
-
-                    // 跳过排除的目录
-                    if should_exclude_dir(&dir_name_str) {
-                        continue;
-                    }
+/// 配置分类规则：(相对路径前缀, 分类)，更具体的前缀排在前面
+const CONFIG_CATEGORY_RULES: &[(&str, &str)] = &[
+    ("llm-chat/agents", "agents"),
+    ("agent-manager", "agents"),
+    ("app-settings", "app"),
+    ("llm-chat", "chat"),
+    ("llm-service", "llm"),
+    ("model-metadata", "llm"),
+    ("tokenizer-registry", "llm"),
+    ("plugins-config", "plugins"),
+    ("plugins-data", "plugins"),
+    ("plugin-manager", "plugins"),
+    ("plugins", "plugins"),
+];
+
+/// 配置分组（导出/导入时的最小选择单位）
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ConfigGroup {
+    /// 分组 key，通常是模块目录名，如 `llm-chat`、`llm-chat/agents`
+    pub key: String,
+    /// 分类标签：app / agents / chat / llm / plugins / tools
+    pub category: String,
+    pub file_count: usize,
+    pub total_size: u64,
+}
 
-                    let module_dir = entry.path();
-                    let mut files = Vec::new();
-
-                    if let Ok(file_entries) = fs::read_dir(&module_dir) {
-                        for file_entry in file_entries.flatten() {
-                            if let Ok(file_type) = file_entry.file_type() {
-                                if file_type.is_file() {
-                                    let file_name = file_entry.file_name();
-                                    let file_name_str = file_name.to_string_lossy();
-
-                                    // 排除不需要的文件
-                                    if !should_exclude_file(&file_name_str)
-                                        && file_name_str.ends_with(".json")
-                                    {
-                                        files.push(file_name_str.to_string());
-                                    }
-                                }
-                            }
-                        }
-                    }
+/// 判断相对路径是否位于某前缀（目录）之下
+fn path_has_prefix(path: &str, prefix: &str) -> bool {
+    path == prefix
+        || path
+            .strip_prefix(prefix)
+            .is_some_and(|rest| rest.starts_with('/'))
+}
 
-                    if !files.is_empty() {
-                        result.insert(dir_name_str.to_string(), files);
-                    }
-                }
-            }
+/// 根据相对路径得出 (分组 key, 分类)
+fn classify_config_path(relative_path: &str) -> (String, &'static str) {
+    for (prefix, category) in CONFIG_CATEGORY_RULES {
+        if path_has_prefix(relative_path, prefix) {
+            return (prefix.to_string(), category);
         }
     }
+    match relative_path.split_once('/') {
+        Some((module, _)) => (module.to_string(), "tools"),
+        None => (relative_path.to_string(), "app"),
+    }
+}
 
-    Ok(result)
+/// 判断配置文件是否被选中；`include` 中的每一项可以是分类或分组 key，为 None 表示全选
+fn is_config_selected(relative_path: &str, include: Option<&[String]>) -> bool {
+    let Some(include) = include else {
+        return true;
+    };
+    let (key, category) = classify_config_path(relative_path);
+    include.iter().any(|item| item == category || *item == key)
+}
+
+/// 将 (相对路径, 大小) 列表按分组汇总
+fn group_config_files<'a>(files: impl IntoIterator<Item = (&'a str, u64)>) -> Vec<ConfigGroup> {
+    let mut groups: BTreeMap<String, ConfigGroup> = BTreeMap::new();
+    for (relative_path, size) in files {
+        let (key, category) = classify_config_path(relative_path);
+        let group = groups.entry(key.clone()).or_insert_with(|| ConfigGroup {
+            key,
+            category: category.to_string(),
+            file_count: 0,
+            total_size: 0,
+        });
+        group.file_count += 1;
+        group.total_size += size;
+    }
+    groups.into_values().collect()
+}
+
+/// 收集应用数据目录下所有可导出的配置文件，返回 (绝对路径, 相对路径)
+fn collect_config_files(app_data_dir: &Path) -> Result<Vec<(PathBuf, String)>, String> {
+    let mut files = Vec::new();
+
+    // 遍历应用数据目录
+    for entry in WalkDir::new(app_data_dir).into_iter().filter_entry(|e| {
+        // 过滤掉不需要的目录
+        let file_name = e.file_name().to_string_lossy();
+        !should_exclude_dir(&file_name)
+    }) {
+        let entry = entry.map_err(|e| format!("遍历目录失败: {}", e))?;
+        let path = entry.path();
+
+        // 只处理文件
+        if !path.is_file() {
+            continue;
+        }
+
+        let file_name = path
+            .file_name()
+            .and_then(|n| n.to_str())
+            .ok_or_else(|| "无效的文件名".to_string())?;
+
+        // 跳过不需要的文件
+        if should_exclude_file(file_name) {
+            continue;
+        }
+
+        // 计算相对路径
+        let relative_path = path
+            .strip_prefix(app_data_dir)
+            .map_err(|e| format!("计算相对路径失败: {}", e))?;
+
+        let relative_str = relative_path.to_string_lossy().replace('\\', "/"); // 统一使用 Unix 风格路径
+        files.push((path.to_path_buf(), relative_str));
+    }
+
+    Ok(files)
+}
+
+/// 获取配置清单，按模块分组并带分类标签
+#[tauri::command]
+pub async fn list_config_files(app: AppHandle) -> Result<Vec<ConfigGroup>, String> {
+    let app_data_dir = get_app_data_dir(&app)?;
+    if !app_data_dir.exists() {
+        return Ok(Vec::new());
+    }
+
+    let files = collect_config_files(&app_data_dir)?;
+    let sized: Vec<(&str, u64)> = files
+        .iter()
+        .map(|(path, relative)| {
+            let size = fs::metadata(path).map(|m| m.len()).unwrap_or(0);
+            (relative.as_str(), size)
+        })
+        .collect();
+
+    Ok(group_config_files(sized))
 }
 
 /// 清单文件结构
@@ -140,11 +223,117 @@ struct ZipManifest {
     app_version: String,
     /// 导出的文件数量
     file_count: usize,
+    /// 选择性导出时包含的分类/分组，全量导出时为空
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    included: Option<Vec<String>>,
+}
+
+/// 当前配置结构（schema）版本，配置文件字段发生不兼容变化时递增，并注册对应的迁移
+const CONFIG_SCHEMA_VERSION: u32 = 1;
+
+/// 仍可迁移的最低 schema 版本，更早的导出会被拒绝
+const MIN_SUPPORTED_SCHEMA_VERSION: u32 = 0;
+
+/// 压缩包内的元数据文件，不作为配置导入
+const ZIP_META_FILES: &[&str] = &["manifest.json", "export_meta.json"];
+
+/// 导出元数据（export_meta.json）
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ExportMeta {
+    app_version: String,
+    schema_version: u32,
+    exported_at: String,
 }
 
-/// 将所有配置导出为 ZIP 压缩包
+/// 单步配置迁移：把 `from_version` 的配置升级到 `from_version + 1`
+struct ConfigMigration {
+    from_version: u32,
+    /// 参数为配置文件的相对路径与其 JSON 内容
+    migrate: fn(&str, &mut Value),
+}
+
+/// 已注册的迁移，按版本逐步执行
+const CONFIG_MIGRATIONS: &[ConfigMigration] = &[ConfigMigration {
+    from_version: 0,
+    migrate: migrate_v0_to_v1,
+}];
+
+/// v0 为引入 export_meta.json 之前的导出，字段结构与 v1 相同，无需改动
+fn migrate_v0_to_v1(_relative_path: &str, _value: &mut Value) {}
+
+/// 检查导出的 schema 版本能否导入
+fn check_schema_compatibility(schema_version: u32) -> Result<(), String> {
+    if schema_version > CONFIG_SCHEMA_VERSION {
+        return Err(format!(
+            "该配置由更新版本的应用导出（配置版本 v{}，当前支持 v{}），请升级应用后再导入",
+            schema_version, CONFIG_SCHEMA_VERSION
+        ));
+    }
+    if !(MIN_SUPPORTED_SCHEMA_VERSION..=CONFIG_SCHEMA_VERSION).contains(&schema_version) {
+        return Err(format!(
+            "该配置版本过旧（v{}），已无法迁移到当前版本（最低支持 v{}）",
+            schema_version, MIN_SUPPORTED_SCHEMA_VERSION
+        ));
+    }
+    Ok(())
+}
+
+/// 依次执行 `from..to` 之间的迁移
+fn run_config_migrations(
+    migrations: &[ConfigMigration],
+    relative_path: &str,
+    value: &mut Value,
+    from: u32,
+    to: u32,
+) -> Result<(), String> {
+    for version in from..to {
+        let migration = migrations
+            .iter()
+            .find(|m| m.from_version == version)
+            .ok_or_else(|| format!("缺少配置迁移: v{} → v{}", version, version + 1))?;
+        (migration.migrate)(relative_path, value);
+    }
+    Ok(())
+}
+
+/// 读取导出元数据，旧版本导出没有该文件时视为 v0
+fn read_export_meta<R: Read + std::io::Seek>(
+    archive: &mut ZipArchive<R>,
+) -> Result<Option<ExportMeta>, String> {
+    let mut file = match archive.by_name("export_meta.json") {
+        Ok(file) => file,
+        Err(_) => return Ok(None),
+    };
+    let mut content = String::new();
+    file.read_to_string(&mut content)
+        .map_err(|e| format!("读取导出元数据失败: {}", e))?;
+    serde_json::from_str(&content)
+        .map(Some)
+        .map_err(|e| format!("解析导出元数据失败: {}", e))
+}
+
+/// 从 ZIP 中读取清单文件
+fn read_zip_manifest<R: Read + std::io::Seek>(
+    archive: &mut ZipArchive<R>,
+) -> Result<ZipManifest, String> {
+    let mut file = archive
+        .by_name("manifest.json")
+        .map_err(|_| "ZIP 文件中未找到清单文件".to_string())?;
+    let mut content = String::new();
+    file.read_to_string(&mut content)
+        .map_err(|e| format!("读取清单文件失败: {}", e))?;
+    serde_json::from_str(&content).map_err(|e| format!("解析清单文件失败: {}", e))
+}
+
+/// 将配置导出为 ZIP 压缩包
+///
+/// `include` 为要导出的分类或分组 key（见 `list_config_files`），为空时导出全部
 #[tauri::command]
-pub async fn export_all_configs_to_zip(app: AppHandle) -> Result<Vec<u8>, String> {
+pub async fn export_all_configs_to_zip(
+    app: AppHandle,
+    include: Option<Vec<String>>,
+) -> Result<Vec<u8>, String> {
     let app_data_dir = get_app_data_dir(&app)?;
 
     // 在内存中创建 ZIP
@@ -155,47 +344,27 @@ pub async fn export_all_configs_to_zip(app: AppHandle) -> Result<Vec<u8>, String
         .compression_level(Some(6));
 
     let mut file_count = 0;
+    let include = include.filter(|items| !items.is_empty());
 
-    // 遍历应用数据目录
-    for entry in WalkDir::new(&app_data_dir).into_iter().filter_entry(|e| {
-        // 过滤掉不需要的目录
-        let file_name = e.file_name().to_string_lossy();
-        !should_exclude_dir(&file_name)
-    }) {
-        let entry = entry.map_err(|e| format!("遍历目录失败: {}", e))?;
-        let path = entry.path();
-
-        // 只处理文件
-        if path.is_file() {
-            let file_name = path
-                .file_name()
-                .and_then(|n| n.to_str())
-                .ok_or_else(|| "无效的文件名".to_string())?;
-
-            // 跳过不需要的文件
-            if should_exclude_file(file_name) {
-                continue;
-            }
-
-            // 计算相对路径
-            let relative_path = path
-                .strip_prefix(&app_data_dir)
-                .map_err(|e| format!("计算相对路径失败: {}", e))?;
+    for (path, zip_path_str) in collect_config_files(&app_data_dir)? {
+        if !is_config_selected(&zip_path_str, include.as_deref()) {
+            continue;
+        }
 
-            let zip_path_str = relative_path.to_string_lossy().replace('\\', "/"); // 统一使用 Unix 风格路径
+        // 添加文件到 ZIP
+        zip.start_file(&zip_path_str, options)
+            .map_err(|e| format!("添加文件到 ZIP 失败: {}", e))?;
 
-            // 添加文件到 ZIP
-            zip.start_file(&zip_path_str, options)
-                .map_err(|e| format!("添加文件到 ZIP 失败: {}", e))?;
+        let mut file =
+            fs::File::open(&path).map_err(|e| format!("打开文件失败 {}: {}", path.display(), e))?;
 
-            let mut file = fs::File::open(path)
-                .map_err(|e| format!("打开文件失败 {}: {}", path.display(), e))?;
+        std::io::copy(&mut file, &mut zip).map_err(|e| format!("写入文件到 ZIP 失败: {}", e))?;
 
-            std::io::copy(&mut file, &mut zip)
-                .map_err(|e| format!("写入文件到 ZIP 失败: {}", e))?;
+        file_count += 1;
+    }
 
-            file_count += 1;
-        }
+    if file_count == 0 && include.is_some() {
+        return Err("所选分类下没有可导出的配置".to_string());
     }
 
     // 获取时区设置
@@ -230,6 +399,7 @@ pub async fn export_all_configs_to_zip(app: AppHandle) -> Result<Vec<u8>, String
         timestamp,
         app_version: app.package_info().version.to_string(),
         file_count,
+        included: include,
     };
 
     let manifest_json =
@@ -241,6 +411,21 @@ pub async fn export_all_configs_to_zip(app: AppHandle) -> Result<Vec<u8>, String
     zip.write_all(manifest_json.as_bytes())
         .map_err(|e| format!("写入清单文件失败: {}", e))?;
 
+    // 写入导出元数据，供导入时做版本迁移
+    let export_meta = ExportMeta {
+        app_version: manifest.app_version.clone(),
+        schema_version: CONFIG_SCHEMA_VERSION,
+        exported_at: manifest.timestamp.clone(),
+    };
+    let export_meta_json = serde_json::to_string_pretty(&export_meta)
+        .map_err(|e| format!("序列化导出元数据失败: {}", e))?;
+
+    zip.start_file("export_meta.json", options)
+        .map_err(|e| format!("添加导出元数据失败: {}", e))?;
+
+    zip.write_all(export_meta_json.as_bytes())
+        .map_err(|e| format!("写入导出元数据失败: {}", e))?;
+
     // 完成 ZIP 文件
     zip.finish()
         .map_err(|e| format!("完成 ZIP 文件失败: {}", e))?;
@@ -272,14 +457,25 @@ pub fn get_proxy_settings(app: &AppHandle) -> ProxySettings {
     }
 }
 
-/// 从 ZIP 压缩包导入配置
-#[tauri::command]
-pub async fn import_all_configs_from_zip(
-    app: AppHandle,
-    zip_file_path: String,
-    merge: bool,
-) -> Result<String, String> {
-    let zip_path = Path::new(&zip_file_path);
+/// 配置导入预览
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ConfigImportPreview {
+    pub timestamp: String,
+    pub app_version: String,
+    /// 导出时的配置 schema 版本
+    pub schema_version: u32,
+    /// 是否需要迁移到当前版本
+    pub needs_migration: bool,
+    /// 压缩包内（经过筛选后）的配置分组
+    pub groups: Vec<ConfigGroup>,
+    /// 本地已存在、导入时会被覆盖或合并的文件
+    pub conflicts: Vec<String>,
+}
+
+/// 打开配置压缩包
+fn open_config_zip(zip_file_path: &str) -> Result<ZipArchive<fs::File>, String> {
+    let zip_path = Path::new(zip_file_path);
 
     if !zip_path.exists() {
         return Err("ZIP 文件不存在".to_string());
@@ -287,28 +483,79 @@ pub async fn import_all_configs_from_zip(
 
     let file = fs::File::open(zip_path).map_err(|e| format!("打开 ZIP 文件失败: {}", e))?;
 
-    let mut archive = ZipArchive::new(file).map_err(|e| format!("解析 ZIP 文件失败: {}", e))?;
+    ZipArchive::new(file).map_err(|e| format!("解析 ZIP 文件失败: {}", e))
+}
 
-    // 首先读取清单文件进行校验
-    let mut manifest: Option<ZipManifest> = None;
+/// 预览配置导入：列出压缩包内的分组，以及本地已存在会被覆盖的文件，供用户确认
+#[tauri::command]
+pub async fn preview_config_import(
+    app: AppHandle,
+    zip_file_path: String,
+    include: Option<Vec<String>>,
+) -> Result<ConfigImportPreview, String> {
+    let mut archive = open_config_zip(&zip_file_path)?;
+    let manifest = read_zip_manifest(&mut archive)?;
+    let schema_version = read_export_meta(&mut archive)?.map_or(0, |meta| meta.schema_version);
+    check_schema_compatibility(schema_version)?;
+    let app_data_dir = get_app_data_dir(&app)?;
+    let include = include.filter(|items| !items.is_empty());
+
+    let mut entries: Vec<(String, u64)> = Vec::new();
+    let mut conflicts = Vec::new();
     for i in 0..archive.len() {
-        let mut file = archive
+        let file = archive
             .by_index(i)
             .map_err(|e| format!("读取 ZIP 条目失败: {}", e))?;
-
-        if file.name() == "manifest.json" {
-            let mut content = String::new();
-            file.read_to_string(&mut content)
-                .map_err(|e| format!("读取清单文件失败: {}", e))?;
-
-            manifest = Some(
-                serde_json::from_str(&content).map_err(|e| format!("解析清单文件失败: {}", e))?,
-            );
-            break;
+        let file_name = file.name().to_string();
+        if ZIP_META_FILES.contains(&file_name.as_str())
+            || file.is_dir()
+            || !is_config_selected(&file_name, include.as_deref())
+        {
+            continue;
         }
+        if app_data_dir.join(&file_name).exists() {
+            conflicts.push(file_name.clone());
+        }
+        entries.push((file_name, file.size()));
     }
 
-    let manifest = manifest.ok_or_else(|| "ZIP 文件中未找到清单文件".to_string())?;
+    Ok(ConfigImportPreview {
+        timestamp: manifest.timestamp,
+        app_version: manifest.app_version,
+        schema_version,
+        needs_migration: schema_version < CONFIG_SCHEMA_VERSION,
+        groups: group_config_files(entries.iter().map(|(name, size)| (name.as_str(), *size))),
+        conflicts,
+    })
+}
+
+/// 从 ZIP 压缩包导入配置
+///
+/// `include` 为要导入的分类或分组 key，为空时导入全部；导入前可先调用
+/// `preview_config_import` 获取会被覆盖的文件列表。
+/// 旧版本导出的 JSON 配置会先逐版本迁移到当前 schema，无法迁移时拒绝导入
+#[tauri::command]
+pub async fn import_all_configs_from_zip(
+    app: AppHandle,
+    zip_file_path: String,
+    merge: bool,
+    include: Option<Vec<String>>,
+) -> Result<String, String> {
+    let mut archive = open_config_zip(&zip_file_path)?;
+
+    // 首先读取清单文件进行校验
+    let manifest = read_zip_manifest(&mut archive)?;
+    let schema_version = read_export_meta(&mut archive)?.map_or(0, |meta| meta.schema_version);
+    check_schema_compatibility(schema_version)?;
+    let needs_migration = schema_version < CONFIG_SCHEMA_VERSION;
+    if needs_migration {
+        log::info!(
+            "导入的配置版本为 v{}，将迁移到 v{}",
+            schema_version,
+            CONFIG_SCHEMA_VERSION
+        );
+    }
+    let include = include.filter(|items| !items.is_empty());
 
     let app_data_dir = get_app_data_dir(&app)?;
     let mut imported_count = 0;
@@ -323,8 +570,11 @@ pub async fn import_all_configs_from_zip(
 
         let file_name = file.name().to_string();
 
-        // 跳过清单文件和目录
-        if file_name == "manifest.json" || file.is_dir() {
+        // 跳过元数据文件、目录和未选中的配置
+        if ZIP_META_FILES.contains(&file_name.as_str())
+            || file.is_dir()
+            || !is_config_selected(&file_name, include.as_deref())
+        {
             continue;
         }
 
@@ -336,6 +586,24 @@ pub async fn import_all_configs_from_zip(
         file.read_to_end(&mut content)
             .map_err(|e| format!("读取文件内容失败 {}: {}", file_name, e))?;
 
+        // 旧版本配置先迁移到当前结构
+        if needs_migration && file_name.ends_with(".json") {
+            match serde_json::from_slice::<Value>(&content) {
+                Ok(mut value) => {
+                    run_config_migrations(
+                        CONFIG_MIGRATIONS,
+                        &file_name,
+                        &mut value,
+                        schema_version,
+                        CONFIG_SCHEMA_VERSION,
+                    )?;
+                    content = serde_json::to_vec_pretty(&value)
+                        .map_err(|e| format!("序列化迁移后的配置失败 {}: {}", file_name, e))?;
+                }
+                Err(e) => log::warn!("配置 {} 不是有效的 JSON，跳过迁移: {}", file_name, e),
+            }
+        }
+
         // 根据 merge 参数决定是覆盖还是合并
         let should_write = if merge && target_path.exists() && file_name.ends_with(".json") {
             // 合并模式：对于 JSON 文件，尝试合并
@@ -383,16 +651,28 @@ pub async fn import_all_configs_from_zip(
     }
 
     // 构建结果消息
+    let migration_note = if needs_migration {
+        format!(
+            "\n配置已从 v{} 迁移到 v{}",
+            schema_version, CONFIG_SCHEMA_VERSION
+        )
+    } else {
+        String::new()
+    };
     if errors.is_empty() {
         if merge && merged_count > 0 {
             Ok(format!(
-                "成功导入 {} 个文件（其中 {} 个已合并）\n导出时间: {}\n导出版本: {}",
-                imported_count, merged_count, manifest.timestamp, manifest.app_version
+                "成功导入 {} 个文件（其中 {} 个已合并）\n导出时间: {}\n导出版本: {}{}",
+                imported_count,
+                merged_count,
+                manifest.timestamp,
+                manifest.app_version,
+                migration_note
             ))
         } else {
             Ok(format!(
-                "成功导入 {} 个文件\n导出时间: {}\n导出版本: {}",
-                imported_count, manifest.timestamp, manifest.app_version
+                "成功导入 {} 个文件\n导出时间: {}\n导出版本: {}{}",
+                imported_count, manifest.timestamp, manifest.app_version, migration_note
             ))
         }
     } else {
@@ -404,3 +684,102 @@ pub async fn import_all_configs_from_zip(
         ))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_classify_and_select_config() {
+        assert_eq!(
+            classify_config_path("llm-chat/agents/a1/agent.json"),
+            ("llm-chat/agents".to_string(), "agents")
+        );
+        assert_eq!(
+            classify_config_path("llm-chat/sessions/s1.json"),
+            ("llm-chat".to_string(), "chat")
+        );
+        assert_eq!(
+            classify_config_path("plugins-config/demo/settings.json"),
+            ("plugins-config".to_string(), "plugins")
+        );
+        assert_eq!(
+            classify_config_path("regex_applier/config.json"),
+            ("regex_applier".to_string(), "tools")
+        );
+        // 前缀须按目录边界匹配
+        assert_eq!(classify_config_path("plugins2/x.json").1, "tools");
+
+        let include = vec!["agents".to_string(), "regex_applier".to_string()];
+        assert!(is_config_selected(
+            "agent-manager/agents.json",
+            Some(&include)
+        ));
+        assert!(is_config_selected(
+            "regex_applier/config.json",
+            Some(&include)
+        ));
+        assert!(!is_config_selected(
+            "llm-chat/sessions/s1.json",
+            Some(&include)
+        ));
+        assert!(is_config_selected("llm-chat/sessions/s1.json", None));
+
+        let groups = group_config_files([
+            ("llm-chat/agents/a1/agent.json", 10),
+            ("llm-chat/agents/a2/agent.json", 5),
+            ("app-settings/settings.json", 1),
+        ]);
+        assert_eq!(groups.len(), 2);
+        assert_eq!(groups[1].key, "llm-chat/agents");
+        assert_eq!(groups[1].file_count, 2);
+        assert_eq!(groups[1].total_size, 15);
+    }
+
+    #[test]
+    fn test_config_migrations() {
+        fn rename_field(_path: &str, value: &mut Value) {
+            if let Some(map) = value.as_object_mut() {
+                if let Some(v) = map.remove("oldName") {
+                    map.insert("newName".to_string(), v);
+                }
+            }
+        }
+        fn add_field(_path: &str, value: &mut Value) {
+            value["added"] = Value::Bool(true);
+        }
+        let migrations = [
+            ConfigMigration {
+                from_version: 1,
+                migrate: add_field,
+            },
+            ConfigMigration {
+                from_version: 0,
+                migrate: rename_field,
+            },
+        ];
+
+        let mut value = serde_json::json!({ "oldName": 1 });
+        run_config_migrations(&migrations, "a.json", &mut value, 0, 2).unwrap();
+        assert_eq!(value, serde_json::json!({ "newName": 1, "added": true }));
+
+        // 缺少中间版本的迁移时报错
+        let mut value = serde_json::json!({});
+        assert!(run_config_migrations(&migrations, "a.json", &mut value, 0, 3).is_err());
+
+        assert!(check_schema_compatibility(CONFIG_SCHEMA_VERSION).is_ok());
+        assert!(check_schema_compatibility(CONFIG_SCHEMA_VERSION + 1).is_err());
+        // 内置迁移链覆盖所有受支持的版本
+        for from in MIN_SUPPORTED_SCHEMA_VERSION..CONFIG_SCHEMA_VERSION {
+            let mut value = serde_json::json!({});
+            run_config_migrations(
+                CONFIG_MIGRATIONS,
+                "a.json",
+                &mut value,
+                from,
+                CONFIG_SCHEMA_VERSION,
+            )
+            .unwrap();
+        }
+    }
+}
@@ -17,26 +17,31 @@ use content_inspector::inspect;
 use ignore::{WalkBuilder, WalkState};
 use rayon::prelude::*;
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::fs::{self, File};
 use std::io::{Read, Seek, SeekFrom};
 use std::path::PathBuf;
-use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::atomic::{AtomicBool, AtomicU64, AtomicUsize, Ordering};
 use std::sync::{Arc, Mutex};
-use std::time::SystemTime;
+use std::time::{Duration, Instant, SystemTime};
 use tauri::{Emitter, State};
 
 // ==================== 取消机制 ====================
 
-/// 查重扫描取消标志（独立于 directory-janitor 的 ScanCancellation）
+/// 暂停状态下轮询恢复/取消的间隔
+const PAUSE_POLL_INTERVAL: Duration = Duration::from_millis(100);
+
+/// 查重扫描取消/暂停标志（独立于 directory-janitor 的 ScanCancellation）
 pub struct DedupScanCancellation {
     cancelled: Arc<AtomicBool>,
+    paused: Arc<AtomicBool>,
 }
 
 impl DedupScanCancellation {
     pub fn new() -> Self {
         Self {
             cancelled: Arc::new(AtomicBool::new(false)),
+            paused: Arc::new(AtomicBool::new(false)),
         }
     }
 
@@ -44,13 +49,35 @@ impl DedupScanCancellation {
         self.cancelled.store(true, Ordering::SeqCst);
     }
 
+    pub fn pause(&self) {
+        self.paused.store(true, Ordering::SeqCst);
+    }
+
+    pub fn resume(&self) {
+        self.paused.store(false, Ordering::SeqCst);
+    }
+
     pub fn reset(&self) {
         self.cancelled.store(false, Ordering::SeqCst);
+        self.paused.store(false, Ordering::SeqCst);
     }
 
     pub fn is_cancelled(&self) -> bool {
         self.cancelled.load(Ordering::SeqCst)
     }
+
+    /// 暂停时阻塞直到恢复或取消，返回是否已取消
+    pub fn checkpoint(&self) -> bool {
+        wait_while_paused(&self.paused, &self.cancelled);
+        self.is_cancelled()
+    }
+}
+
+/// 暂停期间阻塞当前线程；取消标志被置位时立即返回
+fn wait_while_paused(paused: &AtomicBool, cancelled: &AtomicBool) {
+    while paused.load(Ordering::Relaxed) && !cancelled.load(Ordering::Relaxed) {
+        std::thread::sleep(PAUSE_POLL_INTERVAL);
+    }
 }
 
 impl Default for DedupScanCancellation {
@@ -98,16 +125,18 @@ pub struct SimilarityConfig {
     pub max_file_size_mb: u64,
     /// 尺寸差异阈值（默认 0.05 即 5%）
     pub size_diff_threshold: f32,
-    /// 最小相似度阈值（默认 0.85）
-    #[allow(dead_code)]
+    /// 最小相似度阈值（默认 0.85），用于近似重复（fuzzy）判定
     pub min_similarity: f32,
     /// 小文件阈值 (bytes)，默认 3072 (3KB)
     pub suspicious_size_limit: u64,
     /// 预设名称
     #[allow(dead_code)]
     pub preset: String,
-    /// 规范化选项
+    /// 规范化选项（对二进制文件无效）
     pub normalize_options: NormalizeOptions,
+    /// 是否包含二进制文件（仅按原始内容精确查重）
+    #[serde(default)]
+    pub include_binary: bool,
 }
 
 // ==================== 结果结构 ====================
@@ -208,6 +237,79 @@ struct CollectedFile {
     size: u64,
     modified: u64,
     extension: String,
+    is_text: bool,
+}
+
+/// 进度事件最小发送间隔（毫秒）
+const PROGRESS_EMIT_INTERVAL_MS: u64 = 100;
+
+/// 并行阶段的进度上报器
+///
+/// 多个 rayon 线程共享同一计数器，按时间间隔节流发送事件，最后一个文件完成时必定发送一次
+struct ThrottledProgress<'a> {
+    window: &'a tauri::Window,
+    stage: &'static str,
+    total: usize,
+    found_groups: usize,
+    current: AtomicUsize,
+    started: Instant,
+    last_emit_ms: AtomicU64,
+}
+
+impl<'a> ThrottledProgress<'a> {
+    fn new(
+        window: &'a tauri::Window,
+        stage: &'static str,
+        total: usize,
+        found_groups: usize,
+    ) -> Self {
+        let progress = Self {
+            window,
+            stage,
+            total,
+            found_groups,
+            current: AtomicUsize::new(0),
+            started: Instant::now(),
+            last_emit_ms: AtomicU64::new(0),
+        };
+        progress.emit(0, None);
+        progress
+    }
+
+    fn tick(&self, file: &CollectedFile) {
+        let current = self.current.fetch_add(1, Ordering::Relaxed) + 1;
+        let elapsed_ms = self.started.elapsed().as_millis() as u64;
+        let last_ms = self.last_emit_ms.load(Ordering::Relaxed);
+
+        if current < self.total {
+            // 未到间隔，或其他线程已抢先发送
+            if elapsed_ms.saturating_sub(last_ms) < PROGRESS_EMIT_INTERVAL_MS
+                || self
+                    .last_emit_ms
+                    .compare_exchange(last_ms, elapsed_ms, Ordering::Relaxed, Ordering::Relaxed)
+                    .is_err()
+            {
+                return;
+            }
+        }
+
+        self.emit(current, Some(file.path.to_string_lossy().to_string()));
+    }
+
+    fn emit(&self, current: usize, current_file: Option<String>) {
+        let _ = self.window.emit(
+            "dedup-scan-progress",
+            DedupScanProgress {
+                stage: self.stage.to_string(),
+                stage_progress: StageProgress {
+                    current,
+                    total: self.total,
+                },
+                found_groups: self.found_groups,
+                current_file,
+            },
+        );
+    }
 }
 
 // ==================== 规范化逻辑 ====================
@@ -311,7 +413,9 @@ fn collect_files(
 
     // 克隆需要在闭包中使用的数据
     let extensions = config.extensions.clone();
+    let include_binary = config.include_binary;
     let cancelled_flag = cancellation.cancelled.clone();
+    let paused_flag = cancellation.paused.clone();
 
     // 使用并行 walker
     let walker = builder.build_parallel();
@@ -320,9 +424,11 @@ fn collect_files(
         let scanned = scanned.clone();
         let extensions = extensions.clone();
         let cancelled_flag = cancelled_flag.clone();
+        let paused_flag = paused_flag.clone();
         let window = window.clone();
 
         Box::new(move |entry_result| {
+            wait_while_paused(&paused_flag, &cancelled_flag);
             if cancelled_flag.load(Ordering::Relaxed) {
                 return WalkState::Quit;
             }
@@ -400,7 +506,7 @@ fn collect_files(
                 }
             };
 
-            if !is_text {
+            if !is_text && !include_binary {
                 return WalkState::Continue;
             }
 
@@ -417,6 +523,7 @@ fn collect_files(
                 size,
                 modified,
                 extension,
+                is_text,
             });
 
             let count = scanned.fetch_add(1, Ordering::Relaxed) + 1;
@@ -529,9 +636,12 @@ fn compute_quick_fingerprint(
 }
 
 /// Step 4: 规范化全文流式哈希
+///
+/// 每读取一块都会检查取消标志，避免大文件拖慢取消响应
 fn compute_normalized_full_hash(
     path: &PathBuf,
     options: &NormalizeOptions,
+    cancellation: &DedupScanCancellation,
 ) -> Result<(String, String), String> {
     let mut file = File::open(path).map_err(|e| format!("打开文件失败: {}", e))?;
     let mut raw_hasher = Blake3Hasher::new();
@@ -541,6 +651,9 @@ fn compute_normalized_full_hash(
     let mut is_first_chunk = true;
 
     loop {
+        if cancellation.checkpoint() {
+            return Err("扫描已被用户取消".to_string());
+        }
         let n = file
             .read(&mut buf)
             .map_err(|e| format!("读取文件失败: {}", e))?;
@@ -570,6 +683,170 @@ fn compute_normalized_full_hash(
     Ok((raw_hash, norm_hash))
 }
 
+/// 原始内容流式哈希（二进制文件使用，不做任何规范化）
+fn compute_raw_full_hash(
+    path: &PathBuf,
+    cancellation: &DedupScanCancellation,
+) -> Result<String, String> {
+    let mut file = File::open(path).map_err(|e| format!("打开文件失败: {}", e))?;
+    let mut hasher = Blake3Hasher::new();
+
+    let mut buf = [0u8; 65536]; // 64KB buffer
+    loop {
+        if cancellation.checkpoint() {
+            return Err("扫描已被用户取消".to_string());
+        }
+        let n = file
+            .read(&mut buf)
+            .map_err(|e| format!("读取文件失败: {}", e))?;
+        if n == 0 {
+            break;
+        }
+        hasher.update(&buf[..n]);
+    }
+
+    Ok(hasher.finalize().to_hex().to_string())
+}
+
+/// Step 5: 按行计算 64 位 SimHash 指纹，用于近似重复检测
+///
+/// 每个非空的规范化行作为一个特征，只差几行的文件指纹的汉明距离很小
+fn compute_simhash(path: &PathBuf, options: &NormalizeOptions) -> Result<u64, String> {
+    let data = fs::read(path).map_err(|e| format!("读取文件失败: {}", e))?;
+    Ok(simhash_lines(skip_bom(&data), options))
+}
+
+fn simhash_lines(data: &[u8], options: &NormalizeOptions) -> u64 {
+    let mut weights = [0i64; 64];
+    for line in data.split(|b| *b == b'\n') {
+        let normalized = normalize_bytes(line, options);
+        if normalized.is_empty() {
+            continue;
+        }
+        let hash = blake3::hash(&normalized);
+        let mut bits = [0u8; 8];
+        bits.copy_from_slice(&hash.as_bytes()[..8]);
+        let bits = u64::from_le_bytes(bits);
+        for (i, weight) in weights.iter_mut().enumerate() {
+            if (bits >> i) & 1 == 1 {
+                *weight += 1;
+            } else {
+                *weight -= 1;
+            }
+        }
+    }
+
+    weights
+        .iter()
+        .enumerate()
+        .filter(|(_, weight)| **weight > 0)
+        .fold(0u64, |acc, (i, _)| acc | (1u64 << i))
+}
+
+/// 由 SimHash 汉明距离换算的相似度 (0.0 ~ 1.0)
+fn simhash_similarity(a: u64, b: u64) -> f64 {
+    1.0 - (a ^ b).count_ones() as f64 / 64.0
+}
+
+/// 对未精确命中的文件按 SimHash 相似度贪心聚类
+///
+/// 修改时间最新的文件优先作为代表，返回的每组第一个元素为代表文件，
+/// 其余为与代表相似度不低于阈值的文件及其相似度
+fn cluster_by_simhash(
+    mut candidates: Vec<(&CollectedFile, u64)>,
+    min_similarity: f64,
+) -> Vec<Vec<(&CollectedFile, f64)>> {
+    candidates.sort_by_key(|(file, _)| std::cmp::Reverse(file.modified));
+
+    let mut assigned = vec![false; candidates.len()];
+    let mut clusters = Vec::new();
+
+    for i in 0..candidates.len() {
+        if assigned[i] {
+            continue;
+        }
+        let (representative, rep_hash) = candidates[i];
+        let mut cluster = vec![(representative, 1.0)];
+
+        for j in (i + 1)..candidates.len() {
+            if assigned[j] {
+                continue;
+            }
+            let similarity = simhash_similarity(rep_hash, candidates[j].1);
+            if similarity >= min_similarity {
+                assigned[j] = true;
+                cluster.push((candidates[j].0, similarity));
+            }
+        }
+
+        if cluster.len() >= 2 {
+            assigned[i] = true;
+            clusters.push(cluster);
+        }
+    }
+
+    clusters
+}
+
+/// 超过该大小的文件不生成差异摘要，避免拖慢扫描
+const DIFF_SUMMARY_MAX_BYTES: u64 = 512 * 1024;
+/// 差异摘要中列出的差异位置数量
+const DIFF_SUMMARY_MAX_HUNKS: usize = 3;
+
+/// 生成代表文件与成员文件的行级差异摘要
+///
+/// 形如 "+2 / -1 行，差异位于第 12、40 行"；文件过大、读取失败或没有行级差异时返回 None
+fn build_diff_summary(representative: &CollectedFile, other: &CollectedFile) -> Option<String> {
+    if representative.size > DIFF_SUMMARY_MAX_BYTES || other.size > DIFF_SUMMARY_MAX_BYTES {
+        return None;
+    }
+
+    let old = fs::read(&representative.path).ok()?;
+    let new = fs::read(&other.path).ok()?;
+    summarize_line_diff(
+        &String::from_utf8_lossy(skip_bom(&old)),
+        &String::from_utf8_lossy(skip_bom(&new)),
+    )
+}
+
+fn summarize_line_diff(old: &str, new: &str) -> Option<String> {
+    let diff = similar::TextDiff::configure()
+        .timeout(std::time::Duration::from_millis(200))
+        .diff_lines(old, new);
+
+    let mut inserted = 0usize;
+    let mut deleted = 0usize;
+    for change in diff.iter_all_changes() {
+        match change.tag() {
+            similar::ChangeTag::Insert => inserted += 1,
+            similar::ChangeTag::Delete => deleted += 1,
+            similar::ChangeTag::Equal => {}
+        }
+    }
+    if inserted == 0 && deleted == 0 {
+        return None;
+    }
+
+    let hunks = diff.grouped_ops(0);
+    let positions: Vec<String> = hunks
+        .iter()
+        .take(DIFF_SUMMARY_MAX_HUNKS)
+        .filter_map(|ops| ops.first())
+        .map(|op| (op.old_range().start + 1).to_string())
+        .collect();
+
+    let mut summary = format!(
+        "+{} / -{} 行，差异位于第 {} 行",
+        inserted,
+        deleted,
+        positions.join("、")
+    );
+    if hunks.len() > DIFF_SUMMARY_MAX_HUNKS {
+        summary.push_str(&format!(" 等 {} 处", hunks.len()));
+    }
+    Some(summary)
+}
+
 /// 选择代表文件：修改时间最新的文件
 fn select_representative(files: &[&CollectedFile]) -> usize {
     files
@@ -588,7 +865,7 @@ fn to_file_info(file: &CollectedFile) -> DedupFileInfo {
         size: file.size,
         modified: file.modified,
         extension: file.extension.clone(),
-        is_text: true,
+        is_text: file.is_text,
     }
 }
 
@@ -630,6 +907,11 @@ pub async fn scan_content_duplicates(
     let all_files = collect_files(&root_path, &config, &cancellation, &skipped, &window);
     let total_files_scanned = all_files.len();
 
+    // 二进制文件不走规范化漏斗，单独按原始内容精确查重
+    let (all_files, binary_files): (Vec<CollectedFile>, Vec<CollectedFile>) =
+        all_files.into_iter().partition(|f| f.is_text);
+    let total_text_files = all_files.len();
+
     if cancellation.is_cancelled() {
         return Err("扫描已被用户取消".to_string());
     }
@@ -667,187 +949,236 @@ pub async fn scan_content_duplicates(
     let mut group_counter = 0usize;
 
     // --- 处理普通文件桶 ---
-    let total_buckets = size_buckets.len();
-    for (bucket_idx, bucket) in size_buckets.iter().enumerate() {
-        if cancellation.is_cancelled() {
-            return Err("扫描已被用户取消".to_string());
-        }
-
-        let _ = window.emit(
-            "dedup-scan-progress",
-            DedupScanProgress {
-                stage: "fingerprint".to_string(),
-                stage_progress: StageProgress {
-                    current: bucket_idx,
-                    total: total_buckets,
-                },
-                found_groups: all_groups.len(),
-                current_file: None,
-            },
-        );
-
-        // Step 3: 快速指纹分组
-        let mut fingerprint_groups: HashMap<String, Vec<&CollectedFile>> = HashMap::new();
-
-        for file in bucket {
+    // Step 3: 所有桶内文件并行计算快速指纹
+    let bucket_files: Vec<(usize, &CollectedFile)> = size_buckets
+        .iter()
+        .enumerate()
+        .flat_map(|(idx, bucket)| bucket.iter().map(move |file| (idx, file)))
+        .collect();
+
+    let fingerprint_progress =
+        ThrottledProgress::new(&window, "fingerprint", bucket_files.len(), 0);
+    let fingerprints: Vec<(usize, &CollectedFile, String)> = bucket_files
+        .par_iter()
+        .filter_map(|(bucket_idx, file)| {
+            if cancellation.checkpoint() {
+                return None;
+            }
+            fingerprint_progress.tick(file);
             match compute_quick_fingerprint(&file.path, file.size, &normalize_options) {
-                Ok(fp) => {
-                    fingerprint_groups.entry(fp).or_default().push(file);
-                }
+                Ok(fp) => Some((*bucket_idx, *file, fp)),
                 Err(reason) => {
                     skipped.lock().unwrap().push(SkippedFile {
                         path: file.path.to_string_lossy().to_string(),
                         reason,
                     });
+                    None
                 }
             }
-        }
+        })
+        .collect();
 
-        // 只保留有 2 个以上文件的指纹组
-        let fp_groups: Vec<Vec<&CollectedFile>> = fingerprint_groups
-            .into_values()
-            .filter(|g| g.len() >= 2)
-            .collect();
+    if cancellation.is_cancelled() {
+        return Err("扫描已被用户取消".to_string());
+    }
 
-        // Step 4: 对每个指纹组并行计算全文哈希
-        for fp_group in fp_groups {
-            if cancellation.is_cancelled() {
-                return Err("扫描已被用户取消".to_string());
+    // 同一桶内指纹相同（2 个以上）的文件才进入全文哈希
+    let mut fingerprint_groups: HashMap<(usize, String), Vec<&CollectedFile>> = HashMap::new();
+    for (bucket_idx, file, fp) in fingerprints {
+        fingerprint_groups
+            .entry((bucket_idx, fp))
+            .or_default()
+            .push(file);
+    }
+    let hash_candidates: Vec<(usize, &CollectedFile)> = fingerprint_groups
+        .into_iter()
+        .filter(|(_, group)| group.len() >= 2)
+        .flat_map(|((bucket_idx, _), group)| group.into_iter().map(move |file| (bucket_idx, file)))
+        .collect();
+
+    // Step 4: 所有候选文件并行计算全文哈希
+    let hashing_progress = ThrottledProgress::new(&window, "hashing", hash_candidates.len(), 0);
+    let hash_results: Vec<(usize, &CollectedFile, String, String)> = hash_candidates
+        .par_iter()
+        .filter_map(|(bucket_idx, file)| {
+            if cancellation.checkpoint() {
+                return None;
             }
-
-            // 并行计算全文哈希
-            let hash_results: Vec<_> = fp_group
-                .par_iter()
-                .filter_map(|file| {
-                    if cancellation.is_cancelled() {
-                        return None;
-                    }
-                    match compute_normalized_full_hash(&file.path, &normalize_options) {
-                        Ok((raw_hash, norm_hash)) => Some((*file, raw_hash, norm_hash)),
-                        Err(reason) => {
-                            skipped.lock().unwrap().push(SkippedFile {
-                                path: file.path.to_string_lossy().to_string(),
-                                reason,
-                            });
-                            None
-                        }
+            hashing_progress.tick(file);
+            match compute_normalized_full_hash(&file.path, &normalize_options, &cancellation) {
+                Ok((raw_hash, norm_hash)) => Some((*bucket_idx, *file, raw_hash, norm_hash)),
+                Err(reason) => {
+                    if !cancellation.is_cancelled() {
+                        skipped.lock().unwrap().push(SkippedFile {
+                            path: file.path.to_string_lossy().to_string(),
+                            reason,
+                        });
                     }
-                })
-                .collect();
+                    None
+                }
+            }
+        })
+        .collect();
 
-            let mut hash_map: HashMap<String, Vec<(&CollectedFile, String)>> = HashMap::new();
-            for (file, raw_hash, norm_hash) in &hash_results {
-                hash_map
-                    .entry(norm_hash.clone())
-                    .or_default()
-                    .push((file, raw_hash.clone()));
+    if cancellation.is_cancelled() {
+        return Err("扫描已被用户取消".to_string());
+    }
+
+    // 按桶归并全文哈希结果
+    let mut bucket_hash_maps: Vec<HashMap<String, Vec<(&CollectedFile, String)>>> =
+        (0..size_buckets.len()).map(|_| HashMap::new()).collect();
+    for (bucket_idx, file, raw_hash, norm_hash) in hash_results {
+        bucket_hash_maps[bucket_idx]
+            .entry(norm_hash)
+            .or_default()
+            .push((file, raw_hash));
+    }
+
+    for (bucket, hash_map) in size_buckets.iter().zip(bucket_hash_maps) {
+        if cancellation.checkpoint() {
+            return Err("扫描已被用户取消".to_string());
+        }
+
+        // 已被精确/规范化命中的文件，不再参与近似匹配
+        let mut matched: HashSet<&PathBuf> = HashSet::new();
+
+        // 构建重复组
+        for (_norm_hash, members) in hash_map {
+            if members.len() < 2 {
+                continue;
             }
 
-            // 构建重复组
-            for (_norm_hash, members) in hash_map {
-                if members.len() < 2 {
+            let files_ref: Vec<&CollectedFile> = members.iter().map(|(f, _)| *f).collect();
+            let raw_hashes: Vec<&str> = members.iter().map(|(_, h)| h.as_str()).collect();
+            matched.extend(files_ref.iter().map(|f| &f.path));
+
+            let rep_idx = select_representative(&files_ref);
+            let representative = files_ref[rep_idx];
+
+            // 判断是否所有原始哈希一致（exact）
+            let all_exact = raw_hashes.iter().all(|h| *h == raw_hashes[0]);
+
+            let mut similar_files = Vec::new();
+            for (i, (file, _raw_hash)) in members.iter().enumerate() {
+                if i == rep_idx {
                     continue;
                 }
+                similar_files.push(SimilarFile {
+                    file: to_file_info(file),
+                    similarity: 1.0,
+                    match_type: if all_exact {
+                        "exact".to_string()
+                    } else {
+                        "normalized".to_string()
+                    },
+                    diff_summary: if all_exact {
+                        None
+                    } else {
+                        build_diff_summary(representative, file)
+                    },
+                });
+            }
 
-                let files_ref: Vec<&CollectedFile> = members.iter().map(|(f, _)| *f).collect();
-                let raw_hashes: Vec<&str> = members.iter().map(|(_, h)| h.as_str()).collect();
+            let total_wasted: u64 = similar_files.iter().map(|sf| sf.file.size).sum();
 
-                let rep_idx = select_representative(&files_ref);
-                let representative = files_ref[rep_idx];
+            group_counter += 1;
+            all_groups.push(DuplicateGroup {
+                id: format!("group-{}", group_counter),
+                representative_file: to_file_info(representative),
+                similar_files,
+                metadata: DuplicateGroupMetadata {
+                    is_suspicious: false,
+                    total_wasted_bytes: total_wasted,
+                    avg_similarity: 1.0,
+                },
+            });
+        }
 
-                // 判断是否所有原始哈希一致（exact）
-                let all_exact = raw_hashes.iter().all(|h| *h == raw_hashes[0]);
+        // Step 5: 同一尺寸桶内未命中的文件计算 SimHash，找出近似重复
+        let unmatched: Vec<&CollectedFile> = bucket
+            .iter()
+            .filter(|f| !matched.contains(&f.path))
+            .collect();
+        if unmatched.len() < 2 {
+            continue;
+        }
 
-                let mut similar_files = Vec::new();
-                for (i, (file, _raw_hash)) in members.iter().enumerate() {
-                    if i == rep_idx {
-                        continue;
-                    }
-                    similar_files.push(SimilarFile {
-                        file: to_file_info(file),
-                        similarity: 1.0,
-                        match_type: if all_exact {
-                            "exact".to_string()
-                        } else {
-                            "normalized".to_string()
-                        },
-                        diff_summary: None,
-                    });
+        let simhashes: Vec<(&CollectedFile, u64)> = unmatched
+            .par_iter()
+            .filter_map(|file| {
+                if cancellation.checkpoint() {
+                    return None;
                 }
+                // 读取失败的文件已在前面的步骤记录到跳过列表
+                compute_simhash(&file.path, &normalize_options)
+                    .ok()
+                    .map(|hash| (*file, hash))
+            })
+            .collect();
 
-                let total_wasted: u64 = similar_files.iter().map(|sf| sf.file.size).sum();
-
-                group_counter += 1;
-                all_groups.push(DuplicateGroup {
-                    id: format!("group-{}", group_counter),
-                    representative_file: to_file_info(representative),
-                    similar_files,
-                    metadata: DuplicateGroupMetadata {
-                        is_suspicious: false,
-                        total_wasted_bytes: total_wasted,
-                        avg_similarity: 1.0,
-                    },
-                });
-            }
+        for cluster in cluster_by_simhash(simhashes, config.min_similarity.clamp(0.0, 1.0) as f64) {
+            let representative = cluster[0].0;
+            let similar_files: Vec<SimilarFile> = cluster[1..]
+                .iter()
+                .map(|(file, similarity)| SimilarFile {
+                    file: to_file_info(file),
+                    similarity: *similarity,
+                    match_type: "fuzzy".to_string(),
+                    diff_summary: build_diff_summary(representative, file),
+                })
+                .collect();
+
+            let total_wasted: u64 = similar_files.iter().map(|sf| sf.file.size).sum();
+            let avg_similarity = similar_files.iter().map(|sf| sf.similarity).sum::<f64>()
+                / similar_files.len() as f64;
+
+            group_counter += 1;
+            all_groups.push(DuplicateGroup {
+                id: format!("group-{}", group_counter),
+                representative_file: to_file_info(representative),
+                similar_files,
+                metadata: DuplicateGroupMetadata {
+                    is_suspicious: false,
+                    total_wasted_bytes: total_wasted,
+                    avg_similarity,
+                },
+            });
         }
     }
 
     // --- 处理小文件（跳过尺寸过滤和快速指纹，直接全文哈希）---
     if !small_files.is_empty() {
-        let _ = window.emit(
-            "dedup-scan-progress",
-            DedupScanProgress {
-                stage: "hashing".to_string(),
-                stage_progress: StageProgress {
-                    current: 0,
-                    total: small_files.len(),
-                },
-                found_groups: all_groups.len(),
-                current_file: None,
-            },
-        );
-
         // 并行计算小文件全文哈希
-        let small_progress = Arc::new(AtomicUsize::new(0));
-        let small_total = small_files.len();
-        let groups_found = all_groups.len();
+        let small_progress =
+            ThrottledProgress::new(&window, "hashing", small_files.len(), all_groups.len());
 
         let small_hash_results: Vec<_> = small_files
             .par_iter()
             .filter_map(|file| {
-                if cancellation.is_cancelled() {
+                if cancellation.checkpoint() {
                     return None;
                 }
+                small_progress.tick(file);
 
-                let idx = small_progress.fetch_add(1, Ordering::Relaxed);
-                if idx.is_multiple_of(50) {
-                    let _ = window.emit(
-                        "dedup-scan-progress",
-                        DedupScanProgress {
-                            stage: "hashing".to_string(),
-                            stage_progress: StageProgress {
-                                current: idx,
-                                total: small_total,
-                            },
-                            found_groups: groups_found,
-                            current_file: Some(file.path.to_string_lossy().to_string()),
-                        },
-                    );
-                }
-
-                match compute_normalized_full_hash(&file.path, &normalize_options) {
+                match compute_normalized_full_hash(&file.path, &normalize_options, &cancellation) {
                     Ok((raw_hash, norm_hash)) => Some((file, raw_hash, norm_hash)),
                     Err(reason) => {
-                        skipped.lock().unwrap().push(SkippedFile {
-                            path: file.path.to_string_lossy().to_string(),
-                            reason,
-                        });
+                        if !cancellation.is_cancelled() {
+                            skipped.lock().unwrap().push(SkippedFile {
+                                path: file.path.to_string_lossy().to_string(),
+                                reason,
+                            });
+                        }
                         None
                     }
                 }
             })
             .collect();
 
+        if cancellation.is_cancelled() {
+            return Err("扫描已被用户取消".to_string());
+        }
+
         let mut small_hash_map: HashMap<String, Vec<(&CollectedFile, String)>> = HashMap::new();
         for (file, raw_hash, norm_hash) in &small_hash_results {
             small_hash_map
@@ -882,7 +1213,11 @@ pub async fn scan_content_duplicates(
                     } else {
                         "normalized".to_string()
                     },
-                    diff_summary: None,
+                    diff_summary: if all_exact {
+                        None
+                    } else {
+                        build_diff_summary(representative, file)
+                    },
                 });
             }
 
@@ -902,6 +1237,87 @@ pub async fn scan_content_duplicates(
         }
     }
 
+    // --- 处理二进制文件（按大小精确分组后直接原始全文哈希）---
+    if !binary_files.is_empty() {
+        let mut size_map: HashMap<u64, Vec<&CollectedFile>> = HashMap::new();
+        for file in &binary_files {
+            size_map.entry(file.size).or_default().push(file);
+        }
+        let candidates: Vec<&CollectedFile> = size_map
+            .into_values()
+            .filter(|g| g.len() >= 2)
+            .flatten()
+            .collect();
+
+        let binary_progress =
+            ThrottledProgress::new(&window, "hashing", candidates.len(), all_groups.len());
+        let binary_hash_results: Vec<_> = candidates
+            .par_iter()
+            .filter_map(|file| {
+                if cancellation.checkpoint() {
+                    return None;
+                }
+                binary_progress.tick(file);
+                match compute_raw_full_hash(&file.path, &cancellation) {
+                    Ok(hash) => Some((*file, hash)),
+                    Err(reason) => {
+                        if !cancellation.is_cancelled() {
+                            skipped.lock().unwrap().push(SkippedFile {
+                                path: file.path.to_string_lossy().to_string(),
+                                reason,
+                            });
+                        }
+                        None
+                    }
+                }
+            })
+            .collect();
+
+        if cancellation.is_cancelled() {
+            return Err("扫描已被用户取消".to_string());
+        }
+
+        let mut binary_hash_map: HashMap<String, Vec<&CollectedFile>> = HashMap::new();
+        for (file, hash) in binary_hash_results {
+            binary_hash_map.entry(hash).or_default().push(file);
+        }
+
+        for (_hash, files_ref) in binary_hash_map {
+            if files_ref.len() < 2 {
+                continue;
+            }
+
+            let rep_idx = select_representative(&files_ref);
+            let representative = files_ref[rep_idx];
+
+            let similar_files: Vec<SimilarFile> = files_ref
+                .iter()
+                .enumerate()
+                .filter(|(i, _)| *i != rep_idx)
+                .map(|(_, file)| SimilarFile {
+                    file: to_file_info(file),
+                    similarity: 1.0,
+                    match_type: "exact".to_string(),
+                    diff_summary: None,
+                })
+                .collect();
+
+            let total_wasted: u64 = similar_files.iter().map(|sf| sf.file.size).sum();
+
+            group_counter += 1;
+            all_groups.push(DuplicateGroup {
+                id: format!("group-{}", group_counter),
+                representative_file: to_file_info(representative),
+                similar_files,
+                metadata: DuplicateGroupMetadata {
+                    is_suspicious: representative.size < config.suspicious_size_limit,
+                    total_wasted_bytes: total_wasted,
+                    avg_similarity: 1.0,
+                },
+            });
+        }
+    }
+
     // ===== 构建最终结果 =====
     let _ = window.emit(
         "dedup-scan-progress",
@@ -931,7 +1347,7 @@ pub async fn scan_content_duplicates(
 
     let statistics = DedupStatistics {
         total_files_scanned,
-        total_text_files: total_files_scanned, // 已经过滤了非文本
+        total_text_files,
         total_groups: all_groups.len(),
         total_duplicates,
         total_wasted_bytes,
@@ -978,6 +1394,24 @@ pub async fn stop_dedup_scan(cancellation: State<'_, DedupScanCancellation>) ->
     Ok(())
 }
 
+/// 暂停查重扫描（各阶段在下一个检查点阻塞等待，不丢弃已完成的进度）
+#[tauri::command]
+pub async fn pause_dedup_scan(
+    cancellation: State<'_, DedupScanCancellation>,
+) -> Result<(), String> {
+    cancellation.pause();
+    Ok(())
+}
+
+/// 恢复已暂停的查重扫描
+#[tauri::command]
+pub async fn resume_dedup_scan(
+    cancellation: State<'_, DedupScanCancellation>,
+) -> Result<(), String> {
+    cancellation.resume();
+    Ok(())
+}
+
 /// 删除重复文件（移入回收站）
 #[tauri::command]
 pub async fn delete_duplicate_files(
@@ -1051,3 +1485,39 @@ pub struct DedupDeleteProgress {
     pub total: usize,
     pub current_file: String,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_simhash_similarity_for_near_duplicate_lines() {
+        let options = NormalizeOptions::default();
+        let base: String = (0..60)
+            .map(|i| format!("let value_{} = compute({}, {});\n", i, i, i * 2))
+            .collect();
+        let edited = base.replacen("compute(3, 6)", "compute(3, 7)", 1);
+        let unrelated: String = (0..60)
+            .map(|i| format!("fn other_{}() -> u32 {{ {} }}\n", i, i * 7))
+            .collect();
+
+        let base_hash = simhash_lines(base.as_bytes(), &options);
+        let edited_hash = simhash_lines(edited.as_bytes(), &options);
+        let unrelated_hash = simhash_lines(unrelated.as_bytes(), &options);
+
+        assert!(simhash_similarity(base_hash, edited_hash) >= 0.85);
+        assert!(simhash_similarity(base_hash, unrelated_hash) < 0.85);
+    }
+
+    #[test]
+    fn test_summarize_line_diff_counts_changes() {
+        let old = "a\nb\nc\nd\n";
+        let new = "a\nB\nc\nd\ne\n";
+
+        assert_eq!(
+            summarize_line_diff(old, new),
+            Some("+2 / -1 行，差异位于第 2、5 行".to_string())
+        );
+        assert_eq!(summarize_line_diff(old, old), None);
+    }
+}
@@ -15,10 +15,12 @@
 use crate::events::DirectoryScanProgress;
 use regex::Regex;
 use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
 use std::fs;
 use std::path::{Path, PathBuf};
 use std::sync::atomic::{AtomicBool, Ordering};
-use std::sync::Arc;
+use std::sync::{Arc, Mutex};
 use std::time::SystemTime;
 use tauri::{Emitter, State};
 
@@ -84,15 +86,36 @@ impl Default for CleanupCancellation {
     }
 }
 
+// 最近一次分析得到的待清理项 (id -> 路径)，供 cleanup_items 按 id 执行
+#[derive(Default)]
+pub struct CleanupCandidates {
+    items: Mutex<HashMap<String, PathBuf>>,
+}
+
 // 项目信息结构
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct ItemInfo {
+    /// 由路径派生的稳定 ID
+    #[serde(default)]
+    pub id: String,
     pub path: String,
     pub name: String,
     pub is_dir: bool,
-    pub size: u64,
+    pub size: u64,     // 即清理后预计释放的空间
     pub modified: u64, // Unix timestamp in seconds
+    /// 命中的自定义规则 ID（未使用自定义规则时为空）
+    #[serde(default)]
+    pub matched_rule: Option<String>,
+}
+
+// 单条规则的命中统计
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RuleStatistics {
+    pub rule_id: String,
+    pub item_count: usize,
+    pub total_size: u64,
 }
 
 // 统计信息结构
@@ -103,6 +126,8 @@ pub struct Statistics {
     pub total_size: u64,
     pub total_dirs: usize,
     pub total_files: usize,
+    #[serde(default)]
+    pub rule_stats: Vec<RuleStatistics>,
 }
 
 // 分析结果结构
@@ -111,6 +136,8 @@ pub struct Statistics {
 pub struct AnalysisResult {
     pub items: Vec<ItemInfo>,
     pub statistics: Statistics,
+    /// 扫描被用户取消，`items` 只包含取消前已发现的项目
+    pub was_cancelled: bool,
 }
 
 // 清理结果结构
@@ -123,6 +150,87 @@ pub struct CleanupResult {
     pub errors: Vec<String>,
 }
 
+// 自定义规则的作用对象
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum CleanupRuleTarget {
+    File,
+    Dir,
+}
+
+// 自定义清理规则：规则内的条件同时满足才算命中，多条规则按顺序取第一条命中的
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CleanupRule {
+    pub id: String,
+    /// 扩展名列表（不区分大小写，可带前导点）
+    #[serde(default)]
+    pub extensions: Vec<String>,
+    pub min_size_mb: Option<f64>,
+    /// 最后修改时间早于 N 天
+    pub older_than_days: Option<u32>,
+    /// 通配符模式；包含 `/` 时匹配相对扫描目录的路径，否则匹配名称
+    pub glob: Option<String>,
+    /// 仅匹配文件或目录，为空时两者都匹配
+    pub target: Option<CleanupRuleTarget>,
+}
+
+impl CleanupRule {
+    fn matches(
+        &self,
+        name: &str,
+        relative_path: &str,
+        is_dir: bool,
+        size: u64,
+        age_days: u64,
+    ) -> bool {
+        match self.target {
+            Some(CleanupRuleTarget::File) if is_dir => return false,
+            Some(CleanupRuleTarget::Dir) if !is_dir => return false,
+            _ => {}
+        }
+
+        if !self.extensions.is_empty() {
+            let ext = Path::new(name)
+                .extension()
+                .map(|e| e.to_string_lossy().to_lowercase());
+            let hit = ext.is_some_and(|ext| {
+                self.extensions
+                    .iter()
+                    .any(|e| e.trim_start_matches('.').eq_ignore_ascii_case(&ext))
+            });
+            if !hit {
+                return false;
+            }
+        }
+
+        if let Some(min_size_mb) = self.min_size_mb {
+            if (size as f64) < min_size_mb * 1024.0 * 1024.0 {
+                return false;
+            }
+        }
+
+        if let Some(days) = self.older_than_days {
+            if age_days < days as u64 {
+                return false;
+            }
+        }
+
+        if let Some(glob) = self.glob.as_deref().filter(|g| !g.is_empty()) {
+            let target = if glob.contains('/') {
+                relative_path
+            } else {
+                name
+            };
+            if !matches_name_pattern(target, glob) {
+                return false;
+            }
+        }
+
+        true
+    }
+}
+
 // 过滤条件结构
 #[derive(Debug, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -131,6 +239,15 @@ pub struct FilterCriteria {
     pub min_age_days: Option<u32>,
     pub min_size_mb: Option<u64>,
     pub max_depth: Option<usize>,
+    /// 自定义规则，非空时只保留命中任一规则的项目
+    #[serde(default)]
+    pub rules: Vec<CleanupRule>,
+}
+
+// 由路径生成稳定的项目 ID
+fn item_id(path: &str) -> String {
+    let digest = Sha256::digest(path.as_bytes());
+    digest[..8].iter().map(|b| format!("{:02x}", b)).collect()
 }
 
 // 递归计算目录大小（复用自 file_operations.rs 的逻辑）
@@ -184,12 +301,14 @@ fn matches_name_pattern(name: &str, pattern: &str) -> bool {
 
 // 递归分析目录的参数结构
 struct AnalysisConfig<'a> {
+    root: &'a Path,
     criteria: &'a FilterCriteria,
     window: Option<&'a tauri::Window>,
     cancellation: &'a ScanCancellation,
 }
 
 // 递归分析目录
+// 检测到取消标志时停止遍历并正常返回，已收集到 `items` 的项目会保留
 fn analyze_directory_recursive(
     dir: &Path,
     config: &AnalysisConfig,
@@ -199,7 +318,7 @@ fn analyze_directory_recursive(
 ) -> Result<(), String> {
     // 检查是否已取消
     if config.cancellation.is_cancelled() {
-        return Err("扫描已被用户取消".to_string());
+        return Ok(());
     }
     // 检查深度限制
     if let Some(max_depth) = config.criteria.max_depth {
@@ -217,6 +336,11 @@ fn analyze_directory_recursive(
         .as_secs();
 
     for entry in entries {
+        // 计算目录大小可能很慢，每个条目前都检查一次取消标志
+        if config.cancellation.is_cancelled() {
+            return Ok(());
+        }
+
         let entry = entry.map_err(|e| format!("读取目录项失败: {}", e))?;
         let path = entry.path();
         let name = entry.file_name().to_string_lossy().to_string();
@@ -261,14 +385,34 @@ fn analyze_directory_recursive(
             matches = matches && (size_mb >= min_size_mb);
         }
 
+        // 自定义规则：取第一条命中的规则
+        let mut matched_rule = None;
+        if matches && !config.criteria.rules.is_empty() {
+            let relative_path = path
+                .strip_prefix(config.root)
+                .map(|p| p.to_string_lossy().replace('\\', "/"))
+                .unwrap_or_else(|_| name.clone());
+            let age_days = current_time.saturating_sub(modified) / 86400;
+            matched_rule = config
+                .criteria
+                .rules
+                .iter()
+                .find(|rule| rule.matches(&name, &relative_path, is_dir, size, age_days))
+                .map(|rule| rule.id.clone());
+            matches = matched_rule.is_some();
+        }
+
         // 如果匹配，添加到结果列表
         if matches {
+            let path_str = path.to_string_lossy().to_string();
             items.push(ItemInfo {
-                path: path.to_string_lossy().to_string(),
+                id: item_id(&path_str),
+                path: path_str,
                 name,
                 is_dir,
                 size,
                 modified,
+                matched_rule,
             });
         }
 
@@ -301,15 +445,19 @@ fn analyze_directory_recursive(
 }
 
 // Tauri 命令：分析目录，返回符合条件的项目
+// `rules` 为自定义清理规则，命中的规则 ID 会标注在每个项目上
 #[tauri::command]
+#[allow(clippy::too_many_arguments)]
 pub async fn analyze_directory_for_cleanup(
     path: String,
     name_pattern: Option<String>,
     min_age_days: Option<u32>,
     min_size_mb: Option<u64>,
     max_depth: Option<usize>,
+    rules: Option<Vec<CleanupRule>>,
     window: tauri::Window,
     cancellation: State<'_, ScanCancellation>,
+    candidates: State<'_, CleanupCandidates>,
 ) -> Result<AnalysisResult, String> {
     // 重置取消标志
     cancellation.reset();
@@ -328,6 +476,7 @@ pub async fn analyze_directory_for_cleanup(
         min_age_days,
         min_size_mb,
         max_depth,
+        rules: rules.unwrap_or_default(),
     };
 
     let mut items = Vec::new();
@@ -346,6 +495,7 @@ pub async fn analyze_directory_for_cleanup(
     }
 
     let analysis_config = AnalysisConfig {
+        root: &root_path,
         criteria: &criteria,
         window: Some(&window),
         cancellation: &cancellation,
@@ -357,6 +507,14 @@ pub async fn analyze_directory_for_cleanup(
         &mut items,
         &mut scanned_count,
     )?;
+    let was_cancelled = cancellation.is_cancelled();
+    if was_cancelled {
+        log::info!(
+            "目录扫描已取消，返回已发现的 {} 个待清理项: {}",
+            items.len(),
+            path
+        );
+    }
 
     // 发送扫描完成事件
     let end_progress = DirectoryScanProgress {
@@ -376,26 +534,71 @@ pub async fn analyze_directory_for_cleanup(
     let total_dirs = items.iter().filter(|item| item.is_dir).count();
     let total_files = items.iter().filter(|item| !item.is_dir).count();
 
+    let rule_stats = criteria
+        .rules
+        .iter()
+        .map(|rule| {
+            let matched = items
+                .iter()
+                .filter(|item| item.matched_rule.as_deref() == Some(rule.id.as_str()));
+            RuleStatistics {
+                rule_id: rule.id.clone(),
+                item_count: matched.clone().count(),
+                total_size: matched.map(|item| item.size).sum(),
+            }
+        })
+        .collect();
+
     let statistics = Statistics {
         total_items,
         total_size,
         total_dirs,
         total_files,
+        rule_stats,
     };
 
-    Ok(AnalysisResult { items, statistics })
+    // 记录本次结果，供按 id 清理
+    if let Ok(mut map) = candidates.items.lock() {
+        *map = items
+            .iter()
+            .map(|item| (item.id.clone(), PathBuf::from(&item.path)))
+            .collect();
+    }
+
+    Ok(AnalysisResult {
+        items,
+        statistics,
+        was_cancelled,
+    })
 }
 
 // Tauri 命令：清理选定的项目（移入回收站）
+// 可直接传路径，也可传最近一次分析结果中的项目 id
 #[tauri::command]
 pub async fn cleanup_items(
-    paths: Vec<String>,
+    paths: Option<Vec<String>>,
+    ids: Option<Vec<String>>,
     window: tauri::Window,
     cancellation: State<'_, CleanupCancellation>,
+    candidates: State<'_, CleanupCandidates>,
 ) -> Result<CleanupResult, String> {
     // 重置取消标志
     cancellation.reset();
 
+    let mut paths = paths.unwrap_or_default();
+    if let Some(ids) = ids {
+        let map = candidates
+            .items
+            .lock()
+            .map_err(|e| format!("读取待清理项失败: {}", e))?;
+        for id in ids {
+            match map.get(&id) {
+                Some(path) => paths.push(path.to_string_lossy().to_string()),
+                None => return Err(format!("未找到待清理项: {}，请重新分析目录", id)),
+            }
+        }
+    }
+
     // 定义清理进度事件结构
     #[derive(Clone, serde::Serialize)]
     #[serde(rename_all = "camelCase")]
@@ -484,3 +687,93 @@ pub async fn stop_directory_cleanup(
     cancellation.cancel();
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rule(id: &str) -> CleanupRule {
+        CleanupRule {
+            id: id.to_string(),
+            extensions: Vec::new(),
+            min_size_mb: None,
+            older_than_days: None,
+            glob: None,
+            target: None,
+        }
+    }
+
+    #[test]
+    fn test_cleanup_rule_matching() {
+        let logs = CleanupRule {
+            extensions: vec![".LOG".to_string(), "tmp".to_string()],
+            older_than_days: Some(7),
+            ..rule("logs")
+        };
+        assert!(logs.matches("app.log", "app.log", false, 10, 30));
+        assert!(!logs.matches("app.log", "app.log", false, 10, 3));
+        assert!(!logs.matches("app.txt", "app.txt", false, 10, 30));
+
+        let big = CleanupRule {
+            min_size_mb: Some(1.5),
+            target: Some(CleanupRuleTarget::File),
+            ..rule("big")
+        };
+        assert!(big.matches("a.bin", "a.bin", false, 2 * 1024 * 1024, 0));
+        assert!(!big.matches("dir", "dir", true, 2 * 1024 * 1024, 0));
+
+        let cache = CleanupRule {
+            glob: Some("*/node_modules".to_string()),
+            ..rule("cache")
+        };
+        assert!(cache.matches("node_modules", "web/node_modules", true, 0, 0));
+        assert!(!cache.matches("node_modules", "node_modules", true, 0, 0));
+
+        assert_eq!(item_id("/a/b"), item_id("/a/b"));
+        assert_ne!(item_id("/a/b"), item_id("/a/c"));
+    }
+
+    #[test]
+    fn test_cancelled_scan_keeps_collected_items() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::write(dir.path().join("a.log"), b"a").unwrap();
+        fs::write(dir.path().join("b.log"), b"b").unwrap();
+
+        let criteria = FilterCriteria {
+            name_pattern: Some("*.log".to_string()),
+            min_age_days: None,
+            min_size_mb: None,
+            max_depth: None,
+            rules: Vec::new(),
+        };
+        let cancellation = ScanCancellation::new();
+        let config = AnalysisConfig {
+            root: dir.path(),
+            criteria: &criteria,
+            window: None,
+            cancellation: &cancellation,
+        };
+
+        // 取消前已收集的项目不会被丢弃，也不再继续遍历
+        let mut items = vec![ItemInfo {
+            id: item_id("/found/earlier.log"),
+            path: "/found/earlier.log".to_string(),
+            name: "earlier.log".to_string(),
+            is_dir: false,
+            size: 1,
+            modified: 0,
+            matched_rule: None,
+        }];
+        let mut scanned_count = 0;
+        cancellation.cancel();
+        analyze_directory_recursive(dir.path(), &config, 0, &mut items, &mut scanned_count)
+            .unwrap();
+        assert_eq!(items.len(), 1);
+        assert_eq!(scanned_count, 0);
+
+        cancellation.reset();
+        analyze_directory_recursive(dir.path(), &config, 0, &mut items, &mut scanned_count)
+            .unwrap();
+        assert_eq!(items.len(), 3);
+    }
+}
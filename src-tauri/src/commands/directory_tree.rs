@@ -17,9 +17,10 @@
 //! 使用 `ignore` crate（ripgrep 核心库）实现高性能并行目录遍历。
 //! 支持 .gitignore 规则、自定义过滤模式、深度限制等功能。
 
+use super::file_operations::calculate_dir_size_fast;
 use ignore::overrides::OverrideBuilder;
 use ignore::WalkBuilder;
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::path::{Path, PathBuf};
 use std::sync::atomic::{AtomicUsize, Ordering};
@@ -37,17 +38,14 @@ pub struct TreeNode {
     pub size: u64,
     pub children: Vec<TreeNode>,
     pub error: Option<String>,
+    /// 超过深度限制被折叠的直接子项数量
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub collapsed_count: Option<usize>,
 }
 
 impl TreeNode {
     fn new(name: String, is_dir: bool) -> Self {
-        Self {
-            name,
-            is_dir,
-            size: 0,
-            children: Vec::new(),
-            error: None,
-        }
+        Self::new_with_size(name, is_dir, 0)
     }
 
     fn new_with_size(name: String, is_dir: bool, size: u64) -> Self {
@@ -57,6 +55,7 @@ impl TreeNode {
             size,
             children: Vec::new(),
             error: None,
+            collapsed_count: None,
         }
     }
 }
@@ -72,11 +71,26 @@ pub struct DirectoryTreeStats {
     pub filter_count: usize,
 }
 
+/// 目录树输出格式
+#[derive(Deserialize, Clone, Copy, Debug, Default, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum TreeOutputFormat {
+    /// 带连接线的文本树
+    #[default]
+    Ascii,
+    /// 缩进列表
+    Markdown,
+    /// 节点带 is_dir/size/children 的 JSON，供前端渲染可折叠树
+    Json,
+}
+
 /// 目录树生成结果
 #[derive(Serialize)]
 pub struct DirectoryTreeResult {
     pub structure: TreeNode,
     pub stats: DirectoryTreeStats,
+    /// 按 `format` 渲染的输出
+    pub output: String,
 }
 
 // ============================================================================
@@ -94,6 +108,15 @@ struct FileEntry {
     size: u64,
 }
 
+/// 并行收集的结果
+struct CollectedEntries {
+    entries: Vec<FileEntry>,
+    /// 深度限制处被折叠目录的相对路径 -> 直接子项数量
+    collapsed: HashMap<PathBuf, usize>,
+    total_dirs: usize,
+    total_files: usize,
+}
+
 /// 统计计数器（线程安全）
 struct AtomicStats {
     total_dirs: AtomicUsize,
@@ -114,17 +137,23 @@ impl AtomicStats {
 // ============================================================================
 
 /// 使用 ignore crate 并行收集文件列表
+///
+/// 设置了深度限制时会多遍历一层，只用于统计被折叠目录的子项数量。
+/// 跟随符号链接时由 ignore crate 检测链接循环，循环处报错跳过而不会无限递归。
 fn collect_entries_parallel(
     root: &Path,
     show_files: bool,
     show_hidden: bool,
     max_depth: usize,
     use_gitignore: bool,
+    follow_links: bool,
     custom_patterns: &[String],
-) -> Result<(Vec<FileEntry>, usize, usize), String> {
+) -> Result<CollectedEntries, String> {
     let stats = Arc::new(AtomicStats::new());
     let entries: Arc<std::sync::Mutex<Vec<FileEntry>>> =
         Arc::new(std::sync::Mutex::new(Vec::new()));
+    let collapsed: Arc<std::sync::Mutex<HashMap<PathBuf, usize>>> =
+        Arc::new(std::sync::Mutex::new(HashMap::new()));
 
     // 构建 WalkBuilder
     let mut builder = WalkBuilder::new(root);
@@ -137,12 +166,12 @@ fn collect_entries_parallel(
         .git_exclude(use_gitignore) // 是否使用 .git/info/exclude
         .ignore(use_gitignore) // 是否使用 .ignore 文件
         .parents(use_gitignore) // 是否检查父目录的 ignore 文件
-        .follow_links(false) // 不跟随符号链接
+        .follow_links(follow_links) // 是否跟随符号链接
         .same_file_system(false); // 允许跨文件系统
 
-    // 设置深度限制
+    // 设置深度限制 (多走一层用于统计折叠数量)
     if max_depth > 0 {
-        builder.max_depth(Some(max_depth));
+        builder.max_depth(Some(max_depth + 1));
     }
 
     // 添加自定义过滤模式
@@ -178,9 +207,11 @@ fn collect_entries_parallel(
     // 这样后续的 Arc::try_unwrap 才能成功
     {
         let entries_clone = Arc::clone(&entries);
+        let collapsed_clone = Arc::clone(&collapsed);
         walker.run(|| {
             let stats = Arc::clone(&stats_clone);
             let entries = Arc::clone(&entries_clone);
+            let collapsed = Arc::clone(&collapsed_clone);
             let root = root_path.clone();
 
             Box::new(move |result| {
@@ -193,6 +224,18 @@ fn collect_entries_parallel(
                             return ignore::WalkState::Continue;
                         }
 
+                        // 超出深度限制的条目只计入父目录的折叠数量
+                        if max_depth > 0 && entry.depth() > max_depth {
+                            let parent = path
+                                .parent()
+                                .and_then(|p| p.strip_prefix(&root).ok())
+                                .map(|p| p.to_path_buf());
+                            if let (Some(parent), Ok(mut map)) = (parent, collapsed.lock()) {
+                                *map.entry(parent).or_insert(0) += 1;
+                            }
+                            return ignore::WalkState::Continue;
+                        }
+
                         let is_dir = entry.file_type().map(|ft| ft.is_dir()).unwrap_or(false);
 
                         // 统计
@@ -245,12 +288,25 @@ fn collect_entries_parallel(
         .map_err(|_| "无法获取条目列表".to_string())?
         .into_inner()
         .map_err(|e| format!("锁错误: {}", e))?;
+    let collapsed = Arc::try_unwrap(collapsed)
+        .map_err(|_| "无法获取折叠统计".to_string())?
+        .into_inner()
+        .map_err(|e| format!("锁错误: {}", e))?;
 
-    Ok((entries, total_dirs, total_files))
+    Ok(CollectedEntries {
+        entries,
+        collapsed,
+        total_dirs,
+        total_files,
+    })
 }
 
 /// 从扁平列表构建树形结构
-fn build_tree_from_entries(root_name: String, mut entries: Vec<FileEntry>) -> TreeNode {
+fn build_tree_from_entries(
+    root_name: String,
+    mut entries: Vec<FileEntry>,
+    collapsed: &HashMap<PathBuf, usize>,
+) -> TreeNode {
     // 按路径排序，确保父目录在子目录之前
     entries.sort_by(|a, b| a.relative_path.cmp(&b.relative_path));
 
@@ -274,7 +330,10 @@ fn build_tree_from_entries(root_name: String, mut entries: Vec<FileEntry>) -> Tr
             .map(|n| n.to_string_lossy().to_string())
             .unwrap_or_default();
 
-        let node = TreeNode::new_with_size(name, entry.is_dir, entry.size);
+        let mut node = TreeNode::new_with_size(name, entry.is_dir, entry.size);
+        if entry.is_dir {
+            node.collapsed_count = collapsed.get(&entry.relative_path).copied();
+        }
 
         // 找到父节点并添加子节点
         if let Some(parent_indices) = path_index.get(&parent_path).cloned() {
@@ -335,6 +394,102 @@ fn sort_tree(node: &mut TreeNode) {
     });
 }
 
+/// 为被折叠的目录补算实际累计大小
+fn fill_collapsed_dir_sizes(node: &mut TreeNode, path: &Path) {
+    for child in &mut node.children {
+        if child.is_dir {
+            fill_collapsed_dir_sizes(child, &path.join(&child.name));
+        }
+    }
+    if node.collapsed_count.is_some() && node.children.is_empty() {
+        node.size = calculate_dir_size_fast(path);
+    }
+}
+
+/// 格式化文件大小（与前端 formatSize 一致）
+fn format_size(size: u64) -> String {
+    const UNITS: [&str; 5] = ["B", "KB", "MB", "GB", "TB"];
+    let mut s = size as f64;
+    let mut unit = 0;
+    while s >= 1024.0 && unit < UNITS.len() - 1 {
+        s /= 1024.0;
+        unit += 1;
+    }
+    if unit == 0 {
+        format!("{} {}", size, UNITS[0])
+    } else {
+        format!("{:.2} {}", s, UNITS[unit])
+    }
+}
+
+fn node_label(node: &TreeNode, show_size: bool) -> String {
+    let slash = if node.is_dir { "/" } else { "" };
+    if show_size {
+        format!("{}{} ({})", node.name, slash, format_size(node.size))
+    } else {
+        format!("{}{}", node.name, slash)
+    }
+}
+
+fn collapsed_label(node: &TreeNode) -> Option<String> {
+    match node.collapsed_count {
+        Some(count) if node.children.is_empty() => Some(format!("…（{} 项）", count)),
+        _ => None,
+    }
+}
+
+/// 渲染为带连接线的文本树
+fn render_tree_ascii(root: &TreeNode, show_size: bool) -> String {
+    fn walk(node: &TreeNode, prefix: &str, show_size: bool, output: &mut Vec<String>) {
+        let collapsed = collapsed_label(node);
+        let count = node.children.len() + usize::from(collapsed.is_some());
+        for (i, child) in node.children.iter().enumerate() {
+            let is_last = i + 1 == count;
+            let connector = if is_last { "└── " } else { "├── " };
+            output.push(format!(
+                "{}{}{}",
+                prefix,
+                connector,
+                node_label(child, show_size)
+            ));
+            let child_prefix = format!("{}{}", prefix, if is_last { "    " } else { "│   " });
+            walk(child, &child_prefix, show_size, output);
+        }
+        if let Some(label) = collapsed {
+            output.push(format!("{}└── {}", prefix, label));
+        }
+    }
+
+    let root_label = if show_size {
+        format!("{} ({})", root.name, format_size(root.size))
+    } else {
+        root.name.clone()
+    };
+    let mut output = vec![root_label];
+    walk(root, "", show_size, &mut output);
+    output.join("\n")
+}
+
+/// 渲染为 Markdown 缩进列表
+fn render_tree_markdown(root: &TreeNode, show_size: bool) -> String {
+    fn walk(node: &TreeNode, depth: usize, show_size: bool, output: &mut Vec<String>) {
+        let indent = "  ".repeat(depth);
+        let label = node_label(node, show_size);
+        let (name, rest) = label.split_at(node.name.len() + usize::from(node.is_dir));
+        output.push(format!("{}- `{}`{}", indent, name, rest));
+        for child in &node.children {
+            walk(child, depth + 1, show_size, output);
+        }
+        if let Some(label) = collapsed_label(node) {
+            output.push(format!("{}  - *{}*", indent, label));
+        }
+    }
+
+    let mut output = Vec::new();
+    walk(root, 0, show_size, &mut output);
+    output.join("\n")
+}
+
 // ============================================================================
 // macOS Spotlight 支持（可选）
 // ============================================================================
@@ -390,13 +545,22 @@ mod spotlight {
 /// - `show_hidden`: 是否显示隐藏文件
 /// - `max_depth`: 最大深度（0 表示无限制）
 /// - `ignore_patterns`: 忽略模式列表，特殊值 `__USE_GITIGNORE__` 表示启用 gitignore
+/// - `follow_links`: 是否跟随符号链接（默认不跟随）
+/// - `format`: 输出格式 `ascii`（默认）/ `markdown` / `json`
+/// - `show_size`: 是否在文本输出中标注文件大小和目录累计大小
+///
+/// 超过 `max_depth` 的目录不再展开，其直接子项数量记录在 `collapsed_count` 中。
 #[tauri::command]
+#[allow(clippy::too_many_arguments)]
 pub async fn generate_directory_tree(
     path: String,
     show_files: bool,
     show_hidden: bool,
     max_depth: usize,
     ignore_patterns: Vec<String>,
+    follow_links: Option<bool>,
+    format: Option<TreeOutputFormat>,
+    show_size: Option<bool>,
 ) -> Result<DirectoryTreeResult, String> {
     let root_path = PathBuf::from(&path);
 
@@ -416,12 +580,18 @@ pub async fn generate_directory_tree(
         .collect();
 
     // 并行收集文件列表
-    let (entries, total_dirs, total_files) = collect_entries_parallel(
+    let CollectedEntries {
+        entries,
+        collapsed,
+        total_dirs,
+        total_files,
+    } = collect_entries_parallel(
         &root_path,
         show_files,
         show_hidden,
         max_depth,
         use_gitignore,
+        follow_links.unwrap_or(false),
         &custom_patterns,
     )?;
 
@@ -432,10 +602,25 @@ pub async fn generate_directory_tree(
         .unwrap_or_else(|| path.clone());
 
     // 构建树形结构
-    let structure = build_tree_from_entries(root_name, entries);
+    let mut structure = build_tree_from_entries(root_name, entries, &collapsed);
+    structure.collapsed_count = collapsed.get(Path::new("")).copied();
+
+    let show_size = show_size.unwrap_or(false);
+    if show_size && !collapsed.is_empty() {
+        fill_collapsed_dir_sizes(&mut structure, &root_path);
+        calculate_dir_sizes(&mut structure);
+    }
+
+    let output = match format.unwrap_or_default() {
+        TreeOutputFormat::Ascii => render_tree_ascii(&structure, show_size),
+        TreeOutputFormat::Markdown => render_tree_markdown(&structure, show_size),
+        TreeOutputFormat::Json => serde_json::to_string_pretty(&structure)
+            .map_err(|e| format!("序列化目录树失败: {}", e))?,
+    };
 
     Ok(DirectoryTreeResult {
         structure,
+        output,
         stats: DirectoryTreeStats {
             total_dirs,
             total_files,
@@ -488,7 +673,7 @@ mod tests {
             },
         ];
 
-        let tree = build_tree_from_entries("root".to_string(), entries);
+        let tree = build_tree_from_entries("root".to_string(), entries, &HashMap::new());
 
         assert_eq!(tree.name, "root");
         assert!(tree.is_dir);
@@ -508,4 +693,63 @@ mod tests {
         // 根目录大小应该是所有文件大小之和
         assert_eq!(tree.size, 300);
     }
+
+    #[test]
+    fn test_depth_limit_collapses_directories() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        std::fs::create_dir_all(root.join("a/b/c")).unwrap();
+        std::fs::write(root.join("a/b/one.txt"), "1").unwrap();
+        std::fs::write(root.join("a/b/two.txt"), "2").unwrap();
+        std::fs::write(root.join("top.txt"), "t").unwrap();
+
+        let collected = collect_entries_parallel(root, true, false, 2, false, false, &[]).unwrap();
+        assert_eq!(collected.total_dirs, 2);
+        assert_eq!(collected.total_files, 1);
+
+        let tree =
+            build_tree_from_entries("root".to_string(), collected.entries, &collected.collapsed);
+        let b = &tree.children[0].children[0];
+        assert_eq!(b.name, "b");
+        assert!(b.children.is_empty());
+        assert_eq!(b.collapsed_count, Some(3));
+    }
+
+    #[test]
+    fn test_render_formats() {
+        let mut tree = build_tree_from_entries(
+            "root".to_string(),
+            vec![
+                FileEntry {
+                    relative_path: PathBuf::from("src"),
+                    is_dir: true,
+                    size: 0,
+                },
+                FileEntry {
+                    relative_path: PathBuf::from("src/main.rs"),
+                    is_dir: false,
+                    size: 1536,
+                },
+                FileEntry {
+                    relative_path: PathBuf::from("README.md"),
+                    is_dir: false,
+                    size: 10,
+                },
+            ],
+            &HashMap::new(),
+        );
+
+        assert_eq!(
+            render_tree_ascii(&tree, true),
+            "root (1.51 KB)\n├── src/ (1.50 KB)\n│   └── main.rs (1.50 KB)\n└── README.md (10 B)"
+        );
+        assert_eq!(
+            render_tree_markdown(&tree, false),
+            "- `root/`\n  - `src/`\n    - `main.rs`\n  - `README.md`"
+        );
+
+        tree.children[0].children.clear();
+        tree.children[0].collapsed_count = Some(7);
+        assert!(render_tree_ascii(&tree, false).contains("├── src/\n│   └── …（7 项）"));
+    }
 }
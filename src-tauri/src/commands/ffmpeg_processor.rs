@@ -13,21 +13,24 @@
 // limitations under the License.
 
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::path::Path;
 use std::process::Stdio;
 use std::sync::{Arc, Mutex};
+use std::time::Instant;
 use tauri::{Emitter, State};
-use tokio::process::{Child, Command};
+use tokio::process::Command;
+use tokio_util::sync::CancellationToken;
 
 pub struct FFmpegState {
-    pub active_processes: Arc<Mutex<HashMap<String, Child>>>,
+    /// 运行中任务的取消令牌，进程由各自的任务持有并在取消时终止
+    pub active_tasks: Arc<Mutex<HashMap<String, CancellationToken>>>,
 }
 
 impl Default for FFmpegState {
     fn default() -> Self {
         Self {
-            active_processes: Arc::new(Mutex::new(HashMap::new())),
+            active_tasks: Arc::new(Mutex::new(HashMap::new())),
         }
     }
 }
@@ -39,6 +42,61 @@ pub struct FFmpegProgress {
     pub current_time: f64,
     pub speed: String,
     pub bitrate: String,
+    /// 已处理的帧数 (纯音频任务没有)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub frame: Option<u64>,
+    /// 预计剩余时间 (秒)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub eta: Option<f64>,
+}
+
+impl FFmpegProgress {
+    /// 解析 ffmpeg stderr 中的一行进度信息，返回是否有字段更新
+    ///
+    /// `duration` 为输入总时长 (未知时为 0)，`elapsed` 为任务已运行的秒数，用于估算剩余时间。
+    fn apply_line(&mut self, line: &str, duration: f64, elapsed: f64) -> bool {
+        let field = |key: &str| {
+            line.find(key)
+                .and_then(|pos| line[pos + key.len()..].split_whitespace().next())
+        };
+
+        let mut updated = false;
+        if let Some(t) = field("time=").and_then(parse_ffmpeg_time) {
+            self.current_time = t;
+            if duration > 0.0 {
+                self.percent = (t / duration * 100.0).min(99.9);
+            }
+            updated = true;
+        }
+        if let Some(frame) = field("frame=").and_then(|s| s.parse::<u64>().ok()) {
+            self.frame = Some(frame);
+            updated = true;
+        }
+        if let Some(speed) = field("speed=") {
+            self.speed = speed.to_string();
+            updated = true;
+        }
+        if let Some(bitrate) = field("bitrate=") {
+            self.bitrate = bitrate.to_string();
+            updated = true;
+        }
+
+        if updated && duration > 0.0 {
+            let remaining = (duration - self.current_time).max(0.0);
+            let speed = self
+                .speed
+                .trim_end_matches('x')
+                .parse::<f64>()
+                .ok()
+                .filter(|s| *s > 0.0);
+            self.eta = match speed {
+                Some(speed) => Some(remaining / speed),
+                None if self.percent > 0.0 => Some(elapsed * (100.0 - self.percent) / self.percent),
+                None => None,
+            };
+        }
+        updated
+    }
 }
 
 #[derive(Serialize, Clone)]
@@ -95,6 +153,50 @@ pub struct CommandVersionInfo {
     pub error: Option<String>,
 }
 
+/// 前端压缩面板关心的编码器，用于判断当前 ffmpeg 构建是否支持
+const KEY_ENCODERS: &[(&str, &str)] = &[
+    ("libx264", "video"),
+    ("libx265", "video"),
+    ("libsvtav1", "video"),
+    ("libaom-av1", "video"),
+    ("libvpx-vp9", "video"),
+    ("h264_nvenc", "video"),
+    ("hevc_nvenc", "video"),
+    ("av1_nvenc", "video"),
+    ("h264_qsv", "video"),
+    ("hevc_qsv", "video"),
+    ("h264_amf", "video"),
+    ("hevc_amf", "video"),
+    ("h264_videotoolbox", "video"),
+    ("hevc_videotoolbox", "video"),
+    ("aac", "audio"),
+    ("libmp3lame", "audio"),
+    ("libopus", "audio"),
+    ("flac", "audio"),
+];
+
+#[derive(Serialize, Clone, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct FFmpegEncoderSupport {
+    pub name: String,
+    /// "video" | "audio"
+    pub kind: String,
+    pub available: bool,
+}
+
+/// FFmpeg 可用性与能力信息
+#[derive(Serialize, Clone, Debug, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct FFmpegCapabilities {
+    pub available: bool,
+    /// 版本号，如 "6.1.1"
+    pub version: Option<String>,
+    pub ffprobe_available: bool,
+    /// 关键编码器的可用性
+    pub encoders: Vec<FFmpegEncoderSupport>,
+    pub error: Option<String>,
+}
+
 #[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct FFProbeOutput {
     pub streams: Vec<FFProbeStream>,
@@ -151,15 +253,7 @@ pub async fn get_full_media_info(
     input_path: String,
     ffprobe_path: Option<String>,
 ) -> Result<FFProbeOutput, String> {
-    let ffprobe_path = ffprobe_path
-        .filter(|p| !p.trim().is_empty())
-        .map(std::path::PathBuf::from)
-        .unwrap_or_else(|| {
-            Path::new(&ffmpeg_path)
-                .parent()
-                .map(|p| p.join("ffprobe"))
-                .unwrap_or_else(|| Path::new("ffprobe").to_path_buf())
-        });
+    let ffprobe_path = resolve_ffprobe_path(&ffmpeg_path, ffprobe_path);
 
     let output = Command::new(ffprobe_path)
         .arg("-v")
@@ -186,6 +280,19 @@ pub async fn get_full_media_info(
     Ok(probe_data)
 }
 
+/// 未显式指定 ffprobe 时，默认取与 ffmpeg 同目录的 ffprobe
+fn resolve_ffprobe_path(ffmpeg_path: &str, ffprobe_path: Option<String>) -> std::path::PathBuf {
+    ffprobe_path
+        .filter(|p| !p.trim().is_empty())
+        .map(std::path::PathBuf::from)
+        .unwrap_or_else(|| {
+            Path::new(ffmpeg_path)
+                .parent()
+                .map(|p| p.join("ffprobe"))
+                .unwrap_or_else(|| Path::new("ffprobe").to_path_buf())
+        })
+}
+
 fn parse_ffmpeg_time(s: &str) -> Option<f64> {
     let parts: Vec<&str> = s.split(':').collect();
     if parts.len() == 3 {
@@ -267,15 +374,217 @@ async fn get_video_metadata(ffmpeg_path: &str, input_path: &str) -> MediaMetadat
 }
 
 /// 验证 FFmpeg 路径是否有效
-#[tauri::command]
-pub async fn check_ffmpeg_availability(path: String) -> bool {
-    let output = Command::new(&path).arg("-version").output().await;
+pub async fn is_ffmpeg_available(path: &str) -> bool {
+    let output = Command::new(path).arg("-version").output().await;
     match output {
         Ok(output) => output.status.success(),
         Err(_) => false,
     }
 }
 
+/// 从 `ffmpeg -version` 的首行解析版本号
+fn parse_ffmpeg_version(output: &str) -> Option<String> {
+    let line = output.lines().find(|l| l.contains("version"))?;
+    let rest = &line[line.find("version")? + "version".len()..];
+    rest.split_whitespace().next().map(|v| v.to_string())
+}
+
+/// 解析 `ffmpeg -encoders` 输出中的编码器名称
+fn parse_ffmpeg_encoders(output: &str) -> HashSet<String> {
+    output
+        .lines()
+        .skip_while(|line| !line.trim_start().starts_with("---"))
+        .skip(1)
+        .filter_map(|line| line.split_whitespace().nth(1).map(|s| s.to_string()))
+        .collect()
+}
+
+/// 检查 FFmpeg 可用性，并返回版本、ffprobe 以及关键编码器的支持情况
+#[tauri::command]
+pub async fn check_ffmpeg_availability(
+    path: String,
+    ffprobe_path: Option<String>,
+) -> FFmpegCapabilities {
+    let output = match Command::new(&path).arg("-version").output().await {
+        Ok(output) if output.status.success() => output,
+        Ok(output) => {
+            return FFmpegCapabilities {
+                error: Some(format!("FFmpeg exited with status: {}", output.status)),
+                ..Default::default()
+            }
+        }
+        Err(e) => {
+            return FFmpegCapabilities {
+                error: Some(format!("Failed to execute FFmpeg: {}", e)),
+                ..Default::default()
+            }
+        }
+    };
+    let version = parse_ffmpeg_version(&String::from_utf8_lossy(&output.stdout));
+
+    let ffprobe_path = resolve_ffprobe_path(&path, ffprobe_path);
+    let ffprobe_available = Command::new(&ffprobe_path)
+        .arg("-version")
+        .output()
+        .await
+        .map(|o| o.status.success())
+        .unwrap_or(false);
+
+    let found = match Command::new(&path)
+        .args(["-hide_banner", "-encoders"])
+        .output()
+        .await
+    {
+        Ok(output) => parse_ffmpeg_encoders(&String::from_utf8_lossy(&output.stdout)),
+        Err(e) => {
+            log::warn!("[FFmpeg] 获取编码器列表失败: {}", e);
+            HashSet::new()
+        }
+    };
+    let encoders = KEY_ENCODERS
+        .iter()
+        .map(|(name, kind)| FFmpegEncoderSupport {
+            name: name.to_string(),
+            kind: kind.to_string(),
+            available: found.contains(*name),
+        })
+        .collect();
+
+    FFmpegCapabilities {
+        available: true,
+        version,
+        ffprobe_available,
+        encoders,
+        error: None,
+    }
+}
+
+/// 构造截取单帧的 ffmpeg 参数
+///
+/// `timestamp` 为 None 时从结尾前 1 秒开始持续覆盖写入，最终保留最后一帧。
+fn build_frame_extract_args(
+    input_path: &str,
+    output_path: &str,
+    timestamp: Option<f64>,
+    width: Option<u32>,
+) -> Vec<String> {
+    let mut args = vec!["-hide_banner".to_string(), "-y".to_string()];
+    match timestamp {
+        Some(ts) => args.extend(["-ss".to_string(), format!("{:.3}", ts.max(0.0))]),
+        None => args.extend(["-sseof".to_string(), "-1".to_string()]),
+    }
+    args.extend(["-i".to_string(), input_path.to_string()]);
+    if let Some(width) = width.filter(|w| *w > 0) {
+        // 高度按比例缩放并保持偶数
+        args.extend(["-vf".to_string(), format!("scale={}:-2", width)]);
+    }
+    match timestamp {
+        Some(_) => args.extend(["-frames:v".to_string(), "1".to_string()]),
+        None => args.extend(["-update".to_string(), "1".to_string()]),
+    }
+    let is_jpeg = Path::new(output_path)
+        .extension()
+        .and_then(|e| e.to_str())
+        .is_some_and(|e| e.eq_ignore_ascii_case("jpg") || e.eq_ignore_ascii_case("jpeg"));
+    if is_jpeg {
+        args.extend(["-q:v".to_string(), "2".to_string()]);
+    }
+    args.push(output_path.to_string());
+    args
+}
+
+async fn run_frame_extract(ffmpeg_path: &str, args: &[String]) -> Result<(), String> {
+    let mut command = Command::new(ffmpeg_path);
+    #[cfg(target_os = "windows")]
+    {
+        const CREATE_NO_WINDOW: u32 = 0x08000000;
+        command.creation_flags(CREATE_NO_WINDOW);
+    }
+    let output = command
+        .args(args)
+        .stdin(Stdio::null())
+        .output()
+        .await
+        .map_err(|e| format!("Failed to execute FFmpeg: {}", e))?;
+
+    if output.status.success() {
+        Ok(())
+    } else {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        let reason = stderr
+            .lines()
+            .rev()
+            .find(|line| !line.trim().is_empty())
+            .unwrap_or("");
+        Err(format!(
+            "FFmpeg exited with code {:?}: {}",
+            output.status.code(),
+            reason
+        ))
+    }
+}
+
+/// 从视频中截取一帧保存为图片 (jpg/png)，可用作封面或缩略图
+///
+/// `timestamp_sec` 超过视频时长时回退到最后一帧；`width` 指定时按比例缩放。
+#[tauri::command]
+pub async fn extract_video_frame(
+    ffmpeg_path: String,
+    path: String,
+    timestamp_sec: f64,
+    output_path: String,
+    width: Option<u32>,
+) -> Result<String, String> {
+    if !is_ffmpeg_available(&ffmpeg_path).await {
+        return Err(format!(
+            "FFmpeg 不可用，请检查 FFmpeg 路径配置: {}",
+            ffmpeg_path
+        ));
+    }
+    if !Path::new(&path).exists() {
+        return Err(format!("Input file not found: {}", path));
+    }
+
+    let extension = Path::new(&output_path)
+        .extension()
+        .and_then(|e| e.to_str())
+        .map(|e| e.to_ascii_lowercase())
+        .unwrap_or_default();
+    if !matches!(extension.as_str(), "jpg" | "jpeg" | "png") {
+        return Err(format!("不支持的封面格式，仅支持 jpg/png: {}", output_path));
+    }
+    if let Some(parent) = Path::new(&output_path).parent() {
+        let _ = std::fs::create_dir_all(parent);
+    }
+
+    let duration = get_video_metadata(&ffmpeg_path, &path).await.duration;
+    let timestamp = match duration {
+        Some(d) if timestamp_sec >= d => None,
+        _ => Some(timestamp_sec),
+    };
+
+    // 先移除旧文件，以便根据输出是否存在判断是否截取成功
+    let _ = std::fs::remove_file(&output_path);
+    let args = build_frame_extract_args(&path, &output_path, timestamp, width);
+    log::info!("[FFmpeg] 截取视频帧: {} {}", ffmpeg_path, args.join(" "));
+    let result = run_frame_extract(&ffmpeg_path, &args).await;
+
+    // 时长未知或不准确时，指定时间点可能落在结尾之后而没有产出，改取最后一帧
+    let produced = result.is_ok() && Path::new(&output_path).exists();
+    if !produced && timestamp.is_some() {
+        log::warn!("[FFmpeg] 指定时间点未截取到画面，回退到最后一帧: {}", path);
+        let args = build_frame_extract_args(&path, &output_path, None, width);
+        run_frame_extract(&ffmpeg_path, &args).await?;
+    } else {
+        result?;
+    }
+
+    if !Path::new(&output_path).exists() {
+        return Err(format!("未能从视频中截取画面: {}", path));
+    }
+    Ok(output_path)
+}
+
 #[tauri::command]
 pub async fn check_command_version(
     path: String,
@@ -321,13 +630,13 @@ pub async fn kill_ffmpeg_process(
     state: State<'_, FFmpegState>,
     task_id: String,
 ) -> Result<(), String> {
-    let child = {
-        let mut processes = state.active_processes.lock().map_err(|e| e.to_string())?;
-        processes.remove(&task_id)
+    let token = {
+        let mut tasks = state.active_tasks.lock().map_err(|e| e.to_string())?;
+        tasks.remove(&task_id)
     };
 
-    if let Some(mut c) = child {
-        let _ = c.kill().await;
+    if let Some(token) = token {
+        token.cancel();
     }
     Ok(())
 }
@@ -339,6 +648,41 @@ pub struct FFmpegLogPayload {
     pub message: String,
 }
 
+/// 批量处理中的单个任务，各自带独立的输出参数
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FFmpegBatchTask {
+    pub task_id: String,
+    pub params: FFmpegParams,
+}
+
+/// 批量处理中单个任务的结果
+#[derive(Serialize, Clone, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct FFmpegBatchTaskResult {
+    pub task_id: String,
+    pub success: bool,
+    pub output_path: Option<String>,
+    pub error: Option<String>,
+}
+
+/// 批量处理的总体进度 (在每个任务开始和结束时发送)
+#[derive(Serialize, Clone, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct FFmpegBatchProgressPayload {
+    pub batch_id: String,
+    /// 当前任务在队列中的索引
+    pub current_index: usize,
+    pub current_task_id: String,
+    pub total: usize,
+    pub completed: usize,
+    pub failed: usize,
+    /// 按已结束任务数计算的总体百分比，当前任务的细粒度进度见 ffmpeg-progress
+    pub percent: f64,
+    /// 刚结束的任务结果，任务开始时为 None
+    pub result: Option<FFmpegBatchTaskResult>,
+}
+
 /// 统一媒体处理入口
 #[tauri::command]
 pub async fn process_media(
@@ -347,7 +691,113 @@ pub async fn process_media(
     window: tauri::Window,
     params: FFmpegParams,
 ) -> Result<String, String> {
-    let active_processes = state.active_processes.clone();
+    run_ffmpeg_task(
+        state.active_tasks.clone(),
+        task_id,
+        window,
+        params,
+        CancellationToken::new(),
+    )
+    .await
+}
+
+/// 批量媒体处理队列
+///
+/// 按顺序串行执行，单个任务失败只记录错误并继续下一个。
+/// 通过 `kill_ffmpeg_process(batch_id)` 可取消整个队列 (含正在运行的任务)，
+/// 取消单个 `task_id` 则只终止该任务。
+#[tauri::command]
+pub async fn process_media_batch(
+    state: State<'_, FFmpegState>,
+    batch_id: String,
+    window: tauri::Window,
+    tasks: Vec<FFmpegBatchTask>,
+) -> Result<Vec<FFmpegBatchTaskResult>, String> {
+    let active_tasks = state.active_tasks.clone();
+    let batch_token = CancellationToken::new();
+    {
+        let mut registry = active_tasks.lock().map_err(|e| e.to_string())?;
+        registry.insert(batch_id.clone(), batch_token.clone());
+    }
+
+    let total = tasks.len();
+    let mut results: Vec<FFmpegBatchTaskResult> = Vec::with_capacity(total);
+    let mut failed = 0;
+
+    for (index, task) in tasks.into_iter().enumerate() {
+        let emit_progress = |result: Option<FFmpegBatchTaskResult>, failed: usize| {
+            let completed = results.len() + usize::from(result.is_some());
+            let _ = window.emit(
+                "ffmpeg-batch-progress",
+                FFmpegBatchProgressPayload {
+                    batch_id: batch_id.clone(),
+                    current_index: index,
+                    current_task_id: task.task_id.clone(),
+                    total,
+                    completed,
+                    failed,
+                    percent: completed as f64 / total as f64 * 100.0,
+                    result,
+                },
+            );
+        };
+
+        let outcome = if batch_token.is_cancelled() {
+            Err("FFmpeg task cancelled".to_string())
+        } else {
+            emit_progress(None, failed);
+            run_ffmpeg_task(
+                active_tasks.clone(),
+                task.task_id.clone(),
+                window.clone(),
+                task.params,
+                batch_token.child_token(),
+            )
+            .await
+        };
+
+        let result = match outcome {
+            Ok(output_path) => FFmpegBatchTaskResult {
+                task_id: task.task_id.clone(),
+                success: true,
+                output_path: Some(output_path),
+                error: None,
+            },
+            Err(e) => {
+                log::warn!("[FFmpeg] 批量任务 {} 失败: {}", task.task_id, e);
+                failed += 1;
+                FFmpegBatchTaskResult {
+                    task_id: task.task_id.clone(),
+                    success: false,
+                    output_path: None,
+                    error: Some(e),
+                }
+            }
+        };
+        emit_progress(Some(result.clone()), failed);
+        results.push(result);
+    }
+
+    {
+        let mut registry = active_tasks.lock().map_err(|e| e.to_string())?;
+        registry.remove(&batch_id);
+    }
+    log::info!(
+        "[FFmpeg] 批量任务完成: {} 个任务，失败 {} 个",
+        total,
+        failed
+    );
+    Ok(results)
+}
+
+/// 执行单个 FFmpeg 任务，`cancel_token` 被取消时终止子进程
+async fn run_ffmpeg_task(
+    active_tasks: Arc<Mutex<HashMap<String, CancellationToken>>>,
+    task_id: String,
+    window: tauri::Window,
+    params: FFmpegParams,
+    cancel_token: CancellationToken,
+) -> Result<String, String> {
     let ffmpeg_path = params.ffmpeg_path.clone();
     let input_path = params.input_path.clone();
     let output_path = params.output_path.clone();
@@ -457,8 +907,10 @@ pub async fn process_media(
 
     command
         .args(&args)
+        .stdin(Stdio::null()) // 避免 ffmpeg 等待交互输入而卡住
         .stderr(Stdio::piped())
-        .stdout(Stdio::null()); // 进度解析改用 stderr，不再需要 stdout
+        .stdout(Stdio::null()) // 进度解析改用 stderr，不再需要 stdout
+        .kill_on_drop(true);
 
     let mut child = command
         .spawn()
@@ -466,11 +918,12 @@ pub async fn process_media(
 
     let stderr = child.stderr.take().ok_or("Failed to open stderr")?;
 
-    // 记录进程
+    // 登记取消令牌，进程本身留在当前任务中等待
     {
-        let mut processes = active_processes.lock().map_err(|e| e.to_string())?;
-        processes.insert(task_id.clone(), child);
+        let mut tasks = active_tasks.lock().map_err(|e| e.to_string())?;
+        tasks.insert(task_id.clone(), cancel_token.clone());
     }
+    let started_at = Instant::now();
 
     let task_id_clone = task_id.clone();
     let window_clone = window.clone();
@@ -481,6 +934,8 @@ pub async fn process_media(
         current_time: 0.0,
         speed: "0x".to_string(),
         bitrate: "0kbps".to_string(),
+        frame: None,
+        eta: None,
     }));
 
     // 处理 stderr (日志 + 进度解析)
@@ -525,41 +980,13 @@ pub async fn process_media(
                     );
 
                     // 2. 解析进度
-                    let mut updated = false;
                     let mut progress = {
                         let p = last_progress_for_stderr.lock().unwrap();
                         p.clone()
                     };
+                    let elapsed = started_at.elapsed().as_secs_f64();
 
-                    if let Some(pos) = line.find("time=") {
-                        let rest = line[pos + 5..].trim_start();
-                        let time_str = rest.split_whitespace().next().unwrap_or("");
-                        if let Some(t) = parse_ffmpeg_time(time_str) {
-                            progress.current_time = t;
-                            if duration > 0.0 {
-                                progress.percent = (t / duration * 100.0).min(99.9);
-                            }
-                            updated = true;
-                        }
-                    }
-
-                    if let Some(pos) = line.find("speed=") {
-                        let rest = line[pos + 6..].trim_start();
-                        progress.speed = rest.split_whitespace().next().unwrap_or("0x").to_string();
-                        updated = true;
-                    }
-
-                    if let Some(pos) = line.find("bitrate=") {
-                        let rest = line[pos + 8..].trim_start();
-                        progress.bitrate = rest
-                            .split_whitespace()
-                            .next()
-                            .unwrap_or("0kbps")
-                            .to_string();
-                        updated = true;
-                    }
-
-                    if updated {
+                    if progress.apply_line(&line, duration, elapsed) {
                         // 更新共享状态
                         {
                             let mut p = last_progress_for_stderr.lock().unwrap();
@@ -581,16 +1008,24 @@ pub async fn process_media(
         }
     });
 
-    // 取回进程并等待
-    let mut child = {
-        let mut processes = active_processes.lock().map_err(|e| e.to_string())?;
-        processes.remove(&task_id_clone).ok_or("Process lost")?
+    // 等待进程结束或被取消
+    let wait_result = tokio::select! {
+        status = child.wait() => Some(status),
+        _ = cancel_token.cancelled() => None,
     };
+    {
+        let mut tasks = active_tasks.lock().map_err(|e| e.to_string())?;
+        tasks.remove(&task_id_clone);
+    }
 
-    let status = child
-        .wait()
-        .await
-        .map_err(|e| format!("Wait failed: {}", e))?;
+    let status = match wait_result {
+        Some(status) => status.map_err(|e| format!("Wait failed: {}", e))?,
+        None => {
+            let _ = child.kill().await;
+            log::info!("[FFmpeg] 任务已取消: {}", task_id_clone);
+            return Err("FFmpeg task cancelled".to_string());
+        }
+    };
 
     if status.success() {
         // 任务成功后，发送 100% 进度，并保留最后一次解析到的速率和比特率
@@ -600,6 +1035,7 @@ pub async fn process_media(
         };
         final_progress.percent = 100.0;
         final_progress.current_time = duration;
+        final_progress.eta = Some(0.0);
 
         let _ = window_clone.emit(
             "ffmpeg-progress",
@@ -613,3 +1049,61 @@ pub async fn process_media(
         Err(format!("FFmpeg exited with code: {:?}", status.code()))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_version_and_encoders() {
+        let version =
+            "ffmpeg version 6.1.1-full_build-www.gyan.dev Copyright (c) 2000-2023\nbuilt with gcc";
+        assert_eq!(
+            parse_ffmpeg_version(version),
+            Some("6.1.1-full_build-www.gyan.dev".to_string())
+        );
+
+        let encoders = "Encoders:\n V..... = Video\n A..... = Audio\n ------\n V....D libx264              libx264 H.264\n A....D aac                  AAC (Advanced Audio Coding)\n";
+        let parsed = parse_ffmpeg_encoders(encoders);
+        assert_eq!(parsed.len(), 2);
+        assert!(parsed.contains("libx264") && parsed.contains("aac"));
+    }
+
+    #[test]
+    fn test_frame_extract_args() {
+        let args = build_frame_extract_args("in.mp4", "cover.JPG", Some(12.5), Some(320));
+        assert_eq!(
+            args.join(" "),
+            "-hide_banner -y -ss 12.500 -i in.mp4 -vf scale=320:-2 -frames:v 1 -q:v 2 cover.JPG"
+        );
+
+        let args = build_frame_extract_args("in.mp4", "cover.png", None, None);
+        assert_eq!(
+            args.join(" "),
+            "-hide_banner -y -sseof -1 -i in.mp4 -update 1 cover.png"
+        );
+    }
+
+    #[test]
+    fn test_progress_line_parsing() {
+        let mut progress = FFmpegProgress::default();
+        let line = "frame= 1200 fps= 60 q=28.0 size=   10240kB time=00:00:50.00 bitrate=1677.7kbits/s speed=2.00x";
+        assert!(progress.apply_line(line, 100.0, 25.0));
+        assert_eq!(progress.frame, Some(1200));
+        assert_eq!(progress.current_time, 50.0);
+        assert_eq!(progress.percent, 50.0);
+        assert_eq!(progress.bitrate, "1677.7kbits/s");
+        assert_eq!(progress.eta, Some(25.0));
+
+        // 速度未知时按已用时间估算
+        let mut progress = FFmpegProgress::default();
+        assert!(progress.apply_line(
+            "size=N/A time=00:00:25.00 bitrate=N/A speed=N/A",
+            100.0,
+            10.0
+        ));
+        assert_eq!(progress.eta, Some(30.0));
+
+        assert!(!progress.apply_line("Stream mapping:", 100.0, 10.0));
+    }
+}
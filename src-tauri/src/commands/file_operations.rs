@@ -13,23 +13,29 @@
 // limitations under the License.
 
 use fs_extra;
+use ignore::overrides::OverrideBuilder;
+use ignore::WalkBuilder;
 use lazy_static::lazy_static;
+use rayon::prelude::*;
 use regex::Regex;
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 use std::collections::HashMap;
 use std::fs;
-use std::io::{Read, Write};
+use std::io::{self, Read, Write};
 #[cfg(windows)]
 use std::path::Component;
 use std::path::{Path, PathBuf};
-use std::sync::{Arc, Mutex};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex, Once};
 use std::time::Instant;
 use std::time::SystemTime;
 use tauri::{AppHandle, Emitter};
 use tokio_util::sync::CancellationToken;
 use zip::ZipArchive;
 
+use crate::error::{AppError, AppResult, ResultExt};
+
 // ==================== 插件清单类型 ====================
 // 注意：这些结构体是根据 TypeScript 定义手动转换的，用于 manifest.json 的反序列化
 
@@ -111,6 +117,17 @@ pub struct PluginManifest {
     pub ui: Option<PluginUiConfig>,
     pub contributions: Option<Vec<Value>>,
     pub permissions: Option<Vec<String>>,
+    pub sha256: Option<PluginChecksum>,
+    pub signature: Option<String>,
+}
+
+/// 插件包校验和：字符串为除 manifest.json 外全部文件的整体摘要，对象为关键文件摘要
+/// 路径均相对于 manifest.json 所在目录
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(untagged)]
+pub enum PluginChecksum {
+    Archive(String),
+    Files(HashMap<String, String>),
 }
 
 // 进度事件结构体
@@ -139,27 +156,129 @@ pub struct OperationLog {
     pub source_paths: Vec<String>,    // 源文件路径列表
     pub total_size: u64,              // 总文件大小（字节）
     pub processed_files: Vec<String>, // 成功处理的文件名列表
+    #[serde(default)]
+    pub applied_policies: Vec<AppliedConflictPolicy>, // 每个文件实际采用的冲突策略（仅 move 操作）
+}
+
+// 单个文件实际采用的冲突策略
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AppliedConflictPolicy {
+    pub source_path: String,
+    pub target_path: String,
+    pub policy: String, // "none"（无冲突） | "skip" | "overwrite" | "rename"
 }
 
-// 全局操作日志历史（最多保存100条）
+// 操作日志最多保留的条数（内存与磁盘文件一致）
+const MAX_OPERATION_LOGS: usize = 100;
+
+// 全局操作日志历史（最多保存 MAX_OPERATION_LOGS 条），首次访问时从磁盘加载
 lazy_static! {
     static ref OPERATION_LOGS: Mutex<Vec<OperationLog>> = Mutex::new(Vec::new());
 }
+static OPERATION_LOGS_LOADED: Once = Once::new();
+// 日志文件当前行数，加载历史时读取一次，之后随写入在内存中更新
+static OPERATION_LOG_FILE_LINES: AtomicUsize = AtomicUsize::new(0);
+
+// 操作日志持久化文件：app_data_dir/logs/operations.jsonl
+fn get_operation_log_path(app: &AppHandle) -> PathBuf {
+    crate::get_app_data_dir(app.config())
+        .join("logs")
+        .join("operations.jsonl")
+}
+
+// 解析 JSONL 内容，跳过损坏的行，只保留最近 limit 条
+fn parse_operation_logs(content: &str, limit: usize) -> Vec<OperationLog> {
+    let mut logs: Vec<OperationLog> = content
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .filter_map(|line| match serde_json::from_str::<OperationLog>(line) {
+            Ok(log) => Some(log),
+            Err(e) => {
+                log::warn!("跳过损坏的操作日志行: {}", e);
+                None
+            }
+        })
+        .collect();
+    if logs.len() > limit {
+        logs.drain(..logs.len() - limit);
+    }
+    logs
+}
+
+// 首次访问时从磁盘加载历史日志
+fn ensure_operation_logs_loaded(app: &AppHandle) {
+    OPERATION_LOGS_LOADED.call_once(|| {
+        let path = get_operation_log_path(app);
+        let loaded = match fs::read_to_string(&path) {
+            Ok(content) => {
+                OPERATION_LOG_FILE_LINES.store(content.lines().count(), Ordering::SeqCst);
+                parse_operation_logs(&content, MAX_OPERATION_LOGS)
+            }
+            Err(_) => Vec::new(),
+        };
+        if let Ok(mut logs) = OPERATION_LOGS.lock() {
+            *logs = loaded;
+        }
+    });
+}
+
+// 将日志以 JSONL 追加写入磁盘；文件行数超过上限两倍时按内存中的最近记录重写
+//
+// 调用方需持有 OPERATION_LOGS 锁，保证行数计数与文件一致
+fn persist_operation_log(app: &AppHandle, log: &OperationLog, recent: &[OperationLog]) {
+    let path = get_operation_log_path(app);
+    let result = (|| -> Result<(), String> {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent).map_err(|e| format!("创建日志目录失败: {}", e))?;
+        }
+
+        let line_count = OPERATION_LOG_FILE_LINES.load(Ordering::SeqCst);
+        if line_count >= MAX_OPERATION_LOGS * 2 {
+            let mut content = String::new();
+            for entry in recent {
+                let line =
+                    serde_json::to_string(entry).map_err(|e| format!("序列化日志失败: {}", e))?;
+                content.push_str(&line);
+                content.push('\n');
+            }
+            fs::write(&path, content).map_err(|e| format!("重写日志文件失败: {}", e))?;
+            OPERATION_LOG_FILE_LINES.store(recent.len(), Ordering::SeqCst);
+        } else {
+            let line = serde_json::to_string(log).map_err(|e| format!("序列化日志失败: {}", e))?;
+            let mut file = fs::OpenOptions::new()
+                .create(true)
+                .append(true)
+                .open(&path)
+                .map_err(|e| format!("打开日志文件失败: {}", e))?;
+            writeln!(file, "{}", line).map_err(|e| format!("写入日志文件失败: {}", e))?;
+            OPERATION_LOG_FILE_LINES.fetch_add(1, Ordering::SeqCst);
+        }
+        Ok(())
+    })();
+
+    if let Err(e) = result {
+        log::warn!("持久化操作日志失败: {}", e);
+    }
+}
 
 // 添加操作日志
-fn add_operation_log(log: OperationLog) {
+fn add_operation_log(app: &AppHandle, log: OperationLog) {
+    ensure_operation_logs_loaded(app);
     if let Ok(mut logs) = OPERATION_LOGS.lock() {
-        logs.push(log);
-        // 只保留最近100条
-        if logs.len() > 100 {
+        logs.push(log.clone());
+        // 只保留最近 MAX_OPERATION_LOGS 条
+        if logs.len() > MAX_OPERATION_LOGS {
             logs.remove(0);
         }
+        persist_operation_log(app, &log, &logs);
     }
 }
 
 // 获取最新的操作日志
 #[tauri::command]
-pub fn get_latest_operation_log() -> Option<OperationLog> {
+pub fn get_latest_operation_log(app: AppHandle) -> Option<OperationLog> {
+    ensure_operation_logs_loaded(&app);
     if let Ok(logs) = OPERATION_LOGS.lock() {
         logs.last().cloned()
     } else {
@@ -169,7 +288,8 @@ pub fn get_latest_operation_log() -> Option<OperationLog> {
 
 // 获取所有操作日志
 #[tauri::command]
-pub fn get_all_operation_logs() -> Vec<OperationLog> {
+pub fn get_all_operation_logs(app: AppHandle) -> Vec<OperationLog> {
+    ensure_operation_logs_loaded(&app);
     if let Ok(logs) = OPERATION_LOGS.lock() {
         logs.clone()
     } else {
@@ -257,6 +377,109 @@ fn build_regex_with_flags(pattern: &str, flags: &str) -> Result<Regex, regex::Er
     builder.build()
 }
 
+// fancy-regex 回退时的提示文案
+const FANCY_REGEX_WARNING: &str = "将使用 fancy-regex（性能较低）";
+
+/// 规则使用的正则引擎：标准 regex 不支持前瞻/后瞻和反向引用时回退到 fancy-regex
+enum RuleRegex {
+    Standard(Regex),
+    Fancy(fancy_regex::Regex),
+}
+
+impl RuleRegex {
+    fn count_matches(&self, text: &str) -> usize {
+        match self {
+            RuleRegex::Standard(re) => re.find_iter(text).count(),
+            // fancy-regex 回溯超限时返回 Err，按不匹配处理
+            RuleRegex::Fancy(re) => re.find_iter(text).filter(|m| m.is_ok()).count(),
+        }
+    }
+
+    fn replace_all(&self, text: &str, replacement: &str) -> Result<String, String> {
+        match self {
+            RuleRegex::Standard(re) => Ok(re.replace_all(text, replacement).to_string()),
+            RuleRegex::Fancy(re) => re
+                .try_replacen(text, 0, replacement)
+                .map(|s| s.to_string())
+                .map_err(|e| format!("fancy-regex 替换失败: {}", e)),
+        }
+    }
+
+    /// 用第一个匹配的捕获组展开模板
+    fn expand_first(&self, text: &str, template: &str) -> Option<String> {
+        let mut expanded = String::new();
+        match self {
+            RuleRegex::Standard(re) => re.captures(text)?.expand(template, &mut expanded),
+            RuleRegex::Fancy(re) => re.captures(text).ok()??.expand(template, &mut expanded),
+        }
+        Some(expanded)
+    }
+
+    /// 前 limit 个匹配的 (起始, 结束, 替换后文本)
+    fn expanded_matches(
+        &self,
+        text: &str,
+        replacement: &str,
+        limit: usize,
+    ) -> Vec<(usize, usize, String)> {
+        match self {
+            RuleRegex::Standard(re) => re
+                .captures_iter(text)
+                .take(limit)
+                .filter_map(|caps| {
+                    let m = caps.get(0)?;
+                    let mut replaced = String::new();
+                    caps.expand(replacement, &mut replaced);
+                    Some((m.start(), m.end(), replaced))
+                })
+                .collect(),
+            RuleRegex::Fancy(re) => re
+                .captures_iter(text)
+                .take(limit)
+                .filter_map(|caps| {
+                    let caps = caps.ok()?;
+                    let m = caps.get(0)?;
+                    let mut replaced = String::new();
+                    caps.expand(replacement, &mut replaced);
+                    Some((m.start(), m.end(), replaced))
+                })
+                .collect(),
+        }
+    }
+}
+
+/// 判断标准引擎的编译错误是否可由 fancy-regex 支持（前瞻/后瞻、反向引用）
+fn is_fancy_regex_feature_error(error: &regex::Error) -> bool {
+    let error_str = error.to_string();
+    error_str.contains("look-around")
+        || error_str.contains("look-ahead")
+        || error_str.contains("look-behind")
+        || error_str.contains("backreference")
+}
+
+/// 用 fancy-regex 编译，flags 以内联形式 `(?ims)` 传入
+fn build_fancy_regex_with_flags(pattern: &str, flags: &str) -> Result<fancy_regex::Regex, String> {
+    let inline_flags: String = flags.chars().filter(|c| "imsx".contains(*c)).collect();
+    let full_pattern = if inline_flags.is_empty() {
+        pattern.to_string()
+    } else {
+        format!("(?{}){}", inline_flags, pattern)
+    };
+    fancy_regex::Regex::new(&full_pattern).map_err(|e| e.to_string())
+}
+
+/// 编译规则正则：优先标准引擎，遇到不支持的语法时回退 fancy-regex
+/// 返回的 bool 表示是否使用了 fancy-regex
+fn build_rule_regex(pattern: &str, flags: &str) -> Result<(RuleRegex, bool), regex::Error> {
+    match build_regex_with_flags(pattern, flags) {
+        Ok(re) => Ok((RuleRegex::Standard(re), false)),
+        Err(e) if is_fancy_regex_feature_error(&e) => build_fancy_regex_with_flags(pattern, flags)
+            .map(|re| (RuleRegex::Fancy(re), true))
+            .map_err(|_| e),
+        Err(e) => Err(e),
+    }
+}
+
 // 日志条目结构体
 #[derive(Serialize, Clone)]
 pub struct LogEntry {
@@ -273,6 +496,39 @@ pub struct ProcessResult {
     pub duration_ms: f64,
     pub errors: HashMap<String, String>,
     pub logs: Vec<LogEntry>,
+    pub previews: Vec<FilePreview>, // 仅 dry-run 模式下填充
+}
+
+// dry-run 模式下每个文件最多展示的替换位置数
+const DRY_RUN_PREVIEW_LIMIT: usize = 5;
+// 替换位置前后保留的上下文字符数
+const PREVIEW_CONTEXT_CHARS: usize = 30;
+
+// dry-run 预览：单个替换位置的上下文片段
+#[derive(Serialize, Clone)]
+pub struct ReplacementPreview {
+    pub rule_index: usize,
+    pub line: usize,
+    pub before: String,
+    pub after: String,
+}
+
+// dry-run 预览：单个文件的处理结果
+#[derive(Serialize, Clone)]
+pub struct FilePreview {
+    pub file_path: String,
+    pub output_path: String,
+    pub matches: usize,
+    pub original_len: usize,
+    pub final_len: usize,
+    pub len_diff: i64,
+    pub samples: Vec<ReplacementPreview>,
+}
+
+// 单个文件的处理结果
+struct FileProcessOutcome {
+    matches: usize,
+    preview: Option<FilePreview>,
 }
 
 // Tauri 命令：批量处理文件应用正则规则
@@ -283,10 +539,14 @@ pub async fn process_files_with_regex(
     rules: Vec<RegexRule>,
     force_txt: Option<bool>,
     filename_suffix: Option<String>,
+    dry_run: Option<bool>,
+    ignore_patterns: Option<Vec<String>>,
 ) -> Result<ProcessResult, String> {
     let start_time = Instant::now();
     let force_txt = force_txt.unwrap_or(false);
+    let dry_run = dry_run.unwrap_or(false);
     let filename_suffix = filename_suffix.unwrap_or_default();
+    let ignore_patterns = ignore_patterns.unwrap_or_default();
     let output_path = PathBuf::from(&output_dir);
     let mut logs = Vec::new();
 
@@ -303,16 +563,19 @@ pub async fn process_files_with_regex(
     add_log(format!("输出目录: {}", output_dir), "info");
     add_log(format!("规则数量: {}", rules.len()), "info");
     add_log(format!("强制 TXT: {}", force_txt), "info");
+    if dry_run {
+        add_log("预览模式: 不会写入任何文件".to_string(), "info");
+    }
     if !filename_suffix.is_empty() {
         add_log(format!("文件后缀: {}", filename_suffix), "info");
     }
 
-    // 确保输出目录存在
-    if !output_path.exists() {
+    // 确保输出目录存在（预览模式不创建）
+    if !output_path.exists() && !dry_run {
         fs::create_dir_all(&output_path).map_err(|e| format!("创建输出目录失败: {}", e))?;
     }
 
-    if !output_path.is_dir() {
+    if output_path.exists() && !output_path.is_dir() {
         return Err(format!("输出路径不是目录: {}", output_dir));
     }
 
@@ -350,8 +613,11 @@ pub async fn process_files_with_regex(
             "info",
         );
 
-        match build_regex_with_flags(&pattern, &flags) {
-            Ok(r) => {
+        match build_rule_regex(&pattern, &flags) {
+            Ok((r, uses_fancy)) => {
+                if uses_fancy {
+                    add_log(format!("⚠ {}: {}", rule_label, FANCY_REGEX_WARNING), "warn");
+                }
                 compiled_rules_vec.push((r, rule.replacement.clone(), pattern, flags));
             }
             Err(e) => {
@@ -386,57 +652,82 @@ pub async fn process_files_with_regex(
         if path.is_file() {
             all_files.push(path);
         } else if path.is_dir() {
-            collect_files_recursive(&path, &mut all_files)?;
+            collect_files_recursive(&path, &ignore_patterns, &mut all_files)?;
         }
     }
     add_log(format!("找到 {} 个文件待处理", all_files.len()), "info");
 
-    // 处理每个文件
+    // 并行处理每个文件（日志顺序不保证，每条都带文件名以便排查）
     add_log("--- 处理文件 ---".to_string(), "info");
-    let mut success_count = 0;
-    let mut error_count = 0;
-    let mut errors = HashMap::new();
-    let mut total_matches = 0;
+    let total_files = all_files.len();
+    let success_counter = AtomicUsize::new(0);
+    let error_counter = AtomicUsize::new(0);
+    let match_counter = AtomicUsize::new(0);
+    let errors_map: Mutex<HashMap<String, String>> = Mutex::new(HashMap::new());
+    let file_logs: Mutex<Vec<LogEntry>> = Mutex::new(Vec::new());
+    let file_previews: Mutex<Vec<FilePreview>> = Mutex::new(Vec::new());
+
+    all_files
+        .par_iter()
+        .enumerate()
+        .for_each(|(idx, file_path)| {
+            let file_name = file_path.display().to_string();
+            let entry = match process_single_file(
+                file_path,
+                &output_path,
+                &compiled_rules_vec,
+                force_txt,
+                &filename_suffix,
+                dry_run,
+            ) {
+                Ok(FileProcessOutcome { matches, preview }) => {
+                    if let Some(preview) = preview {
+                        if let Ok(mut previews) = file_previews.lock() {
+                            previews.push(preview);
+                        }
+                    }
+                    success_counter.fetch_add(1, Ordering::Relaxed);
+                    match_counter.fetch_add(matches, Ordering::Relaxed);
+                    LogEntry {
+                        message: format!(
+                            "[{}/{}] {}: 成功 (匹配 {} 次)",
+                            idx + 1,
+                            total_files,
+                            file_name,
+                            matches
+                        ),
+                        level: "info".to_string(),
+                    }
+                }
+                Err(e) => {
+                    error_counter.fetch_add(1, Ordering::Relaxed);
+                    let message =
+                        format!("[{}/{}] {}: 失败 - {}", idx + 1, total_files, file_name, e);
+                    if let Ok(mut errors) = errors_map.lock() {
+                        errors.insert(file_name, e);
+                    }
+                    LogEntry {
+                        message,
+                        level: "error".to_string(),
+                    }
+                }
+            };
 
-    for (idx, file_path) in all_files.iter().enumerate() {
-        let file_name = file_path.display().to_string();
-        match process_single_file(
-            file_path,
-            &output_path,
-            &compiled_rules_vec,
-            force_txt,
-            &filename_suffix,
-        ) {
-            Ok(matches) => {
-                success_count += 1;
-                total_matches += matches;
-                add_log(
-                    format!(
-                        "[{}/{}] {}: 成功 (匹配 {} 次)",
-                        idx + 1,
-                        all_files.len(),
-                        file_name,
-                        matches
-                    ),
-                    "info",
-                );
+            log::debug!("{}", entry.message);
+            if let Ok(mut logs) = file_logs.lock() {
+                logs.push(entry);
             }
-            Err(e) => {
-                error_count += 1;
-                errors.insert(file_name.clone(), e.clone());
-                add_log(
-                    format!(
-                        "[{}/{}] {}: 失败 - {}",
-                        idx + 1,
-                        all_files.len(),
-                        file_name,
-                        e
-                    ),
-                    "error",
-                );
-            }
-        }
+        });
+
+    for entry in file_logs.into_inner().unwrap_or_default() {
+        add_log(entry.message, &entry.level);
     }
+    let success_count = success_counter.into_inner();
+    let error_count = error_counter.into_inner();
+    let total_matches = match_counter.into_inner();
+    let errors = errors_map.into_inner().unwrap_or_default();
+    let mut previews = file_previews.into_inner().unwrap_or_default();
+    previews.sort_by(|a, b| a.file_path.cmp(&b.file_path));
 
     let duration = start_time.elapsed();
     let duration_ms = duration.as_secs_f64() * 1000.0;
@@ -458,37 +749,61 @@ pub async fn process_files_with_regex(
         duration_ms,
         errors,
         logs,
+        previews,
     })
 }
 
-// 递归收集目录中的所有文件
-fn collect_files_recursive(dir: &Path, files: &mut Vec<PathBuf>) -> Result<(), String> {
-    if dir.is_dir() {
-        let entries =
-            fs::read_dir(dir).map_err(|e| format!("读取目录失败 {}: {}", dir.display(), e))?;
-
-        for entry in entries {
-            let entry = entry.map_err(|e| format!("读取目录项失败: {}", e))?;
-            let path = entry.path();
-
-            if path.is_file() {
-                files.push(path);
-            } else if path.is_dir() {
-                collect_files_recursive(&path, files)?;
+// 递归收集目录中的所有文件（尊重 .gitignore，并跳过 ignore_patterns 匹配的路径）
+fn collect_files_recursive(
+    dir: &Path,
+    ignore_patterns: &[String],
+    files: &mut Vec<PathBuf>,
+) -> Result<(), String> {
+    if !dir.is_dir() {
+        return Ok(());
+    }
+
+    let mut builder = WalkBuilder::new(dir);
+    builder.hidden(false); // 隐藏文件是否处理交给忽略规则决定
+    builder.require_git(false); // 非 git 仓库中的 .gitignore 同样生效
+    builder.git_global(false); // 不使用全局 gitignore
+    builder.follow_links(false); // 不跟随符号链接
+    builder.filter_entry(|entry| entry.file_name() != ".git");
+
+    if !ignore_patterns.is_empty() {
+        let mut override_builder = OverrideBuilder::new(dir);
+        for pattern in ignore_patterns {
+            let trimmed = pattern.trim();
+            if !trimmed.is_empty() {
+                override_builder
+                    .add(&format!("!{}", trimmed))
+                    .map_err(|e| format!("无效的忽略模式 '{}': {}", trimmed, e))?;
             }
         }
+        let overrides = override_builder
+            .build()
+            .map_err(|e| format!("构建忽略规则失败: {}", e))?;
+        builder.overrides(overrides);
+    }
+
+    for entry in builder.build() {
+        let entry = entry.map_err(|e| format!("读取目录项失败: {}", e))?;
+        if entry.file_type().is_some_and(|t| t.is_file()) {
+            files.push(entry.into_path());
+        }
     }
     Ok(())
 }
 
-// 处理单个文件，返回总匹配次数
+// 处理单个文件；dry_run 时不写入，而是返回预览信息
 fn process_single_file(
     file_path: &Path,
     output_dir: &Path,
-    rules: &[(Regex, String, String, String)], // (regex, replacement, pattern, flags)
+    rules: &[(RuleRegex, String, String, String)], // (regex, replacement, pattern, flags)
     force_txt: bool,
     filename_suffix: &str,
-) -> Result<usize, String> {
+    dry_run: bool,
+) -> Result<FileProcessOutcome, String> {
     // 读取文件内容
     let content = fs::read_to_string(file_path).map_err(|e| format!("读取文件失败: {}", e))?;
 
@@ -497,14 +812,23 @@ fn process_single_file(
     // 应用所有正则规则
     let mut processed = content.clone();
     let mut total_matches = 0;
+    let mut samples = Vec::new();
 
-    for (regex, replacement, _pattern, _flags) in rules {
+    for (rule_index, (regex, replacement, _pattern, _flags)) in rules.iter().enumerate() {
         let before = processed.clone();
-        let matches = regex.find_iter(&before).count();
+        let matches = regex.count_matches(&before);
         if matches > 0 {
-            processed = regex
-                .replace_all(&processed, replacement.as_str())
-                .to_string();
+            if dry_run && samples.len() < DRY_RUN_PREVIEW_LIMIT {
+                collect_replacement_samples(
+                    &before,
+                    regex,
+                    replacement,
+                    rule_index,
+                    DRY_RUN_PREVIEW_LIMIT - samples.len(),
+                    &mut samples,
+                );
+            }
+            processed = regex.replace_all(&processed, replacement)?;
             total_matches += matches;
         }
     }
@@ -527,20 +851,52 @@ fn process_single_file(
             .unwrap_or_else(|| "txt".to_string())
     };
 
-    let final_name = if filename_suffix.is_empty() {
+    // 后缀中含 `$` 时视为模板，用第一条匹配规则的捕获组填充（如 `_${1}`）
+    let resolved_suffix = if filename_suffix.contains('$') {
+        match expand_filename_template(&content, rules, filename_suffix) {
+            Some(expanded) => expanded,
+            None => {
+                log::warn!(
+                    "文件名模板 '{}' 在 {} 中没有匹配，回退到原始文件名",
+                    filename_suffix,
+                    file_path.display()
+                );
+                String::new()
+            }
+        }
+    } else {
+        filename_suffix.to_string()
+    };
+
+    let final_name = if resolved_suffix.is_empty() {
         format!("{}.{}", original_name, extension)
     } else {
-        format!("{}{}.{}", original_name, filename_suffix, extension)
+        format!("{}{}.{}", original_name, resolved_suffix, extension)
     };
 
     let output_file = output_dir.join(final_name);
+    let len_diff = final_len as i64 - original_len as i64;
+
+    if dry_run {
+        return Ok(FileProcessOutcome {
+            matches: total_matches,
+            preview: Some(FilePreview {
+                file_path: file_path.display().to_string(),
+                output_path: output_file.display().to_string(),
+                matches: total_matches,
+                original_len,
+                final_len,
+                len_diff,
+                samples,
+            }),
+        });
+    }
 
     // 写入处理后的内容
     fs::write(&output_file, &processed).map_err(|e| format!("写入文件失败: {}", e))?;
 
     // 如果文本发生变化，输出详细统计
     if text_changed {
-        let len_diff = final_len as i64 - original_len as i64;
         let sign = if len_diff >= 0 { "+" } else { "" };
         log::debug!(
             "    原始长度: {} 字符, 处理后: {} 字符 ({}{})",
@@ -551,11 +907,69 @@ fn process_single_file(
         );
     }
 
-    Ok(total_matches)
+    Ok(FileProcessOutcome {
+        matches: total_matches,
+        preview: None,
+    })
+}
+
+// 用第一条在原文中有匹配的规则展开文件名模板，捕获组中的非法文件名字符替换为 `_`
+fn expand_filename_template(
+    content: &str,
+    rules: &[(RuleRegex, String, String, String)],
+    template: &str,
+) -> Option<String> {
+    let expanded = rules
+        .iter()
+        .find_map(|(regex, _, _, _)| regex.expand_first(content, template))?;
+
+    Some(
+        expanded
+            .chars()
+            .map(|c| match c {
+                '/' | '\\' | ':' | '*' | '?' | '"' | '<' | '>' | '|' => '_',
+                c if c.is_control() => '_',
+                c => c,
+            })
+            .collect(),
+    )
+}
+
+// 收集前 limit 个替换位置的上下文片段（before 为原文片段，after 为替换后片段）
+fn collect_replacement_samples(
+    text: &str,
+    regex: &RuleRegex,
+    replacement: &str,
+    rule_index: usize,
+    limit: usize,
+    samples: &mut Vec<ReplacementPreview>,
+) {
+    for (start, end, replaced) in regex.expanded_matches(text, replacement, limit) {
+        let prefix_start = text[..start]
+            .char_indices()
+            .rev()
+            .nth(PREVIEW_CONTEXT_CHARS - 1)
+            .map(|(i, _)| i)
+            .unwrap_or(0);
+        let suffix_end = text[end..]
+            .char_indices()
+            .nth(PREVIEW_CONTEXT_CHARS)
+            .map(|(i, _)| end + i)
+            .unwrap_or(text.len());
+
+        let prefix = &text[prefix_start..start];
+        let suffix = &text[end..suffix_end];
+        samples.push(ReplacementPreview {
+            rule_index,
+            line: text[..start].matches('\n').count() + 1,
+            before: format!("{}{}{}", prefix, &text[start..end], suffix),
+            after: format!("{}{}{}", prefix, replaced, suffix),
+        });
+    }
 }
 
 /// 使用 ignore crate 高性能并行计算目录大小（参照 directory_tree.rs 的实现方式）
-fn calculate_dir_size_fast(dir: &Path) -> u64 {
+pub(crate) fn calculate_dir_size_fast(dir: &Path) -> u64 {
     use ignore::WalkBuilder;
     use std::sync::atomic::{AtomicU64, Ordering};
 
@@ -585,6 +999,268 @@ fn calculate_dir_size_fast(dir: &Path) -> u64 {
     total.load(Ordering::Relaxed)
 }
 
+/// 目标已存在时的冲突处理策略
+#[derive(Clone, Copy, PartialEq)]
+enum ConflictPolicy {
+    Skip,
+    Overwrite,
+    Rename,
+}
+
+impl ConflictPolicy {
+    fn as_str(&self) -> &'static str {
+        match self {
+            ConflictPolicy::Skip => "skip",
+            ConflictPolicy::Overwrite => "overwrite",
+            ConflictPolicy::Rename => "rename",
+        }
+    }
+}
+
+/// 解析冲突策略字符串
+fn parse_conflict_policy(policy: &str) -> Result<ConflictPolicy, String> {
+    match policy {
+        "skip" => Ok(ConflictPolicy::Skip),
+        "overwrite" => Ok(ConflictPolicy::Overwrite),
+        "rename" => Ok(ConflictPolicy::Rename),
+        other => Err(format!(
+            "无效的冲突处理策略: '{}'，有效值为 skip, overwrite, rename",
+            other
+        )),
+    }
+}
+
+/// 为已存在的目标路径生成不冲突的新路径
+/// 例如: name.ext -> name(1).ext -> name(2).ext；无扩展名时: name -> name(1)
+fn next_available_path(path: &Path, is_dir: bool) -> PathBuf {
+    let parent = path.parent().map(Path::to_path_buf).unwrap_or_default();
+    // 目录不拆分扩展名，避免 "v1.0" 这类目录名被误切
+    let (stem, ext) = if is_dir {
+        (
+            path.file_name()
+                .map(|n| n.to_string_lossy().to_string())
+                .unwrap_or_default(),
+            String::new(),
+        )
+    } else {
+        (
+            path.file_stem()
+                .map(|s| s.to_string_lossy().to_string())
+                .unwrap_or_default(),
+            path.extension()
+                .map(|e| e.to_string_lossy().to_string())
+                .unwrap_or_default(),
+        )
+    };
+
+    let mut counter = 1;
+    loop {
+        let new_name = if ext.is_empty() {
+            format!("{}({})", stem, counter)
+        } else {
+            format!("{}({}).{}", stem, counter, ext)
+        };
+        let candidate = parent.join(new_name);
+        // 悬空的符号链接 exists() 返回 false，但仍会占用文件名
+        if !candidate.exists() && candidate.symlink_metadata().is_err() {
+            return candidate;
+        }
+        counter += 1;
+    }
+}
+
+/// 跨盘复制的分块大小（1MB），每块写完后检查一次取消标志
+const COPY_CHUNK_SIZE: usize = 1024 * 1024;
+
+/// 分块复制的失败原因
+enum ChunkedCopyError {
+    /// 用户取消，已写入的部分目标文件已删除
+    Cancelled,
+    Io(std::io::Error),
+}
+
+impl std::fmt::Display for ChunkedCopyError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Cancelled => write!(f, "操作已被用户取消"),
+            Self::Io(e) => write!(f, "{}", e),
+        }
+    }
+}
+
+/// 逐块复制单个文件，每块之后检查取消标志并回报进度
+///
+/// 取消或出错时删除已写入的部分目标文件，避免留下半截文件
+fn copy_file_chunked(
+    source: &Path,
+    target: &Path,
+    cancel_token: &CancellationToken,
+    mut on_progress: impl FnMut(u64, u64),
+) -> Result<u64, ChunkedCopyError> {
+    let result = (|| {
+        let mut reader = fs::File::open(source).map_err(ChunkedCopyError::Io)?;
+        let metadata = reader.metadata().map_err(ChunkedCopyError::Io)?;
+        let total_bytes = metadata.len();
+        let mut writer = fs::File::create(target).map_err(ChunkedCopyError::Io)?;
+
+        let mut buffer = vec![0u8; COPY_CHUNK_SIZE];
+        let mut copied_bytes = 0u64;
+        loop {
+            if cancel_token.is_cancelled() {
+                return Err(ChunkedCopyError::Cancelled);
+            }
+            let read = match reader.read(&mut buffer) {
+                Ok(0) => break,
+                Ok(n) => n,
+                Err(e) if e.kind() == std::io::ErrorKind::Interrupted => continue,
+                Err(e) => return Err(ChunkedCopyError::Io(e)),
+            };
+            writer
+                .write_all(&buffer[..read])
+                .map_err(ChunkedCopyError::Io)?;
+            copied_bytes += read as u64;
+            on_progress(copied_bytes, total_bytes);
+        }
+
+        writer.flush().map_err(ChunkedCopyError::Io)?;
+        let _ = fs::set_permissions(target, metadata.permissions());
+        Ok(copied_bytes)
+    })();
+
+    if result.is_err() && target.exists() {
+        if let Err(e) = fs::remove_file(target) {
+            log::warn!("清理未完成的目标文件失败 {}: {}", target.display(), e);
+        }
+    }
+    result
+}
+
+/// 链接创建失败时回滚已完成的移动，尽量恢复到操作前的状态
+/// 返回 Err 时携带的是需要告知用户的当前文件状态
+fn rollback_failed_move(
+    source_path: &Path,
+    target_file_path: &Path,
+    is_cross_dev: bool,
+) -> Result<(), String> {
+    if !is_cross_dev {
+        // 同盘移动只是 rename，直接挪回去即可
+        return fs::rename(target_file_path, source_path).map_err(|e| {
+            format!(
+                "回滚失败，文件仍位于目标位置 {}: {}",
+                target_file_path.display(),
+                e
+            )
+        });
+    }
+
+    // 跨盘移动时源文件已进回收站：先恢复源文件，成功后再删除目标副本
+    restore_from_trash(source_path).map_err(|e| {
+        format!(
+            "源文件在回收站，目标副本已保留 {}: {}",
+            target_file_path.display(),
+            e
+        )
+    })?;
+
+    let remove_result = if target_file_path.is_dir() {
+        fs::remove_dir_all(target_file_path)
+    } else {
+        fs::remove_file(target_file_path)
+    };
+    remove_result.map_err(|e| {
+        format!(
+            "源文件已从回收站恢复，但删除目标副本失败 {}: {}",
+            target_file_path.display(),
+            e
+        )
+    })
+}
+
+/// 从系统回收站恢复指定原始路径的文件（取最近一次删除的条目）
+#[cfg(any(
+    target_os = "windows",
+    all(
+        unix,
+        not(target_os = "macos"),
+        not(target_os = "ios"),
+        not(target_os = "android")
+    )
+))]
+fn restore_from_trash(original_path: &Path) -> Result<(), String> {
+    // 回收站记录的可能是规范化后的路径，源文件已不存在，只能规范化其父目录
+    let canonical_path = original_path
+        .parent()
+        .and_then(|parent| parent.canonicalize().ok())
+        .zip(original_path.file_name())
+        .map(|(parent, name)| parent.join(name));
+
+    let items = trash::os_limited::list().map_err(|e| format!("读取回收站失败: {}", e))?;
+    let item = items
+        .into_iter()
+        .filter(|item| {
+            let item_path = item.original_path();
+            item_path == original_path || canonical_path.as_deref() == Some(item_path.as_path())
+        })
+        .max_by_key(|item| item.time_deleted)
+        .ok_or_else(|| "回收站中未找到该文件".to_string())?;
+
+    trash::os_limited::restore_all([item]).map_err(|e| format!("从回收站恢复失败: {}", e))
+}
+
+#[cfg(not(any(
+    target_os = "windows",
+    all(
+        unix,
+        not(target_os = "macos"),
+        not(target_os = "ios"),
+        not(target_os = "android")
+    )
+)))]
+fn restore_from_trash(_original_path: &Path) -> Result<(), String> {
+    Err("当前平台不支持从回收站自动恢复".to_string())
+}
+
+/// 计算 target 相对于 base_dir 的路径，用于创建相对符号链接
+/// 两者必须都是绝对路径且位于同一根（Windows 下为同一盘符），否则返回 None
+fn relative_path_between(base_dir: &Path, target: &Path) -> Option<PathBuf> {
+    use std::path::Component as PathComponent;
+
+    if !base_dir.is_absolute() || !target.is_absolute() {
+        return None;
+    }
+
+    let base_components: Vec<PathComponent> = base_dir.components().collect();
+    let target_components: Vec<PathComponent> = target.components().collect();
+
+    let common_len = base_components
+        .iter()
+        .zip(target_components.iter())
+        .take_while(|(a, b)| a == b)
+        .count();
+
+    // 连根（盘符/前缀）都不相同，无法用相对路径表达
+    if common_len == 0 {
+        return None;
+    }
+
+    let mut relative = PathBuf::new();
+    for component in &base_components[common_len..] {
+        if matches!(component, PathComponent::Normal(_)) {
+            relative.push("..");
+        } else {
+            return None;
+        }
+    }
+    for component in &target_components[common_len..] {
+        relative.push(component.as_os_str());
+    }
+
+    if relative.as_os_str().is_empty() {
+        relative.push(".");
+    }
+    Some(relative)
+}
+
 // 检测是否跨盘/跨设备移动
 fn is_cross_device(source: &Path, target_dir: &Path) -> bool {
     #[cfg(windows)]
@@ -634,8 +1310,10 @@ pub async fn move_and_link(
     target_dir: String,
     link_type: String,
     base_source_dir: Option<String>,
+    conflict_policy: String,
     cancel_token: tauri::State<'_, Arc<CancellationToken>>,
 ) -> Result<String, String> {
+    let conflict_policy = parse_conflict_policy(&conflict_policy)?;
     let start_time = Instant::now();
     let timestamp = SystemTime::now()
         .duration_since(SystemTime::UNIX_EPOCH)
@@ -658,6 +1336,7 @@ pub async fn move_and_link(
     let mut errors = Vec::new();
     let mut total_size = 0u64;
     let mut processed_files = Vec::new();
+    let mut applied_policies = Vec::new();
 
     for source_path_str in &source_paths {
         let source_path = PathBuf::from(&source_path_str);
@@ -668,7 +1347,7 @@ pub async fn move_and_link(
             continue;
         }
 
-        let target_file_path = if let Some(base_dir) = &base_source_dir {
+        let mut target_file_path = if let Some(base_dir) = &base_source_dir {
             let base_path = PathBuf::from(base_dir);
             match source_path.strip_prefix(&base_path) {
                 Ok(relative) => {
@@ -698,12 +1377,6 @@ pub async fn move_and_link(
             target_path.join(file_name)
         };
 
-        let file_name = target_file_path
-            .file_name()
-            .unwrap_or_default()
-            .to_string_lossy()
-            .to_string();
-
         // 计算文件/目录大小
         if let Ok(metadata) = source_path.metadata() {
             total_size += if metadata.is_file() {
@@ -714,12 +1387,42 @@ pub async fn move_and_link(
             };
         }
 
-        // 检查目标文件是否已存在
+        // 检查目标文件是否已存在，按冲突策略处理
+        let mut applied_policy = "none";
         if target_file_path.exists() {
-            errors.push(format!("目标文件已存在: {}", target_file_path.display()));
-            continue;
+            applied_policy = conflict_policy.as_str();
+            match conflict_policy {
+                ConflictPolicy::Skip => {
+                    errors.push(format!("目标文件已存在: {}", target_file_path.display()));
+                    applied_policies.push(AppliedConflictPolicy {
+                        source_path: source_path_str.clone(),
+                        target_path: target_file_path.to_string_lossy().to_string(),
+                        policy: applied_policy.to_string(),
+                    });
+                    continue;
+                }
+                ConflictPolicy::Overwrite => {
+                    if let Err(e) = trash::delete(&target_file_path) {
+                        errors.push(format!(
+                            "覆盖前移除已存在的目标失败 {}: {}",
+                            target_file_path.display(),
+                            e
+                        ));
+                        continue;
+                    }
+                }
+                ConflictPolicy::Rename => {
+                    target_file_path = next_available_path(&target_file_path, source_path.is_dir());
+                }
+            }
         }
 
+        let file_name = target_file_path
+            .file_name()
+            .unwrap_or_default()
+            .to_string_lossy()
+            .to_string();
+
         // 检查是否已取消
         if cancel_token.is_cancelled() {
             return Err("操作已被用户取消".to_string());
@@ -736,34 +1439,76 @@ pub async fn move_and_link(
             let source_name = source_path_str.clone();
 
             let copy_result = if source_path.is_dir() {
-                // 目录复制暂不支持进度（fs_extra 的目录复制进度回调较复杂）
-                fs_extra::dir::copy(
-                    &source_path,
-                    &target_path,
-                    &fs_extra::dir::CopyOptions::new(),
-                )
-                .map(|_| ())
+                // 复制到目标父目录时会沿用源目录名，重命名策略下需先创建新目录再只复制内容
+                let (copy_dest, options) =
+                    if target_file_path.file_name() == source_path.file_name() {
+                        (
+                            target_file_path
+                                .parent()
+                                .unwrap_or(&target_path)
+                                .to_path_buf(),
+                            fs_extra::dir::CopyOptions::new(),
+                        )
+                    } else {
+                        (
+                            target_file_path.clone(),
+                            fs_extra::dir::CopyOptions::new().content_only(true),
+                        )
+                    };
+
+                fs::create_dir_all(&copy_dest)
+                    .map_err(fs_extra::error::Error::from)
+                    .and_then(|_| {
+                        fs_extra::dir::copy_with_progress(
+                            &source_path,
+                            &copy_dest,
+                            &options,
+                            |process: fs_extra::dir::TransitProcess| {
+                                // 目录进度按整体字节数计算，current_file 显示当前正在复制的子文件
+                                let progress = CopyProgress {
+                                    current_file: process.file_name.clone(),
+                                    copied_bytes: process.copied_bytes,
+                                    total_bytes: process.total_bytes,
+                                    progress_percentage: if process.total_bytes > 0 {
+                                        (process.copied_bytes as f64 / process.total_bytes as f64)
+                                            * 100.0
+                                    } else {
+                                        0.0
+                                    },
+                                };
+
+                                let _ = app_clone.emit("copy-progress", progress);
+                                if token_clone.is_cancelled() {
+                                    fs_extra::dir::TransitProcessResult::Abort
+                                } else {
+                                    fs_extra::dir::TransitProcessResult::ContinueOrAbort
+                                }
+                            },
+                        )
+                    })
+                    .map(|_| ())
+                    .map_err(|e| {
+                        if token_clone.is_cancelled() {
+                            // 目标在冲突处理后必然是新路径，取消时整体移除已复制的部分
+                            let _ = fs::remove_dir_all(&target_file_path);
+                            ChunkedCopyError::Cancelled
+                        } else {
+                            ChunkedCopyError::Io(std::io::Error::other(e.to_string()))
+                        }
+                    })
             } else {
-                // 文件复制支持进度回调
-                let options = fs_extra::file::CopyOptions::new();
-                fs_extra::file::copy_with_progress(
+                // 自行分块复制，每块之后检查取消标志，保证大文件也能立即响应取消
+                copy_file_chunked(
                     &source_path,
                     &target_file_path,
-                    &options,
-                    |process: fs_extra::file::TransitProcess| {
-                        // 检查取消状态
-                        if token_clone.is_cancelled() {
-                            // fs_extra 的进度回调不支持中断，只能记录状态
-                            // 实际的取消检查在复制完成后进行
-                        }
-
-                        // 发送进度事件
+                    &token_clone,
+                    |copied_bytes, total_bytes| {
                         let progress = CopyProgress {
                             current_file: source_name.clone(),
-                            copied_bytes: process.copied_bytes,
-                            total_bytes: process.total_bytes,
-                            progress_percentage: if process.total_bytes > 0 {
-                                (process.copied_bytes as f64 / process.total_bytes as f64) * 100.0
+                            copied_bytes,
+                            total_bytes,
+                            progress_percentage: if total_bytes > 0 {
+                                (copied_bytes as f64 / total_bytes as f64) * 100.0
                             } else {
                                 0.0
                             },
@@ -792,6 +1537,9 @@ pub async fn move_and_link(
                         }
                     }
                 }
+                Err(ChunkedCopyError::Cancelled) => {
+                    return Err("操作已被用户取消".to_string());
+                }
                 Err(e) => {
                     errors.push(format!(
                         "跨盘复制文件失败 {} -> {}: {}",
@@ -845,15 +1593,28 @@ pub async fn move_and_link(
                     processed_files.push(file_name);
                 }
                 Err(e) => {
+                    // 链接没建起来时源位置已经空了，必须回滚，否则用户会"丢"文件
+                    let rollback_state =
+                        match rollback_failed_move(&source_path, &target_file_path, is_cross_dev) {
+                            Ok(_) => "已回滚，源文件已恢复原位".to_string(),
+                            Err(state) => state,
+                        };
                     errors.push(format!(
-                        "创建链接失败 {} -> {}: {}",
+                        "创建链接失败 {} -> {}: {}（{}）",
                         target_file_path.display(),
                         source_path.display(),
-                        e
+                        e,
+                        rollback_state
                     ));
                 }
             }
         }
+
+        applied_policies.push(AppliedConflictPolicy {
+            source_path: source_path_str.clone(),
+            target_path: target_file_path.to_string_lossy().to_string(),
+            policy: applied_policy.to_string(),
+        });
     }
 
     let duration = start_time.elapsed();
@@ -872,8 +1633,9 @@ pub async fn move_and_link(
         source_paths: source_paths.clone(),
         total_size,
         processed_files,
+        applied_policies,
     };
-    add_operation_log(log);
+    add_operation_log(&app, log);
 
     let mut message = format!("成功处理 {} 个文件", processed_count);
     if !errors.is_empty() {
@@ -889,10 +1651,12 @@ pub async fn move_and_link(
 // Tauri 命令：仅创建链接（不移动文件）
 #[tauri::command]
 pub async fn create_links_only(
+    app: AppHandle,
     source_paths: Vec<String>,
     target_dir: String,
     link_type: String,
     base_source_dir: Option<String>,
+    relative: bool,
 ) -> Result<String, String> {
     let start_time = Instant::now();
     let timestamp = SystemTime::now()
@@ -979,18 +1743,38 @@ pub async fn create_links_only(
 
         // 创建链接
         let link_result = if link_type == "symlink" {
+            // 相对链接：目标写成相对于链接所在目录的路径，整体迁移目录后链接仍然有效
+            let link_target = if relative {
+                match link_path
+                    .parent()
+                    .and_then(|link_dir| relative_path_between(link_dir, &source_path))
+                {
+                    Some(relative_target) => relative_target,
+                    None => {
+                        log::warn!(
+                            "无法计算相对路径，回退为绝对路径: {} -> {}",
+                            link_path.display(),
+                            source_path.display()
+                        );
+                        source_path.clone()
+                    }
+                }
+            } else {
+                source_path.clone()
+            };
+
             // 创建符号链接
             #[cfg(windows)]
             {
                 if source_path.is_dir() {
-                    std::os::windows::fs::symlink_dir(&source_path, &link_path)
+                    std::os::windows::fs::symlink_dir(&link_target, &link_path)
                 } else {
-                    std::os::windows::fs::symlink_file(&source_path, &link_path)
+                    std::os::windows::fs::symlink_file(&link_target, &link_path)
                 }
             }
             #[cfg(unix)]
             {
-                std::os::unix::fs::symlink(&source_path, &link_path)
+                std::os::unix::fs::symlink(&link_target, &link_path)
             }
         } else {
             // 硬链接不支持目录，且要求在同一文件系统
@@ -1033,8 +1817,9 @@ pub async fn create_links_only(
         source_paths: source_paths.clone(),
         total_size,
         processed_files,
+        applied_policies: Vec::new(),
     };
-    add_operation_log(log);
+    add_operation_log(&app, log);
 
     let mut message = format!("成功创建 {} 个链接", processed_count);
     if !errors.is_empty() {
@@ -1055,10 +1840,13 @@ pub fn path_exists(path: String) -> bool {
 
 // Tauri 命令：检查路径是否为目录
 #[tauri::command]
-pub fn is_directory(path: String) -> Result<bool, String> {
+pub fn is_directory(path: String) -> AppResult<bool> {
     let path = Path::new(&path);
     if !path.exists() {
-        return Err(format!("路径不存在: {}", path.display()));
+        return Err(AppError::NotFound(format!(
+            "路径不存在: {}",
+            path.display()
+        )));
     }
     Ok(path.is_dir())
 }
@@ -1072,17 +1860,42 @@ pub struct FileMetadata {
     pub is_dir: bool,
     pub modified: Option<u64>,
     pub created: Option<u64>,
+    pub is_symlink: bool,
+    pub symlink_target: Option<String>, // 链接指向的原始路径（未解析）
+    pub mode: Option<u32>,              // Unix 权限位，其他平台为 None
+    pub readonly: bool,
 }
 // Tauri 命令：获取文件元数据
 #[tauri::command]
-pub fn get_file_metadata(path: String) -> Result<FileMetadata, String> {
+pub fn get_file_metadata(path: String) -> AppResult<FileMetadata> {
     let path = Path::new(&path);
 
-    if !path.exists() {
-        return Err(format!("路径不存在: {}", path.display()));
-    }
+    // 使用 symlink_metadata 判断链接本身，悬空链接也应能返回信息
+    let link_metadata =
+        fs::symlink_metadata(path).context(format!("无法访问路径 {}", path.display()))?;
+    let is_symlink = link_metadata.file_type().is_symlink();
+    let symlink_target = if is_symlink {
+        fs::read_link(path)
+            .ok()
+            .map(|target| target.to_string_lossy().to_string())
+    } else {
+        None
+    };
 
-    let metadata = fs::metadata(path).map_err(|e| format!("获取文件元数据失败: {}", e))?;
+    // 其余字段跟随链接到目标；目标不存在时退回到链接自身的元数据
+    let metadata = match fs::metadata(path) {
+        Ok(metadata) => metadata,
+        Err(_) if is_symlink => link_metadata,
+        Err(e) => return Err(AppError::from(e).context("获取文件元数据失败")),
+    };
+
+    #[cfg(unix)]
+    let mode = {
+        use std::os::unix::fs::PermissionsExt;
+        Some(metadata.permissions().mode())
+    };
+    #[cfg(not(unix))]
+    let mode = None;
 
     let modified = metadata
         .modified()
@@ -1102,6 +1915,10 @@ pub fn get_file_metadata(path: String) -> Result<FileMetadata, String> {
         is_dir: metadata.is_dir(),
         modified,
         created,
+        is_symlink,
+        symlink_target,
+        mode,
+        readonly: metadata.permissions().readonly(),
     })
 }
 #[derive(Serialize)]
@@ -1124,13 +1941,13 @@ pub fn get_image_dimensions(path: String) -> Result<ImageDimensions, String> {
 
 // Tauri 命令：读取文件为二进制数据
 #[tauri::command]
-pub fn read_file_binary(path: String) -> Result<Vec<u8>, String> {
+pub fn read_file_binary(path: String) -> AppResult<Vec<u8>> {
     let file_path = Path::new(&path);
     if !file_path.exists() {
-        return Err(format!("文件不存在: {}", path));
+        return Err(AppError::NotFound(format!("文件不存在: {}", path)));
     }
 
-    let bytes = fs::read(file_path).map_err(|e| format!("读取文件失败: {}", e))?;
+    let bytes = fs::read(file_path).context("读取文件失败")?;
 
     Ok(bytes)
 }
@@ -1139,38 +1956,104 @@ pub fn read_file_binary(path: String) -> Result<Vec<u8>, String> {
 // 与 read_file_binary 不同，此命令返回 tauri::ipc::Response，
 // 前端收到的是 ArrayBuffer 而非 number[]，避免了 3-4x 的传输膨胀
 #[tauri::command]
-pub fn read_file_binary_raw(path: String) -> Result<tauri::ipc::Response, String> {
+pub fn read_file_binary_raw(path: String) -> AppResult<tauri::ipc::Response> {
     let file_path = Path::new(&path);
     if !file_path.exists() {
-        return Err(format!("文件不存在: {}", path));
+        return Err(AppError::NotFound(format!("文件不存在: {}", path)));
     }
 
-    let bytes = fs::read(file_path).map_err(|e| format!("读取文件失败: {}", e))?;
+    let bytes = fs::read(file_path).context("读取文件失败")?;
 
     Ok(tauri::ipc::Response::new(bytes))
 }
 
+// read_file_as_base64 一次性读取的文件大小上限（50MB），更大的文件需分块读取
+const MAX_BASE64_READ_BYTES: u64 = 50 * 1024 * 1024;
+// read_file_base64_chunk 单次最多读取的字节数（16MB）
+const MAX_BASE64_CHUNK_BYTES: u64 = 16 * 1024 * 1024;
+
 // Tauri 命令：读取文件为base64
 #[tauri::command]
-pub fn read_file_as_base64(path: String) -> Result<String, String> {
+pub fn read_file_as_base64(path: String) -> AppResult<String> {
     use base64::{engine::general_purpose, Engine as _};
 
     let file_path = Path::new(&path);
     if !file_path.exists() {
-        return Err(format!("文件不存在: {}", path));
+        return Err(AppError::NotFound(format!("文件不存在: {}", path)));
+    }
+
+    let size = fs::metadata(file_path).context("获取文件元数据失败")?.len();
+    if size > MAX_BASE64_READ_BYTES {
+        return Err(AppError::TooLarge(format!(
+            "文件过大 ({:.1} MB)，超过 {} MB 上限，请使用 read_file_base64_chunk 分块读取",
+            size as f64 / 1024.0 / 1024.0,
+            MAX_BASE64_READ_BYTES / 1024 / 1024
+        )));
     }
 
-    let bytes = fs::read(file_path).map_err(|e| format!("读取文件失败: {}", e))?;
+    let bytes = fs::read(file_path).context("读取文件失败")?;
 
     Ok(general_purpose::STANDARD.encode(&bytes))
 }
 
+// 分块读取结果
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FileBase64Chunk {
+    pub data: String,    // 该字节范围的 base64
+    pub bytes_read: u64, // 实际读取的字节数，到达文件末尾时可能小于请求长度
+    pub total_size: u64, // 文件总大小
+}
+
+// Tauri 命令：读取文件指定字节范围并编码为 base64
+// 前端拼接时，除最后一块外 length 应为 3 的倍数，否则 base64 片段之间会出现填充字符
+#[tauri::command]
+pub async fn read_file_base64_chunk(
+    path: String,
+    offset: u64,
+    length: u64,
+) -> AppResult<FileBase64Chunk> {
+    use base64::{engine::general_purpose, Engine as _};
+    use std::io::{Seek, SeekFrom};
+
+    if length > MAX_BASE64_CHUNK_BYTES {
+        return Err(AppError::InvalidInput(format!(
+            "单次读取长度不能超过 {} MB",
+            MAX_BASE64_CHUNK_BYTES / 1024 / 1024
+        )));
+    }
+
+    let mut file = fs::File::open(&path).context("打开文件失败")?;
+    let total_size = file.metadata().context("获取文件元数据失败")?.len();
+
+    if offset >= total_size {
+        return Ok(FileBase64Chunk {
+            data: String::new(),
+            bytes_read: 0,
+            total_size,
+        });
+    }
+
+    file.seek(SeekFrom::Start(offset)).context("定位文件失败")?;
+
+    let mut buffer = Vec::with_capacity(length.min(total_size - offset) as usize);
+    file.take(length)
+        .read_to_end(&mut buffer)
+        .context("读取文件失败")?;
+
+    Ok(FileBase64Chunk {
+        data: general_purpose::STANDARD.encode(&buffer),
+        bytes_read: buffer.len() as u64,
+        total_size,
+    })
+}
+
 // Tauri 命令：获取文件的 MIME 类型
 #[tauri::command]
-pub fn get_file_mime_type(path: String) -> Result<String, String> {
+pub fn get_file_mime_type(path: String) -> AppResult<String> {
     let file_path = Path::new(&path);
     if !file_path.exists() {
-        return Err(format!("文件不存在: {}", path));
+        return Err(AppError::NotFound(format!("文件不存在: {}", path)));
     }
 
     Ok(crate::utils::mime::guess_mime_type(file_path))
@@ -1363,6 +2246,22 @@ pub fn validate_regex_pattern(regex: String) -> RegexValidation {
                 },
             }
         }
+        Err(e)
+            if is_fancy_regex_feature_error(&e)
+                && build_fancy_regex_with_flags(&pattern, &flags).is_ok() =>
+        {
+            // 标准引擎不支持，但 fancy-regex 可以处理
+            RegexValidation {
+                is_valid: true,
+                error_message: None,
+                parsed_pattern: Some(pattern),
+                parsed_flags: Some(flags),
+                warning: Some(format!(
+                    "包含前瞻/后瞻断言或反向引用，{}",
+                    FANCY_REGEX_WARNING
+                )),
+            }
+        }
         Err(e) => {
             // 检查是否是常见的不支持特性
             let error_str = e.to_string();
@@ -1565,6 +2464,361 @@ pub struct PluginInstallResult {
     pub install_path: String,
 }
 
+// 插件解压后总大小的默认上限（500MB），可通过 settings.json 的 pluginMaxUnpackedSizeMB 调整
+const DEFAULT_PLUGIN_MAX_UNPACKED_BYTES: u64 = 500 * 1024 * 1024;
+
+// 读取插件解压大小上限
+fn get_plugin_max_unpacked_bytes(app: &AppHandle) -> u64 {
+    let settings_path = crate::get_app_data_dir(app.config())
+        .join("app-settings")
+        .join("settings.json");
+    fs::read_to_string(&settings_path)
+        .ok()
+        .and_then(|contents| serde_json::from_str::<Value>(&contents).ok())
+        .and_then(|json| json.get("pluginMaxUnpackedSizeMB").and_then(|v| v.as_u64()))
+        .filter(|mb| *mb > 0)
+        .map(|mb| mb.saturating_mul(1024 * 1024))
+        .unwrap_or(DEFAULT_PLUGIN_MAX_UNPACKED_BYTES)
+}
+
+// 内置信任的插件签名公钥（ed25519，base64 编码的 32 字节公钥）
+const OFFICIAL_PLUGIN_PUBLIC_KEYS: &[&str] = &[];
+
+// 插件完整性校验结果
+#[derive(Serialize, Clone, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct PluginIntegrityCheck {
+    pub checksum_status: String,  // "absent" | "verified" | "failed"
+    pub signature_status: String, // "absent" | "verified" | "failed"
+    pub errors: Vec<String>,
+}
+
+// 插件预检结果：manifest 字段平铺，附带校验信息与兼容性结论
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PluginPreflightResult {
+    #[serde(flatten)]
+    pub manifest: PluginManifest,
+    pub integrity: PluginIntegrityCheck,
+    pub compatible: bool,
+    pub incompatible_reason: Option<String>,
+}
+
+// 宿主支持的插件 API 版本，需与前端 PluginManager 保持一致
+const HOST_PLUGIN_API_VERSION: u32 = 2;
+
+// 检查插件声明的宿主要求，返回所有不满足的原因
+fn check_host_compatibility(host: &HostRequirements, app_version: &semver::Version) -> Vec<String> {
+    let mut reasons = Vec::new();
+
+    match semver::VersionReq::parse(host.app_version.trim()) {
+        Ok(req) => {
+            // VersionReq 默认不匹配预发布版本，这里退回到去掉预发布标记的版本再比较一次
+            let mut release = app_version.clone();
+            release.pre = semver::Prerelease::EMPTY;
+            if !req.matches(app_version) && !req.matches(&release) {
+                reasons.push(format!(
+                    "插件要求应用版本 {}，当前版本为 {}",
+                    host.app_version, app_version
+                ));
+            }
+        }
+        Err(e) => reasons.push(format!(
+            "无法解析插件要求的应用版本 \"{}\": {}",
+            host.app_version, e
+        )),
+    }
+
+    if let Some(api_version) = host.api_version {
+        if api_version > HOST_PLUGIN_API_VERSION {
+            reasons.push(format!(
+                "插件需要的 API 版本 (v{}) 高于当前应用支持的版本 (v{})",
+                api_version, HOST_PLUGIN_API_VERSION
+            ));
+        }
+    }
+
+    reasons
+}
+
+// 在 ZIP 中查找根目录（或单层包裹目录）下的 manifest.json，返回 (条目名, 内容)
+// manifest.json 的大小上限（1MB），防止伪造大小的条目在预检阶段耗尽内存
+const MAX_PLUGIN_MANIFEST_BYTES: u64 = 1024 * 1024;
+
+fn find_plugin_manifest(archive: &mut ZipArchive<fs::File>) -> Result<(String, String), String> {
+    for i in 0..archive.len() {
+        let mut file = archive
+            .by_index(i)
+            .map_err(|e| format!("读取 ZIP 条目失败: {}", e))?;
+
+        let file_name = file.name().to_string();
+
+        // 检查是否是根目录下的 manifest.json
+        if file_name == "manifest.json" || file_name.ends_with("/manifest.json") {
+            // 确保不是嵌套目录中的 manifest.json
+            let parts: Vec<&str> = file_name.split('/').collect();
+            if parts.len() <= 2 {
+                // 允许 "manifest.json" 或 "folder/manifest.json"
+                let declared = file.size();
+                if declared > MAX_PLUGIN_MANIFEST_BYTES {
+                    return Err(format!("manifest.json 过大 ({} 字节)，已拒绝", declared));
+                }
+                let mut content = String::new();
+                (&mut file)
+                    .take(declared + 1)
+                    .read_to_string(&mut content)
+                    .map_err(|e| format!("读取 manifest.json 失败: {}", e))?;
+                if content.len() as u64 != declared {
+                    return Err(format!(
+                        "manifest.json 的实际大小与声明大小 ({} 字节) 不一致",
+                        declared
+                    ));
+                }
+                return Ok((file_name, content));
+            }
+        }
+    }
+
+    Err("ZIP 文件中未找到 manifest.json".to_string())
+}
+
+// 内置公钥 + settings.json 中 trustedPluginPublicKeys 追加的公钥
+fn get_trusted_plugin_public_keys(app: &AppHandle) -> Vec<String> {
+    let mut keys: Vec<String> = OFFICIAL_PLUGIN_PUBLIC_KEYS
+        .iter()
+        .map(|k| k.to_string())
+        .collect();
+
+    let settings_path = crate::get_app_data_dir(app.config())
+        .join("app-settings")
+        .join("settings.json");
+    if let Some(user_keys) = fs::read_to_string(&settings_path)
+        .ok()
+        .and_then(|contents| serde_json::from_str::<Value>(&contents).ok())
+        .and_then(|json| json.get("trustedPluginPublicKeys").cloned())
+        .and_then(|v| serde_json::from_value::<Vec<String>>(v).ok())
+    {
+        keys.extend(user_keys);
+    }
+
+    keys
+}
+
+// 签名覆盖的规范化内容：整体摘要直接使用十六进制串，文件摘要按路径排序后逐行 "路径\t摘要"
+fn plugin_checksum_signing_payload(checksum: &PluginChecksum) -> String {
+    match checksum {
+        PluginChecksum::Archive(hash) => hash.to_lowercase(),
+        PluginChecksum::Files(files) => {
+            let mut entries: Vec<_> = files.iter().collect();
+            entries.sort_by(|a, b| a.0.cmp(b.0));
+            entries
+                .into_iter()
+                .map(|(path, hash)| format!("{}\t{}\n", path, hash.to_lowercase()))
+                .collect()
+        }
+    }
+}
+
+// 校验插件包的 sha256 与签名
+// 条目以流式方式计算摘要，单个条目与累计读取量都不超过 `max_unpacked_bytes`
+fn verify_plugin_integrity(
+    archive: &mut ZipArchive<fs::File>,
+    manifest_entry: &str,
+    manifest: &Value,
+    trusted_keys: &[String],
+    max_unpacked_bytes: u64,
+) -> PluginIntegrityCheck {
+    use base64::Engine;
+    use ed25519_dalek::{Signature, Verifier, VerifyingKey};
+    use sha2::{Digest, Sha256};
+
+    let mut check = PluginIntegrityCheck {
+        checksum_status: "absent".to_string(),
+        signature_status: "absent".to_string(),
+        errors: Vec::new(),
+    };
+
+    let checksum = match manifest.get("sha256") {
+        None | Some(Value::Null) => None,
+        Some(v) => match serde_json::from_value::<PluginChecksum>(v.clone()) {
+            Ok(c) => Some(c),
+            Err(_) => {
+                check.checksum_status = "failed".to_string();
+                check
+                    .errors
+                    .push("manifest.json 的 sha256 字段格式无效".to_string());
+                return check;
+            }
+        },
+    };
+    let signature = manifest.get("signature").and_then(|v| v.as_str());
+
+    // 1. 校验和
+    if let Some(checksum) = &checksum {
+        let prefix = manifest_entry.trim_end_matches("manifest.json");
+        let mut file_hashes: HashMap<String, String> = HashMap::new();
+        let mut total_read = 0u64;
+
+        for i in 0..archive.len() {
+            let mut file = match archive.by_index(i) {
+                Ok(f) => f,
+                Err(e) => {
+                    check.errors.push(format!("读取 ZIP 条目失败: {}", e));
+                    continue;
+                }
+            };
+            let name = file.name().to_string();
+            if name.ends_with('/') || name == manifest_entry {
+                continue;
+            }
+            let relative = name.strip_prefix(prefix).unwrap_or(&name).to_string();
+
+            let declared = file.size();
+            if declared > max_unpacked_bytes.saturating_sub(total_read) {
+                check.checksum_status = "failed".to_string();
+                check.errors.push(format!(
+                    "插件解压后大小超过上限 {} MB",
+                    max_unpacked_bytes / 1024 / 1024
+                ));
+                return check;
+            }
+
+            // 最多读取声明大小 + 1 字节，多读出来即说明条目头部声明的大小是伪造的
+            let mut hasher = Sha256::new();
+            let actual = match io::copy(&mut (&mut file).take(declared + 1), &mut hasher) {
+                Ok(n) => n,
+                Err(e) => {
+                    check.errors.push(format!("读取 {} 失败: {}", name, e));
+                    continue;
+                }
+            };
+            if actual != declared {
+                check.checksum_status = "failed".to_string();
+                check.errors.push(format!(
+                    "ZIP 条目 {} 的实际大小与声明大小 ({} 字节) 不一致",
+                    name, declared
+                ));
+                return check;
+            }
+            total_read += actual;
+            file_hashes.insert(relative, format!("{:x}", hasher.finalize()));
+        }
+
+        match checksum {
+            PluginChecksum::Archive(expected) => {
+                // 整体摘要：按路径排序后对 "路径\t摘要\n" 序列再求一次 SHA-256
+                let mut entries: Vec<_> = file_hashes.iter().collect();
+                entries.sort_by(|a, b| a.0.cmp(b.0));
+                let mut hasher = Sha256::new();
+                for (path, hash) in entries {
+                    hasher.update(format!("{}\t{}\n", path, hash).as_bytes());
+                }
+                let actual = format!("{:x}", hasher.finalize());
+                if !actual.eq_ignore_ascii_case(expected) {
+                    check.errors.push(format!(
+                        "插件包整体校验和不匹配（期望 {}，实际 {}）",
+                        expected, actual
+                    ));
+                }
+            }
+            PluginChecksum::Files(expected_files) => {
+                for (path, expected) in expected_files {
+                    match file_hashes.get(path) {
+                        Some(actual) if actual.eq_ignore_ascii_case(expected) => {}
+                        Some(actual) => check.errors.push(format!(
+                            "文件 {} 校验和不匹配（期望 {}，实际 {}）",
+                            path, expected, actual
+                        )),
+                        None => check
+                            .errors
+                            .push(format!("校验清单中的文件 {} 不存在", path)),
+                    }
+                }
+            }
+        }
+
+        check.checksum_status = if check.errors.is_empty() {
+            "verified".to_string()
+        } else {
+            "failed".to_string()
+        };
+    }
+
+    // 2. 签名（签的是校验和清单，因此必须同时提供 sha256）
+    if let Some(signature) = signature {
+        let engine = base64::engine::general_purpose::STANDARD;
+        let signature_error = match &checksum {
+            None => Some("存在 signature 字段但缺少 sha256 字段，无法验签".to_string()),
+            Some(checksum) => {
+                let payload = plugin_checksum_signing_payload(checksum);
+                match engine
+                    .decode(signature.trim())
+                    .ok()
+                    .and_then(|bytes| Signature::from_slice(&bytes).ok())
+                {
+                    None => Some("signature 字段不是有效的 ed25519 签名".to_string()),
+                    Some(sig) => {
+                        let verified = trusted_keys.iter().any(|key| {
+                            engine
+                                .decode(key.trim())
+                                .ok()
+                                .and_then(|bytes| <[u8; 32]>::try_from(bytes.as_slice()).ok())
+                                .and_then(|bytes| VerifyingKey::from_bytes(&bytes).ok())
+                                .is_some_and(|vk| vk.verify(payload.as_bytes(), &sig).is_ok())
+                        });
+                        if verified {
+                            None
+                        } else {
+                            Some("签名验证失败：没有任何受信任的公钥能验证该签名".to_string())
+                        }
+                    }
+                }
+            }
+        };
+
+        match signature_error {
+            None => check.signature_status = "verified".to_string(),
+            Some(e) => {
+                check.signature_status = "failed".to_string();
+                check.errors.push(e);
+            }
+        }
+    }
+
+    check
+}
+
+/// 安装目录守卫：未调用 `commit` 就被丢弃时删除整个安装目录，
+/// 保证安装要么完整成功，要么不留下半成品目录
+struct PluginInstallGuard {
+    install_dir: PathBuf,
+    committed: bool,
+}
+
+impl PluginInstallGuard {
+    fn new(install_dir: PathBuf) -> Self {
+        Self {
+            install_dir,
+            committed: false,
+        }
+    }
+
+    fn commit(mut self) {
+        self.committed = true;
+    }
+}
+
+impl Drop for PluginInstallGuard {
+    fn drop(&mut self) {
+        if self.committed {
+            return;
+        }
+        if let Err(e) = fs::remove_dir_all(&self.install_dir) {
+            log::warn!("回滚插件安装目录失败 {}: {}", self.install_dir.display(), e);
+        }
+    }
+}
+
 // Tauri 命令：从 ZIP 文件安装插件
 #[tauri::command]
 pub async fn install_plugin_from_zip(
@@ -1588,37 +2842,7 @@ pub async fn install_plugin_from_zip(
     let mut archive = ZipArchive::new(file).map_err(|e| format!("无法读取 ZIP 文件: {}", e))?;
 
     // 查找并读取 manifest.json
-    let manifest_content = {
-        let mut manifest_found = false;
-        let mut content = String::new();
-
-        for i in 0..archive.len() {
-            let mut file = archive
-                .by_index(i)
-                .map_err(|e| format!("读取 ZIP 条目失败: {}", e))?;
-
-            let file_name = file.name().to_string();
-
-            // 检查是否是根目录下的 manifest.json
-            if file_name == "manifest.json" || file_name.ends_with("/manifest.json") {
-                // 确保不是嵌套目录中的 manifest.json
-                let parts: Vec<&str> = file_name.split('/').collect();
-                if parts.len() <= 2 {
-                    // 允许 "manifest.json" 或 "folder/manifest.json"
-                    file.read_to_string(&mut content)
-                        .map_err(|e| format!("读取 manifest.json 失败: {}", e))?;
-                    manifest_found = true;
-                    break;
-                }
-            }
-        }
-
-        if !manifest_found {
-            return Err("ZIP 文件中未找到 manifest.json".to_string());
-        }
-
-        content
-    };
+    let (manifest_entry, manifest_content) = find_plugin_manifest(&mut archive)?;
 
     // 解析 manifest.json
     let manifest: serde_json::Value = serde_json::from_str(&manifest_content)
@@ -1659,16 +2883,9 @@ pub async fn install_plugin_from_zip(
     if install_dir.exists() {
         return Err(format!("插件 {} 已安装，请先卸载", plugin_id));
     }
-    // 创建插件目录
-    fs::create_dir_all(&install_dir).map_err(|e| format!("创建插件目录失败: {}", e))?;
-
-    // 重新打开 ZIP 文件进行解压
-    let file =
-        fs::File::open(&zip_file_path).map_err(|e| format!("无法重新打开 ZIP 文件: {}", e))?;
-
-    let mut archive = ZipArchive::new(file).map_err(|e| format!("无法重新读取 ZIP 文件: {}", e))?;
 
-    // 统计总文件数和总字节数（用于进度计算）
+    // 统计总文件数和总字节数（用于进度计算），并在落盘前拒绝超限的包
+    let max_unpacked_bytes = get_plugin_max_unpacked_bytes(&app);
     let mut total_files = 0usize;
     let mut total_bytes = 0u64;
     for i in 0..archive.len() {
@@ -1683,9 +2900,35 @@ pub async fn install_plugin_from_zip(
         }
 
         total_files += 1;
-        total_bytes += file.size();
+        total_bytes = total_bytes.saturating_add(file.size());
+        if total_bytes > max_unpacked_bytes {
+            return Err(format!(
+                "插件解压后大小超过上限 {} MB，已拒绝安装",
+                max_unpacked_bytes / 1024 / 1024
+            ));
+        }
+    }
+
+    // 完整性校验（需要读出全部条目，放在大小检查之后）：校验和或签名不通过时拒绝安装
+    let trusted_keys = get_trusted_plugin_public_keys(&app);
+    let integrity = verify_plugin_integrity(
+        &mut archive,
+        &manifest_entry,
+        &manifest,
+        &trusted_keys,
+        max_unpacked_bytes,
+    );
+    if !integrity.errors.is_empty() {
+        return Err(format!(
+            "插件完整性校验失败，已拒绝安装: {}",
+            integrity.errors.join("; ")
+        ));
     }
 
+    // 创建插件目录，之后任何一步失败都由守卫删除整个目录
+    fs::create_dir_all(&install_dir).map_err(|e| format!("创建插件目录失败: {}", e))?;
+    let install_guard = PluginInstallGuard::new(install_dir.clone());
+
     // 重新打开 ZIP 进行实际解压
     let file =
         fs::File::open(&zip_file_path).map_err(|e| format!("无法重新打开 ZIP 文件: {}", e))?;
@@ -1727,17 +2970,29 @@ pub async fn install_plugin_from_zip(
         let mut target_file =
             fs::File::create(&target_path).map_err(|e| format!("创建文件失败: {}", e))?;
 
-        let mut buffer = Vec::new();
-        file.read_to_end(&mut buffer)
-            .map_err(|e| format!("读取文件内容失败: {}", e))?;
-
-        target_file
-            .write_all(&buffer)
-            .map_err(|e| format!("写入文件失败: {}", e))?;
+        // 最多读取声明大小 + 1 字节，多读出来即说明条目头部声明的大小是伪造的
+        let written = io::copy(
+            &mut (&mut file).take(file_size.saturating_add(1)),
+            &mut target_file,
+        )
+        .map_err(|e| format!("解压文件失败: {}", e))?;
+
+        if written != file_size {
+            return Err(format!(
+                "ZIP 条目 {} 的实际大小与声明大小 ({} 字节) 不一致，已拒绝安装",
+                file_path, file_size
+            ));
+        }
 
-        // 更新进度
+        // 更新进度，并按实际写入量再次检查总大小上限
         processed_files += 1;
-        processed_bytes += file_size;
+        processed_bytes += written;
+        if processed_bytes > max_unpacked_bytes {
+            return Err(format!(
+                "插件解压后大小超过上限 {} MB，已拒绝安装",
+                max_unpacked_bytes / 1024 / 1024
+            ));
+        }
 
         let progress_percentage = if total_files > 0 {
             (processed_files as f64 / total_files as f64) * 100.0
@@ -1758,6 +3013,8 @@ pub async fn install_plugin_from_zip(
         let _ = app.emit("plugin-install-progress", progress);
     }
 
+    install_guard.commit();
+
     Ok(PluginInstallResult {
         plugin_id,
         plugin_name,
@@ -1768,7 +3025,10 @@ pub async fn install_plugin_from_zip(
 
 // Tauri 命令：插件安装预检
 #[tauri::command]
-pub async fn preflight_plugin_zip(zip_path: String) -> Result<PluginManifest, String> {
+pub async fn preflight_plugin_zip(
+    app: AppHandle,
+    zip_path: String,
+) -> Result<PluginPreflightResult, String> {
     let zip_file_path = PathBuf::from(&zip_path);
 
     // 检查 ZIP 文件是否存在
@@ -1786,37 +3046,7 @@ pub async fn preflight_plugin_zip(zip_path: String) -> Result<PluginManifest, St
     let mut archive = ZipArchive::new(file).map_err(|e| format!("无法读取 ZIP 文件: {}", e))?;
 
     // 查找并读取 manifest.json
-    let manifest_content = {
-        let mut manifest_found = false;
-        let mut content = String::new();
-
-        for i in 0..archive.len() {
-            let mut file = archive
-                .by_index(i)
-                .map_err(|e| format!("读取 ZIP 条目失败: {}", e))?;
-
-            let file_name = file.name().to_string();
-
-            // 检查是否是根目录下的 manifest.json
-            if file_name == "manifest.json" || file_name.ends_with("/manifest.json") {
-                // 确保不是嵌套目录中的 manifest.json
-                let parts: Vec<&str> = file_name.split('/').collect();
-                if parts.len() <= 2 {
-                    // 允许 "manifest.json" 或 "folder/manifest.json"
-                    file.read_to_string(&mut content)
-                        .map_err(|e| format!("读取 manifest.json 失败: {}", e))?;
-                    manifest_found = true;
-                    break;
-                }
-            }
-        }
-
-        if !manifest_found {
-            return Err("ZIP 文件中未找到 manifest.json".to_string());
-        }
-
-        content
-    };
+    let (manifest_entry, manifest_content) = find_plugin_manifest(&mut archive)?;
 
     // 解析 manifest.json
     let manifest_value: serde_json::Value = serde_json::from_str(&manifest_content)
@@ -1849,11 +3079,33 @@ pub async fn preflight_plugin_zip(zip_path: String) -> Result<PluginManifest, St
         return Err(format!("非法的插件 ID: {}", plugin_id));
     }
 
+    // 提前校验完整性，结果交给前端展示
+    let trusted_keys = get_trusted_plugin_public_keys(&app);
+    let integrity = verify_plugin_integrity(
+        &mut archive,
+        &manifest_entry,
+        &manifest_value,
+        &trusted_keys,
+        get_plugin_max_unpacked_bytes(&app),
+    );
+
     // 将 serde_json::Value 转换为 PluginManifest 类型
     let plugin_manifest: PluginManifest = serde_json::from_value(manifest_value)
         .map_err(|e| format!("转换 manifest 类型失败: {}", e))?;
 
-    Ok(plugin_manifest)
+    // 宿主版本兼容性检查
+    let reasons = check_host_compatibility(&plugin_manifest.host, &app.package_info().version);
+
+    Ok(PluginPreflightResult {
+        manifest: plugin_manifest,
+        integrity,
+        compatible: reasons.is_empty(),
+        incompatible_reason: if reasons.is_empty() {
+            None
+        } else {
+            Some(reasons.join("；"))
+        },
+    })
 }
 
 // Tauri 命令：读取应用数据目录下的二进制文件
@@ -1959,14 +3211,90 @@ pub async fn copy_directory_in_app_data(
     ))
 }
 
+// 强制写入命令硬性禁止的可执行类扩展名，用户配置也无法放开
+const FORBIDDEN_WRITE_EXTENSIONS: &[&str] = &[
+    "exe", "dll", "sys", "com", "msi", "scr", "bat", "cmd", "ps1", "vbs", "sh", "bash", "zsh",
+    "so", "dylib", "app",
+];
+
+// 从 settings.json 的 forceWriteExtensions 字段读取用户追加的扩展名
+fn get_user_force_write_extensions(app: &AppHandle) -> Vec<String> {
+    let settings_path = crate::get_app_data_dir(app.config())
+        .join("app-settings")
+        .join("settings.json");
+
+    let Ok(contents) = fs::read_to_string(&settings_path) else {
+        return Vec::new();
+    };
+    let Ok(json) = serde_json::from_str::<Value>(&contents) else {
+        return Vec::new();
+    };
+
+    json.get("forceWriteExtensions")
+        .and_then(|v| v.as_array())
+        .map(|items| {
+            items
+                .iter()
+                .filter_map(|item| item.as_str())
+                .map(|ext| ext.trim().trim_start_matches('.').to_lowercase())
+                .filter(|ext| !ext.is_empty())
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+// 检查强制写入的扩展名：默认白名单 + 用户配置，可执行类扩展名始终禁止
+fn check_force_write_extension(
+    app: &AppHandle,
+    file_path: &Path,
+    default_extensions: &[&str],
+    command_name: &str,
+) -> AppResult<()> {
+    let ext = file_path
+        .extension()
+        .and_then(|e| e.to_str())
+        .map(|e| e.to_lowercase())
+        .unwrap_or_default();
+
+    if FORBIDDEN_WRITE_EXTENSIONS.contains(&ext.as_str()) {
+        return Err(AppError::PermissionDenied(format!(
+            "安全限制：{} 禁止写入可执行类文件 .{}",
+            command_name, ext
+        )));
+    }
+
+    let mut allowed: Vec<String> = default_extensions.iter().map(|e| e.to_string()).collect();
+    for user_ext in get_user_force_write_extensions(app) {
+        if !FORBIDDEN_WRITE_EXTENSIONS.contains(&user_ext.as_str()) && !allowed.contains(&user_ext)
+        {
+            allowed.push(user_ext);
+        }
+    }
+
+    if !allowed.contains(&ext) {
+        return Err(AppError::PermissionDenied(format!(
+            "安全限制：{} 不允许写入扩展名为 .{} 的文件。当前允许的扩展名: {}（可在 settings.json 的 forceWriteExtensions 中追加）",
+            command_name,
+            ext,
+            allowed
+                .iter()
+                .map(|e| format!(".{}", e))
+                .collect::<Vec<_>>()
+                .join(", ")
+        )));
+    }
+
+    Ok(())
+}
+
 // Tauri 命令：强制写入文件（绕过前端路径检查，自动创建父目录）
 // 安全限制：仅允许写入特定扩展名的文件，防止覆盖系统文件或写入可执行程序
 #[tauri::command]
-pub async fn write_file_force(path: String, content: Vec<u8>) -> Result<(), String> {
+pub async fn write_file_force(app: AppHandle, path: String, content: Vec<u8>) -> AppResult<()> {
     let file_path = PathBuf::from(&path);
 
     // 1. 扩展名白名单检查
-    let allowed_extensions = [
+    let default_extensions = [
         // 配置文件
         "json", "yaml", "yml", "toml", "ini", "xml", // 图片资源
         "png", "jpg", "jpeg", "gif", "webp", "svg", "ico", "bmp", "tiff",
@@ -1974,29 +3302,20 @@ pub async fn write_file_force(path: String, content: Vec<u8>) -> Result<(), Stri
         "txt", "md", "markdown", "log", // 压缩包
         "zip", "7z", "tar", "gz", "rar",
     ];
-
-    let ext = file_path
-        .extension()
-        .and_then(|e| e.to_str())
-        .map(|e| e.to_lowercase())
-        .unwrap_or_default();
-
-    if !allowed_extensions.contains(&ext.as_str()) {
-        return Err(format!("安全限制：不允许写入扩展名为 .{} 的文件", ext));
-    }
+    check_force_write_extension(&app, &file_path, &default_extensions, "write_file_force")?;
 
     // 2. 确保父目录存在
     let start = Instant::now();
     if let Some(parent) = file_path.parent() {
         if !parent.exists() {
-            fs::create_dir_all(parent).map_err(|e| format!("创建父目录失败: {}", e))?;
+            fs::create_dir_all(parent).context("创建父目录失败")?;
         }
     }
     let dir_duration = start.elapsed();
 
     // 3. 写入文件
     let write_start = Instant::now();
-    fs::write(&file_path, &content).map_err(|e| format!("写入文件失败: {}", e))?;
+    fs::write(&file_path, &content).context("写入文件失败")?;
     let write_duration = write_start.elapsed();
 
     if dir_duration.as_millis() > 100 || write_duration.as_millis() > 100 {
@@ -2016,39 +3335,32 @@ pub async fn write_file_force(path: String, content: Vec<u8>) -> Result<(), Stri
 // 避免了前端 Array.from(Uint8Array) → number[] → IPC JSON 序列化膨胀 3-4x 的问题。
 // 适用于写入 JSON、文本等字符串内容的场景。
 #[tauri::command]
-pub async fn write_text_file_force(path: String, content: String) -> Result<(), String> {
+pub async fn write_text_file_force(app: AppHandle, path: String, content: String) -> AppResult<()> {
     let file_path = PathBuf::from(&path);
 
     // 1. 扩展名白名单检查（与 write_file_force 保持一致）
-    let allowed_extensions = [
+    let default_extensions = [
         // 配置文件
         "json", "yaml", "yml", "toml", "ini", "xml", // 文档文本
         "txt", "md", "markdown", "log",
     ];
-
-    let ext = file_path
-        .extension()
-        .and_then(|e| e.to_str())
-        .map(|e| e.to_lowercase())
-        .unwrap_or_default();
-
-    if !allowed_extensions.contains(&ext.as_str()) {
-        return Err(format!(
-            "安全限制：write_text_file_force 不允许写入扩展名为 .{} 的文件",
-            ext
-        ));
-    }
+    check_force_write_extension(
+        &app,
+        &file_path,
+        &default_extensions,
+        "write_text_file_force",
+    )?;
 
     // 2. 确保父目录存在
     if let Some(parent) = file_path.parent() {
         if !parent.exists() {
-            fs::create_dir_all(parent).map_err(|e| format!("创建父目录失败: {}", e))?;
+            fs::create_dir_all(parent).context("创建父目录失败")?;
         }
     }
 
     // 3. 直接写入字符串内容
     let start = Instant::now();
-    fs::write(&file_path, content.as_bytes()).map_err(|e| format!("写入文件失败: {}", e))?;
+    fs::write(&file_path, content.as_bytes()).context("写入文件失败")?;
     let write_duration = start.elapsed();
 
     if write_duration.as_millis() > 100 {
@@ -2065,19 +3377,19 @@ pub async fn write_text_file_force(path: String, content: String) -> Result<(),
 
 // Tauri 命令：强制读取文本文件（绕过前端路径检查）
 #[tauri::command]
-pub async fn read_text_file_force(path: String) -> Result<String, String> {
+pub async fn read_text_file_force(path: String) -> AppResult<String> {
     let file_path = PathBuf::from(&path);
 
     if !file_path.exists() {
-        return Err(format!("文件不存在: {}", path));
+        return Err(AppError::NotFound(format!("文件不存在: {}", path)));
     }
 
-    fs::read_to_string(file_path).map_err(|e| format!("读取文件失败: {}", e))
+    fs::read_to_string(file_path).context("读取文件失败")
 }
 
 // Tauri 命令：强制追加内容到文件（绕过前端路径检查，自动创建父目录）
 #[tauri::command]
-pub async fn append_file_force(path: String, content: Vec<u8>) -> Result<(), String> {
+pub async fn append_file_force(path: String, content: Vec<u8>) -> AppResult<()> {
     let file_path = PathBuf::from(&path);
 
     // 安全限制：仅允许写入特定扩展名的文件
@@ -2089,13 +3401,16 @@ pub async fn append_file_force(path: String, content: Vec<u8>) -> Result<(), Str
         .unwrap_or_default();
 
     if !allowed_extensions.contains(&ext.as_str()) {
-        return Err(format!("安全限制：不允许追加到扩展名为 .{} 的文件", ext));
+        return Err(AppError::PermissionDenied(format!(
+            "安全限制：不允许追加到扩展名为 .{} 的文件",
+            ext
+        )));
     }
 
     // 确保父目录存在
     if let Some(parent) = file_path.parent() {
         if !parent.exists() {
-            fs::create_dir_all(parent).map_err(|e| format!("创建父目录失败: {}", e))?;
+            fs::create_dir_all(parent).context("创建父目录失败")?;
         }
     }
 
@@ -2104,10 +3419,9 @@ pub async fn append_file_force(path: String, content: Vec<u8>) -> Result<(), Str
         .create(true)
         .append(true)
         .open(&file_path)
-        .map_err(|e| format!("打开文件失败: {}", e))?;
+        .context("打开文件失败")?;
 
-    file.write_all(&content)
-        .map_err(|e| format!("写入文件失败: {}", e))?;
+    file.write_all(&content).context("写入文件失败")?;
 
     Ok(())
 }
@@ -2149,16 +3463,16 @@ pub fn open_path_force(path: String) -> Result<(), String> {
 
 // Tauri 命令：列出目录下的文件（仅限文件名，不包含完整路径）
 #[tauri::command]
-pub async fn list_directory(path: String) -> Result<Vec<String>, String> {
+pub async fn list_directory(path: String) -> AppResult<Vec<String>> {
     let dir_path = PathBuf::from(&path);
     if !dir_path.exists() {
-        return Err(format!("目录不存在: {}", path));
+        return Err(AppError::NotFound(format!("目录不存在: {}", path)));
     }
     if !dir_path.is_dir() {
-        return Err(format!("路径不是目录: {}", path));
+        return Err(AppError::InvalidInput(format!("路径不是目录: {}", path)));
     }
 
-    let entries = fs::read_dir(dir_path).map_err(|e| format!("读取目录失败: {}", e))?;
+    let entries = fs::read_dir(dir_path).context("读取目录失败")?;
     let mut files = Vec::new();
 
     for entry in entries.flatten() {
@@ -2172,12 +3486,193 @@ pub async fn list_directory(path: String) -> Result<Vec<String>, String> {
 
 // Tauri 命令：强制创建目录（绕过前端路径检查）
 #[tauri::command]
-pub async fn create_dir_force(path: String) -> Result<(), String> {
+pub async fn create_dir_force(path: String) -> AppResult<()> {
     let dir_path = PathBuf::from(&path);
 
     if !dir_path.exists() {
-        fs::create_dir_all(&dir_path).map_err(|e| format!("强制创建目录失败: {}", e))?;
+        fs::create_dir_all(&dir_path).context("强制创建目录失败")?;
     }
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_check_host_compatibility_reports_version_mismatch() {
+        let app_version = semver::Version::parse("0.6.6-r.1").unwrap();
+        let ok = HostRequirements {
+            app_version: ">=0.6.0".to_string(),
+            api_version: Some(HOST_PLUGIN_API_VERSION),
+        };
+        assert!(check_host_compatibility(&ok, &app_version).is_empty());
+
+        let too_new = HostRequirements {
+            app_version: "^1.0.0".to_string(),
+            api_version: Some(HOST_PLUGIN_API_VERSION + 1),
+        };
+        assert_eq!(check_host_compatibility(&too_new, &app_version).len(), 2);
+    }
+
+    #[test]
+    fn test_parse_operation_logs_skips_bad_lines_and_keeps_recent() {
+        let make = |timestamp: u64| OperationLog {
+            timestamp,
+            operation_type: "move".to_string(),
+            link_type: "symlink".to_string(),
+            source_count: 1,
+            success_count: 1,
+            error_count: 0,
+            errors: Vec::new(),
+            duration_ms: 1,
+            target_directory: "/target".to_string(),
+            source_paths: vec!["/source/a".to_string()],
+            total_size: 0,
+            processed_files: vec!["a".to_string()],
+            applied_policies: Vec::new(),
+        };
+        let content = [
+            serde_json::to_string(&make(1)).unwrap(),
+            "{not json".to_string(),
+            serde_json::to_string(&make(2)).unwrap(),
+            String::new(),
+            serde_json::to_string(&make(3)).unwrap(),
+        ]
+        .join("\n");
+
+        let logs = parse_operation_logs(&content, 2);
+        let timestamps: Vec<u64> = logs.iter().map(|l| l.timestamp).collect();
+        assert_eq!(timestamps, vec![2, 3]);
+    }
+
+    #[test]
+    fn test_collect_files_recursive_respects_ignores() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::write(dir.path().join(".gitignore"), "node_modules/\n").unwrap();
+        fs::create_dir_all(dir.path().join("node_modules/pkg")).unwrap();
+        fs::write(dir.path().join("node_modules/pkg/index.js"), b"x").unwrap();
+        fs::write(dir.path().join("keep.txt"), b"x").unwrap();
+        fs::write(dir.path().join("debug.log"), b"x").unwrap();
+
+        let mut files = Vec::new();
+        collect_files_recursive(dir.path(), &["*.log".to_string()], &mut files).unwrap();
+        let mut names: Vec<String> = files
+            .iter()
+            .map(|p| p.file_name().unwrap().to_string_lossy().to_string())
+            .collect();
+        names.sort();
+        assert_eq!(names, vec![".gitignore", "keep.txt"]);
+    }
+
+    #[test]
+    fn test_next_available_path_increments() {
+        let dir = tempfile::tempdir().unwrap();
+        let existing = dir.path().join("name.ext");
+        fs::write(&existing, b"a").unwrap();
+        fs::write(dir.path().join("name(1).ext"), b"b").unwrap();
+
+        assert_eq!(
+            next_available_path(&existing, false),
+            dir.path().join("name(2).ext")
+        );
+    }
+
+    #[test]
+    fn test_relative_path_between() {
+        let base = std::env::temp_dir().join("links").join("sub");
+        let target = std::env::temp_dir().join("assets").join("a.png");
+
+        assert_eq!(
+            relative_path_between(&base, &target),
+            Some(PathBuf::from("../../assets/a.png"))
+        );
+        assert_eq!(
+            relative_path_between(Path::new("relative/dir"), &target),
+            None
+        );
+    }
+
+    #[test]
+    fn test_expand_filename_template() {
+        let rules = vec![(
+            RuleRegex::Standard(Regex::new(r"第(\d+)章").unwrap()),
+            String::new(),
+            String::new(),
+            String::new(),
+        )];
+
+        assert_eq!(
+            expand_filename_template("序\n第12章 开始", &rules, "_${1}"),
+            Some("_12".to_string())
+        );
+        assert_eq!(expand_filename_template("没有章节", &rules, "_${1}"), None);
+    }
+
+    #[test]
+    fn test_build_rule_regex_falls_back_to_fancy() {
+        let (regex, uses_fancy) = build_rule_regex(r"foo(?=bar)", "m").unwrap();
+        assert!(uses_fancy);
+        assert_eq!(regex.count_matches("foobar foobaz"), 1);
+        assert_eq!(regex.replace_all("foobar", "X").unwrap(), "Xbar");
+
+        let (_, uses_fancy) = build_rule_regex(r"foo\d+", "m").unwrap();
+        assert!(!uses_fancy);
+    }
+
+    #[test]
+    fn test_next_available_path_without_extension() {
+        let dir = tempfile::tempdir().unwrap();
+        let existing = dir.path().join("README");
+        fs::write(&existing, b"a").unwrap();
+
+        assert_eq!(
+            next_available_path(&existing, false),
+            dir.path().join("README(1)")
+        );
+    }
+
+    #[test]
+    fn test_plugin_install_guard_removes_uncommitted_dir() {
+        let dir = tempfile::tempdir().unwrap();
+        let failed = dir.path().join("failed-plugin");
+        fs::create_dir_all(failed.join("sub")).unwrap();
+        fs::write(failed.join("sub/index.js"), b"x").unwrap();
+        drop(PluginInstallGuard::new(failed.clone()));
+        assert!(!failed.exists());
+
+        let installed = dir.path().join("installed-plugin");
+        fs::create_dir_all(&installed).unwrap();
+        PluginInstallGuard::new(installed.clone()).commit();
+        assert!(installed.exists());
+    }
+
+    #[test]
+    fn test_verify_plugin_integrity_enforces_unpacked_cap() {
+        use sha2::{Digest, Sha256};
+
+        let dir = tempfile::tempdir().unwrap();
+        let zip_path = dir.path().join("plugin.zip");
+        let payload = vec![b'a'; 4096];
+        {
+            let mut writer = zip::ZipWriter::new(fs::File::create(&zip_path).unwrap());
+            let options = zip::write::SimpleFileOptions::default();
+            writer.start_file("manifest.json", options).unwrap();
+            writer.write_all(b"{}").unwrap();
+            writer.start_file("index.js", options).unwrap();
+            writer.write_all(&payload).unwrap();
+            writer.finish().unwrap();
+        }
+        let expected = format!("{:x}", Sha256::digest(&payload));
+        let manifest = serde_json::json!({ "sha256": { "index.js": expected } });
+
+        let mut archive = ZipArchive::new(fs::File::open(&zip_path).unwrap()).unwrap();
+        let check = verify_plugin_integrity(&mut archive, "manifest.json", &manifest, &[], 8192);
+        assert_eq!(check.checksum_status, "verified");
+
+        let mut archive = ZipArchive::new(fs::File::open(&zip_path).unwrap()).unwrap();
+        let check = verify_plugin_integrity(&mut archive, "manifest.json", &manifest, &[], 1024);
+        assert_eq!(check.checksum_status, "failed");
+    }
+}
@@ -24,14 +24,17 @@
 //! - 提交统计信息 (`get_commit_stats`)
 //! - 文件变更列表 (`get_commit_files`)
 //! - 提交总数统计 (`get_total_commits`)
+//! - 模板化日志输出 (`git_format_log`)：后端自行解析 `--pretty=format:` 风格的模板，
+//!   遇到不支持的占位符时回退到 git 命令，检测不到 git 命令则报错
 //!
-//! ## 保留 Command 调用的功能及原因
-//! - `git_cherry_pick`: Cherry-pick 涉及工作区修改和复杂的冲突处理，使用 git2 实现较复杂
-//! - `git_revert`: Revert 同样涉及工作区修改和冲突处理
-//! - `git_format_log`: 支持用户自定义格式模板，git2 难以灵活实现
+//! ## 优先使用 Command 调用的功能
+//! 以下功能在检测到系统 git 命令时优先调用它，检测不到时回退到 git2 实现：
+//! - `git_cherry_pick`: 回退实现仅支持干净工作区下无冲突的 cherry-pick
+//! - `git_revert`: 回退实现同样仅支持干净工作区下无冲突的 revert
+//! - `git_update_commit_message`: 回退实现通过 `Commit::amend` 修改 HEAD
 
 use chrono::{FixedOffset, TimeZone};
-use git2::{BranchType, Delta, Oid, Repository};
+use git2::{BranchType, Delta, Oid, Repository, Status, StatusOptions};
 use lazy_static::lazy_static;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
@@ -80,6 +83,52 @@ pub struct GitBranch {
     pub remote: bool,
 }
 
+/// `git_blame` 返回的单行归属信息
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct BlameLine {
+    /// 当前版本中的行号（从 1 开始）
+    pub line_number: usize,
+    /// 引入该行的提交中的原始行号
+    pub original_line_number: usize,
+    pub hash: String,
+    pub author: String,
+    pub email: String,
+    pub date: String,
+    /// 引入该行的提交标题
+    pub summary: String,
+    /// 引入该行时的文件路径（文件被重命名过时与当前路径不同）
+    pub original_path: String,
+    pub content: String,
+}
+
+/// `git_export_commits` 的返回结果
+#[derive(Debug, Serialize)]
+pub struct GitExportResult {
+    /// 文本类格式（json/csv/markdown/html）的导出内容
+    pub content: Option<String>,
+    /// patch/mbox 格式生成的文件路径
+    pub files: Vec<String>,
+}
+
+/// `git_diff_range` 的返回结果
+#[derive(Debug, Serialize)]
+pub struct DiffRangeResult {
+    pub files: Vec<FileChange>,
+    pub stats: CommitStats,
+    /// unified diff 文本，仅在 `with_patch` 为 true 时返回
+    pub patch: Option<String>,
+}
+
+/// `git_file_history` 返回的单条记录
+#[derive(Debug, Serialize, Clone)]
+pub struct FileHistoryEntry {
+    pub commit: GitCommit,
+    /// 该提交对文件的变更，`path` 为提交后的路径（删除时为被删除的路径）
+    pub change: FileChange,
+    /// 该提交中发生重命名时的旧路径
+    pub previous_path: Option<String>,
+}
+
 #[derive(Debug, Serialize)]
 pub struct RepositoryInfo {
     pub branches: Vec<GitBranch>,
@@ -89,6 +138,26 @@ pub struct RepositoryInfo {
 lazy_static! {
     static ref CANCEL_TOKEN: Mutex<CancellationToken> = Mutex::new(CancellationToken::new());
     static ref ENRICH_CANCEL_TOKEN: Mutex<CancellationToken> = Mutex::new(CancellationToken::new());
+    /// 系统中是否存在可用的 git 命令（仅在首次使用时检测一次）
+    static ref GIT_CLI_AVAILABLE: bool = detect_git_cli();
+}
+
+/// 检测 PATH 中是否有可执行的 git 命令
+fn detect_git_cli() -> bool {
+    let mut cmd = Command::new("git");
+    #[cfg(target_os = "windows")]
+    cmd.creation_flags(0x08000000); // CREATE_NO_WINDOW
+
+    let available = cmd
+        .arg("--version")
+        .output()
+        .map(|output| output.status.success())
+        .unwrap_or(false);
+
+    if !available {
+        log::warn!("[GIT] 未检测到系统 git 命令，写操作与格式化日志将回退到 git2 实现");
+    }
+    available
 }
 
 #[derive(Debug, Clone, Serialize)]
@@ -502,6 +571,108 @@ pub async fn git_get_incremental_commits(
     get_commits_with_skip(repo_path, branch.as_deref(), skip, limit, false)
 }
 
+/// 在后端按作者/提交消息筛选提交
+///
+/// `query` 按空白拆分为多个关键词，每个关键词都是（忽略大小写的）正则表达式，
+/// 所有关键词都命中（任一被搜索的字段）才算匹配；两个开关都关闭时同时搜索作者和消息。
+/// `limit` 为 0 表示不限制数量。
+#[tauri::command]
+pub async fn git_search_commits(
+    path: String,
+    query: String,
+    search_author: bool,
+    search_message: bool,
+    limit: usize,
+) -> Result<Vec<GitCommit>, String> {
+    let repo_path = if path.is_empty() { "." } else { &path };
+    let matcher = CommitSearchMatcher::compile(&query, search_author, search_message)?;
+
+    let repo =
+        Repository::open(repo_path).map_err(|e| format!("Failed to open repository: {}", e))?;
+    let mut revwalk = repo
+        .revwalk()
+        .map_err(|e| format!("Failed to create revwalk: {}", e))?;
+    revwalk
+        .push_head()
+        .map_err(|e| format!("Failed to get HEAD: {}", e))?;
+    revwalk
+        .set_sorting(git2::Sort::TIME)
+        .map_err(|e| format!("Failed to set sorting: {}", e))?;
+
+    let tags_map = get_all_tags_map(&repo).unwrap_or_default();
+    let branch_tips = get_branch_tips_map(&repo).unwrap_or_default();
+    let mut commits = Vec::new();
+
+    for oid_result in revwalk {
+        if limit > 0 && commits.len() >= limit {
+            break;
+        }
+        let oid = oid_result.map_err(|e| format!("Failed to read commit OID: {}", e))?;
+        let Ok(commit) = repo.find_commit(oid) else {
+            continue;
+        };
+
+        // 先用原始字段筛选，命中后再构建完整的 GitCommit
+        let author = commit.author();
+        if !matcher.matches(
+            author.name().unwrap_or(""),
+            author.email().unwrap_or(""),
+            commit.message().unwrap_or(""),
+        ) {
+            continue;
+        }
+
+        if let Ok(parsed) =
+            parse_commit_optimized(&repo, oid, false, false, false, &tags_map, &branch_tips)
+        {
+            commits.push(parsed);
+        }
+    }
+
+    Ok(commits)
+}
+
+/// 提交搜索的匹配器，多个关键词之间为“与”关系
+struct CommitSearchMatcher {
+    patterns: Vec<regex::Regex>,
+    search_author: bool,
+    search_message: bool,
+}
+
+impl CommitSearchMatcher {
+    fn compile(query: &str, search_author: bool, search_message: bool) -> Result<Self, String> {
+        let patterns = query
+            .split_whitespace()
+            .map(|term| {
+                regex::RegexBuilder::new(term)
+                    .case_insensitive(true)
+                    .build()
+                    .map_err(|e| format!("Invalid search pattern '{}': {}", term, e))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        if patterns.is_empty() {
+            return Err("Search query is empty".to_string());
+        }
+
+        // 两个开关都没开时视为全部搜索
+        let search_all = !search_author && !search_message;
+        Ok(Self {
+            patterns,
+            search_author: search_author || search_all,
+            search_message: search_message || search_all,
+        })
+    }
+
+    fn matches(&self, author_name: &str, author_email: &str, message: &str) -> bool {
+        self.patterns.iter().all(|pattern| {
+            (self.search_author
+                && (pattern.is_match(author_name) || pattern.is_match(author_email)))
+                || (self.search_message && pattern.is_match(message))
+        })
+    }
+}
+
 #[tauri::command]
 #[allow(clippy::too_many_arguments)]
 pub async fn git_load_incremental_stream(
@@ -702,6 +873,11 @@ pub async fn git_get_commit_detail(path: String, hash: String) -> Result<GitComm
 pub async fn git_cherry_pick(path: String, hash: String) -> Result<String, String> {
     let repo_path = if path.is_empty() { "." } else { &path };
 
+    if !*GIT_CLI_AVAILABLE {
+        cherry_pick_git2(repo_path, &hash)?;
+        return Ok(format!("Successfully cherry-picked commit {}", hash));
+    }
+
     let mut cmd = Command::new("git");
     #[cfg(target_os = "windows")]
     cmd.creation_flags(0x08000000); // CREATE_NO_WINDOW
@@ -725,6 +901,11 @@ pub async fn git_cherry_pick(path: String, hash: String) -> Result<String, Strin
 pub async fn git_revert(path: String, hash: String) -> Result<String, String> {
     let repo_path = if path.is_empty() { "." } else { &path };
 
+    if !*GIT_CLI_AVAILABLE {
+        revert_git2(repo_path, &hash)?;
+        return Ok(format!("Successfully reverted commit {}", hash));
+    }
+
     let mut cmd = Command::new("git");
     #[cfg(target_os = "windows")]
     cmd.creation_flags(0x08000000); // CREATE_NO_WINDOW
@@ -745,9 +926,46 @@ pub async fn git_revert(path: String, hash: String) -> Result<String, String> {
     Ok(format!("Successfully reverted commit {}", hash))
 }
 
+/// 导出提交
+///
+/// - json/csv/markdown/html：返回格式化文本
+/// - patch：每个提交生成一个 `git format-patch` 风格的文件
+/// - mbox：所有提交合并为单个 mbox 文件
+///
+/// patch/mbox 需要提供仓库路径和输出目录，生成的文件可以直接用 `git am` 应用
 #[tauri::command]
-pub async fn git_export_commits(commits: Vec<GitCommit>, format: String) -> Result<String, String> {
+pub async fn git_export_commits(
+    commits: Vec<GitCommit>,
+    format: String,
+    path: Option<String>,
+    output_dir: Option<String>,
+) -> Result<GitExportResult, String> {
     match format.as_str() {
+        "patch" | "mbox" => {
+            let output_dir = output_dir
+                .filter(|dir| !dir.trim().is_empty())
+                .ok_or_else(|| "Output directory is required for patch/mbox export".to_string())?;
+            let repo_path = path.as_deref().filter(|p| !p.is_empty()).unwrap_or(".");
+            let files = export_commits_as_patches(
+                repo_path,
+                &commits,
+                std::path::Path::new(&output_dir),
+                format == "mbox",
+            )?;
+            Ok(GitExportResult {
+                content: None,
+                files,
+            })
+        }
+        _ => format_commits_as_text(commits, &format).map(|content| GitExportResult {
+            content: Some(content),
+            files: Vec::new(),
+        }),
+    }
+}
+
+fn format_commits_as_text(commits: Vec<GitCommit>, format: &str) -> Result<String, String> {
+    match format {
         "json" => serde_json::to_string_pretty(&commits)
             .map_err(|e| format!("Failed to serialize to JSON: {}", e)),
         "csv" => {
@@ -821,6 +1039,286 @@ pub async fn git_export_commits(commits: Vec<GitCommit>, format: String) -> Resu
     }
 }
 
+/// 把提交导出为 `git am` 可直接应用的 patch 文件或单个 mbox 文件
+fn export_commits_as_patches(
+    repo_path: &str,
+    commits: &[GitCommit],
+    output_dir: &std::path::Path,
+    single_mbox: bool,
+) -> Result<Vec<String>, String> {
+    if commits.is_empty() {
+        return Err("No commits to export".to_string());
+    }
+
+    let repo =
+        Repository::open(repo_path).map_err(|e| format!("Failed to open repository: {}", e))?;
+    let resolved = sort_commits_oldest_first(&repo, commits)?;
+
+    std::fs::create_dir_all(output_dir)
+        .map_err(|e| format!("Failed to create output directory: {}", e))?;
+
+    let total = resolved.len();
+    let mut files = Vec::new();
+    let mut mbox = String::new();
+
+    for (index, commit) in resolved.iter().enumerate() {
+        let email = format_commit_as_email(&repo, commit, index + 1, total)?;
+        if single_mbox {
+            mbox.push_str(&email);
+            continue;
+        }
+
+        let file_name = patch_file_name(
+            index + 1,
+            commit.summary().unwrap_or(""),
+            &commit.id().to_string(),
+        );
+        let file_path = output_dir.join(file_name);
+        std::fs::write(&file_path, email)
+            .map_err(|e| format!("Failed to write {}: {}", file_path.display(), e))?;
+        files.push(file_path.to_string_lossy().to_string());
+    }
+
+    if single_mbox {
+        let short = |commit: &git2::Commit<'_>| commit.id().to_string()[..7].to_string();
+        let file_path = output_dir.join(format!(
+            "{}-{}.mbox",
+            short(&resolved[0]),
+            short(&resolved[total - 1])
+        ));
+        std::fs::write(&file_path, mbox)
+            .map_err(|e| format!("Failed to write {}: {}", file_path.display(), e))?;
+        files.push(file_path.to_string_lossy().to_string());
+    }
+
+    log::info!(
+        "[GIT] 已导出 {} 个提交为 {}，共 {} 个文件",
+        total,
+        if single_mbox { "mbox" } else { "patch" },
+        files.len()
+    );
+    Ok(files)
+}
+
+/// 与 git format-patch 一致，按拓扑顺序从旧到新排列（同一秒内的提交也能保证父提交在前）
+fn sort_commits_oldest_first<'repo>(
+    repo: &'repo Repository,
+    commits: &[GitCommit],
+) -> Result<Vec<git2::Commit<'repo>>, String> {
+    let mut pending: std::collections::HashSet<Oid> = commits
+        .iter()
+        .map(|c| Oid::from_str(&c.hash).map_err(|e| format!("Invalid commit hash: {}", e)))
+        .collect::<Result<_, _>>()?;
+
+    let mut revwalk = repo
+        .revwalk()
+        .map_err(|e| format!("Failed to create revwalk: {}", e))?;
+    for oid in &pending {
+        revwalk
+            .push(*oid)
+            .map_err(|e| format!("Failed to find commit {}: {}", oid, e))?;
+    }
+    revwalk
+        .set_sorting(git2::Sort::TOPOLOGICAL | git2::Sort::TIME | git2::Sort::REVERSE)
+        .map_err(|e| format!("Failed to set sorting: {}", e))?;
+
+    let mut sorted = Vec::with_capacity(pending.len());
+    for oid_result in revwalk {
+        let oid = oid_result.map_err(|e| format!("Failed to read commit OID: {}", e))?;
+        if pending.remove(&oid) {
+            sorted.push(
+                repo.find_commit(oid)
+                    .map_err(|e| format!("Failed to find commit {}: {}", oid, e))?,
+            );
+        }
+        if pending.is_empty() {
+            break;
+        }
+    }
+    Ok(sorted)
+}
+
+/// 生成单个提交的 format-patch 邮件文本
+fn format_commit_as_email(
+    repo: &Repository,
+    commit: &git2::Commit<'_>,
+    index: usize,
+    total: usize,
+) -> Result<String, String> {
+    let parent_tree = if commit.parent_count() > 0 {
+        Some(
+            commit
+                .parent(0)
+                .and_then(|parent| parent.tree())
+                .map_err(|e| format!("Failed to get parent tree: {}", e))?,
+        )
+    } else {
+        None
+    };
+    let tree = commit
+        .tree()
+        .map_err(|e| format!("Failed to get commit tree: {}", e))?;
+
+    // 二进制文件需要输出完整的 binary patch，git am 才能应用
+    let mut options = git2::DiffOptions::new();
+    options.show_binary(true);
+    let mut diff = repo
+        .diff_tree_to_tree(parent_tree.as_ref(), Some(&tree), Some(&mut options))
+        .map_err(|e| format!("Failed to create diff: {}", e))?;
+    diff.find_similar(Some(git2::DiffFindOptions::new().renames(true)))
+        .map_err(|e| format!("Failed to detect renames: {}", e))?;
+
+    let diffstat = diff
+        .stats()
+        .and_then(|stats| stats.to_buf(git2::DiffStatsFormat::FULL, 72))
+        .map_err(|e| format!("Failed to build diffstat: {}", e))?;
+    let patch = render_unified_diff(&diff)?;
+
+    let author = commit.author();
+    let when = author.when();
+    let date = FixedOffset::east_opt(when.offset_minutes().saturating_mul(60))
+        .and_then(|offset| offset.timestamp_opt(when.seconds(), 0).single())
+        .map(|datetime| datetime.to_rfc2822())
+        .unwrap_or_default();
+
+    let message = commit.message().unwrap_or("");
+    let (subject, body) = match message.split_once('\n') {
+        Some((subject, body)) => (subject.trim(), body.trim()),
+        None => (message.trim(), ""),
+    };
+
+    let mut email = build_patch_header(
+        &commit.id().to_string(),
+        author.name().unwrap_or(""),
+        author.email().unwrap_or(""),
+        &date,
+        subject,
+        index,
+        total,
+    );
+    if !body.is_empty() {
+        email.push_str(body);
+        email.push_str("\n\n");
+    }
+    email.push_str("---\n");
+    email.push_str(diffstat.as_str().unwrap_or(""));
+    email.push('\n');
+    email.push_str(&patch);
+    email.push_str("-- \nAIO Hub\n\n");
+    Ok(email)
+}
+
+/// 构建 format-patch 的邮件头（含结尾空行）
+///
+/// 非 ASCII 的作者名和标题按 RFC 2047 编码，正文声明为 UTF-8
+fn build_patch_header(
+    hash: &str,
+    author_name: &str,
+    author_email: &str,
+    date: &str,
+    subject: &str,
+    index: usize,
+    total: usize,
+) -> String {
+    let prefix = if total > 1 {
+        format!("[PATCH {}/{}]", index, total)
+    } else {
+        "[PATCH]".to_string()
+    };
+
+    format!(
+        "From {} Mon Sep 17 00:00:00 2001\n\
+         From: {} <{}>\n\
+         Date: {}\n\
+         Subject: {} {}\n\
+         MIME-Version: 1.0\n\
+         Content-Type: text/plain; charset=UTF-8\n\
+         Content-Transfer-Encoding: 8bit\n\n",
+        hash,
+        encode_header_phrase(author_name),
+        author_email,
+        date,
+        prefix,
+        encode_header_text(subject)
+    )
+}
+
+/// 邮件头中的显示名：非 ASCII 时编码，含特殊字符时加引号
+fn encode_header_phrase(name: &str) -> String {
+    if !name.is_ascii() {
+        return encode_header_text(name);
+    }
+    if name.chars().any(|c| "()<>@,;:\\\".[]".contains(c)) {
+        return format!("\"{}\"", name.replace('\\', "\\\\").replace('"', "\\\""));
+    }
+    name.to_string()
+}
+
+/// RFC 2047 Q 编码；ASCII 文本原样返回，过长时拆分为多个 encoded-word 并折行
+fn encode_header_text(text: &str) -> String {
+    if text.is_ascii() {
+        return text.to_string();
+    }
+
+    // encoded-word 总长不超过 75：去掉 "=?UTF-8?q?" 和 "?=" 后负载最多 63 个字符
+    const MAX_PAYLOAD: usize = 63;
+    let mut words = Vec::new();
+    let mut payload = String::new();
+
+    for ch in text.chars() {
+        let mut encoded = String::new();
+        if ch == ' ' {
+            encoded.push('_');
+        } else if ch.is_ascii_alphanumeric() || "!*+-/".contains(ch) {
+            encoded.push(ch);
+        } else {
+            let mut buf = [0u8; 4];
+            for byte in ch.encode_utf8(&mut buf).bytes() {
+                encoded.push_str(&format!("={:02X}", byte));
+            }
+        }
+
+        // 不在多字节字符中间拆分
+        if payload.len() + encoded.len() > MAX_PAYLOAD {
+            words.push(std::mem::take(&mut payload));
+        }
+        payload.push_str(&encoded);
+    }
+    if !payload.is_empty() {
+        words.push(payload);
+    }
+
+    words
+        .iter()
+        .map(|word| format!("=?UTF-8?q?{}?=", word))
+        .collect::<Vec<_>>()
+        .join("\n ")
+}
+
+/// 与 git format-patch 相同的文件命名：`0001-<净化后的标题>.patch`
+fn patch_file_name(index: usize, subject: &str, hash: &str) -> String {
+    const MAX_SUBJECT_LEN: usize = 52;
+    let mut sanitized = String::new();
+    for ch in subject.chars() {
+        if ch.is_ascii_alphanumeric() || ch == '.' || ch == '_' {
+            sanitized.push(ch);
+        } else if !sanitized.ends_with('-') {
+            sanitized.push('-');
+        }
+    }
+    let mut sanitized = sanitized.trim_matches(['-', '.']).to_string();
+    sanitized.truncate(MAX_SUBJECT_LEN);
+    let sanitized = sanitized.trim_end_matches(['-', '.']);
+
+    // 纯中文标题净化后为空，用短 hash 代替
+    let name = if sanitized.is_empty() {
+        &hash[..hash.len().min(7)]
+    } else {
+        sanitized
+    };
+    format!("{:04}-{}.patch", index, name)
+}
+
 #[tauri::command]
 pub async fn git_update_commit_message(
     path: String,
@@ -836,6 +1334,18 @@ pub async fn git_update_commit_message(
     let head_oid = head.target().ok_or("HEAD 没有指向任何提交")?;
 
     if head_oid.to_string() == hash {
+        if !*GIT_CLI_AVAILABLE {
+            // 与 commit --amend 一致：带上暂存区的改动
+            let head_commit = repo
+                .find_commit(head_oid)
+                .map_err(|e| format!("读取 HEAD 提交失败: {}", e))?;
+            let tree = write_index_tree(&repo)?;
+            head_commit
+                .amend(Some("HEAD"), None, None, None, Some(&message), Some(&tree))
+                .map_err(|e| format!("修改提交消息失败: {}", e))?;
+            return Ok("成功修改最近一次提交的消息".to_string());
+        }
+
         // 是 HEAD，使用 commit --amend
         let mut cmd = Command::new("git");
         #[cfg(target_os = "windows")]
@@ -862,26 +1372,50 @@ pub async fn git_update_commit_message(
     }
 }
 
+/// 按模板逐条渲染提交日志，用于生成发布说明或 changelog
+///
+/// 模板语法见 `render_log_template`；`date_format` 为 strftime 格式（如 `%Y-%m-%d`），
+/// 作用于 `%ad` / `%cd`，为空时使用 git 默认的日期格式
 #[tauri::command]
 pub async fn git_format_log(
     path: String,
     template: String,
     limit: usize,
+    date_format: Option<String>,
 ) -> Result<String, String> {
     let repo_path = if path.is_empty() { "." } else { &path };
+    let date_format = date_format.filter(|f| !f.trim().is_empty());
+    if let Some(format) = date_format.as_deref() {
+        validate_date_format(format)?;
+    }
+
+    // 模板中含有 git2 渲染不了的占位符时交给 git 命令处理，避免把它们当作普通文本输出
+    let unsupported = unsupported_log_placeholders(&template);
+    if unsupported.is_empty() {
+        return format_log_git2(repo_path, &template, limit, date_format.as_deref());
+    }
+    if !*GIT_CLI_AVAILABLE {
+        return Err(format!(
+            "模板包含不支持的占位符: {}（未检测到 git 命令，无法回退）",
+            unsupported.join(", ")
+        ));
+    }
 
     let mut cmd = Command::new("git");
     #[cfg(target_os = "windows")]
     cmd.creation_flags(0x08000000); // CREATE_NO_WINDOW
 
-    let output = cmd
-        .arg("-C")
+    cmd.arg("-C")
         .arg(repo_path)
         .arg("log")
         .arg(format!("--pretty=format:{}", template))
-        .arg(format!("-{}", limit))
+        .arg(format!("-{}", limit));
+    if let Some(format) = date_format.as_deref() {
+        cmd.arg(format!("--date=format:{}", format));
+    }
+    let output = cmd
         .output()
-        .map_err(|e| format!("Failed to format log: {}", e))?;
+        .map_err(|e| format!("Failed to execute git log: {}", e))?;
 
     if !output.status.success() {
         return Err(String::from_utf8_lossy(&output.stderr).to_string());
@@ -890,6 +1424,915 @@ pub async fn git_format_log(
     Ok(String::from_utf8_lossy(&output.stdout).to_string())
 }
 
+/// 比较任意两个版本之间的累计差异
+///
+/// - `from`/`to` 可以是提交 hash、分支名、标签等任意 revspec，`to` 为空时使用 HEAD
+/// - `from` 为空时返回 `to` 相对工作区（含暂存区）的变更
+/// - 会检测重命名（状态 R，路径为新路径）；二进制文件只记录状态，不统计行数
+#[tauri::command]
+pub async fn git_diff_range(
+    path: String,
+    from: String,
+    to: String,
+    with_patch: bool,
+) -> Result<DiffRangeResult, String> {
+    let repo_path = if path.is_empty() { "." } else { &path };
+    let repo =
+        Repository::open(repo_path).map_err(|e| format!("Failed to open repository: {}", e))?;
+
+    let resolve_tree = |spec: &str| {
+        let spec = if spec.trim().is_empty() {
+            "HEAD"
+        } else {
+            spec.trim()
+        };
+        repo.revparse_single(spec)
+            .and_then(|obj| obj.peel_to_tree())
+            .map_err(|e| format!("Failed to resolve '{}': {}", spec, e))
+    };
+
+    let to_tree = resolve_tree(&to)?;
+    let mut options = git2::DiffOptions::new();
+    let mut diff = if from.trim().is_empty() {
+        options.include_untracked(true).recurse_untracked_dirs(true);
+        repo.diff_tree_to_workdir_with_index(Some(&to_tree), Some(&mut options))
+    } else {
+        let from_tree = resolve_tree(&from)?;
+        repo.diff_tree_to_tree(Some(&from_tree), Some(&to_tree), Some(&mut options))
+    }
+    .map_err(|e| format!("Failed to create diff: {}", e))?;
+
+    // 默认的 diff 不做重命名检测，需要显式开启
+    diff.find_similar(Some(git2::DiffFindOptions::new().renames(true)))
+        .map_err(|e| format!("Failed to detect renames: {}", e))?;
+
+    let (stats, mut files) = collect_diff_file_changes(&diff)?;
+    files.sort_by(|a, b| a.path.cmp(&b.path));
+
+    let patch = if with_patch {
+        Some(render_unified_diff(&diff)?)
+    } else {
+        None
+    };
+
+    Ok(DiffRangeResult {
+        files,
+        stats,
+        patch,
+    })
+}
+
+/// 将 diff 渲染为 unified diff 文本（二进制文件输出 "Binary files ... differ"）
+fn render_unified_diff(diff: &git2::Diff<'_>) -> Result<String, String> {
+    let mut text = String::new();
+    diff.print(git2::DiffFormat::Patch, |_delta, _hunk, line| {
+        // 内容行的前缀（+/-/空格）不包含在 content 中，需要手动补上
+        if matches!(line.origin(), '+' | '-' | ' ') {
+            text.push(line.origin());
+        }
+        text.push_str(&String::from_utf8_lossy(line.content()));
+        true
+    })
+    .map_err(|e| format!("Failed to render patch: {}", e))?;
+    Ok(text)
+}
+
+/// 查看单个文件的提交历史
+///
+/// 与 `git log [--follow] -- <file>` 的语义一致：只返回改动过该文件的提交，
+/// 合并提交只有在与所有父提交都不同时才会出现。`follow` 为 true 时跨重命名追踪，
+/// 旧路径记录在 `previous_path` 中。`limit` 为 0 表示不限制数量。
+#[tauri::command]
+pub async fn git_file_history(
+    path: String,
+    file: String,
+    follow: bool,
+    limit: usize,
+) -> Result<Vec<FileHistoryEntry>, String> {
+    let repo_path = if path.is_empty() { "." } else { &path };
+    let repo =
+        Repository::open(repo_path).map_err(|e| format!("Failed to open repository: {}", e))?;
+    let mut current_path = relative_repo_path(&repo, &file);
+
+    let mut revwalk = repo
+        .revwalk()
+        .map_err(|e| format!("Failed to create revwalk: {}", e))?;
+    revwalk
+        .push_head()
+        .map_err(|e| format!("Failed to get HEAD: {}", e))?;
+    revwalk
+        .set_sorting(git2::Sort::TOPOLOGICAL | git2::Sort::TIME)
+        .map_err(|e| format!("Failed to set sorting: {}", e))?;
+
+    let tags_map = get_all_tags_map(&repo).unwrap_or_default();
+    let branch_tips = get_branch_tips_map(&repo).unwrap_or_default();
+    let mut history = Vec::new();
+
+    for oid_result in revwalk {
+        if limit > 0 && history.len() >= limit {
+            break;
+        }
+        let oid = oid_result.map_err(|e| format!("Failed to read commit OID: {}", e))?;
+        let commit = repo
+            .find_commit(oid)
+            .map_err(|e| format!("Failed to find commit {}: {}", oid, e))?;
+        let tree = commit
+            .tree()
+            .map_err(|e| format!("Failed to get commit tree: {}", e))?;
+
+        let entry_id = tree_entry_id(&tree, &current_path);
+        let parent_trees: Vec<git2::Tree<'_>> = commit
+            .parents()
+            .map(|parent| parent.tree())
+            .collect::<Result<_, _>>()
+            .map_err(|e| format!("Failed to get parent tree: {}", e))?;
+        let parent_entry_ids: Vec<Option<Oid>> = parent_trees
+            .iter()
+            .map(|parent_tree| tree_entry_id(parent_tree, &current_path))
+            .collect();
+
+        // 文件在该提交及其父提交中都不存在，或者与某个父提交相同，则没有改动
+        if entry_id.is_none() && parent_entry_ids.iter().all(Option::is_none) {
+            continue;
+        }
+        if parent_entry_ids.contains(&entry_id) {
+            continue;
+        }
+
+        let first_parent_tree = parent_trees.first();
+        let renamed_from = if follow
+            && entry_id.is_some()
+            && first_parent_tree.is_some()
+            && parent_entry_ids.first() == Some(&None)
+        {
+            find_rename_source(&repo, first_parent_tree, &tree, &current_path)?
+        } else {
+            None
+        };
+
+        let change = file_change_in_commit(
+            &repo,
+            first_parent_tree,
+            &tree,
+            &current_path,
+            renamed_from.as_deref(),
+        )?;
+
+        if let Ok(parsed) =
+            parse_commit_optimized(&repo, oid, false, false, false, &tags_map, &branch_tips)
+        {
+            history.push(FileHistoryEntry {
+                commit: parsed,
+                change,
+                previous_path: renamed_from.clone(),
+            });
+        }
+
+        // 更早的提交里文件使用旧路径
+        if let Some(previous) = renamed_from {
+            current_path = previous;
+        }
+    }
+
+    Ok(history)
+}
+
+fn tree_entry_id(tree: &git2::Tree<'_>, path: &str) -> Option<Oid> {
+    tree.get_path(std::path::Path::new(path))
+        .ok()
+        .map(|entry| entry.id())
+}
+
+/// 在父提交到当前提交的完整 diff 中查找以 `path` 为新路径的重命名
+fn find_rename_source(
+    repo: &Repository,
+    parent_tree: Option<&git2::Tree<'_>>,
+    tree: &git2::Tree<'_>,
+    path: &str,
+) -> Result<Option<String>, String> {
+    let mut diff = repo
+        .diff_tree_to_tree(parent_tree, Some(tree), None)
+        .map_err(|e| format!("Failed to create diff: {}", e))?;
+    diff.find_similar(Some(git2::DiffFindOptions::new().renames(true)))
+        .map_err(|e| format!("Failed to detect renames: {}", e))?;
+
+    let source = diff
+        .deltas()
+        .filter(|delta| delta.status() == Delta::Renamed)
+        .find(|delta| delta.new_file().path() == Some(std::path::Path::new(path)))
+        .and_then(|delta| {
+            delta
+                .old_file()
+                .path()
+                .map(|p| p.to_string_lossy().replace('\\', "/"))
+        });
+    Ok(source)
+}
+
+/// 计算单个文件在某次提交中的状态和增删行数
+fn file_change_in_commit(
+    repo: &Repository,
+    parent_tree: Option<&git2::Tree<'_>>,
+    tree: &git2::Tree<'_>,
+    path: &str,
+    renamed_from: Option<&str>,
+) -> Result<FileChange, String> {
+    let mut options = git2::DiffOptions::new();
+    options.disable_pathspec_match(true).pathspec(path);
+    if let Some(previous) = renamed_from {
+        options.pathspec(previous);
+    }
+    let mut diff = repo
+        .diff_tree_to_tree(parent_tree, Some(tree), Some(&mut options))
+        .map_err(|e| format!("Failed to create diff: {}", e))?;
+    if renamed_from.is_some() {
+        diff.find_similar(Some(git2::DiffFindOptions::new().renames(true)))
+            .map_err(|e| format!("Failed to detect renames: {}", e))?;
+    }
+
+    let mut change = FileChange {
+        path: path.to_string(),
+        status: "M".to_string(),
+        additions: 0,
+        deletions: 0,
+    };
+    for (index, delta) in diff.deltas().enumerate() {
+        let delta_path = delta.new_file().path().or_else(|| delta.old_file().path());
+        if delta_path != Some(std::path::Path::new(path)) {
+            continue;
+        }
+        change.status = delta_status_str(delta.status());
+        if let Some(patch) = git2::Patch::from_diff(&diff, index)
+            .map_err(|e| format!("Failed to create patch: {}", e))?
+        {
+            let (_, additions, deletions) = patch
+                .line_stats()
+                .map_err(|e| format!("Failed to get line stats: {}", e))?;
+            change.additions = additions as u32;
+            change.deletions = deletions as u32;
+        }
+        break;
+    }
+
+    Ok(change)
+}
+
+/// 逐行查看文件的最后修改归属
+///
+/// `rev` 为空时对工作区文件执行 blame（未提交的行 hash 为全 0）；
+/// 文件重命名会被自动跟踪，原始路径记录在 `original_path` 中
+#[tauri::command]
+pub async fn git_blame(
+    path: String,
+    file: String,
+    rev: Option<String>,
+) -> Result<Vec<BlameLine>, String> {
+    let repo_path = if path.is_empty() { "." } else { &path };
+    let repo =
+        Repository::open(repo_path).map_err(|e| format!("Failed to open repository: {}", e))?;
+    let file = relative_repo_path(&repo, &file);
+    let rev = rev.filter(|r| !r.trim().is_empty());
+
+    ensure_blame_target_is_text(&repo, &file, rev.as_deref())?;
+
+    if !*GIT_CLI_AVAILABLE {
+        return blame_git2(&repo, &file, rev.as_deref());
+    }
+
+    let mut cmd = Command::new("git");
+    #[cfg(target_os = "windows")]
+    cmd.creation_flags(0x08000000); // CREATE_NO_WINDOW
+
+    cmd.arg("-C")
+        .arg(repo_path)
+        .arg("blame")
+        .arg("--line-porcelain");
+    if let Some(rev) = &rev {
+        cmd.arg(rev);
+    }
+    let output = cmd
+        .arg("--")
+        .arg(&file)
+        .output()
+        .map_err(|e| format!("Failed to run git blame: {}", e))?;
+
+    if !output.status.success() {
+        return Err(String::from_utf8_lossy(&output.stderr).trim().to_string());
+    }
+
+    parse_blame_porcelain(&String::from_utf8_lossy(&output.stdout))
+}
+
+/// 把绝对路径转换为相对仓库根目录的路径，并统一使用 `/` 分隔
+fn relative_repo_path(repo: &Repository, file: &str) -> String {
+    let path = std::path::Path::new(file);
+    let relative = repo
+        .workdir()
+        .and_then(|workdir| path.strip_prefix(workdir).ok())
+        .unwrap_or(path);
+    relative.to_string_lossy().replace('\\', "/")
+}
+
+/// 二进制文件的 blame 没有意义，提前给出明确的错误
+fn ensure_blame_target_is_text(
+    repo: &Repository,
+    file: &str,
+    rev: Option<&str>,
+) -> Result<(), String> {
+    let is_binary = match rev {
+        Some(rev) => repo
+            .revparse_single(&format!("{}:{}", rev, file))
+            .and_then(|obj| obj.peel_to_blob())
+            .map(|blob| blob.is_binary())
+            .unwrap_or(false),
+        None => repo
+            .workdir()
+            .and_then(|workdir| std::fs::read(workdir.join(file)).ok())
+            .map(|bytes| bytes.iter().take(8000).any(|b| *b == 0))
+            .unwrap_or(false),
+    };
+
+    if is_binary {
+        return Err(format!("Cannot blame binary file: {}", file));
+    }
+    Ok(())
+}
+
+/// 将提交时间格式化为带原始时区偏移的 RFC3339 字符串
+fn format_git_time(seconds: i64, offset_minutes: i32) -> String {
+    FixedOffset::east_opt(offset_minutes.saturating_mul(60))
+        .and_then(|offset| offset.timestamp_opt(seconds, 0).single())
+        .map(|datetime| datetime.to_rfc3339())
+        .or_else(|| {
+            chrono::Utc
+                .timestamp_opt(seconds, 0)
+                .single()
+                .map(|datetime| datetime.to_rfc3339())
+        })
+        .unwrap_or_default()
+}
+
+/// 解析 `+0800` / `-0530` 形式的时区偏移，返回分钟数
+fn parse_git_tz(tz: &str) -> i32 {
+    let (sign, digits) = match tz.split_at_checked(1) {
+        Some(("-", rest)) => (-1, rest),
+        Some(("+", rest)) => (1, rest),
+        _ => return 0,
+    };
+    let value: i32 = digits.parse().unwrap_or(0);
+    sign * ((value / 100) * 60 + value % 100)
+}
+
+/// 解析 `git blame --line-porcelain` 的输出
+///
+/// 每行内容前都有完整的提交头信息，内容行以制表符开头
+fn parse_blame_porcelain(output: &str) -> Result<Vec<BlameLine>, String> {
+    let mut lines = Vec::new();
+    let mut current: Option<BlameLine> = None;
+    let mut author_time = 0i64;
+    let mut author_tz = 0i32;
+
+    for raw in output.lines() {
+        if let Some(content) = raw.strip_prefix('\t') {
+            let mut line = current
+                .take()
+                .ok_or_else(|| "Malformed blame output: content without header".to_string())?;
+            line.date = format_git_time(author_time, author_tz);
+            line.content = content.to_string();
+            lines.push(line);
+            continue;
+        }
+
+        let (key, value) = raw.split_once(' ').unwrap_or((raw, ""));
+        if current.is_none() {
+            // 头部首行：<hash> <原始行号> <最终行号> [<分组行数>]
+            let mut parts = value.split(' ');
+            let original = parts.next().and_then(|n| n.parse().ok());
+            let final_line = parts.next().and_then(|n| n.parse().ok());
+            match (original, final_line) {
+                (Some(original), Some(final_line))
+                    if key.len() >= 40 && key.chars().all(|c| c.is_ascii_hexdigit()) =>
+                {
+                    current = Some(BlameLine {
+                        line_number: final_line,
+                        original_line_number: original,
+                        hash: key.to_string(),
+                        author: String::new(),
+                        email: String::new(),
+                        date: String::new(),
+                        summary: String::new(),
+                        original_path: String::new(),
+                        content: String::new(),
+                    });
+                    author_time = 0;
+                    author_tz = 0;
+                }
+                _ => return Err(format!("Malformed blame header: {}", raw)),
+            }
+            continue;
+        }
+
+        if let Some(line) = current.as_mut() {
+            match key {
+                "author" => line.author = value.to_string(),
+                "author-mail" => {
+                    line.email = value
+                        .trim_start_matches('<')
+                        .trim_end_matches('>')
+                        .to_string()
+                }
+                "author-time" => author_time = value.parse().unwrap_or(0),
+                "author-tz" => author_tz = parse_git_tz(value),
+                "summary" => line.summary = value.to_string(),
+                "filename" => line.original_path = value.to_string(),
+                _ => {}
+            }
+        }
+    }
+
+    Ok(lines)
+}
+
+// ============================================================================
+// git2 回退实现（系统未安装 git 时使用）
+// ============================================================================
+
+/// 确认工作区和暂存区没有未提交的改动（忽略未跟踪文件）
+fn ensure_clean_worktree(repo: &Repository) -> Result<(), String> {
+    let mut options = StatusOptions::new();
+    options.include_untracked(false).include_ignored(false);
+    let statuses = repo
+        .statuses(Some(&mut options))
+        .map_err(|e| format!("Failed to read repository status: {}", e))?;
+
+    let dirty = statuses
+        .iter()
+        .any(|entry| entry.status() != Status::CURRENT);
+    if dirty {
+        return Err(
+            "Working tree has uncommitted changes; commit or stash them first (git2 fallback)"
+                .to_string(),
+        );
+    }
+    Ok(())
+}
+
+fn write_index_tree(repo: &Repository) -> Result<git2::Tree<'_>, String> {
+    let mut index = repo
+        .index()
+        .map_err(|e| format!("Failed to read index: {}", e))?;
+    let tree_oid = index
+        .write_tree()
+        .map_err(|e| format!("Failed to write tree: {}", e))?;
+    repo.find_tree(tree_oid)
+        .map_err(|e| format!("Failed to find tree: {}", e))
+}
+
+/// 将合并结果提交到 HEAD，并同步工作区和暂存区
+fn commit_merge_result(
+    repo: &Repository,
+    mut index: git2::Index,
+    head_commit: &git2::Commit<'_>,
+    author: &git2::Signature<'_>,
+    message: &str,
+) -> Result<(), String> {
+    if index.has_conflicts() {
+        return Err(
+            "Operation produced conflicts; install git to resolve them interactively".to_string(),
+        );
+    }
+
+    let tree_oid = index
+        .write_tree_to(repo)
+        .map_err(|e| format!("Failed to write tree: {}", e))?;
+    let tree = repo
+        .find_tree(tree_oid)
+        .map_err(|e| format!("Failed to find tree: {}", e))?;
+    let committer = repo
+        .signature()
+        .map_err(|e| format!("Failed to resolve committer signature: {}", e))?;
+
+    repo.commit(
+        Some("HEAD"),
+        author,
+        &committer,
+        message,
+        &tree,
+        &[head_commit],
+    )
+    .map_err(|e| format!("Failed to create commit: {}", e))?;
+
+    // HEAD 已前移，把工作区和暂存区同步到新提交
+    repo.checkout_head(Some(git2::build::CheckoutBuilder::new().safe()))
+        .map_err(|e| format!("Failed to update working tree: {}", e))
+}
+
+fn open_for_write(repo_path: &str, hash: &str) -> Result<(Repository, Oid, Oid), String> {
+    let repo =
+        Repository::open(repo_path).map_err(|e| format!("Failed to open repository: {}", e))?;
+    ensure_clean_worktree(&repo)?;
+
+    let target_oid = repo
+        .revparse_single(hash)
+        .and_then(|obj| obj.peel_to_commit())
+        .map(|commit| commit.id())
+        .map_err(|e| format!("Failed to find commit {}: {}", hash, e))?;
+    let head_oid = repo
+        .head()
+        .and_then(|head| head.peel_to_commit())
+        .map(|commit| commit.id())
+        .map_err(|e| format!("Failed to get HEAD: {}", e))?;
+
+    Ok((repo, target_oid, head_oid))
+}
+
+fn cherry_pick_git2(repo_path: &str, hash: &str) -> Result<(), String> {
+    let (repo, target_oid, head_oid) = open_for_write(repo_path, hash)?;
+    let target = repo.find_commit(target_oid).map_err(|e| e.to_string())?;
+    let head_commit = repo.find_commit(head_oid).map_err(|e| e.to_string())?;
+
+    if target.parent_count() > 1 {
+        return Err("Cherry-picking merge commits requires git".to_string());
+    }
+
+    let index = repo
+        .cherrypick_commit(&target, &head_commit, 0, None)
+        .map_err(|e| format!("Failed to cherry-pick: {}", e))?;
+
+    // 与 git cherry-pick 一致：保留原作者和原提交消息
+    commit_merge_result(
+        &repo,
+        index,
+        &head_commit,
+        &target.author(),
+        target.message().unwrap_or(""),
+    )?;
+    log::info!("[GIT] 通过 git2 cherry-pick 提交 {}", hash);
+    Ok(())
+}
+
+fn revert_git2(repo_path: &str, hash: &str) -> Result<(), String> {
+    let (repo, target_oid, head_oid) = open_for_write(repo_path, hash)?;
+    let target = repo.find_commit(target_oid).map_err(|e| e.to_string())?;
+    let head_commit = repo.find_commit(head_oid).map_err(|e| e.to_string())?;
+
+    if target.parent_count() > 1 {
+        return Err("Reverting merge commits requires git".to_string());
+    }
+
+    let index = repo
+        .revert_commit(&target, &head_commit, 0, None)
+        .map_err(|e| format!("Failed to revert: {}", e))?;
+
+    // 与 git revert --no-edit 生成的默认消息保持一致
+    let message = format!(
+        "Revert \"{}\"\n\nThis reverts commit {}.\n",
+        target.summary().unwrap_or(""),
+        target.id()
+    );
+    let author = repo
+        .signature()
+        .map_err(|e| format!("Failed to resolve author signature: {}", e))?;
+    commit_merge_result(&repo, index, &head_commit, &author, &message)?;
+    log::info!("[GIT] 通过 git2 revert 提交 {}", hash);
+    Ok(())
+}
+
+/// 渲染日志模板所需的提交字段
+struct LogTemplateFields {
+    hash: String,
+    parents: Vec<String>,
+    author_name: String,
+    author_email: String,
+    author_time: i64,
+    author_offset_minutes: i32,
+    committer_name: String,
+    committer_email: String,
+    committer_time: i64,
+    committer_offset_minutes: i32,
+    message: String,
+}
+
+/// git 默认（`--date=default`）的日期格式
+const DEFAULT_LOG_DATE_FORMAT: &str = "%a %b %-d %H:%M:%S %Y %z";
+
+/// 校验 strftime 格式，避免渲染时 chrono 因非法格式 panic
+fn validate_date_format(format: &str) -> Result<(), String> {
+    use chrono::format::{Item, StrftimeItems};
+    if StrftimeItems::new(format).any(|item| matches!(item, Item::Error)) {
+        return Err(format!("无效的日期格式: {}", format));
+    }
+    Ok(())
+}
+
+/// 列宽修饰符 `%<(N)` / `%<(N,trunc)`：作用于紧随其后的一个占位符
+#[derive(Clone, Copy)]
+struct ColumnSpec {
+    width: usize,
+    truncate: bool,
+}
+
+/// 解析 `<(N[,trunc])`，返回修饰符与消耗的字节数
+fn parse_column_spec(rest: &str) -> Option<(ColumnSpec, usize)> {
+    let inner = rest.strip_prefix("<(")?;
+    let end = inner.find(')')?;
+    let (width, mode) = match inner[..end].split_once(',') {
+        Some((width, mode)) => (width.trim(), Some(mode.trim())),
+        None => (inner[..end].trim(), None),
+    };
+    let width = width.parse().ok()?;
+    let truncate = match mode {
+        None => false,
+        Some("trunc") => true,
+        Some(_) => return None,
+    };
+    Some((ColumnSpec { width, truncate }, end + 3))
+}
+
+/// 按字符数截断（末尾用 `..` 表示截断）并右侧补空格到指定宽度
+fn apply_column_spec(value: String, spec: ColumnSpec) -> String {
+    let len = value.chars().count();
+    if len > spec.width {
+        if !spec.truncate {
+            return value;
+        }
+        if spec.width <= 2 {
+            return value.chars().take(spec.width).collect();
+        }
+        let mut truncated: String = value.chars().take(spec.width - 2).collect();
+        truncated.push_str("..");
+        return truncated;
+    }
+    let mut padded = value;
+    padded.push_str(&" ".repeat(spec.width - len));
+    padded
+}
+
+/// `render_log_template` 能直接渲染的两字符占位符
+const SUPPORTED_LOG_PLACEHOLDERS_2: &[&str] =
+    &["an", "ae", "ad", "ai", "at", "cn", "ce", "cd", "ci", "ct"];
+/// `render_log_template` 能直接渲染的单字符占位符
+const SUPPORTED_LOG_PLACEHOLDERS_1: &[char] = &['H', 'h', 'P', 'p', 's', 'b', 'B', 'n', '%'];
+
+/// 找出模板中 `render_log_template` 无法渲染的占位符（如 %ar、%d、%C(red)、%G?）
+///
+/// 按出现顺序去重返回；末尾孤立的 `%` 不算占位符
+fn unsupported_log_placeholders(template: &str) -> Vec<String> {
+    let mut found: Vec<String> = Vec::new();
+    let mut rest = template;
+    while let Some(pos) = rest.find('%') {
+        rest = &rest[pos + 1..];
+
+        if let Some((_, consumed)) = parse_column_spec(rest) {
+            rest = &rest[consumed..];
+            continue;
+        }
+        let two: String = rest.chars().take(2).collect();
+        if SUPPORTED_LOG_PLACEHOLDERS_2.contains(&two.as_str()) {
+            rest = &rest[two.len()..];
+            continue;
+        }
+        let Some(first) = rest.chars().next() else {
+            break;
+        };
+        if SUPPORTED_LOG_PLACEHOLDERS_1.contains(&first) {
+            rest = &rest[first.len_utf8()..];
+            continue;
+        }
+
+        // 尽量截取完整的占位符名称，便于在错误信息中展示
+        let len = if rest[first.len_utf8()..].starts_with('(') {
+            rest.find(')').map(|end| end + 1).unwrap_or(rest.len())
+        } else if first == 'C' {
+            first.len_utf8()
+                + rest[1..]
+                    .find(|c: char| !c.is_ascii_alphabetic())
+                    .unwrap_or(rest.len() - 1)
+        } else if "acgGx".contains(first) {
+            // 这几类占位符由两个字符组成（如 %ar、%GS、%x0）
+            let second = rest[first.len_utf8()..]
+                .chars()
+                .next()
+                .filter(|c| !c.is_whitespace() && *c != '%');
+            first.len_utf8() + second.map_or(0, char::len_utf8)
+        } else {
+            first.len_utf8()
+        };
+        let placeholder = format!("%{}", &rest[..len]);
+        if !found.contains(&placeholder) {
+            found.push(placeholder);
+        }
+        rest = &rest[len..];
+    }
+    found
+}
+
+/// 按 git `--pretty=format:` 的语义渲染单个提交
+///
+/// 支持 %H %h %P %p %an %ae %ad %ai %at %cn %ce %cd %ci %ct %s %b %B %n %%，
+/// 以及 `%<(N)` / `%<(N,trunc)` 列宽修饰符；其余占位符原样保留
+/// （`git_format_log` 会先用 `unsupported_log_placeholders` 检查，遇到时改走 git 命令）。
+/// `date_format` 覆盖 %ad / %cd 的日期格式（需先经 `validate_date_format` 校验）
+fn render_log_template(
+    template: &str,
+    fields: &LogTemplateFields,
+    date_format: Option<&str>,
+) -> String {
+    let (subject, body) = match fields.message.split_once('\n') {
+        Some((subject, body)) => (subject.trim(), body.trim()),
+        None => (fields.message.trim(), ""),
+    };
+    let to_date = |time: i64, offset_minutes: i32| {
+        FixedOffset::east_opt(offset_minutes.saturating_mul(60))
+            .and_then(|offset| offset.timestamp_opt(time, 0).single())
+    };
+    let author_date = to_date(fields.author_time, fields.author_offset_minutes);
+    let committer_date = to_date(fields.committer_time, fields.committer_offset_minutes);
+    let date_format = date_format.unwrap_or(DEFAULT_LOG_DATE_FORMAT);
+    let short = |hash: &str| hash.chars().take(7).collect::<String>();
+
+    let mut out = String::with_capacity(template.len());
+    let mut rest = template;
+    let mut column: Option<ColumnSpec> = None;
+    while let Some(pos) = rest.find('%') {
+        out.push_str(&rest[..pos]);
+        rest = &rest[pos + 1..];
+
+        if let Some((spec, consumed)) = parse_column_spec(rest) {
+            column = Some(spec);
+            rest = &rest[consumed..];
+            continue;
+        }
+        let mut push_value = |out: &mut String, value: String| match column.take() {
+            Some(spec) => out.push_str(&apply_column_spec(value, spec)),
+            None => out.push_str(&value),
+        };
+
+        // 先匹配两个字符的占位符，再匹配单字符占位符
+        let two: String = rest.chars().take(2).collect();
+        let replacement = match two.as_str() {
+            "an" => Some(fields.author_name.clone()),
+            "ae" => Some(fields.author_email.clone()),
+            "ad" => author_date.map(|d| d.format(date_format).to_string()),
+            "ai" => author_date.map(|d| d.format("%Y-%m-%d %H:%M:%S %z").to_string()),
+            "at" => Some(fields.author_time.to_string()),
+            "cn" => Some(fields.committer_name.clone()),
+            "ce" => Some(fields.committer_email.clone()),
+            "cd" => committer_date.map(|d| d.format(date_format).to_string()),
+            "ci" => committer_date.map(|d| d.format("%Y-%m-%d %H:%M:%S %z").to_string()),
+            "ct" => Some(fields.committer_time.to_string()),
+            _ => None,
+        };
+        if let Some(value) = replacement {
+            push_value(&mut out, value);
+            rest = &rest[two.len()..];
+            continue;
+        }
+
+        let one = rest.chars().next();
+        let replacement = match one {
+            Some('H') => Some(fields.hash.clone()),
+            Some('h') => Some(short(&fields.hash)),
+            Some('P') => Some(fields.parents.join(" ")),
+            Some('p') => Some(
+                fields
+                    .parents
+                    .iter()
+                    .map(|p| short(p))
+                    .collect::<Vec<_>>()
+                    .join(" "),
+            ),
+            Some('s') => Some(subject.to_string()),
+            Some('b') => Some(body.to_string()),
+            Some('B') => Some(fields.message.clone()),
+            Some('n') => Some("\n".to_string()),
+            Some('%') => Some("%".to_string()),
+            _ => None,
+        };
+        match (one, replacement) {
+            (Some(ch), Some(value)) => {
+                push_value(&mut out, value);
+                rest = &rest[ch.len_utf8()..];
+            }
+            // 不认识的占位符原样输出
+            _ => out.push('%'),
+        }
+    }
+    out.push_str(rest);
+    out
+}
+
+fn format_log_git2(
+    repo_path: &str,
+    template: &str,
+    limit: usize,
+    date_format: Option<&str>,
+) -> Result<String, String> {
+    let repo =
+        Repository::open(repo_path).map_err(|e| format!("Failed to open repository: {}", e))?;
+
+    let mut revwalk = repo
+        .revwalk()
+        .map_err(|e| format!("Failed to create revwalk: {}", e))?;
+    revwalk
+        .push_head()
+        .map_err(|e| format!("Failed to get HEAD: {}", e))?;
+    revwalk
+        .set_sorting(git2::Sort::TIME)
+        .map_err(|e| format!("Failed to set sorting: {}", e))?;
+
+    let mut lines = Vec::new();
+    for oid_result in revwalk.take(limit) {
+        let oid = oid_result.map_err(|e| format!("Failed to read commit OID: {}", e))?;
+        let commit = repo
+            .find_commit(oid)
+            .map_err(|e| format!("Failed to find commit {}: {}", oid, e))?;
+        let author = commit.author();
+        let committer = commit.committer();
+
+        let fields = LogTemplateFields {
+            hash: oid.to_string(),
+            parents: commit.parent_ids().map(|p| p.to_string()).collect(),
+            author_name: author.name().unwrap_or("").to_string(),
+            author_email: author.email().unwrap_or("").to_string(),
+            author_time: author.when().seconds(),
+            author_offset_minutes: author.when().offset_minutes(),
+            committer_name: committer.name().unwrap_or("").to_string(),
+            committer_email: committer.email().unwrap_or("").to_string(),
+            committer_time: committer.when().seconds(),
+            committer_offset_minutes: committer.when().offset_minutes(),
+            message: commit.message().unwrap_or("").to_string(),
+        };
+        lines.push(render_log_template(template, &fields, date_format));
+    }
+
+    Ok(lines.join("\n"))
+}
+
+/// 使用 libgit2 的 blame 实现
+///
+/// libgit2 不支持直接对工作区 blame，`rev` 为空时以 HEAD 为准
+fn blame_git2(repo: &Repository, file: &str, rev: Option<&str>) -> Result<Vec<BlameLine>, String> {
+    let newest = repo
+        .revparse_single(rev.unwrap_or("HEAD"))
+        .and_then(|obj| obj.peel_to_commit())
+        .map_err(|e| format!("Failed to resolve revision: {}", e))?;
+    let blob = newest
+        .tree()
+        .and_then(|tree| tree.get_path(std::path::Path::new(file)))
+        .and_then(|entry| entry.to_object(repo))
+        .and_then(|obj| obj.peel_to_blob())
+        .map_err(|e| format!("Failed to read {} at {}: {}", file, newest.id(), e))?;
+
+    let mut options = git2::BlameOptions::new();
+    options.newest_commit(newest.id());
+    let blame = repo
+        .blame_file(std::path::Path::new(file), Some(&mut options))
+        .map_err(|e| format!("Failed to blame {}: {}", file, e))?;
+
+    let content = String::from_utf8_lossy(blob.content());
+    let mut summaries: HashMap<Oid, String> = HashMap::new();
+    let mut lines = Vec::new();
+
+    for (index, text) in content.lines().enumerate() {
+        let line_number = index + 1;
+        let hunk = blame
+            .get_line(line_number)
+            .ok_or_else(|| format!("Missing blame data for line {}", line_number))?;
+        let oid = hunk.final_commit_id();
+        let signature = hunk.final_signature();
+        let summary = summaries
+            .entry(oid)
+            .or_insert_with(|| {
+                repo.find_commit(oid)
+                    .ok()
+                    .and_then(|c| c.summary().map(|s| s.to_string()))
+                    .unwrap_or_default()
+            })
+            .clone();
+
+        lines.push(BlameLine {
+            line_number,
+            original_line_number: hunk.orig_start_line() + (line_number - hunk.final_start_line()),
+            hash: oid.to_string(),
+            author: signature.name().unwrap_or("Unknown").to_string(),
+            email: signature.email().unwrap_or("").to_string(),
+            date: format_git_time(
+                signature.when().seconds(),
+                signature.when().offset_minutes(),
+            ),
+            summary,
+            original_path: hunk
+                .path()
+                .map(|p| p.to_string_lossy().replace('\\', "/"))
+                .unwrap_or_else(|| file.to_string()),
+            content: text.to_string(),
+        });
+    }
+
+    Ok(lines)
+}
+
 // 辅助函数
 fn get_branches(repo_path: &str) -> Result<Vec<GitBranch>, String> {
     let repo =
@@ -964,6 +2407,20 @@ fn get_commits_with_skip(
     limit: usize,
     include_files: bool,
 ) -> Result<Vec<GitCommit>, String> {
+    // 提交读取始终走 git2 (libgit2)，不依赖系统 git 命令
+    log::debug!(
+        "[GIT] 通过 git2 读取提交: repo={}, branch={:?}, skip={}, limit={} (系统 git 命令{})",
+        repo_path,
+        branch,
+        skip,
+        limit,
+        if *GIT_CLI_AVAILABLE {
+            "可用"
+        } else {
+            "不可用"
+        }
+    );
+
     let repo =
         Repository::open(repo_path).map_err(|e| format!("Failed to open repository: {}", e))?;
 
@@ -1348,7 +2805,8 @@ fn get_commit_stats_git2(repo: &Repository, commit: &git2::Commit) -> Result<Com
 
 fn delta_status_str(status: Delta) -> String {
     match status {
-        Delta::Added => "A",
+        // 工作区 diff 中的未跟踪文件视同新增
+        Delta::Added | Delta::Untracked => "A",
         Delta::Deleted => "D",
         Delta::Modified => "M",
         Delta::Renamed => "R",
@@ -1428,6 +2886,13 @@ fn get_commit_full_diff_info(
     commit: &git2::Commit,
 ) -> Result<(CommitStats, Vec<FileChange>), String> {
     let diff = create_commit_diff(repo, commit)?;
+    collect_diff_file_changes(&diff)
+}
+
+/// 汇总 diff 中每个文件的状态和增删行数
+fn collect_diff_file_changes(
+    diff: &git2::Diff<'_>,
+) -> Result<(CommitStats, Vec<FileChange>), String> {
     let mut files: HashMap<String, FileChange> = HashMap::new();
     let mut total_additions = 0u32;
     let mut total_deletions = 0u32;
@@ -1545,3 +3010,187 @@ fn get_total_commits(repo_path: &str, branch: Option<&str>) -> Result<usize, Str
 
     Ok(revwalk.count())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render_log_template() {
+        let fields = LogTemplateFields {
+            hash: "0123456789abcdef0123456789abcdef01234567".to_string(),
+            parents: vec!["fedcba9876543210fedcba9876543210fedcba98".to_string()],
+            author_name: "Alice".to_string(),
+            author_email: "alice@example.com".to_string(),
+            author_time: 1_700_000_000,
+            author_offset_minutes: 480,
+            committer_name: "Bob".to_string(),
+            committer_email: "bob@example.com".to_string(),
+            committer_time: 1_700_000_600,
+            committer_offset_minutes: 0,
+            message: "修复登录问题\n\n详细说明\n".to_string(),
+        };
+
+        assert_eq!(
+            render_log_template("%h %s (%an <%ae>)", &fields, None),
+            "0123456 修复登录问题 (Alice <alice@example.com>)"
+        );
+        assert_eq!(
+            render_log_template("%p|%b", &fields, None),
+            "fedcba9|详细说明"
+        );
+        assert_eq!(
+            render_log_template("%ai%n%at", &fields, None),
+            "2023-11-15 06:13:20 +0800\n1700000000"
+        );
+        // 未知占位符和结尾的 % 原样保留
+        assert_eq!(
+            render_log_template("100%% %x %", &fields, None),
+            "100% %x %"
+        );
+    }
+
+    #[test]
+    fn test_unsupported_log_placeholders() {
+        assert!(unsupported_log_placeholders("%h %<(10,trunc)%an %s%n%% %").is_empty());
+        assert_eq!(
+            unsupported_log_placeholders("%C(yellow)%h%Creset %ar %d %D %G? %x %ar"),
+            vec!["%C(yellow)", "%Creset", "%ar", "%d", "%D", "%G?", "%x"]
+        );
+    }
+
+    #[test]
+    fn test_render_log_template_date_format_and_columns() {
+        let fields = LogTemplateFields {
+            hash: "0123456789abcdef0123456789abcdef01234567".to_string(),
+            parents: Vec::new(),
+            author_name: "Alice".to_string(),
+            author_email: "alice@example.com".to_string(),
+            author_time: 1_700_000_000,
+            author_offset_minutes: 480,
+            committer_name: "Bob".to_string(),
+            committer_email: "bob@example.com".to_string(),
+            committer_time: 1_700_000_000,
+            committer_offset_minutes: 0,
+            message: "feat: add a very long subject line".to_string(),
+        };
+
+        assert_eq!(
+            render_log_template("%ad / %cd", &fields, Some("%Y-%m-%d %H:%M")),
+            "2023-11-15 06:13 / 2023-11-14 22:13"
+        );
+        assert_eq!(
+            render_log_template("%ad", &fields, None),
+            "Wed Nov 15 06:13:20 2023 +0800"
+        );
+        // 截断只作用于紧随其后的占位符
+        assert_eq!(
+            render_log_template("- %<(12,trunc)%s %h", &fields, None),
+            "- feat: add .. 0123456"
+        );
+        assert_eq!(
+            render_log_template("[%<(8)%an]", &fields, None),
+            "[Alice   ]"
+        );
+        // 无效的修饰符原样保留
+        assert_eq!(render_log_template("%<(x)%an", &fields, None), "%<(x)Alice");
+
+        assert!(validate_date_format("%Y-%m-%d").is_ok());
+        assert!(validate_date_format("%Q").is_err());
+    }
+
+    #[test]
+    fn test_build_patch_header() {
+        let header = build_patch_header(
+            "0123456789abcdef0123456789abcdef01234567",
+            "张三",
+            "zhangsan@example.com",
+            "Wed, 15 Nov 2023 06:13:20 +0800",
+            "修复 login 问题",
+            2,
+            3,
+        );
+        assert!(header.starts_with("From 0123456789abcdef0123456789abcdef01234567 Mon Sep 17"));
+        assert!(header.contains("From: =?UTF-8?q?=E5=BC=A0=E4=B8=89?= <zhangsan@example.com>\n"));
+        assert!(header.contains(
+            "Subject: [PATCH 2/3] =?UTF-8?q?=E4=BF=AE=E5=A4=8D_login_=E9=97=AE=E9=A2=98?=\n"
+        ));
+        assert!(header.ends_with("Content-Transfer-Encoding: 8bit\n\n"));
+
+        assert_eq!(encode_header_phrase("Alice"), "Alice");
+        assert_eq!(encode_header_phrase("Smith, J."), "\"Smith, J.\"");
+
+        // 过长的标题拆分为多个不超过 75 字符的 encoded-word
+        let encoded = encode_header_text(&"中文".repeat(20));
+        let words: Vec<&str> = encoded.split("\n ").collect();
+        assert!(words.len() > 1);
+        assert!(words
+            .iter()
+            .all(|w| w.len() <= 75 && w.starts_with("=?UTF-8?q?")));
+    }
+
+    #[test]
+    fn test_patch_file_name() {
+        assert_eq!(
+            patch_file_name(1, "Fix: handle empty input (again)", "abcdef123"),
+            "0001-Fix-handle-empty-input-again.patch"
+        );
+        assert_eq!(
+            patch_file_name(12, "修复问题", "abcdef123"),
+            "0012-abcdef1.patch"
+        );
+    }
+
+    #[test]
+    fn test_commit_search_matcher() {
+        let matcher = CommitSearchMatcher::compile("fix login", false, true).unwrap();
+        assert!(matcher.matches("Alice", "a@x.com", "Fix: login redirect loop"));
+        // 多关键词需要全部命中
+        assert!(!matcher.matches("Alice", "a@x.com", "fix typo"));
+        // 未开启作者搜索时不匹配作者字段
+        assert!(!matcher.matches("fix login", "", "chore"));
+
+        let matcher = CommitSearchMatcher::compile(r"^ali example\.com$", true, false).unwrap();
+        assert!(matcher.matches("Alice", "alice@example.com", "anything"));
+        assert!(!matcher.matches("Bob", "bob@example.com", "alice"));
+
+        // 关键词可以分别命中作者和消息
+        let matcher = CommitSearchMatcher::compile("bob refactor", false, false).unwrap();
+        assert!(matcher.matches("Bob", "b@x.com", "Refactor parser"));
+
+        assert!(CommitSearchMatcher::compile("  ", true, true).is_err());
+        assert!(CommitSearchMatcher::compile("fix(", true, true).is_err());
+    }
+
+    #[test]
+    fn test_parse_blame_porcelain() {
+        let hash_a = "a".repeat(40);
+        let hash_b = "b".repeat(40);
+        let output = format!(
+            "{a} 1 1 2\nauthor Alice\nauthor-mail <alice@example.com>\nauthor-time 1700000000\n\
+             author-tz +0800\nsummary 初始化\nfilename src/old.rs\n\tfn main() {{\n\
+             {a} 2 2\nauthor Alice\nauthor-mail <alice@example.com>\nauthor-time 1700000000\n\
+             author-tz +0800\nsummary 初始化\nfilename src/old.rs\n\t}}\n\
+             {b} 5 3 1\nauthor Bob\nauthor-mail <bob@example.com>\nauthor-time 1700003600\n\
+             author-tz -0530\nprevious {a} src/old.rs\nsummary 重命名\nfilename src/new.rs\n\t\n",
+            a = hash_a,
+            b = hash_b
+        );
+
+        let lines = parse_blame_porcelain(&output).unwrap();
+        assert_eq!(lines.len(), 3);
+        assert_eq!(lines[0].hash, hash_a);
+        assert_eq!(lines[0].email, "alice@example.com");
+        assert_eq!(lines[0].date, "2023-11-15T06:13:20+08:00");
+        assert_eq!(lines[0].original_path, "src/old.rs");
+        assert_eq!(lines[0].content, "fn main() {");
+        assert_eq!(lines[2].line_number, 3);
+        assert_eq!(lines[2].original_line_number, 5);
+        assert_eq!(lines[2].date, "2023-11-14T17:43:20-05:30");
+        assert_eq!(lines[2].summary, "重命名");
+        // 空行内容也要保留
+        assert_eq!(lines[2].content, "");
+
+        assert!(parse_blame_porcelain("garbage\n\tline\n").is_err());
+    }
+}
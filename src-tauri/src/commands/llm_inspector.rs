@@ -22,8 +22,12 @@ use http_body_util::BodyExt;
 use once_cell::sync::Lazy;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
+use std::fs;
+use std::io::Write;
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
-use tauri::{Emitter, Manager, WebviewWindow};
+use tauri::{AppHandle, Emitter, Manager, WebviewWindow};
+use tokio::io::AsyncWriteExt;
 use tokio::sync::Mutex;
 use tower::ServiceBuilder;
 use tower_http::cors::CorsLayer;
@@ -40,6 +44,65 @@ pub static INSPECTOR_TARGET_URL: Lazy<Arc<Mutex<String>>> =
 pub static INSPECTOR_HEADER_OVERRIDE_RULES: Lazy<Arc<Mutex<Vec<HeaderOverrideRule>>>> =
     Lazy::new(|| Arc::new(Mutex::new(Vec::new())));
 
+// 全局请求体改写规则
+pub static INSPECTOR_BODY_OVERRIDE_RULES: Lazy<Arc<Mutex<Vec<BodyOverrideRule>>>> =
+    Lazy::new(|| Arc::new(Mutex::new(Vec::new())));
+
+// 全局上游路由规则
+pub static INSPECTOR_ROUTE_RULES: Lazy<Arc<Mutex<Vec<RouteRule>>>> =
+    Lazy::new(|| Arc::new(Mutex::new(Vec::new())));
+
+// 全局响应 mock 规则
+pub static INSPECTOR_MOCK_RULES: Lazy<Arc<Mutex<Vec<MockRule>>>> =
+    Lazy::new(|| Arc::new(Mutex::new(Vec::new())));
+
+// 全局捕获过滤器（未启用时为 None，全部记录）
+pub static INSPECTOR_CAPTURE_FILTER: Lazy<Arc<Mutex<Option<CompiledCaptureFilter>>>> =
+    Lazy::new(|| Arc::new(Mutex::new(None)));
+
+// 捕获记录持久化目录（开启持久化时为 Some）
+pub static INSPECTOR_RECORDS_DIR: Lazy<Arc<Mutex<Option<PathBuf>>>> =
+    Lazy::new(|| Arc::new(Mutex::new(None)));
+
+// 转发用的 HTTP 客户端（启动时按 TLS 与代理设置构建，运行期间复用）
+pub static INSPECTOR_HTTP_CLIENT: Lazy<Arc<Mutex<Option<reqwest::Client>>>> =
+    Lazy::new(|| Arc::new(Mutex::new(None)));
+
+// 本次代理会话各模型的累计用量（启动代理时清零）
+pub static INSPECTOR_USAGE_STATS: Lazy<Arc<Mutex<HashMap<String, ModelUsageStats>>>> =
+    Lazy::new(|| Arc::new(Mutex::new(HashMap::new())));
+
+// 内置的参考价格（美元 / 百万 token，输入, 输出），按模型名最长前缀匹配，仅用于估算
+const DEFAULT_MODEL_PRICES: &[(&str, f64, f64)] = &[
+    ("gpt-4o-mini", 0.15, 0.6),
+    ("gpt-4o", 2.5, 10.0),
+    ("gpt-4.1-nano", 0.1, 0.4),
+    ("gpt-4.1-mini", 0.4, 1.6),
+    ("gpt-4.1", 2.0, 8.0),
+    ("o3-mini", 1.1, 4.4),
+    ("o4-mini", 1.1, 4.4),
+    ("o3", 2.0, 8.0),
+    ("claude-3-5-haiku", 0.8, 4.0),
+    ("claude-3-5-sonnet", 3.0, 15.0),
+    ("claude-3-7-sonnet", 3.0, 15.0),
+    ("claude-sonnet-4", 3.0, 15.0),
+    ("claude-opus-4", 15.0, 75.0),
+    ("gemini-2.5-flash", 0.3, 2.5),
+    ("gemini-2.5-pro", 1.25, 10.0),
+    ("deepseek-chat", 0.27, 1.1),
+    ("deepseek-reasoner", 0.55, 2.19),
+];
+
+// 本次运行中捕获到的凭据请求头（按记录 ID），只保存在内存中供回放使用，不落盘
+static INSPECTOR_REPLAY_CREDENTIALS: Lazy<Mutex<HashMap<String, HashMap<String, String>>>> =
+    Lazy::new(|| Mutex::new(HashMap::new()));
+
+// 持久化索引写入锁，避免并发请求交错写入；同时保存索引当前行数（开启持久化时从磁盘读取一次）
+static INSPECTOR_INDEX_LINE_COUNT: Lazy<Mutex<usize>> = Lazy::new(|| Mutex::new(0));
+
+// 磁盘上最多保留的捕获记录数（索引行数超过两倍时裁剪）
+const MAX_PERSISTED_RECORDS: usize = 1000;
+
 #[derive(Default)]
 pub struct InspectorServiceState {
     is_running: bool,
@@ -59,6 +122,15 @@ pub struct RequestRecord {
     pub headers: HashMap<String, String>,
     pub body: Option<String>,
     pub request_size: usize,
+    // 请求体被改写规则修改时保留的原始 body（body 为实际发送的内容）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub original_body: Option<String>,
+    // 实际转发到的上游基础地址
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub upstream: Option<String>,
+    // 命中的路由规则 ID，未命中时回退到默认目标
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub route_rule_id: Option<String>,
 }
 
 // 响应记录结构
@@ -71,6 +143,53 @@ pub struct ResponseRecord {
     pub body: Option<String>,
     pub response_size: usize,
     pub duration_ms: u64,
+    // 从响应体中解析出的 token 用量（OpenAI 兼容的 usage 字段）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub usage: Option<TokenUsage>,
+    // body 超过内存上限时只保留尾部，完整内容落盘到 body_path
+    #[serde(default)]
+    pub body_truncated: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub body_path: Option<String>,
+}
+
+// token 用量
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub struct TokenUsage {
+    pub prompt_tokens: u64,
+    pub completion_tokens: u64,
+    pub total_tokens: u64,
+}
+
+// 单个模型的累计用量
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ModelUsageStats {
+    pub model: String,
+    pub request_count: u64,
+    pub prompt_tokens: u64,
+    pub completion_tokens: u64,
+    pub total_tokens: u64,
+    // 估算费用（美元），未知模型为 0
+    pub estimated_cost: f64,
+    // 是否匹配到了价格
+    pub priced: bool,
+}
+
+// 模型价格（美元 / 百万 token）
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct ModelPrice {
+    pub input_per_million: f64,
+    pub output_per_million: f64,
+}
+
+// 本次代理会话的用量汇总
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct UsageSummary {
+    pub models: Vec<ModelUsageStats>,
+    pub prompt_tokens: u64,
+    pub completion_tokens: u64,
+    pub total_tokens: u64,
+    pub estimated_cost: f64,
 }
 
 // 流式更新事件结构
@@ -90,6 +209,263 @@ pub struct HeaderOverrideRule {
     pub value: String,
 }
 
+// 请求体改写动作
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum BodyOverrideAction {
+    Set,
+    Remove,
+}
+
+// 请求体改写规则（仅作用于 JSON 请求体）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BodyOverrideRule {
+    pub id: String,
+    pub enabled: bool,
+    // JSON Pointer 路径（RFC 6901），如 /model、/messages/0
+    pub path: String,
+    pub action: BodyOverrideAction,
+    // set 动作写入的值
+    #[serde(default)]
+    pub value: serde_json::Value,
+}
+
+// 响应 mock 规则：命中时不请求上游，直接返回给定响应
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MockRule {
+    pub id: String,
+    pub enabled: bool,
+    // 请求方法，空字符串匹配任意方法
+    #[serde(default)]
+    pub method: String,
+    // 请求路径（不含查询参数），以 * 结尾时按前缀匹配
+    pub path: String,
+    pub status: u16,
+    #[serde(default)]
+    pub headers: HashMap<String, String>,
+    #[serde(default)]
+    pub body: String,
+    // 返回前的延迟，用于模拟慢响应
+    #[serde(default)]
+    pub delay_ms: u64,
+}
+
+// 上游路由规则：按路径前缀或自定义请求头把请求转发到不同上游
+// 路径前缀与请求头条件都配置时需同时满足，规则按顺序匹配，第一条命中即生效
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RouteRule {
+    pub id: String,
+    pub enabled: bool,
+    // 路径前缀，如 /ollama，空字符串表示不按路径匹配
+    #[serde(default)]
+    pub path_prefix: String,
+    // 自定义请求头名，如 x-upstream，空字符串表示不按请求头匹配
+    #[serde(default)]
+    pub header_name: String,
+    // 请求头取值，为空时只要求请求头存在
+    #[serde(default)]
+    pub header_value: String,
+    // 上游基础地址，如 http://127.0.0.1:11434
+    pub upstream: String,
+    // 转发前去掉命中的路径前缀
+    #[serde(default)]
+    pub strip_prefix: bool,
+}
+
+/// 按路径段匹配前缀：`/ollama` 匹配 `/ollama` 与 `/ollama/...`，但不匹配 `/ollama2`
+fn path_has_prefix(path: &str, prefix: &str) -> bool {
+    match path.strip_prefix(prefix) {
+        Some(rest) => rest.is_empty() || rest.starts_with('/') || prefix.ends_with('/'),
+        None => false,
+    }
+}
+
+impl RouteRule {
+    fn matches(&self, path: &str, headers: &axum::http::HeaderMap) -> bool {
+        if !self.enabled || self.upstream.trim().is_empty() {
+            return false;
+        }
+        if self.path_prefix.is_empty() && self.header_name.is_empty() {
+            return false;
+        }
+        if !self.path_prefix.is_empty() && !path_has_prefix(path, &self.path_prefix) {
+            return false;
+        }
+        if !self.header_name.is_empty() {
+            let Some(value) = headers.get(self.header_name.as_str()) else {
+                return false;
+            };
+            if !self.header_value.is_empty()
+                && value.to_str().map(str::trim) != Ok(self.header_value.as_str())
+            {
+                return false;
+            }
+        }
+        true
+    }
+}
+
+// 路由结果：上游基础地址、转发路径（不含查询参数）与命中的规则
+struct ResolvedRoute<'a> {
+    upstream: String,
+    path: String,
+    rule: Option<&'a RouteRule>,
+}
+
+// 按规则表选择上游，没命中时回退到默认目标地址
+fn resolve_route<'a>(
+    rules: &'a [RouteRule],
+    default_target: &str,
+    path: &str,
+    headers: &axum::http::HeaderMap,
+) -> ResolvedRoute<'a> {
+    match rules.iter().find(|rule| rule.matches(path, headers)) {
+        Some(rule) => {
+            let forwarded = if rule.strip_prefix && !rule.path_prefix.is_empty() {
+                let rest = path.strip_prefix(&rule.path_prefix).unwrap_or(path);
+                if rest.starts_with('/') {
+                    rest.to_string()
+                } else {
+                    format!("/{}", rest)
+                }
+            } else {
+                path.to_string()
+            };
+            ResolvedRoute {
+                upstream: rule.upstream.trim().to_string(),
+                path: forwarded,
+                rule: Some(rule),
+            }
+        }
+        None => ResolvedRoute {
+            upstream: default_target.to_string(),
+            path: path.to_string(),
+            rule: None,
+        },
+    }
+}
+
+// 捕获过滤模式
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum CaptureFilterMode {
+    // 命中模式的请求不记录
+    #[default]
+    Blacklist,
+    // 仅记录命中模式的请求
+    Whitelist,
+}
+
+// 捕获过滤器：决定哪些请求进入记录面板
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct CaptureFilter {
+    #[serde(default)]
+    pub enabled: bool,
+    #[serde(default)]
+    pub mode: CaptureFilterMode,
+    // 每条为可选的方法前缀 + 路径模式，如 "GET /health"、"/favicon.ico"、"/v1/*"
+    // 路径默认按 glob 匹配（* 任意字符，? 单个字符），以 re: 开头时按正则匹配
+    #[serde(default)]
+    pub patterns: Vec<String>,
+    // 未被捕获的请求直接返回 404，而不是照常转发
+    #[serde(default)]
+    pub reject_filtered: bool,
+}
+
+struct CapturePattern {
+    method: Option<String>,
+    path: regex::Regex,
+}
+
+// 启动时编译好的捕获过滤器
+pub struct CompiledCaptureFilter {
+    mode: CaptureFilterMode,
+    patterns: Vec<CapturePattern>,
+    reject_filtered: bool,
+}
+
+// glob 转为整串匹配的正则
+fn glob_to_regex(glob: &str) -> String {
+    let mut pattern = String::from("^");
+    let mut literal = String::new();
+    for c in glob.chars() {
+        if c == '*' || c == '?' {
+            pattern.push_str(&regex::escape(&literal));
+            literal.clear();
+            pattern.push_str(if c == '*' { ".*" } else { "." });
+        } else {
+            literal.push(c);
+        }
+    }
+    pattern.push_str(&regex::escape(&literal));
+    pattern.push('$');
+    pattern
+}
+
+impl CompiledCaptureFilter {
+    // 过滤器未启用时返回 None
+    fn compile(filter: &CaptureFilter) -> Result<Option<Self>, String> {
+        if !filter.enabled {
+            return Ok(None);
+        }
+
+        let mut patterns = Vec::new();
+        for raw in filter.patterns.iter() {
+            let raw = raw.trim();
+            if raw.is_empty() {
+                continue;
+            }
+            let (method, path) = match raw.split_once(char::is_whitespace) {
+                Some((m, rest)) if m.chars().all(|c| c.is_ascii_alphabetic()) => {
+                    (Some(m.to_ascii_uppercase()), rest.trim())
+                }
+                _ => (None, raw),
+            };
+            let source = match path.strip_prefix("re:") {
+                Some(re) => re.to_string(),
+                None => glob_to_regex(path),
+            };
+            let path =
+                regex::Regex::new(&source).map_err(|e| format!("无效的过滤模式 {}: {}", raw, e))?;
+            patterns.push(CapturePattern { method, path });
+        }
+
+        Ok(Some(Self {
+            mode: filter.mode,
+            patterns,
+            reject_filtered: filter.reject_filtered,
+        }))
+    }
+
+    fn should_capture(&self, method: &str, path: &str) -> bool {
+        let matched = self.patterns.iter().any(|p| {
+            p.method
+                .as_deref()
+                .is_none_or(|m| m.eq_ignore_ascii_case(method))
+                && p.path.is_match(path)
+        });
+        match self.mode {
+            CaptureFilterMode::Blacklist => !matched,
+            CaptureFilterMode::Whitelist => matched,
+        }
+    }
+}
+
+impl MockRule {
+    fn matches(&self, method: &str, path: &str) -> bool {
+        if !self.enabled {
+            return false;
+        }
+        if !self.method.is_empty() && !self.method.eq_ignore_ascii_case(method) {
+            return false;
+        }
+        match self.path.strip_suffix('*') {
+            Some(prefix) => path.starts_with(prefix),
+            None => self.path == path,
+        }
+    }
+}
+
 // 检查器配置
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct InspectorConfig {
@@ -97,6 +473,355 @@ pub struct InspectorConfig {
     pub target_url: String,
     #[serde(default)]
     pub header_override_rules: Vec<HeaderOverrideRule>,
+    #[serde(default)]
+    pub body_override_rules: Vec<BodyOverrideRule>,
+    #[serde(default)]
+    pub mock_rules: Vec<MockRule>,
+    // 上游路由规则，未命中时使用 target_url
+    #[serde(default)]
+    pub route_rules: Vec<RouteRule>,
+    #[serde(default)]
+    pub capture_filter: CaptureFilter,
+    // 是否把捕获记录持久化到磁盘
+    #[serde(default)]
+    pub persist_records: bool,
+    // 是否接受无效的上游证书（自签名等），默认不接受
+    #[serde(default)]
+    pub accept_invalid_certs: bool,
+    // 额外信任的自定义 CA 证书路径（PEM 或 DER）
+    #[serde(default)]
+    pub ca_cert_path: Option<String>,
+}
+
+// 持久化的一次完整交互（请求 + 响应）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PersistedRecord {
+    pub request: RequestRecord,
+    pub response: Option<ResponseRecord>,
+    // 原始请求路径（含查询参数），回放时拼接到当前目标地址
+    pub path: String,
+    // 流式响应的完整 body 单独存放在 records/<id>.stream 中
+    pub is_streaming: bool,
+}
+
+// 持久化索引中的记录摘要
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PersistedRecordSummary {
+    pub id: String,
+    pub timestamp: i64,
+    pub method: String,
+    pub url: String,
+    pub status: Option<u16>,
+    pub duration_ms: Option<u64>,
+    pub request_size: usize,
+    pub response_size: Option<usize>,
+    pub is_streaming: bool,
+}
+
+impl PersistedRecord {
+    fn summary(&self) -> PersistedRecordSummary {
+        PersistedRecordSummary {
+            id: self.request.id.clone(),
+            timestamp: self.request.timestamp,
+            method: self.request.method.clone(),
+            url: self.request.url.clone(),
+            status: self.response.as_ref().map(|r| r.status),
+            duration_ms: self.response.as_ref().map(|r| r.duration_ms),
+            request_size: self.request.request_size,
+            response_size: self.response.as_ref().map(|r| r.response_size),
+            is_streaming: self.is_streaming,
+        }
+    }
+}
+
+// 捕获记录目录：app_data_dir/llm-inspector
+fn get_records_dir(config: &tauri::Config) -> PathBuf {
+    crate::get_app_data_dir(config).join("llm-inspector")
+}
+
+// 流式响应在内存中最多保留的 body 大小，超出部分落盘
+const MAX_ACCUMULATED_SIZE: usize = 10 * 1024 * 1024;
+
+// 未开启持久化时，超长响应体的落盘目录（每次启动代理时清空）
+fn body_spill_dir() -> PathBuf {
+    std::env::temp_dir().join("aio-hub-llm-inspector")
+}
+
+// 创建溢出文件并写入已累积的内容
+async fn create_body_spill_file(
+    id: &str,
+    accumulated: &[u8],
+) -> std::io::Result<(PathBuf, tokio::fs::File)> {
+    let dir = body_spill_dir();
+    tokio::fs::create_dir_all(&dir).await?;
+    let path = dir.join(format!("{}.body", id));
+    let mut file = tokio::fs::File::create(&path).await?;
+    file.write_all(accumulated).await?;
+    Ok((path, file))
+}
+
+fn record_file_path(dir: &Path, id: &str, extension: &str) -> PathBuf {
+    dir.join("records").join(format!("{}.{}", id, extension))
+}
+
+// 记录 ID 会拼接进文件名，只接受 UUID
+fn validate_record_id(id: &str) -> Result<(), String> {
+    uuid::Uuid::parse_str(id)
+        .map(|_| ())
+        .map_err(|_| format!("无效的记录 ID: {}", id))
+}
+
+// 解析索引文件，跳过损坏的行
+fn parse_record_index(content: &str) -> Vec<PersistedRecordSummary> {
+    content
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .filter_map(
+            |line| match serde_json::from_str::<PersistedRecordSummary>(line) {
+                Ok(summary) => Some(summary),
+                Err(e) => {
+                    log::warn!("跳过损坏的检查器记录索引行: {}", e);
+                    None
+                }
+            },
+        )
+        .collect()
+}
+
+// 写入完整记录文件并追加索引；索引超过上限两倍时裁剪旧记录
+async fn persist_record(dir: &Path, record: &PersistedRecord) {
+    let credentials = sensitive_headers(&record.request.headers);
+    if !credentials.is_empty() {
+        INSPECTOR_REPLAY_CREDENTIALS
+            .lock()
+            .await
+            .insert(record.request.id.clone(), credentials);
+    }
+
+    // 文件读写放到阻塞线程池执行；持有锁期间索引行数与磁盘保持一致
+    let mut line_count = INSPECTOR_INDEX_LINE_COUNT.lock().await;
+    let dir = dir.to_path_buf();
+    let record = record.clone();
+    let result =
+        tokio::task::spawn_blocking(move || write_record_files(&dir, &record, &mut line_count))
+            .await
+            .unwrap_or_else(|e| Err(format!("持久化任务执行失败: {}", e)));
+
+    match result {
+        Ok(removed_ids) if !removed_ids.is_empty() => {
+            let mut credentials = INSPECTOR_REPLAY_CREDENTIALS.lock().await;
+            for id in &removed_ids {
+                credentials.remove(id);
+            }
+        }
+        Ok(_) => {}
+        Err(e) => log::warn!("持久化检查器记录失败: {}", e),
+    }
+}
+
+// 写入记录文件并追加索引，返回被裁剪掉的记录 ID
+//
+// `line_count` 为索引当前行数，只在即将超过上限、需要裁剪时才重新读取索引文件
+fn write_record_files(
+    dir: &Path,
+    record: &PersistedRecord,
+    line_count: &mut usize,
+) -> Result<Vec<String>, String> {
+    let id = &record.request.id;
+    fs::create_dir_all(dir.join("records")).map_err(|e| format!("创建记录目录失败: {}", e))?;
+
+    // 凭据请求头不以明文落盘
+    let mut redacted = record.clone();
+    redacted.request.headers = redact_headers(&record.request.headers);
+    let content = serde_json::to_string(&redacted).map_err(|e| format!("序列化记录失败: {}", e))?;
+    fs::write(record_file_path(dir, id, "json"), content)
+        .map_err(|e| format!("写入记录文件失败: {}", e))?;
+
+    let index_path = dir.join("index.jsonl");
+    let line = serde_json::to_string(&record.summary())
+        .map_err(|e| format!("序列化记录索引失败: {}", e))?;
+    let mut file = fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(&index_path)
+        .map_err(|e| format!("打开记录索引失败: {}", e))?;
+    writeln!(file, "{}", line).map_err(|e| format!("写入记录索引失败: {}", e))?;
+    drop(file);
+    *line_count += 1;
+
+    let mut removed_ids = Vec::new();
+    if *line_count >= MAX_PERSISTED_RECORDS * 2 {
+        let index_content =
+            fs::read_to_string(&index_path).map_err(|e| format!("读取记录索引失败: {}", e))?;
+        let mut summaries = parse_record_index(&index_content);
+        let excess = summaries.len().saturating_sub(MAX_PERSISTED_RECORDS);
+        for removed in summaries.drain(..excess) {
+            let _ = fs::remove_file(record_file_path(dir, &removed.id, "json"));
+            let _ = fs::remove_file(record_file_path(dir, &removed.id, "stream"));
+            removed_ids.push(removed.id);
+        }
+
+        let mut content = String::new();
+        for summary in &summaries {
+            let line =
+                serde_json::to_string(summary).map_err(|e| format!("序列化记录索引失败: {}", e))?;
+            content.push_str(&line);
+            content.push('\n');
+        }
+        fs::write(&index_path, content).map_err(|e| format!("重写记录索引失败: {}", e))?;
+        *line_count = summaries.len();
+    }
+    Ok(removed_ids)
+}
+
+// 携带凭据的请求头，落盘前需脱敏
+const SENSITIVE_HEADERS: &[&str] = &[
+    "authorization",
+    "proxy-authorization",
+    "x-api-key",
+    "api-key",
+    "x-goog-api-key",
+];
+
+// 脱敏后的占位值
+const REDACTED_VALUE: &str = "***";
+
+fn is_sensitive_header(name: &str) -> bool {
+    SENSITIVE_HEADERS
+        .iter()
+        .any(|sensitive| name.eq_ignore_ascii_case(sensitive))
+}
+
+// 取出凭据请求头的原始值
+fn sensitive_headers(headers: &HashMap<String, String>) -> HashMap<String, String> {
+    headers
+        .iter()
+        .filter(|(name, _)| is_sensitive_header(name))
+        .map(|(name, value)| (name.clone(), value.clone()))
+        .collect()
+}
+
+// 找出回放时无法补全的凭据请求头：已脱敏、内存中没有原始值，且没有启用的请求头覆盖规则提供
+fn missing_replay_credentials(
+    headers: &HashMap<String, String>,
+    credentials: &HashMap<String, String>,
+    override_rules: &[HeaderOverrideRule],
+) -> Vec<String> {
+    let mut missing: Vec<String> = headers
+        .iter()
+        .filter(|(name, value)| is_sensitive_header(name) && value.ends_with(REDACTED_VALUE))
+        .map(|(name, _)| name)
+        .filter(|name| !credentials.keys().any(|key| key.eq_ignore_ascii_case(name)))
+        .filter(|name| {
+            !override_rules
+                .iter()
+                .any(|rule| rule.enabled && rule.key.eq_ignore_ascii_case(name))
+        })
+        .cloned()
+        .collect();
+    missing.sort();
+    missing
+}
+
+// 脱敏凭据请求头，保留认证方案（如 `Bearer ***`）便于排查
+fn redact_headers(headers: &HashMap<String, String>) -> HashMap<String, String> {
+    headers
+        .iter()
+        .map(|(name, value)| {
+            if !is_sensitive_header(name) {
+                return (name.clone(), value.clone());
+            }
+            let redacted = match value.trim().split_once(' ') {
+                Some((scheme, _)) => format!("{} {}", scheme, REDACTED_VALUE),
+                None => REDACTED_VALUE.to_string(),
+            };
+            (name.clone(), redacted)
+        })
+        .collect()
+}
+
+// 读取完整记录；流式响应从 .stream 文件补全 body
+fn load_persisted_record(dir: &Path, id: &str) -> Result<PersistedRecord, String> {
+    validate_record_id(id)?;
+
+    let content = fs::read_to_string(record_file_path(dir, id, "json"))
+        .map_err(|e| format!("读取记录失败: {}", e))?;
+    let mut record: PersistedRecord =
+        serde_json::from_str(&content).map_err(|e| format!("解析记录失败: {}", e))?;
+
+    if record.is_streaming {
+        if let Some(response) = record.response.as_mut() {
+            match fs::read(record_file_path(dir, id, "stream")) {
+                Ok(bytes) if !bytes.is_empty() => {
+                    response.body = Some(String::from_utf8_lossy(&bytes).to_string());
+                    response.body_truncated = false;
+                    response.body_path = None;
+                }
+                Ok(_) => {}
+                Err(e) => log::warn!("读取流式响应体失败 {}: {}", id, e),
+            }
+        }
+    }
+
+    Ok(record)
+}
+
+// 读取自定义 CA 证书，PEM 文件可包含多个证书
+fn load_ca_certificates(path: &str) -> Result<Vec<reqwest::Certificate>, String> {
+    let data = std::fs::read(path).map_err(|e| format!("读取 CA 证书失败: {}", e))?;
+    if data.starts_with(b"-----BEGIN") {
+        reqwest::Certificate::from_pem_bundle(&data)
+            .map_err(|e| format!("解析 PEM 证书失败: {}", e))
+    } else {
+        reqwest::Certificate::from_der(&data)
+            .map(|cert| vec![cert])
+            .map_err(|e| format!("解析 DER 证书失败: {}", e))
+    }
+}
+
+// 按 TLS 配置与全局代理设置构建转发客户端
+fn build_proxy_client(
+    accept_invalid_certs: bool,
+    ca_cert_path: Option<&str>,
+    proxy_settings: &super::config_manager::ProxySettings,
+) -> Result<reqwest::Client, String> {
+    // 对于SSE流，我们需要禁用超时和自动解压
+    let mut client_builder = reqwest::Client::builder()
+        .danger_accept_invalid_certs(accept_invalid_certs)
+        .no_gzip() // 对于流式响应，禁用自动gzip解压
+        .no_brotli() // 禁用自动brotli解压
+        .no_deflate(); // 禁用自动deflate解压
+
+    if accept_invalid_certs {
+        log::warn!("LLM检查器已关闭上游证书校验");
+    }
+
+    if let Some(path) = ca_cert_path.filter(|p| !p.trim().is_empty()) {
+        for cert in load_ca_certificates(path.trim())? {
+            client_builder = client_builder.add_root_certificate(cert);
+        }
+    }
+
+    // 应用代理设置
+    match proxy_settings.mode.as_str() {
+        "none" => {
+            client_builder = client_builder.no_proxy();
+        }
+        "custom" => {
+            if !proxy_settings.custom_url.is_empty() {
+                if let Ok(proxy) = reqwest::Proxy::all(&proxy_settings.custom_url) {
+                    client_builder = client_builder.proxy(proxy);
+                }
+            }
+        }
+        _ => {
+            // "system" 模式，reqwest 默认会尝试读取系统代理（env vars）
+        }
+    }
+
+    client_builder
+        .build()
+        .map_err(|e| format!("创建HTTP客户端失败: {}", e))
 }
 
 // 启动检查器服务
@@ -111,6 +836,18 @@ pub async fn start_llm_inspector(
         return Err("代理服务已在运行".to_string());
     }
 
+    // 过滤模式有误时直接拒绝启动
+    let capture_filter = CompiledCaptureFilter::compile(&config.capture_filter)?;
+
+    // 构建转发客户端，证书或代理配置有误时直接拒绝启动
+    let proxy_settings = super::config_manager::get_proxy_settings(window.app_handle());
+    let client = build_proxy_client(
+        config.accept_invalid_certs,
+        config.ca_cert_path.as_deref(),
+        &proxy_settings,
+    )?;
+    *INSPECTOR_HTTP_CLIENT.lock().await = Some(client);
+
     // 创建关闭信号通道
     let (shutdown_tx, shutdown_rx) = tokio::sync::oneshot::channel::<()>();
 
@@ -130,6 +867,41 @@ pub async fn start_llm_inspector(
     *global_rules = config.header_override_rules.clone();
     drop(global_rules);
 
+    // 更新全局请求体改写规则
+    *INSPECTOR_BODY_OVERRIDE_RULES.lock().await = config.body_override_rules.clone();
+
+    // 更新全局 mock 规则
+    *INSPECTOR_MOCK_RULES.lock().await = config.mock_rules.clone();
+
+    // 更新全局路由规则
+    *INSPECTOR_ROUTE_RULES.lock().await = config.route_rules.clone();
+
+    // 更新全局捕获过滤器
+    *INSPECTOR_CAPTURE_FILTER.lock().await = capture_filter;
+
+    // 新会话重新统计 token 用量
+    INSPECTOR_USAGE_STATS.lock().await.clear();
+
+    // 清理上次会话遗留的响应体溢出文件
+    let _ = tokio::fs::remove_dir_all(body_spill_dir()).await;
+
+    // 更新持久化目录
+    let mut records_dir = INSPECTOR_RECORDS_DIR.lock().await;
+    *records_dir = if config.persist_records {
+        Some(get_records_dir(window.app_handle().config()))
+    } else {
+        None
+    };
+    if let Some(dir) = records_dir.as_ref() {
+        // 载入索引行数，之后追加记录时在内存中累加
+        *INSPECTOR_INDEX_LINE_COUNT.lock().await =
+            tokio::fs::read_to_string(dir.join("index.jsonl"))
+                .await
+                .map(|content| content.lines().count())
+                .unwrap_or(0);
+    }
+    drop(records_dir);
+
     let port = config.port;
     let window_clone = window.clone();
 
@@ -196,6 +968,15 @@ pub async fn stop_llm_inspector() -> Result<String, String> {
     // 清空全局请求头覆盖规则
     let mut global_rules = INSPECTOR_HEADER_OVERRIDE_RULES.lock().await;
     global_rules.clear();
+    drop(global_rules);
+    INSPECTOR_BODY_OVERRIDE_RULES.lock().await.clear();
+    INSPECTOR_MOCK_RULES.lock().await.clear();
+    INSPECTOR_ROUTE_RULES.lock().await.clear();
+    *INSPECTOR_CAPTURE_FILTER.lock().await = None;
+
+    // 停止持久化
+    *INSPECTOR_RECORDS_DIR.lock().await = None;
+    *INSPECTOR_HTTP_CLIENT.lock().await = None;
 
     Ok("代理服务已停止".to_string())
 }
@@ -241,6 +1022,525 @@ pub async fn update_inspector_target(target_url: String) -> Result<String, Strin
     Ok(format!("目标地址已更新为: {}", target_url))
 }
 
+// 运行中更新 TLS 配置：重建转发客户端并替换，同时重新读取全局代理设置
+// 已在途的请求继续使用旧客户端完成
+#[tauri::command]
+pub async fn update_inspector_tls(
+    app: AppHandle,
+    accept_invalid_certs: bool,
+    ca_cert_path: Option<String>,
+) -> Result<String, String> {
+    if !INSPECTOR_STATE.lock().await.is_running {
+        return Err("检查器服务未运行".to_string());
+    }
+
+    let proxy_settings = super::config_manager::get_proxy_settings(&app);
+    let client = build_proxy_client(
+        accept_invalid_certs,
+        ca_cert_path.as_deref(),
+        &proxy_settings,
+    )?;
+    *INSPECTOR_HTTP_CLIENT.lock().await = Some(client);
+
+    Ok("转发客户端已按新的 TLS 配置重建".to_string())
+}
+
+// 列出已持久化的捕获记录（按时间倒序）
+#[tauri::command]
+pub async fn inspector_list_records(
+    app: AppHandle,
+    offset: Option<usize>,
+    limit: Option<usize>,
+) -> Result<Vec<PersistedRecordSummary>, String> {
+    let index_path = get_records_dir(app.config()).join("index.jsonl");
+    let content = match fs::read_to_string(&index_path) {
+        Ok(content) => content,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(e) => return Err(format!("读取记录索引失败: {}", e)),
+    };
+
+    let mut summaries = parse_record_index(&content);
+    summaries.reverse();
+    Ok(summaries
+        .into_iter()
+        .skip(offset.unwrap_or(0))
+        .take(limit.unwrap_or(usize::MAX))
+        .collect())
+}
+
+// 获取单条已持久化记录的完整内容
+#[tauri::command]
+pub async fn inspector_get_record(app: AppHandle, id: String) -> Result<PersistedRecord, String> {
+    load_persisted_record(&get_records_dir(app.config()), &id)
+}
+
+// 读取被截断响应的完整 body：先找本次会话的溢出文件，再找持久化的 .stream 文件
+#[tauri::command]
+pub async fn inspector_read_full_response_body(
+    app: AppHandle,
+    id: String,
+) -> Result<String, String> {
+    validate_record_id(&id)?;
+
+    let candidates = [
+        body_spill_dir().join(format!("{}.body", id)),
+        record_file_path(&get_records_dir(app.config()), &id, "stream"),
+    ];
+    for path in candidates.iter() {
+        match tokio::fs::read(path).await {
+            Ok(bytes) => return Ok(String::from_utf8_lossy(&bytes).to_string()),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => continue,
+            Err(e) => return Err(format!("读取完整响应体失败: {}", e)),
+        }
+    }
+    Err(format!("记录 {} 没有落盘的完整响应体", id))
+}
+
+// 把历史请求通过本地代理重新发送到当前目标地址，返回响应状态码
+// 回放请求会像普通请求一样被捕获并推送到前端
+#[tauri::command]
+pub async fn inspector_replay_record(app: AppHandle, id: String) -> Result<u16, String> {
+    let state = INSPECTOR_STATE.lock().await;
+    if !state.is_running {
+        return Err("检查器服务未运行，无法回放".to_string());
+    }
+    let port = state.port;
+    drop(state);
+
+    let record = load_persisted_record(&get_records_dir(app.config()), &id)?;
+    // 凭据只以脱敏形式落盘，本次运行捕获的记录从内存补回原始值
+    let credentials = INSPECTOR_REPLAY_CREDENTIALS
+        .lock()
+        .await
+        .get(&id)
+        .cloned()
+        .unwrap_or_default();
+    let missing = missing_replay_credentials(
+        &record.request.headers,
+        &credentials,
+        &INSPECTOR_HEADER_OVERRIDE_RULES.lock().await,
+    );
+    if !missing.is_empty() {
+        return Err(format!(
+            "记录中的凭据请求头已脱敏且不在本次运行的内存中，无法回放: {}（可添加请求头覆盖规则提供）",
+            missing.join(", ")
+        ));
+    }
+
+    let method = reqwest::Method::from_bytes(record.request.method.as_bytes())
+        .map_err(|e| format!("无效的请求方法: {}", e))?;
+    let url = format!("http://127.0.0.1:{}{}", port, record.path);
+
+    let client = reqwest::Client::builder()
+        .no_proxy()
+        .build()
+        .map_err(|e| format!("创建HTTP客户端失败: {}", e))?;
+    let mut req_builder = client.request(method, &url);
+    for (name, value) in record.request.headers.iter() {
+        let name_lower = name.to_lowercase();
+        if is_sensitive_header(&name_lower) {
+            // 优先使用内存中的原始凭据；没有时由代理的请求头覆盖规则补上
+            if let Some(original) = credentials.get(name) {
+                req_builder = req_builder.header(name, original);
+            }
+            continue;
+        }
+        if !matches!(
+            name_lower.as_str(),
+            "host" | "content-length" | "connection" | "accept-encoding"
+        ) {
+            req_builder = req_builder.header(name, value);
+        }
+    }
+    // 优先回放改写前的原始请求体，让当前的改写规则重新生效
+    if let Some(body) = record.request.original_body.or(record.request.body) {
+        req_builder = req_builder.body(body);
+    }
+
+    let response = req_builder
+        .send()
+        .await
+        .map_err(|e| format!("回放请求失败: {}", e))?;
+    let status = response.status().as_u16();
+
+    // 消费完整响应体，流式响应也要等到结束
+    response
+        .bytes()
+        .await
+        .map_err(|e| format!("读取回放响应失败: {}", e))?;
+
+    log::info!("[检查器] 已回放记录 {} -> {}", id, status);
+    Ok(status)
+}
+
+// 获取本次代理会话各模型的 token 用量与估算费用
+// prices 可按模型名前缀覆盖内置价格
+#[tauri::command]
+pub async fn inspector_get_usage_summary(
+    prices: Option<HashMap<String, ModelPrice>>,
+) -> Result<UsageSummary, String> {
+    let stats = INSPECTOR_USAGE_STATS.lock().await;
+    Ok(build_usage_summary(
+        stats.values().cloned().collect(),
+        &prices.unwrap_or_default(),
+    ))
+}
+
+// 从 usage 对象中读取用量，兼容 input_tokens/output_tokens 命名
+fn parse_usage_object(usage: &serde_json::Value) -> Option<TokenUsage> {
+    let field = |names: &[&str]| {
+        names
+            .iter()
+            .find_map(|name| usage.get(*name).and_then(|v| v.as_u64()))
+    };
+    let prompt_tokens = field(&["prompt_tokens", "input_tokens"]);
+    let completion_tokens = field(&["completion_tokens", "output_tokens"]);
+    if prompt_tokens.is_none() && completion_tokens.is_none() {
+        return None;
+    }
+    let prompt_tokens = prompt_tokens.unwrap_or(0);
+    let completion_tokens = completion_tokens.unwrap_or(0);
+    Some(TokenUsage {
+        prompt_tokens,
+        completion_tokens,
+        total_tokens: field(&["total_tokens"]).unwrap_or(prompt_tokens + completion_tokens),
+    })
+}
+
+// 从非流式 JSON 响应体中提取 usage
+fn extract_usage_from_json(body: &str) -> Option<TokenUsage> {
+    let json: serde_json::Value = serde_json::from_str(body).ok()?;
+    json.get("usage").and_then(parse_usage_object)
+}
+
+// 从 SSE 响应体中提取 usage，取最后一个带 usage 的 data 块
+fn extract_usage_from_sse(body: &str) -> Option<TokenUsage> {
+    body.lines()
+        .rev()
+        .filter_map(|line| line.trim().strip_prefix("data:"))
+        .map(str::trim)
+        .filter(|data| data.starts_with('{') && data.contains("usage"))
+        .find_map(extract_usage_from_json)
+}
+
+// 请求体中的 model 字段
+fn request_model(request: &RequestRecord) -> Option<String> {
+    let body = request.body.as_deref()?;
+    let json: serde_json::Value = serde_json::from_str(body).ok()?;
+    json.get("model")?.as_str().map(|s| s.to_string())
+}
+
+// 把一次响应的用量累加到会话统计中
+async fn record_usage(request: &RequestRecord, usage: &TokenUsage) {
+    let model = request_model(request).unwrap_or_else(|| "unknown".to_string());
+    let mut stats = INSPECTOR_USAGE_STATS.lock().await;
+    let entry = stats
+        .entry(model.clone())
+        .or_insert_with(|| ModelUsageStats {
+            model,
+            ..Default::default()
+        });
+    entry.request_count += 1;
+    entry.prompt_tokens += usage.prompt_tokens;
+    entry.completion_tokens += usage.completion_tokens;
+    entry.total_tokens += usage.total_tokens;
+}
+
+// 按模型名最长前缀查找价格，自定义价格优先
+fn find_model_price(model: &str, overrides: &HashMap<String, ModelPrice>) -> Option<ModelPrice> {
+    let model = model.to_lowercase();
+    let custom = overrides
+        .iter()
+        .filter(|(prefix, _)| model.starts_with(&prefix.to_lowercase()))
+        .max_by_key(|(prefix, _)| prefix.len())
+        .map(|(_, price)| *price);
+    custom.or_else(|| {
+        DEFAULT_MODEL_PRICES
+            .iter()
+            .filter(|(prefix, _, _)| model.starts_with(prefix))
+            .max_by_key(|(prefix, _, _)| prefix.len())
+            .map(|(_, input, output)| ModelPrice {
+                input_per_million: *input,
+                output_per_million: *output,
+            })
+    })
+}
+
+fn build_usage_summary(
+    mut models: Vec<ModelUsageStats>,
+    overrides: &HashMap<String, ModelPrice>,
+) -> UsageSummary {
+    let mut summary = UsageSummary::default();
+    for stats in models.iter_mut() {
+        if let Some(price) = find_model_price(&stats.model, overrides) {
+            stats.priced = true;
+            stats.estimated_cost = (stats.prompt_tokens as f64 * price.input_per_million
+                + stats.completion_tokens as f64 * price.output_per_million)
+                / 1_000_000.0;
+        }
+        summary.prompt_tokens += stats.prompt_tokens;
+        summary.completion_tokens += stats.completion_tokens;
+        summary.total_tokens += stats.total_tokens;
+        summary.estimated_cost += stats.estimated_cost;
+    }
+    models.sort_by_key(|m| std::cmp::Reverse(m.total_tokens));
+    summary.models = models;
+    summary
+}
+
+// 把已持久化的记录导出为 HAR 1.2 JSON
+#[tauri::command]
+pub async fn inspector_export_har(app: AppHandle, ids: Vec<String>) -> Result<String, String> {
+    if ids.is_empty() {
+        return Err("未选择要导出的记录".to_string());
+    }
+
+    let dir = get_records_dir(app.config());
+    let records = ids
+        .iter()
+        .map(|id| load_persisted_record(&dir, id))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    serde_json::to_string_pretty(&build_har(&records))
+        .map_err(|e| format!("序列化 HAR 失败: {}", e))
+}
+
+// 导出前同样脱敏凭据头，兼容脱敏功能加入之前落盘的旧记录
+fn har_headers(headers: &HashMap<String, String>) -> Vec<serde_json::Value> {
+    let headers = redact_headers(headers);
+    let mut pairs: Vec<_> = headers.iter().collect();
+    pairs.sort_by(|a, b| a.0.cmp(b.0));
+    pairs
+        .into_iter()
+        .map(|(name, value)| serde_json::json!({ "name": name, "value": value }))
+        .collect()
+}
+
+fn har_content_type(headers: &HashMap<String, String>) -> String {
+    headers
+        .iter()
+        .find(|(name, _)| name.eq_ignore_ascii_case("content-type"))
+        .map(|(_, value)| value.clone())
+        .unwrap_or_default()
+}
+
+fn har_entry(record: &PersistedRecord) -> serde_json::Value {
+    let request = &record.request;
+    let started = chrono::DateTime::from_timestamp_millis(request.timestamp)
+        .unwrap_or_default()
+        .to_rfc3339_opts(chrono::SecondsFormat::Millis, true);
+
+    let query_string: Vec<serde_json::Value> = url::Url::parse(&request.url)
+        .map(|url| {
+            url.query_pairs()
+                .map(|(name, value)| serde_json::json!({ "name": name, "value": value }))
+                .collect()
+        })
+        .unwrap_or_default();
+
+    let mut har_request = serde_json::json!({
+        "method": request.method,
+        "url": request.url,
+        "httpVersion": "HTTP/1.1",
+        "cookies": [],
+        "headers": har_headers(&request.headers),
+        "queryString": query_string,
+        "headersSize": -1,
+        "bodySize": request.request_size,
+    });
+    if let Some(body) = &request.body {
+        har_request["postData"] = serde_json::json!({
+            "mimeType": har_content_type(&request.headers),
+            "text": body,
+        });
+    }
+
+    // 未收到响应（如仍在进行中）时按 HAR 约定填 status 0
+    let (har_response, duration_ms) = match &record.response {
+        Some(response) => {
+            let status_text = reqwest::StatusCode::from_u16(response.status)
+                .ok()
+                .and_then(|s| s.canonical_reason())
+                .unwrap_or("");
+            (
+                serde_json::json!({
+                    "status": response.status,
+                    "statusText": status_text,
+                    "httpVersion": "HTTP/1.1",
+                    "cookies": [],
+                    "headers": har_headers(&response.headers),
+                    "content": {
+                        "size": response.response_size,
+                        "mimeType": har_content_type(&response.headers),
+                        "text": response.body.clone().unwrap_or_default(),
+                    },
+                    "redirectURL": "",
+                    "headersSize": -1,
+                    "bodySize": response.response_size,
+                }),
+                response.duration_ms,
+            )
+        }
+        None => (
+            serde_json::json!({
+                "status": 0,
+                "statusText": "",
+                "httpVersion": "HTTP/1.1",
+                "cookies": [],
+                "headers": [],
+                "content": { "size": 0, "mimeType": "" },
+                "redirectURL": "",
+                "headersSize": -1,
+                "bodySize": -1,
+            }),
+            0,
+        ),
+    };
+
+    // 代理只记录了总耗时，全部计入 wait
+    serde_json::json!({
+        "startedDateTime": started,
+        "time": duration_ms,
+        "request": har_request,
+        "response": har_response,
+        "cache": {},
+        "timings": {
+            "send": 0,
+            "wait": duration_ms,
+            "receive": 0,
+        },
+    })
+}
+
+fn build_har(records: &[PersistedRecord]) -> serde_json::Value {
+    serde_json::json!({
+        "log": {
+            "version": "1.2",
+            "creator": {
+                "name": "AIO Hub LLM Inspector",
+                "version": env!("CARGO_PKG_VERSION"),
+            },
+            "entries": records.iter().map(har_entry).collect::<Vec<_>>(),
+        }
+    })
+}
+
+// 拆分 JSON Pointer 并反转义（~1 -> /，~0 -> ~）
+fn parse_json_pointer(pointer: &str) -> Option<Vec<String>> {
+    if pointer.is_empty() {
+        return Some(Vec::new());
+    }
+    let rest = pointer.strip_prefix('/')?;
+    Some(
+        rest.split('/')
+            .map(|token| token.replace("~1", "/").replace("~0", "~"))
+            .collect(),
+    )
+}
+
+// 按 JSON Pointer 写入值，缺失的中间层级自动创建为对象
+fn json_pointer_set(root: &mut serde_json::Value, pointer: &str, value: serde_json::Value) -> bool {
+    let Some(tokens) = parse_json_pointer(pointer) else {
+        return false;
+    };
+    let Some((last, parents)) = tokens.split_last() else {
+        *root = value;
+        return true;
+    };
+
+    let mut current = root;
+    for token in parents {
+        current = match current {
+            serde_json::Value::Object(map) => map
+                .entry(token.clone())
+                .or_insert_with(|| serde_json::Value::Object(serde_json::Map::new())),
+            serde_json::Value::Array(items) => match token.parse::<usize>() {
+                Ok(index) if index < items.len() => &mut items[index],
+                _ => return false,
+            },
+            _ => return false,
+        };
+    }
+
+    match current {
+        serde_json::Value::Object(map) => {
+            map.insert(last.clone(), value);
+            true
+        }
+        serde_json::Value::Array(items) => {
+            if last == "-" {
+                items.push(value);
+                return true;
+            }
+            match last.parse::<usize>() {
+                Ok(index) if index < items.len() => {
+                    items[index] = value;
+                    true
+                }
+                Ok(index) if index == items.len() => {
+                    items.push(value);
+                    true
+                }
+                _ => false,
+            }
+        }
+        _ => false,
+    }
+}
+
+// 按 JSON Pointer 删除值
+fn json_pointer_remove(root: &mut serde_json::Value, pointer: &str) -> bool {
+    let Some(tokens) = parse_json_pointer(pointer) else {
+        return false;
+    };
+    let Some((last, parents)) = tokens.split_last() else {
+        return false;
+    };
+
+    let parent_pointer: String = parents
+        .iter()
+        .map(|t| format!("/{}", t.replace('~', "~0").replace('/', "~1")))
+        .collect();
+    match root.pointer_mut(&parent_pointer) {
+        Some(serde_json::Value::Object(map)) => map.remove(last).is_some(),
+        Some(serde_json::Value::Array(items)) => match last.parse::<usize>() {
+            Ok(index) if index < items.len() => {
+                items.remove(index);
+                true
+            }
+            _ => false,
+        },
+        _ => false,
+    }
+}
+
+// 对 JSON 请求体应用改写规则；无启用规则、非 JSON 或未发生修改时返回 None
+fn apply_body_override_rules(body: &[u8], rules: &[BodyOverrideRule]) -> Option<Vec<u8>> {
+    if !rules.iter().any(|rule| rule.enabled) {
+        return None;
+    }
+    let mut json: serde_json::Value = serde_json::from_slice(body).ok()?;
+
+    let mut changed = false;
+    for rule in rules.iter().filter(|rule| rule.enabled) {
+        let applied = match rule.action {
+            BodyOverrideAction::Set => json_pointer_set(&mut json, &rule.path, rule.value.clone()),
+            BodyOverrideAction::Remove => json_pointer_remove(&mut json, &rule.path),
+        };
+        if applied {
+            log::info!("[代理] 应用请求体改写: {:?} {}", rule.action, rule.path);
+            changed = true;
+        } else {
+            log::warn!("[代理] 请求体改写未生效: {:?} {}", rule.action, rule.path);
+        }
+    }
+
+    if !changed {
+        return None;
+    }
+    serde_json::to_vec(&json).ok()
+}
+
 // 代理处理函数
 async fn proxy_handler(req: Request, window: WebviewWindow) -> Result<Response<Body>, StatusCode> {
     let start_time = std::time::Instant::now();
@@ -251,15 +1551,40 @@ async fn proxy_handler(req: Request, window: WebviewWindow) -> Result<Response<B
     let uri = req.uri().clone();
     let headers = req.headers().clone();
 
-    // 从全局状态获取目标URL
-    let target_base_url = INSPECTOR_TARGET_URL.lock().await.clone();
+    // 捕获过滤：未被捕获的请求照常转发，但不推送事件、不持久化、不计入用量
+    let (capture, reject_filtered) = match INSPECTOR_CAPTURE_FILTER.lock().await.as_ref() {
+        Some(filter) => (
+            filter.should_capture(method.as_str(), uri.path()),
+            filter.reject_filtered,
+        ),
+        None => (true, false),
+    };
+    if !capture && reject_filtered {
+        log::debug!("[代理] 请求被过滤器拒绝: {} {}", method, uri.path());
+        return Err(StatusCode::NOT_FOUND);
+    }
+
+    // 从全局状态获取默认目标URL，再按路由规则选择实际上游
+    let default_target_url = INSPECTOR_TARGET_URL.lock().await.clone();
+    let route_rules = INSPECTOR_ROUTE_RULES.lock().await.clone();
+    let route = resolve_route(&route_rules, &default_target_url, uri.path(), &headers);
+    if let Some(rule) = route.rule {
+        log::debug!(
+            "[代理] 命中路由规则 {}: {} -> {}",
+            rule.id,
+            uri.path(),
+            route.upstream
+        );
+    }
 
-    // 构建目标URL
+    // 构建目标URL（request_path 保留原始路径，回放时会重新走一遍路由）
+    let query = uri.query().map(|q| format!("?{}", q)).unwrap_or_default();
+    let request_path = format!("{}{}", uri.path(), query);
     let target_url = format!(
         "{}{}{}",
-        target_base_url.trim_end_matches('/'),
-        uri.path(),
-        uri.query().map(|q| format!("?{}", q)).unwrap_or_default()
+        route.upstream.trim_end_matches('/'),
+        route.path,
+        query
     );
 
     // 收集请求头
@@ -277,6 +1602,16 @@ async fn proxy_handler(req: Request, window: WebviewWindow) -> Result<Response<B
         .await
         .map_err(|_| StatusCode::BAD_REQUEST)?
         .to_bytes();
+
+    // 应用请求体改写规则（非 JSON body 时跳过）
+    let body_rules = INSPECTOR_BODY_OVERRIDE_RULES.lock().await.clone();
+    let (body_bytes, original_body) = match apply_body_override_rules(&body_bytes, &body_rules) {
+        Some(rewritten) => (
+            Bytes::from(rewritten),
+            Some(String::from_utf8_lossy(&body_bytes).to_string()),
+        ),
+        None => (body_bytes, None),
+    };
     let request_body = String::from_utf8_lossy(&body_bytes).to_string();
     let request_size = body_bytes.len();
 
@@ -293,42 +1628,84 @@ async fn proxy_handler(req: Request, window: WebviewWindow) -> Result<Response<B
             None
         },
         request_size,
+        original_body,
+        upstream: Some(route.upstream.clone()),
+        route_rule_id: route.rule.map(|rule| rule.id.clone()),
     };
 
     // 发送请求事件到前端
-    let _ = window.emit("inspector-request", &request_record);
+    if capture {
+        let _ = window.emit("inspector-request", &request_record);
+    }
 
-    // 获取代理配置
-    let proxy_settings = super::config_manager::get_proxy_settings(window.app_handle());
+    let records_dir = if capture {
+        INSPECTOR_RECORDS_DIR.lock().await.clone()
+    } else {
+        None
+    };
 
-    // 使用 reqwest 客户端来支持 HTTPS
-    // 对于SSE流，我们需要禁用超时和自动解压
-    let mut client_builder = reqwest::Client::builder()
-        .danger_accept_invalid_certs(true) // 临时接受无效证书以进行测试
-        .no_gzip() // 对于流式响应，禁用自动gzip解压
-        .no_brotli() // 禁用自动brotli解压
-        .no_deflate(); // 禁用自动deflate解压
+    // 命中 mock 规则时直接返回，不请求上游
+    let mock_rule = INSPECTOR_MOCK_RULES
+        .lock()
+        .await
+        .iter()
+        .find(|rule| rule.matches(method.as_str(), uri.path()))
+        .cloned();
+    if let Some(rule) = mock_rule {
+        log::info!(
+            "[代理] 命中 mock 规则: {} {} -> {}",
+            method,
+            uri.path(),
+            rule.status
+        );
+        if rule.delay_ms > 0 {
+            tokio::time::sleep(tokio::time::Duration::from_millis(rule.delay_ms)).await;
+        }
 
-    // 应用代理设置
-    match proxy_settings.mode.as_str() {
-        "none" => {
-            client_builder = client_builder.no_proxy();
+        let response_record = ResponseRecord {
+            id: request_id,
+            timestamp: chrono::Utc::now().timestamp_millis(),
+            status: rule.status,
+            headers: rule.headers.clone(),
+            body: if !rule.body.is_empty() {
+                Some(rule.body.clone())
+            } else {
+                None
+            },
+            response_size: rule.body.len(),
+            duration_ms: start_time.elapsed().as_millis() as u64,
+            usage: None,
+            body_truncated: false,
+            body_path: None,
+        };
+        if capture {
+            let _ = window.emit("inspector-response", &response_record);
         }
-        "custom" => {
-            if !proxy_settings.custom_url.is_empty() {
-                if let Ok(proxy) = reqwest::Proxy::all(&proxy_settings.custom_url) {
-                    client_builder = client_builder.proxy(proxy);
-                }
-            }
+
+        if let Some(dir) = records_dir {
+            let record = PersistedRecord {
+                request: request_record,
+                response: Some(response_record),
+                path: request_path,
+                is_streaming: false,
+            };
+            tokio::spawn(async move { persist_record(&dir, &record).await });
         }
-        _ => {
-            // "system" 模式，reqwest 默认会尝试读取系统代理（env vars）
+
+        let mut mock_response = Response::builder().status(rule.status);
+        for (name, value) in rule.headers.iter() {
+            mock_response = mock_response.header(name.as_str(), value.as_str());
         }
+        return mock_response.body(Body::from(rule.body)).map_err(|e| {
+            log::error!("构建 mock 响应失败: {}", e);
+            StatusCode::INTERNAL_SERVER_ERROR
+        });
     }
 
-    let client = client_builder.build().map_err(|e| {
-        log::error!("创建HTTP客户端失败: {}", e);
-        StatusCode::INTERNAL_SERVER_ERROR
+    // 复用启动时构建的客户端（reqwest::Client 内部为 Arc，clone 开销很小）
+    let client = INSPECTOR_HTTP_CLIENT.lock().await.clone().ok_or_else(|| {
+        log::error!("HTTP客户端未初始化");
+        StatusCode::SERVICE_UNAVAILABLE
     })?;
 
     // 构建 reqwest 请求
@@ -403,9 +1780,24 @@ async fn proxy_handler(req: Request, window: WebviewWindow) -> Result<Response<B
                 body: Some(format!("代理请求失败: {}", e)),
                 response_size: 0,
                 duration_ms: start_time.elapsed().as_millis() as u64,
+                usage: None,
+                body_truncated: false,
+                body_path: None,
             };
 
-            let _ = window.emit("inspector-response", &error_response);
+            if capture {
+                let _ = window.emit("inspector-response", &error_response);
+            }
+
+            if let Some(dir) = &records_dir {
+                let record = PersistedRecord {
+                    request: request_record,
+                    response: Some(error_response),
+                    path: request_path,
+                    is_streaming: false,
+                };
+                persist_record(dir, &record).await;
+            }
 
             return Err(StatusCode::BAD_GATEWAY);
         }
@@ -443,7 +1835,9 @@ async fn proxy_handler(req: Request, window: WebviewWindow) -> Result<Response<B
             chunk: String::new(),
             is_complete: false,
         };
-        let _ = window.emit("inspector-stream-update", &stream_start);
+        if capture {
+            let _ = window.emit("inspector-stream-update", &stream_start);
+        }
 
         // 为流式响应创建一个流
         log::info!(
@@ -466,12 +1860,35 @@ async fn proxy_handler(req: Request, window: WebviewWindow) -> Result<Response<B
         let request_id_for_analysis = request_id.clone();
         let headers_map_for_analysis = headers_map.clone();
         let start_time_for_analysis = start_time;
+        let request_record_for_analysis = request_record.clone();
+        let request_path_for_analysis = request_path.clone();
 
         tokio::spawn(async move {
+            // 持久化开启时把完整流式 body 写入 .stream 文件（不受内存截断影响）
+            let mut stream_file = match &records_dir {
+                Some(dir) => {
+                    let path = record_file_path(dir, &request_id_for_analysis, "stream");
+                    let created = match tokio::fs::create_dir_all(dir.join("records")).await {
+                        Ok(()) => tokio::fs::File::create(&path).await,
+                        Err(e) => Err(e),
+                    };
+                    match created {
+                        Ok(file) => Some(file),
+                        Err(e) => {
+                            log::warn!("[分析器] 创建流式记录文件失败: {}", e);
+                            None
+                        }
+                    }
+                }
+                None => None,
+            };
             let mut accumulated_body = Vec::new();
+            let mut total_size = 0usize;
+            // 超过内存上限后完整 body 所在的文件；持久化时直接复用 .stream 文件
+            let mut body_path: Option<PathBuf> = None;
+            let mut spill_file: Option<tokio::fs::File> = None;
             let mut chunk_count = 0;
             let mut parse_errors = 0;
-            const MAX_ACCUMULATED_SIZE: usize = 10 * 1024 * 1024;
             let mut last_update_size = 0;
             const UPDATE_THRESHOLD: usize = 256; // 降低阈值到256字节，更频繁地更新
 
@@ -480,12 +1897,54 @@ async fn proxy_handler(req: Request, window: WebviewWindow) -> Result<Response<B
                 chunk_count += 1;
                 let chunk_size = chunk.len();
 
-                // 累积数据（带大小限制）
+                if let Some(file) = stream_file.as_mut() {
+                    if let Err(e) = file.write_all(&chunk).await {
+                        log::warn!("[分析器] 写入流式记录文件失败: {}", e);
+                        stream_file = None;
+                    }
+                }
+
+                if let Some(file) = spill_file.as_mut() {
+                    if let Err(e) = file.write_all(&chunk).await {
+                        log::warn!("[分析器] 写入响应体溢出文件失败: {}", e);
+                        spill_file = None;
+                        body_path = None;
+                    }
+                }
+
+                // 累积数据（带大小限制），超限前先把完整内容落盘，内存中只保留尾部
+                total_size += chunk_size;
                 accumulated_body.extend_from_slice(&chunk);
                 if accumulated_body.len() > MAX_ACCUMULATED_SIZE {
+                    if total_size == accumulated_body.len() {
+                        body_path = match (&records_dir, &stream_file) {
+                            (Some(dir), Some(_)) => {
+                                Some(record_file_path(dir, &request_id_for_analysis, "stream"))
+                            }
+                            _ => match create_body_spill_file(
+                                &request_id_for_analysis,
+                                &accumulated_body,
+                            )
+                            .await
+                            {
+                                Ok((path, file)) => {
+                                    spill_file = Some(file);
+                                    Some(path)
+                                }
+                                Err(e) => {
+                                    log::warn!("[分析器] 创建响应体溢出文件失败: {}", e);
+                                    None
+                                }
+                            },
+                        };
+                        log::warn!(
+                            "[分析器] 累积数据超过 {} MB，完整内容已落盘: {:?}",
+                            MAX_ACCUMULATED_SIZE / 1024 / 1024,
+                            body_path
+                        );
+                    }
                     let start = accumulated_body.len() - MAX_ACCUMULATED_SIZE;
                     accumulated_body.drain(..start);
-                    log::warn!("[分析器] 累积数据超过10MB，已截断旧数据");
                 }
 
                 // 定期报告分析进度
@@ -528,7 +1987,9 @@ async fn proxy_handler(req: Request, window: WebviewWindow) -> Result<Response<B
                         is_complete: false,
                     };
 
-                    let _ = window_for_analysis.emit("inspector-stream-update", &stream_update);
+                    if capture {
+                        let _ = window_for_analysis.emit("inspector-stream-update", &stream_update);
+                    }
 
                     // 在第一个块时额外打印日志
                     if chunk_count == 1 {
@@ -543,9 +2004,13 @@ async fn proxy_handler(req: Request, window: WebviewWindow) -> Result<Response<B
             log::info!(
                 "[分析器] 数据收集完成：{} 个块，{} 字节，{} 个解析错误",
                 chunk_count,
-                accumulated_body.len(),
+                total_size,
                 parse_errors
             );
+            if let Some(mut file) = spill_file {
+                let _ = file.flush().await;
+            }
+            let body_truncated = total_size > accumulated_body.len();
 
             // 最终尝试构建响应记录
             let response_body = if accumulated_body.is_empty() {
@@ -582,7 +2047,15 @@ async fn proxy_handler(req: Request, window: WebviewWindow) -> Result<Response<B
                 chunk: String::new(),
                 is_complete: true,
             };
-            let _ = window_for_analysis.emit("inspector-stream-update", &final_stream_update);
+            if capture {
+                let _ = window_for_analysis.emit("inspector-stream-update", &final_stream_update);
+            }
+
+            // 流式响应的 usage 通常在末尾的 data 块中
+            let usage = extract_usage_from_sse(&response_body);
+            if let Some(usage) = usage.as_ref().filter(|_| capture) {
+                record_usage(&request_record_for_analysis, usage).await;
+            }
 
             let response_record = ResponseRecord {
                 id: request_id_for_analysis,
@@ -594,11 +2067,32 @@ async fn proxy_handler(req: Request, window: WebviewWindow) -> Result<Response<B
                 } else {
                     None
                 },
-                response_size: accumulated_body.len(),
+                response_size: total_size,
                 duration_ms: start_time_for_analysis.elapsed().as_millis() as u64,
+                usage,
+                body_truncated,
+                body_path: body_path.map(|p| p.to_string_lossy().to_string()),
             };
 
-            let _ = window_for_analysis.emit("inspector-response", &response_record);
+            if capture {
+                let _ = window_for_analysis.emit("inspector-response", &response_record);
+            }
+
+            if let Some(dir) = &records_dir {
+                if let Some(mut file) = stream_file {
+                    let _ = file.flush().await;
+                }
+                let record = PersistedRecord {
+                    request: request_record_for_analysis,
+                    response: Some(ResponseRecord {
+                        body: None,
+                        ..response_record
+                    }),
+                    path: request_path_for_analysis,
+                    is_streaming: true,
+                };
+                persist_record(dir, &record).await;
+            }
         });
 
         // 使用futures stream转换为axum body - 纯转发，不关心内容
@@ -752,6 +2246,11 @@ async fn proxy_handler(req: Request, window: WebviewWindow) -> Result<Response<B
         };
         let response_size = response_body_bytes.len();
 
+        let usage = extract_usage_from_json(&response_body);
+        if let Some(usage) = usage.as_ref().filter(|_| capture) {
+            record_usage(&request_record, usage).await;
+        }
+
         // 创建响应记录
         let response_record = ResponseRecord {
             id: request_id,
@@ -765,10 +2264,26 @@ async fn proxy_handler(req: Request, window: WebviewWindow) -> Result<Response<B
             },
             response_size,
             duration_ms: start_time.elapsed().as_millis() as u64,
+            usage,
+            body_truncated: false,
+            body_path: None,
         };
 
         // 发送响应事件到前端
-        let _ = window.emit("inspector-response", &response_record);
+        if capture {
+            let _ = window.emit("inspector-response", &response_record);
+        }
+
+        // 后台持久化，不阻塞响应返回
+        if let Some(dir) = records_dir {
+            let record = PersistedRecord {
+                request: request_record,
+                response: Some(response_record),
+                path: request_path,
+                is_streaming: false,
+            };
+            tokio::spawn(async move { persist_record(&dir, &record).await });
+        }
 
         // 构建返回给客户端的响应
         let mut final_response = Response::builder().status(status.as_u16());
@@ -787,3 +2302,341 @@ async fn proxy_handler(req: Request, window: WebviewWindow) -> Result<Response<B
             .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_build_har_entry_fields() {
+        let record = PersistedRecord {
+            request: RequestRecord {
+                id: "a".to_string(),
+                timestamp: 1_700_000_000_123,
+                method: "POST".to_string(),
+                url: "https://api.example.com/v1/chat?stream=true".to_string(),
+                headers: HashMap::from([
+                    ("Content-Type".to_string(), "application/json".to_string()),
+                    ("Authorization".to_string(), "Bearer sk-secret".to_string()),
+                ]),
+                body: Some("{}".to_string()),
+                request_size: 2,
+                original_body: None,
+                upstream: None,
+                route_rule_id: None,
+            },
+            response: Some(ResponseRecord {
+                id: "a".to_string(),
+                timestamp: 1_700_000_001_000,
+                status: 200,
+                headers: HashMap::from([(
+                    "content-type".to_string(),
+                    "text/event-stream".to_string(),
+                )]),
+                body: Some("data: done".to_string()),
+                response_size: 10,
+                duration_ms: 877,
+                usage: None,
+                body_truncated: false,
+                body_path: None,
+            }),
+            path: "/v1/chat?stream=true".to_string(),
+            is_streaming: true,
+        };
+
+        let har = build_har(&[record]);
+        let entry = &har["log"]["entries"][0];
+        assert_eq!(har["log"]["version"], "1.2");
+        assert_eq!(entry["startedDateTime"], "2023-11-14T22:13:20.123Z");
+        assert_eq!(entry["time"], 877);
+        assert_eq!(entry["timings"]["wait"], 877);
+        assert_eq!(entry["request"]["queryString"][0]["name"], "stream");
+        assert_eq!(entry["request"]["postData"]["mimeType"], "application/json");
+        assert_eq!(entry["request"]["headers"][0]["name"], "Authorization");
+        assert_eq!(entry["request"]["headers"][0]["value"], "Bearer ***");
+        assert_eq!(entry["response"]["statusText"], "OK");
+        assert_eq!(entry["response"]["content"]["text"], "data: done");
+    }
+
+    #[test]
+    fn test_redact_headers() {
+        let headers = HashMap::from([
+            ("Authorization".to_string(), "Bearer sk-secret".to_string()),
+            ("x-goog-api-key".to_string(), "AIza-secret".to_string()),
+            ("content-type".to_string(), "application/json".to_string()),
+        ]);
+        let redacted = redact_headers(&headers);
+        assert_eq!(redacted["Authorization"], "Bearer ***");
+        assert_eq!(redacted["x-goog-api-key"], "***");
+        assert_eq!(redacted["content-type"], "application/json");
+    }
+
+    #[test]
+    fn test_missing_replay_credentials() {
+        let headers = HashMap::from([
+            ("Authorization".to_string(), "Bearer ***".to_string()),
+            ("x-api-key".to_string(), "***".to_string()),
+            ("content-type".to_string(), "application/json".to_string()),
+        ]);
+        let credentials =
+            HashMap::from([("Authorization".to_string(), "Bearer sk-secret".to_string())]);
+        let mut rules = vec![HeaderOverrideRule {
+            id: "r".to_string(),
+            enabled: false,
+            key: "X-Api-Key".to_string(),
+            value: "key".to_string(),
+        }];
+
+        assert_eq!(
+            missing_replay_credentials(&headers, &HashMap::new(), &rules),
+            vec!["Authorization".to_string(), "x-api-key".to_string()]
+        );
+        assert_eq!(
+            missing_replay_credentials(&headers, &credentials, &rules),
+            vec!["x-api-key".to_string()]
+        );
+        // 启用的覆盖规则会在代理中补上该请求头
+        rules[0].enabled = true;
+        assert!(missing_replay_credentials(&headers, &credentials, &rules).is_empty());
+    }
+
+    #[test]
+    fn test_resolve_route() {
+        let rule = |id: &str, prefix: &str, header: &str, value: &str, strip: bool| RouteRule {
+            id: id.to_string(),
+            enabled: true,
+            path_prefix: prefix.to_string(),
+            header_name: header.to_string(),
+            header_value: value.to_string(),
+            upstream: format!("http://{}.local/", id),
+            strip_prefix: strip,
+        };
+        let rules = vec![
+            rule("ollama", "/ollama", "", "", true),
+            rule("openai", "", "x-upstream", "openai", false),
+            rule("any-header", "/v1", "x-debug", "", false),
+        ];
+        let default = "https://default.example.com";
+        let mut headers = axum::http::HeaderMap::new();
+
+        let route = resolve_route(&rules, default, "/ollama/api/chat", &headers);
+        assert_eq!(route.upstream, "http://ollama.local/");
+        assert_eq!(route.path, "/api/chat");
+        assert_eq!(route.rule.map(|r| r.id.as_str()), Some("ollama"));
+
+        // 前缀按路径段匹配
+        let route = resolve_route(&rules, default, "/ollama", &headers);
+        assert_eq!(route.rule.map(|r| r.id.as_str()), Some("ollama"));
+        assert_eq!(route.path, "/");
+        let route = resolve_route(&rules, default, "/ollama2/api/chat", &headers);
+        assert!(route.rule.is_none());
+        assert_eq!(route.path, "/ollama2/api/chat");
+
+        let route = resolve_route(&rules, default, "/v1/chat", &headers);
+        assert_eq!(route.upstream, default);
+        assert_eq!(route.path, "/v1/chat");
+        assert!(route.rule.is_none());
+
+        headers.insert("x-upstream", "openai".parse().unwrap());
+        let route = resolve_route(&rules, default, "/v1/chat", &headers);
+        assert_eq!(route.rule.map(|r| r.id.as_str()), Some("openai"));
+        assert_eq!(route.path, "/v1/chat");
+
+        headers.insert("x-upstream", "other".parse().unwrap());
+        headers.insert("x-debug", "1".parse().unwrap());
+        let route = resolve_route(&rules, default, "/v1/chat", &headers);
+        assert_eq!(route.rule.map(|r| r.id.as_str()), Some("any-header"));
+
+        // 禁用的规则不参与匹配
+        let mut disabled = rules.clone();
+        disabled[0].enabled = false;
+        let route = resolve_route(&disabled, default, "/ollama/api/chat", &headers);
+        assert_eq!(route.upstream, default);
+    }
+
+    #[test]
+    fn test_mock_rule_matching() {
+        let mut rule = MockRule {
+            id: "m".to_string(),
+            enabled: true,
+            method: "post".to_string(),
+            path: "/v1/chat/completions".to_string(),
+            status: 429,
+            headers: HashMap::new(),
+            body: String::new(),
+            delay_ms: 0,
+        };
+        assert!(rule.matches("POST", "/v1/chat/completions"));
+        assert!(!rule.matches("GET", "/v1/chat/completions"));
+        assert!(!rule.matches("POST", "/v1/chat/completions/extra"));
+
+        rule.method.clear();
+        rule.path = "/v1/*".to_string();
+        assert!(rule.matches("GET", "/v1/models"));
+        assert!(!rule.matches("GET", "/v2/models"));
+
+        rule.enabled = false;
+        assert!(!rule.matches("GET", "/v1/models"));
+    }
+
+    fn body_rule(
+        path: &str,
+        action: BodyOverrideAction,
+        value: serde_json::Value,
+    ) -> BodyOverrideRule {
+        BodyOverrideRule {
+            id: path.to_string(),
+            enabled: true,
+            path: path.to_string(),
+            action,
+            value,
+        }
+    }
+
+    #[test]
+    fn test_apply_body_override_rules() {
+        let body =
+            br#"{"model":"gpt-4o","temperature":1,"messages":[{"role":"user","content":"hi"}]}"#;
+        let rules = vec![
+            body_rule(
+                "/model",
+                BodyOverrideAction::Set,
+                serde_json::json!("gpt-4o-mini"),
+            ),
+            body_rule(
+                "/temperature",
+                BodyOverrideAction::Remove,
+                serde_json::Value::Null,
+            ),
+            body_rule(
+                "/messages/0",
+                BodyOverrideAction::Set,
+                serde_json::json!({ "role": "system", "content": "be brief" }),
+            ),
+            body_rule(
+                "/extra/top_p",
+                BodyOverrideAction::Set,
+                serde_json::json!(0.5),
+            ),
+        ];
+
+        let rewritten = apply_body_override_rules(body, &rules).unwrap();
+        let json: serde_json::Value = serde_json::from_slice(&rewritten).unwrap();
+        assert_eq!(json["model"], "gpt-4o-mini");
+        assert!(json.get("temperature").is_none());
+        assert_eq!(json["messages"][0]["role"], "system");
+        assert_eq!(json["extra"]["top_p"], 0.5);
+
+        // 非 JSON 请求体跳过改写
+        assert!(apply_body_override_rules(b"plain text", &rules).is_none());
+    }
+
+    #[test]
+    fn test_capture_filter() {
+        let filter = |mode, patterns: &[&str]| {
+            CompiledCaptureFilter::compile(&CaptureFilter {
+                enabled: true,
+                mode,
+                patterns: patterns.iter().map(|p| p.to_string()).collect(),
+                reject_filtered: false,
+            })
+            .unwrap()
+            .unwrap()
+        };
+
+        let blacklist = filter(
+            CaptureFilterMode::Blacklist,
+            &["/favicon.ico", "GET /health*", "re:^/v\\d+/models$"],
+        );
+        assert!(!blacklist.should_capture("GET", "/favicon.ico"));
+        assert!(!blacklist.should_capture("get", "/healthz"));
+        assert!(blacklist.should_capture("POST", "/health"));
+        assert!(!blacklist.should_capture("GET", "/v1/models"));
+        assert!(blacklist.should_capture("POST", "/v1/chat/completions"));
+
+        let whitelist = filter(CaptureFilterMode::Whitelist, &["POST /v1/*"]);
+        assert!(whitelist.should_capture("POST", "/v1/chat/completions"));
+        assert!(!whitelist.should_capture("GET", "/v1/models"));
+        assert!(!whitelist.should_capture("POST", "/v1"));
+
+        // 未启用时不过滤，无效正则拒绝
+        assert!(CompiledCaptureFilter::compile(&CaptureFilter::default())
+            .unwrap()
+            .is_none());
+        assert!(CompiledCaptureFilter::compile(&CaptureFilter {
+            enabled: true,
+            patterns: vec!["re:(".to_string()],
+            ..Default::default()
+        })
+        .is_err());
+    }
+
+    #[test]
+    fn test_extract_usage() {
+        let json =
+            r#"{"id":"x","usage":{"prompt_tokens":12,"completion_tokens":30,"total_tokens":42}}"#;
+        assert_eq!(
+            extract_usage_from_json(json),
+            Some(TokenUsage {
+                prompt_tokens: 12,
+                completion_tokens: 30,
+                total_tokens: 42,
+            })
+        );
+        assert_eq!(extract_usage_from_json(r#"{"usage":null}"#), None);
+
+        let sse = "data: {\"choices\":[{\"delta\":{\"content\":\"hi\"}}],\"usage\":null}\n\n\
+                   data: {\"choices\":[],\"usage\":{\"input_tokens\":5,\"output_tokens\":7}}\n\n\
+                   data: [DONE]\n\n";
+        assert_eq!(
+            extract_usage_from_sse(sse),
+            Some(TokenUsage {
+                prompt_tokens: 5,
+                completion_tokens: 7,
+                total_tokens: 12,
+            })
+        );
+    }
+
+    #[test]
+    fn test_build_usage_summary_prices() {
+        let stats = |model: &str, prompt: u64, completion: u64| ModelUsageStats {
+            model: model.to_string(),
+            request_count: 1,
+            prompt_tokens: prompt,
+            completion_tokens: completion,
+            total_tokens: prompt + completion,
+            ..Default::default()
+        };
+        let overrides = HashMap::from([(
+            "my-model".to_string(),
+            ModelPrice {
+                input_per_million: 1.0,
+                output_per_million: 2.0,
+            },
+        )]);
+
+        let summary = build_usage_summary(
+            vec![
+                stats("gpt-4o-mini-2024-07-18", 1_000_000, 0),
+                stats("my-model-v2", 1_000_000, 1_000_000),
+                stats("unknown", 10, 10),
+            ],
+            &overrides,
+        );
+
+        // 最长前缀匹配：gpt-4o-mini 而不是 gpt-4o
+        let mini = summary
+            .models
+            .iter()
+            .find(|m| m.model.starts_with("gpt"))
+            .unwrap();
+        assert!((mini.estimated_cost - 0.15).abs() < 1e-9);
+        let custom = &summary.models[0];
+        assert_eq!(custom.model, "my-model-v2");
+        assert!((custom.estimated_cost - 3.0).abs() < 1e-9);
+        assert!(!summary.models[2].priced);
+        assert!((summary.estimated_cost - 3.15).abs() < 1e-9);
+        assert_eq!(summary.total_tokens, 3_000_020);
+    }
+}
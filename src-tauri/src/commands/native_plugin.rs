@@ -21,6 +21,7 @@ use serde::Deserialize;
 use std::collections::HashMap;
 use std::ffi::{CStr, CString};
 use std::os::raw::c_char;
+use std::path::PathBuf;
 use std::sync::{
     atomic::{AtomicUsize, Ordering},
     Arc, Mutex,
@@ -33,14 +34,26 @@ type CallFunction = unsafe extern "C" fn(*const c_char, *const c_char) -> *mut c
 /// 原生插件释放字符串函数类型
 type FreeStringFunction = unsafe extern "C" fn(*mut c_char);
 
+/// 卸载时等待插件空闲的超时时间
+const UNLOAD_IDLE_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(5);
+
 /// 插件元数据
 #[derive(Clone)]
 struct PluginMetadata {
     library: Arc<Library>,
+    /// 已解析的库文件绝对路径，热重载时复用
+    library_path: PathBuf,
     reloadable: bool,
     ref_count: Arc<AtomicUsize>,
 }
 
+impl PluginMetadata {
+    /// 没有进行中的调用，且库句柄没有被其他地方持有
+    fn is_idle(&self) -> bool {
+        self.ref_count.load(Ordering::SeqCst) == 0 && Arc::strong_count(&self.library) == 1
+    }
+}
+
 /// 全局原生插件状态
 pub struct NativePluginState {
     /// 已加载的插件库
@@ -84,18 +97,85 @@ pub async fn load_native_plugin(
     );
 
     // 检查插件是否已加载
-    {
-        let mut plugins = state
+    let already_loaded = {
+        let plugins = state
             .plugins
             .lock()
             .map_err(|e| format!("获取插件锁失败: {}", e))?;
-        if plugins.contains_key(&plugin_id) {
-            log::info!("[NATIVE] 插件 {} 已加载，先卸载", plugin_id);
-            // 如果已加载，先卸载
-            plugins.remove(&plugin_id);
+        plugins.get(&plugin_id).map(|metadata| metadata.reloadable)
+    };
+    match already_loaded {
+        Some(true) => {
+            log::info!("[NATIVE] 插件 {} 已加载，先安全卸载", plugin_id);
+            release_plugin(&state, &plugin_id).await?;
         }
+        Some(false) => {
+            // 不可重载的插件不能替换正在使用的库，保留现有实例
+            log::warn!(
+                "[NATIVE] 插件 {} 已加载且不支持重载，沿用现有实例",
+                plugin_id
+            );
+            return Ok(());
+        }
+        None => {}
+    }
+
+    let absolute_path = resolve_library_path(&library_path)?;
+    insert_plugin(&state, &plugin_id, absolute_path, reloadable)?;
+
+    log::info!("[NATIVE] 插件 {} 加载成功", plugin_id);
+    Ok(())
+}
+
+/// 热重载原生插件
+///
+/// 仅 manifest 中 `reloadable` 为 true 的插件可用：等待进行中的调用结束并释放旧库句柄后，
+/// 从原路径（或传入的新路径）重新加载
+#[tauri::command]
+pub async fn reload_native_plugin(
+    plugin_id: String,
+    library_path: Option<String>,
+    state: State<'_, NativePluginState>,
+) -> Result<(), String> {
+    log::info!("[NATIVE] 请求热重载插件: {}", plugin_id);
+
+    let (old_path, reloadable) = {
+        let plugins = state
+            .plugins
+            .lock()
+            .map_err(|e| format!("获取插件锁失败: {}", e))?;
+        let metadata = plugins
+            .get(&plugin_id)
+            .ok_or_else(|| format!("插件 {} 未加载", plugin_id))?;
+        (metadata.library_path.clone(), metadata.reloadable)
+    };
+
+    if !reloadable {
+        return Err(format!(
+            "插件 {} 不支持热重载，请重启应用以加载新版本",
+            plugin_id
+        ));
+    }
+
+    // 先解析新路径，路径无效时不动旧实例
+    let new_path = match library_path {
+        Some(path) => resolve_library_path(&path)?,
+        None => old_path,
+    };
+    if !new_path.exists() {
+        return Err(format!("插件文件不存在: {:?}", new_path));
     }
 
+    // 旧库必须完全释放，否则同一路径的 dlopen/LoadLibrary 会直接返回旧句柄
+    release_plugin(&state, &plugin_id).await?;
+    insert_plugin(&state, &plugin_id, new_path, reloadable)?;
+
+    log::info!("[NATIVE] 插件 {} 热重载成功", plugin_id);
+    Ok(())
+}
+
+/// 解析库文件的绝对路径
+fn resolve_library_path(library_path: &str) -> Result<PathBuf, String> {
     // 在开发模式下，library_path 是相对于项目根目录的
     // 我们需要获取项目根目录来构建绝对路径
     #[cfg(debug_assertions)]
@@ -108,7 +188,7 @@ pub async fn load_native_plugin(
             .parent()
             .ok_or_else(|| "无法获取项目根目录".to_string())?;
 
-        let full_path = workspace_dir.join(&library_path);
+        let full_path = workspace_dir.join(library_path);
         log::debug!("[NATIVE] 开发模式，项目根目录: {:?}", workspace_dir);
         log::debug!("[NATIVE] 拼接后的路径: {:?}", full_path);
 
@@ -124,13 +204,23 @@ pub async fn load_native_plugin(
     #[cfg(not(debug_assertions))]
     let absolute_path = {
         use std::path::Path;
-        let path = Path::new(&library_path);
+        let path = Path::new(library_path);
         if !path.exists() {
             return Err(format!("插件文件不存在: {:?}", path));
         }
         path.to_path_buf()
     };
 
+    Ok(absolute_path)
+}
+
+/// 加载动态库并登记到插件表
+fn insert_plugin(
+    state: &NativePluginState,
+    plugin_id: &str,
+    absolute_path: PathBuf,
+    reloadable: bool,
+) -> Result<(), String> {
     log::info!("[NATIVE] 最终加载路径: {:?}", absolute_path);
 
     // 加载动态库
@@ -139,20 +229,52 @@ pub async fn load_native_plugin(
     );
 
     // 存储插件库
-    {
+    let mut plugins = state
+        .plugins
+        .lock()
+        .map_err(|e| format!("获取插件锁失败: {}", e))?;
+    let metadata = PluginMetadata {
+        library,
+        library_path: absolute_path,
+        reloadable,
+        ref_count: Arc::new(AtomicUsize::new(0)),
+    };
+    plugins.insert(plugin_id.to_string(), metadata);
+    Ok(())
+}
+
+/// 从插件表移除插件，并在其空闲后释放库句柄
+///
+/// 超时仍在使用时会把插件放回插件表并返回错误
+async fn release_plugin(state: &NativePluginState, plugin_id: &str) -> Result<(), String> {
+    let metadata = {
         let mut plugins = state
             .plugins
             .lock()
             .map_err(|e| format!("获取插件锁失败: {}", e))?;
-        let metadata = PluginMetadata {
-            library,
-            reloadable,
-            ref_count: Arc::new(AtomicUsize::new(0)),
-        };
-        plugins.insert(plugin_id.clone(), metadata);
+        plugins
+            .remove(plugin_id)
+            .ok_or_else(|| format!("插件 {} 未找到或已卸载", plugin_id))?
+    };
+
+    // 等待引用计数归零，且没有调用方仍持有库句柄
+    let start = std::time::Instant::now();
+    while !metadata.is_idle() {
+        if start.elapsed() > UNLOAD_IDLE_TIMEOUT {
+            // 如果超时，需要将插件重新插回，因为它仍在被使用
+            let mut plugins = state
+                .plugins
+                .lock()
+                .map_err(|e| format!("获取插件锁失败: {}", e))?;
+            plugins.insert(plugin_id.to_string(), metadata);
+            return Err(format!("卸载超时: 插件 {} 仍在使用中", plugin_id));
+        }
+        tokio::time::sleep(std::time::Duration::from_millis(100)).await;
     }
 
-    log::info!("[NATIVE] 插件 {} 加载成功", plugin_id);
+    // 这是最后一个句柄，drop 即卸载动态库
+    drop(metadata);
+    log::info!("[NATIVE] 插件 {} 已安全卸载", plugin_id);
     Ok(())
 }
 
@@ -166,8 +288,8 @@ pub async fn unload_native_plugin(
 ) -> Result<(), String> {
     log::info!("[NATIVE] 请求卸载插件: {}", plugin_id);
 
-    let plugin_to_unload = {
-        let mut plugins = state
+    {
+        let plugins = state
             .plugins
             .lock()
             .map_err(|e| format!("获取插件锁失败: {}", e))?;
@@ -176,33 +298,9 @@ pub async fn unload_native_plugin(
                 return Err(format!("插件 {} 不支持运行时卸载，请重启应用", plugin_id));
             }
         }
-        plugins.remove(&plugin_id)
-    };
-
-    if let Some(metadata) = plugin_to_unload {
-        // 等待引用计数归零
-        let timeout = std::time::Duration::from_secs(5);
-        let start = std::time::Instant::now();
-        while metadata.ref_count.load(Ordering::SeqCst) > 0 {
-            if start.elapsed() > timeout {
-                // 如果超时，需要将插件重新插回，因为它仍在被使用
-                let mut plugins = state
-                    .plugins
-                    .lock()
-                    .map_err(|e| format!("获取插件锁失败: {}", e))?;
-                plugins.insert(plugin_id.clone(), metadata);
-                return Err(format!("卸载超时: 插件 {} 仍在使用中", plugin_id));
-            }
-            tokio::time::sleep(std::time::Duration::from_millis(100)).await;
-        }
-
-        // 引用计数为 0，可以安全 drop (卸载)
-        drop(metadata);
-        log::info!("[NATIVE] 插件 {} 已安全卸载", plugin_id);
-        Ok(())
-    } else {
-        Err(format!("插件 {} 未找到或已卸载", plugin_id))
     }
+
+    release_plugin(&state, &plugin_id).await
 }
 
 /// 调用原生插件方法
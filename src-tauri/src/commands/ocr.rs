@@ -13,17 +13,182 @@
 // limitations under the License.
 
 use base64::{engine::general_purpose, Engine as _};
+use futures_util::{stream, StreamExt};
 use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Emitter};
+
+/// 批量 OCR 的最大并发数，避免同时解码过多大图导致内存暴涨
+const OCR_BATCH_CONCURRENCY: usize = 4;
+
+/// 未指定语言时使用的 OCR 语言
+#[cfg(target_os = "windows")]
+const DEFAULT_OCR_LANGUAGE: &str = "zh-Hans";
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct OcrResult {
     pub text: String,
     pub confidence: f64,
+    /// 文字块及其边界框，仅在请求时返回
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub blocks: Option<Vec<OcrBlock>>,
+}
+
+/// 文字块的粒度
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum OcrBlockLevel {
+    Word,
+    Line,
+}
+
+/// 识别出的文字块，坐标以原图左上角为原点、单位为像素
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OcrBlock {
+    pub text: String,
+    pub level: OcrBlockLevel,
+    pub x: f64,
+    pub y: f64,
+    pub width: f64,
+    pub height: f64,
+    /// 引擎未提供置信度时为 None (如 Windows OCR)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub confidence: Option<f64>,
+}
+
+impl OcrBlock {
+    /// 合并多个块的边界框，用于由词构造行
+    #[cfg_attr(not(target_os = "windows"), allow(dead_code))]
+    fn union_bounds(blocks: &[OcrBlock]) -> Option<(f64, f64, f64, f64)> {
+        let first = blocks.first()?;
+        let (mut left, mut top) = (first.x, first.y);
+        let (mut right, mut bottom) = (first.x + first.width, first.y + first.height);
+        for block in &blocks[1..] {
+            left = left.min(block.x);
+            top = top.min(block.y);
+            right = right.max(block.x + block.width);
+            bottom = bottom.max(block.y + block.height);
+        }
+        Some((left, top, right - left, bottom - top))
+    }
+}
+
+/// 过滤掉置信度低于阈值的块，没有置信度的块保留
+fn retain_confident_blocks(blocks: &mut Vec<OcrBlock>, min_confidence: f64) {
+    blocks.retain(|block| block.confidence.is_none_or(|c| c >= min_confidence));
+}
+
+/// 系统已安装的 OCR 语言
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OcrLanguage {
+    /// BCP-47 语言标签，如 "zh-Hans"
+    pub tag: String,
+    pub display_name: String,
+}
+
+/// 批量 OCR 中单张图片的结果
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OcrBatchItem {
+    /// 在请求列表中的位置
+    pub index: usize,
+    pub path: String,
+    pub text: Option<String>,
+    pub confidence: Option<f64>,
+    /// 失败原因，成功时为 None
+    pub error: Option<String>,
+}
+
+/// 批量 OCR 进度事件
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OcrBatchProgress {
+    pub completed: usize,
+    pub total: usize,
+    pub item: OcrBatchItem,
+}
+
+/// 将语言参数解析为候选的 BCP-47 标签列表 (按优先级排列)
+///
+/// 支持 Tesseract 风格的 "chi_sim+eng"，也可直接传入 "zh-Hans" 这类标签。
+#[cfg_attr(not(target_os = "windows"), allow(dead_code))]
+fn resolve_ocr_language_tags(lang: &str) -> Vec<String> {
+    let mut tags: Vec<String> = Vec::new();
+    for code in lang
+        .split(['+', ','])
+        .map(str::trim)
+        .filter(|c| !c.is_empty())
+    {
+        let tag = match code.to_ascii_lowercase().as_str() {
+            "chi_sim" | "zh" | "zh-cn" => "zh-Hans".to_string(),
+            "chi_tra" | "zh-tw" | "zh-hk" => "zh-Hant".to_string(),
+            "eng" => "en-US".to_string(),
+            "jpn" => "ja".to_string(),
+            "kor" => "ko".to_string(),
+            "fra" => "fr".to_string(),
+            "deu" => "de".to_string(),
+            "spa" => "es".to_string(),
+            "rus" => "ru".to_string(),
+            "ita" => "it".to_string(),
+            "por" => "pt".to_string(),
+            _ => code.to_string(),
+        };
+        if !tags.iter().any(|t| t.eq_ignore_ascii_case(&tag)) {
+            tags.push(tag);
+        }
+    }
+    tags
+}
+
+/// 列出当前系统已安装语言包、可用于原生 OCR 的语言
+#[tauri::command]
+pub async fn ocr_list_available_languages() -> Result<Vec<OcrLanguage>, String> {
+    #[cfg(target_os = "windows")]
+    {
+        use windows::Media::Ocr::OcrEngine;
+
+        let languages = OcrEngine::AvailableRecognizerLanguages()
+            .map_err(|e| format!("获取OCR语言列表失败: {}", e))?;
+        let size = languages
+            .Size()
+            .map_err(|e| format!("获取OCR语言列表失败: {}", e))?;
+        let mut result = Vec::with_capacity(size as usize);
+        for i in 0..size {
+            let language = languages
+                .GetAt(i)
+                .map_err(|e| format!("读取OCR语言失败: {}", e))?;
+            result.push(OcrLanguage {
+                tag: language
+                    .LanguageTag()
+                    .map(|s| s.to_string())
+                    .unwrap_or_default(),
+                display_name: language
+                    .DisplayName()
+                    .map(|s| s.to_string())
+                    .unwrap_or_default(),
+            });
+        }
+        Ok(result)
+    }
+
+    #[cfg(not(target_os = "windows"))]
+    {
+        Ok(Vec::new())
+    }
 }
 
 /// 原生 OCR 识别命令
+///
+/// `lang` 可选，如 "chi_sim+eng" 或 "ja"；多个语言时使用第一个已安装的语言包。
+/// `with_blocks` 为 true 时额外返回每个词/行的边界框，`min_confidence` 用于过滤低置信度的块。
+/// Windows OCR 不提供块级置信度，该平台上 `min_confidence` 不会过滤掉任何块。
 #[tauri::command]
-pub async fn native_ocr(image_data: String) -> Result<OcrResult, String> {
+pub async fn native_ocr(
+    image_data: String,
+    lang: Option<String>,
+    with_blocks: Option<bool>,
+    min_confidence: Option<f64>,
+) -> Result<OcrResult, String> {
     // 解析 base64 图片数据
     let base64_data = image_data
         .strip_prefix("data:image/png;base64,")
@@ -31,19 +196,108 @@ pub async fn native_ocr(image_data: String) -> Result<OcrResult, String> {
         .or_else(|| image_data.strip_prefix("data:image/jpg;base64,"))
         .unwrap_or(&image_data);
 
-    let _image_bytes = general_purpose::STANDARD
+    let image_bytes = general_purpose::STANDARD
         .decode(base64_data)
         .map_err(|e| format!("Base64解码失败: {}", e))?;
 
-    // 根据操作系统选择不同的 OCR 实现
+    let with_blocks = with_blocks.unwrap_or(false);
+    let mut result = tokio::task::spawn_blocking(move || {
+        recognize_image_bytes(&image_bytes, lang.as_deref(), with_blocks)
+    })
+    .await
+    .map_err(|e| format!("OCR任务执行失败: {}", e))??;
+
+    if let (Some(blocks), Some(min)) = (result.blocks.as_mut(), min_confidence) {
+        retain_confident_blocks(blocks, min);
+    }
+    Ok(result)
+}
+
+/// 批量识别本地图片文件
+///
+/// 在后台以有限并发执行，每完成一张通过 `ocr-batch-progress` 事件汇报结果。
+/// 单张失败只记录在对应结果的 `error` 中，不会中断整批。返回顺序与 `paths` 一致。
+#[tauri::command]
+pub async fn native_ocr_batch(
+    app: AppHandle,
+    paths: Vec<String>,
+    lang: Option<String>,
+) -> Result<Vec<OcrBatchItem>, String> {
+    let total = paths.len();
+    log::info!("[OCR] 开始批量识别 {} 张图片", total);
+
+    let mut completed = 0;
+    let mut items: Vec<OcrBatchItem> = Vec::with_capacity(total);
+    let mut tasks = stream::iter(paths.into_iter().enumerate())
+        .map(|(index, path)| {
+            let lang = lang.clone();
+            async move {
+                let task_path = path.clone();
+                let result = tokio::task::spawn_blocking(move || {
+                    let image_bytes =
+                        std::fs::read(&task_path).map_err(|e| format!("读取图片失败: {}", e))?;
+                    recognize_image_bytes(&image_bytes, lang.as_deref(), false)
+                })
+                .await
+                .map_err(|e| format!("OCR任务执行失败: {}", e))
+                .and_then(|r| r);
+
+                match result {
+                    Ok(ocr) => OcrBatchItem {
+                        index,
+                        path,
+                        text: Some(ocr.text),
+                        confidence: Some(ocr.confidence),
+                        error: None,
+                    },
+                    Err(e) => {
+                        log::warn!("[OCR] 识别失败 {}: {}", path, e);
+                        OcrBatchItem {
+                            index,
+                            path,
+                            text: None,
+                            confidence: None,
+                            error: Some(e),
+                        }
+                    }
+                }
+            }
+        })
+        .buffer_unordered(OCR_BATCH_CONCURRENCY);
+
+    while let Some(item) = tasks.next().await {
+        completed += 1;
+        let progress = OcrBatchProgress {
+            completed,
+            total,
+            item: item.clone(),
+        };
+        if let Err(e) = app.emit("ocr-batch-progress", &progress) {
+            log::debug!("[OCR] 发出 ocr-batch-progress 事件失败: {}", e);
+        }
+        items.push(item);
+    }
+
+    items.sort_by_key(|item| item.index);
+    let failed = items.iter().filter(|item| item.error.is_some()).count();
+    log::info!("[OCR] 批量识别完成: 共 {} 张，失败 {} 张", total, failed);
+    Ok(items)
+}
+
+/// 根据操作系统选择不同的 OCR 实现 (阻塞执行)
+fn recognize_image_bytes(
+    _image_bytes: &[u8],
+    _lang: Option<&str>,
+    _with_blocks: bool,
+) -> Result<OcrResult, String> {
     #[cfg(target_os = "windows")]
     {
-        windows_ocr(&_image_bytes).await
+        windows_ocr(_image_bytes, _lang, _with_blocks)
     }
 
     #[cfg(target_os = "macos")]
     {
-        macos_ocr(&_image_bytes).await
+        macos_ocr(_image_bytes)
     }
 
     #[cfg(not(any(target_os = "windows", target_os = "macos")))]
@@ -54,7 +308,11 @@ pub async fn native_ocr(image_data: String) -> Result<OcrResult, String> {
 
 /// Windows OCR 实现
 #[cfg(target_os = "windows")]
-async fn windows_ocr(image_bytes: &[u8]) -> Result<OcrResult, String> {
+fn windows_ocr(
+    image_bytes: &[u8],
+    lang: Option<&str>,
+    with_blocks: bool,
+) -> Result<OcrResult, String> {
     use windows::{
         Graphics::Imaging::{BitmapDecoder, SoftwareBitmap},
         Media::Ocr::OcrEngine,
@@ -106,13 +364,7 @@ async fn windows_ocr(image_bytes: &[u8]) -> Result<OcrResult, String> {
     )
     .map_err(|e| format!("转换位图格式失败: {}", e))?;
 
-    // 使用简体中文 OCR 引擎
-    let language =
-        windows::Globalization::Language::CreateLanguage(&windows::core::HSTRING::from("zh-Hans"))
-            .map_err(|e| format!("创建语言对象失败: {}", e))?;
-
-    let engine = OcrEngine::TryCreateFromLanguage(&language)
-        .map_err(|e| format!("创建OCR引擎失败: {}", e))?;
+    let engine = create_windows_ocr_engine(lang)?;
 
     // 执行 OCR 识别
     let result = engine
@@ -126,16 +378,147 @@ async fn windows_ocr(image_bytes: &[u8]) -> Result<OcrResult, String> {
         .map_err(|e| format!("获取识别文本失败: {}", e))?
         .to_string();
 
+    let blocks = if with_blocks {
+        Some(collect_windows_ocr_blocks(&result)?)
+    } else {
+        None
+    };
+
     // Windows OCR API 不直接提供置信度，这里返回固定值
     Ok(OcrResult {
         text,
         confidence: 0.95,
+        blocks,
     })
 }
 
+/// 从 Windows OCR 结果中提取词与行的边界框 (行框由其包含的词合并而来)
+#[cfg(target_os = "windows")]
+fn collect_windows_ocr_blocks(
+    result: &windows::Media::Ocr::OcrResult,
+) -> Result<Vec<OcrBlock>, String> {
+    let mut blocks = Vec::new();
+    let lines = result
+        .Lines()
+        .map_err(|e| format!("获取识别行失败: {}", e))?;
+    for line in lines {
+        let words = line.Words().map_err(|e| format!("获取识别词失败: {}", e))?;
+        let mut word_blocks = Vec::new();
+        for word in words {
+            let rect = word
+                .BoundingRect()
+                .map_err(|e| format!("获取文字边界框失败: {}", e))?;
+            word_blocks.push(OcrBlock {
+                text: word.Text().map(|s| s.to_string()).unwrap_or_default(),
+                level: OcrBlockLevel::Word,
+                x: rect.X as f64,
+                y: rect.Y as f64,
+                width: rect.Width as f64,
+                height: rect.Height as f64,
+                confidence: None,
+            });
+        }
+
+        if let Some((x, y, width, height)) = OcrBlock::union_bounds(&word_blocks) {
+            blocks.push(OcrBlock {
+                text: line.Text().map(|s| s.to_string()).unwrap_or_default(),
+                level: OcrBlockLevel::Line,
+                x,
+                y,
+                width,
+                height,
+                confidence: None,
+            });
+        }
+        blocks.extend(word_blocks);
+    }
+    Ok(blocks)
+}
+
+/// 按语言参数创建 Windows OCR 引擎，未安装对应语言包时给出明确提示
+#[cfg(target_os = "windows")]
+fn create_windows_ocr_engine(lang: Option<&str>) -> Result<windows::Media::Ocr::OcrEngine, String> {
+    use windows::{core::HSTRING, Globalization::Language, Media::Ocr::OcrEngine};
+
+    let candidates = match lang {
+        Some(lang) => resolve_ocr_language_tags(lang),
+        None => vec![DEFAULT_OCR_LANGUAGE.to_string()],
+    };
+    if candidates.is_empty() {
+        return Err("OCR语言参数为空".to_string());
+    }
+
+    for tag in &candidates {
+        let language = Language::CreateLanguage(&HSTRING::from(tag.as_str()))
+            .map_err(|e| format!("创建语言对象失败 ({}): {}", tag, e))?;
+        if OcrEngine::IsLanguageSupported(&language).unwrap_or(false) {
+            return OcrEngine::TryCreateFromLanguage(&language)
+                .map_err(|e| format!("创建OCR引擎失败: {}", e));
+        }
+    }
+
+    Err(format!(
+        "系统未安装所需的OCR语言包: {}。请在「设置 > 时间和语言 > 语言和区域」中添加对应语言并安装「光学字符识别」功能",
+        candidates.join(", ")
+    ))
+}
+
 /// macOS OCR 实现（占位）
 #[cfg(target_os = "macos")]
-async fn macos_ocr(_image_bytes: &[u8]) -> Result<OcrResult, String> {
+fn macos_ocr(_image_bytes: &[u8]) -> Result<OcrResult, String> {
     // TODO: 实现 macOS Vision Framework OCR
     Err("macOS OCR 暂未实现".to_string())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_resolve_ocr_language_tags() {
+        assert_eq!(
+            resolve_ocr_language_tags("chi_sim+eng"),
+            vec!["zh-Hans".to_string(), "en-US".to_string()]
+        );
+        assert_eq!(
+            resolve_ocr_language_tags(" ja , jpn "),
+            vec!["ja".to_string()]
+        );
+        assert_eq!(
+            resolve_ocr_language_tags("ko-KR"),
+            vec!["ko-KR".to_string()]
+        );
+        assert!(resolve_ocr_language_tags("+").is_empty());
+    }
+
+    fn block(x: f64, y: f64, width: f64, height: f64, confidence: Option<f64>) -> OcrBlock {
+        OcrBlock {
+            text: String::new(),
+            level: OcrBlockLevel::Word,
+            x,
+            y,
+            width,
+            height,
+            confidence,
+        }
+    }
+
+    #[test]
+    fn test_block_bounds_and_confidence_filter() {
+        let words = vec![
+            block(10.0, 20.0, 30.0, 10.0, Some(0.9)),
+            block(45.0, 18.0, 20.0, 14.0, Some(0.3)),
+            block(70.0, 21.0, 5.0, 8.0, None),
+        ];
+        assert_eq!(
+            OcrBlock::union_bounds(&words),
+            Some((10.0, 18.0, 65.0, 14.0))
+        );
+        assert_eq!(OcrBlock::union_bounds(&[]), None);
+
+        let mut kept = words;
+        retain_confident_blocks(&mut kept, 0.5);
+        assert_eq!(kept.len(), 2);
+        assert!(kept.iter().all(|b| b.confidence != Some(0.3)));
+    }
+}
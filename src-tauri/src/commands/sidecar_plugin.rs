@@ -17,8 +17,10 @@
 //! 负责启动和管理 Sidecar 插件进程，通过 stdin/stdout 进行通信
 
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use std::path::PathBuf;
 use std::process::Stdio;
+use std::time::Duration;
 use tauri::{AppHandle, Emitter};
 use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
 use tokio::process::Command;
@@ -38,6 +40,18 @@ pub struct SidecarExecuteRequest {
     pub input: Option<String>,
     /// 是否为开发模式
     pub dev_mode: bool,
+    /// 会话 ID，用于前端关联流式输出；为空时自动生成
+    #[serde(default)]
+    pub session_id: Option<String>,
+    /// 超时时间（毫秒），超时后终止子进程
+    #[serde(default)]
+    pub timeout_ms: Option<u64>,
+    /// 额外的环境变量
+    #[serde(default)]
+    pub env: Option<HashMap<String, String>>,
+    /// 工作目录（相对路径基于插件目录），默认为插件目录
+    #[serde(default)]
+    pub working_dir: Option<String>,
 }
 
 /// Sidecar 进程输出事件
@@ -45,6 +59,10 @@ pub struct SidecarExecuteRequest {
 pub struct SidecarOutputEvent {
     /// 插件 ID
     pub plugin_id: String,
+    /// 本次执行的会话 ID
+    pub session_id: String,
+    /// 输出来源：stdout 或 stderr
+    pub stream: String,
     /// 输出类型：progress, result, error
     pub event_type: String,
     /// 输出数据（JSON 字符串）
@@ -54,17 +72,24 @@ pub struct SidecarOutputEvent {
 /// 执行 Sidecar 插件
 ///
 /// 启动外部进程，通过 stdin 发送输入，通过 stdout 接收输出
-/// 实时将输出事件发送到前端
+/// 实时将 stdout/stderr 逐行以 `sidecar-output` 事件发送到前端（按 session_id 区分），
+/// 设置了 `timeout_ms` 时超时会终止子进程并返回错误
 #[tauri::command]
 pub async fn execute_sidecar(
     app: AppHandle,
     request: SidecarExecuteRequest,
 ) -> Result<String, String> {
+    let session_id = request
+        .session_id
+        .clone()
+        .unwrap_or_else(|| uuid::Uuid::new_v4().to_string());
+
     log::info!(
-        "[SIDECAR] 开始执行插件: {}, 可执行文件: {}, 开发模式: {}",
+        "[SIDECAR] 开始执行插件: {}, 可执行文件: {}, 开发模式: {}, 会话: {}",
         request.plugin_id,
         request.executable_path,
-        request.dev_mode
+        request.dev_mode,
+        session_id
     );
 
     // 获取插件目录。优先使用前端加载器传入的真实安装/源码路径，
@@ -127,6 +152,23 @@ pub async fn execute_sidecar(
         plugin_data_dir.display()
     );
 
+    // 解析工作目录
+    let working_dir = match request.working_dir.as_deref() {
+        Some(dir) => {
+            let path = PathBuf::from(dir.replace('\\', "/"));
+            let path = if path.is_absolute() {
+                path
+            } else {
+                plugin_dir.join(path)
+            };
+            if !path.is_dir() {
+                return Err(format!("工作目录不存在: {}", path.display()));
+            }
+            path
+        }
+        None => plugin_dir.clone(),
+    };
+
     // 启动子进程
     let mut command = Command::new(&executable_full_path);
     command
@@ -134,8 +176,13 @@ pub async fn execute_sidecar(
         .stdin(Stdio::piped())
         .stdout(Stdio::piped())
         .stderr(Stdio::piped())
-        .current_dir(&plugin_dir)
-        .env(crate::utils::AIOHUB_PLUGIN_DATA_DIR_ENV, &plugin_data_dir);
+        .current_dir(&working_dir)
+        .kill_on_drop(true);
+    if let Some(env) = &request.env {
+        command.envs(env);
+    }
+    // 插件数据目录变量最后注入，不允许被覆盖
+    command.env(crate::utils::AIOHUB_PLUGIN_DATA_DIR_ENV, &plugin_data_dir);
     crate::utils::hide_child_process_window(&mut command);
 
     let mut child = command
@@ -172,12 +219,13 @@ pub async fn execute_sidecar(
             .flush()
             .await
             .map_err(|e| format!("刷新 stdin 失败: {}", e))?;
-        // 关闭 stdin 以通知子进程输入结束
-        drop(stdin);
     }
+    // 关闭 stdin 以通知子进程输入结束
+    drop(stdin);
 
     // 读取 stdout
     let plugin_id_clone = request.plugin_id.clone();
+    let session_id_clone = session_id.clone();
     let app_clone = app.clone();
     let stdout_handle = tokio::spawn(async move {
         let mut reader = BufReader::new(stdout);
@@ -199,6 +247,8 @@ pub async fn execute_sidecar(
             if let Ok(event_data) = serde_json::from_str::<serde_json::Value>(&line) {
                 let event = SidecarOutputEvent {
                     plugin_id: plugin_id_clone.clone(),
+                    session_id: session_id_clone.clone(),
+                    stream: "stdout".to_string(),
                     event_type: event_data
                         .get("type")
                         .and_then(|v| v.as_str())
@@ -218,6 +268,8 @@ pub async fn execute_sidecar(
                 // 非 JSON 输出，作为普通日志发送
                 let event = SidecarOutputEvent {
                     plugin_id: plugin_id_clone.clone(),
+                    session_id: session_id_clone.clone(),
+                    stream: "stdout".to_string(),
                     event_type: "log".to_string(),
                     data: line,
                 };
@@ -230,6 +282,7 @@ pub async fn execute_sidecar(
 
     // 读取 stderr
     let plugin_id_clone = request.plugin_id.clone();
+    let session_id_clone = session_id.clone();
     let app_clone = app.clone();
     let stderr_handle = tokio::spawn(async move {
         let mut reader = BufReader::new(stderr);
@@ -248,6 +301,8 @@ pub async fn execute_sidecar(
 
             let event = SidecarOutputEvent {
                 plugin_id: plugin_id_clone.clone(),
+                session_id: session_id_clone.clone(),
+                stream: "stderr".to_string(),
                 event_type: "error".to_string(),
                 data: line,
             };
@@ -256,11 +311,32 @@ pub async fn execute_sidecar(
         }
     });
 
-    // 等待进程结束
-    let status = child
-        .wait()
-        .await
-        .map_err(|e| format!("等待进程结束失败: {}", e))?;
+    // 等待进程结束（可选超时）
+    let status = match request.timeout_ms.filter(|ms| *ms > 0) {
+        Some(timeout_ms) => {
+            match tokio::time::timeout(Duration::from_millis(timeout_ms), child.wait()).await {
+                Ok(status) => status.map_err(|e| format!("等待进程结束失败: {}", e))?,
+                Err(_) => {
+                    log::warn!(
+                        "[SIDECAR] 插件 {} 执行超时 ({} ms)，终止进程",
+                        request.plugin_id,
+                        timeout_ms
+                    );
+                    if let Err(e) = child.kill().await {
+                        log::error!("[SIDECAR] 终止超时进程失败: {}", e);
+                    }
+                    // 孙进程可能仍持有管道，不再等待输出读取
+                    stdout_handle.abort();
+                    stderr_handle.abort();
+                    return Err(format!("进程执行超时（{} ms），已终止", timeout_ms));
+                }
+            }
+        }
+        None => child
+            .wait()
+            .await
+            .map_err(|e| format!("等待进程结束失败: {}", e))?,
+    };
 
     log::info!("[SIDECAR] 进程已结束，状态: {:?}", status);
 
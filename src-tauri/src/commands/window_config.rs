@@ -31,6 +31,12 @@ pub struct WindowConfig {
     pub height: f64,
     /// 是否最大化
     pub maximized: bool,
+    /// 是否置顶（旧配置中不存在时不做恢复）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub always_on_top: Option<bool>,
+    /// 窗口不透明度 0.1 ~ 1.0（未调节过时不保存）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub opacity: Option<f64>,
 }
 
 /// 获取配置文件路径
@@ -135,18 +141,23 @@ pub fn save_window_config_sync(app: &AppHandle, label: &str) -> Result<(), Strin
         return Ok(());
     }
 
-    // 创建配置对象
+    // 加载现有配置
+    let mut all_configs = load_all_configs(app).unwrap_or_default();
+
+    // 创建配置对象（置顶与不透明度只记录用户显式设置过的值，否则沿用旧配置，
+    // 未设置过置顶的窗口保持 None，由窗口创建流程决定默认行为）
     let config = WindowConfig {
         x: position.x,
         y: position.y,
         width: logical_width,
         height: logical_height,
         maximized,
+        always_on_top: super::window_manager::current_window_always_on_top(label)
+            .or_else(|| all_configs.get(label).and_then(|c| c.always_on_top)),
+        opacity: super::window_manager::current_window_opacity(label)
+            .or_else(|| all_configs.get(label).and_then(|c| c.opacity)),
     };
 
-    // 加载现有配置
-    let mut all_configs = load_all_configs(app).unwrap_or_default();
-
     // 更新或插入新配置
     all_configs.insert(label.to_string(), config.clone());
 
@@ -210,6 +221,18 @@ pub async fn apply_window_config(window: WebviewWindow) -> Result<bool, String>
             }
         }
 
+        // 恢复置顶与不透明度
+        if let Some(always_on_top) = config.always_on_top {
+            window
+                .set_always_on_top(always_on_top)
+                .map_err(|e| format!("设置窗口置顶失败: {}", e))?;
+        }
+        if let Some(opacity) = config.opacity {
+            if let Err(e) = super::window_manager::apply_window_opacity(&window, opacity) {
+                log::warn!("[WINDOW_CONFIG] 恢复窗口不透明度失败: {}", e);
+            }
+        }
+
         Ok(true)
     } else {
         log::info!(
@@ -220,6 +243,11 @@ pub async fn apply_window_config(window: WebviewWindow) -> Result<bool, String>
     }
 }
 
+/// 读取指定窗口保存的配置
+pub fn get_saved_window_config(app: &AppHandle, label: &str) -> Option<WindowConfig> {
+    load_all_configs(app).ok()?.remove(label)
+}
+
 /// 删除指定窗口的配置
 #[tauri::command]
 pub async fn delete_window_config(app: AppHandle, label: String) -> Result<(), String> {
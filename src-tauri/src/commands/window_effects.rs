@@ -16,98 +16,158 @@ use std::fs;
 use std::path::Path;
 
 #[cfg(target_os = "windows")]
-use window_vibrancy::apply_acrylic;
-#[cfg(target_os = "windows")]
-use window_vibrancy::apply_blur;
-#[cfg(target_os = "windows")]
-use window_vibrancy::apply_mica;
-#[cfg(target_os = "windows")]
-use window_vibrancy::clear_acrylic;
-#[cfg(target_os = "windows")]
-use window_vibrancy::clear_blur;
-#[cfg(target_os = "windows")]
-use window_vibrancy::clear_mica;
+use window_vibrancy::{
+    apply_acrylic, apply_blur, apply_mica, apply_tabbed, clear_acrylic, clear_blur, clear_mica,
+    clear_tabbed,
+};
 
 #[cfg(target_os = "macos")]
 use window_vibrancy::{apply_vibrancy, clear_vibrancy, NSVisualEffectMaterial};
 
-#[tauri::command]
-pub async fn apply_window_effect(_window: tauri::Window, effect: &str) -> Result<(), String> {
+/// Windows 10 1809，acrylic / blur 所需的最低版本
+#[cfg_attr(not(target_os = "windows"), allow(dead_code))]
+const WIN10_1809_BUILD: u32 = 17763;
+/// Windows 11 首个正式版，mica 所需的最低版本
+#[cfg_attr(not(target_os = "windows"), allow(dead_code))]
+const WIN11_BUILD: u32 = 22000;
+/// 支持 DWMSBT_TABBEDWINDOW (mica-alt) 的最低版本
+#[cfg_attr(not(target_os = "windows"), allow(dead_code))]
+const WIN11_TABBED_BUILD: u32 = 22523;
+
+/// Windows 上某特效的回退链（按优先级排列）
+#[cfg_attr(not(target_os = "windows"), allow(dead_code))]
+fn windows_fallback_chain(effect: &str) -> Option<&'static [&'static str]> {
     match effect {
-        "blur" => {
-            #[cfg(target_os = "macos")]
-            {
-                apply_vibrancy(&_window, NSVisualEffectMaterial::HudWindow, None, None)
-                    .map_err(|e| format!("Failed to apply blur effect: {}", e))?;
-            }
-            #[cfg(target_os = "windows")]
-            {
-                apply_blur(&_window, Some((18, 18, 18, 125)))
-                    .map_err(|e| format!("Failed to apply blur effect: {}", e))?;
-            }
-            #[cfg(target_os = "linux")]
-            {
-                return Err("Blur effect is not supported on Linux".to_string());
-            }
-        }
-        "acrylic" => {
-            #[cfg(target_os = "windows")]
-            {
-                apply_acrylic(&_window, Some((18, 18, 18, 125)))
-                    .map_err(|e| format!("Failed to apply acrylic effect: {}", e))?;
-            }
-            #[cfg(not(target_os = "windows"))]
-            {
-                return Err(format!("Acrylic effect is only supported on Windows"));
-            }
-        }
-        "mica" => {
-            #[cfg(target_os = "windows")]
-            {
-                apply_mica(&_window, None)
-                    .map_err(|e| format!("Failed to apply mica effect: {}", e))?;
-            }
-            #[cfg(not(target_os = "windows"))]
-            {
-                return Err(format!("Mica effect is only supported on Windows"));
-            }
+        "mica-alt" => Some(&["mica-alt", "mica", "acrylic", "blur"]),
+        "mica" => Some(&["mica", "acrylic", "blur"]),
+        "acrylic" => Some(&["acrylic", "blur"]),
+        "blur" => Some(&["blur"]),
+        _ => None,
+    }
+}
+
+/// 判断当前系统版本是否支持该 Windows 特效
+#[cfg_attr(not(target_os = "windows"), allow(dead_code))]
+fn is_windows_effect_supported(effect: &str, build: u32) -> bool {
+    match effect {
+        "mica-alt" => build >= WIN11_TABBED_BUILD,
+        "mica" => build >= WIN11_BUILD,
+        "acrylic" | "blur" => build >= WIN10_1809_BUILD,
+        _ => false,
+    }
+}
+
+/// 根据系统版本筛出可尝试的特效列表
+#[cfg_attr(not(target_os = "windows"), allow(dead_code))]
+fn resolve_windows_candidates(effect: &str, build: u32) -> Result<Vec<&'static str>, String> {
+    let chain = windows_fallback_chain(effect).ok_or_else(|| {
+        format!(
+            "Effect '{}' is not supported on Windows (available: mica, mica-alt, acrylic, blur)",
+            effect
+        )
+    })?;
+    let candidates: Vec<&'static str> = chain
+        .iter()
+        .copied()
+        .filter(|e| is_windows_effect_supported(e, build))
+        .collect();
+    if candidates.is_empty() {
+        return Err(format!(
+            "Effect '{}' is not supported on this Windows version (build {})",
+            effect, build
+        ));
+    }
+    Ok(candidates)
+}
+
+/// 读取 Windows 构建号
+#[cfg(target_os = "windows")]
+fn windows_build_number() -> Option<u32> {
+    sysinfo::System::kernel_version().and_then(|v| v.trim().parse().ok())
+}
+
+/// macOS 上各特效对应的 vibrancy 材质
+#[cfg(target_os = "macos")]
+fn macos_material(effect: &str) -> Option<NSVisualEffectMaterial> {
+    match effect {
+        "blur" => Some(NSVisualEffectMaterial::HudWindow),
+        "acrylic" => Some(NSVisualEffectMaterial::UnderWindowBackground),
+        "mica" => Some(NSVisualEffectMaterial::Sidebar),
+        "mica-alt" => Some(NSVisualEffectMaterial::HeaderView),
+        "vibrancy" => Some(NSVisualEffectMaterial::WindowBackground),
+        _ => None,
+    }
+}
+
+/// 应用窗口特效，返回实际生效的特效名
+///
+/// Windows 上会按系统版本自动回退（mica-alt → mica → acrylic → blur），
+/// macOS 上映射为对应的 vibrancy 材质，Linux 暂不支持。
+#[tauri::command]
+pub async fn apply_window_effect(_window: tauri::Window, effect: &str) -> Result<String, String> {
+    if effect == "none" {
+        // 清除所有效果
+        #[cfg(target_os = "windows")]
+        {
+            // 尝试清除所有可能的效果
+            let _ = clear_blur(&_window);
+            let _ = clear_acrylic(&_window);
+            let _ = clear_mica(&_window);
+            let _ = clear_tabbed(&_window);
         }
-        "vibrancy" => {
-            #[cfg(target_os = "macos")]
-            {
-                apply_vibrancy(
-                    &_window,
-                    NSVisualEffectMaterial::WindowBackground,
-                    None,
-                    None,
-                )
-                .map_err(|e| format!("Failed to apply vibrancy effect: {}", e))?;
-            }
-            #[cfg(not(target_os = "macos"))]
-            {
-                return Err("Vibrancy effect is only supported on macOS".to_string());
-            }
+        #[cfg(target_os = "macos")]
+        {
+            clear_vibrancy(&_window)
+                .map_err(|e| format!("Failed to clear vibrancy effect: {}", e))?;
         }
-        "none" => {
-            // 清除所有效果
-            #[cfg(target_os = "windows")]
-            {
-                // 尝试清除所有可能的效果
-                let _ = clear_blur(&_window);
-                let _ = clear_acrylic(&_window);
-                let _ = clear_mica(&_window);
-            }
-            #[cfg(target_os = "macos")]
-            {
-                clear_vibrancy(&_window)
-                    .map_err(|e| format!("Failed to clear vibrancy effect: {}", e))?;
+        return Ok("none".to_string());
+    }
+
+    #[cfg(target_os = "windows")]
+    {
+        let build =
+            windows_build_number().ok_or_else(|| "Failed to detect Windows version".to_string())?;
+        let candidates = resolve_windows_candidates(effect, build)?;
+
+        let mut last_error = String::new();
+        for candidate in candidates {
+            let result = match candidate {
+                "mica-alt" => apply_tabbed(&_window, None),
+                "mica" => apply_mica(&_window, None),
+                "acrylic" => apply_acrylic(&_window, Some((18, 18, 18, 125))),
+                _ => apply_blur(&_window, Some((18, 18, 18, 125))),
+            };
+            match result {
+                Ok(()) => {
+                    if candidate != effect {
+                        log::info!(
+                            "[WINDOW_EFFECT] '{}' 不可用，已回退到 '{}' (build {})",
+                            effect,
+                            candidate,
+                            build
+                        );
+                    }
+                    return Ok(candidate.to_string());
+                }
+                Err(e) => last_error = format!("Failed to apply {} effect: {}", candidate, e),
             }
         }
-        _ => {
-            return Err(format!("Unknown effect: {}", effect));
-        }
+        Err(last_error)
+    }
+
+    #[cfg(target_os = "macos")]
+    {
+        let material =
+            macos_material(effect).ok_or_else(|| format!("Unknown effect: {}", effect))?;
+        apply_vibrancy(&_window, material, None, None)
+            .map_err(|e| format!("Failed to apply {} effect: {}", effect, e))?;
+        Ok(effect.to_string())
+    }
+
+    #[cfg(target_os = "linux")]
+    {
+        Err(format!("Effect '{}' is not supported on Linux", effect))
     }
-    Ok(())
 }
 
 #[tauri::command]
@@ -143,3 +203,29 @@ pub fn list_directory_images(directory: String) -> Result<Vec<String>, String> {
         Err(e) => Err(format!("Failed to read directory: {}", e)),
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_resolve_windows_candidates() {
+        // Windows 11 新版本：全部可用
+        assert_eq!(
+            resolve_windows_candidates("mica-alt", 22631).unwrap(),
+            vec!["mica-alt", "mica", "acrylic", "blur"]
+        );
+        // Windows 11 早期版本：mica-alt 回退到 mica
+        assert_eq!(
+            resolve_windows_candidates("mica-alt", 22000).unwrap()[0],
+            "mica"
+        );
+        // Windows 10：mica 回退到 acrylic
+        assert_eq!(
+            resolve_windows_candidates("mica", 19045).unwrap(),
+            vec!["acrylic", "blur"]
+        );
+        assert!(resolve_windows_candidates("blur", 10240).is_err());
+        assert!(resolve_windows_candidates("vibrancy", 22631).is_err());
+    }
+}
@@ -55,6 +55,106 @@ struct DragSessionState {
     handle_offset_y: f64,
     /// 上次更新窗口位置的时间（用于节流）
     last_update_time: Instant,
+    /// 当前的边缘吸附目标（未靠近边缘时为 None）
+    snap_target: Option<SnapTarget>,
+}
+
+/// 边缘吸附区
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SnapZone {
+    Left,
+    Right,
+    Maximize,
+}
+
+/// 吸附目标：吸附区 + 所在显示器的工作区（物理像素）
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct SnapTarget {
+    zone: SnapZone,
+    area: ScreenRect,
+}
+
+/// 屏幕上的矩形区域（物理像素），用于显示器边界、工作区与窗口外框
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct ScreenRect {
+    x: i32,
+    y: i32,
+    width: u32,
+    height: u32,
+}
+
+/// 根据鼠标位置判断吸附区，顶部优先于左右两侧
+fn detect_snap_zone(x: f64, y: f64, area: &ScreenRect, threshold: f64) -> Option<SnapZone> {
+    let left = area.x as f64;
+    let top = area.y as f64;
+    let right = left + area.width as f64;
+    let bottom = top + area.height as f64;
+    if x < left || x >= right || y < top || y >= bottom {
+        return None;
+    }
+
+    if y - top <= threshold {
+        Some(SnapZone::Maximize)
+    } else if x - left <= threshold {
+        Some(SnapZone::Left)
+    } else if right - x <= threshold {
+        Some(SnapZone::Right)
+    } else {
+        None
+    }
+}
+
+/// 查询鼠标所在显示器的工作区，查不到时退回预览窗口当前所在的显示器
+fn monitor_work_area(window: &tauri::WebviewWindow, x: f64, y: f64) -> Option<ScreenRect> {
+    let monitor = match window.monitor_from_point(x, y) {
+        Ok(Some(monitor)) => monitor,
+        _ => window.current_monitor().ok().flatten()?,
+    };
+    Some(monitor_work_area_rect(&monitor))
+}
+
+/// 显示器工作区（不含任务栏 / 菜单栏）
+fn monitor_work_area_rect(monitor: &tauri::Monitor) -> ScreenRect {
+    let rect = monitor.work_area();
+    ScreenRect {
+        x: rect.position.x,
+        y: rect.position.y,
+        width: rect.size.width,
+        height: rect.size.height,
+    }
+}
+
+/// 显示器完整边界
+fn monitor_bounds_rect(monitor: &tauri::Monitor) -> ScreenRect {
+    ScreenRect {
+        x: monitor.position().x,
+        y: monitor.position().y,
+        width: monitor.size().width,
+        height: monitor.size().height,
+    }
+}
+
+/// 按吸附目标调整窗口位置与尺寸
+fn apply_snap_target(window: &tauri::WebviewWindow, target: &SnapTarget) -> Result<(), String> {
+    let area = &target.area;
+    match target.zone {
+        SnapZone::Maximize => window.maximize().map_err(|e| e.to_string()),
+        SnapZone::Left | SnapZone::Right => {
+            let half_width = area.width / 2;
+            let x = if target.zone == SnapZone::Left {
+                area.x
+            } else {
+                area.x + half_width as i32
+            };
+            window
+                .set_size(tauri::PhysicalSize::new(half_width, area.height))
+                .map_err(|e| e.to_string())?;
+            window
+                .set_position(PhysicalPosition::new(x, area.y))
+                .map_err(|e| e.to_string())
+        }
+    }
 }
 
 /// 全局拖拽会话（同一时刻只有一个）
@@ -64,90 +164,128 @@ static DRAG_SESSION: once_cell::sync::Lazy<Arc<Mutex<Option<DragSessionState>>>>
 /// 分离阈值（与前端保持一致）
 const DETACH_THRESHOLD: f64 = 50.0;
 
+/// 边缘吸附的触发距离（物理像素）
+const SNAP_EDGE_THRESHOLD: f64 = 24.0;
+
 /// 窗口位置更新的最小时间间隔（毫秒）
 /// 设置为约 8ms，即最高 120Hz 更新频率
 const UPDATE_THROTTLE_MS: u64 = 8;
 
-/// 初始化全局鼠标监听器
-pub fn init_global_mouse_listener() {
-    thread::spawn(move || {
-        let session_arc = DRAG_SESSION.clone();
+/// 拖拽期间鼠标释放：延迟片刻后结束会话（由 rdev 监听器与 macOS 轮询共用）
+fn handle_drag_button_release(session_arc: Arc<Mutex<Option<DragSessionState>>>) {
+    let app_handle_opt = {
+        let session_opt = session_arc.lock().unwrap();
+        session_opt.as_ref().map(|s| s.app_handle.clone())
+    };
 
-        // 提取通用逻辑以避免代码重复
-        let handle_button_release = |session_arc: Arc<Mutex<Option<DragSessionState>>>| {
-            let app_handle_opt = {
-                let session_opt = session_arc.lock().unwrap();
-                session_opt.as_ref().map(|s| s.app_handle.clone())
-            };
+    if let Some(app_handle) = app_handle_opt {
+        log::debug!("[DRAG] 检测到全局鼠标释放，尝试结束会话");
+        thread::spawn(move || {
+            let rt = tokio::runtime::Builder::new_current_thread()
+                .enable_all()
+                .build()
+                .unwrap();
+            rt.block_on(async {
+                tokio::time::sleep(Duration::from_millis(100)).await;
+                if let Err(e) = end_drag_session(app_handle).await {
+                    if !e.contains("没有活动的拖拽会话") {
+                        log::warn!("[DRAG] 自动结束会话失败: {}", e);
+                    }
+                }
+            });
+        });
+    }
+}
 
-            if let Some(app_handle) = app_handle_opt {
-                log::debug!("[DRAG] 检测到全局鼠标释放，尝试结束会话");
-                thread::spawn(move || {
-                    let rt = tokio::runtime::Builder::new_current_thread()
-                        .enable_all()
-                        .build()
-                        .unwrap();
-                    rt.block_on(async {
-                        tokio::time::sleep(Duration::from_millis(100)).await;
-                        if let Err(e) = end_drag_session(app_handle).await {
-                            if !e.contains("没有活动的拖拽会话") {
-                                log::warn!("[DRAG] 自动结束会话失败: {}", e);
-                            }
-                        }
-                    });
-                });
+/// 拖拽期间鼠标移动：更新会话状态并让预览窗口跟随（由 rdev 监听器与 macOS 轮询共用）
+fn handle_drag_mouse_move(session_arc: Arc<Mutex<Option<DragSessionState>>>, x: f64, y: f64) {
+    let update_data = {
+        let mut session_opt = session_arc.lock().unwrap();
+        if let Some(session) = session_opt.as_mut() {
+            session.current_x = x;
+            session.current_y = y;
+
+            let delta_x = x - session.start_x;
+            let delta_y = y - session.start_y;
+            let distance = (delta_x * delta_x + delta_y * delta_y).sqrt();
+            session.can_detach = distance >= DETACH_THRESHOLD;
+
+            let now = Instant::now();
+            let should_update = now.duration_since(session.last_update_time).as_millis()
+                >= UPDATE_THROTTLE_MS as u128;
+
+            if should_update {
+                session.last_update_time = now;
+                Some((
+                    session.app_handle.clone(),
+                    session.preview_window_label.clone(),
+                    session.can_detach,
+                    session.handle_offset_x,
+                    session.handle_offset_y,
+                    // 禁用缩放的窗口保持原尺寸，不参与吸附
+                    !session.config.disable_native_resize,
+                ))
+            } else {
+                None
             }
-        };
+        } else {
+            None
+        }
+    };
 
-        let handle_mouse_move = |session_arc: Arc<Mutex<Option<DragSessionState>>>,
-                                 x: f64,
-                                 y: f64| {
-            let update_data = {
+    if let Some((
+        app_handle,
+        preview_label,
+        can_detach,
+        handle_offset_x,
+        handle_offset_y,
+        snappable,
+    )) = update_data
+    {
+        if let Some(window) = app_handle.get_webview_window(&preview_label) {
+            let physical_x = (x - handle_offset_x) as i32;
+            let physical_y = (y - handle_offset_y) as i32;
+            let _ = window.set_position(PhysicalPosition::new(physical_x, physical_y));
+            let _ = window.emit(
+                "detach-status-update",
+                serde_json::json!({ "canDetach": can_detach }),
+            );
+
+            // 检测是否靠近显示器工作区边缘
+            let snap_target = if can_detach && snappable {
+                monitor_work_area(&window, x, y).and_then(|area| {
+                    detect_snap_zone(x, y, &area, SNAP_EDGE_THRESHOLD)
+                        .map(|zone| SnapTarget { zone, area })
+                })
+            } else {
+                None
+            };
+
+            // 吸附区变化时才通知预览窗口
+            let changed = {
                 let mut session_opt = session_arc.lock().unwrap();
-                if let Some(session) = session_opt.as_mut() {
-                    session.current_x = x;
-                    session.current_y = y;
-
-                    let delta_x = x - session.start_x;
-                    let delta_y = y - session.start_y;
-                    let distance = (delta_x * delta_x + delta_y * delta_y).sqrt();
-                    session.can_detach = distance >= DETACH_THRESHOLD;
-
-                    let now = Instant::now();
-                    let should_update = now.duration_since(session.last_update_time).as_millis()
-                        >= UPDATE_THROTTLE_MS as u128;
-
-                    if should_update {
-                        session.last_update_time = now;
-                        Some((
-                            session.app_handle.clone(),
-                            session.preview_window_label.clone(),
-                            session.can_detach,
-                            session.handle_offset_x,
-                            session.handle_offset_y,
-                        ))
-                    } else {
-                        None
+                match session_opt.as_mut() {
+                    Some(session) if session.snap_target != snap_target => {
+                        session.snap_target = snap_target;
+                        true
                     }
-                } else {
-                    None
+                    _ => false,
                 }
             };
-
-            if let Some((app_handle, preview_label, can_detach, handle_offset_x, handle_offset_y)) =
-                update_data
-            {
-                if let Some(window) = app_handle.get_webview_window(&preview_label) {
-                    let physical_x = (x - handle_offset_x) as i32;
-                    let physical_y = (y - handle_offset_y) as i32;
-                    let _ = window.set_position(PhysicalPosition::new(physical_x, physical_y));
-                    let _ = window.emit(
-                        "detach-status-update",
-                        serde_json::json!({ "canDetach": can_detach }),
-                    );
-                }
+            if changed {
+                let _ = window.emit(
+                    "detach-snap-update",
+                    serde_json::json!({ "zone": snap_target.map(|t| t.zone) }),
+                );
             }
-        };
+        }
+    }
+}
+
+/// 初始化全局鼠标监听器
+pub fn init_global_mouse_listener() {
+    thread::spawn(move || {
+        let session_arc = DRAG_SESSION.clone();
 
         // 根据操作系统定义不同的回调
         let callback = {
@@ -157,9 +295,11 @@ pub fn init_global_mouse_listener() {
                 {
                     // 在 macOS 上，采取最严格的策略，只处理鼠标事件
                     match event.event_type {
-                        EventType::ButtonRelease(_) => handle_button_release(session_arc.clone()),
+                        EventType::ButtonRelease(_) => {
+                            handle_drag_button_release(session_arc.clone())
+                        }
                         EventType::MouseMove { x, y } => {
-                            handle_mouse_move(session_arc.clone(), x, y)
+                            handle_drag_mouse_move(session_arc.clone(), x, y)
                         }
                         _ => {
                             // 忽略所有其他事件，特别是键盘事件，以防止崩溃
@@ -170,9 +310,11 @@ pub fn init_global_mouse_listener() {
                 {
                     // 在其他系统上，可以稍微放宽，但仍然忽略键盘事件
                     match event.event_type {
-                        EventType::ButtonRelease(_) => handle_button_release(session_arc.clone()),
+                        EventType::ButtonRelease(_) => {
+                            handle_drag_button_release(session_arc.clone())
+                        }
                         EventType::MouseMove { x, y } => {
-                            handle_mouse_move(session_arc.clone(), x, y)
+                            handle_drag_mouse_move(session_arc.clone(), x, y)
                         }
                         EventType::KeyPress(_) | EventType::KeyRelease(_) => {
                             // 显式忽略键盘事件
@@ -190,7 +332,48 @@ pub fn init_global_mouse_listener() {
     });
 }
 
+/// macOS 上的拖拽跟随：会话期间轮询光标位置与鼠标按键状态
+///
+/// rdev 在 macOS 上依赖辅助功能权限，与系统输入监听冲突时会直接崩溃。
+/// 这里改用 NSEvent 的类方法查询按键状态（无需额外权限），光标位置取自 tauri，
+/// 与 set_position 使用同一套物理坐标；移动与释放的判定复用 rdev 路径的处理函数。
+#[cfg(target_os = "macos")]
+#[allow(deprecated)] // cocoa 整体已标记弃用，这里只用到 NSEvent 的两个类方法
+fn spawn_macos_drag_poller(app: AppHandle) {
+    use cocoa::appkit::NSEvent;
+    use cocoa::base::{id, nil};
+
+    thread::spawn(move || {
+        let session_arc = DRAG_SESSION.clone();
+        log::debug!("[DRAG] macOS 拖拽轮询已启动");
+
+        loop {
+            thread::sleep(Duration::from_millis(UPDATE_THROTTLE_MS));
+
+            // 会话已结束（如 ESC 取消）
+            if session_arc.lock().unwrap().is_none() {
+                break;
+            }
+
+            // pressedMouseButtons 的第 0 位为左键
+            let left_pressed = unsafe { <id as NSEvent>::pressedMouseButtons(nil) } & 1 != 0;
+            if !left_pressed {
+                handle_drag_button_release(session_arc.clone());
+                break;
+            }
+
+            if let Ok(position) = app.cursor_position() {
+                handle_drag_mouse_move(session_arc.clone(), position.x, position.y);
+            }
+        }
+
+        log::debug!("[DRAG] macOS 拖拽轮询已结束");
+    });
+}
+
 /// 开始一个基于全局鼠标监听的拖拽会话
+///
+/// 非 macOS 平台由 rdev 全局监听驱动，macOS 平台由会话期间的 NSEvent 轮询驱动
 #[tauri::command]
 pub async fn start_drag_session(app: AppHandle, config: DetachableConfig) -> Result<(), String> {
     // 检查并清理可能卡住的旧会话
@@ -266,6 +449,7 @@ pub async fn start_drag_session(app: AppHandle, config: DetachableConfig) -> Res
         handle_offset_x: physical_handle_offset_x,
         handle_offset_y: physical_handle_offset_y,
         last_update_time: now,
+        snap_target: None,
     };
 
     // 保存会话状态
@@ -274,6 +458,9 @@ pub async fn start_drag_session(app: AppHandle, config: DetachableConfig) -> Res
         *session = Some(session_state);
     }
 
+    #[cfg(target_os = "macos")]
+    spawn_macos_drag_poller(app.clone());
+
     Ok(())
 }
 /// 结束拖拽会话
@@ -304,8 +491,14 @@ pub async fn end_drag_session(app: AppHandle) -> Result<bool, String> {
         let should_detach = state.can_detach;
 
         if should_detach {
-            // 固化窗口
-            finalize_window_internal(&app, &preview_window_label, &state.config).await?;
+            // 固化窗口（释放时位于吸附区则按吸附区布局）
+            finalize_window_internal(
+                &app,
+                &preview_window_label,
+                &state.config,
+                state.snap_target.as_ref(),
+            )
+            .await?;
             Ok(true)
         } else {
             // 取消分离，关闭预览窗口
@@ -391,12 +584,77 @@ struct DetachSession {
     config: DetachableConfig,
     /// 预览窗口的标签
     preview_window_label: String,
+    /// 会话创建时间，用于清理前端未调用 finalize 的僵尸会话
+    created_at: Instant,
 }
 
 /// 全局分离会话管理器
 static DETACH_SESSIONS: once_cell::sync::Lazy<Arc<Mutex<HashMap<String, DetachSession>>>> =
     once_cell::sync::Lazy::new(|| Arc::new(Mutex::new(HashMap::new())));
 
+/// 分离会话超过该时长仍未固化或取消，即视为卡住
+const DETACH_SESSION_TIMEOUT: Duration = Duration::from_secs(30);
+/// 后台巡检分离会话的间隔
+const DETACH_SESSION_REAP_INTERVAL: Duration = Duration::from_secs(15);
+
+/// 找出已超时的分离会话 ID
+fn expired_detach_session_ids(
+    sessions: &HashMap<String, DetachSession>,
+    now: Instant,
+    timeout: Duration,
+) -> Vec<String> {
+    sessions
+        .iter()
+        .filter(|(_, session)| now.saturating_duration_since(session.created_at) > timeout)
+        .map(|(id, _)| id.clone())
+        .collect()
+}
+
+/// 清理超时的分离会话：从 map 中移除并关闭残留的预览窗口
+///
+/// 返回被清理的会话数量
+pub fn cleanup_stale_detach_sessions(app: &AppHandle) -> usize {
+    // 先在锁内摘出超时会话，关闭窗口时不持有锁
+    let stale: Vec<DetachSession> = {
+        let mut sessions = DETACH_SESSIONS.lock().unwrap();
+        expired_detach_session_ids(&sessions, Instant::now(), DETACH_SESSION_TIMEOUT)
+            .into_iter()
+            .filter_map(|id| sessions.remove(&id))
+            .collect()
+    };
+
+    for session in &stale {
+        log::warn!(
+            "[DETACH] 会话 {} 超时未固化，强制清理预览窗口",
+            session.preview_window_label
+        );
+        if let Some(window) = app.get_webview_window(&session.preview_window_label) {
+            if let Err(e) = window.close() {
+                log::error!(
+                    "[DETACH] 关闭残留预览窗口 {} 失败: {}",
+                    session.preview_window_label,
+                    e
+                );
+            }
+        }
+    }
+
+    stale.len()
+}
+
+/// 启动后台任务，定期清理卡住的分离会话
+pub fn spawn_detach_session_reaper(app: AppHandle) {
+    tauri::async_runtime::spawn(async move {
+        loop {
+            sleep(DETACH_SESSION_REAP_INTERVAL).await;
+            let cleaned = cleanup_stale_detach_sessions(&app);
+            if cleaned > 0 {
+                log::info!("[DETACH] 已清理 {} 个超时分离会话", cleaned);
+            }
+        }
+    });
+}
+
 /// 辅助函数：将驼峰命名转换为短横线命名
 fn camel_to_kebab(s: &str) -> String {
     let mut result = String::new();
@@ -517,6 +775,9 @@ pub async fn begin_detach_session(
     app: AppHandle,
     config: DetachableConfig,
 ) -> Result<String, String> {
+    // 新会话开始前先清理卡住的旧会话，避免残留的隐形预览窗口
+    cleanup_stale_detach_sessions(&app);
+
     // 使用固定标签以支持窗口状态记忆
     let preview_label = format!("detached-{}", &config.id);
     let session_id = preview_label.clone();
@@ -534,6 +795,7 @@ pub async fn begin_detach_session(
     let session = DetachSession {
         config,
         preview_window_label: preview_label,
+        created_at: Instant::now(),
     };
 
     {
@@ -609,10 +871,14 @@ static FINALIZED_DETACHED_WINDOWS: once_cell::sync::Lazy<
 > = once_cell::sync::Lazy::new(|| Arc::new(Mutex::new(HashMap::new())));
 
 /// 辅助函数：固化一个窗口
+///
+/// # 参数
+/// * `snap_target` - 拖拽释放时的吸附目标，存在时把窗口设为左半屏/右半屏/最大化
 async fn finalize_window_internal(
     app: &AppHandle,
     label: &str,
     config: &DetachableConfig,
+    snap_target: Option<&SnapTarget>,
 ) -> Result<(), String> {
     let window = app
         .get_webview_window(label)
@@ -633,8 +899,10 @@ async fn finalize_window_internal(
         window.set_shadow(true).map_err(|e| e.to_string())?;
     }
 
-    // 只对组件类型窗口默认置顶
-    if config.r#type == "component" {
+    // 只对组件类型窗口默认置顶（用户手动切换过时以保存的配置为准）
+    let saved_always_on_top = crate::commands::window_config::get_saved_window_config(app, label)
+        .and_then(|c| c.always_on_top);
+    if config.r#type == "component" && saved_always_on_top.is_none() {
         window.set_always_on_top(true).map_err(|e| e.to_string())?;
     }
 
@@ -643,6 +911,13 @@ async fn finalize_window_internal(
         window.set_resizable(false).map_err(|e| e.to_string())?;
     }
 
+    if let Some(target) = snap_target {
+        log::info!("[DRAG] 窗口 {} 吸附到 {:?}", label, target.zone);
+        if let Err(e) = apply_snap_target(&window, target) {
+            log::warn!("[DRAG] 应用吸附布局失败: {}", e);
+        }
+    }
+
     // 通知前端视图更新 (e.g., to hide preview-only elements)
     window
         .emit("finalize-component-view", ())
@@ -703,7 +978,7 @@ pub async fn finalize_detach_session(
 
         if should_detach {
             log::info!("[DETACH] 会话 {} 已固化", session_id);
-            finalize_window_internal(&app, &preview_window_label, &session.config).await?;
+            finalize_window_internal(&app, &preview_window_label, &session.config, None).await?;
         } else {
             log::info!("[DETACH] 会话 {} 已取消", session_id);
             preview_window.close().map_err(|e| e.to_string())?;
@@ -751,7 +1026,9 @@ pub async fn create_tool_window(app: AppHandle, config: WindowConfig) -> Result<
             .title(&config.title)
             .inner_size(config.width, config.height)
             .min_inner_size(400.0, 300.0)
-            .decorations(false);
+            .decorations(false)
+            // 先隐藏，恢复历史尺寸位置后再显示，避免窗口闪跳
+            .visible(false);
 
     #[cfg(target_os = "macos")]
     {
@@ -763,7 +1040,26 @@ pub async fn create_tool_window(app: AppHandle, config: WindowConfig) -> Result<
         builder = builder.transparent(true);
     }
 
-    let _window = builder.build().map_err(|e| e.to_string())?;
+    let window = builder.build().map_err(|e| e.to_string())?;
+
+    // 恢复该工具上次关闭时保存的尺寸与位置（关闭时由 save_window_config_sync 保存）
+    let restored = match crate::commands::window_config::apply_window_config(window.clone()).await {
+        Ok(restored) => restored,
+        Err(e) => {
+            log::error!("[WINDOW_CONFIG] 应用窗口配置失败: {}", e);
+            false
+        }
+    };
+    if restored {
+        // 保存的位置可能位于已断开的显示器上
+        if let Err(e) = ensure_window_visible(app.clone(), config.label.clone()).await {
+            log::warn!("[WINDOW_CONFIG] 检查窗口可见性失败: {}", e);
+        }
+    } else {
+        // 首次打开：使用传入的默认尺寸并居中
+        window.center().map_err(|e| e.to_string())?;
+    }
+    window.show().map_err(|e| e.to_string())?;
 
     let detachable_config = DetachableConfig {
         id: config.label.clone(),
@@ -779,7 +1075,7 @@ pub async fn create_tool_window(app: AppHandle, config: WindowConfig) -> Result<
         disable_drag_drop_handler: false,
     };
 
-    finalize_window_internal(&app, &config.label, &detachable_config).await
+    finalize_window_internal(&app, &config.label, &detachable_config, None).await
 }
 
 /// 关闭分离的窗口（重新附加）
@@ -851,103 +1147,205 @@ pub async fn set_window_position(
 }
 
 /// 检查窗口位置是否在屏幕内，如果不在则拉回
+///
+/// 窗口与任意一个可用显示器有足够交集即视为可见，不做调整；
+/// 完全不可见（如副屏已拔掉）时才限制到主显示器的工作区内
 #[tauri::command]
 pub async fn ensure_window_visible(app: AppHandle, label: String) -> Result<bool, String> {
-    if let Some(window) = app.get_webview_window(&label) {
-        // 如果窗口已最大化，跳过位置调整
-        // 在 Windows 上，调用 set_position 会自动取消最大化状态
-        if window.is_maximized().map_err(|e| e.to_string())? {
-            return Ok(false);
-        }
+    let window = app
+        .get_webview_window(&label)
+        .ok_or_else(|| format!("Window '{}' not found", label))?;
 
-        if let Some(monitor) = window.current_monitor().map_err(|e| e.to_string())? {
-            let position = window.outer_position().map_err(|e| e.to_string())?;
-            let size = window.outer_size().map_err(|e| e.to_string())?;
-            let scale_factor = monitor.scale_factor();
+    // 如果窗口已最大化，跳过位置调整
+    // 在 Windows 上，调用 set_position 会自动取消最大化状态
+    if window.is_maximized().map_err(|e| e.to_string())? {
+        return Ok(false);
+    }
+
+    let position = window.outer_position().map_err(|e| e.to_string())?;
+    let size = window.outer_size().map_err(|e| e.to_string())?;
+    let window_rect = ScreenRect {
+        x: position.x,
+        y: position.y,
+        width: size.width,
+        height: size.height,
+    };
 
-            let logical_x = position.x as f64 / scale_factor;
-            let logical_y = position.y as f64 / scale_factor;
-            let logical_width = size.width as f64 / scale_factor;
-            let logical_height = size.height as f64 / scale_factor;
+    let monitors = app.available_monitors().map_err(|e| e.to_string())?;
+    let bounds: Vec<ScreenRect> = monitors.iter().map(monitor_bounds_rect).collect();
+    if is_rect_visible(&window_rect, &bounds) {
+        return Ok(false);
+    }
 
-            let monitor_pos = monitor.position();
-            let monitor_size = monitor.size();
+    let primary = app
+        .primary_monitor()
+        .map_err(|e| e.to_string())?
+        .or_else(|| monitors.into_iter().next())
+        .ok_or_else(|| "No monitor found for the window".to_string())?;
+    let (x, y) = clamp_into_area(&window_rect, &monitor_work_area_rect(&primary));
 
-            let monitor_bounds = ScreenBounds {
-                pos_x: monitor_pos.x,
-                pos_y: monitor_pos.y,
-                width: monitor_size.width,
-                height: monitor_size.height,
-            };
+    log::info!(
+        "[WINDOW] 窗口 {} 不在任何显示器内 ({}, {})，移动到主显示器 ({}, {})",
+        label,
+        window_rect.x,
+        window_rect.y,
+        x,
+        y
+    );
+    window
+        .set_position(PhysicalPosition::new(x, y))
+        .map_err(|e| e.to_string())?;
 
-            let (clamped_x, clamped_y) = clamp_position_to_screen(
-                logical_x,
-                logical_y,
-                logical_width,
-                logical_height,
-                &monitor_bounds,
-                scale_factor,
-            );
+    Ok(true)
+}
 
-            let needs_adjustment =
-                (clamped_x - logical_x).abs() > 0.1 || (clamped_y - logical_y).abs() > 0.1;
+/// 窗口至少要有这么多像素（每个方向）落在某个显示器内才算可见
+const MIN_VISIBLE_PX: i64 = 60;
 
-            if needs_adjustment {
-                set_window_position(app.clone(), label, clamped_x, clamped_y, Some(false)).await?;
-            }
+/// 两个矩形交集的宽高，不相交时为 0
+fn overlap_size(a: &ScreenRect, b: &ScreenRect) -> (i64, i64) {
+    let span = |a_start: i32, a_len: u32, b_start: i32, b_len: u32| {
+        let start = (a_start as i64).max(b_start as i64);
+        let end = (a_start as i64 + a_len as i64).min(b_start as i64 + b_len as i64);
+        (end - start).max(0)
+    };
+    (
+        span(a.x, a.width, b.x, b.width),
+        span(a.y, a.height, b.y, b.height),
+    )
+}
 
-            Ok(needs_adjustment)
-        } else {
-            Err("No monitor found for the window".to_string())
+/// 窗口与任意显示器有足够交集即视为可见
+fn is_rect_visible(window: &ScreenRect, monitors: &[ScreenRect]) -> bool {
+    let need_width = MIN_VISIBLE_PX.min(window.width as i64);
+    let need_height = MIN_VISIBLE_PX.min(window.height as i64);
+    monitors.iter().any(|monitor| {
+        let (width, height) = overlap_size(window, monitor);
+        width >= need_width && height >= need_height
+    })
+}
+
+/// 把窗口左上角限制到区域内，使窗口尽量完整显示；窗口比区域大时贴左上角
+fn clamp_into_area(window: &ScreenRect, area: &ScreenRect) -> (i32, i32) {
+    let max_x = area.x + (area.width as i32 - window.width as i32).max(0);
+    let max_y = area.y + (area.height as i32 - window.height as i32).max(0);
+    (window.x.clamp(area.x, max_x), window.y.clamp(area.y, max_y))
+}
+
+/// 本次运行中各窗口设置过的不透明度（原生 API 无法直接查询，保存窗口配置时从这里读取）
+static WINDOW_OPACITY: once_cell::sync::Lazy<Mutex<HashMap<String, f64>>> =
+    once_cell::sync::Lazy::new(|| Mutex::new(HashMap::new()));
+
+/// 最低不透明度，避免窗口完全看不见后无法找回
+#[cfg(any(windows, target_os = "macos"))]
+const MIN_WINDOW_OPACITY: f64 = 0.1;
+
+/// 查询窗口在本次运行中设置过的不透明度
+pub fn current_window_opacity(label: &str) -> Option<f64> {
+    WINDOW_OPACITY.lock().unwrap().get(label).copied()
+}
+
+/// 通过平台 API 设置窗口整体不透明度
+#[cfg(windows)]
+pub fn apply_window_opacity(window: &tauri::WebviewWindow, opacity: f64) -> Result<(), String> {
+    use windows::Win32::Foundation::{COLORREF, HWND};
+    use windows::Win32::UI::WindowsAndMessaging::{
+        GetWindowLongW, SetLayeredWindowAttributes, SetWindowLongW, GWL_EXSTYLE, LWA_ALPHA,
+        WS_EX_LAYERED,
+    };
+
+    let opacity = opacity.clamp(MIN_WINDOW_OPACITY, 1.0);
+    let hwnd_ptr = window.hwnd().map_err(|e| e.to_string())?;
+    let hwnd = HWND(hwnd_ptr.0 as *mut _);
+    unsafe {
+        // 分层窗口才能设置整体 alpha
+        let ex_style = GetWindowLongW(hwnd, GWL_EXSTYLE);
+        if (ex_style as u32 & WS_EX_LAYERED.0) == 0 {
+            SetWindowLongW(hwnd, GWL_EXSTYLE, ex_style | WS_EX_LAYERED.0 as i32);
         }
-    } else {
-        Err(format!("Window '{}' not found", label))
+        SetLayeredWindowAttributes(
+            hwnd,
+            COLORREF(0),
+            (opacity * 255.0).round() as u8,
+            LWA_ALPHA,
+        )
+        .map_err(|e| format!("设置窗口不透明度失败: {}", e))?;
     }
+    WINDOW_OPACITY
+        .lock()
+        .unwrap()
+        .insert(window.label().to_string(), opacity);
+    Ok(())
 }
 
-struct ScreenBounds {
-    pos_x: i32,
-    pos_y: i32,
-    width: u32,
-    height: u32,
+/// 通过平台 API 设置窗口整体不透明度
+#[cfg(target_os = "macos")]
+#[allow(deprecated)] // cocoa 整体已标记弃用，这里只用到 NSWindow::setAlphaValue_
+pub fn apply_window_opacity(window: &tauri::WebviewWindow, opacity: f64) -> Result<(), String> {
+    use cocoa::appkit::NSWindow;
+    use cocoa::base::id;
+
+    let opacity = opacity.clamp(MIN_WINDOW_OPACITY, 1.0);
+    // 裸指针不能跨线程传递，先转成地址
+    let ns_window = window.ns_window().map_err(|e| e.to_string())? as usize;
+    // AppKit 要求在主线程调用
+    window
+        .run_on_main_thread(move || unsafe {
+            (ns_window as id).setAlphaValue_(opacity);
+        })
+        .map_err(|e| format!("设置窗口不透明度失败: {}", e))?;
+    WINDOW_OPACITY
+        .lock()
+        .unwrap()
+        .insert(window.label().to_string(), opacity);
+    Ok(())
 }
 
-fn clamp_position_to_screen(
-    x: f64,
-    y: f64,
-    width: f64,
-    _height: f64,
-    monitor: &ScreenBounds,
-    scale_factor: f64,
-) -> (f64, f64) {
-    let physical_x = (x * scale_factor) as i32;
-    let physical_y = (y * scale_factor) as i32;
-    let physical_width = (width * scale_factor) as i32;
+/// 通过平台 API 设置窗口整体不透明度
+#[cfg(not(any(windows, target_os = "macos")))]
+pub fn apply_window_opacity(_window: &tauri::WebviewWindow, _opacity: f64) -> Result<(), String> {
+    Err("当前平台不支持调节窗口不透明度".to_string())
+}
 
-    let monitor_right = monitor.pos_x + monitor.width as i32;
-    let monitor_bottom = monitor.pos_y + monitor.height as i32;
+/// 本次运行中用户显式切换过的置顶状态，未切换过的窗口保存配置时沿用旧值
+static WINDOW_ALWAYS_ON_TOP: once_cell::sync::Lazy<Mutex<HashMap<String, bool>>> =
+    once_cell::sync::Lazy::new(|| Mutex::new(HashMap::new()));
 
-    let mut clamped_x = physical_x;
-    let mut clamped_y = physical_y;
+/// 查询窗口在本次运行中显式设置过的置顶状态
+pub fn current_window_always_on_top(label: &str) -> Option<bool> {
+    WINDOW_ALWAYS_ON_TOP.lock().unwrap().get(label).copied()
+}
 
-    if clamped_x + physical_width < monitor.pos_x + 60 {
-        // 至少保留60px可见
-        clamped_x = monitor.pos_x + 60 - physical_width;
-    }
-    if clamped_x > monitor_right - 60 {
-        clamped_x = monitor_right - 60;
-    }
-    if clamped_y < monitor.pos_y {
-        clamped_y = monitor.pos_y;
-    }
-    if clamped_y > monitor_bottom - 60 {
-        clamped_y = monitor_bottom - 60;
-    }
+/// 切换窗口置顶
+#[tauri::command]
+pub async fn set_window_always_on_top(
+    app: AppHandle,
+    label: String,
+    always_on_top: bool,
+) -> Result<(), String> {
+    let window = app
+        .get_webview_window(&label)
+        .ok_or_else(|| format!("Window '{}' not found", label))?;
+    window
+        .set_always_on_top(always_on_top)
+        .map_err(|e| e.to_string())?;
+    WINDOW_ALWAYS_ON_TOP
+        .lock()
+        .unwrap()
+        .insert(label, always_on_top);
+    Ok(())
+}
 
-    (
-        clamped_x as f64 / scale_factor,
-        clamped_y as f64 / scale_factor,
-    )
+/// 调节窗口整体不透明度（0.1 ~ 1.0），关闭窗口时随窗口配置一起保存
+#[tauri::command]
+pub async fn set_window_opacity(app: AppHandle, label: String, opacity: f64) -> Result<(), String> {
+    if !opacity.is_finite() {
+        return Err("无效的不透明度".to_string());
+    }
+    let window = app
+        .get_webview_window(&label)
+        .ok_or_else(|| format!("Window '{}' not found", label))?;
+    apply_window_opacity(&window, opacity)
 }
 
 /// 从分离窗口导航主窗口到设置页面
@@ -994,3 +1392,112 @@ pub async fn set_window_shadow(
         Err(format!("Window '{}' not found", label))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn detach_session(id: &str, created_at: Instant) -> DetachSession {
+        DetachSession {
+            config: DetachableConfig {
+                id: id.to_string(),
+                display_name: id.to_string(),
+                r#type: "component".to_string(),
+                width: 400.0,
+                height: 300.0,
+                mouse_x: 0.0,
+                mouse_y: 0.0,
+                handle_offset_x: 0.0,
+                handle_offset_y: 0.0,
+                disable_native_resize: false,
+                disable_drag_drop_handler: false,
+            },
+            preview_window_label: format!("detached-{}", id),
+            created_at,
+        }
+    }
+
+    #[test]
+    fn test_expired_detach_session_ids() {
+        let start = Instant::now();
+        let mut sessions = HashMap::new();
+        sessions.insert("detached-old".to_string(), detach_session("old", start));
+        sessions.insert(
+            "detached-new".to_string(),
+            detach_session("new", start + Duration::from_secs(25)),
+        );
+
+        let now = start + Duration::from_secs(40);
+        let expired = expired_detach_session_ids(&sessions, now, Duration::from_secs(30));
+        assert_eq!(expired, vec!["detached-old".to_string()]);
+
+        // 创建时间晚于 now 的会话不应被判定超时
+        assert!(expired_detach_session_ids(&sessions, start, Duration::from_secs(30)).is_empty());
+    }
+
+    #[test]
+    fn test_detect_snap_zone() {
+        // 副屏工作区：从 (1920, 0) 开始，底部 40px 为任务栏
+        let area = ScreenRect {
+            x: 1920,
+            y: 0,
+            width: 1920,
+            height: 1040,
+        };
+
+        assert_eq!(
+            detect_snap_zone(2500.0, 10.0, &area, 24.0),
+            Some(SnapZone::Maximize)
+        );
+        // 左上角同时靠近两条边时优先最大化
+        assert_eq!(
+            detect_snap_zone(1925.0, 5.0, &area, 24.0),
+            Some(SnapZone::Maximize)
+        );
+        assert_eq!(
+            detect_snap_zone(1930.0, 500.0, &area, 24.0),
+            Some(SnapZone::Left)
+        );
+        assert_eq!(
+            detect_snap_zone(3830.0, 500.0, &area, 24.0),
+            Some(SnapZone::Right)
+        );
+        assert_eq!(detect_snap_zone(2500.0, 500.0, &area, 24.0), None);
+        // 不在该显示器内
+        assert_eq!(detect_snap_zone(100.0, 10.0, &area, 24.0), None);
+    }
+
+    #[test]
+    fn test_window_visibility_across_monitors() {
+        let rect = |x, y, width, height| ScreenRect {
+            x,
+            y,
+            width,
+            height,
+        };
+        let primary = rect(0, 0, 1920, 1080);
+        let left_secondary = rect(-2560, 0, 2560, 1440);
+
+        // 位于副屏上的窗口可见，不应被拉回
+        let on_secondary = rect(-2000, 200, 800, 600);
+        assert!(is_rect_visible(&on_secondary, &[primary, left_secondary]));
+        // 副屏拔掉后同一位置不可见
+        assert!(!is_rect_visible(&on_secondary, &[primary]));
+        // 只露出一小条边不算可见
+        assert!(!is_rect_visible(&rect(1890, 100, 800, 600), &[primary]));
+        assert!(is_rect_visible(&rect(1800, 100, 800, 600), &[primary]));
+
+        // 限制到主屏工作区（底部 40px 任务栏）
+        let work_area = rect(0, 0, 1920, 1040);
+        assert_eq!(clamp_into_area(&on_secondary, &work_area), (0, 200));
+        assert_eq!(
+            clamp_into_area(&rect(3000, 900, 800, 600), &work_area),
+            (1120, 440)
+        );
+        // 窗口比工作区大时贴左上角
+        assert_eq!(
+            clamp_into_area(&rect(-50, -50, 2500, 1200), &work_area),
+            (0, 0)
+        );
+    }
+}
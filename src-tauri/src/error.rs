@@ -0,0 +1,247 @@
+// Copyright 2025-2026 miaotouy(Github@miaotouy)
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! 统一的命令错误类型
+//!
+//! 命令返回 `Result<T, AppError>` 时，前端收到的是 `{ code, message, details? }` 对象，
+//! 可以按 `code` 分支做 i18n 与重试决策，而不是去匹配中文错误字符串。
+//! 旧命令仍返回 `Result<T, String>`，两者通过 `From` 互转以便逐步迁移。
+
+use serde::ser::SerializeStruct;
+use serde::{Serialize, Serializer};
+use std::fmt;
+use std::io;
+
+/// 前端可见的错误码
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ErrorCode {
+    NotFound,
+    PermissionDenied,
+    AlreadyExists,
+    InvalidInput,
+    TooLarge,
+    Cancelled,
+    Io,
+    Zip,
+    Serialization,
+    Internal,
+}
+
+impl ErrorCode {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::NotFound => "NOT_FOUND",
+            Self::PermissionDenied => "PERMISSION_DENIED",
+            Self::AlreadyExists => "ALREADY_EXISTS",
+            Self::InvalidInput => "INVALID_INPUT",
+            Self::TooLarge => "TOO_LARGE",
+            Self::Cancelled => "CANCELLED",
+            Self::Io => "IO_ERROR",
+            Self::Zip => "ZIP_ERROR",
+            Self::Serialization => "SERIALIZATION_ERROR",
+            Self::Internal => "INTERNAL",
+        }
+    }
+}
+
+/// 命令错误
+#[derive(Debug)]
+pub enum AppError {
+    /// 文件、目录或资源不存在
+    NotFound(String),
+    /// 权限不足或被安全策略拒绝
+    PermissionDenied(String),
+    /// 目标已存在
+    AlreadyExists(String),
+    /// 参数不合法
+    InvalidInput(String),
+    /// 超出大小限制
+    TooLarge(String),
+    /// 操作被用户取消
+    Cancelled(String),
+    Io(io::Error),
+    Zip(zip::result::ZipError),
+    Json(serde_json::Error),
+    /// 尚未归类的错误（多来自仍返回 String 的内部函数）
+    Other(String),
+    /// 附带上下文说明的错误，错误码沿用内层错误
+    Context {
+        message: String,
+        source: Box<AppError>,
+    },
+}
+
+impl AppError {
+    pub fn code(&self) -> ErrorCode {
+        match self {
+            Self::NotFound(_) => ErrorCode::NotFound,
+            Self::PermissionDenied(_) => ErrorCode::PermissionDenied,
+            Self::AlreadyExists(_) => ErrorCode::AlreadyExists,
+            Self::InvalidInput(_) => ErrorCode::InvalidInput,
+            Self::TooLarge(_) => ErrorCode::TooLarge,
+            Self::Cancelled(_) => ErrorCode::Cancelled,
+            Self::Io(e) => match e.kind() {
+                io::ErrorKind::NotFound => ErrorCode::NotFound,
+                io::ErrorKind::PermissionDenied => ErrorCode::PermissionDenied,
+                io::ErrorKind::AlreadyExists => ErrorCode::AlreadyExists,
+                io::ErrorKind::InvalidInput => ErrorCode::InvalidInput,
+                _ => ErrorCode::Io,
+            },
+            Self::Zip(zip::result::ZipError::Io(e)) => match e.kind() {
+                io::ErrorKind::NotFound => ErrorCode::NotFound,
+                io::ErrorKind::PermissionDenied => ErrorCode::PermissionDenied,
+                _ => ErrorCode::Zip,
+            },
+            Self::Zip(_) => ErrorCode::Zip,
+            Self::Json(_) => ErrorCode::Serialization,
+            Self::Other(_) => ErrorCode::Internal,
+            Self::Context { source, .. } => source.code(),
+        }
+    }
+
+    /// 内层错误的原始描述，供前端展开详情或写日志
+    pub fn details(&self) -> Option<String> {
+        match self {
+            Self::Context { source, .. } => Some(source.to_string()),
+            _ => None,
+        }
+    }
+
+    /// 给错误加一层上下文说明，如 "读取文件失败"
+    pub fn context(self, message: impl Into<String>) -> Self {
+        Self::Context {
+            message: message.into(),
+            source: Box::new(self),
+        }
+    }
+}
+
+impl fmt::Display for AppError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::NotFound(msg)
+            | Self::PermissionDenied(msg)
+            | Self::AlreadyExists(msg)
+            | Self::InvalidInput(msg)
+            | Self::TooLarge(msg)
+            | Self::Cancelled(msg)
+            | Self::Other(msg) => write!(f, "{}", msg),
+            Self::Io(e) => write!(f, "{}", e),
+            Self::Zip(e) => write!(f, "{}", e),
+            Self::Json(e) => write!(f, "{}", e),
+            Self::Context { message, source } => write!(f, "{}: {}", message, source),
+        }
+    }
+}
+
+impl std::error::Error for AppError {}
+
+impl Serialize for AppError {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let details = self.details();
+        let mut state = serializer.serialize_struct("AppError", 3)?;
+        state.serialize_field("code", self.code().as_str())?;
+        state.serialize_field("message", &self.to_string())?;
+        state.serialize_field("details", &details)?;
+        state.end()
+    }
+}
+
+impl From<io::Error> for AppError {
+    fn from(e: io::Error) -> Self {
+        Self::Io(e)
+    }
+}
+
+impl From<zip::result::ZipError> for AppError {
+    fn from(e: zip::result::ZipError) -> Self {
+        Self::Zip(e)
+    }
+}
+
+impl From<serde_json::Error> for AppError {
+    fn from(e: serde_json::Error) -> Self {
+        Self::Json(e)
+    }
+}
+
+impl From<String> for AppError {
+    fn from(msg: String) -> Self {
+        Self::Other(msg)
+    }
+}
+
+impl From<&str> for AppError {
+    fn from(msg: &str) -> Self {
+        Self::Other(msg.to_string())
+    }
+}
+
+/// 让已迁移的函数仍可被返回 `Result<T, String>` 的旧调用方用 `?` 调用
+impl From<AppError> for String {
+    fn from(e: AppError) -> Self {
+        e.to_string()
+    }
+}
+
+pub type AppResult<T> = Result<T, AppError>;
+
+/// 为任意可转换为 `AppError` 的错误附加上下文
+pub trait ResultExt<T> {
+    fn context(self, message: impl Into<String>) -> AppResult<T>;
+}
+
+impl<T, E: Into<AppError>> ResultExt<T> for Result<T, E> {
+    fn context(self, message: impl Into<String>) -> AppResult<T> {
+        self.map_err(|e| e.into().context(message))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_io_error_kind_maps_to_code() {
+        let err: AppError = io::Error::new(io::ErrorKind::NotFound, "missing").into();
+        assert_eq!(err.code(), ErrorCode::NotFound);
+
+        let err: AppError = io::Error::new(io::ErrorKind::PermissionDenied, "denied").into();
+        assert_eq!(err.code(), ErrorCode::PermissionDenied);
+
+        let err: AppError = io::Error::new(io::ErrorKind::Other, "boom").into();
+        assert_eq!(err.code(), ErrorCode::Io);
+    }
+
+    #[test]
+    fn test_context_keeps_inner_code_and_details() {
+        let result: Result<(), io::Error> =
+            Err(io::Error::new(io::ErrorKind::PermissionDenied, "denied"));
+        let err = result.context("读取文件失败").unwrap_err();
+
+        assert_eq!(err.code(), ErrorCode::PermissionDenied);
+        assert_eq!(err.to_string(), "读取文件失败: denied");
+        assert_eq!(err.details().as_deref(), Some("denied"));
+    }
+
+    #[test]
+    fn test_serialize_shape() {
+        let err = AppError::NotFound("文件不存在: a.txt".to_string());
+        let value = serde_json::to_value(&err).unwrap();
+
+        assert_eq!(value["code"], "NOT_FOUND");
+        assert_eq!(value["message"], "文件不存在: a.txt");
+        assert!(value["details"].is_null());
+    }
+}
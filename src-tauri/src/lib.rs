@@ -14,6 +14,7 @@
 
 // 模块声明
 mod commands;
+mod error;
 mod events;
 mod frontend_monitor;
 mod knowledge;
@@ -252,6 +253,7 @@ pub fn run() {
         .manage(commands::native_plugin::NativePluginState::default())
         .manage(commands::directory_janitor::ScanCancellation::new())
         .manage(commands::directory_janitor::CleanupCancellation::new())
+        .manage(commands::directory_janitor::CleanupCandidates::default())
         .manage(commands::dir_search::DirSearchCancellation::new())
         .manage(commands::content_deduplicator::DedupScanCancellation::new())
         .manage(commands::llmchat_search::LlmChatSearchCancellation::new())
@@ -452,10 +454,14 @@ pub fn run() {
                 create_system_tray(app)?;
             }
 
-            // 初始化全局鼠标监听器（用于基于 rdev 的拖拽, 仅在非 macOS 上启用）
+            // 初始化全局鼠标监听器（用于基于 rdev 的拖拽, 仅在非 macOS 上启用；
+            // macOS 在拖拽会话期间改用 NSEvent 轮询，见 window_manager::start_drag_session）
             #[cfg(not(target_os = "macos"))]
             init_global_mouse_listener();
 
+            // 定期清理前端未固化的按钮分离会话
+            commands::window_manager::spawn_detach_session_reaper(app.handle().clone());
+
             // 启动时清理过期临时文件（超过 24 小时）
             commands::sidecar_plugin_manager::cleanup_expired_temp_files(app.app_handle());
 
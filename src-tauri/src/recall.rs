@@ -14,6 +14,7 @@
 
 pub mod commands;
 pub mod core;
+pub mod extract;
 pub mod index;
 pub mod io;
 pub mod monitor;
@@ -15,6 +15,7 @@
 pub mod backup;
 pub mod base;
 pub mod entry;
+pub mod graph;
 pub mod retrieval_cache;
 pub mod search;
 pub mod tag;
@@ -23,6 +24,7 @@ pub mod vector;
 pub use backup::*;
 pub use base::*;
 pub use entry::*;
+pub use graph::*;
 pub use retrieval_cache::*;
 pub use search::*;
 pub use tag::*;
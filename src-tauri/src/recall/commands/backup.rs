@@ -17,7 +17,9 @@ use crate::commands::asset_manager::{
 };
 use crate::recall::core::{AssetRef, RecallCollection, RecallCollectionMeta, RecallEntry};
 use crate::recall::index::InMemoryBase;
-use crate::recall::io::{get_bases_dir, get_knowledge_root, get_recall_dir};
+use crate::recall::io::{
+    get_bases_dir, get_knowledge_root, get_recall_dir, list_entry_files, read_entry_file,
+};
 use crate::recall::state::RecallState;
 use chrono::Utc;
 use serde::{Deserialize, Serialize};
@@ -338,26 +340,10 @@ fn read_library_directory(recall_dir: &Path) -> Result<KnowledgeLibraryDtoV1, St
         .map_err(|error| format!("解析思绪集元数据失败: {}", error))?;
     let entries_dir = recall_dir.join("entries");
     let mut entries = Vec::new();
-    if entries_dir.exists() {
-        let mut paths = Vec::new();
-        for item in
-            fs::read_dir(&entries_dir).map_err(|error| format!("读取条目目录失败: {}", error))?
-        {
-            let path = item
-                .map_err(|error| format!("枚举思绪集条目失败: {}", error))?
-                .path();
-            if path.extension().and_then(|ext| ext.to_str()) == Some("json") {
-                paths.push(path);
-            }
-        }
-        paths.sort();
-        for path in paths {
-            let bytes = fs::read(&path)
-                .map_err(|error| format!("读取条目失败 {}: {}", path.display(), error))?;
-            let entry: RecallEntry = serde_json::from_slice(&bytes)
-                .map_err(|error| format!("解析条目失败 {}: {}", path.display(), error))?;
-            entries.push(entry);
-        }
+    for path in list_entry_files(&entries_dir)? {
+        let entry =
+            read_entry_file(&path).map_err(|error| format!("{} ({})", error, path.display()))?;
+        entries.push(entry);
     }
     reset_derived_state(&mut meta, &entries);
     let library = KnowledgeLibraryDtoV1 { meta, entries };
@@ -405,7 +391,7 @@ fn collect_package_assets(
                             package_path: None,
                             name: asset.name,
                             mime_type: asset.mime_type,
-                            sha256: asset.metadata.and_then(|metadata| metadata.sha256),
+                            sha256: Some(sha256_hex(&bytes)),
                             missing_reason: Some("资产超过单文件备份上限".to_string()),
                         },
                         bytes: None,
@@ -1603,6 +1589,8 @@ pub async fn recall_import_backup(
                 );
             }
         }
+    } else {
+        state.clear_search_page_cache();
     }
     result
 }
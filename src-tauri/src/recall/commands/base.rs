@@ -12,11 +12,16 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use crate::recall::core::{RecallCollection, RecallCollectionMeta};
+use crate::recall::core::{RecallCollection, RecallCollectionMeta, RecallEntry};
 use crate::recall::io::*;
+use crate::recall::monitor::{
+    emit_monitor_event, IndexMetadata, IndexPayload, IndexStats, RecallMonitorEvent,
+    RecallMonitorLevel, RecallMonitorStep, RecallStepStatus,
+};
 use crate::recall::ops::*;
 use crate::recall::state::RecallState;
 use crate::recall::utils::*;
+use std::collections::HashSet;
 use std::path::{Path, PathBuf};
 use std::sync::{Arc, RwLock};
 use tauri::{AppHandle, Manager, State};
@@ -270,6 +275,7 @@ pub async fn recall_delete_base(
         .write()
         .map_err(|_| "获取检索缓存写锁失败")?
         .clear();
+    state.clear_search_page_cache();
 
     Ok(())
 }
@@ -342,6 +348,7 @@ pub async fn recall_clone_base(
         });
     }
 
+    state.clear_search_page_cache();
     Ok(new_recall_id)
 }
 
@@ -363,10 +370,275 @@ pub async fn recall_export_base(
     })
 }
 
+/// Markdown 导出时每处理多少个条目推送一次进度
+const MARKDOWN_EXPORT_PROGRESS_STEP: usize = 20;
+
+/// 文件名最大字符数，避免超出文件系统限制
+const MAX_MARKDOWN_FILE_STEM_CHARS: usize = 120;
+
+/// 将条目 key 转换为可安全落盘的文件名（不含扩展名）
+///
+/// 替换各平台的非法字符与控制字符，去掉结尾的点和空格，并规避 Windows 保留设备名。
+fn sanitize_markdown_file_stem(key: &str) -> String {
+    let replaced: String = key
+        .chars()
+        .map(|c| match c {
+            '<' | '>' | ':' | '"' | '/' | '\\' | '|' | '?' | '*' => '_',
+            c if c.is_control() => '_',
+            c => c,
+        })
+        .take(MAX_MARKDOWN_FILE_STEM_CHARS)
+        .collect();
+    let trimmed = replaced.trim().trim_end_matches(['.', ' ']);
+
+    if trimmed.is_empty() {
+        return "untitled".to_string();
+    }
+
+    const RESERVED: [&str; 22] = [
+        "CON", "PRN", "AUX", "NUL", "COM1", "COM2", "COM3", "COM4", "COM5", "COM6", "COM7", "COM8",
+        "COM9", "LPT1", "LPT2", "LPT3", "LPT4", "LPT5", "LPT6", "LPT7", "LPT8", "LPT9",
+    ];
+    let base_name = trimmed.split('.').next().unwrap_or(trimmed);
+    if RESERVED
+        .iter()
+        .any(|reserved| reserved.eq_ignore_ascii_case(base_name))
+    {
+        return format!("_{}", trimmed);
+    }
+
+    trimmed.to_string()
+}
+
+/// 为重名文件追加 `-2`、`-3` 后缀
+///
+/// 比较时忽略大小写，以兼容 Windows/macOS 默认的大小写不敏感文件系统。
+fn unique_file_stem(stem: String, used: &mut HashSet<String>) -> String {
+    if used.insert(stem.to_lowercase()) {
+        return stem;
+    }
+    let mut index = 2;
+    loop {
+        let candidate = format!("{}-{}", stem, index);
+        if used.insert(candidate.to_lowercase()) {
+            return candidate;
+        }
+        index += 1;
+    }
+}
+
+fn format_export_time(timestamp: i64) -> String {
+    chrono::DateTime::from_timestamp_millis(crate::recall::utils::timestamp_to_millis(timestamp))
+        .map(|time| time.to_rfc3339())
+        .unwrap_or_default()
+}
+
+/// 生成 YAML front-matter，字符串值使用 JSON 转义（JSON 字符串是合法的 YAML 标量）
+fn build_entry_front_matter(entry: &RecallEntry, include_tags: bool) -> String {
+    let quote = |value: &str| serde_json::to_string(value).unwrap_or_default();
+    let mut lines = vec!["---".to_string(), format!("key: {}", quote(&entry.key))];
+    if include_tags {
+        let tags: Vec<String> = entry.tags.iter().map(|tag| quote(&tag.name)).collect();
+        lines.push(format!("tags: [{}]", tags.join(", ")));
+    }
+    lines.push(format!("priority: {}", entry.priority));
+    lines.push(format!(
+        "created_at: {}",
+        quote(&format_export_time(entry.created_at))
+    ));
+    lines.push("---".to_string());
+    lines.join("\n")
+}
+
+fn render_entry_markdown(entry: &RecallEntry, include_tags: bool) -> String {
+    format!(
+        "{}\n\n{}\n",
+        build_entry_front_matter(entry, include_tags),
+        entry.content.trim_end()
+    )
+}
+
+/// 合并导出：文件开头为目录，每个条目使用显式锚点，避免依赖各渲染器不同的标题 slug 规则
+fn render_combined_markdown(title: &str, entries: &[RecallEntry], include_tags: bool) -> String {
+    let mut output = format!("# {}\n\n## 目录\n\n", title);
+    for (index, entry) in entries.iter().enumerate() {
+        output.push_str(&format!("- [{}](#entry-{})\n", entry.key, index + 1));
+    }
+
+    for (index, entry) in entries.iter().enumerate() {
+        output.push_str(&format!(
+            "\n<a id=\"entry-{}\"></a>\n\n## {}\n\n",
+            index + 1,
+            entry.key
+        ));
+        let mut meta = Vec::new();
+        if include_tags && !entry.tags.is_empty() {
+            let tags: Vec<&str> = entry.tags.iter().map(|tag| tag.name.as_str()).collect();
+            meta.push(format!("标签: {}", tags.join(", ")));
+        }
+        meta.push(format!("优先级: {}", entry.priority));
+        meta.push(format!("创建于: {}", format_export_time(entry.created_at)));
+        output.push_str(&format!("> {}\n\n", meta.join(" · ")));
+        output.push_str(entry.content.trim_end());
+        output.push('\n');
+    }
+
+    output
+}
+
+fn emit_markdown_export_progress(
+    app: &AppHandle,
+    recall_id: Uuid,
+    processed: usize,
+    total: usize,
+    start_time: std::time::Instant,
+) {
+    let done = processed >= total;
+    let duration = start_time.elapsed().as_millis() as u64;
+    let _ = emit_monitor_event(
+        app,
+        RecallMonitorEvent::Index(IndexPayload {
+            steps: vec![RecallMonitorStep {
+                name: "Markdown 导出".to_string(),
+                status: if done {
+                    RecallStepStatus::Completed
+                } else {
+                    RecallStepStatus::Running
+                },
+                duration,
+                details: Some(format!("已导出 {}/{} 个条目", processed, total)),
+            }],
+            stats: IndexStats {
+                total_files: total as u32,
+                processed_files: processed as u32,
+                total_chunks: total as u32,
+                vectorized_chunks: 0,
+                duration,
+            },
+            metadata: Some(IndexMetadata {
+                recall_id: recall_id.to_string(),
+                model_id: "".to_string(),
+                file_patterns: vec![],
+            }),
+        }),
+        if done {
+            RecallMonitorLevel::Success
+        } else {
+            RecallMonitorLevel::Info
+        },
+        if done {
+            "Markdown 导出完成"
+        } else {
+            "Markdown 导出中"
+        },
+        &format!("已导出 {}/{} 个条目", processed, total),
+        "Exporter",
+    );
+}
+
+/// 将思绪集导出为 Markdown 文档
+///
+/// 默认每个条目导出为一个以 key 命名的 `.md` 文件，带 front-matter；
+/// `single_file` 为 true 时合并为一个带目录的文件。返回生成的文件路径列表。
+#[tauri::command]
+pub async fn recall_export_markdown(
+    app: AppHandle,
+    state: State<'_, RecallState>,
+    recall_id: Uuid,
+    output_dir: String,
+    include_tags: bool,
+    single_file: Option<bool>,
+) -> Result<Vec<String>, String> {
+    let start_time = std::time::Instant::now();
+    let (base_name, mut entries) = {
+        let imdb = state.imdb.read().map_err(|_| "获取内存数据库读锁失败")?;
+        let base_lock = imdb
+            .bases
+            .get(&recall_id)
+            .ok_or_else(|| format!("找不到思绪集: {}", recall_id))?;
+        let base = base_lock.read().map_err(|_| "获取思绪集读锁失败")?;
+        (
+            base.meta.name.clone(),
+            base.entries.values().cloned().collect::<Vec<_>>(),
+        )
+    };
+    // 按创建时间排序，保证多次导出的顺序与去重后缀稳定
+    entries.sort_by(|a, b| {
+        a.created_at
+            .cmp(&b.created_at)
+            .then_with(|| a.key.cmp(&b.key))
+            .then_with(|| a.id.cmp(&b.id))
+    });
+
+    let output_dir = PathBuf::from(output_dir);
+    std::fs::create_dir_all(&output_dir).map_err(|e| format!("创建导出目录失败: {}", e))?;
+
+    let total = entries.len();
+    if single_file.unwrap_or(false) {
+        let path = output_dir.join(format!("{}.md", sanitize_markdown_file_stem(&base_name)));
+        let content = render_combined_markdown(&base_name, &entries, include_tags);
+        std::fs::write(&path, content).map_err(|e| format!("写入 Markdown 文件失败: {}", e))?;
+        emit_markdown_export_progress(&app, recall_id, total, total, start_time);
+        return Ok(vec![path.to_string_lossy().to_string()]);
+    }
+
+    let mut used_stems = HashSet::new();
+    let mut paths = Vec::with_capacity(total);
+    for (index, entry) in entries.iter().enumerate() {
+        let stem = unique_file_stem(sanitize_markdown_file_stem(&entry.key), &mut used_stems);
+        let path = output_dir.join(format!("{}.md", stem));
+        std::fs::write(&path, render_entry_markdown(entry, include_tags))
+            .map_err(|e| format!("写入 Markdown 文件失败: {}", e))?;
+        paths.push(path.to_string_lossy().to_string());
+
+        let processed = index + 1;
+        if processed < total && processed % MARKDOWN_EXPORT_PROGRESS_STEP == 0 {
+            emit_markdown_export_progress(&app, recall_id, processed, total, start_time);
+        }
+    }
+    emit_markdown_export_progress(&app, recall_id, total, total, start_time);
+
+    log::info!(
+        "[KB_EXPORT] 思绪集 {} 已导出 {} 个 Markdown 文件到 {:?}",
+        recall_id,
+        paths.len(),
+        output_dir
+    );
+    Ok(paths)
+}
+
+/// 转换思绪集条目的存储格式（明文 JSON ↔ gzip），返回实际转换的条目数
+///
+/// 两个方向都可执行，方便用户随时把压缩库还原为可直接阅读的明文文件。
+#[tauri::command]
+pub async fn recall_convert_entry_storage(
+    app: AppHandle,
+    state: State<'_, RecallState>,
+    recall_id: Uuid,
+    format: EntryStorageFormat,
+) -> Result<usize, String> {
+    let app_data_dir = app.path().app_data_dir().map_err(|e| e.to_string())?;
+    let recall_id_str = recall_id.to_string();
+
+    if !get_recall_dir(&app_data_dir, &recall_id_str).exists() {
+        return Err(format!("思绪集不存在: {}", recall_id));
+    }
+
+    // 持有思绪集写锁，避免转换期间有条目按旧格式写入
+    let imdb = state.imdb.read().map_err(|_| "获取内存数据库读锁失败")?;
+    let _base_guard = match imdb.bases.get(&recall_id) {
+        Some(base_lock) => Some(base_lock.write().map_err(|_| "获取思绪集写锁失败")?),
+        None => None,
+    };
+
+    convert_entry_storage(&app_data_dir, &recall_id_str, format)
+}
+
 #[cfg(test)]
 mod tests {
-    use super::delete_base_directories;
+    use super::{delete_base_directories, sanitize_markdown_file_stem, unique_file_stem};
     use crate::recall::io::{get_recall_dir, get_recall_vectors_root};
+    use std::collections::HashSet;
     use std::fs;
     use tempfile::tempdir;
 
@@ -395,4 +667,18 @@ mod tests {
         delete_base_directories(app_data_dir.path(), "60f7ad7e-9a59-4b25-bad0-e87a74dcf622")
             .unwrap();
     }
+
+    #[test]
+    fn markdown_file_stems_are_sanitized_and_deduplicated() {
+        assert_eq!(sanitize_markdown_file_stem("a/b:c*?"), "a_b_c__");
+        assert_eq!(sanitize_markdown_file_stem("  笔记. "), "笔记");
+        assert_eq!(sanitize_markdown_file_stem("..."), "untitled");
+        assert_eq!(sanitize_markdown_file_stem("con"), "_con");
+        assert_eq!(sanitize_markdown_file_stem("Aux.txt"), "_Aux.txt");
+
+        let mut used = HashSet::new();
+        assert_eq!(unique_file_stem("Key".to_string(), &mut used), "Key");
+        assert_eq!(unique_file_stem("key".to_string(), &mut used), "key-2");
+        assert_eq!(unique_file_stem("Key".to_string(), &mut used), "Key-3");
+    }
 }
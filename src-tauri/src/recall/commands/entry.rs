@@ -12,7 +12,8 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use crate::recall::core::RecallEntry;
+use crate::recall::core::{RecallEntry, RecallEntryVersion};
+use crate::recall::extract::{extract_import_text, SkippedImportFile};
 use crate::recall::io::*;
 use crate::recall::monitor::{
     emit_monitor_event, IndexMetadata, IndexPayload, IndexStats, RecallMonitorEvent,
@@ -38,6 +39,25 @@ pub struct BatchImportResult {
     pub entries: Vec<RecallEntry>,
     pub skipped_count: usize,
     pub duplicate_count: usize,
+    /// 被跳过的文件及原因
+    pub skipped_files: Vec<SkippedImportFile>,
+}
+
+/// 批量删除中单个条目的失败原因
+#[derive(serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BatchDeleteError {
+    pub entry_id: Uuid,
+    pub error: String,
+}
+
+/// 批量删除结果
+#[derive(serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BatchDeleteResult {
+    pub success_count: usize,
+    pub failed_count: usize,
+    pub errors: Vec<BatchDeleteError>,
 }
 
 #[tauri::command]
@@ -132,10 +152,34 @@ pub async fn recall_upsert_entry(
         }
     }
 
+    let app_data_dir = app.path().app_data_dir().map_err(|e| e.to_string())?;
     let new_hash = calculate_content_hash(&entry.content);
+
+    // 内容变动时为旧版本留存快照，便于误改后回退
+    let previous = {
+        let imdb = state.imdb.read().map_err(|_| "获取内存数据库读锁失败")?;
+        imdb.bases.get(&recall_id).and_then(|base_lock| {
+            base_lock
+                .read()
+                .ok()
+                .and_then(|base| base.entries.get(&entry.id).cloned())
+        })
+    };
+    if let Some(previous) = previous {
+        if calculate_content_hash(&previous.content) != new_hash {
+            if let Err(e) = save_entry_snapshot(
+                &app_data_dir,
+                &recall_id.to_string(),
+                &previous,
+                MAX_ENTRY_VERSIONS,
+            ) {
+                log::warn!("[KB_ENTRY] 保存条目历史版本失败 {}: {}", entry.id, e);
+            }
+        }
+    }
+
     if entry.content_hash.as_ref() != Some(&new_hash) {
         // 内容变动，清理旧向量文件
-        let app_data_dir = app.path().app_data_dir().map_err(|e| e.to_string())?;
         let _ = crate::recall::ops::delete_entry_files(
             &app_data_dir,
             &recall_id.to_string(),
@@ -148,7 +192,6 @@ pub async fn recall_upsert_entry(
         entry.summary = generate_summary(&entry.content);
     }
 
-    let app_data_dir = app.path().app_data_dir().map_err(|e| e.to_string())?;
     save_entry(&app_data_dir, &recall_id.to_string(), &entry)?;
 
     let imdb = state.imdb.read().map_err(|_| "获取内存数据库读锁失败")?;
@@ -195,9 +238,86 @@ pub async fn recall_upsert_entry(
         "Indexer",
     );
 
+    state.clear_search_page_cache();
     Ok(entry)
 }
 
+/// 列出条目的历史版本，按从新到旧排序
+#[tauri::command]
+pub async fn recall_list_entry_versions(
+    app: AppHandle,
+    recall_id: Uuid,
+    entry_id: Uuid,
+) -> Result<Vec<RecallEntryVersion>, String> {
+    let app_data_dir = app.path().app_data_dir().map_err(|e| e.to_string())?;
+    let recall_id_str = recall_id.to_string();
+    let entry_id_str = entry_id.to_string();
+
+    let mut versions = Vec::new();
+    for version in list_entry_snapshot_versions(&app_data_dir, &recall_id_str, &entry_id_str)? {
+        match load_entry_snapshot(&app_data_dir, &recall_id_str, &entry_id_str, version) {
+            Ok(snapshot) => versions.push(RecallEntryVersion {
+                version,
+                key: snapshot.key,
+                summary: snapshot.summary,
+                content_hash: snapshot.content_hash,
+                updated_at: snapshot.updated_at,
+            }),
+            Err(e) => log::warn!(
+                "[KB_ENTRY] 跳过损坏的历史版本 {}/{}: {}",
+                entry_id,
+                version,
+                e
+            ),
+        }
+    }
+    Ok(versions)
+}
+
+/// 将条目恢复到指定历史版本
+///
+/// 恢复走与普通保存相同的流程，因此当前版本也会先被留存为快照，恢复操作本身可撤销。
+#[tauri::command]
+pub async fn recall_restore_entry_version(
+    app: AppHandle,
+    state: State<'_, RecallState>,
+    recall_id: Uuid,
+    entry_id: Uuid,
+    version: i64,
+) -> Result<RecallEntry, String> {
+    let app_data_dir = app.path().app_data_dir().map_err(|e| e.to_string())?;
+    let mut entry = load_entry_snapshot(
+        &app_data_dir,
+        &recall_id.to_string(),
+        &entry_id.to_string(),
+        version,
+    )?;
+    entry.updated_at = get_now();
+
+    // 快照里的 content_hash 是旧内容的哈希，换成当前条目的哈希，
+    // 让 upsert 按内容是否真的变化来决定是否清理旧向量
+    entry.content_hash = {
+        let imdb = state.imdb.read().map_err(|_| "获取内存数据库读锁失败")?;
+        match imdb.bases.get(&recall_id) {
+            Some(base_lock) => base_lock
+                .read()
+                .map_err(|_| "获取思绪集读锁失败")?
+                .entries
+                .get(&entry_id)
+                .and_then(|current| current.content_hash.clone()),
+            None => None,
+        }
+    };
+
+    log::info!(
+        "[KB_ENTRY] 恢复条目历史版本: recall={}, entry={}, version={}",
+        recall_id,
+        entry_id,
+        version
+    );
+    recall_upsert_entry(app, state, recall_id, entry, None).await
+}
+
 #[tauri::command]
 pub async fn recall_delete_entry(
     app: AppHandle,
@@ -207,6 +327,7 @@ pub async fn recall_delete_entry(
 ) -> Result<(), String> {
     let app_data_dir = app.path().app_data_dir().map_err(|e| e.to_string())?;
     crate::recall::ops::delete_entry_files(&app_data_dir, &recall_id.to_string(), &entry_id)?;
+    let _ = delete_entry_history(&app_data_dir, &recall_id.to_string(), &entry_id.to_string());
 
     let imdb = state.imdb.read().map_err(|_| "获取内存数据库读锁失败")?;
     if let Some(base_lock) = imdb.bases.get(&recall_id) {
@@ -221,6 +342,7 @@ pub async fn recall_delete_entry(
         );
         let _ = save_recall_meta(&app_data_dir, &recall_id.to_string(), &base.meta);
     }
+    state.clear_search_page_cache();
     Ok(())
 }
 
@@ -267,10 +389,10 @@ pub async fn recall_batch_import_files(
         "Indexer",
     );
 
-    let candidates: Vec<RecallEntry> = paths
+    let results: Vec<Result<RecallEntry, SkippedImportFile>> = paths
         .into_par_iter()
         .enumerate()
-        .filter_map(|(idx, path_str)| {
+        .map(|(idx, path_str)| {
             let path = std::path::Path::new(&path_str);
 
             // 进度推送 (每处理 5 个文件推送一次，避免过于频繁)
@@ -304,13 +426,17 @@ pub async fn recall_batch_import_files(
                 );
             }
 
-            if !crate::utils::mime::is_text_file(path) {
-                return None;
-            }
-
-            let content = std::fs::read_to_string(path).ok()?;
-            let filename = path.file_name()?.to_str()?;
-            let mut key = filename.split('.').next()?.to_string();
+            let content =
+                extract_import_text(path).map_err(|(reason, message)| SkippedImportFile {
+                    path: path_str.clone(),
+                    reason,
+                    message,
+                })?;
+            let filename = path
+                .file_name()
+                .map(|name| name.to_string_lossy().to_string())
+                .unwrap_or_default();
+            let mut key = filename.split('.').next().unwrap_or_default().to_string();
 
             if config.auto_extract_title {
                 if let Some(title) = extract_title_from_content(&content) {
@@ -337,7 +463,7 @@ pub async fn recall_batch_import_files(
             let now = get_now();
             let content_hash = calculate_content_hash(&content);
 
-            Some(RecallEntry {
+            Ok(RecallEntry {
                 id: Uuid::new_v4(),
                 key,
                 content: content.clone(),
@@ -357,7 +483,23 @@ pub async fn recall_batch_import_files(
         })
         .collect();
 
-    let skipped_count = total_paths - candidates.len();
+    let mut candidates = Vec::with_capacity(results.len());
+    let mut skipped_files = Vec::new();
+    for result in results {
+        match result {
+            Ok(entry) => candidates.push(entry),
+            Err(skipped) => {
+                log::info!(
+                    "[KB_IMPORT] 跳过文件 {} ({:?}): {}",
+                    skipped.path,
+                    skipped.reason,
+                    skipped.message
+                );
+                skipped_files.push(skipped);
+            }
+        }
+    }
+    let skipped_count = skipped_files.len();
 
     let imdb = state.imdb.read().map_err(|_| "获取内存数据库读锁失败")?;
     let base_lock = imdb
@@ -411,10 +553,12 @@ pub async fn recall_batch_import_files(
         "Indexer",
     );
 
+    state.clear_search_page_cache();
     Ok(BatchImportResult {
         entries,
         skipped_count,
         duplicate_count,
+        skipped_files,
     })
 }
 
@@ -461,10 +605,12 @@ pub async fn recall_batch_upsert_entries(
     let (filtered_entries, duplicate_count) =
         batch_upsert_entries_logic(&app_data_dir, base_lock, entries, deduplicate)?;
 
+    state.clear_search_page_cache();
     Ok(BatchImportResult {
         entries: filtered_entries,
         skipped_count: 0,
         duplicate_count,
+        skipped_files: vec![],
     })
 }
 
@@ -489,19 +635,17 @@ pub async fn recall_batch_patch_entries(
     // 1. 用读锁读取所有需要更新的条目（内存优先，回退到磁盘）
     let entries_to_update: Vec<RecallEntry> = {
         let base = base_lock.read().map_err(|_| "获取思绪集读锁失败")?;
-        let entries_dir = crate::recall::io::get_recall_entries_dir(&app_data_dir, &recall_id_str);
         let mut result = Vec::new();
         for id in &entry_ids {
             if let Some(entry) = base.entries.get(id) {
                 result.push(entry.clone());
             } else {
                 // 内存中没有，尝试从磁盘加载
-                let entry_path = entries_dir.join(format!("{}.json", id));
-                if entry_path.exists() {
-                    if let Ok(content) = std::fs::read_to_string(&entry_path) {
-                        if let Ok(entry) = serde_json::from_str::<RecallEntry>(&content) {
-                            result.push(entry);
-                        }
+                if let Some(entry_path) =
+                    find_entry_file(&app_data_dir, &recall_id_str, &id.to_string())
+                {
+                    if let Ok(entry) = read_entry_file(&entry_path) {
+                        result.push(entry);
                     }
                 }
             }
@@ -556,6 +700,7 @@ pub async fn recall_batch_patch_entries(
         updated_count
     );
 
+    state.clear_search_page_cache();
     Ok(updated_count)
 }
 
@@ -565,27 +710,152 @@ pub async fn recall_batch_delete_entries(
     state: State<'_, RecallState>,
     recall_id: Uuid,
     entry_ids: Vec<Uuid>,
-) -> Result<(), String> {
+) -> Result<BatchDeleteResult, String> {
+    let start_time = std::time::Instant::now();
     let app_data_dir = app.path().app_data_dir().map_err(|e| e.to_string())?;
     let recall_id_str = recall_id.to_string();
+    let total = entry_ids.len();
+    let batch_id = Uuid::new_v4().to_string();
+    let staging_dir = get_delete_staging_dir(&app_data_dir, &recall_id_str, &batch_id);
+
+    // 1. 逐个把条目文件移入暂存区；单个失败只记入错误列表，不中断整体
+    let mut staged_ids: Vec<Uuid> = Vec::new();
+    let mut staged_moves: Vec<(std::path::PathBuf, std::path::PathBuf)> = Vec::new();
+    let mut errors: Vec<BatchDeleteError> = Vec::new();
+    let emit_step = (total / 20).max(1);
+
+    for (i, entry_id) in entry_ids.iter().enumerate() {
+        match stage_entry_files(&app_data_dir, &recall_id_str, entry_id, &staging_dir) {
+            Ok(moves) => {
+                staged_ids.push(*entry_id);
+                staged_moves.extend(moves);
+            }
+            Err(e) => {
+                log::warn!("[KB_ENTRY] 暂存条目 {} 失败: {}", entry_id, e);
+                errors.push(BatchDeleteError {
+                    entry_id: *entry_id,
+                    error: e,
+                });
+            }
+        }
 
-    for entry_id in &entry_ids {
-        crate::recall::ops::delete_entry_files(&app_data_dir, &recall_id_str, entry_id)?;
+        let processed = i + 1;
+        if processed % emit_step == 0 || processed == total {
+            emit_batch_delete_progress(
+                &app,
+                &recall_id_str,
+                RecallStepStatus::Running,
+                processed,
+                total,
+                start_time.elapsed().as_millis() as u64,
+            );
+        }
     }
 
-    let imdb = state.imdb.read().map_err(|_| "获取内存数据库读锁失败")?;
-    if let Some(base_lock) = imdb.bases.get(&recall_id) {
+    // 2. 先落盘新的元数据索引，成功后再改内存；落盘失败则把暂存文件全部移回
+    let commit_result = (|| -> Result<(), String> {
+        let imdb = state.imdb.read().map_err(|_| "获取内存数据库读锁失败")?;
+        let Some(base_lock) = imdb.bases.get(&recall_id) else {
+            return Ok(());
+        };
         let mut base = base_lock.write().map_err(|_| "获取思绪集写锁失败")?;
-        for entry_id in &entry_ids {
-            base.remove_entry(entry_id);
-        }
 
-        // 同步持久化元数据索引
+        let mut new_meta = base.meta.clone();
+        let staged_set: std::collections::HashSet<&Uuid> = staged_ids.iter().collect();
+        new_meta.entries.retain(|e| !staged_set.contains(&e.id));
         log::debug!(
             "[KB_ENTRY] 批量条目删除，同步元数据索引: recall={}",
             recall_id_str
         );
-        let _ = save_recall_meta(&app_data_dir, &recall_id_str, &base.meta);
+        save_recall_meta(&app_data_dir, &recall_id_str, &new_meta)?;
+
+        for entry_id in &staged_ids {
+            base.remove_entry(entry_id);
+        }
+        Ok(())
+    })();
+
+    if let Err(e) = commit_result {
+        log::error!("[KB_ENTRY] 批量删除提交失败，回滚已暂存文件: {}", e);
+        restore_staged_files(&staged_moves);
+        let _ = std::fs::remove_dir_all(&staging_dir);
+        emit_batch_delete_progress(
+            &app,
+            &recall_id_str,
+            RecallStepStatus::Failed,
+            total,
+            total,
+            start_time.elapsed().as_millis() as u64,
+        );
+        return Err(format!("批量删除条目失败，已回滚: {}", e));
     }
-    Ok(())
+
+    // 3. 提交成功，清理暂存区与历史版本
+    if staging_dir.exists() {
+        if let Err(e) = std::fs::remove_dir_all(&staging_dir) {
+            log::warn!("[KB_ENTRY] 清理删除暂存目录失败: {}", e);
+        }
+    }
+    for entry_id in &staged_ids {
+        let _ = delete_entry_history(&app_data_dir, &recall_id_str, &entry_id.to_string());
+    }
+
+    emit_batch_delete_progress(
+        &app,
+        &recall_id_str,
+        RecallStepStatus::Completed,
+        total,
+        total,
+        start_time.elapsed().as_millis() as u64,
+    );
+
+    state.clear_search_page_cache();
+    Ok(BatchDeleteResult {
+        success_count: staged_ids.len(),
+        failed_count: errors.len(),
+        errors,
+    })
+}
+
+/// 发送批量删除进度监控事件
+fn emit_batch_delete_progress(
+    app: &AppHandle,
+    recall_id: &str,
+    status: RecallStepStatus,
+    processed: usize,
+    total: usize,
+    duration: u64,
+) {
+    let (level, title) = match status {
+        RecallStepStatus::Completed => (RecallMonitorLevel::Success, "批量删除完成"),
+        RecallStepStatus::Failed => (RecallMonitorLevel::Error, "批量删除失败"),
+        _ => (RecallMonitorLevel::Info, "批量删除中"),
+    };
+    let _ = emit_monitor_event(
+        app,
+        RecallMonitorEvent::Index(IndexPayload {
+            steps: vec![RecallMonitorStep {
+                name: "条目删除".to_string(),
+                status,
+                duration,
+                details: Some(format!("已处理 {}/{} 个条目", processed, total)),
+            }],
+            stats: IndexStats {
+                total_files: total as u32,
+                processed_files: processed as u32,
+                total_chunks: total as u32,
+                vectorized_chunks: 0,
+                duration,
+            },
+            metadata: Some(IndexMetadata {
+                recall_id: recall_id.to_string(),
+                model_id: "".to_string(),
+                file_patterns: vec![],
+            }),
+        }),
+        level,
+        title,
+        &format!("已处理 {}/{} 个条目", processed, total),
+        "Indexer",
+    );
 }
@@ -0,0 +1,276 @@
+// Copyright 2025-2026 miaotouy(Github@miaotouy)
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::recall::state::RecallState;
+use crate::recall::utils::extract_reference_keys;
+use serde::Serialize;
+use std::collections::{HashMap, HashSet, VecDeque};
+use tauri::State;
+use uuid::Uuid;
+
+/// 子图查询的默认深度
+const DEFAULT_GRAPH_DEPTH: usize = 2;
+
+/// 引用图节点
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ReferenceGraphNode {
+    pub id: Uuid,
+    pub key: String,
+    pub recall_id: Uuid,
+    pub recall_name: String,
+    /// 是否为查询范围之外、因跨库引用被带入的节点
+    pub external: bool,
+}
+
+/// 引用图的边 (source 引用了 target)
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ReferenceGraphEdge {
+    pub source: Uuid,
+    /// 悬空引用时为 None
+    pub target: Option<Uuid>,
+    /// 原始引用的 Key
+    pub target_key: String,
+    /// 引用的 Key 已找不到对应条目 (条目被删除或改名)
+    pub dangling: bool,
+    /// 引用跨越了思绪集
+    pub cross_recall: bool,
+}
+
+#[derive(Debug, Clone, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ReferenceGraph {
+    pub nodes: Vec<ReferenceGraphNode>,
+    pub edges: Vec<ReferenceGraphEdge>,
+}
+
+/// 构图所需的条目快照 (脱离锁后处理)
+struct GraphDocument {
+    id: Uuid,
+    key: String,
+    recall_id: Uuid,
+    recall_name: String,
+    ref_keys: Vec<String>,
+}
+
+/// 由条目快照构建引用图
+///
+/// 引用按 Key 解析：优先匹配同一思绪集，其次匹配其他思绪集。
+/// `scope` 为空表示全部思绪集；指定 `root` 时返回以该条目为中心、
+/// 沿引用双向扩展不超过 `depth` 跳的子图。
+fn build_reference_graph(
+    docs: &[GraphDocument],
+    scope: &HashSet<Uuid>,
+    root: Option<Uuid>,
+    depth: usize,
+) -> ReferenceGraph {
+    let index_of: HashMap<Uuid, usize> = docs.iter().enumerate().map(|(i, d)| (d.id, i)).collect();
+    let mut keys_by_recall: HashMap<(Uuid, &str), Uuid> = HashMap::new();
+    let mut keys_global: HashMap<&str, Uuid> = HashMap::new();
+    for doc in docs {
+        keys_by_recall
+            .entry((doc.recall_id, doc.key.as_str()))
+            .or_insert(doc.id);
+        keys_global.entry(doc.key.as_str()).or_insert(doc.id);
+    }
+
+    let mut all_edges = Vec::new();
+    for doc in docs {
+        for key in &doc.ref_keys {
+            let target = keys_by_recall
+                .get(&(doc.recall_id, key.as_str()))
+                .or_else(|| keys_global.get(key.as_str()))
+                .copied();
+            let cross_recall = target
+                .map(|id| docs[index_of[&id]].recall_id != doc.recall_id)
+                .unwrap_or(false);
+            all_edges.push(ReferenceGraphEdge {
+                source: doc.id,
+                target,
+                target_key: key.clone(),
+                dangling: target.is_none(),
+                cross_recall,
+            });
+        }
+    }
+
+    let in_scope = |doc: &GraphDocument| scope.is_empty() || scope.contains(&doc.recall_id);
+
+    // 确定节点集合：子图模式按 BFS 扩展，否则取范围内全部条目
+    let mut included: Vec<Uuid> = Vec::new();
+    let mut included_set: HashSet<Uuid> = HashSet::new();
+    match root {
+        Some(root_id) if index_of.contains_key(&root_id) => {
+            let mut neighbors: HashMap<Uuid, Vec<Uuid>> = HashMap::new();
+            for edge in &all_edges {
+                if let Some(target) = edge.target {
+                    neighbors.entry(edge.source).or_default().push(target);
+                    neighbors.entry(target).or_default().push(edge.source);
+                }
+            }
+
+            let mut queue = VecDeque::from([(root_id, 0usize)]);
+            included_set.insert(root_id);
+            while let Some((id, level)) = queue.pop_front() {
+                included.push(id);
+                if level >= depth {
+                    continue;
+                }
+                for next in neighbors.get(&id).into_iter().flatten() {
+                    if included_set.insert(*next) {
+                        queue.push_back((*next, level + 1));
+                    }
+                }
+            }
+        }
+        Some(_) => return ReferenceGraph::default(),
+        None => {
+            for doc in docs.iter().filter(|doc| in_scope(doc)) {
+                included_set.insert(doc.id);
+                included.push(doc.id);
+            }
+        }
+    }
+
+    // 边：起点在集合内；子图模式下终点也须在集合内 (悬空引用除外)。
+    // 外部节点只作为终点出现，不继续展开其引用
+    let sources = included_set.clone();
+    let mut edges = Vec::new();
+    for edge in all_edges {
+        if !sources.contains(&edge.source) {
+            continue;
+        }
+        match edge.target {
+            Some(target) if !included_set.contains(&target) => {
+                if root.is_some() {
+                    continue;
+                }
+                // 跨库引用把范围外的目标带入为外部节点
+                included_set.insert(target);
+                included.push(target);
+            }
+            _ => {}
+        }
+        edges.push(edge);
+    }
+
+    let nodes = included
+        .into_iter()
+        .map(|id| {
+            let doc = &docs[index_of[&id]];
+            ReferenceGraphNode {
+                id: doc.id,
+                key: doc.key.clone(),
+                recall_id: doc.recall_id,
+                recall_name: doc.recall_name.clone(),
+                external: !in_scope(doc),
+            }
+        })
+        .collect();
+
+    ReferenceGraph { nodes, edges }
+}
+
+/// 获取条目引用关系图
+///
+/// 引用来自条目内容中的 `[[Key]]`，可跨思绪集解析；`recall_ids` 为空时查询全部思绪集。
+/// 传入 `root_entry_id` 时只返回以该条目为中心、深度不超过 `depth` 的子图。
+#[tauri::command]
+pub async fn recall_get_reference_graph(
+    state: State<'_, RecallState>,
+    recall_ids: Vec<Uuid>,
+    root_entry_id: Option<Uuid>,
+    depth: Option<usize>,
+) -> Result<ReferenceGraph, String> {
+    let mut docs = Vec::new();
+    {
+        let imdb = state.imdb.read().map_err(|_| "获取内存数据库读锁失败")?;
+        // 为了跨库解析引用，始终读取全部思绪集
+        for (recall_id, base_lock) in &imdb.bases {
+            let base = base_lock.read().map_err(|_| "获取思绪集读锁失败")?;
+            for entry in base.entries.values() {
+                docs.push(GraphDocument {
+                    id: entry.id,
+                    key: entry.key.clone(),
+                    recall_id: *recall_id,
+                    recall_name: base.meta.name.clone(),
+                    ref_keys: extract_reference_keys(&entry.content),
+                });
+            }
+        }
+    }
+    // 固定顺序，保证 Key 冲突时的解析结果稳定
+    docs.sort_by(|a, b| {
+        (a.recall_id, a.key.as_str(), a.id).cmp(&(b.recall_id, b.key.as_str(), b.id))
+    });
+
+    let scope: HashSet<Uuid> = recall_ids.into_iter().collect();
+    Ok(build_reference_graph(
+        &docs,
+        &scope,
+        root_entry_id,
+        depth.unwrap_or(DEFAULT_GRAPH_DEPTH),
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn doc(id: u128, key: &str, recall_id: u128, refs: &[&str]) -> GraphDocument {
+        GraphDocument {
+            id: Uuid::from_u128(id),
+            key: key.to_string(),
+            recall_id: Uuid::from_u128(recall_id),
+            recall_name: format!("base-{}", recall_id),
+            ref_keys: refs.iter().map(|s| s.to_string()).collect(),
+        }
+    }
+
+    #[test]
+    fn test_build_reference_graph() {
+        let docs = vec![
+            doc(1, "A", 100, &["B", "Gone"]),
+            doc(2, "B", 100, &["C"]),
+            doc(3, "C", 200, &["D"]),
+            doc(4, "D", 200, &[]),
+            doc(5, "B", 200, &[]),
+        ];
+
+        // 全量：同库优先解析，跨库目标作为外部节点带入，悬空引用被标记
+        let scope: HashSet<Uuid> = [Uuid::from_u128(100)].into_iter().collect();
+        let graph = build_reference_graph(&docs, &scope, None, 0);
+        let ids: Vec<u128> = graph.nodes.iter().map(|n| n.id.as_u128()).collect();
+        assert_eq!(ids, vec![1, 2, 3]);
+        assert!(graph.nodes[2].external);
+        assert_eq!(graph.edges.len(), 3);
+        assert_eq!(graph.edges[0].target, Some(Uuid::from_u128(2)));
+        assert!(graph.edges[1].dangling && graph.edges[1].target_key == "Gone");
+        assert!(graph.edges[2].cross_recall);
+
+        // 子图：从 C 出发 1 跳，双向扩展
+        let graph = build_reference_graph(&docs, &HashSet::new(), Some(Uuid::from_u128(3)), 1);
+        let mut ids: Vec<u128> = graph.nodes.iter().map(|n| n.id.as_u128()).collect();
+        ids.sort();
+        assert_eq!(ids, vec![2, 3, 4]);
+        assert_eq!(graph.edges.len(), 2);
+
+        assert!(
+            build_reference_graph(&docs, &HashSet::new(), Some(Uuid::from_u128(9)), 1)
+                .nodes
+                .is_empty()
+        );
+    }
+}
@@ -122,6 +122,7 @@ pub async fn recall_retrieval_cache_clear(state: State<'_, RecallState>) -> Resu
         .write()
         .map_err(|_| "获取检索缓存写锁失败".to_string())?;
     cache.clear();
+    state.clear_search_page_cache();
     Ok(())
 }
 
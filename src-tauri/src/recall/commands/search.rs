@@ -20,8 +20,16 @@ use crate::recall::monitor::{
     RecallMonitorLevel, RecallMonitorStep, RecallStepStatus,
 };
 use crate::recall::state::RecallState;
+use sha2::{Digest, Sha256};
 use tauri::{AppHandle, Manager, State};
 
+/// 分页检索时一次性计算的最大结果数
+const PAGINATED_MAX_RESULTS: usize = 500;
+/// 分页缓存的有效期（秒），过期后重新检索以反映条目变动
+const PAGINATED_CACHE_TTL_SECS: u64 = 300;
+/// 分页缓存最多保留的查询数
+const PAGINATED_CACHE_MAX_ITEMS: usize = 20;
+
 #[tauri::command]
 pub async fn recall_list_engines(
     state: State<'_, RecallState>,
@@ -39,8 +47,175 @@ pub async fn recall_search(
     vector_payload: Option<Vec<f32>>,
     model: Option<String>,
 ) -> Result<Vec<RecallResult>, String> {
-    let start_time = std::time::Instant::now();
     let id = engine_id.unwrap_or_else(|| "keyword".to_string());
+    execute_search(&app, &state, query, &filters, id, vector_payload, model)
+}
+
+/// 分页检索结果
+#[derive(Debug, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RecallSearchPage {
+    pub items: Vec<RecallResult>,
+    /// 全量排序结果数（受 PAGINATED_MAX_RESULTS 限制）
+    pub total: usize,
+    pub offset: usize,
+    pub limit: usize,
+    pub has_more: bool,
+    /// 本页是否来自缓存
+    pub cached: bool,
+}
+
+/// 分页检索
+///
+/// 首次查询时以较大上限跑一次检索并缓存全量排序结果，后续翻页按 offset/limit 切片返回。
+/// `refresh` 为 true 时忽略缓存重新检索。
+#[tauri::command]
+#[allow(clippy::too_many_arguments)]
+pub async fn recall_search_paginated(
+    app: AppHandle,
+    state: State<'_, RecallState>,
+    query: String,
+    filters: RecallSearchFilters,
+    offset: usize,
+    limit: usize,
+    engine_id: Option<String>,
+    vector_payload: Option<Vec<f32>>,
+    model: Option<String>,
+    refresh: Option<bool>,
+) -> Result<RecallSearchPage, String> {
+    let id = engine_id.unwrap_or_else(|| "keyword".to_string());
+    let key = build_page_cache_key(&query, &filters, &id, vector_payload.as_deref(), &model);
+    let now = now_secs();
+
+    let cached_results = if refresh.unwrap_or(false) {
+        None
+    } else {
+        let cache = state
+            .search_page_cache
+            .read()
+            .map_err(|_| "获取分页检索缓存读锁失败".to_string())?;
+        cache
+            .get(&key)
+            .filter(|(_, ts)| now.saturating_sub(*ts) < PAGINATED_CACHE_TTL_SECS)
+            .map(|(results, _)| results.clone())
+    };
+
+    let cached = cached_results.is_some();
+    let results = match cached_results {
+        Some(results) => results,
+        None => {
+            let mut full_filters = filters.clone();
+            full_filters.limit = Some(PAGINATED_MAX_RESULTS);
+            let results = execute_search(
+                &app,
+                &state,
+                query,
+                &full_filters,
+                id,
+                vector_payload,
+                model,
+            )?;
+
+            let mut cache = state
+                .search_page_cache
+                .write()
+                .map_err(|_| "获取分页检索缓存写锁失败".to_string())?;
+            cache.retain(|_, (_, ts)| now.saturating_sub(*ts) < PAGINATED_CACHE_TTL_SECS);
+            if cache.len() >= PAGINATED_CACHE_MAX_ITEMS {
+                if let Some(oldest) = cache
+                    .iter()
+                    .min_by_key(|(_, (_, ts))| *ts)
+                    .map(|(k, _)| k.clone())
+                {
+                    cache.remove(&oldest);
+                }
+            }
+            cache.insert(key, (results.clone(), now));
+            results
+        }
+    };
+
+    let total = results.len();
+    let items: Vec<RecallResult> = results.into_iter().skip(offset).take(limit).collect();
+
+    Ok(RecallSearchPage {
+        has_more: offset + items.len() < total,
+        items,
+        total,
+        offset,
+        limit,
+        cached,
+    })
+}
+
+fn now_secs() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs()
+}
+
+/// 分页缓存 Key：query + 除 limit 外的全部过滤条件 + 引擎 + 查询向量
+fn build_page_cache_key(
+    query: &str,
+    filters: &RecallSearchFilters,
+    engine_id: &str,
+    vector: Option<&[f32]>,
+    model: &Option<String>,
+) -> String {
+    let mut filters = filters.clone();
+    filters.limit = None;
+    let filters_json = serde_json::to_value(&filters)
+        .map(|v| canonical_json(&v))
+        .unwrap_or_default();
+
+    let mut hasher = Sha256::new();
+    hasher.update(query.as_bytes());
+    hasher.update(b"\0");
+    hasher.update(filters_json.as_bytes());
+    hasher.update(b"\0");
+    hasher.update(engine_id.as_bytes());
+    hasher.update(b"\0");
+    hasher.update(model.as_deref().unwrap_or_default().as_bytes());
+    hasher.update(b"\0");
+    for v in vector.unwrap_or_default() {
+        hasher.update(v.to_le_bytes());
+    }
+
+    format!("{:x}", hasher.finalize())
+}
+
+/// 对象键排序后序列化，保证 extra 等 HashMap 字段在不同请求间得到相同的字符串
+fn canonical_json(value: &serde_json::Value) -> String {
+    match value {
+        serde_json::Value::Object(map) => {
+            let mut keys: Vec<&String> = map.keys().collect();
+            keys.sort();
+            let fields: Vec<String> = keys
+                .into_iter()
+                .map(|k| format!("{:?}:{}", k, canonical_json(&map[k])))
+                .collect();
+            format!("{{{}}}", fields.join(","))
+        }
+        serde_json::Value::Array(items) => {
+            let items: Vec<String> = items.iter().map(canonical_json).collect();
+            format!("[{}]", items.join(","))
+        }
+        other => other.to_string(),
+    }
+}
+
+/// 执行一次检索并推送 RAG 监控事件
+fn execute_search(
+    app: &AppHandle,
+    state: &RecallState,
+    query: String,
+    filters: &RecallSearchFilters,
+    id: String,
+    vector_payload: Option<Vec<f32>>,
+    model: Option<String>,
+) -> Result<Vec<RecallResult>, String> {
+    let start_time = std::time::Instant::now();
 
     log::info!(
         "[KB_SEARCH] 接收检索请求: engine={}, query='{}', recall_ids={:?}, has_vector={}",
@@ -81,7 +256,7 @@ pub async fn recall_search(
 
     // 步骤2: 向量召回与初步过滤
     let recall_start = std::time::Instant::now();
-    let results = engine.search(&payload, &filters, &context)?;
+    let results = engine.search(&payload, filters, &context)?;
     let recall_duration = recall_start.elapsed().as_millis() as u64;
 
     // 推送监控事件
@@ -148,7 +323,7 @@ pub async fn recall_search(
     };
 
     let _ = emit_monitor_event(
-        &app,
+        app,
         RecallMonitorEvent::RAG(monitor_payload),
         RecallMonitorLevel::Info,
         "RAG 检索完成",
@@ -162,3 +337,31 @@ pub async fn recall_search(
 
     Ok(results)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_page_cache_key_ignores_limit_and_extra_order() {
+        let mut a = RecallSearchFilters::default();
+        a.extra.insert("x".to_string(), serde_json::json!(1));
+        a.extra.insert("y".to_string(), serde_json::json!(2));
+        let mut b = RecallSearchFilters {
+            limit: Some(5),
+            ..Default::default()
+        };
+        b.extra.insert("y".to_string(), serde_json::json!(2));
+        b.extra.insert("x".to_string(), serde_json::json!(1));
+
+        let model = Some("m".to_string());
+        assert_eq!(
+            build_page_cache_key("q", &a, "lens", None, &model),
+            build_page_cache_key("q", &b, "lens", None, &model)
+        );
+        assert_ne!(
+            build_page_cache_key("q", &a, "lens", None, &model),
+            build_page_cache_key("q", &a, "blender", None, &model)
+        );
+    }
+}
@@ -12,9 +12,14 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use crate::recall::core::TagWithWeight;
 use crate::recall::io::*;
+use crate::recall::ops::warmup_knowledge_base;
 use crate::recall::state::RecallState;
+use crate::recall::utils::{calculate_content_hash, get_now};
+use std::collections::HashSet;
 use tauri::{AppHandle, Manager, State};
+use uuid::Uuid;
 
 #[derive(serde::Serialize)]
 #[serde(rename_all = "camelCase")]
@@ -68,7 +73,7 @@ pub async fn recall_get_library_stats(
                 *tag_usage_stats.entry(tag.name.clone()).or_insert(0) += 1;
             }
 
-            if is_model_match && base.vector_store.ids.contains(&entry.id) {
+            if is_model_match && base.vector_store.contains(&entry.id) {
                 vectorized_entries += 1;
                 recall_vectorized += 1;
             }
@@ -319,3 +324,236 @@ pub async fn recall_flush_all_tag_pools(
 
     Ok(saved_count)
 }
+
+/// 标签合并时的权重处理策略
+#[derive(Debug, Clone, Copy, Default, PartialEq, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum TagWeightMergeStrategy {
+    /// 取最大权重
+    #[default]
+    Max,
+    /// 权重求和
+    Sum,
+}
+
+/// 将条目标签中命中 `sources` 的项替换为 `target`，返回是否有改动
+///
+/// 合并后的标签保留在首个命中位置；条目原本已有 `target` 时一并参与权重合并。
+fn merge_tags_in_entry(
+    tags: &mut Vec<TagWithWeight>,
+    sources: &HashSet<&str>,
+    target: &str,
+    strategy: TagWeightMergeStrategy,
+) -> bool {
+    if !tags.iter().any(|tag| sources.contains(tag.name.as_str())) {
+        return false;
+    }
+
+    let mut merged: Option<(usize, f32)> = None;
+    let mut kept = Vec::with_capacity(tags.len());
+    for tag in tags.drain(..) {
+        if tag.name != target && !sources.contains(tag.name.as_str()) {
+            kept.push(tag);
+            continue;
+        }
+        merged = Some(match merged {
+            None => (kept.len(), tag.weight),
+            Some((pos, weight)) => (
+                pos,
+                match strategy {
+                    TagWeightMergeStrategy::Max => weight.max(tag.weight),
+                    TagWeightMergeStrategy::Sum => weight + tag.weight,
+                },
+            ),
+        });
+    }
+
+    if let Some((pos, weight)) = merged {
+        kept.insert(
+            pos,
+            TagWithWeight {
+                name: target.to_string(),
+                weight,
+                hash: calculate_content_hash(target),
+            },
+        );
+    }
+    *tags = kept;
+    true
+}
+
+/// 合并思绪集中的标签
+///
+/// 命中的条目会同步落盘并更新元数据索引；已向量化模型的标签池中，
+/// 目标标签若尚无向量则复用源标签的向量。返回受影响的条目数。
+#[tauri::command]
+pub async fn recall_merge_tags(
+    app: AppHandle,
+    state: State<'_, RecallState>,
+    recall_id: Uuid,
+    sources: Vec<String>,
+    target: String,
+    strategy: Option<TagWeightMergeStrategy>,
+) -> Result<usize, String> {
+    let target = target.trim().to_string();
+    if target.is_empty() {
+        return Err("目标标签不能为空".to_string());
+    }
+    let sources: Vec<String> = sources
+        .into_iter()
+        .map(|source| source.trim().to_string())
+        .filter(|source| !source.is_empty() && *source != target)
+        .collect();
+    if sources.is_empty() {
+        return Ok(0);
+    }
+
+    let app_data_dir = app.path().app_data_dir().map_err(|e| e.to_string())?;
+    let recall_id_str = recall_id.to_string();
+    let base_lock = {
+        let imdb = state.imdb.read().map_err(|_| "获取内存数据库读锁失败")?;
+        imdb.bases
+            .get(&recall_id)
+            .cloned()
+            .ok_or_else(|| format!("找不到思绪集: {}", recall_id))?
+    };
+
+    // 条目尚未全量加载时先预热，避免漏改
+    let is_fully_loaded = base_lock
+        .read()
+        .map_err(|_| "获取思绪集读锁失败")?
+        .is_fully_loaded;
+    if !is_fully_loaded {
+        let recall_dir = get_recall_dir(&app_data_dir, &recall_id_str);
+        warmup_knowledge_base(&app_data_dir, &base_lock, &recall_dir)?;
+    }
+
+    let source_set: HashSet<&str> = sources.iter().map(String::as_str).collect();
+    let strategy = strategy.unwrap_or_default();
+    let now = get_now();
+
+    let mut base = base_lock.write().map_err(|_| "获取思绪集写锁失败")?;
+    // 先在副本上改写并全部落盘，成功后再同步到内存与索引，避免中途失败导致三者不一致
+    let mut changed = Vec::new();
+    for entry in base.entries.values() {
+        let mut tags = entry.tags.clone();
+        if merge_tags_in_entry(&mut tags, &source_set, &target, strategy) {
+            let mut updated = entry.clone();
+            updated.tags = tags;
+            updated.updated_at = now;
+            changed.push(updated);
+        }
+    }
+    for (index, entry) in changed.iter().enumerate() {
+        if let Err(e) = save_entry(&app_data_dir, &recall_id_str, entry) {
+            // 回滚已写入的条目文件，内存中的条目尚未改动
+            for written in &changed[..index] {
+                if let Some(original) = base.entries.get(&written.id) {
+                    if let Err(rollback_err) = save_entry(&app_data_dir, &recall_id_str, original) {
+                        log::error!("[KB_TAG] 回滚条目 {} 失败: {}", written.id, rollback_err);
+                    }
+                }
+            }
+            return Err(e);
+        }
+    }
+
+    let changed_count = changed.len();
+    for entry in changed {
+        base.sync_entry(entry);
+    }
+    if changed_count > 0 {
+        save_recall_meta(&app_data_dir, &recall_id_str, &base.meta)?;
+    }
+    let models = base.meta.models.clone();
+    drop(base);
+
+    for model_id in models {
+        let pool_lock = state.tag_pool.get_pool(&app_data_dir, &model_id)?;
+        let mut pool = pool_lock.write().map_err(|_| "获取池写锁失败")?;
+        if sources.iter().any(|source| pool.alias_tag(source, &target)) {
+            pool.save(&app_data_dir)?;
+        }
+    }
+
+    log::info!(
+        "[KB_TAG] 思绪集 {} 标签合并完成: {:?} -> {} (影响 {} 个条目)",
+        recall_id,
+        sources,
+        target,
+        changed_count
+    );
+    if changed_count > 0 {
+        state.clear_search_page_cache();
+    }
+    Ok(changed_count)
+}
+
+/// 重命名思绪集中的标签
+///
+/// 条目中已同时存在新旧标签时按合并处理。
+#[tauri::command]
+pub async fn recall_rename_tag(
+    app: AppHandle,
+    state: State<'_, RecallState>,
+    recall_id: Uuid,
+    from: String,
+    to: String,
+    strategy: Option<TagWeightMergeStrategy>,
+) -> Result<usize, String> {
+    recall_merge_tags(app, state, recall_id, vec![from], to, strategy).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tag(name: &str, weight: f32) -> TagWithWeight {
+        TagWithWeight {
+            name: name.to_string(),
+            weight,
+            hash: calculate_content_hash(name),
+        }
+    }
+
+    fn names(tags: &[TagWithWeight]) -> Vec<(&str, f32)> {
+        tags.iter().map(|t| (t.name.as_str(), t.weight)).collect()
+    }
+
+    #[test]
+    fn merge_tags_in_entry_combines_weights() {
+        let sources: HashSet<&str> = ["java", "JAVA"].into_iter().collect();
+
+        let mut tags = vec![
+            tag("rust", 1.0),
+            tag("java", 0.5),
+            tag("Java", 2.0),
+            tag("JAVA", 1.5),
+        ];
+        assert!(merge_tags_in_entry(
+            &mut tags,
+            &sources,
+            "Java",
+            TagWeightMergeStrategy::Max
+        ));
+        assert_eq!(names(&tags), vec![("rust", 1.0), ("Java", 2.0)]);
+        assert_eq!(tags[1].hash, calculate_content_hash("Java"));
+
+        let mut tags = vec![tag("java", 0.5), tag("rust", 1.0), tag("JAVA", 1.5)];
+        assert!(merge_tags_in_entry(
+            &mut tags,
+            &sources,
+            "Java",
+            TagWeightMergeStrategy::Sum
+        ));
+        assert_eq!(names(&tags), vec![("Java", 2.0), ("rust", 1.0)]);
+
+        let mut tags = vec![tag("Java", 1.0)];
+        assert!(!merge_tags_in_entry(
+            &mut tags,
+            &sources,
+            "Java",
+            TagWeightMergeStrategy::Max
+        ));
+    }
+}
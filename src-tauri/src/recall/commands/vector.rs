@@ -12,6 +12,7 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use crate::recall::index::vector_matrix::VectorUpsertOutcome;
 use crate::recall::io::*;
 use crate::recall::monitor::{
     emit_monitor_event, IndexMetadata, IndexPayload, IndexStats, RecallMonitorEvent,
@@ -75,14 +76,27 @@ pub async fn recall_update_entry_vector(
     if let Some(base_lock) = imdb.bases.get(&recall_id) {
         let mut base = base_lock.write().map_err(|_| "获取思绪集写锁失败")?;
 
-        // 1. 更新向量矩阵
-        if base.vector_store.model_id.is_empty() || base.vector_store.model_id == model {
-            if base.vector_store.model_id.is_empty() {
-                base.vector_store.model_id = model.clone();
-                base.vector_store.dimension = vector.len();
+        // 1. 增量更新向量矩阵，仅维度变化时从磁盘全量重建
+        let outcome = base.vector_store.upsert_one(&model, entry_id, &vector);
+        if outcome == VectorUpsertOutcome::DimensionChanged {
+            log::info!(
+                "[KB_VECTOR] 模型维度变化 ({} -> {})，全量重建向量矩阵: recall={}, model={}",
+                base.vector_store.dimension,
+                vector.len(),
+                recall_id_str,
+                model
+            );
+            // 旧维度的向量文件会在重建时被过滤，需要重新向量化
+            if let Ok(Some((vectors, _, total_tokens))) =
+                load_vectors_to_vec(&app_data_dir, recall_id, &model)
+            {
+                base.vector_store
+                    .rebuild(model.clone(), vector.len(), total_tokens, vectors);
             }
-            base.vector_store.update_vector(entry_id, vector);
+            base.refresh_vector_status();
         }
+        // 向量矩阵已变化，缓存的分页检索结果不再可信
+        state.clear_search_page_cache();
 
         // 2. 更新索引中的向量化状态 (仅索引处理状态)
         if !base.meta.models.contains(&model) {
@@ -13,6 +13,7 @@
 // limitations under the License.
 
 #![allow(dead_code)]
+use crate::recall::utils::timestamp_to_millis;
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
@@ -147,6 +148,18 @@ pub struct RecallEntryIndexItem {
     pub total_tokens: u32,
 }
 
+/// 条目历史版本概要 (用于版本列表展示)
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct RecallEntryVersion {
+    /// 版本号，即快照时间戳 (毫秒)
+    pub version: i64,
+    pub key: String,
+    pub summary: String,
+    pub content_hash: Option<String>,
+    pub updated_at: i64,
+}
+
 /// 带权重的标签
 #[derive(Debug, Serialize, Deserialize, Clone)]
 #[serde(rename_all = "camelCase")]
@@ -297,7 +310,24 @@ pub struct RecallResult {
     pub match_type: String, // "vector" 或 "keyword"
     pub recall_id: Uuid,
     pub recall_name: String,
-    pub highlight: Option<String>, // 匹配片段高亮
+    pub highlight: Option<RecallHighlight>, // 匹配片段高亮
+}
+
+/// 检索结果中的高亮片段
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct RecallHighlight {
+    /// 截取的上下文片段，被截断的一侧带有 "..."
+    pub snippet: String,
+    /// 需要高亮的区间，基于 snippet 的 UTF-16 码元偏移（与 JS 字符串下标一致）
+    pub ranges: Vec<HighlightRange>,
+}
+
+/// 高亮区间 [start, end)
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct HighlightRange {
+    pub start: usize,
+    pub end: usize,
 }
 
 /// 搜索过滤器
@@ -309,6 +339,10 @@ pub struct RecallSearchFilters {
     pub limit: Option<usize>,
     pub min_score: Option<f32>,
     pub enabled_only: Option<bool>,
+    /// 仅检索在此时间之后创建的条目 (秒或毫秒级时间戳均可，比较前统一换算为毫秒)
+    pub created_after: Option<i64>,
+    /// 仅检索在此时间之后更新的条目 (秒或毫秒级时间戳均可，比较前统一换算为毫秒)
+    pub updated_after: Option<i64>,
     /// 透镜检索：纹理 (coarse/fine)
     pub texture: Option<String>,
     /// 透镜检索：折射率 (0.0 - 1.0)
@@ -334,6 +368,8 @@ impl Default for RecallSearchFilters {
             limit: Some(20),
             min_score: None,
             enabled_only: Some(true),
+            created_after: None,
+            updated_after: None,
             texture: None,
             refraction_index: None,
             required_tags: None,
@@ -345,6 +381,30 @@ impl Default for RecallSearchFilters {
     }
 }
 
+impl RecallSearchFilters {
+    /// 条目是否满足启用状态与时间范围过滤
+    pub fn accepts_entry(&self, entry: &RecallEntry) -> bool {
+        if self.enabled_only.unwrap_or(true) && !entry.enabled {
+            return false;
+        }
+        // 条目时间戳为秒，前端传入的过滤值可能是毫秒，两侧统一换算后再比较
+        let before = |time: i64, after: i64| timestamp_to_millis(time) < timestamp_to_millis(after);
+        if self
+            .created_after
+            .is_some_and(|after| before(entry.created_at, after))
+        {
+            return false;
+        }
+        if self
+            .updated_after
+            .is_some_and(|after| before(entry.updated_at, after))
+        {
+            return false;
+        }
+        true
+    }
+}
+
 /// 检索上下文，提供引擎运行所需的环境信息
 pub struct RetrievalContext {
     /// 内存数据库的引用
@@ -522,4 +582,60 @@ mod tests {
         assert!(deserialized.tags.is_empty());
         assert!(deserialized.assets.is_empty());
     }
+
+    #[test]
+    fn test_search_filters_accept_entry_by_time_range() {
+        let mut entry: RecallEntry = serde_json::from_value(serde_json::json!({
+            "id": Uuid::new_v4(),
+            "key": "k",
+            "content": "c",
+            "contentHash": null,
+            "createdAt": 1_000,
+            "updatedAt": 2_000,
+        }))
+        .unwrap();
+
+        let mut filters = RecallSearchFilters::default();
+        assert!(filters.accepts_entry(&entry));
+
+        filters.created_after = Some(1_000);
+        assert!(filters.accepts_entry(&entry));
+        filters.created_after = Some(1_001);
+        assert!(!filters.accepts_entry(&entry));
+
+        filters.created_after = None;
+        filters.updated_after = Some(2_500);
+        assert!(!filters.accepts_entry(&entry));
+
+        filters.updated_after = None;
+        entry.enabled = false;
+        assert!(!filters.accepts_entry(&entry));
+        filters.enabled_only = Some(false);
+        assert!(filters.accepts_entry(&entry));
+    }
+
+    #[test]
+    fn test_search_filters_compare_seconds_with_millis() {
+        // 条目使用秒级时间戳，过滤值为毫秒
+        let entry: RecallEntry = serde_json::from_value(serde_json::json!({
+            "id": Uuid::new_v4(),
+            "key": "k",
+            "content": "c",
+            "contentHash": null,
+            "createdAt": 1_706_600_000,
+            "updatedAt": 1_706_600_000,
+        }))
+        .unwrap();
+
+        let mut filters = RecallSearchFilters::default();
+        filters.created_after = Some(1_706_599_000_000);
+        assert!(filters.accepts_entry(&entry));
+        filters.created_after = Some(1_706_601_000_000);
+        assert!(!filters.accepts_entry(&entry));
+
+        // 过滤值为秒时同样生效
+        filters.created_after = None;
+        filters.updated_after = Some(1_706_600_001);
+        assert!(!filters.accepts_entry(&entry));
+    }
 }
@@ -0,0 +1,373 @@
+// Copyright 2025-2026 miaotouy(Github@miaotouy)
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! 导入文件的文本提取
+//!
+//! 纯文本文件直接读取；DOCX / EPUB 本质是 ZIP + XML，解压后抽取正文文本。
+//! 其余二进制格式（含 PDF）暂不支持，会以明确的原因返回给调用方。
+
+use quick_xml::events::Event;
+use quick_xml::Reader;
+use serde::Serialize;
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::Read;
+use std::path::Path;
+use zip::ZipArchive;
+
+/// 单个压缩包内 XML 文件的读取上限，防止恶意压缩包撑爆内存
+const MAX_XML_ENTRY_SIZE: u64 = 64 * 1024 * 1024;
+
+/// 导入时文件被跳过的原因
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum ImportSkipReason {
+    /// 非文本且不支持提取的格式
+    NotText,
+    /// 支持的格式，但读取或解析失败
+    ExtractFailed,
+}
+
+/// 被跳过的导入文件及原因
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SkippedImportFile {
+    pub path: String,
+    pub reason: ImportSkipReason,
+    pub message: String,
+}
+
+/// 提取文件中可入库的纯文本
+pub fn extract_import_text(path: &Path) -> Result<String, (ImportSkipReason, String)> {
+    let extension = path
+        .extension()
+        .and_then(|ext| ext.to_str())
+        .map(|ext| ext.to_ascii_lowercase())
+        .unwrap_or_default();
+
+    let text = match extension.as_str() {
+        "docx" => extract_docx_text(path),
+        "epub" => extract_epub_text(path),
+        "pdf" => {
+            return Err((
+                ImportSkipReason::NotText,
+                "暂不支持 PDF 文本提取".to_string(),
+            ))
+        }
+        _ if crate::utils::mime::is_text_file(path) => {
+            std::fs::read_to_string(path).map_err(|e| format!("读取文件失败: {}", e))
+        }
+        _ => {
+            return Err((
+                ImportSkipReason::NotText,
+                "非文本文件，且不是支持提取的文档格式".to_string(),
+            ))
+        }
+    }
+    .map_err(|message| (ImportSkipReason::ExtractFailed, message))?;
+
+    if text.trim().is_empty() {
+        return Err((
+            ImportSkipReason::ExtractFailed,
+            "未提取到文本内容".to_string(),
+        ));
+    }
+    Ok(text)
+}
+
+fn read_zip_entry(archive: &mut ZipArchive<File>, name: &str) -> Result<String, String> {
+    let entry = archive
+        .by_name(name)
+        .map_err(|e| format!("缺少 {}: {}", name, e))?;
+    if entry.size() > MAX_XML_ENTRY_SIZE {
+        return Err(format!("{} 过大", name));
+    }
+    let mut content = String::new();
+    entry
+        .take(MAX_XML_ENTRY_SIZE)
+        .read_to_string(&mut content)
+        .map_err(|e| format!("读取 {} 失败: {}", name, e))?;
+    Ok(content)
+}
+
+fn open_zip(path: &Path) -> Result<ZipArchive<File>, String> {
+    let file = File::open(path).map_err(|e| format!("打开文件失败: {}", e))?;
+    ZipArchive::new(file).map_err(|e| format!("不是有效的压缩文档: {}", e))
+}
+
+fn extract_docx_text(path: &Path) -> Result<String, String> {
+    let mut archive = open_zip(path)?;
+    docx_xml_to_text(&read_zip_entry(&mut archive, "word/document.xml")?)
+}
+
+fn extract_epub_text(path: &Path) -> Result<String, String> {
+    let mut archive = open_zip(path)?;
+    let container = read_zip_entry(&mut archive, "META-INF/container.xml")?;
+    let opf_path = find_epub_rootfile(&container)?;
+    let opf = read_zip_entry(&mut archive, &opf_path)?;
+
+    let base_dir = opf_path
+        .rsplit_once('/')
+        .map(|(dir, _)| format!("{}/", dir))
+        .unwrap_or_default();
+
+    let mut chapters = Vec::new();
+    for href in epub_spine_hrefs(&opf)? {
+        let chapter_path = normalize_zip_path(&format!("{}{}", base_dir, href));
+        match read_zip_entry(&mut archive, &chapter_path) {
+            Ok(xhtml) => {
+                let text = xhtml_to_text(&xhtml)?;
+                if !text.is_empty() {
+                    chapters.push(text);
+                }
+            }
+            Err(e) => log::warn!("[KB_IMPORT] 跳过 EPUB 章节 {}: {}", chapter_path, e),
+        }
+    }
+    Ok(chapters.join("\n\n"))
+}
+
+/// 解析压缩包内的相对路径（处理 `./` 与 `../`）
+fn normalize_zip_path(path: &str) -> String {
+    let mut parts: Vec<&str> = Vec::new();
+    for part in path.split('/') {
+        match part {
+            "" | "." => {}
+            ".." => {
+                parts.pop();
+            }
+            part => parts.push(part),
+        }
+    }
+    parts.join("/")
+}
+
+fn xml_reader(xml: &str) -> Reader<&[u8]> {
+    let mut reader = Reader::from_str(xml);
+    reader.config_mut().trim_text(false);
+    reader
+}
+
+/// 解析实体引用，未知实体原样保留
+fn resolve_entity(reference: &quick_xml::events::BytesRef) -> String {
+    if let Ok(Some(ch)) = reference.resolve_char_ref() {
+        return ch.to_string();
+    }
+    let name = reference.decode().unwrap_or_default();
+    match quick_xml::escape::resolve_predefined_entity(&name) {
+        Some(value) => value.to_string(),
+        None if name == "nbsp" => " ".to_string(),
+        None => format!("&{};", name),
+    }
+}
+
+fn docx_xml_to_text(xml: &str) -> Result<String, String> {
+    let mut reader = xml_reader(xml);
+    let mut output = String::new();
+    let mut in_text = false;
+
+    loop {
+        match reader.read_event() {
+            Ok(Event::Start(e)) if e.local_name().as_ref() == b"t" => in_text = true,
+            Ok(Event::End(e)) => match e.local_name().as_ref() {
+                b"t" => in_text = false,
+                b"p" => output.push('\n'),
+                _ => {}
+            },
+            Ok(Event::Empty(e)) => match e.local_name().as_ref() {
+                b"tab" => output.push('\t'),
+                b"br" | b"cr" => output.push('\n'),
+                _ => {}
+            },
+            Ok(Event::Text(t)) if in_text => {
+                output.push_str(&t.decode().map_err(|e| format!("解析 DOCX 失败: {}", e))?)
+            }
+            Ok(Event::GeneralRef(r)) if in_text => output.push_str(&resolve_entity(&r)),
+            Ok(Event::Eof) => break,
+            Err(e) => return Err(format!("解析 DOCX 失败: {}", e)),
+            _ => {}
+        }
+    }
+
+    Ok(output.trim().to_string())
+}
+
+fn find_epub_rootfile(container: &str) -> Result<String, String> {
+    let mut reader = xml_reader(container);
+    loop {
+        match reader.read_event() {
+            Ok(Event::Start(e)) | Ok(Event::Empty(e)) if e.local_name().as_ref() == b"rootfile" => {
+                if let Ok(Some(attr)) = e.try_get_attribute("full-path") {
+                    let value = attr
+                        .unescape_value()
+                        .map_err(|e| format!("解析 EPUB 容器失败: {}", e))?;
+                    return Ok(value.to_string());
+                }
+            }
+            Ok(Event::Eof) => return Err("EPUB 缺少 rootfile 声明".to_string()),
+            Err(e) => return Err(format!("解析 EPUB 容器失败: {}", e)),
+            _ => {}
+        }
+    }
+}
+
+/// 按 spine 顺序返回章节文件的 href
+fn epub_spine_hrefs(opf: &str) -> Result<Vec<String>, String> {
+    let mut reader = xml_reader(opf);
+    let mut manifest = HashMap::new();
+    let mut spine = Vec::new();
+
+    loop {
+        match reader.read_event() {
+            Ok(Event::Start(e)) | Ok(Event::Empty(e)) => {
+                let attr = |name: &str| {
+                    e.try_get_attribute(name)
+                        .ok()
+                        .flatten()
+                        .and_then(|a| a.unescape_value().ok())
+                        .map(|v| v.to_string())
+                };
+                match e.local_name().as_ref() {
+                    b"item" => {
+                        if let (Some(id), Some(href)) = (attr("id"), attr("href")) {
+                            manifest.insert(id, href);
+                        }
+                    }
+                    b"itemref" => {
+                        if let Some(idref) = attr("idref") {
+                            spine.push(idref);
+                        }
+                    }
+                    _ => {}
+                }
+            }
+            Ok(Event::Eof) => break,
+            Err(e) => return Err(format!("解析 EPUB 目录失败: {}", e)),
+            _ => {}
+        }
+    }
+
+    Ok(spine
+        .into_iter()
+        .filter_map(|idref| manifest.get(&idref).cloned())
+        .collect())
+}
+
+fn xhtml_to_text(xhtml: &str) -> Result<String, String> {
+    let mut reader = xml_reader(xhtml);
+    let mut output = String::new();
+    let mut in_body = false;
+    let mut skip_depth = 0usize;
+
+    let is_block = |name: &[u8]| {
+        matches!(
+            name,
+            b"p" | b"div"
+                | b"h1"
+                | b"h2"
+                | b"h3"
+                | b"h4"
+                | b"h5"
+                | b"h6"
+                | b"li"
+                | b"tr"
+                | b"blockquote"
+                | b"pre"
+                | b"section"
+        )
+    };
+
+    loop {
+        match reader.read_event() {
+            Ok(Event::Start(e)) => match e.local_name().as_ref() {
+                b"body" => in_body = true,
+                b"script" | b"style" => skip_depth += 1,
+                _ => {}
+            },
+            Ok(Event::End(e)) => {
+                let name = e.local_name();
+                match name.as_ref() {
+                    b"body" => in_body = false,
+                    b"script" | b"style" => skip_depth = skip_depth.saturating_sub(1),
+                    name if is_block(name) && !output.ends_with('\n') => output.push('\n'),
+                    _ => {}
+                }
+            }
+            Ok(Event::Empty(e)) if e.local_name().as_ref() == b"br" => output.push('\n'),
+            Ok(Event::Text(t)) if in_body && skip_depth == 0 => {
+                output.push_str(&t.decode().map_err(|e| format!("解析章节失败: {}", e))?)
+            }
+            Ok(Event::CData(t)) if in_body && skip_depth == 0 => {
+                output.push_str(&String::from_utf8_lossy(&t))
+            }
+            Ok(Event::GeneralRef(r)) if in_body && skip_depth == 0 => {
+                output.push_str(&resolve_entity(&r))
+            }
+            Ok(Event::Eof) => break,
+            Err(e) => return Err(format!("解析章节失败: {}", e)),
+            _ => {}
+        }
+    }
+
+    // 合并块级元素间产生的多余空行
+    let lines: Vec<&str> = output
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+        .collect();
+    Ok(lines.join("\n"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_docx_xml_to_text() {
+        let xml = r#"<w:document xmlns:w="w"><w:body>
+            <w:p><w:r><w:t>第一段</w:t></w:r><w:r><w:tab/><w:t xml:space="preserve"> A &amp; B</w:t></w:r></w:p>
+            <w:p><w:r><w:t>第二段</w:t><w:br/><w:t>换行</w:t></w:r></w:p>
+        </w:body></w:document>"#;
+        assert_eq!(
+            docx_xml_to_text(xml).unwrap(),
+            "第一段\t A & B\n第二段\n换行"
+        );
+    }
+
+    #[test]
+    fn test_epub_parsing() {
+        let container = r#"<container><rootfiles><rootfile full-path="OEBPS/content.opf" media-type="application/oebps-package+xml"/></rootfiles></container>"#;
+        assert_eq!(find_epub_rootfile(container).unwrap(), "OEBPS/content.opf");
+
+        let opf = r#"<package><manifest>
+            <item id="c2" href="text/ch2.xhtml"/><item id="c1" href="text/ch1.xhtml"/>
+        </manifest><spine><itemref idref="c1"/><itemref idref="c2"/></spine></package>"#;
+        assert_eq!(
+            epub_spine_hrefs(opf).unwrap(),
+            vec!["text/ch1.xhtml", "text/ch2.xhtml"]
+        );
+        assert_eq!(
+            normalize_zip_path("OEBPS/text/../img/./a.png"),
+            "OEBPS/img/a.png"
+        );
+
+        let xhtml = r#"<html><head><title>忽略</title><style>p{}</style></head><body>
+            <h1>标题</h1><p>正文&#x4E00;<b>加粗</b></p><script>x()</script><p>第二行<br/>第三行</p>
+        </body></html>"#;
+        assert_eq!(
+            xhtml_to_text(xhtml).unwrap(),
+            "标题\n正文一加粗\n第二行\n第三行"
+        );
+    }
+}
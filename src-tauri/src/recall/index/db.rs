@@ -15,6 +15,7 @@
 use super::inverted_index::TextInvertedIndex;
 use super::vector_matrix::VectorMatrix;
 use crate::recall::core::{RecallCollectionMeta, RecallEntry, RecallEntryIndexItem};
+use crate::recall::tag_sea::TagSea;
 use std::collections::HashMap;
 use std::sync::{Arc, RwLock};
 use uuid::Uuid;
@@ -47,6 +48,8 @@ pub struct InMemoryBase {
     /// 向量存储系统 (用于基础 Vector 引擎)
     #[allow(dead_code)]
     pub vector_store: VectorMatrix,
+    /// TagSea 缓存 (条目增删改时失效，下次检索时重建)
+    tag_sea: Option<Arc<TagSea>>,
 }
 
 impl InMemoryBase {
@@ -58,12 +61,24 @@ impl InMemoryBase {
             key_to_id: HashMap::new(),
             text_index: TextInvertedIndex::new(),
             vector_store: VectorMatrix::new(),
+            tag_sea: None,
         }
     }
 
+    /// 获取 TagSea，缓存失效时重建
+    pub fn tag_sea(&mut self) -> Arc<TagSea> {
+        if let Some(tag_sea) = &self.tag_sea {
+            return tag_sea.clone();
+        }
+        let tag_sea = Arc::new(TagSea::build(self));
+        self.tag_sea = Some(tag_sea.clone());
+        tag_sea
+    }
+
     /// 增量同步一个条目
     pub fn sync_entry(&mut self, entry: RecallEntry) {
         let id = entry.id;
+        self.tag_sea = None;
 
         // 1. 更新 Key 映射
         if !entry.key.is_empty() {
@@ -76,7 +91,7 @@ impl InMemoryBase {
         // 3. 更新元数据中的索引项 (保持同步)
         // 注意：条目内容加载不应覆盖索引中的 vector_status
         // 但我们需要根据内存中已加载的向量库状态进行实时补偿
-        let is_vectorized = self.vector_store.ids.contains(&id);
+        let is_vectorized = self.vector_store.contains(&id);
         let current_model = self.vector_store.model_id.clone();
 
         if let Some(pos) = self.meta.entries.iter().position(|e| e.id == id) {
@@ -106,6 +121,8 @@ impl InMemoryBase {
                 existing.content_hash = entry.content_hash.clone();
                 existing.vector_status = "none".to_string();
                 existing.vectorized_models.clear();
+                // 旧向量已随文件删除，同步移除内存中的矩阵行
+                self.vector_store.remove_one(&id);
             } else if is_vectorized {
                 // 如果内容没变，且内存中已有向量，确保状态为 ready
                 existing.vector_status = "ready".to_string();
@@ -165,7 +182,7 @@ impl InMemoryBase {
 
         let mut changed = false;
         for entry in &mut self.meta.entries {
-            if self.vector_store.ids.contains(&entry.id) {
+            if self.vector_store.contains(&entry.id) {
                 if entry.vector_status != "ready" {
                     entry.vector_status = "ready".to_string();
                     changed = true;
@@ -183,6 +200,7 @@ impl InMemoryBase {
     pub fn remove_entry(&mut self, id: &Uuid) {
         // 1. 从条目详情缓存中移除
         if let Some(entry) = self.entries.remove(id) {
+            self.tag_sea = None;
             // 2. 从 Key 索引中移除
             if !entry.key.is_empty() {
                 self.key_to_id.remove(&entry.key);
@@ -190,7 +208,7 @@ impl InMemoryBase {
             // 3. 从文本倒排索引中移除
             self.text_index.remove_entry(id);
             // 4. 从向量矩阵中移除
-            self.vector_store.remove_vector(id);
+            self.vector_store.remove_one(id);
             // 5. 从元数据索引列表中移除
             self.meta.entries.retain(|e| e.id != *id);
         }
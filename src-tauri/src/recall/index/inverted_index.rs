@@ -19,7 +19,7 @@ use std::collections::HashMap;
 use uuid::Uuid;
 
 lazy_static! {
-    static ref JIEBA: Jieba = Jieba::new();
+    pub(crate) static ref JIEBA: Jieba = Jieba::new();
 }
 
 /// 文本倒排索引，用于关键词和标签检索
@@ -28,6 +28,8 @@ pub struct TextInvertedIndex {
     pub tag_index: HashMap<String, Vec<Uuid>>,
     /// 词项索引：Term -> (RecallEntryID, Frequency)
     pub term_index: HashMap<String, Vec<(Uuid, u32)>>,
+    /// 文档长度：RecallEntryID -> 参与索引的词项总数（BM25 长度归一化使用）
+    pub doc_lengths: HashMap<Uuid, u32>,
 }
 
 impl TextInvertedIndex {
@@ -35,6 +37,7 @@ impl TextInvertedIndex {
         Self {
             tag_index: HashMap::new(),
             term_index: HashMap::new(),
+            doc_lengths: HashMap::new(),
         }
     }
 
@@ -42,6 +45,11 @@ impl TextInvertedIndex {
     pub fn index_entry(&mut self, entry: &RecallEntry) {
         let id = entry.id;
 
+        // 重复索引同一条目时先清掉旧的倒排项，避免词频重复累加
+        if self.doc_lengths.contains_key(&id) {
+            self.remove_entry(&id);
+        }
+
         // 1. 处理标签
         for tag in &entry.tags {
             self.tag_index.entry(tag.name.clone()).or_default().push(id);
@@ -59,6 +67,7 @@ impl TextInvertedIndex {
             *frequencies.entry(word).or_insert(0) += 1;
         }
 
+        self.doc_lengths.insert(id, frequencies.values().sum());
         for (term, freq) in frequencies {
             self.term_index.entry(term).or_default().push((id, freq));
         }
@@ -74,6 +83,59 @@ impl TextInvertedIndex {
         for entries in self.term_index.values_mut() {
             entries.retain(|&(x, _)| x != *id);
         }
+        self.doc_lengths.remove(id);
+    }
+
+    /// 按与索引一致的规则切分查询，返回去重后的词项
+    pub fn query_terms(query: &str) -> Vec<String> {
+        let mut terms: Vec<String> = Vec::new();
+        for word in JIEBA.cut(&query.to_lowercase(), false) {
+            let word = word.trim();
+            if word.is_empty() || word.len() < 2 {
+                continue;
+            }
+            if !terms.iter().any(|t| t == word) {
+                terms.push(word.to_string());
+            }
+        }
+        terms
+    }
+
+    /// 标准 Okapi BM25 评分，返回按分数降序排列的 (条目 ID, 评分) 列表
+    ///
+    /// idf = ln(1 + (N - df + 0.5) / (df + 0.5))，
+    /// tf 部分 = f * (k1 + 1) / (f + k1 * (1 - b + b * dl / avgdl))
+    pub fn bm25_search(&self, query: &str, k1: f32, b: f32) -> Vec<(Uuid, f32)> {
+        let doc_count = self.doc_lengths.len();
+        if doc_count == 0 {
+            return Vec::new();
+        }
+        let avg_doc_len =
+            (self.doc_lengths.values().map(|&l| l as f32).sum::<f32>() / doc_count as f32).max(1.0);
+
+        let mut scores: HashMap<Uuid, f32> = HashMap::new();
+        for term in Self::query_terms(query) {
+            let Some(postings) = self.term_index.get(&term) else {
+                continue;
+            };
+            if postings.is_empty() {
+                continue;
+            }
+
+            let df = postings.len() as f32;
+            let idf = (1.0 + (doc_count as f32 - df + 0.5) / (df + 0.5)).ln();
+
+            for (id, freq) in postings {
+                let tf = *freq as f32;
+                let doc_len = self.doc_lengths.get(id).copied().unwrap_or(0) as f32;
+                let norm = 1.0 - b + b * (doc_len / avg_doc_len);
+                *scores.entry(*id).or_insert(0.0) += idf * tf * (k1 + 1.0) / (tf + k1 * norm);
+            }
+        }
+
+        let mut result: Vec<(Uuid, f32)> = scores.into_iter().collect();
+        result.sort_by(|a, b| b.1.partial_cmp(&a.1).unwrap_or(std::cmp::Ordering::Equal));
+        result
     }
 
     /// 搜索关键词，返回 (条目 ID, 评分) 列表
@@ -109,3 +171,58 @@ impl TextInvertedIndex {
         result
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(content: &str) -> RecallEntry {
+        RecallEntry {
+            id: Uuid::new_v4(),
+            key: String::new(),
+            content: content.to_string(),
+            summary: String::new(),
+            core_tags: vec![],
+            tags: vec![],
+            assets: vec![],
+            priority: 100,
+            enabled: true,
+            created_at: 0,
+            updated_at: 0,
+            error_message: None,
+            content_hash: None,
+            refs: vec![],
+            ref_by: vec![],
+        }
+    }
+
+    #[test]
+    fn test_bm25_search() {
+        let mut index = TextInvertedIndex::new();
+        let short = entry("rust async runtime");
+        let long =
+            entry("rust async runtime with many other unrelated words padding padding padding");
+        let other = entry("python scripting language");
+        for e in [&short, &long, &other] {
+            index.index_entry(e);
+        }
+
+        let results = index.bm25_search("rust runtime", 1.2, 0.75);
+        assert_eq!(results.len(), 2);
+        // 词频相同时，较短的文档得分更高
+        assert_eq!(results[0].0, short.id);
+        assert!(results[0].1 > results[1].1);
+
+        // b = 0 时不做长度归一化，两者得分相同
+        let results = index.bm25_search("rust runtime", 1.2, 0.0);
+        assert!((results[0].1 - results[1].1).abs() < 1e-6);
+
+        // 重复索引不应累加词频
+        index.index_entry(&short);
+        assert_eq!(index.term_index["rust"].len(), 2);
+        assert_eq!(index.doc_lengths.len(), 3);
+
+        index.remove_entry(&other.id);
+        assert!(index.bm25_search("python", 1.2, 0.75).is_empty());
+    }
+}
@@ -12,8 +12,20 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::collections::HashMap;
 use uuid::Uuid;
 
+/// 单个向量增量写入的结果
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum VectorUpsertOutcome {
+    Inserted,
+    Updated,
+    /// 向量不属于当前矩阵的模型，未写入 (切换模型时由全量加载负责)
+    ModelMismatch,
+    /// 同一模型的维度发生变化，需要全量重建矩阵
+    DimensionChanged,
+}
+
 /// 向量矩阵，用于语义相似度计算
 #[allow(dead_code)]
 pub struct VectorMatrix {
@@ -24,6 +36,8 @@ pub struct VectorMatrix {
     pub data: Vec<f32>,
     pub dimension: usize,
     pub total_tokens: usize,
+    /// 条目 ID -> 行号，供增量更新定位
+    positions: HashMap<Uuid, usize>,
 }
 
 impl VectorMatrix {
@@ -34,6 +48,7 @@ impl VectorMatrix {
             data: Vec::new(),
             dimension: 0,
             total_tokens: 0,
+            positions: HashMap::new(),
         }
     }
 
@@ -51,44 +66,74 @@ impl VectorMatrix {
         self.total_tokens = total_tokens;
         self.ids.clear();
         self.data.clear();
+        self.positions.clear();
 
         for (id, vector) in entries {
             if vector.len() == dimension {
-                self.ids.push(id);
-                self.data.extend_from_slice(&vector);
+                self.push_row(id, &vector);
             }
         }
     }
 
-    /// 更新或添加单个向量
-    #[allow(dead_code)]
-    pub fn update_vector(&mut self, id: Uuid, vector: Vec<f32>) {
-        if self.dimension == 0 {
-            self.dimension = vector.len();
-        }
+    /// 是否包含某条目的向量
+    pub fn contains(&self, id: &Uuid) -> bool {
+        self.positions.contains_key(id)
+    }
 
-        if vector.len() != self.dimension {
+    fn push_row(&mut self, id: Uuid, vector: &[f32]) {
+        if let Some(&pos) = self.positions.get(&id) {
+            let start = pos * self.dimension;
+            self.data[start..start + self.dimension].copy_from_slice(vector);
             return;
         }
+        self.positions.insert(id, self.ids.len());
+        self.ids.push(id);
+        self.data.extend_from_slice(vector);
+    }
 
-        if let Some(pos) = self.ids.iter().position(|&x| x == id) {
-            let start = pos * self.dimension;
-            let end = start + self.dimension;
-            self.data[start..end].copy_from_slice(&vector);
+    /// 增量更新或追加单个向量
+    ///
+    /// 空矩阵直接采用该向量的模型与维度；模型或维度不一致时不写入，
+    /// 由调用方决定是否全量重建。
+    pub fn upsert_one(&mut self, model_id: &str, id: Uuid, vector: &[f32]) -> VectorUpsertOutcome {
+        if self.ids.is_empty() {
+            if !self.model_id.is_empty() && self.model_id != model_id {
+                return VectorUpsertOutcome::ModelMismatch;
+            }
+            self.model_id = model_id.to_string();
+            self.dimension = vector.len();
+        } else if self.model_id != model_id {
+            return VectorUpsertOutcome::ModelMismatch;
+        } else if vector.len() != self.dimension {
+            return VectorUpsertOutcome::DimensionChanged;
+        }
+
+        let existed = self.contains(&id);
+        self.push_row(id, vector);
+        if existed {
+            VectorUpsertOutcome::Updated
         } else {
-            self.ids.push(id);
-            self.data.extend_from_slice(&vector);
+            VectorUpsertOutcome::Inserted
         }
     }
 
-    /// 移除单个向量
-    pub fn remove_vector(&mut self, id: &Uuid) {
-        if let Some(pos) = self.ids.iter().position(|x| x == id) {
-            self.ids.remove(pos);
-            let start = pos * self.dimension;
-            let end = start + self.dimension;
-            self.data.drain(start..end);
+    /// 移除单个向量 (末行换入空位，避免整体搬移数据)
+    pub fn remove_one(&mut self, id: &Uuid) -> bool {
+        let Some(pos) = self.positions.remove(id) else {
+            return false;
+        };
+        let last = self.ids.len() - 1;
+        if pos != last {
+            let dim = self.dimension;
+            self.data
+                .copy_within(last * dim..(last + 1) * dim, pos * dim);
+            let moved = self.ids[last];
+            self.ids[pos] = moved;
+            self.positions.insert(moved, pos);
         }
+        self.ids.pop();
+        self.data.truncate(last * self.dimension);
+        true
     }
 
     /// 获取单个向量
@@ -102,3 +147,53 @@ impl VectorMatrix {
         Some(&self.data[start..end])
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_incremental_upsert_and_remove() {
+        let a = Uuid::from_u128(1);
+        let b = Uuid::from_u128(2);
+        let c = Uuid::from_u128(3);
+        let mut matrix = VectorMatrix::new();
+
+        assert_eq!(
+            matrix.upsert_one("m1", a, &[1.0, 0.0]),
+            VectorUpsertOutcome::Inserted
+        );
+        assert_eq!(
+            matrix.upsert_one("m1", b, &[0.0, 1.0]),
+            VectorUpsertOutcome::Inserted
+        );
+        assert_eq!(
+            matrix.upsert_one("m1", c, &[1.0, 1.0]),
+            VectorUpsertOutcome::Inserted
+        );
+        assert_eq!(
+            matrix.upsert_one("m1", b, &[0.5, 0.5]),
+            VectorUpsertOutcome::Updated
+        );
+        assert_eq!(
+            matrix.upsert_one("m2", a, &[1.0, 0.0]),
+            VectorUpsertOutcome::ModelMismatch
+        );
+        assert_eq!(
+            matrix.upsert_one("m1", a, &[1.0, 0.0, 0.0]),
+            VectorUpsertOutcome::DimensionChanged
+        );
+
+        // 删除首行后末行换入，行号映射仍然正确
+        assert!(matrix.remove_one(&a));
+        assert!(!matrix.remove_one(&a));
+        assert_eq!(matrix.ids, vec![c, b]);
+        assert_eq!(matrix.get_vector(0), Some(&[1.0, 1.0][..]));
+        assert_eq!(
+            matrix.upsert_one("m1", b, &[0.2, 0.8]),
+            VectorUpsertOutcome::Updated
+        );
+        assert_eq!(matrix.get_vector(1), Some(&[0.2, 0.8][..]));
+        assert_eq!(matrix.data.len(), 4);
+    }
+}
@@ -13,9 +13,15 @@
 // limitations under the License.
 
 use crate::recall::core::{RecallCollectionMeta, RecallEntry};
+use flate2::read::GzDecoder;
+use flate2::write::GzEncoder;
+use flate2::Compression;
+use serde::{Deserialize, Serialize};
 use std::collections::hash_map::DefaultHasher;
+use std::collections::HashMap;
 use std::fs;
 use std::hash::{Hash, Hasher};
+use std::io::{Read, Write};
 use std::path::{Path, PathBuf};
 
 /// 思绪集根目录名称
@@ -26,6 +32,41 @@ const BASES_DIR: &str = "bases";
 const VECTORS_DIR: &str = "vectors";
 /// 全局标签池子目录
 const TAG_POOL_DIR: &str = "tag_pool";
+/// 条目历史版本子目录
+const HISTORY_DIR: &str = ".history";
+const DELETE_STAGING_DIR: &str = ".delete-staging";
+/// 每个条目保留的历史版本上限
+pub const MAX_ENTRY_VERSIONS: usize = 20;
+/// 条目以 gzip 压缩存储时的标记文件 (bases/{recall_id}/.entries-gzip)
+const GZIP_ENTRIES_MARKER: &str = ".entries-gzip";
+const PLAIN_ENTRY_SUFFIX: &str = ".json";
+const GZIP_ENTRY_SUFFIX: &str = ".json.gz";
+
+/// 条目文件的存储格式
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum EntryStorageFormat {
+    /// 明文 JSON (entries/{id}.json)
+    Json,
+    /// gzip 压缩的 JSON (entries/{id}.json.gz)
+    Gzip,
+}
+
+impl EntryStorageFormat {
+    fn suffix(self) -> &'static str {
+        match self {
+            EntryStorageFormat::Json => PLAIN_ENTRY_SUFFIX,
+            EntryStorageFormat::Gzip => GZIP_ENTRY_SUFFIX,
+        }
+    }
+
+    fn other(self) -> Self {
+        match self {
+            EntryStorageFormat::Json => EntryStorageFormat::Gzip,
+            EntryStorageFormat::Gzip => EntryStorageFormat::Json,
+        }
+    }
+}
 
 /// 获取思绪集根目录
 pub fn get_knowledge_root(app_data_dir: &Path) -> PathBuf {
@@ -47,6 +88,20 @@ pub fn get_recall_entries_dir(app_data_dir: &Path, recall_id: &str) -> PathBuf {
     get_recall_dir(app_data_dir, recall_id).join("entries")
 }
 
+/// 获取批量删除的暂存目录 (bases/{recall_id}/.delete-staging/{batch_id}/)
+pub fn get_delete_staging_dir(app_data_dir: &Path, recall_id: &str, batch_id: &str) -> PathBuf {
+    get_recall_dir(app_data_dir, recall_id)
+        .join(DELETE_STAGING_DIR)
+        .join(batch_id)
+}
+
+/// 获取特定条目的历史版本目录 (bases/{recall_id}/.history/{entry_id}/)
+pub fn get_entry_history_dir(app_data_dir: &Path, recall_id: &str, entry_id: &str) -> PathBuf {
+    get_recall_dir(app_data_dir, recall_id)
+        .join(HISTORY_DIR)
+        .join(entry_id)
+}
+
 /// 获取向量存储目录
 pub fn get_vectors_dir(app_data_dir: &Path) -> PathBuf {
     get_knowledge_root(app_data_dir).join(VECTORS_DIR)
@@ -127,25 +182,289 @@ pub fn init_workspace(app_data_dir: &Path) -> Result<(), String> {
     Ok(())
 }
 
-/// 保存单个条目到磁盘
+/// 获取思绪集当前的条目存储格式（由标记文件决定，默认明文）
+pub fn get_entry_storage_format(app_data_dir: &Path, recall_id: &str) -> EntryStorageFormat {
+    if get_recall_dir(app_data_dir, recall_id)
+        .join(GZIP_ENTRIES_MARKER)
+        .exists()
+    {
+        EntryStorageFormat::Gzip
+    } else {
+        EntryStorageFormat::Json
+    }
+}
+
+/// 获取条目在指定格式下的文件路径
+pub fn get_entry_file_path(
+    app_data_dir: &Path,
+    recall_id: &str,
+    entry_id: &str,
+    format: EntryStorageFormat,
+) -> PathBuf {
+    get_recall_entries_dir(app_data_dir, recall_id).join(format!("{}{}", entry_id, format.suffix()))
+}
+
+/// 查找条目在磁盘上的实际文件（两种格式都会尝试）
+pub fn find_entry_file(app_data_dir: &Path, recall_id: &str, entry_id: &str) -> Option<PathBuf> {
+    let preferred = get_entry_storage_format(app_data_dir, recall_id);
+    [preferred, preferred.other()]
+        .into_iter()
+        .map(|format| get_entry_file_path(app_data_dir, recall_id, entry_id, format))
+        .find(|path| path.exists())
+}
+
+/// 从条目文件名中解析条目 ID 与存储格式，不是条目文件时返回 None
+fn parse_entry_file_name(file_name: &str) -> Option<(&str, EntryStorageFormat)> {
+    if let Some(id) = file_name.strip_suffix(GZIP_ENTRY_SUFFIX) {
+        Some((id, EntryStorageFormat::Gzip))
+    } else {
+        file_name
+            .strip_suffix(PLAIN_ENTRY_SUFFIX)
+            .map(|id| (id, EntryStorageFormat::Json))
+    }
+}
+
+/// 列出条目目录下的所有条目文件
+///
+/// 格式转换中途中断时同一条目可能同时存在两种文件，此时只取压缩版本。
+pub fn list_entry_files(entries_dir: &Path) -> Result<Vec<PathBuf>, String> {
+    if !entries_dir.exists() {
+        return Ok(vec![]);
+    }
+
+    let mut files: HashMap<String, (EntryStorageFormat, PathBuf)> = HashMap::new();
+    for item in fs::read_dir(entries_dir).map_err(|e| format!("读取条目目录失败: {}", e))? {
+        let path = item.map_err(|e| format!("枚举条目文件失败: {}", e))?.path();
+        let Some((id, format)) = path
+            .file_name()
+            .and_then(|name| name.to_str())
+            .and_then(parse_entry_file_name)
+        else {
+            continue;
+        };
+        match files.get(id) {
+            Some((EntryStorageFormat::Gzip, _)) => {}
+            _ => {
+                files.insert(id.to_string(), (format, path));
+            }
+        }
+    }
+
+    let mut paths: Vec<PathBuf> = files.into_values().map(|(_, path)| path).collect();
+    paths.sort();
+    Ok(paths)
+}
+
+/// 读取条目文件，按扩展名透明解压
+pub fn read_entry_file(path: &Path) -> Result<RecallEntry, String> {
+    let bytes = fs::read(path).map_err(|e| format!("读取条目文件失败: {}", e))?;
+    let is_gzip = path
+        .file_name()
+        .and_then(|name| name.to_str())
+        .is_some_and(|name| name.ends_with(GZIP_ENTRY_SUFFIX));
+
+    if is_gzip {
+        let mut json = Vec::new();
+        GzDecoder::new(bytes.as_slice())
+            .read_to_end(&mut json)
+            .map_err(|e| format!("解压条目文件失败: {}", e))?;
+        serde_json::from_slice(&json).map_err(|e| format!("解析条目失败: {}", e))
+    } else {
+        serde_json::from_slice(&bytes).map_err(|e| format!("解析条目失败: {}", e))
+    }
+}
+
+fn encode_entry(entry: &RecallEntry, format: EntryStorageFormat) -> Result<Vec<u8>, String> {
+    let json = serde_json::to_vec_pretty(entry).map_err(|e| format!("序列化条目失败: {}", e))?;
+    match format {
+        EntryStorageFormat::Json => Ok(json),
+        EntryStorageFormat::Gzip => {
+            let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+            encoder
+                .write_all(&json)
+                .and_then(|_| encoder.finish())
+                .map_err(|e| format!("压缩条目失败: {}", e))
+        }
+    }
+}
+
+/// 按指定格式写入条目文件（先写临时文件再替换），并移除另一种格式的旧文件
+fn write_entry_file(
+    app_data_dir: &Path,
+    recall_id: &str,
+    entry: &RecallEntry,
+    format: EntryStorageFormat,
+) -> Result<(), String> {
+    let entry_id = entry.id.to_string();
+    let path = get_entry_file_path(app_data_dir, recall_id, &entry_id, format);
+    let temp_path = path.with_extension("tmp");
+    let data = encode_entry(entry, format)?;
+    if let Err(e) = fs::write(&temp_path, data).and_then(|_| fs::rename(&temp_path, &path)) {
+        // 写入或重命名失败时清理临时文件，避免在条目目录中残留
+        let _ = fs::remove_file(&temp_path);
+        return Err(format!("写入条目文件失败: {}", e));
+    }
+
+    let stale = get_entry_file_path(app_data_dir, recall_id, &entry_id, format.other());
+    if stale.exists() {
+        fs::remove_file(stale).map_err(|e| format!("删除旧格式条目文件失败: {}", e))?;
+    }
+    Ok(())
+}
+
+/// 保存单个条目到磁盘，按思绪集的存储格式透明压缩
 pub fn save_entry(app_data_dir: &Path, recall_id: &str, entry: &RecallEntry) -> Result<(), String> {
     let entries_dir = get_recall_entries_dir(app_data_dir, recall_id);
     if !entries_dir.exists() {
         fs::create_dir_all(&entries_dir).map_err(|e| format!("创建条目目录失败: {}", e))?;
     }
 
-    let path = entries_dir.join(format!("{}.json", entry.id));
-    let json = serde_json::to_string_pretty(entry).map_err(|e| format!("序列化条目失败: {}", e))?;
-    fs::write(path, json).map_err(|e| format!("写入条目文件失败: {}", e))?;
+    let format = get_entry_storage_format(app_data_dir, recall_id);
+    write_entry_file(app_data_dir, recall_id, entry, format)
+}
 
+/// 从磁盘删除单个条目文件 (不含向量与历史版本)
+pub fn delete_entry(app_data_dir: &Path, recall_id: &str, entry_id: &str) -> Result<(), String> {
+    for format in [EntryStorageFormat::Json, EntryStorageFormat::Gzip] {
+        let path = get_entry_file_path(app_data_dir, recall_id, entry_id, format);
+        if path.exists() {
+            fs::remove_file(path).map_err(|e| format!("删除条目文件失败: {}", e))?;
+        }
+    }
     Ok(())
 }
 
-/// 从磁盘删除单个条目 (仅 JSON)
-pub fn delete_entry(app_data_dir: &Path, recall_id: &str, entry_id: &str) -> Result<(), String> {
-    let path = get_recall_entries_dir(app_data_dir, recall_id).join(format!("{}.json", entry_id));
-    if path.exists() {
-        fs::remove_file(path).map_err(|e| format!("删除条目文件失败: {}", e))?;
+/// 把思绪集的全部条目转换为指定存储格式，返回实际转换的条目数
+///
+/// 逐个条目先写新格式再删旧文件，中途失败时已转换和未转换的条目都能正常读取；
+/// 全部完成后才切换格式标记，之后新保存的条目使用新格式。
+pub fn convert_entry_storage(
+    app_data_dir: &Path,
+    recall_id: &str,
+    target: EntryStorageFormat,
+) -> Result<usize, String> {
+    let entries_dir = get_recall_entries_dir(app_data_dir, recall_id);
+    let mut converted = 0;
+    for path in list_entry_files(&entries_dir)? {
+        let Some((entry_id, format)) = path
+            .file_name()
+            .and_then(|name| name.to_str())
+            .and_then(parse_entry_file_name)
+        else {
+            continue;
+        };
+        if format == target {
+            // 清理上次中断遗留的旧格式副本
+            let stale = get_entry_file_path(app_data_dir, recall_id, entry_id, target.other());
+            if stale.exists() {
+                fs::remove_file(stale).map_err(|e| format!("删除旧格式条目文件失败: {}", e))?;
+            }
+            continue;
+        }
+        let entry = read_entry_file(&path)?;
+        write_entry_file(app_data_dir, recall_id, &entry, target)?;
+        converted += 1;
+    }
+
+    let marker = get_recall_dir(app_data_dir, recall_id).join(GZIP_ENTRIES_MARKER);
+    match target {
+        EntryStorageFormat::Gzip => {
+            fs::write(&marker, b"").map_err(|e| format!("写入存储格式标记失败: {}", e))?
+        }
+        EntryStorageFormat::Json => {
+            if marker.exists() {
+                fs::remove_file(&marker).map_err(|e| format!("删除存储格式标记失败: {}", e))?;
+            }
+        }
+    }
+
+    log::info!(
+        "[KB_IO] 思绪集 {} 的条目存储已切换为 {:?}，转换 {} 个条目",
+        recall_id,
+        target,
+        converted
+    );
+    Ok(converted)
+}
+
+/// 保存条目旧版本快照，并清理超出保留上限的最旧快照
+///
+/// 快照文件名为毫秒时间戳，返回该时间戳作为版本号。
+pub fn save_entry_snapshot(
+    app_data_dir: &Path,
+    recall_id: &str,
+    entry: &RecallEntry,
+    max_versions: usize,
+) -> Result<i64, String> {
+    let history_dir = get_entry_history_dir(app_data_dir, recall_id, &entry.id.to_string());
+    fs::create_dir_all(&history_dir).map_err(|e| format!("创建历史版本目录失败: {}", e))?;
+
+    // 同一毫秒内多次保存时顺延，保证版本号唯一且有序
+    let mut version = chrono::Utc::now().timestamp_millis();
+    while history_dir.join(format!("{}.json", version)).exists() {
+        version += 1;
+    }
+
+    let json = serde_json::to_string_pretty(entry).map_err(|e| format!("序列化条目失败: {}", e))?;
+    fs::write(history_dir.join(format!("{}.json", version)), json)
+        .map_err(|e| format!("写入历史版本失败: {}", e))?;
+
+    let versions = list_entry_snapshot_versions(app_data_dir, recall_id, &entry.id.to_string())?;
+    for stale in versions.iter().skip(max_versions) {
+        let _ = fs::remove_file(history_dir.join(format!("{}.json", stale)));
+    }
+
+    Ok(version)
+}
+
+/// 列出条目的历史版本号，按从新到旧排序
+pub fn list_entry_snapshot_versions(
+    app_data_dir: &Path,
+    recall_id: &str,
+    entry_id: &str,
+) -> Result<Vec<i64>, String> {
+    let history_dir = get_entry_history_dir(app_data_dir, recall_id, entry_id);
+    if !history_dir.exists() {
+        return Ok(vec![]);
+    }
+
+    let mut versions: Vec<i64> = fs::read_dir(&history_dir)
+        .map_err(|e| format!("读取历史版本目录失败: {}", e))?
+        .flatten()
+        .filter_map(|item| {
+            let path = item.path();
+            if path.extension().and_then(|ext| ext.to_str()) != Some("json") {
+                return None;
+            }
+            path.file_stem()?.to_str()?.parse::<i64>().ok()
+        })
+        .collect();
+    versions.sort_unstable_by(|a, b| b.cmp(a));
+    Ok(versions)
+}
+
+/// 读取条目的某个历史版本快照
+pub fn load_entry_snapshot(
+    app_data_dir: &Path,
+    recall_id: &str,
+    entry_id: &str,
+    version: i64,
+) -> Result<RecallEntry, String> {
+    let path =
+        get_entry_history_dir(app_data_dir, recall_id, entry_id).join(format!("{}.json", version));
+    let content = fs::read_to_string(&path).map_err(|e| format!("读取历史版本失败: {}", e))?;
+    serde_json::from_str(&content).map_err(|e| format!("解析历史版本失败: {}", e))
+}
+
+/// 删除条目的全部历史版本
+pub fn delete_entry_history(
+    app_data_dir: &Path,
+    recall_id: &str,
+    entry_id: &str,
+) -> Result<(), String> {
+    let history_dir = get_entry_history_dir(app_data_dir, recall_id, entry_id);
+    if history_dir.exists() {
+        fs::remove_dir_all(history_dir).map_err(|e| format!("删除历史版本失败: {}", e))?;
     }
     Ok(())
 }
@@ -193,4 +512,99 @@ mod tests {
         assert!(get_bases_dir(app_data_dir).exists());
         assert!(get_vectors_dir(app_data_dir).exists());
     }
+
+    #[test]
+    fn test_entry_snapshots_are_pruned_newest_first() {
+        let dir = tempdir().unwrap();
+        let app_data_dir = dir.path();
+        let recall_id = "60f7ad7e-9a59-4b25-bad0-e87a74dcf622";
+        let mut entry: RecallEntry = serde_json::from_value(serde_json::json!({
+            "id": "0b8f3c1e-2d4a-4f6b-9c8d-7e6f5a4b3c2d",
+            "key": "条目",
+            "content": "v0",
+            "contentHash": null,
+        }))
+        .unwrap();
+
+        let mut saved = Vec::new();
+        for index in 0..4 {
+            entry.content = format!("v{}", index);
+            saved.push(save_entry_snapshot(app_data_dir, recall_id, &entry, 3).unwrap());
+        }
+
+        let entry_id = entry.id.to_string();
+        let versions = list_entry_snapshot_versions(app_data_dir, recall_id, &entry_id).unwrap();
+        assert_eq!(versions, vec![saved[3], saved[2], saved[1]]);
+
+        let restored = load_entry_snapshot(app_data_dir, recall_id, &entry_id, saved[1]).unwrap();
+        assert_eq!(restored.content, "v1");
+
+        delete_entry_history(app_data_dir, recall_id, &entry_id).unwrap();
+        assert!(
+            list_entry_snapshot_versions(app_data_dir, recall_id, &entry_id)
+                .unwrap()
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn test_entry_storage_converts_between_plain_and_gzip() {
+        let dir = tempdir().unwrap();
+        let app_data_dir = dir.path();
+        let recall_id = "60f7ad7e-9a59-4b25-bad0-e87a74dcf622";
+        let entry: RecallEntry = serde_json::from_value(serde_json::json!({
+            "id": "0b8f3c1e-2d4a-4f6b-9c8d-7e6f5a4b3c2d",
+            "key": "条目",
+            "content": "压缩存储".repeat(50),
+            "contentHash": null,
+        }))
+        .unwrap();
+        let entry_id = entry.id.to_string();
+        let entries_dir = get_recall_entries_dir(app_data_dir, recall_id);
+
+        save_entry(app_data_dir, recall_id, &entry).unwrap();
+        let plain =
+            get_entry_file_path(app_data_dir, recall_id, &entry_id, EntryStorageFormat::Json);
+        let gzip =
+            get_entry_file_path(app_data_dir, recall_id, &entry_id, EntryStorageFormat::Gzip);
+        assert!(plain.exists());
+
+        assert_eq!(
+            convert_entry_storage(app_data_dir, recall_id, EntryStorageFormat::Gzip).unwrap(),
+            1
+        );
+        assert_eq!(
+            get_entry_storage_format(app_data_dir, recall_id),
+            EntryStorageFormat::Gzip
+        );
+        assert!(!plain.exists());
+        assert!(
+            fs::metadata(&gzip).unwrap().len()
+                < serde_json::to_vec_pretty(&entry).unwrap().len() as u64
+        );
+        assert_eq!(list_entry_files(&entries_dir).unwrap(), vec![gzip.clone()]);
+        assert_eq!(read_entry_file(&gzip).unwrap().content, entry.content);
+
+        // 压缩模式下保存仍写 .json.gz，并清理中断遗留的明文副本
+        fs::write(&plain, serde_json::to_vec(&entry).unwrap()).unwrap();
+        save_entry(app_data_dir, recall_id, &entry).unwrap();
+        assert!(gzip.exists() && !plain.exists());
+
+        assert_eq!(
+            convert_entry_storage(app_data_dir, recall_id, EntryStorageFormat::Json).unwrap(),
+            1
+        );
+        assert_eq!(
+            get_entry_storage_format(app_data_dir, recall_id),
+            EntryStorageFormat::Json
+        );
+        assert_eq!(
+            find_entry_file(app_data_dir, recall_id, &entry_id),
+            Some(plain.clone())
+        );
+        assert_eq!(read_entry_file(&plain).unwrap().key, "条目");
+
+        delete_entry(app_data_dir, recall_id, &entry_id).unwrap();
+        assert!(list_entry_files(&entries_dir).unwrap().is_empty());
+    }
 }
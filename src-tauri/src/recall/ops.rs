@@ -168,31 +168,19 @@ pub fn load_entries_to_vec(
     _recall_id: &Uuid,
     out_entries: &mut Vec<RecallEntry>,
 ) -> Result<(), String> {
-    use ignore::WalkBuilder;
-
     let entries_dir = recall_path.join("entries");
     if !entries_dir.exists() {
         return Ok(());
     }
 
-    // 1. 扫描所有 JSON 文件
-    let mut file_paths = Vec::new();
-    for result in WalkBuilder::new(entries_dir)
-        .max_depth(Some(1))
-        .build()
-        .flatten()
-    {
-        if result.path().extension().and_then(|s| s.to_str()) == Some("json") {
-            file_paths.push(result.path().to_path_buf());
-        }
-    }
+    // 1. 扫描所有条目文件 (.json / .json.gz)
+    let file_paths = list_entry_files(&entries_dir)?;
 
     // 2. 并行读取和解析
     let entries: Vec<RecallEntry> = file_paths
         .into_par_iter()
         .filter_map(|path| {
-            let content = std::fs::read_to_string(&path).ok()?;
-            let mut entry: RecallEntry = serde_json::from_str(&content).ok()?;
+            let mut entry = read_entry_file(&path).ok()?;
 
             // 补全缺失的摘要
             if entry.summary.is_empty() {
@@ -387,6 +375,78 @@ pub fn delete_entry_files(
     Ok(())
 }
 
+/// 已移入暂存区的文件 (原路径, 暂存路径)
+pub type StagedMoves = Vec<(std::path::PathBuf, std::path::PathBuf)>;
+
+/// 将单个条目的文件（条目 JSON + 各模型向量）移入暂存目录
+///
+/// 任一文件移动失败时会把本条目已移动的文件移回原处，保证单个条目要么全部暂存要么原样保留。
+pub fn stage_entry_files(
+    app_data_dir: &std::path::Path,
+    recall_id_str: &str,
+    entry_id: &Uuid,
+    staging_dir: &std::path::Path,
+) -> Result<StagedMoves, String> {
+    let entry_id_str = entry_id.to_string();
+    let mut candidates = Vec::new();
+
+    if let Some(entry_file) = find_entry_file(app_data_dir, recall_id_str, &entry_id_str) {
+        let file_name = entry_file
+            .file_name()
+            .map(|name| name.to_os_string())
+            .unwrap_or_default();
+        candidates.push((entry_file, staging_dir.join("entries").join(file_name)));
+    }
+
+    let recall_vec_root = get_recall_vectors_root(app_data_dir, recall_id_str);
+    if let Ok(model_dirs) = std::fs::read_dir(recall_vec_root) {
+        for model_dir in model_dirs.flatten() {
+            if !model_dir.path().is_dir() {
+                continue;
+            }
+            let vec_file = model_dir.path().join(format!("{}.vec", entry_id_str));
+            if vec_file.exists() {
+                candidates.push((
+                    vec_file,
+                    staging_dir
+                        .join("vectors")
+                        .join(model_dir.file_name())
+                        .join(format!("{}.vec", entry_id_str)),
+                ));
+            }
+        }
+    }
+
+    let mut moved: StagedMoves = Vec::new();
+    for (from, to) in candidates {
+        let result = to
+            .parent()
+            .map_or(Ok(()), std::fs::create_dir_all)
+            .and_then(|_| std::fs::rename(&from, &to));
+        if let Err(e) = result {
+            restore_staged_files(&moved);
+            return Err(format!("暂存文件 {} 失败: {}", from.display(), e));
+        }
+        moved.push((from, to));
+    }
+
+    Ok(moved)
+}
+
+/// 把暂存的文件移回原处（尽力而为，失败只记录日志）
+pub fn restore_staged_files(moves: &[(std::path::PathBuf, std::path::PathBuf)]) {
+    for (original, staged) in moves.iter().rev() {
+        if let Err(e) = std::fs::rename(staged, original) {
+            log::error!(
+                "[KB] 回滚暂存文件失败 {} -> {}: {}",
+                staged.display(),
+                original.display(),
+                e
+            );
+        }
+    }
+}
+
 /// 更新思绪集模型索引表 (vectors/{recall_id}/models.json)
 pub fn update_recall_models_index(
     app_data_dir: &std::path::Path,
@@ -422,15 +482,25 @@ pub fn batch_upsert_entries_logic(
     };
     let recall_id_str = recall_id.to_string();
 
-    // 1. 获取现有的内容哈希集合（用于去重）
-    let mut seen_hashes: std::collections::HashSet<String> = if deduplicate {
+    // 1. 获取库内已有内容的去重哈希集合
+    // 已加载的条目按规范化内容计算，未加载内容的条目退化为比较持久化在 meta 中的原文哈希
+    let (mut seen_hashes, persisted_hashes) = if deduplicate {
         let base = base_lock.read().map_err(|_| "获取思绪集读锁失败")?;
-        base.entries
-            .values()
-            .filter_map(|e| e.content_hash.clone())
-            .collect()
+        let seen: std::collections::HashSet<String> = base
+            .entries
+            .par_iter()
+            .map(|(_, e)| calculate_dedup_hash(&e.content))
+            .collect();
+        let persisted: std::collections::HashSet<String> = base
+            .meta
+            .entries
+            .iter()
+            .filter(|item| !base.entries.contains_key(&item.id))
+            .filter_map(|item| item.content_hash.clone())
+            .collect();
+        (seen, persisted)
     } else {
-        std::collections::HashSet::new()
+        Default::default()
     };
 
     // 2. 补全哈希和摘要
@@ -443,21 +513,26 @@ pub fn batch_upsert_entries_logic(
         }
     });
 
-    // 3. 去重过滤
+    // 3. 去重过滤 (同时覆盖库内已有条目与本批次内部的重复)
     let mut duplicate_count = 0;
-    let filtered_entries: Vec<RecallEntry> = entries
-        .into_iter()
-        .filter(|entry| {
-            if let Some(hash) = &entry.content_hash {
-                if deduplicate && seen_hashes.contains(hash) {
+    let filtered_entries: Vec<RecallEntry> = if deduplicate {
+        entries
+            .into_iter()
+            .filter(|entry| {
+                let is_persisted = entry
+                    .content_hash
+                    .as_ref()
+                    .is_some_and(|hash| persisted_hashes.contains(hash));
+                if is_persisted || !seen_hashes.insert(calculate_dedup_hash(&entry.content)) {
                     duplicate_count += 1;
                     return false;
                 }
-                seen_hashes.insert(hash.clone());
-            }
-            true
-        })
-        .collect();
+                true
+            })
+            .collect()
+    } else {
+        entries
+    };
 
     if filtered_entries.is_empty() {
         return Ok((vec![], duplicate_count));
@@ -13,11 +13,14 @@
 // limitations under the License.
 
 pub mod blender;
+pub mod bm25;
+pub mod highlight;
 pub mod keyword;
 pub mod lens;
 pub mod vector;
 
 pub use blender::BlenderRetrievalEngine;
+pub use bm25::Bm25RetrievalEngine;
 pub use keyword::KeywordRetrievalEngine;
 pub use lens::LensRetrievalEngine;
 pub use vector::VectorRetrievalEngine;
@@ -16,9 +16,10 @@ use crate::recall::core::{
     QueryPayload, RecallResult, RecallSearchFilters, RetrievalContext, RetrievalEngine,
     RetrievalEngineInfo,
 };
+use crate::recall::index::TextInvertedIndex;
+use crate::recall::search::highlight::build_highlight;
 use crate::recall::search::vector::cosine_similarity;
 use crate::recall::tag_pool::ModelTagPool;
-use crate::recall::tag_sea::TagSea;
 use crate::recall::utils::{project_onto, projection_coeff, vec_norm_sq, vec_subtract};
 use jieba_rs::Jieba;
 use lazy_static::lazy_static;
@@ -183,6 +184,8 @@ impl RetrievalEngine for BlenderRetrievalEngine {
 
         let imdb = context.db.read().map_err(|_| "获取内存数据库读锁失败")?;
         let mut all_results = Vec::new();
+        // 各思绪集的高亮词（激活标签可能因库而异），在最终截断后统一生成高亮
+        let mut highlight_terms_by_recall: HashMap<Uuid, Vec<String>> = HashMap::new();
 
         // 获取标签池
         let tag_pool_lock = context
@@ -198,7 +201,9 @@ impl RetrievalEngine for BlenderRetrievalEngine {
             }
         }
 
-        let tag_pool = tag_pool_lock.and_then(|lock| lock.read().ok().map(|g| g.clone()));
+        // 持有读锁直接引用标签池，避免每次检索克隆整个池
+        let tag_pool_guard = tag_pool_lock.as_ref().and_then(|lock| lock.read().ok());
+        let tag_pool = tag_pool_guard.as_deref();
 
         // 获取引擎参数
         let max_layers = filters
@@ -218,6 +223,16 @@ impl RetrievalEngine for BlenderRetrievalEngine {
         let limit = filters.limit.unwrap_or(20);
         let min_score = filters.min_score.unwrap_or(0.0);
 
+        // 高亮词：原始查询及其分词结果，后续再补充残差挖掘激活的标签
+        let query_highlight_terms: Vec<String> = match raw_query {
+            Some(q) => {
+                let mut terms = vec![q.to_lowercase()];
+                terms.extend(TextInvertedIndex::query_terms(q));
+                terms
+            }
+            None => Vec::new(),
+        };
+
         for (recall_id, base_lock) in &imdb.bases {
             if let Some(ref recall_ids) = filters.recall_ids {
                 if !recall_ids.contains(recall_id) {
@@ -310,13 +325,19 @@ impl RetrievalEngine for BlenderRetrievalEngine {
 
             // 1c. Gravitational Signal
             let mut gravitational_scores: HashMap<Uuid, f32> = HashMap::new();
-            if let Some(ref pool) = tag_pool {
+            let mut highlight_terms = query_highlight_terms.clone();
+            if let Some(pool) = tag_pool {
                 // Phase 2: 残差挖掘
                 let activated_tags =
                     self.residual_mining(query_vector, pool, max_layers, layer_decay);
+                for (tag_name, _, _) in &activated_tags {
+                    if !highlight_terms.contains(tag_name) {
+                        highlight_terms.push(tag_name.clone());
+                    }
+                }
 
-                // 标签->条目映射 (TagSea)
-                let tag_sea = TagSea::build(&base, pool.clone());
+                // 标签->条目映射 (TagSea，按思绪集缓存)
+                let tag_sea = base.tag_sea();
                 for (tag_name, tag_weight, _layer) in activated_tags {
                     if let Some(entry_list) = tag_sea.tag_to_entry_weights.get(&tag_name) {
                         for (entry_id, entry_tag_weight) in entry_list {
@@ -379,7 +400,7 @@ impl RetrievalEngine for BlenderRetrievalEngine {
                     None => continue,
                 };
 
-                if filters.enabled_only.unwrap_or(true) && !entry.enabled {
+                if !filters.accepts_entry(entry) {
                     continue;
                 }
 
@@ -447,6 +468,7 @@ impl RetrievalEngine for BlenderRetrievalEngine {
                 recall_results.truncate(k);
             }
             all_results.extend(recall_results);
+            highlight_terms_by_recall.insert(*recall_id, highlight_terms);
         }
 
         // --- Phase 4: 坍缩输出 ---
@@ -465,12 +487,18 @@ impl RetrievalEngine for BlenderRetrievalEngine {
             }
         }
 
-        let final_results: Vec<_> = all_results
+        let mut final_results: Vec<_> = all_results
             .into_iter()
             .filter(|r| r.score >= min_score)
             .take(limit)
             .collect();
 
+        for result in final_results.iter_mut() {
+            if let Some(terms) = highlight_terms_by_recall.get(&result.recall_id) {
+                result.highlight = build_highlight(&result.entry.content, terms);
+            }
+        }
+
         log::info!(
             "[BLENDER_SEARCH] 检索完成: 命中总数={}, 返回数={}, 最高分={:?}",
             final_results.len(),
@@ -0,0 +1,243 @@
+// Copyright 2025-2026 miaotouy(Github@miaotouy)
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::recall::core::{
+    QueryPayload, RecallResult, RecallSearchFilters, RetrievalContext, RetrievalEngine,
+    RetrievalEngineInfo,
+};
+use crate::recall::index::TextInvertedIndex;
+use crate::recall::search::highlight::build_highlight;
+
+/// 纯 BM25 关键词检索引擎
+///
+/// 只使用倒排索引中的词频和文档长度打分，不依赖 Embedding，结果可解释
+pub struct Bm25RetrievalEngine {
+    /// 词频饱和度参数
+    k1: f32,
+    /// 文档长度归一化参数 (0.0 - 1.0)
+    b: f32,
+}
+
+impl Bm25RetrievalEngine {
+    pub fn new() -> Self {
+        Self { k1: 1.2, b: 0.75 }
+    }
+}
+
+impl RetrievalEngine for Bm25RetrievalEngine {
+    fn id(&self) -> &str {
+        "bm25"
+    }
+
+    fn info(&self) -> RetrievalEngineInfo {
+        RetrievalEngineInfo {
+            id: self.id().to_string(),
+            name: "BM25 检索".to_string(),
+            description: "标准 Okapi BM25 关键词检索，按词频、逆文档频率和文档长度打分，无需 Embedding 模型。"
+                .to_string(),
+            icon: Some("lucide:text-search".to_string()),
+            supported_payload_types: vec!["text".to_string()],
+            requires_embedding: false,
+            parameters: vec![
+                serde_json::json!({
+                    "id": "bm25K1",
+                    "label": "饱和度 (k1: {{ localSettings.vectorIndex.k1 }})",
+                    "component": "SliderWithInput",
+                    "modelPath": "k1",
+                    "defaultValue": 1.2,
+                    "hint": "控制词频饱和速度，通常为 1.2 - 2.0。值越大，同一词多次出现带来的加分越多。",
+                    "props": { "min": 0.0, "max": 3.0, "step": 0.1, "size": "small" }
+                }),
+                serde_json::json!({
+                    "id": "bm25B",
+                    "label": "长度归一化 (b: {{ localSettings.vectorIndex.b }})",
+                    "component": "SliderWithInput",
+                    "modelPath": "b",
+                    "defaultValue": 0.75,
+                    "hint": "控制文档长度对评分的影响 (0.0 - 1.0)。0 表示不考虑长度，1 表示完全按平均长度归一。",
+                    "props": { "min": 0.0, "max": 1.0, "step": 0.05, "size": "small" }
+                }),
+                serde_json::json!({
+                    "id": "limit",
+                    "label": "召回上限 ({{ localSettings.vectorIndex.limit }})",
+                    "component": "SliderWithInput",
+                    "modelPath": "limit",
+                    "defaultValue": 20,
+                    "hint": "检索结果的最大返回数量。实际截断以最低分数为主要依据。",
+                    "props": { "min": 1, "max": 100, "step": 1, "size": "small" }
+                }),
+                serde_json::json!({
+                    "id": "minScore",
+                    "label": "最低相关性分数 ({{ (localSettings.vectorIndex.minScore * 100).toFixed(0) }}%)",
+                    "component": "SliderWithInput",
+                    "modelPath": "minScore",
+                    "defaultValue": 0.0,
+                    "hint": "过滤掉分数低于此值的检索结果（分数已按最高分归一化到 0 - 1）",
+                    "props": { "min": 0, "max": 1, "step": 0.01, "size": "small" }
+                }),
+            ],
+        }
+    }
+
+    fn search(
+        &self,
+        payload: &QueryPayload,
+        filters: &RecallSearchFilters,
+        context: &RetrievalContext,
+    ) -> Result<Vec<RecallResult>, String> {
+        let query = match payload {
+            QueryPayload::Text(t) => t,
+            _ => return Ok(vec![]),
+        };
+
+        let k1 = filters.k1.unwrap_or(self.k1).max(0.0);
+        let b = filters.b.unwrap_or(self.b).clamp(0.0, 1.0);
+        log::info!(
+            "[BM25_SEARCH] 开始检索: query='{}', k1={}, b={}",
+            query,
+            k1,
+            b
+        );
+
+        let mut highlight_terms = vec![query.to_lowercase()];
+        highlight_terms.extend(TextInvertedIndex::query_terms(query));
+        let imdb = context.db.read().map_err(|_| "获取内存数据库读锁失败")?;
+        let mut results = Vec::new();
+        let mut recall_min_scores = std::collections::HashMap::new();
+
+        for (recall_id, base_lock) in &imdb.bases {
+            if let Some(ref recall_ids) = filters.recall_ids {
+                if !recall_ids.contains(recall_id) {
+                    continue;
+                }
+            }
+
+            let base = base_lock.read().map_err(|_| "获取思绪集读锁失败")?;
+
+            if let Some(min_score) = base
+                .meta
+                .config
+                .get("minScore")
+                .and_then(|v| v.as_f64())
+                .map(|v| v as f32)
+            {
+                recall_min_scores.insert(*recall_id, min_score);
+            }
+
+            let recall_search_top_k = base
+                .meta
+                .config
+                .get("searchTopK")
+                .and_then(|v| v.as_u64())
+                .map(|v| v as usize);
+
+            let mut recall_results = Vec::new();
+            for (entry_id, score) in base.text_index.bm25_search(query, k1, b) {
+                let Some(entry) = base.entries.get(&entry_id) else {
+                    continue;
+                };
+
+                if !filters.accepts_entry(entry) {
+                    continue;
+                }
+
+                if let Some(ref filter_tags) = filters.tags {
+                    let has_tag = entry.tags.iter().any(|t| filter_tags.contains(&t.name))
+                        || entry
+                            .core_tags
+                            .iter()
+                            .any(|t| filter_tags.contains(&t.name));
+                    if !has_tag {
+                        continue;
+                    }
+                }
+
+                recall_results.push(RecallResult {
+                    entry: entry.clone(),
+                    score,
+                    match_type: "bm25".to_string(),
+                    recall_id: *recall_id,
+                    recall_name: base.meta.name.clone(),
+                    highlight: None,
+                });
+            }
+
+            // bm25_search 已按分数降序返回，直接应用库级别 TopK 截断
+            if let Some(limit) = recall_search_top_k {
+                recall_results.truncate(limit);
+            }
+            results.extend(recall_results);
+        }
+
+        results.sort_by(|a, b| {
+            b.score
+                .partial_cmp(&a.score)
+                .unwrap_or(std::cmp::Ordering::Equal)
+        });
+
+        // BM25 原始分数没有上界，按最高分线性归一化到 0-1，以便使用统一的 minScore 阈值
+        if let Some(max_score) = results.first().map(|r| r.score) {
+            if max_score > 0.0 {
+                for result in results.iter_mut() {
+                    result.score /= max_score;
+                }
+            }
+        }
+
+        results.retain(|r| {
+            let effective_min_score = recall_min_scores
+                .get(&r.recall_id)
+                .cloned()
+                .or(filters.min_score);
+            effective_min_score.is_none_or(|min_score| r.score >= min_score)
+        });
+
+        let total_found = results.len();
+        if let Some(limit) = filters.limit {
+            results.truncate(limit);
+        }
+
+        // 只为最终返回的结果生成高亮，避免对大量候选逐条分词
+        for result in results.iter_mut() {
+            result.highlight = build_highlight(&result.entry.content, &highlight_terms);
+        }
+
+        log::info!(
+            "[BM25_SEARCH] 检索完成: 总命中={}, 返回={}",
+            total_found,
+            results.len()
+        );
+
+        Ok(results)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bm25_engine_info() {
+        let engine = Bm25RetrievalEngine::new();
+        let info = engine.info();
+        assert_eq!(info.id, "bm25");
+        assert!(!info.requires_embedding);
+        let ids: Vec<&str> = info
+            .parameters
+            .iter()
+            .filter_map(|p| p.get("modelPath").and_then(|v| v.as_str()))
+            .collect();
+        assert_eq!(ids, vec!["k1", "b", "limit", "minScore"]);
+    }
+}
@@ -0,0 +1,228 @@
+// Copyright 2025-2026 miaotouy(Github@miaotouy)
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! 检索结果高亮片段提取
+//!
+//! 在条目内容中定位命中的查询词/标签，截取上下文片段并给出高亮区间。
+//! 匹配和截断都对齐到 JIEBA 的分词边界，避免把词语从中间切开。
+
+use crate::recall::core::{HighlightRange, RecallHighlight};
+use crate::recall::index::inverted_index::JIEBA;
+
+/// 片段目标长度（字符数，不含省略号）
+const SNIPPET_CHARS: usize = 120;
+/// 首个命中词之前保留的上下文字符数
+const LEADING_CONTEXT_CHARS: usize = 30;
+/// 参与定位的最大命中数，避免超长文档中的高频词拖慢检索
+const MAX_MATCHES: usize = 256;
+const ELLIPSIS: &str = "...";
+
+/// 为内容构建高亮片段
+///
+/// `terms` 为需要高亮的词（查询词、激活标签等），大小写不敏感。
+/// 没有任何命中时返回内容开头的片段和空的高亮区间；内容为空时返回 None。
+pub fn build_highlight(content: &str, terms: &[String]) -> Option<RecallHighlight> {
+    if content.is_empty() {
+        return None;
+    }
+
+    let boundaries = token_boundaries(content);
+    let matches = find_matches(content, terms, &boundaries);
+
+    let (start, end) = match matches.first() {
+        Some(_) => best_window(content, &matches, &boundaries),
+        None => (
+            0,
+            snap_forward(&boundaries, char_advance(content, 0, SNIPPET_CHARS)),
+        ),
+    };
+
+    let prefix = if start > 0 { ELLIPSIS } else { "" };
+    let suffix = if end < content.len() { ELLIPSIS } else { "" };
+    let snippet = format!("{}{}{}", prefix, &content[start..end], suffix);
+
+    // 高亮区间以 UTF-16 码元计，与前端 JS 字符串下标一致
+    let prefix_units = prefix.encode_utf16().count();
+    let ranges = matches
+        .iter()
+        .filter(|(m_start, m_end)| *m_start >= start && *m_end <= end)
+        .map(|&(m_start, m_end)| {
+            let offset = prefix_units + content[start..m_start].encode_utf16().count();
+            HighlightRange {
+                start: offset,
+                end: offset + content[m_start..m_end].encode_utf16().count(),
+            }
+        })
+        .collect();
+
+    Some(RecallHighlight { snippet, ranges })
+}
+
+/// JIEBA 分词后每个词的起止字节位置（升序、去重）
+fn token_boundaries(content: &str) -> Vec<usize> {
+    let mut boundaries = vec![0];
+    let mut offset = 0;
+    for word in JIEBA.cut(content, false) {
+        offset += word.len();
+        boundaries.push(offset);
+    }
+    boundaries
+}
+
+fn is_boundary(boundaries: &[usize], pos: usize) -> bool {
+    boundaries.binary_search(&pos).is_ok()
+}
+
+/// 向前对齐到不小于 pos 的分词边界
+fn snap_forward(boundaries: &[usize], pos: usize) -> usize {
+    match boundaries.binary_search(&pos) {
+        Ok(i) => boundaries[i],
+        Err(i) => boundaries.get(i).copied().unwrap_or(pos),
+    }
+}
+
+/// 向后对齐到不大于 pos 的分词边界
+fn snap_backward(boundaries: &[usize], pos: usize) -> usize {
+    match boundaries.binary_search(&pos) {
+        Ok(i) => boundaries[i],
+        Err(i) => boundaries[i.saturating_sub(1)],
+    }
+}
+
+/// 从 byte 位置 pos 开始前进 n 个字符，返回新的 byte 位置
+fn char_advance(content: &str, pos: usize, n: usize) -> usize {
+    content[pos..]
+        .char_indices()
+        .nth(n)
+        .map(|(i, _)| pos + i)
+        .unwrap_or(content.len())
+}
+
+/// 从 byte 位置 pos 开始后退 n 个字符
+fn char_retreat(content: &str, pos: usize, n: usize) -> usize {
+    content[..pos]
+        .char_indices()
+        .rev()
+        .nth(n.saturating_sub(1))
+        .map(|(i, _)| i)
+        .unwrap_or(0)
+}
+
+/// 找出所有首尾都落在分词边界上的命中区间（字节位置，按起点排序且互不重叠）
+fn find_matches(content: &str, terms: &[String], boundaries: &[usize]) -> Vec<(usize, usize)> {
+    // 只做 ASCII 小写化，保证字节位置与原文一致
+    let haystack = content.to_ascii_lowercase();
+    let mut matches: Vec<(usize, usize)> = Vec::new();
+
+    for term in terms {
+        let needle = term.trim().to_ascii_lowercase();
+        if needle.is_empty() {
+            continue;
+        }
+        for (pos, _) in haystack.match_indices(&needle) {
+            let end = pos + needle.len();
+            if is_boundary(boundaries, pos) && is_boundary(boundaries, end) {
+                matches.push((pos, end));
+            }
+            if matches.len() >= MAX_MATCHES {
+                break;
+            }
+        }
+    }
+
+    // 较长的命中优先，去掉与之重叠的较短命中
+    matches.sort_by(|a, b| a.0.cmp(&b.0).then((b.1 - b.0).cmp(&(a.1 - a.0))));
+    let mut merged: Vec<(usize, usize)> = Vec::with_capacity(matches.len());
+    for m in matches {
+        if merged.last().is_none_or(|last| m.0 >= last.1) {
+            merged.push(m);
+        }
+    }
+    merged
+}
+
+/// 选出包含命中最多的窗口，返回对齐到分词边界的 (start, end)
+fn best_window(content: &str, matches: &[(usize, usize)], boundaries: &[usize]) -> (usize, usize) {
+    let mut best = (0usize, 0usize);
+    for (i, &(m_start, _)) in matches.iter().enumerate() {
+        let window_end = char_advance(content, m_start, SNIPPET_CHARS - LEADING_CONTEXT_CHARS);
+        let covered = matches[i..]
+            .iter()
+            .take_while(|(_, e)| *e <= window_end)
+            .count();
+        if covered > best.1 {
+            best = (i, covered);
+        }
+    }
+
+    let first = matches[best.0];
+    let start = snap_backward(
+        boundaries,
+        char_retreat(content, first.0, LEADING_CONTEXT_CHARS),
+    );
+    let end = snap_forward(boundaries, char_advance(content, start, SNIPPET_CHARS)).max(first.1);
+    (start, end)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn slice_utf16(text: &str, range: &HighlightRange) -> String {
+        let units: Vec<u16> = text.encode_utf16().collect();
+        String::from_utf16(&units[range.start..range.end]).unwrap()
+    }
+
+    #[test]
+    fn test_build_highlight_marks_terms() {
+        let content = "Rust 的所有权系统保证了内存安全。借用检查器会在编译期检查 ownership 规则。";
+        let highlight =
+            build_highlight(content, &["所有权".to_string(), "Ownership".to_string()]).unwrap();
+
+        let marked: Vec<String> = highlight
+            .ranges
+            .iter()
+            .map(|r| slice_utf16(&highlight.snippet, r))
+            .collect();
+        assert_eq!(marked, vec!["所有权", "ownership"]);
+    }
+
+    #[test]
+    fn test_build_highlight_respects_word_boundaries() {
+        // “索引”横跨“检索”和“引擎”两个词，不应被高亮
+        let content = "这是一个检索引擎。";
+        let highlight = build_highlight(content, &["索引".to_string()]).unwrap();
+        assert!(highlight.ranges.is_empty());
+        assert_eq!(highlight.snippet, content);
+    }
+
+    #[test]
+    fn test_build_highlight_windows_long_content() {
+        let content = format!(
+            "{}关键词出现在这里{}",
+            "前文内容。".repeat(40),
+            "后文内容。".repeat(40)
+        );
+        let highlight = build_highlight(&content, &["关键词".to_string()]).unwrap();
+
+        assert!(highlight.snippet.starts_with(ELLIPSIS));
+        assert!(highlight.snippet.ends_with(ELLIPSIS));
+        assert_eq!(highlight.ranges.len(), 1);
+        assert_eq!(
+            slice_utf16(&highlight.snippet, &highlight.ranges[0]),
+            "关键词"
+        );
+        assert!(highlight.snippet.chars().count() < content.chars().count());
+    }
+}
@@ -13,6 +13,8 @@
 // limitations under the License.
 
 use crate::recall::core::{RecallResult, RecallSearchFilters, RetrievalContext, RetrievalEngine};
+use crate::recall::index::TextInvertedIndex;
+use crate::recall::search::highlight::build_highlight;
 
 pub struct KeywordRetrievalEngine;
 
@@ -84,6 +86,9 @@ impl RetrievalEngine for KeywordRetrievalEngine {
         let imdb = context.db.read().map_err(|_| "获取内存数据库读锁失败")?;
         let mut results = Vec::new();
         let query_lower = query.to_lowercase();
+        // 高亮时优先匹配完整查询，其次是分词后的各个词项
+        let mut highlight_terms = vec![query_lower.clone()];
+        highlight_terms.extend(TextInvertedIndex::query_terms(query));
         let mut recall_min_scores = std::collections::HashMap::new();
 
         for (recall_id, base_lock) in &imdb.bases {
@@ -125,8 +130,8 @@ impl RetrievalEngine for KeywordRetrievalEngine {
             let mut recall_results = Vec::new();
             for (entry_id, score_from_index) in candidate_ids {
                 if let Some(entry) = base.entries.get(&entry_id) {
-                    // 过滤器：仅启用
-                    if filters.enabled_only.unwrap_or(true) && !entry.enabled {
+                    // 过滤器：启用状态与时间范围
+                    if !filters.accepts_entry(entry) {
                         continue;
                     }
 
@@ -148,14 +153,13 @@ impl RetrievalEngine for KeywordRetrievalEngine {
                     if entry.key.to_lowercase().contains(&query_lower) {
                         score += 10.0;
                     }
-                    let highlight = Some(extract_highlight(&entry.content, &query_lower));
                     recall_results.push(RecallResult {
                         entry: entry.clone(),
                         score,
                         match_type: "keyword".to_string(),
                         recall_id: *recall_id,
                         recall_name: base.meta.name.clone(),
-                        highlight,
+                        highlight: None,
                     });
                 }
             }
@@ -213,6 +217,11 @@ impl RetrievalEngine for KeywordRetrievalEngine {
             results.truncate(limit);
         }
 
+        // 只为最终返回的结果生成高亮，避免对大量候选逐条分词
+        for result in results.iter_mut() {
+            result.highlight = build_highlight(&result.entry.content, &highlight_terms);
+        }
+
         log::info!(
             "[KEYWORD_SEARCH] 检索完成: 总命中={}, 返回={}, 归一化后最高分={:?}",
             total_found,
@@ -223,33 +232,3 @@ impl RetrievalEngine for KeywordRetrievalEngine {
         Ok(results)
     }
 }
-
-fn extract_highlight(content: &str, query_lower: &str) -> String {
-    let content_lower = content.to_lowercase();
-
-    if let Some(pos) = content_lower.find(query_lower) {
-        let start = pos.saturating_sub(30);
-        let end = std::cmp::min(content.len(), pos + query_lower.len() + 60);
-
-        // 确保不会在字符中间截断 (UTF-8 安全)
-        let mut start_idx = start;
-        while start_idx > 0 && !content.is_char_boundary(start_idx) {
-            start_idx -= 1;
-        }
-        let mut end_idx = end;
-        while end_idx < content.len() && !content.is_char_boundary(end_idx) {
-            end_idx += 1;
-        }
-
-        let mut snippet = content[start_idx..end_idx].to_string();
-        if start_idx > 0 {
-            snippet = format!("...{}", snippet);
-        }
-        if end_idx < content.len() {
-            snippet = format!("{}...", snippet);
-        }
-        snippet
-    } else {
-        content.chars().take(100).collect()
-    }
-}
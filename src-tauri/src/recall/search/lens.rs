@@ -17,17 +17,73 @@ use crate::recall::core::{
     QueryPayload, RecallResult, RecallSearchFilters, RetrievalContext, RetrievalEngine,
     RetrievalEngineInfo,
 };
+use crate::recall::tag_pool::ModelTagPool;
 use crate::recall::tag_sea::TagSea;
 use nalgebra::{DMatrix, DVector};
 use std::collections::HashMap;
 use uuid::Uuid;
 
-pub struct LensRetrievalEngine;
+pub struct LensRetrievalEngine {
+    graph_neighbors: usize,
+    auto_lens_tags: usize,
+    auto_refraction_index: f32,
+    history_decay: f32,
+    regularization: f32,
+}
 
 impl LensRetrievalEngine {
     pub fn new() -> Self {
-        Self
+        Self {
+            graph_neighbors: 80,
+            auto_lens_tags: 3,
+            auto_refraction_index: 0.3,
+            history_decay: 0.5,
+            regularization: 0.01,
+        }
     }
+
+    /// 从 filters.extra 读取本次检索的可调参数，未提供时使用引擎默认值
+    fn resolve_params(&self, filters: &RecallSearchFilters) -> LensParams {
+        let get_usize = |key: &str, default: usize| {
+            filters
+                .extra
+                .get(key)
+                .and_then(|v| v.as_u64())
+                .map(|v| (v as usize).max(1))
+                .unwrap_or(default)
+        };
+        let get_f32 = |key: &str, default: f32| {
+            filters
+                .extra
+                .get(key)
+                .and_then(|v| v.as_f64())
+                .map(|v| v as f32)
+                .unwrap_or(default)
+        };
+
+        LensParams {
+            graph_neighbors: get_usize("graphNeighbors", self.graph_neighbors),
+            auto_lens_tags: get_usize("autoLensTags", self.auto_lens_tags),
+            auto_refraction_index: get_f32("autoRefractionIndex", self.auto_refraction_index)
+                .clamp(0.0, 1.0),
+            history_decay: get_f32("historyDecay", self.history_decay).max(0.0),
+            regularization: get_f32("regularization", self.regularization).max(1e-6),
+        }
+    }
+}
+
+/// 单次检索生效的透镜参数
+struct LensParams {
+    /// 图谱编织阶段捕获的邻居标签数
+    graph_neighbors: usize,
+    /// 自动折射时选取的临时引力标签数
+    auto_lens_tags: usize,
+    /// 自动折射的折射率
+    auto_refraction_index: f32,
+    /// 上下文投射中历史消息向量的衰减系数
+    history_decay: f32,
+    /// 空间反转求伪逆时的正则化系数
+    regularization: f32,
 }
 
 impl RetrievalEngine for LensRetrievalEngine {
@@ -77,6 +133,51 @@ impl RetrievalEngine for LensRetrievalEngine {
                         "size": "small"
                     }
                 }),
+                serde_json::json!({
+                    "id": "graphNeighbors",
+                    "label": "图谱邻居数",
+                    "component": "SliderWithInput",
+                    "modelPath": "graphNeighbors",
+                    "defaultValue": self.graph_neighbors,
+                    "hint": "图谱编织时捕获的邻居标签数量，越大召回越广但计算越慢",
+                    "props": { "min": 10, "max": 200, "step": 10, "size": "small" }
+                }),
+                serde_json::json!({
+                    "id": "autoLensTags",
+                    "label": "自动透镜标签数",
+                    "component": "SliderWithInput",
+                    "modelPath": "autoLensTags",
+                    "defaultValue": self.auto_lens_tags,
+                    "hint": "未指定约束标签时，选取最近的若干标签作为临时引力中心",
+                    "props": { "min": 1, "max": 10, "step": 1, "size": "small" }
+                }),
+                serde_json::json!({
+                    "id": "autoRefractionIndex",
+                    "label": "自动折射率",
+                    "component": "SliderWithInput",
+                    "modelPath": "autoRefractionIndex",
+                    "defaultValue": self.auto_refraction_index,
+                    "hint": "自动折射的引力强度，较低时保持查询本身的灵活性",
+                    "props": { "min": 0, "max": 1, "step": 0.05, "size": "small" }
+                }),
+                serde_json::json!({
+                    "id": "historyDecay",
+                    "label": "上下文衰减系数",
+                    "component": "SliderWithInput",
+                    "modelPath": "historyDecay",
+                    "defaultValue": self.history_decay,
+                    "hint": "历史消息向量随距离的衰减速度，越大越聚焦当前查询",
+                    "props": { "min": 0, "max": 2, "step": 0.05, "size": "small" }
+                }),
+                serde_json::json!({
+                    "id": "regularization",
+                    "label": "正则化系数",
+                    "component": "SliderWithInput",
+                    "modelPath": "regularization",
+                    "defaultValue": self.regularization,
+                    "hint": "空间反转时的正则化强度，越大能量传播越平滑",
+                    "props": { "min": 0.001, "max": 0.1, "step": 0.001, "size": "small" }
+                }),
                 serde_json::json!({
                     "id": "limit",
                     "label": "召回上限 ({{ localSettings.vectorIndex.limit }})",
@@ -156,7 +257,8 @@ impl RetrievalEngine for LensRetrievalEngine {
             }
         }
 
-        let tag_pool = pool_lock.read().map_err(|_| "获取标签池读锁失败")?.clone();
+        let tag_pool = pool_lock.read().map_err(|_| "获取标签池读锁失败")?;
+        let params = self.resolve_params(filters);
 
         for (recall_id, base_lock) in &imdb.bases {
             // 过滤器：思绪集 ID
@@ -222,12 +324,12 @@ impl RetrievalEngine for LensRetrievalEngine {
                 }
             }
 
-            // 构建 TagSea
-            let tag_sea = TagSea::build(&base, tag_pool.clone());
+            // 获取 TagSea (按思绪集缓存，条目变动时才重建)
+            let tag_sea = base.tag_sea();
             log::debug!(
-                "[LENS_SEARCH] TagSea 构建完成: recall={}, tags={}, relations={}",
+                "[LENS_SEARCH] TagSea 就绪: recall={}, tags={}, relations={}",
                 recall_id,
-                tag_sea.tag_pool.registry.len(),
+                tag_pool.registry.len(),
                 tag_sea.tag_to_entry_weights.len()
             );
 
@@ -235,10 +337,11 @@ impl RetrievalEngine for LensRetrievalEngine {
             let mut recall_results = self.execute_lens_pipeline(
                 query_vector,
                 filters,
+                &params,
                 &tag_sea,
+                &tag_pool,
                 &base,
                 *recall_id,
-                base.meta.name.clone(),
             )?;
 
             // 应用库级别 TopK 截断
@@ -307,16 +410,17 @@ impl LensRetrievalEngine {
         &self,
         query_vector: &[f32],
         filters: &RecallSearchFilters,
+        params: &LensParams,
         tag_sea: &TagSea,
+        tag_pool: &ModelTagPool,
         base: &crate::recall::index::db::InMemoryBase,
         recall_id: Uuid,
-        recall_name: String,
     ) -> Result<Vec<RecallResult>, String> {
         // Phase 1: 上下文投射 (Context Projection)
         // 计算投影向量：当前查询向量 + 衰减后的历史消息向量
         let mut projected_vector = query_vector.to_vec();
         if let Some(history) = &filters.history_vectors {
-            let tau = 0.5; // 衰减系数
+            let tau = params.history_decay;
             for (i, h_vec) in history.iter().rev().enumerate() {
                 let decay = (-tau * (i as f32 + 1.0)).exp();
                 for (j, val) in h_vec.iter().enumerate() {
@@ -339,32 +443,37 @@ impl LensRetrievalEngine {
         let mut refracted_vector = projected_vector;
         if let Some(required_tags) = &filters.required_tags {
             if !required_tags.is_empty() {
-                if let Some(lens_center) = tag_sea.compute_lens_center(required_tags) {
+                if let Some(lens_center) = tag_sea.compute_lens_center(tag_pool, required_tags) {
                     let refraction_index = filters.refraction_index.unwrap_or(0.6);
                     refracted_vector =
                         self.apply_refraction(&refracted_vector, &lens_center, refraction_index);
                 }
             }
         } else {
-            // 自动折射：寻找最近的 3 个标签作为临时引力中心
-            let auto_neighbors = tag_sea.tag_pool.search_neighbors(&refracted_vector, 3);
+            // 自动折射：寻找最近的若干标签作为临时引力中心
+            let auto_neighbors =
+                tag_pool.search_neighbors(&refracted_vector, params.auto_lens_tags);
             if !auto_neighbors.is_empty() {
                 let mut auto_tags = Vec::new();
                 for (idx, _) in auto_neighbors {
-                    if let Some(name) = tag_sea.tag_pool.get_tag_name(idx) {
+                    if let Some(name) = tag_pool.get_tag_name(idx) {
                         auto_tags.push(name.clone());
                     }
                 }
-                if let Some(lens_center) = tag_sea.compute_lens_center(&auto_tags) {
+                if let Some(lens_center) = tag_sea.compute_lens_center(tag_pool, &auto_tags) {
                     // 自动折射率较低，保持灵活性
-                    refracted_vector = self.apply_refraction(&refracted_vector, &lens_center, 0.3);
+                    refracted_vector = self.apply_refraction(
+                        &refracted_vector,
+                        &lens_center,
+                        params.auto_refraction_index,
+                    );
                 }
             }
         }
 
         // Phase 3: 图谱编织 (Graph Weaving)
-        // 捕获 80 个邻居节点
-        let neighbors = tag_sea.tag_pool.search_neighbors(&refracted_vector, 80);
+        // 捕获邻居节点
+        let neighbors = tag_pool.search_neighbors(&refracted_vector, params.graph_neighbors);
         if neighbors.is_empty() {
             log::warn!("[LENS_PIPELINE] 图谱编织失败：未找到邻居标签");
             return Ok(vec![]);
@@ -381,8 +490,8 @@ impl LensRetrievalEngine {
                 let (idx_i, _) = neighbors[i];
                 let (idx_j, _) = neighbors[j];
 
-                let vec_i = tag_sea.tag_pool.get_vector(idx_i).ok_or("获取向量失败")?;
-                let vec_j = tag_sea.tag_pool.get_vector(idx_j).ok_or("获取向量失败")?;
+                let vec_i = tag_pool.get_vector(idx_i).ok_or("获取向量失败")?;
+                let vec_j = tag_pool.get_vector(idx_j).ok_or("获取向量失败")?;
 
                 let mut sim = self.cosine_similarity(vec_i, vec_j);
 
@@ -412,7 +521,7 @@ impl LensRetrievalEngine {
 
         // 使用 SVD 计算正则化伪逆 (L^T L + λI)^{-1} L^T
         // 实际上对于拉普拉斯矩阵，直接对 L 进行 SVD 更稳
-        let lambda = 0.01;
+        let lambda = params.regularization;
         let l_t_l = laplacian.transpose() * &laplacian;
         let n_dim = l_t_l.nrows();
         let mut target_matrix = l_t_l;
@@ -439,10 +548,7 @@ impl LensRetrievalEngine {
         let mut entry_scores: HashMap<Uuid, f32> = HashMap::new();
         for i in 0..n {
             let (tag_idx, _) = neighbors[i];
-            let tag_name = tag_sea
-                .tag_pool
-                .get_tag_name(tag_idx)
-                .ok_or("获取标签名失败")?;
+            let tag_name = tag_pool.get_tag_name(tag_idx).ok_or("获取标签名失败")?;
             let energy = propagated_energy[i];
 
             if let Some(associated_entrys) = tag_sea.tag_to_entry_weights.get(tag_name) {
@@ -457,7 +563,7 @@ impl LensRetrievalEngine {
         let mut results = Vec::new();
         for (entry_id, score) in entry_scores {
             if let Some(entry) = base.entries.get(&entry_id) {
-                if filters.enabled_only.unwrap_or(true) && !entry.enabled {
+                if !filters.accepts_entry(entry) {
                     continue;
                 }
 
@@ -466,7 +572,7 @@ impl LensRetrievalEngine {
                     score,
                     match_type: "lens".to_string(),
                     recall_id,
-                    recall_name: recall_name.clone(),
+                    recall_name: base.meta.name.clone(),
                     highlight: None,
                 });
             }
@@ -504,3 +610,46 @@ impl LensRetrievalEngine {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_lens_params_from_extra() {
+        let engine = LensRetrievalEngine::new();
+
+        let defaults = engine.resolve_params(&RecallSearchFilters::default());
+        assert_eq!(defaults.graph_neighbors, 80);
+        assert_eq!(defaults.auto_lens_tags, 3);
+
+        let mut filters = RecallSearchFilters::default();
+        filters
+            .extra
+            .insert("graphNeighbors".to_string(), serde_json::json!(120));
+        filters
+            .extra
+            .insert("autoRefractionIndex".to_string(), serde_json::json!(1.5));
+        let params = engine.resolve_params(&filters);
+        assert_eq!(params.graph_neighbors, 120);
+        assert_eq!(params.auto_refraction_index, 1.0);
+    }
+
+    #[test]
+    fn test_lens_engine_info_declares_params() {
+        let info = LensRetrievalEngine::new().info();
+        let ids: Vec<&str> = info
+            .parameters
+            .iter()
+            .filter_map(|p| p.get("id").and_then(|v| v.as_str()))
+            .collect();
+        for id in [
+            "graphNeighbors",
+            "autoLensTags",
+            "autoRefractionIndex",
+            "historyDecay",
+        ] {
+            assert!(ids.contains(&id), "缺少参数 {}", id);
+        }
+    }
+}
@@ -256,7 +256,7 @@ impl RetrievalEngine for VectorRetrievalEngine {
                 );
                 for (entry_id, tag_score) in tag_scores {
                     if let Some(entry) = base.entries.get(&entry_id) {
-                        if filters.enabled_only.unwrap_or(true) && !entry.enabled {
+                        if !filters.accepts_entry(entry) {
                             continue;
                         }
                         if tag_score > 0.5 {
@@ -356,7 +356,7 @@ impl RetrievalEngine for VectorRetrievalEngine {
 
             for (entry_id, vector_score) in scores {
                 if let Some(entry) = base.entries.get(&entry_id) {
-                    if filters.enabled_only.unwrap_or(true) && !entry.enabled {
+                    if !filters.accepts_entry(entry) {
                         continue;
                     }
 
@@ -418,7 +418,7 @@ impl RetrievalEngine for VectorRetrievalEngine {
                 }
 
                 if let Some(entry) = base.entries.get(&entry_id) {
-                    if filters.enabled_only.unwrap_or(true) && !entry.enabled {
+                    if !filters.accepts_entry(entry) {
                         continue;
                     }
 
@@ -15,7 +15,8 @@
 use crate::recall::core::{RecallResult, RetrievalEngine};
 use crate::recall::index::InMemoryDatabase;
 use crate::recall::search::{
-    BlenderRetrievalEngine, KeywordRetrievalEngine, LensRetrievalEngine, VectorRetrievalEngine,
+    BlenderRetrievalEngine, Bm25RetrievalEngine, KeywordRetrievalEngine, LensRetrievalEngine,
+    VectorRetrievalEngine,
 };
 use crate::recall::tag_pool::GlobalTagPoolManager;
 use std::collections::HashMap;
@@ -34,6 +35,9 @@ pub struct CachedRetrievalEntry {
 /// Key 为 SHA-256 字符串，Value 为 (结果, 最后访问时间戳)
 pub type RetrievalCache = HashMap<String, (CachedRetrievalEntry, u64)>;
 
+/// 分页检索的全量排序结果缓存。Key 为 query + filters 的 SHA-256，Value 为 (全量结果, 写入时间戳)
+pub type SearchPageCache = HashMap<String, (Vec<RecallResult>, u64)>;
+
 pub struct RecallState {
     // 互斥锁保护，防止并发写入导致冲突
     pub lock: Mutex<()>,
@@ -47,6 +51,8 @@ pub struct RecallState {
     pub embedding_cache: Arc<RwLock<EmbeddingCache>>,
     /// 全局 RAG 检索结果缓存（不按 session 隔离）
     pub retrieval_cache: Arc<RwLock<RetrievalCache>>,
+    /// 分页检索缓存，翻页时直接切片，避免重跑向量/残差计算
+    pub search_page_cache: Arc<RwLock<SearchPageCache>>,
 }
 
 impl RecallState {
@@ -54,6 +60,7 @@ impl RecallState {
         // 注册默认引擎
         let engines: Vec<Box<dyn RetrievalEngine>> = vec![
             Box::new(KeywordRetrievalEngine::new()),
+            Box::new(Bm25RetrievalEngine::new()),
             Box::new(VectorRetrievalEngine::new()),
             Box::new(LensRetrievalEngine::new()),
             Box::new(BlenderRetrievalEngine::new()),
@@ -66,6 +73,14 @@ impl RecallState {
             tag_pool: GlobalTagPoolManager::new(),
             embedding_cache: Arc::new(RwLock::new(HashMap::new())),
             retrieval_cache: Arc::new(RwLock::new(HashMap::new())),
+            search_page_cache: Arc::new(RwLock::new(HashMap::new())),
+        }
+    }
+
+    /// 清空分页检索缓存，思绪集或条目发生变动后调用，避免翻页返回已删除或过期的条目
+    pub fn clear_search_page_cache(&self) {
+        if let Ok(mut cache) = self.search_page_cache.write() {
+            cache.clear();
         }
     }
 
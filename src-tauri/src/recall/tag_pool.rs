@@ -173,6 +173,26 @@ impl ModelTagPool {
         self.index = None;
     }
 
+    /// 让新标签名复用已有标签的向量 (用于标签重命名/合并)
+    ///
+    /// 源向量会复制为目标标签的新行，保持注册表与向量行一一对应。
+    /// 仅在目标标签尚未向量化时生效，返回是否有改动。
+    pub fn alias_tag(&mut self, source: &str, target: &str) -> bool {
+        if self.registry.contains_key(target) || self.dimension == 0 {
+            return false;
+        }
+        let Some(&idx) = self.registry.get(source) else {
+            return false;
+        };
+        let start = idx * self.dimension;
+        let Some(vector) = self.vectors.get(start..start + self.dimension) else {
+            return false;
+        };
+        let vector = vector.to_vec();
+        self.sync_vectors(vec![(target.to_string(), vector)]);
+        true
+    }
+
     /// 检查缺失的标签
     pub fn get_missing_tags(&self, tags: Vec<String>) -> Vec<String> {
         tags.into_iter()
@@ -303,3 +323,31 @@ impl GlobalTagPoolManager {
         Ok(pool_arc)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::ModelTagPool;
+
+    #[test]
+    fn alias_tag_copies_vector_into_a_new_row() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut pool = ModelTagPool::new("model".to_string());
+        pool.sync_vectors(vec![
+            ("旧名".to_string(), vec![1.0, 0.0]),
+            ("其他".to_string(), vec![0.0, 1.0]),
+        ]);
+
+        assert!(pool.alias_tag("旧名", "新名"));
+        assert!(!pool.alias_tag("旧名", "新名"));
+        assert_eq!(pool.registry.len(), 3);
+        assert_eq!(pool.id_to_name.len(), 3);
+        assert_eq!(pool.vectors.len(), 3 * pool.dimension);
+        assert_eq!(pool.id_to_name[pool.registry["新名"]], "新名");
+
+        pool.save(dir.path()).unwrap();
+        let loaded = ModelTagPool::load(dir.path(), "model").unwrap();
+        assert_eq!(loaded.dimension, 2);
+        let idx = loaded.registry["新名"];
+        assert_eq!(&loaded.vectors[idx * 2..idx * 2 + 2], &[1.0, 0.0]);
+    }
+}
@@ -17,11 +17,11 @@ use crate::recall::tag_pool::ModelTagPool;
 use std::collections::HashMap;
 use uuid::Uuid;
 
-/// TagSea: 标签海，整合了向量空间、关联权重和语法权重的数据结构
-#[allow(dead_code)]
+/// TagSea: 标签海，整合了关联权重和语法权重的数据结构
+///
+/// 只依赖思绪集条目，与向量模型无关，因此按思绪集缓存在 `InMemoryBase` 中；
+/// 向量空间部分由调用方传入的模型标签池提供，避免每次检索克隆整个标签池。
 pub struct TagSea {
-    /// 模型标签池引用
-    pub tag_pool: ModelTagPool,
     /// 标签名 -> 关联的 CAIU ID 及权重
     pub tag_to_entry_weights: HashMap<String, Vec<(Uuid, f32)>>,
     /// 标签名 -> 静态语法权重
@@ -31,9 +31,8 @@ pub struct TagSea {
 }
 
 impl TagSea {
-    /// 从内存库和标签池构建 TagSea
-    #[allow(dead_code)]
-    pub fn build(base: &InMemoryBase, tag_pool: ModelTagPool) -> Self {
+    /// 从内存库构建 TagSea
+    pub fn build(base: &InMemoryBase) -> Self {
         let mut tag_to_entry_weights: HashMap<String, Vec<(Uuid, f32)>> = HashMap::new();
         let mut tag_syntax_weights: HashMap<String, f32> = HashMap::new();
         let total_entrys = base.entries.len() as f32;
@@ -68,7 +67,6 @@ impl TagSea {
         }
 
         Self {
-            tag_pool,
             tag_to_entry_weights,
             tag_syntax_weights,
             tag_entropy_weights,
@@ -77,26 +75,29 @@ impl TagSea {
 
     /// 计算透镜检索引力中心 (Lens Center)
     /// 逻辑：对 required_tags 的向量进行复合加权平均 (语法权重 * 信息熵权重)
-    #[allow(dead_code)]
-    pub fn compute_lens_center(&self, required_tags: &[String]) -> Option<Vec<f32>> {
-        if required_tags.is_empty() || self.tag_pool.dimension == 0 {
+    pub fn compute_lens_center(
+        &self,
+        tag_pool: &ModelTagPool,
+        required_tags: &[String],
+    ) -> Option<Vec<f32>> {
+        if required_tags.is_empty() || tag_pool.dimension == 0 {
             return None;
         }
 
-        let mut center = vec![0.0; self.tag_pool.dimension];
+        let mut center = vec![0.0; tag_pool.dimension];
         let mut total_weight = 0.0;
 
         for tag_name in required_tags {
-            if let Some(&idx) = self.tag_pool.registry.get(tag_name) {
-                let start = idx * self.tag_pool.dimension;
-                let vector = &self.tag_pool.vectors[start..start + self.tag_pool.dimension];
+            if let Some(&idx) = tag_pool.registry.get(tag_name) {
+                let start = idx * tag_pool.dimension;
+                let vector = &tag_pool.vectors[start..start + tag_pool.dimension];
 
                 // 复合权重 = 静态语法权重 * 信息熵权重
                 let syntax_w = *self.tag_syntax_weights.get(tag_name).unwrap_or(&1.0);
                 let entropy_w = *self.tag_entropy_weights.get(tag_name).unwrap_or(&1.0);
                 let weight = syntax_w * entropy_w;
 
-                for i in 0..self.tag_pool.dimension {
+                for i in 0..tag_pool.dimension {
                     center[i] += vector[i] * weight;
                 }
                 total_weight += weight;
@@ -113,3 +114,54 @@ impl TagSea {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::recall::core::{RecallCollectionMeta, RecallEntry};
+    use crate::recall::index::db::InMemoryBase;
+    use std::sync::Arc;
+    use uuid::Uuid;
+
+    fn entry(tags: &[&str]) -> RecallEntry {
+        serde_json::from_value(serde_json::json!({
+            "id": Uuid::new_v4(),
+            "key": "k",
+            "content": "c",
+            "contentHash": null,
+            "tags": tags
+                .iter()
+                .map(|name| serde_json::json!({ "name": name, "weight": 1.0 }))
+                .collect::<Vec<_>>(),
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_tag_sea_cache_invalidated_on_entry_change() {
+        let meta: RecallCollectionMeta = serde_json::from_value(serde_json::json!({
+            "id": Uuid::new_v4(),
+            "name": "test",
+            "description": null,
+            "author": null,
+            "vectorization": { "isIndexed": false, "lastIndexedAt": null, "modelUsed": "" },
+            "icon": null,
+        }))
+        .unwrap();
+        let mut base = InMemoryBase::new(meta);
+        let first = entry(&["rust"]);
+        let first_id = first.id;
+        base.sync_entry(first);
+
+        let sea = base.tag_sea();
+        assert!(Arc::ptr_eq(&sea, &base.tag_sea()));
+        assert_eq!(sea.tag_to_entry_weights["rust"].len(), 1);
+
+        base.sync_entry(entry(&["rust", "tauri"]));
+        let rebuilt = base.tag_sea();
+        assert!(!Arc::ptr_eq(&sea, &rebuilt));
+        assert_eq!(rebuilt.tag_to_entry_weights["rust"].len(), 2);
+
+        base.remove_entry(&first_id);
+        assert_eq!(base.tag_sea().tag_to_entry_weights["rust"].len(), 1);
+    }
+}
@@ -22,7 +22,24 @@ pub fn get_now() -> i64 {
         .as_secs() as i64
 }
 
+/// 秒级与毫秒级时间戳的分界：小于该值的视为秒（1e11 秒约为公元 5138 年，毫秒则为 1973 年）
+const MILLIS_TIMESTAMP_THRESHOLD: i64 = 100_000_000_000;
+
+/// 将时间戳统一为毫秒
+///
+/// 后端条目使用秒（见 [`get_now`]），前端传入的多为 `Date.now()` 毫秒值，比较或格式化前需先对齐单位。
+pub fn timestamp_to_millis(timestamp: i64) -> i64 {
+    if timestamp.abs() < MILLIS_TIMESTAMP_THRESHOLD {
+        timestamp.saturating_mul(1000)
+    } else {
+        timestamp
+    }
+}
+
 /// 计算内容的 SHA-256 哈希值（与前端保持一致）
+///
+/// 对原文逐字节计算，不做 trim 或换行规范化；该哈希决定向量是否失效，口径不可随意变动。
+/// 导入去重请使用 [`calculate_dedup_hash`]。
 pub fn calculate_content_hash(content: &str) -> String {
     if content.is_empty() {
         return String::new();
@@ -32,20 +49,154 @@ pub fn calculate_content_hash(content: &str) -> String {
     format!("{:x}", hasher.finalize())
 }
 
-/// 自动生成内容摘要 (截取前 120 字符并清理 Markdown)
-pub fn generate_summary(content: &str) -> String {
-    let plain_text = content
+/// 计算用于去重的内容哈希
+///
+/// 先去掉 BOM、统一换行为 `\n`、去除行尾空白与首尾空行，
+/// 避免同一内容因编辑器或平台差异被判为不同条目。
+pub fn calculate_dedup_hash(content: &str) -> String {
+    let content = content.strip_prefix('\u{feff}').unwrap_or(content);
+    let normalized = content
         .lines()
-        .filter(|line| !line.trim().starts_with('#')) // 过滤标题
+        .map(str::trim_end)
         .collect::<Vec<_>>()
-        .join(" ");
+        .join("\n");
+    calculate_content_hash(normalized.trim_matches('\n'))
+}
 
-    let summary: String = plain_text.chars().take(120).collect();
-    if plain_text.chars().count() > 120 {
-        format!("{}...", summary.trim())
-    } else {
-        summary.trim().to_string()
+/// 摘要的最大字符数
+const SUMMARY_MAX_CHARS: usize = 120;
+/// 摘要最多包含的句子数
+const SUMMARY_MAX_SENTENCES: usize = 3;
+
+/// 句末标点（中文句号/问号/叹号/省略号与英文 .!?）
+fn is_sentence_terminator(ch: char) -> bool {
+    matches!(ch, '。' | '！' | '？' | '…' | '!' | '?' | '.')
+}
+
+/// 可紧跟在句末标点后的闭合符号，归入同一句
+fn is_closing_mark(ch: char) -> bool {
+    matches!(
+        ch,
+        '”' | '’' | '」' | '』' | '）' | '》' | '"' | '\'' | ')' | ']'
+    )
+}
+
+/// 按句子边界切分文本，保留句末标点
+///
+/// 英文句点只有后跟空白或位于结尾时才算句末，避免把 3.14、v1.2、example.com 切开
+fn split_sentences(text: &str) -> Vec<String> {
+    let chars: Vec<char> = text.chars().collect();
+    let mut sentences = Vec::new();
+    let mut current = String::new();
+    let mut i = 0;
+    while i < chars.len() {
+        let ch = chars[i];
+        current.push(ch);
+        i += 1;
+
+        let is_end = is_sentence_terminator(ch)
+            && (ch != '.' || chars.get(i).is_none_or(|next| next.is_whitespace()));
+        if !is_end {
+            continue;
+        }
+        // 连续的句末标点（如 "？！"、"……"）与闭合引号一并归入当前句
+        while let Some(&next) = chars.get(i) {
+            if is_sentence_terminator(next) || is_closing_mark(next) {
+                current.push(next);
+                i += 1;
+            } else {
+                break;
+            }
+        }
+        let sentence = current.trim();
+        if !sentence.is_empty() {
+            sentences.push(sentence.to_string());
+        }
+        current.clear();
+    }
+    let rest = current.trim();
+    if !rest.is_empty() {
+        sentences.push(rest.to_string());
+    }
+    sentences
+}
+
+/// 去掉 Markdown 标题行与行首的列表/引用标记，合并为一段纯文本
+fn summary_plain_text(content: &str) -> String {
+    let mut text = String::new();
+    for line in content.lines() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let line = line.trim_start_matches('>').trim_start();
+        let line = ["- ", "* ", "+ "]
+            .iter()
+            .find_map(|marker| line.strip_prefix(marker))
+            .unwrap_or(line)
+            .trim_start();
+        if line.is_empty() {
+            continue;
+        }
+        // 中文行之间直接拼接，避免在汉字之间插入多余空格
+        let needs_space = text
+            .chars()
+            .last()
+            .zip(line.chars().next())
+            .is_some_and(|(prev, next)| prev.is_ascii() || next.is_ascii());
+        if needs_space {
+            text.push(' ');
+        }
+        text.push_str(line);
+    }
+    text
+}
+
+/// 自动生成内容摘要
+///
+/// 清理 Markdown 后按句子边界截取前若干个完整句子（不超过 120 字符）；
+/// 首句本身就超长（如无标点的长文本）时退回按长度截断，优先断在逗号或空白处
+pub fn generate_summary(content: &str) -> String {
+    let plain_text = summary_plain_text(content);
+    let sentences = split_sentences(&plain_text);
+
+    let mut summary = String::new();
+    let mut summary_chars = 0;
+    for sentence in sentences.iter().take(SUMMARY_MAX_SENTENCES) {
+        // 英文句子之间保留空格，中文句子直接相连
+        let needs_space = !summary.is_empty() && sentence.starts_with(|c: char| c.is_ascii());
+        let sentence_chars = sentence.chars().count();
+        if summary_chars + usize::from(needs_space) + sentence_chars > SUMMARY_MAX_CHARS {
+            break;
+        }
+        if needs_space {
+            summary.push(' ');
+            summary_chars += 1;
+        }
+        summary.push_str(sentence);
+        summary_chars += sentence_chars;
+    }
+    if !summary.is_empty() {
+        return summary;
+    }
+
+    // 兜底：没有可用的句子边界，按长度截断
+    if plain_text.chars().count() <= SUMMARY_MAX_CHARS {
+        return plain_text.trim().to_string();
     }
+    let truncated: String = plain_text.chars().take(SUMMARY_MAX_CHARS).collect();
+    // 在后半段找最近的逗号/分号/空白断开，避免把词语或数字切成两半
+    let min_cut = truncated.len() / 2;
+    let cut = truncated
+        .char_indices()
+        .rev()
+        .take_while(|(idx, _)| *idx >= min_cut)
+        .find(|(_, ch)| matches!(ch, '，' | ',' | '；' | ';' | '、' | ' '))
+        .map_or(truncated.len(), |(idx, _)| idx);
+    format!(
+        "{}...",
+        truncated[..cut].trim_end_matches(['，', ',', '；', ';', '、', ' '])
+    )
 }
 
 /// 从内容中提取标签 (Tag: xxx 或 标签: xxx)
@@ -74,6 +225,21 @@ pub fn extract_title_from_content(content: &str) -> Option<String> {
         .and_then(|caps| caps.get(1).map(|m| m.as_str().trim().to_string()))
 }
 
+/// 从内容中提取 [[Key]] 引用 (支持 [[Key|别名]] 与 [[Key#章节]]，按出现顺序去重)
+pub fn extract_reference_keys(content: &str) -> Vec<String> {
+    use regex::Regex;
+    let re = Regex::new(r"\[\[([^\[\]\n]+?)\]\]").unwrap();
+    let mut keys: Vec<String> = Vec::new();
+    for caps in re.captures_iter(content) {
+        let target = caps[1].split('|').next().unwrap_or_default();
+        let key = target.split('#').next().unwrap_or_default().trim();
+        if !key.is_empty() && !keys.iter().any(|k| k == key) {
+            keys.push(key.to_string());
+        }
+    }
+    keys
+}
+
 /// Gram-Schmidt 投影：将 vec 投影到 basis 方向
 /// 返回投影向量 proj = (vec·basis / ||basis||²) * basis
 pub fn project_onto(vec: &[f32], basis: &[f32]) -> Vec<f32> {
@@ -121,6 +287,71 @@ pub fn vec_normalize(v: &mut [f32]) {
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_timestamp_to_millis_normalizes_seconds() {
+        assert_eq!(timestamp_to_millis(1706600000), 1706600000000);
+        assert_eq!(timestamp_to_millis(1706600000123), 1706600000123);
+        assert_eq!(timestamp_to_millis(0), 0);
+    }
+
+    #[test]
+    fn test_dedup_hash_normalizes_line_endings_and_whitespace() {
+        let base = calculate_dedup_hash("# 标题\n正文");
+        assert_eq!(calculate_dedup_hash("\u{feff}# 标题\r\n正文\r\n"), base);
+        assert_eq!(calculate_dedup_hash("\n# 标题  \n正文\t\n\n"), base);
+        assert_ne!(calculate_dedup_hash("# 标题\n\n正文"), base);
+        // 存储用哈希保持逐字节口径
+        assert_ne!(
+            calculate_content_hash("正文\n"),
+            calculate_content_hash("正文")
+        );
+    }
+
+    #[test]
+    fn test_generate_summary_keeps_whole_sentences() {
+        let content = "# 标题\n第一句话。第二句话！\n第三句话？第四句话。";
+        assert_eq!(generate_summary(content), "第一句话。第二句话！第三句话？");
+
+        let content = "Version 3.14 is out. It fixes bugs at example.com! More soon.";
+        assert_eq!(
+            generate_summary(content),
+            "Version 3.14 is out. It fixes bugs at example.com! More soon."
+        );
+
+        // 引号归入同一句，列表标记被去掉
+        let content = "- 他说：“好的。”然后离开了。";
+        assert_eq!(generate_summary(content), "他说：“好的。”然后离开了。");
+    }
+
+    #[test]
+    fn test_generate_summary_stops_before_exceeding_limit() {
+        let first = "短句。";
+        let long = "很".repeat(150) + "。";
+        let summary = generate_summary(&format!("{}{}", first, long));
+        assert_eq!(summary, first);
+    }
+
+    #[test]
+    fn test_generate_summary_falls_back_to_length_limit() {
+        let content = "甲".repeat(80) + "，" + &"乙".repeat(80);
+        let summary = generate_summary(&content);
+        assert_eq!(summary, format!("{}...", "甲".repeat(80)));
+        assert!(summary.chars().count() <= SUMMARY_MAX_CHARS + 3);
+
+        let content = "无".repeat(300);
+        assert_eq!(
+            generate_summary(&content),
+            format!("{}...", "无".repeat(SUMMARY_MAX_CHARS))
+        );
+        assert_eq!(generate_summary("没有标点的短文本"), "没有标点的短文本");
+    }
+
+    #[test]
+    fn test_extract_reference_keys() {
+        let content = "见 [[Rust]] 与 [[Tauri|框架]]，另见 [[Rust#所有权]] 和 [[ ]]、[[a\nb]]";
+        assert_eq!(extract_reference_keys(content), vec!["Rust", "Tauri"]);
+    }
+
     #[test]
     fn test_project_onto() {
         let vec = vec![3.0, 4.0];
@@ -32,6 +32,9 @@ import type {
   PaginatedAssetsResponse,
   AssetStats,
   AssetSidecarAction,
+  CatalogVerifyResult,
+  BatchAssetDeleteResult,
+  AssetCollection,
 } from "@/types/asset-management";
 import { toolRegistryManager } from "@/services/registry";
 import { customMessage } from "@/utils/customMessage";
@@ -56,6 +59,21 @@ function showAssetImportWarnings(warnings?: AssetImportWarning[]) {
   }
 }
 
+/**
+ * 校验缩略图选项组合，与后端 AssetImportOptions::validate 保持一致：
+ * WebP 缩略图只支持无损编码，不接受 thumbnailQuality
+ */
+function validateImportOptions(options?: AssetImportOptions) {
+  if (
+    options?.thumbnailFormat === "webp" &&
+    options.thumbnailQuality !== undefined
+  ) {
+    throw new Error(
+      "WebP 缩略图使用无损编码，不支持 thumbnailQuality，请移除该选项或改用 JPEG"
+    );
+  }
+}
+
 /**
  * 重置资产根目录的缓存。
  * 当用户在设置中更改了资产路径时，需要调用此函数。
@@ -87,6 +105,7 @@ export const assetManagerEngine = {
     originalPath: string,
     options?: AssetImportOptions
   ): Promise<AssetImportResult> => {
+    validateImportOptions(options);
     return await invoke<AssetImportResult>("import_asset_from_path", {
       originalPath,
       options,
@@ -115,6 +134,7 @@ export const assetManagerEngine = {
     originalName: string,
     options?: AssetImportOptions
   ): Promise<Asset> => {
+    validateImportOptions(options);
     const uint8Array = new Uint8Array(bytes);
     return await invoke<Asset>("import_asset_from_bytes", {
       bytes: uint8Array,
@@ -123,6 +143,17 @@ export const assetManagerEngine = {
     });
   },
 
+  /**
+   * 从网络 URL 下载并导入资产，来源自动记为 network
+   */
+  importAssetFromUrl: async (
+    url: string,
+    options?: AssetImportOptions
+  ): Promise<Asset> => {
+    validateImportOptions(options);
+    return await invoke<Asset>("import_asset_from_url", { url, options });
+  },
+
   /**
    * 获取资产的二进制数据
    * 注意：对于大文件（>10MB），此方法由于 Tauri 的 JSON 序列化限制（Vec<u8> 转数组）会非常慢且阻塞主线程。
@@ -236,6 +267,20 @@ export const assetManagerEngine = {
     return await invoke<Asset>("add_asset_source", { assetId, origin });
   },
 
+  /**
+   * 为资产添加标签，已存在的标签会被忽略
+   */
+  addAssetTags: async (assetId: string, tags: string[]): Promise<Asset> => {
+    return await invoke<Asset>("add_asset_tags", { assetId, tags });
+  },
+
+  /**
+   * 从资产中移除标签
+   */
+  removeAssetTags: async (assetId: string, tags: string[]): Promise<Asset> => {
+    return await invoke<Asset>("remove_asset_tags", { assetId, tags });
+  },
+
   /**
    * 从资产中移除一个来源。如果这是最后一个来源，资产将被删除。
    * @returns {Promise<{deleted: boolean, asset: Asset | null}>} 返回操作结果，如果资产被删除，asset为null
@@ -255,9 +300,17 @@ export const assetManagerEngine = {
 
   /**
    * 完全删除资产（移除所有来源并删除文件）
+   * @param permanent 为 true 时跳过回收站彻底删除，调用前必须经过用户二次确认
+   * @returns 实际释放的字节数
    */
-  removeAssetCompletely: async (assetId: string): Promise<void> => {
-    return await invoke<void>("remove_asset_completely", { assetId });
+  removeAssetCompletely: async (
+    assetId: string,
+    permanent = false
+  ): Promise<{ freedBytes: number }> => {
+    return await invoke<{ freedBytes: number }>("remove_asset_completely", {
+      assetId,
+      permanent,
+    });
   },
 
   /**
@@ -269,10 +322,57 @@ export const assetManagerEngine = {
 
   /**
    * 批量完全删除资产（移除所有来源并删除文件）
-   * @returns {Promise<string[]>} 返回删除失败的资产 ID 列表
+   * @param permanent 为 true 时跳过回收站彻底删除，调用前必须经过用户二次确认
+   * @returns 删除失败的资产 ID 列表与实际释放的字节数
+   */
+  removeAssetsCompletely: async (
+    assetIds: string[],
+    permanent = false
+  ): Promise<BatchAssetDeleteResult> => {
+    return await invoke<BatchAssetDeleteResult>("remove_assets_completely", {
+      assetIds,
+      permanent,
+    });
+  },
+
+  /**
+   * 创建资产集合
+   */
+  createCollection: async (
+    name: string,
+    description?: string
+  ): Promise<AssetCollection> => {
+    return await invoke<AssetCollection>("create_collection", {
+      name,
+      description: description ?? null,
+    });
+  },
+
+  /**
+   * 把资产加入集合，重复或不存在的资产会被忽略
+   */
+  addAssetsToCollection: async (
+    collectionId: string,
+    assetIds: string[]
+  ): Promise<AssetCollection> => {
+    return await invoke<AssetCollection>("add_assets_to_collection", {
+      collectionId,
+      assetIds,
+    });
+  },
+
+  /**
+   * 列出所有资产集合
+   */
+  listCollections: async (): Promise<AssetCollection[]> => {
+    return await invoke<AssetCollection[]>("list_collections");
+  },
+
+  /**
+   * 列出集合中的资产（按加入顺序）
    */
-  removeAssetsCompletely: async (assetIds: string[]): Promise<string[]> => {
-    return await invoke<string[]>("remove_assets_completely", { assetIds });
+  listCollectionAssets: async (collectionId: string): Promise<Asset[]> => {
+    return await invoke<Asset[]>("list_collection_assets", { collectionId });
   },
 
   /**
@@ -307,6 +407,13 @@ export const assetManagerEngine = {
     return await invoke<string>("rebuild_catalog_index");
   },
 
+  /**
+   * 校验 Catalog 索引文件，统计损坏的行
+   */
+  verifyCatalogIndex: async (): Promise<CatalogVerifyResult> => {
+    return await invoke<CatalogVerifyResult>("verify_catalog_index");
+  },
+
   /**
    * 保存资产缩略图
    * @param assetId 资产 ID
@@ -495,6 +602,7 @@ export function useAssetManager() {
     typeCounts: { image: 0, video: 0, audio: 0, document: 0, other: 0 },
     sourceModuleCounts: {},
     originCounts: { local: 0, clipboard: 0, network: 0, generated: 0 },
+    monthlyCounts: {},
   });
 
   // --- 方法 ---
@@ -705,6 +813,25 @@ export function useAssetManager() {
     }
   };
 
+  /**
+   * 从网络 URL 导入资产
+   */
+  const importAssetFromUrl = async (
+    url: string,
+    options?: AssetImportOptions
+  ): Promise<Asset> => {
+    try {
+      const promise = assetManagerEngine.importAssetFromUrl(url, options);
+      const asset = await withLoading(promise);
+      await handlePostImport(asset);
+      return asset;
+    } catch (err) {
+      handleError(err, "从 URL 导入失败");
+      // @ts-ignore
+      return Promise.reject(err);
+    }
+  };
+
   /**
    * 从剪贴板导入图片
    */
@@ -928,7 +1055,7 @@ export function useAssetManager() {
     assetIds: string[]
   ): Promise<string[]> => {
     try {
-      const failedIds =
+      const { failedIds } =
         await assetManagerEngine.removeAssetsCompletely(assetIds);
 
       // 从本地列表中移除成功删除的资产
@@ -995,6 +1122,7 @@ export function useAssetManager() {
     importAssetFromPath,
     importMultipleAssets,
     importAssetFromBytes,
+    importAssetFromUrl,
     importAssetFromClipboard,
 
     // 删除方法 - 业务模块应使用 removeSourceFromAsset
@@ -19,6 +19,18 @@ import { createModuleLogger } from "@/utils/logger";
 
 const logger = createModuleLogger("composables/useFFmpeg");
 
+/**
+ * FFmpeg 可用性与能力信息（check_ffmpeg_availability 的返回值）
+ */
+export interface FFmpegCapabilities {
+  available: boolean;
+  version: string | null;
+  ffprobeAvailable: boolean;
+  /** 关键编码器的可用性，用于在界面上禁用当前构建不支持的选项 */
+  encoders: { name: string; kind: "video" | "audio"; available: boolean }[];
+  error: string | null;
+}
+
 type MaybePathRef = Ref<string | undefined> | ComputedRef<string | undefined>;
 
 const normalizePath = (path?: string) => path?.trim() || "";
@@ -41,23 +53,32 @@ export function useFFmpeg(toolPrivatePath?: MaybePathRef) {
 
   const isUsingGlobal = computed(() => !normalizePath(toolPrivatePath?.value));
 
-  const checkAvailability = async (pathToCheck?: string): Promise<boolean> => {
+  const getCapabilities = async (
+    pathToCheck?: string
+  ): Promise<FFmpegCapabilities | null> => {
     const targetPath = normalizePath(pathToCheck) || activeFfmpegPath.value;
     try {
-      return await invoke<boolean>("check_ffmpeg_availability", {
+      return await invoke<FFmpegCapabilities>("check_ffmpeg_availability", {
         path: targetPath,
+        ffprobePath: globalFfprobePath.value,
       });
     } catch (error) {
       logger.error("检查 FFmpeg 可用性失败", error, { path: targetPath });
-      return false;
+      return null;
     }
   };
 
+  const checkAvailability = async (pathToCheck?: string): Promise<boolean> => {
+    const capabilities = await getCapabilities(pathToCheck);
+    return capabilities?.available ?? false;
+  };
+
   return {
     globalFfmpegPath,
     globalFfprobePath,
     activeFfmpegPath,
     isUsingGlobal,
     checkAvailability,
+    getCapabilities,
   };
 }
@@ -1042,8 +1042,11 @@ async function _updateWallpaper(
 async function _applyWindowEffect(effect: WindowEffect, enabled: boolean) {
   const finalEffect = enabled ? effect : "none";
   try {
-    await invoke("apply_window_effect", { effect: finalEffect });
-    logger.info("窗口特效已应用", { effect: finalEffect });
+    // 后端会按系统版本自动回退，返回实际生效的特效
+    const applied = await invoke<string>("apply_window_effect", {
+      effect: finalEffect,
+    });
+    logger.info("窗口特效已应用", { effect: finalEffect, applied });
   } catch (error) {
     errorHandler.warn(error, `应用窗口特效失败: ${finalEffect}`, {
       operation: "应用窗口特效",
@@ -123,6 +123,33 @@ export class NativePluginAdapter implements PluginProxy {
     }
   }
 
+  /**
+   * 热重载插件 - 卸载旧动态库后重新加载
+   *
+   * 仅 manifest.native.reloadable 为 true 时可用，否则后端会提示需要重启应用
+   */
+  async reload(): Promise<void> {
+    if (!this.enabled) {
+      throw new Error(`插件 ${this.id} 未启用，无法重载`);
+    }
+
+    logger.info(`热重载原生插件: ${this.id}`);
+
+    try {
+      const libraryPath = await this.getLibraryPath();
+      await invoke("reload_native_plugin", {
+        pluginId: this.manifest.id,
+        libraryPath,
+      });
+      logger.info(`原生插件 ${this.id} 重载成功`);
+    } catch (error) {
+      errorHandler.error(error, "重载原生插件失败", {
+        context: { pluginId: this.id },
+      });
+      throw error;
+    }
+  }
+
   /**
    * 获取当前平台的库文件路径
    */
@@ -127,6 +127,14 @@ export interface SidecarConfig {
    * 仅当 resident 为 true 时有效。
    */
   startupParams?: Record<string, unknown>;
+  /**
+   * 一次性模式的执行超时（毫秒），超时后进程会被终止
+   */
+  timeoutMs?: number;
+  /** 额外注入的环境变量 */
+  env?: Record<string, string>;
+  /** 工作目录，相对路径基于插件目录，默认为插件目录 */
+  cwd?: string;
 }
 
 /**
@@ -40,6 +40,10 @@ const errorHandler = createModuleErrorHandler(
  */
 interface SidecarOutputEvent {
   plugin_id: string;
+  /** 一次性模式下本次执行的会话 ID */
+  session_id?: string;
+  /** 输出来源 */
+  stream?: "stdout" | "stderr";
   event_type: string;
   data: string;
 }
@@ -64,6 +68,10 @@ interface SidecarExecuteRequest {
   args: string[];
   input?: string;
   dev_mode: boolean;
+  session_id?: string;
+  timeout_ms?: number;
+  env?: Record<string, string>;
+  working_dir?: string;
 }
 
 /**
@@ -90,6 +98,8 @@ export class SidecarPluginAdapter implements PluginProxy {
   private residentEventUnlisten: UnlistenFn | null = null;
   private eventHandlers: Map<string, (event: SidecarOutputEvent) => void> =
     new Map();
+  /** 一次性模式当前执行的会话 ID，用于过滤其他调用的输出 */
+  private activeSessionId: string | null = null;
   /** 常驻模式自定义事件回调 */
   private residentEventCallbacks: Map<string, Array<(data: any) => void>> =
     new Map();
@@ -157,7 +167,10 @@ export class SidecarPluginAdapter implements PluginProxy {
       (event) => {
         const data = event.payload;
 
-        if (data.plugin_id === this.manifest.id) {
+        if (
+          data.plugin_id === this.manifest.id &&
+          (!data.session_id || data.session_id === this.activeSessionId)
+        ) {
           logger.debug(`收到 Sidecar 输出事件: ${data.event_type}`, {
             data: data.data,
           });
@@ -502,6 +515,10 @@ export class SidecarPluginAdapter implements PluginProxy {
       environment: pluginEnvironmentService.get(),
     };
 
+    const sidecarConfig = this.manifest.sidecar;
+    const sessionId = crypto.randomUUID();
+    this.activeSessionId = sessionId;
+
     const request: SidecarExecuteRequest = {
       plugin_id: this.manifest.id,
       install_path: this.installPath,
@@ -509,6 +526,10 @@ export class SidecarPluginAdapter implements PluginProxy {
       args,
       input: JSON.stringify(inputData),
       dev_mode: this.devMode,
+      session_id: sessionId,
+      timeout_ms: sidecarConfig?.timeoutMs,
+      env: sidecarConfig?.env,
+      working_dir: sidecarConfig?.cwd,
     };
 
     return new Promise((resolve, reject) => {
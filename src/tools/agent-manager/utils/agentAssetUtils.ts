@@ -694,6 +694,34 @@ function isLikelyFilename(icon: string): boolean {
   );
 }
 
+/** 孤儿资产清理结果 */
+export interface OrphanAssetCleanupResult {
+  removedCount: number;
+  freedBytes: number;
+  removedAgentIds: string[];
+  errors: string[];
+}
+
+/**
+ * 清理已删除智能体残留的资产目录（移入回收站）
+ *
+ * @param validAgentIds 当前仍存在的智能体 ID，不能为空
+ */
+export async function cleanupOrphanAgentAssets(
+  validAgentIds: string[]
+): Promise<OrphanAssetCleanupResult> {
+  const result = await invoke<OrphanAssetCleanupResult>(
+    "cleanup_orphan_agent_assets",
+    { validAgentIds }
+  );
+  logger.info("孤儿资产清理完成", {
+    removedCount: result.removedCount,
+    freedBytes: result.freedBytes,
+    errors: result.errors.length,
+  });
+  return result;
+}
+
 /** 获取智能体目录相对于 AppData 的路径。 */
 export function getAgentStorageSubdirectory(agentId: string): string {
   return `agent-manager/agents/${agentId}`;
@@ -415,6 +415,8 @@ export async function listDirectory(
           isDir: boolean;
           modified: number | null;
           created: number | null;
+          isSymlink: boolean;
+          symlinkTarget: string | null;
         }>("get_file_metadata", { path: fullPath });
         entries.push({
           name,
@@ -423,6 +425,8 @@ export async function listDirectory(
           isDirectory: metadata.isDir,
           modified: metadata.modified,
           created: metadata.created,
+          isSymlink: metadata.isSymlink,
+          symlinkTarget: metadata.symlinkTarget,
         });
       } catch {
         // 如果获取元数据失败，跳过该文件
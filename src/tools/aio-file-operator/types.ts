@@ -24,6 +24,10 @@ export interface FileEntry {
   isDirectory: boolean;
   modified: number | null;
   created: number | null;
+  /** 是否为符号链接 */
+  isSymlink?: boolean;
+  /** 符号链接指向的路径 */
+  symlinkTarget?: string | null;
 }
 
 /** 读取文件参数 */
@@ -71,6 +71,7 @@ export function useDeduplicatorRunner() {
     }
 
     store.isScanning = true;
+    store.isPaused = false;
     store.scanProgress = null;
 
     const result = await errorHandler.wrapAsync(
@@ -83,6 +84,7 @@ export function useDeduplicatorRunner() {
     );
 
     store.isScanning = false;
+    store.isPaused = false;
     store.scanProgress = null;
 
     if (result === null) return null;
@@ -108,12 +110,39 @@ export function useDeduplicatorRunner() {
 
     if (result !== null) {
       store.isScanning = false;
+      store.isPaused = false;
       store.scanProgress = null;
       customMessage.success("已停止扫描");
       logger.info("用户手动停止扫描");
     }
   }
 
+  /** 暂停扫描（已完成的进度保留，可随时继续或停止） */
+  async function pauseScan() {
+    const result = await errorHandler.wrapAsync(
+      () => invoke("pause_dedup_scan"),
+      { userMessage: "暂停扫描失败" }
+    );
+
+    if (result !== null) {
+      store.isPaused = true;
+      logger.info("用户暂停扫描");
+    }
+  }
+
+  /** 继续已暂停的扫描 */
+  async function resumeScan() {
+    const result = await errorHandler.wrapAsync(
+      () => invoke("resume_dedup_scan"),
+      { userMessage: "继续扫描失败" }
+    );
+
+    if (result !== null) {
+      store.isPaused = false;
+      logger.info("用户继续扫描");
+    }
+  }
+
   /** 删除选中的重复文件 */
   async function deleteSelected(): Promise<DedupDeleteResult | null> {
     const paths = Array.from(store.selectedPaths);
@@ -172,6 +201,8 @@ export function useDeduplicatorRunner() {
     dispose,
     scanDirectory,
     stopScan,
+    pauseScan,
+    resumeScan,
     deleteSelected,
     readFileForDiff,
   };
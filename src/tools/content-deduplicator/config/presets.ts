@@ -48,6 +48,7 @@ export const DEFAULT_CONFIG: SimilarityConfig = {
   suspiciousSizeLimit: 3072,
   preset: "relaxed",
   normalizeOptions: { ...DEFAULT_NORMALIZE },
+  includeBinary: false,
 };
 
 /** 预设列表 */
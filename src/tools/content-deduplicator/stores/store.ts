@@ -33,6 +33,8 @@ export const useContentDeduplicatorStore = defineStore(
 
     // ==================== 扫描状态 ====================
     const isScanning = ref(false);
+    /** 扫描是否处于暂停状态 */
+    const isPaused = ref(false);
     const scanProgress = ref<DedupScanProgress | null>(null);
     const hasScanned = ref(false);
 
@@ -221,6 +223,7 @@ export const useContentDeduplicatorStore = defineStore(
       scanPath.value = "";
       config.value = getConfigFromPreset("relaxed");
       isScanning.value = false;
+      isPaused.value = false;
       scanProgress.value = null;
       hasScanned.value = false;
       result.value = null;
@@ -239,6 +242,7 @@ export const useContentDeduplicatorStore = defineStore(
       config,
       // 扫描状态
       isScanning,
+      isPaused,
       scanProgress,
       hasScanned,
       // 结果
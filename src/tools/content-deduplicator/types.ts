@@ -48,8 +48,10 @@ export interface SimilarityConfig {
   suspiciousSizeLimit: number;
   /** 预设名称 */
   preset: string;
-  /** 规范化选项 */
+  /** 规范化选项（对二进制文件无效） */
   normalizeOptions: NormalizeOptions;
+  /** 是否包含二进制文件（仅按原始内容精确查重） */
+  includeBinary?: boolean;
 }
 
 // ==================== 结果类型 ====================
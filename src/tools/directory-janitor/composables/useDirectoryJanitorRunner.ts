@@ -18,7 +18,7 @@ import { createModuleLogger } from "@/utils/logger";
 import { createModuleErrorHandler, ErrorLevel } from "@/utils/errorHandler";
 import { formatBytes, resolveEnvPath } from "../utils/utils";
 import { useDirectoryJanitorStore } from "../stores/store";
-import type { AnalysisResult, CleanupResult } from "../types";
+import type { AnalysisResult, CleanupResult, CleanupRule } from "../types";
 import type { CleanupPreset } from "../config/presets";
 
 const logger = createModuleLogger("directory-janitor/runner");
@@ -30,6 +30,8 @@ export interface ScanOptions {
   minAgeDays?: number;
   minSizeMB?: number;
   maxDepth?: number;
+  /** 自定义清理规则，非空时只保留命中规则的项目 */
+  rules?: CleanupRule[];
 }
 
 export interface FormattedScanResult {
@@ -166,6 +168,7 @@ export function useDirectoryJanitorRunner() {
           minAgeDays: options?.minAgeDays ?? store.minAgeDays,
           minSizeMB: options?.minSizeMB ?? store.minSizeMB,
           maxDepth: options?.maxDepth ?? store.maxDepth,
+          rules: options?.rules,
         };
 
         if (!scanOptions.path) {
@@ -186,6 +189,7 @@ export function useDirectoryJanitorRunner() {
               minSizeMb: scanOptions.minSizeMB,
               maxDepth:
                 scanOptions.maxDepth === 10 ? undefined : scanOptions.maxDepth,
+              rules: scanOptions.rules,
               window: getCurrentWindow(),
             }
           );
@@ -193,15 +197,19 @@ export function useDirectoryJanitorRunner() {
           store.allItems = result.items;
           store.selectedPaths = new Set();
           store.hasAnalyzed = true;
+          store.scanWasCancelled = result.wasCancelled;
 
           // 清除之前的二次筛选条件
           store.clearFilters();
 
-          logger.info("目录分析完成", {
-            path: scanOptions.path,
-            totalItems: result.statistics.totalItems,
-            totalSize: result.statistics.totalSize,
-          });
+          logger.info(
+            result.wasCancelled ? "目录分析已取消，保留部分结果" : "目录分析完成",
+            {
+              path: scanOptions.path,
+              totalItems: result.statistics.totalItems,
+              totalSize: result.statistics.totalSize,
+            }
+          );
 
           return result;
         } finally {
@@ -310,7 +318,8 @@ export function useDirectoryJanitorRunner() {
    */
   function getFormattedScanResult(): FormattedScanResult {
     const stats = store.filteredStatistics;
-    const summary = `扫描完成: 找到 ${stats.totalItems} 项（${stats.totalDirs} 个目录，${stats.totalFiles} 个文件），共 ${formatBytes(stats.totalSize)}`;
+    const prefix = store.scanWasCancelled ? "扫描已取消（部分结果）" : "扫描完成";
+    const summary = `${prefix}: 找到 ${stats.totalItems} 项（${stats.totalDirs} 个目录，${stats.totalFiles} 个文件），共 ${formatBytes(stats.totalSize)}`;
 
     return {
       summary,
@@ -216,11 +216,27 @@ export default class DirectoryJanitorRegistry implements ToolRegistry {
 
         try {
           // 执行扫描
-          await runner.analyzePath(normalizedScanOptions);
+          const analysis = await runner.analyzePath(normalizedScanOptions);
 
           // 获取扫描结果
           const scanResult = runner.getFormattedScanResult();
 
+          // 扫描被取消时只返回部分结果，不对其执行清理
+          if (analysis?.wasCancelled) {
+            return {
+              scanResult,
+              cleanupResult: {
+                summary: "扫描已取消，未执行清理",
+                details: {
+                  successCount: 0,
+                  errorCount: 0,
+                  freedSpace: 0,
+                  errors: [],
+                },
+              },
+            };
+          }
+
           // 如果没有找到项目，直接返回
           if (scanResult.details.totalItems === 0) {
             return {
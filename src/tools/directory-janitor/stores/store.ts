@@ -38,6 +38,8 @@ export const useDirectoryJanitorStore = defineStore("directory-janitor", () => {
   const allItems = ref<ItemInfo[]>([]);
   const selectedPaths = ref<Set<string>>(new Set());
   const hasAnalyzed = ref(false);
+  /** 最近一次扫描是否被用户取消（结果为部分结果） */
+  const scanWasCancelled = ref(false);
 
   // ==================== 二次筛选条件 ====================
   const filterNamePattern = ref("");
@@ -143,6 +145,7 @@ export const useDirectoryJanitorStore = defineStore("directory-janitor", () => {
     allItems.value = [];
     selectedPaths.value = new Set();
     hasAnalyzed.value = false;
+    scanWasCancelled.value = false;
     clearFilters();
   }
 
@@ -158,6 +161,7 @@ export const useDirectoryJanitorStore = defineStore("directory-janitor", () => {
     allItems,
     selectedPaths,
     hasAnalyzed,
+    scanWasCancelled,
 
     // 二次筛选条件
     filterNamePattern,
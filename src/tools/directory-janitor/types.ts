@@ -18,11 +18,41 @@
 
 /** 项目信息 */
 export interface ItemInfo {
+  /** 由路径派生的稳定 ID，可用于按 id 清理 */
+  id?: string;
   path: string;
   name: string;
   isDir: boolean;
   size: number;
   modified: number;
+  /** 命中的自定义规则 ID */
+  matchedRule?: string | null;
+}
+
+/**
+ * 自定义清理规则
+ *
+ * 规则内的条件需同时满足；多条规则按顺序取第一条命中的
+ */
+export interface CleanupRule {
+  id: string;
+  /** 扩展名列表，不区分大小写 */
+  extensions?: string[];
+  minSizeMb?: number;
+  /** 最后修改时间早于 N 天 */
+  olderThanDays?: number;
+  /** 通配符；包含 `/` 时匹配相对扫描目录的路径，否则匹配名称 */
+  glob?: string;
+  /** 仅匹配文件或目录 */
+  target?: "file" | "dir";
+}
+
+/** 单条规则的命中统计 */
+export interface RuleStatistics {
+  ruleId: string;
+  itemCount: number;
+  /** 预计释放空间 */
+  totalSize: number;
 }
 
 /** 统计信息 */
@@ -31,12 +61,15 @@ export interface Statistics {
   totalSize: number;
   totalDirs: number;
   totalFiles: number;
+  ruleStats?: RuleStatistics[];
 }
 
 /** 分析结果 */
 export interface AnalysisResult {
   items: ItemInfo[];
   statistics: Statistics;
+  /** 扫描被用户取消，items 只包含取消前已发现的项目 */
+  wasCancelled: boolean;
 }
 
 /** 清理结果 */
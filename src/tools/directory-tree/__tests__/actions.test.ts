@@ -189,6 +189,26 @@ describe("directory-tree actions", () => {
       expect(output).not.toContain("README.md");
     });
 
+    it("应将后端折叠的目录渲染为子项数量", () => {
+      const output = renderTree({
+        name: "repo",
+        is_dir: true,
+        size: 0,
+        children: [
+          {
+            name: "node_modules",
+            is_dir: true,
+            size: 0,
+            children: [],
+            collapsed_count: 42,
+          },
+        ],
+      });
+
+      expect(output).toContain("└── node_modules/");
+      expect(output).toContain("    └── …（42 项）");
+    });
+
     it("应支持包含路径、包含模式和排除模式", () => {
       const byPath = renderTree(tree, {
         includePathChains: [["repo", "src"]],
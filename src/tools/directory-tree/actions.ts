@@ -43,8 +43,12 @@ export interface GenerateTreeOptions {
   showFiles: boolean;
   /** 是否显示隐藏文件 */
   showHidden: boolean;
-  /** 最大深度（0 表示无限制） */
+  /** 最大深度（0 表示无限制），超出部分折叠显示为 "…（N 项）" */
   maxDepth: number;
+  /** 是否跟随符号链接（链接循环会被检测并跳过） */
+  followLinks?: boolean;
+  /** 后端输出格式，默认 ascii */
+  format?: "ascii" | "markdown" | "json";
   /** 过滤模式 */
   filterMode: "none" | "gitignore" | "custom" | "both";
   /** 自定义过滤规则（当 filterMode 为 'custom' 时使用） */
@@ -73,6 +77,8 @@ export interface TreeGenerationResult {
     filter_count: number;
     generated_at: string;
   };
+  /** 后端按 format 渲染的输出（ascii / markdown / json） */
+  output?: string;
 }
 
 /**
@@ -608,6 +614,13 @@ export function renderTreeRecursive(
     );
   }
 
+  // 后端按深度限制折叠的目录，只显示子项数量
+  if (node.is_dir && node.children.length === 0 && node.collapsed_count) {
+    const collapsedPrefix = isRoot ? "" : prefix + (isLast ? "    " : "│   ");
+    output.push(`${collapsedPrefix}└── …（${node.collapsed_count} 项）`);
+    return;
+  }
+
   if (node.is_dir && node.children.length > 0) {
     if (!isRoot && currentDepth >= options.maxDepth) return;
     const newPrefix = isRoot ? "" : prefix + (isLast ? "    " : "│   ");
@@ -698,6 +711,9 @@ export async function generateTree(
         showHidden: options.showHidden,
         maxDepth: options.maxDepth,
         ignorePatterns,
+        followLinks: options.followLinks,
+        format: options.format,
+        showSize: options.showSize,
       }
     );
 
@@ -713,6 +729,7 @@ export async function generateTree(
     return {
       structure: result.structure,
       stats: statsWithTime,
+      output: result.output,
     };
   } catch (error: any) {
     errorHandler.handle(error, {
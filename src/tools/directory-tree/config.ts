@@ -31,6 +31,7 @@ export interface TreeNode {
   size: number;
   children: TreeNode[];
   error?: string;
+  collapsed_count?: number; // 超过深度限制被折叠的直接子项数量
 }
 
 /**
@@ -17,7 +17,13 @@ import { listen } from "@tauri-apps/api/event";
 import { computed } from "vue";
 import { useFFmpegStore } from "../ffmpegStore";
 import { useFFmpeg } from "@/composables/useFFmpeg";
-import type { FFmpegParams, MediaMetadata, FFmpegProgress } from "../types";
+import type {
+  FFmpegBatchProgress,
+  FFmpegBatchTaskResult,
+  FFmpegParams,
+  MediaMetadata,
+  FFmpegProgress,
+} from "../types";
 import { createModuleLogger } from "@/utils/logger";
 import { createModuleErrorHandler } from "@/utils/errorHandler";
 
@@ -35,6 +41,13 @@ export function useFFmpegCore() {
     return await ffmpeg.checkAvailability(path);
   };
 
+  /**
+   * 获取 FFmpeg 版本与编码器支持情况
+   */
+  const getCapabilities = async () => {
+    return await ffmpeg.getCapabilities();
+  };
+
   /**
    * 获取媒体元数据
    */
@@ -66,6 +79,29 @@ export function useFFmpegCore() {
     );
   };
 
+  /**
+   * 从视频中截取一帧保存为 jpg/png（超过时长时取最后一帧）
+   */
+  const extractFrame = async (
+    inputPath: string,
+    timestampSec: number,
+    outputPath: string,
+    width?: number
+  ) => {
+    return await errorHandler.wrapAsync(
+      async () => {
+        return await invoke<string>("extract_video_frame", {
+          ffmpegPath: ffmpeg.activeFfmpegPath.value,
+          path: inputPath,
+          timestampSec,
+          outputPath,
+          width,
+        });
+      },
+      { userMessage: "截取视频帧失败" }
+    );
+  };
+
   /**
    * 启动处理任务
    */
@@ -96,6 +132,53 @@ export function useFFmpegCore() {
     }
   };
 
+  /**
+   * 批量启动处理任务（后端串行执行，单个失败不影响后续任务）
+   */
+  const startBatch = async (
+    batchId: string,
+    tasks: { taskId: string; params: FFmpegParams }[],
+    onProgress?: (progress: FFmpegBatchProgress) => void
+  ): Promise<FFmpegBatchTaskResult[]> => {
+    const unlisten = await listen<FFmpegBatchProgress>(
+      "ffmpeg-batch-progress",
+      (event) => {
+        const progress = event.payload;
+        if (progress.batchId !== batchId) return;
+
+        const { currentTaskId, result } = progress;
+        if (!result) {
+          store.updateTask(currentTaskId, { status: "processing" });
+        } else if (result.success) {
+          store.updateTask(currentTaskId, {
+            status: "completed",
+            outputPath: result.outputPath ?? undefined,
+          });
+        } else {
+          store.addTaskLog(
+            currentTaskId,
+            `[Error] 任务处理失败: ${result.error}`
+          );
+          store.updateTask(currentTaskId, {
+            status: "failed",
+            error: result.error ?? undefined,
+          });
+        }
+        onProgress?.(progress);
+      }
+    );
+
+    try {
+      logger.info("开始批量 FFmpeg 任务", { batchId, count: tasks.length });
+      return await invoke<FFmpegBatchTaskResult[]>("process_media_batch", {
+        batchId,
+        tasks,
+      });
+    } finally {
+      unlisten();
+    }
+  };
+
   /**
    * 终止任务
    */
@@ -136,13 +219,16 @@ export function useFFmpegCore() {
 
   return {
     checkAvailability,
+    getCapabilities,
     activeFfmpegPath: ffmpeg.activeFfmpegPath,
     globalFfmpegPath: ffmpeg.globalFfmpegPath,
     globalFfprobePath: ffmpeg.globalFfprobePath,
     isUsingGlobal: ffmpeg.isUsingGlobal,
     getMetadata,
     getFullMediaInfo,
+    extractFrame,
     startProcess,
+    startBatch,
     killProcess,
     setupListeners,
   };
@@ -34,6 +34,32 @@ export interface FFmpegProgress {
   currentTime: number; // 处理进度(秒)
   speed: string; // 如 "1.5x"
   bitrate: string;
+  frame?: number; // 已处理帧数
+  eta?: number; // 预计剩余时间(秒)
+}
+
+/**
+ * 批量处理中单个任务的结果
+ */
+export interface FFmpegBatchTaskResult {
+  taskId: string;
+  success: boolean;
+  outputPath: string | null;
+  error: string | null;
+}
+
+/**
+ * 批量处理的总体进度 (每个任务开始和结束时推送)
+ */
+export interface FFmpegBatchProgress {
+  batchId: string;
+  currentIndex: number;
+  currentTaskId: string;
+  total: number;
+  completed: number;
+  failed: number;
+  percent: number; // 按已结束任务数计算
+  result: FFmpegBatchTaskResult | null; // 任务开始时为 null
 }
 
 export interface FFmpegTask {
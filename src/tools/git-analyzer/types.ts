@@ -35,6 +35,53 @@ export interface FileChange {
   deletions: number;
 }
 
+/**
+ * git_file_history 返回的单条记录
+ */
+export interface FileHistoryEntry {
+  commit: GitCommit;
+  /** 该提交对文件的变更，path 为提交后的路径 */
+  change: FileChange;
+  /** 该提交中发生重命名时的旧路径 */
+  previous_path: string | null;
+}
+
+/**
+ * git_export_commits 的返回结果
+ */
+export interface GitExportResult {
+  /** 文本类格式（json/csv/markdown/html）的导出内容 */
+  content: string | null;
+  /** patch/mbox 格式生成的文件路径 */
+  files: string[];
+}
+
+/**
+ * git_diff_range 的返回结果
+ */
+export interface DiffRangeResult {
+  files: FileChange[];
+  stats: CommitStats;
+  /** unified diff 文本，仅在请求时返回 */
+  patch: string | null;
+}
+
+/**
+ * git_blame 返回的单行归属信息
+ */
+export interface BlameLine {
+  line_number: number;
+  original_line_number: number;
+  hash: string;
+  author: string;
+  email: string;
+  date: string;
+  summary: string;
+  /** 引入该行时的文件路径，文件被重命名过时与当前路径不同 */
+  original_path: string;
+  content: string;
+}
+
 export interface GitCommit {
   hash: string;
   author: string;
@@ -457,9 +457,9 @@ export function useAttachmentManager(
       const existingIndex = attachments.value.findIndex(
         (a) =>
           a.id !== pendingAsset.id &&
-          a.metadata?.sha256 &&
-          importedAsset.metadata?.sha256 &&
-          a.metadata.sha256 === importedAsset.metadata.sha256
+          a.metadata?.blake3 &&
+          importedAsset.metadata?.blake3 &&
+          a.metadata.blake3 === importedAsset.metadata.blake3
       );
 
       if (existingIndex !== -1) {
@@ -656,9 +656,9 @@ export function useAttachmentManager(
     const isDuplicate = attachments.value.some(
       (existing) =>
         existing.id === asset.id ||
-        (existing.metadata?.sha256 &&
-          asset.metadata?.sha256 &&
-          existing.metadata.sha256 === asset.metadata.sha256)
+        (existing.metadata?.blake3 &&
+          asset.metadata?.blake3 &&
+          existing.metadata.blake3 === asset.metadata.blake3)
     );
 
     if (isDuplicate) {
@@ -39,6 +39,7 @@ import {
   stopInspectorService,
   getInspectorServiceStatus,
   updateInspectorTarget,
+  updateInspectorTls,
   onRequestEvent,
   onResponseEvent,
   onStreamUpdateEvent,
@@ -228,6 +229,32 @@ export function useExternalProxy(options: UseExternalProxyOptions) {
     }
   }
 
+  async function updateTlsSettings(): Promise<void> {
+    if (!isRunning.value) return;
+
+    try {
+      isLoading.value = true;
+      error.value = null;
+
+      await updateInspectorTls(
+        config.value.accept_invalid_certs === true,
+        config.value.ca_cert_path
+      );
+
+      logger.info("代理 TLS 配置更新成功");
+      customMessage.success("TLS 设置已应用");
+    } catch (err) {
+      error.value = err instanceof Error ? err.message : "更新失败";
+      errorHandler.handle(err, {
+        userMessage: "更新代理 TLS 配置失败",
+        showToUser: false,
+      });
+      throw err;
+    } finally {
+      isLoading.value = false;
+    }
+  }
+
   /**
    * 启动时对账：检测后端是否已经在跑代理（前端重启 / 分离窗口场景）。
    * 若已运行则同步状态机、回填配置、并补挂事件监听器。
@@ -282,6 +309,7 @@ export function useExternalProxy(options: UseExternalProxyOptions) {
     startInspector,
     stopInspector,
     updateTargetUrl,
+    updateTlsSettings,
     checkInspectorStatus,
 
     // 工具
@@ -307,6 +307,7 @@ export function useInspectorManager() {
     startInspector: proxyMgr.startInspector,
     stopInspector: proxyMgr.stopInspector,
     updateTargetUrl: proxyMgr.updateTargetUrl,
+    updateTlsSettings: proxyMgr.updateTlsSettings,
     checkInspectorStatus: proxyMgr.checkInspectorStatus,
 
     // 方法 — 配置
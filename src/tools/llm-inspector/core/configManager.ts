@@ -41,6 +41,18 @@ function createDefaultSettings(): LlmInspectorSettings {
       port: 8999,
       target_url: "https://api.openai.com",
       header_override_rules: [],
+      body_override_rules: [],
+      mock_rules: [],
+      route_rules: [],
+      capture_filter: {
+        enabled: false,
+        mode: "blacklist",
+        patterns: [],
+        reject_filtered: false,
+      },
+      persist_records: false,
+      accept_invalid_certs: false,
+      ca_cert_path: "",
     },
     searchQuery: "",
     filterStatus: "",
@@ -184,6 +196,18 @@ export function getDefaultInspectorConfig(): InspectorConfig {
     port: 8999,
     target_url: "https://api.openai.com",
     header_override_rules: [],
+    body_override_rules: [],
+    mock_rules: [],
+    route_rules: [],
+    capture_filter: {
+      enabled: false,
+      mode: "blacklist",
+      patterns: [],
+      reject_filtered: false,
+    },
+    persist_records: false,
+    accept_invalid_certs: false,
+    ca_cert_path: "",
   };
 }
 
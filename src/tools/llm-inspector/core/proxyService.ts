@@ -19,9 +19,13 @@ import { createModuleErrorHandler } from "@utils/errorHandler";
 import type {
   InspectorConfig,
   InspectorStatus,
+  ModelPrice,
+  PersistedRecord,
+  PersistedRecordSummary,
   RequestRecord,
   ResponseRecord,
   StreamUpdate,
+  UsageSummary,
 } from "../types";
 
 const logger = createModuleLogger("LlmInspector/ProxyService");
@@ -114,6 +118,143 @@ export async function updateInspectorTarget(
   }
 }
 
+/**
+ * 运行中更新 TLS 配置，后端会重建转发客户端
+ */
+export async function updateInspectorTls(
+  acceptInvalidCerts: boolean,
+  caCertPath?: string
+): Promise<string> {
+  try {
+    logger.info("更新检查器 TLS 配置", { acceptInvalidCerts, caCertPath });
+    return await invoke<string>("update_inspector_tls", {
+      acceptInvalidCerts,
+      caCertPath: caCertPath || null,
+    });
+  } catch (error) {
+    errorHandler.handle(error, {
+      userMessage: "更新检查器 TLS 配置失败",
+      context: { acceptInvalidCerts, caCertPath },
+      showToUser: false,
+    });
+    throw new Error(`更新 TLS 配置失败: ${error}`);
+  }
+}
+
+/**
+ * 列出已持久化的历史记录（按时间倒序）
+ */
+export async function listPersistedRecords(
+  offset?: number,
+  limit?: number
+): Promise<PersistedRecordSummary[]> {
+  try {
+    return await invoke<PersistedRecordSummary[]>("inspector_list_records", {
+      offset,
+      limit,
+    });
+  } catch (error) {
+    errorHandler.handle(error, {
+      userMessage: "读取历史记录失败",
+      showToUser: false,
+    });
+    throw new Error(`读取历史记录失败: ${error}`);
+  }
+}
+
+/**
+ * 获取单条历史记录的完整内容（含流式响应的完整 body）
+ */
+export async function getPersistedRecord(
+  id: string
+): Promise<PersistedRecord> {
+  try {
+    return await invoke<PersistedRecord>("inspector_get_record", { id });
+  } catch (error) {
+    errorHandler.handle(error, {
+      userMessage: "读取历史记录详情失败",
+      context: { id },
+      showToUser: false,
+    });
+    throw new Error(`读取历史记录详情失败: ${error}`);
+  }
+}
+
+/**
+ * 读取被截断响应的完整 body（来自落盘文件）
+ */
+export async function readFullResponseBody(id: string): Promise<string> {
+  try {
+    return await invoke<string>("inspector_read_full_response_body", { id });
+  } catch (error) {
+    errorHandler.handle(error, {
+      userMessage: "读取完整响应体失败",
+      context: { id },
+      showToUser: false,
+    });
+    throw new Error(`读取完整响应体失败: ${error}`);
+  }
+}
+
+/**
+ * 回放历史请求到当前目标地址，返回响应状态码
+ *
+ * 回放经由本地代理发出，会像普通请求一样出现在捕获列表中。
+ */
+export async function replayPersistedRecord(id: string): Promise<number> {
+  try {
+    logger.info("回放历史请求", { id });
+    return await invoke<number>("inspector_replay_record", { id });
+  } catch (error) {
+    errorHandler.handle(error, {
+      userMessage: "回放请求失败",
+      context: { id },
+      showToUser: false,
+    });
+    throw new Error(`回放请求失败: ${error}`);
+  }
+}
+
+/**
+ * 把历史记录导出为 HAR 1.2 JSON 字符串（可导入 Chrome DevTools 等工具）
+ */
+export async function exportPersistedRecordsAsHar(
+  ids: string[]
+): Promise<string> {
+  try {
+    logger.info("导出 HAR", { count: ids.length });
+    return await invoke<string>("inspector_export_har", { ids });
+  } catch (error) {
+    errorHandler.handle(error, {
+      userMessage: "导出 HAR 失败",
+      context: { count: ids.length },
+      showToUser: false,
+    });
+    throw new Error(`导出 HAR 失败: ${error}`);
+  }
+}
+
+/**
+ * 获取本次代理会话各模型的 token 用量与估算费用
+ *
+ * @param prices 按模型名前缀覆盖后端内置的参考价格
+ */
+export async function getUsageSummary(
+  prices?: Record<string, ModelPrice>
+): Promise<UsageSummary> {
+  try {
+    return await invoke<UsageSummary>("inspector_get_usage_summary", {
+      prices: prices ?? null,
+    });
+  } catch (error) {
+    errorHandler.handle(error, {
+      userMessage: "获取用量统计失败",
+      showToUser: false,
+    });
+    throw new Error(`获取用量统计失败: ${error}`);
+  }
+}
+
 /**
  * 监听检查器请求事件
  */
@@ -17,6 +17,10 @@
  *
  * 包含：
  * - {@link HeaderOverrideRule} — 请求头覆盖规则；
+ * - {@link BodyOverrideRule} — 请求体改写规则；
+ * - {@link MockRule} — 响应 mock 规则；
+ * - {@link RouteRule} — 上游路由规则；
+ * - {@link CaptureFilter} — 外部代理的捕获过滤器；
  * - {@link InspectorConfig} — 检查器核心配置（端口 / 目标 / 规则）；
  * - {@link InspectorStatus} — 后端运行状态；
  * - {@link InspectorServiceState} — 前端运行状态视图；
@@ -32,11 +36,89 @@ export interface HeaderOverrideRule {
   value: string;
 }
 
+/** 请求体改写动作 */
+export type BodyOverrideAction = "set" | "remove";
+
+/** 单条请求体改写规则（仅作用于 JSON 请求体） */
+export interface BodyOverrideRule {
+  id: string;
+  enabled: boolean;
+  /** JSON Pointer 路径（RFC 6901），如 `/model`、`/messages/0` */
+  path: string;
+  action: BodyOverrideAction;
+  /** set 动作写入的 JSON 值 */
+  value?: unknown;
+}
+
+/** 响应 mock 规则：命中时代理不请求上游，直接返回给定响应 */
+export interface MockRule {
+  id: string;
+  enabled: boolean;
+  /** 请求方法，空字符串匹配任意方法 */
+  method: string;
+  /** 请求路径（不含查询参数），以 `*` 结尾时按前缀匹配 */
+  path: string;
+  status: number;
+  headers: Record<string, string>;
+  body: string;
+  /** 返回前的延迟（毫秒），用于模拟慢响应 */
+  delay_ms: number;
+}
+
+/**
+ * 上游路由规则：按路径前缀或自定义请求头把请求转发到不同上游。
+ * 两个条件都填写时需同时满足；规则按顺序匹配，未命中时使用 target_url。
+ */
+export interface RouteRule {
+  id: string;
+  enabled: boolean;
+  /** 路径前缀（如 `/ollama`），空字符串表示不按路径匹配 */
+  path_prefix: string;
+  /** 自定义请求头名（如 `x-upstream`），空字符串表示不按请求头匹配 */
+  header_name: string;
+  /** 请求头取值，为空时只要求请求头存在 */
+  header_value: string;
+  /** 上游基础地址，如 `http://127.0.0.1:11434` */
+  upstream: string;
+  /** 转发前去掉命中的路径前缀 */
+  strip_prefix: boolean;
+}
+
+/** 捕获过滤模式：黑名单不记录命中的请求，白名单仅记录命中的请求 */
+export type CaptureFilterMode = "blacklist" | "whitelist";
+
+/** 外部代理的捕获过滤器 */
+export interface CaptureFilter {
+  enabled: boolean;
+  mode: CaptureFilterMode;
+  /**
+   * 每条为可选的方法前缀 + 路径模式，如 `GET /health`、`/v1/*`；
+   * 路径默认按 glob 匹配，以 `re:` 开头时按正则匹配
+   */
+  patterns: string[];
+  /** 被过滤的请求直接返回 404，而不是照常转发 */
+  reject_filtered: boolean;
+}
+
 /** 检查器核心配置 */
 export interface InspectorConfig {
   port: number;
   target_url: string;
   header_override_rules: HeaderOverrideRule[];
+  /** 请求体改写规则（向后兼容可选） */
+  body_override_rules?: BodyOverrideRule[];
+  /** 响应 mock 规则（向后兼容可选） */
+  mock_rules?: MockRule[];
+  /** 上游路由规则（向后兼容可选） */
+  route_rules?: RouteRule[];
+  /** 捕获过滤器（向后兼容可选） */
+  capture_filter?: CaptureFilter;
+  /** 是否把外部代理捕获的记录持久化到磁盘（默认 false） */
+  persist_records?: boolean;
+  /** 是否接受无效的上游证书（自签名等，默认 false） */
+  accept_invalid_certs?: boolean;
+  /** 额外信任的自定义 CA 证书路径（PEM 或 DER） */
+  ca_cert_path?: string;
 }
 
 /** 后端返回的运行状态 */
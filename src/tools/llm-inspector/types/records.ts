@@ -18,7 +18,9 @@
  * 这里聚合「一次 HTTP 交互」的所有数据结构：
  * - {@link RequestRecord} / {@link ResponseRecord}：单向记录；
  * - {@link CombinedRecord}：组合后的双向记录（含来源 + Inspector 元数据）；
- * - {@link FilterOptions}：列表过滤选项（搜索 / 状态码）。
+ * - {@link FilterOptions}：列表过滤选项（搜索 / 状态码）；
+ * - {@link PersistedRecord} / {@link PersistedRecordSummary}：落盘的历史记录；
+ * - {@link TokenUsage} / {@link UsageSummary}：外部代理统计的 token 用量。
  */
 
 /** 单条请求记录 */
@@ -30,6 +32,12 @@ export interface RequestRecord {
   headers: Record<string, string>;
   body?: string;
   request_size: number;
+  /** 请求体被改写规则修改时的原始内容（body 为实际发送的内容） */
+  original_body?: string;
+  /** 实际转发到的上游基础地址 */
+  upstream?: string;
+  /** 命中的路由规则 ID，未命中时缺省（使用默认目标） */
+  route_rule_id?: string;
 }
 
 /** 单条响应记录 */
@@ -41,6 +49,46 @@ export interface ResponseRecord {
   body?: string;
   response_size: number;
   duration_ms: number;
+  /** 外部代理从响应体解析出的 usage（无 usage 字段时缺省） */
+  usage?: TokenUsage;
+  /** body 超过内存上限时只保留了尾部，完整内容需通过 body_path 按需读取 */
+  body_truncated?: boolean;
+  /** 完整 body 的落盘路径 */
+  body_path?: string;
+}
+
+/** token 用量（OpenAI 兼容命名） */
+export interface TokenUsage {
+  prompt_tokens: number;
+  completion_tokens: number;
+  total_tokens: number;
+}
+
+/** 单个模型在本次代理会话中的累计用量 */
+export interface ModelUsageStats {
+  model: string;
+  request_count: number;
+  prompt_tokens: number;
+  completion_tokens: number;
+  total_tokens: number;
+  /** 估算费用（美元），未匹配到价格时为 0 */
+  estimated_cost: number;
+  priced: boolean;
+}
+
+/** 模型价格（美元 / 百万 token） */
+export interface ModelPrice {
+  input_per_million: number;
+  output_per_million: number;
+}
+
+/** 本次代理会话的用量汇总，models 按 total_tokens 降序 */
+export interface UsageSummary {
+  models: ModelUsageStats[];
+  prompt_tokens: number;
+  completion_tokens: number;
+  total_tokens: number;
+  estimated_cost: number;
 }
 
 /**
@@ -90,3 +138,26 @@ export interface FilterOptions {
   searchQuery: string;
   filterStatus: string;
 }
+
+/** 持久化到磁盘的一次完整交互（外部代理捕获） */
+export interface PersistedRecord {
+  request: RequestRecord;
+  response?: ResponseRecord | null;
+  /** 原始请求路径（含查询参数），回放时拼接到当前目标地址 */
+  path: string;
+  /** 是否为流式响应（完整 body 由后端从独立文件补全） */
+  is_streaming: boolean;
+}
+
+/** 持久化记录索引摘要（用于历史列表） */
+export interface PersistedRecordSummary {
+  id: string;
+  timestamp: number;
+  method: string;
+  url: string;
+  status?: number | null;
+  duration_ms?: number | null;
+  request_size: number;
+  response_size?: number | null;
+  is_streaming: boolean;
+}
@@ -520,7 +520,7 @@ export async function searchEntries(
       recallId: r.recallId,
       recallName: r.recallName,
       tags: r.entry?.tags?.map((t: any) => t.name) || r.tags || [],
-      highlight: r.highlight,
+      highlight: r.highlight?.snippet,
     }));
 
     return {
@@ -17,7 +17,11 @@ import { useRecallCollectionStore } from "../stores/recallCollectionStore";
 import { recallStorage } from "../utils/recallStorage";
 import { customMessage } from "@/utils/customMessage";
 import { createModuleErrorHandler } from "@/utils/errorHandler";
-import type { RecallEntry, RecallEntryInput } from "../types";
+import type {
+  RecallEntry,
+  RecallEntryInput,
+  SkippedImportFile,
+} from "../types";
 import { useRecallVectorSync } from "./useRecallVectorSync";
 
 const errorHandler = createModuleErrorHandler("useRecallEntryManagement");
@@ -112,7 +116,12 @@ export function useRecallEntryManagement() {
    */
   async function batchImportFiles(paths: string[]) {
     if (!store.activeBaseId || !store.activeBaseMeta || paths.length === 0) {
-      return { ids: [], skippedCount: 0, dupeCount: 0 };
+      return {
+        ids: [],
+        skippedCount: 0,
+        dupeCount: 0,
+        skippedFiles: [] as SkippedImportFile[],
+      };
     }
 
     store.loading = true;
@@ -121,6 +130,7 @@ export function useRecallEntryManagement() {
         entries: RecallEntry[];
         skippedCount: number;
         duplicateCount: number;
+        skippedFiles: SkippedImportFile[];
       }>("recall_batch_import_files", {
         recallId: store.activeBaseId,
         paths,
@@ -162,10 +172,16 @@ export function useRecallEntryManagement() {
         ids: result.entries.map((e: any) => e.id),
         skippedCount: result.skippedCount,
         dupeCount: result.duplicateCount,
+        skippedFiles: result.skippedFiles ?? [],
       };
     } catch (e) {
       errorHandler.error(e, "批量导入文件失败");
-      return { ids: [], skippedCount: paths.length, dupeCount: 0 };
+      return {
+        ids: [],
+        skippedCount: paths.length,
+        dupeCount: 0,
+        skippedFiles: [] as SkippedImportFile[],
+      };
     } finally {
       store.loading = false;
     }
@@ -390,16 +406,29 @@ export function useRecallEntryManagement() {
       return;
     store.loading = true;
     try {
-      await recallStorage.deleteEntries(store.activeBaseId, entryIds);
-      for (const id of entryIds) {
+      const result = await recallStorage.deleteEntries(
+        store.activeBaseId,
+        entryIds
+      );
+      if (!result) return;
+
+      const failedIds = new Set(result.errors.map((e) => e.entryId));
+      const deletedIds = entryIds.filter((id) => !failedIds.has(id));
+      for (const id of deletedIds) {
         store.entriesCache.delete(id);
         if (store.activeEntryId === id) store.activeEntryId = null;
       }
       store.activeBaseMeta.entries = store.activeBaseMeta.entries.filter(
-        (e) => !entryIds.includes(e.id)
+        (e) => !deletedIds.includes(e.id)
       );
       await store.syncBaseMeta();
-      customMessage.success(`已删除 ${entryIds.length} 个条目`);
+      if (result.failedCount === 0) {
+        customMessage.success(`已删除 ${result.successCount} 个条目`);
+      } else {
+        customMessage.warning(
+          `已删除 ${result.successCount} 个条目，${result.failedCount} 个失败`
+        );
+      }
     } finally {
       store.loading = false;
     }
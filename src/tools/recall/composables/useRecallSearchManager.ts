@@ -89,6 +89,11 @@ export function useRecallSearchManager() {
       const finalExtraFilters = {
         texture: recallStore.config.vectorIndex?.texture,
         refractionIndex: recallStore.config.vectorIndex?.refractionIndex,
+        graphNeighbors: recallStore.config.vectorIndex?.graphNeighbors,
+        autoLensTags: recallStore.config.vectorIndex?.autoLensTags,
+        autoRefractionIndex: recallStore.config.vectorIndex?.autoRefractionIndex,
+        historyDecay: recallStore.config.vectorIndex?.historyDecay,
+        regularization: recallStore.config.vectorIndex?.regularization,
         k1: recallStore.config.vectorIndex?.k1,
         b: recallStore.config.vectorIndex?.b,
         ...(extraFilters || {}),
@@ -78,6 +78,72 @@ export interface RecallEntry {
   contentHash?: string;
 }
 
+/**
+ * 条目历史版本概要
+ */
+export interface RecallEntryVersion {
+  /** 版本号，即快照时间戳 (毫秒) */
+  version: number;
+  key: string;
+  summary: string;
+  contentHash?: string;
+  updatedAt: number;
+}
+
+/**
+ * 批量删除条目的结果
+ */
+export interface RecallBatchDeleteResult {
+  successCount: number;
+  failedCount: number;
+  /** 删除失败的条目及原因 */
+  errors: { entryId: string; error: string }[];
+}
+
+/**
+ * 条目文件的存储格式：明文 JSON 或 gzip 压缩 (.json.gz)
+ */
+export type EntryStorageFormat = "json" | "gzip";
+
+/**
+ * 引用关系图节点
+ */
+export interface ReferenceGraphNode {
+  id: string;
+  key: string;
+  recallId: string;
+  recallName: string;
+  /** 查询范围之外、因跨库引用被带入的节点 */
+  external: boolean;
+}
+
+/**
+ * 引用关系图的边 (source 通过 [[Key]] 引用 target)
+ */
+export interface ReferenceGraphEdge {
+  source: string;
+  /** 悬空引用时为 null */
+  target: string | null;
+  targetKey: string;
+  dangling: boolean;
+  crossRecall: boolean;
+}
+
+export interface ReferenceGraph {
+  nodes: ReferenceGraphNode[];
+  edges: ReferenceGraphEdge[];
+}
+
+/**
+ * 批量导入时被跳过的文件
+ */
+export interface SkippedImportFile {
+  path: string;
+  /** notText: 非文本且不支持提取；extractFailed: 读取或解析失败 */
+  reason: "notText" | "extractFailed";
+  message: string;
+}
+
 /**
  * 用于添加/更新条目的输入结构
  */
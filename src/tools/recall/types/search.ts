@@ -18,6 +18,24 @@ import type { RecallEntry } from "./recall-entry";
  * 搜索相关类型定义
  */
 
+/**
+ * 高亮区间 [start, end)，基于 snippet 的 UTF-16 下标
+ */
+export interface HighlightRange {
+  start: number;
+  end: number;
+}
+
+/**
+ * 检索结果中的高亮片段
+ */
+export interface RecallHighlight {
+  /** 截取的上下文片段，被截断的一侧带有 "..." */
+  snippet: string;
+  /** 需要高亮的区间 */
+  ranges: HighlightRange[];
+}
+
 /**
  * 搜索结果
  */
@@ -26,16 +44,31 @@ export interface RecallResult {
   entry: RecallEntry;
   /** 相关性评分 (0.0 - 1.0) */
   score: number;
-  /** 匹配类型: "vector", "keyword", "tag", "key" */
-  matchType: "vector" | "keyword" | "tag" | "key";
+  /** 匹配类型: "vector", "keyword", "bm25", "tag", "key" */
+  matchType: "vector" | "keyword" | "bm25" | "tag" | "key";
   /** 高亮片段 */
-  highlight: string | null;
+  highlight: RecallHighlight | null;
   /** 所属思绪集 ID */
   recallId: string;
   /** 所属思绪集名称 */
   recallName: string;
 }
 
+/**
+ * 分页检索结果（recall_search_paginated）
+ */
+export interface RecallSearchPage {
+  /** 当前页结果 */
+  items: RecallResult[];
+  /** 全量排序结果数 */
+  total: number;
+  offset: number;
+  limit: number;
+  hasMore: boolean;
+  /** 本页是否来自后端缓存 */
+  cached: boolean;
+}
+
 /**
  * 搜索过滤器
  */
@@ -48,6 +81,10 @@ export interface RecallSearchFilters {
   minScore?: number;
   /** 是否仅包含已启用的条目 */
   enabledOnly?: boolean;
+  /** 仅检索此时间之后创建的条目 (Unix 时间戳，秒) */
+  createdAfter?: number;
+  /** 仅检索此时间之后更新的条目 (Unix 时间戳，秒) */
+  updatedAfter?: number;
   /** 结果数量限制 */
   limit?: number;
   /** 指定检索引擎 ID */
@@ -21,7 +21,11 @@ import type {
   RecallCollectionIndex,
   RecallCollectionMeta,
   RecallEntry,
+  RecallEntryVersion,
+  RecallBatchDeleteResult,
   RecallCollection,
+  EntryStorageFormat,
+  ReferenceGraph,
 } from "../types";
 import { DEFAULT_WORKSPACE_CONFIG } from "../config";
 
@@ -210,6 +214,24 @@ export class KnowledgeStorage {
     );
   }
 
+  /**
+   * 转换思绪集条目的存储格式（明文 JSON ↔ gzip），返回实际转换的条目数
+   */
+  async convertEntryStorage(
+    baseId: string,
+    format: EntryStorageFormat
+  ): Promise<number | null> {
+    return await errorHandler.wrapAsync(
+      async () => {
+        return await invoke<number>("recall_convert_entry_storage", {
+          recallId: baseId,
+          format,
+        });
+      },
+      { userMessage: "转换条目存储格式失败" }
+    );
+  }
+
   /**
    * 导出思绪集
    */
@@ -222,6 +244,29 @@ export class KnowledgeStorage {
     );
   }
 
+  /**
+   * 导出思绪集为 Markdown 文档，返回生成的文件路径
+   */
+  async exportBaseAsMarkdown(
+    baseId: string,
+    outputDir: string,
+    options: { includeTags?: boolean; singleFile?: boolean } = {}
+  ): Promise<string[]> {
+    return (
+      (await errorHandler.wrapAsync(
+        async () => {
+          return await invoke<string[]>("recall_export_markdown", {
+            recallId: baseId,
+            outputDir,
+            includeTags: options.includeTags ?? true,
+            singleFile: options.singleFile ?? false,
+          });
+        },
+        { userMessage: "导出 Markdown 失败" }
+      )) || []
+    );
+  }
+
   /**
    * 删除思绪集
    */
@@ -278,6 +323,113 @@ export class KnowledgeStorage {
     );
   }
 
+  /**
+   * 列出条目的历史版本 (从新到旧)
+   */
+  async listEntryVersions(
+    baseId: string,
+    entryId: string
+  ): Promise<RecallEntryVersion[]> {
+    return (
+      (await errorHandler.wrapAsync(
+        async () => {
+          return await invoke<RecallEntryVersion[]>(
+            "recall_list_entry_versions",
+            { recallId: baseId, entryId }
+          );
+        },
+        { userMessage: "加载历史版本失败", showToUser: false }
+      )) || []
+    );
+  }
+
+  /**
+   * 将条目恢复到指定历史版本
+   */
+  async restoreEntryVersion(
+    baseId: string,
+    entryId: string,
+    version: number
+  ): Promise<RecallEntry | null> {
+    return await errorHandler.wrapAsync(
+      async () => {
+        return await invoke<RecallEntry>("recall_restore_entry_version", {
+          recallId: baseId,
+          entryId,
+          version,
+        });
+      },
+      { userMessage: "恢复历史版本失败" }
+    );
+  }
+
+  /**
+   * 合并标签 (renameTag 即单个来源的合并)，返回受影响的条目数
+   */
+  async mergeTags(
+    baseId: string,
+    sources: string[],
+    target: string,
+    strategy: "max" | "sum" = "max"
+  ): Promise<number> {
+    return (
+      (await errorHandler.wrapAsync(
+        async () => {
+          return await invoke<number>("recall_merge_tags", {
+            recallId: baseId,
+            sources,
+            target,
+            strategy,
+          });
+        },
+        { userMessage: "合并标签失败" }
+      )) ?? 0
+    );
+  }
+
+  /**
+   * 重命名标签，返回受影响的条目数
+   */
+  async renameTag(
+    baseId: string,
+    from: string,
+    to: string,
+    strategy: "max" | "sum" = "max"
+  ): Promise<number> {
+    return (
+      (await errorHandler.wrapAsync(
+        async () => {
+          return await invoke<number>("recall_rename_tag", {
+            recallId: baseId,
+            from,
+            to,
+            strategy,
+          });
+        },
+        { userMessage: "重命名标签失败" }
+      )) ?? 0
+    );
+  }
+
+  /**
+   * 获取条目引用关系图；指定 rootEntryId 时返回以其为中心的子图
+   */
+  async getReferenceGraph(
+    recallIds: string[],
+    options: { rootEntryId?: string; depth?: number } = {}
+  ): Promise<ReferenceGraph | null> {
+    return await errorHandler.wrapAsync(
+      async () => {
+        return await invoke<ReferenceGraph>("recall_get_reference_graph", {
+          recallIds,
+          rootEntryId: options.rootEntryId ?? null,
+          depth: options.depth ?? null,
+        });
+      },
+      { userMessage: "获取引用关系图失败", showToUser: false }
+    );
+  }
+
   /**
    * 删除单个条目
    */
@@ -292,11 +444,19 @@ export class KnowledgeStorage {
 
   /**
    * 批量删除条目
+   *
+   * 单个条目失败不会中断整体，失败项记录在返回结果中；整体提交失败时后端会回滚并返回 null
    */
-  async deleteEntries(baseId: string, entryIds: string[]): Promise<void> {
-    await errorHandler.wrapAsync(
+  async deleteEntries(
+    baseId: string,
+    entryIds: string[]
+  ): Promise<RecallBatchDeleteResult | null> {
+    return await errorHandler.wrapAsync(
       async () => {
-        await invoke("recall_batch_delete_entries", { recallId: baseId, entryIds });
+        return await invoke<RecallBatchDeleteResult>("recall_batch_delete_entries", {
+          recallId: baseId,
+          entryIds,
+        });
       },
       { userMessage: `批量删除 ${entryIds.length} 个条目失败` }
     );
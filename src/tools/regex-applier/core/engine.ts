@@ -330,6 +330,8 @@ export async function processFiles(
           rules: rulesForBackend,
           forceTxt: options.forceTxt ?? false,
           filenameSuffix: options.filenameSuffix ?? "",
+          dryRun: options.dryRun ?? false,
+          ignorePatterns: options.ignorePatterns ?? [],
         }
       );
 
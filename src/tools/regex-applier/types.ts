@@ -86,6 +86,10 @@ export interface FileProcessOptions {
   forceTxt?: boolean;
   /** 文件名后缀 */
   filenameSuffix?: string;
+  /** 预览模式：不写入文件，只返回每个文件的替换预览 */
+  dryRun?: boolean;
+  /** 目录递归时额外忽略的 glob 模式（.gitignore 始终生效） */
+  ignorePatterns?: string[];
 }
 
 /** 文件处理结果（来自 Rust 后端） */
@@ -102,6 +106,27 @@ export interface FileProcessResult {
   errors?: Record<string, string>;
   /** 后端日志 */
   logs?: Array<{ level: string; message: string }>;
+  /** 预览结果（仅 dry-run 模式） */
+  previews?: FilePreview[];
+}
+
+/** dry-run 预览：单个替换位置的上下文片段 */
+export interface ReplacementPreview {
+  rule_index: number;
+  line: number;
+  before: string;
+  after: string;
+}
+
+/** dry-run 预览：单个文件的处理结果 */
+export interface FilePreview {
+  file_path: string;
+  output_path: string;
+  matches: number;
+  original_len: number;
+  final_len: number;
+  len_diff: number;
+  samples: ReplacementPreview[];
 }
 
 /** 格式化的处理摘要 */
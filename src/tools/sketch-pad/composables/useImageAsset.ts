@@ -326,7 +326,7 @@ export function useImageAsset() {
       context.assetRefs.push({
         assetId: asset.id,
         originalName: asset.name,
-        hash: asset.metadata?.sha256 || "",
+        hash: asset.metadata?.blake3 || "",
         usedBy: [objectId],
       });
     }
@@ -13,7 +13,15 @@
 // limitations under the License.
 
 import { invoke } from "@tauri-apps/api/core";
-import type { ImageBlock, OcrResult } from "../types";
+import { listen } from "@tauri-apps/api/event";
+import type {
+  ImageBlock,
+  NativeOcrBatchItem,
+  NativeOcrBatchProgress,
+  NativeOcrBlock,
+  NativeOcrLanguage,
+  OcrResult,
+} from "../types";
 import { createModuleErrorHandler } from "@/utils/errorHandler";
 import { createModuleLogger } from "@/utils/logger";
 
@@ -29,7 +37,8 @@ export function useNativeEngine() {
    * 使用原生 API 识别单个图片
    */
   const recognizeSingle = async (
-    canvas: HTMLCanvasElement
+    canvas: HTMLCanvasElement,
+    lang?: string
   ): Promise<{ text: string; confidence: number }> => {
     try {
       // 将 canvas 转换为 base64
@@ -40,6 +49,7 @@ export function useNativeEngine() {
         "native_ocr",
         {
           imageData,
+          lang,
         }
       );
 
@@ -62,7 +72,8 @@ export function useNativeEngine() {
   const recognizeBatch = async (
     blocks: ImageBlock[],
     onProgress?: (results: OcrResult[]) => void,
-    signal?: AbortSignal
+    signal?: AbortSignal,
+    lang?: string
   ): Promise<OcrResult[]> => {
     const results: OcrResult[] = blocks.map((block) => ({
       blockId: block.id,
@@ -97,7 +108,7 @@ export function useNativeEngine() {
           engine: "native",
         });
 
-        const { text, confidence } = await recognizeSingle(block.canvas);
+        const { text, confidence } = await recognizeSingle(block.canvas, lang);
 
         if (signal?.aborted) {
           results[i].status = "cancelled";
@@ -143,8 +154,80 @@ export function useNativeEngine() {
     return results;
   };
 
+  /**
+   * 识别单个图片并返回每个词/行的边界框，用于在原图上叠加可选取的文字区域
+   *
+   * `minConfidence` 只过滤带置信度的块；Windows OCR 不提供置信度，在该平台上不生效
+   */
+  const recognizeWithBlocks = async (
+    canvas: HTMLCanvasElement,
+    options: { lang?: string; minConfidence?: number } = {}
+  ): Promise<{
+    text: string;
+    confidence: number;
+    blocks: NativeOcrBlock[];
+  }> => {
+    const result = await invoke<{
+      text: string;
+      confidence: number;
+      blocks?: NativeOcrBlock[];
+    }>("native_ocr", {
+      imageData: canvas.toDataURL("image/png"),
+      lang: options.lang,
+      withBlocks: true,
+      minConfidence: options.minConfidence,
+    });
+
+    return {
+      text: result.text.trim(),
+      confidence: result.confidence,
+      blocks: result.blocks ?? [],
+    };
+  };
+
+  /**
+   * 获取系统已安装语言包的 OCR 语言
+   */
+  const listLanguages = async (): Promise<NativeOcrLanguage[]> => {
+    return await invoke<NativeOcrLanguage[]>("ocr_list_available_languages");
+  };
+
+  /**
+   * 批量识别本地图片文件（后端并发执行，单张失败不影响其他图片）
+   */
+  const recognizeFiles = async (
+    paths: string[],
+    lang?: string,
+    onProgress?: (progress: NativeOcrBatchProgress) => void
+  ): Promise<NativeOcrBatchItem[]> => {
+    const unlisten = onProgress
+      ? await listen<NativeOcrBatchProgress>("ocr-batch-progress", (event) =>
+          onProgress(event.payload)
+        )
+      : undefined;
+
+    try {
+      logger.info(`使用原生 OCR 批量识别文件 (${paths.length} 张)`, { lang });
+      return await invoke<NativeOcrBatchItem[]>("native_ocr_batch", {
+        paths,
+        lang,
+      });
+    } catch (error) {
+      errorHandler.handle(error as Error, {
+        userMessage: "Native OCR 批量识别失败",
+        showToUser: false,
+      });
+      throw error;
+    } finally {
+      unlisten?.();
+    }
+  };
+
   return {
     recognizeSingle,
     recognizeBatch,
+    recognizeWithBlocks,
+    recognizeFiles,
+    listLanguages,
   };
 }
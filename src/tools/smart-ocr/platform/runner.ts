@@ -69,7 +69,12 @@ export function useOcrRunner() {
           );
           break;
         case "native":
-          finalResults = await runNativeEngine(blocks, onProgress, signal);
+          finalResults = await runNativeEngine(
+            blocks,
+            config,
+            onProgress,
+            signal
+          );
           break;
         case "vlm":
           finalResults = await runVlmEngine(blocks, config, onProgress, signal);
@@ -148,11 +153,12 @@ export function useOcrRunner() {
    */
   const runNativeEngine = async (
     blocks: ImageBlock[],
+    config: Extract<OcrEngineConfig, { type: "native" }>,
     onProgress?: (results: OcrResult[]) => void,
     signal?: AbortSignal
   ): Promise<OcrResult[]> => {
     const { recognizeBatch } = useNativeEngine();
-    return await recognizeBatch(blocks, onProgress, signal);
+    return await recognizeBatch(blocks, onProgress, signal, config.language);
   };
 
   /**
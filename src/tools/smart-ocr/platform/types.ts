@@ -19,6 +19,9 @@ import type {
   EngineConfigs,
   ImageBlock,
   NativeEngineConfig,
+  NativeOcrBatchItem,
+  NativeOcrBatchProgress,
+  NativeOcrLanguage,
   OcrEngineConfig,
   OcrEngineType,
   OcrHistoryIndexItem,
@@ -37,6 +40,9 @@ export type {
   EngineConfigs,
   ImageBlock,
   NativeEngineConfig,
+  NativeOcrBatchItem,
+  NativeOcrBatchProgress,
+  NativeOcrLanguage,
   OcrEngineConfig,
   OcrEngineType,
   OcrHistoryIndexItem,
@@ -75,6 +81,14 @@ export interface OcrBox {
   height: number;
 }
 
+/**
+ * 原生 OCR 返回的文字块（坐标单位为原图像素）
+ */
+export interface NativeOcrBlock extends OcrBox {
+  text: string;
+  level: "word" | "line";
+}
+
 export interface OcrExtension {
   id: string;
   contributionId: string;
@@ -30,6 +30,7 @@ export type OcrEngineConfig =
   | {
       type: "native";
       name: string;
+      language?: string; // 如 "chi_sim+eng" 或 "ja"，未设置时使用简体中文
     }
   | {
       type: "vlm";
@@ -70,6 +71,15 @@ export interface TesseractEngineConfig {
  */
 export interface NativeEngineConfig {
   name: string;
+  language?: string; // 如 "chi_sim+eng" 或 "ja"，未设置时使用简体中文
+}
+
+/**
+ * 系统已安装的原生 OCR 语言
+ */
+export interface NativeOcrLanguage {
+  tag: string; // BCP-47 语言标签，如 "zh-Hans"
+  displayName: string;
 }
 
 /**
@@ -157,6 +167,26 @@ export interface OcrResult {
   ignored?: boolean; // 是否被忽略（复制时跳过）
 }
 
+/**
+ * 原生批量 OCR 中单张图片的结果
+ */
+export interface NativeOcrBatchItem {
+  index: number; // 在请求列表中的位置
+  path: string;
+  text?: string;
+  confidence?: number;
+  error?: string; // 失败原因，成功时为空
+}
+
+/**
+ * 原生批量 OCR 进度事件
+ */
+export interface NativeOcrBatchProgress {
+  completed: number;
+  total: number;
+  item: NativeOcrBatchItem;
+}
+
 /**
  * 智能切图配置
  */
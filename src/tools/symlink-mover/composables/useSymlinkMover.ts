@@ -209,6 +209,7 @@ export function useSymlinkMoverLogic() {
           targetDir: options.targetDir,
           linkType: options.linkType,
           baseSourceDir: options.baseSourceDir,
+          conflictPolicy: options.conflictPolicy ?? "skip",
         });
 
         logger.info("移动和链接操作完成", { result });
@@ -237,6 +238,7 @@ export function useSymlinkMoverLogic() {
           targetDir: options.targetDir,
           linkType: options.linkType,
           baseSourceDir: options.baseSourceDir,
+          relative: options.relative ?? false,
         });
 
         logger.info("创建链接操作完成", { result });
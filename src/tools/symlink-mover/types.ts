@@ -54,6 +54,21 @@ export interface OperationLog {
   sourcePaths: string[];
   totalSize: number;
   processedFiles: string[];
+  appliedPolicies?: AppliedConflictPolicy[];
+}
+
+/**
+ * 冲突处理策略
+ */
+export type ConflictPolicy = "skip" | "overwrite" | "rename";
+
+/**
+ * 单个文件实际采用的冲突策略
+ */
+export interface AppliedConflictPolicy {
+  sourcePath: string;
+  targetPath: string;
+  policy: ConflictPolicy | "none";
 }
 
 /**
@@ -84,6 +99,7 @@ export interface MoveAndLinkOptions {
   targetDir: string;
   linkType: LinkType;
   baseSourceDir?: string; // 镜像搬家模式的基准源目录
+  conflictPolicy?: ConflictPolicy; // 目标已存在时的处理策略，默认 skip
 }
 
 /**
@@ -94,6 +110,7 @@ export interface CreateLinksOnlyOptions {
   targetDir: string;
   linkType: LinkType;
   baseSourceDir?: string; // 镜像搬家模式的基准源目录
+  relative?: boolean; // 是否创建相对路径符号链接（仅 symlink 有效）
 }
 
 /**
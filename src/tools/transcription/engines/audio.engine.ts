@@ -91,10 +91,9 @@ export class AudioTranscriptionEngine implements ITranscriptionEngine {
 
       let shouldCompress = false;
       if (config.audio?.enableCompression && ffmpegPath) {
-        const isFFmpegAvailable = await invoke<boolean>(
-          "check_ffmpeg_availability",
-          { path: ffmpegPath }
-        );
+        const { available: isFFmpegAvailable } = await invoke<{
+          available: boolean;
+        }>("check_ffmpeg_availability", { path: ffmpegPath });
         if (isFFmpegAvailable) {
           try {
             const fileStat = await stat(fullPath);
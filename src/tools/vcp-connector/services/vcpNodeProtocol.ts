@@ -14,6 +14,7 @@
 
 import { createModuleLogger } from "@/utils/logger";
 import { createModuleErrorHandler } from "@/utils/errorHandler";
+import { readFileAsBase64Chunked } from "@/utils/base64";
 import { toolRegistryManager } from "@/services/registry";
 import { invoke } from "@tauri-apps/api/core";
 import { useVcpDistributedStore } from "../stores/vcpDistributedStore";
@@ -452,9 +453,7 @@ export class VcpNodeProtocol {
     logger.info(`Handling internal_request_file: ${filePath}`);
 
     // 1. 读取文件为 Base64
-    const fileData = await invoke<string>("read_file_as_base64", {
-      path: filePath,
-    });
+    const fileData = await readFileAsBase64Chunked(filePath);
 
     // 2. 检测 MIME 类型 (使用后端能力)
     const mimeType = await invoke<string>("get_file_mime_type", {
@@ -61,20 +61,42 @@ export interface DerivedDataInfo {
  * 资产元数据
  */
 export interface AssetMetadata {
-  /** 图片宽度（像素） */
+  /** 图片/视频宽度（像素） */
   width?: number;
-  /** 图片高度（像素） */
+  /** 图片/视频高度（像素） */
   height?: number;
   /** 音视频时长（秒） */
   duration?: number;
+  /** 音视频主流编解码器名称（如 h264、aac） */
+  codec?: string;
   /** 音频波形采样数据 (0-255 的整数数组，长度通常为 100) */
   audioWaveform?: number[];
-  /** 文件 SHA-256 哈希值，用于去重 */
-  sha256?: string;
-  /** 转换前原始文件 SHA-256，用于 DOC 等转换类资产在转换前去重 */
-  originalSha256?: string;
+  /** 文件 BLAKE3 哈希值，用于去重 */
+  blake3?: string;
+  /** 转换前原始文件 BLAKE3，用于 DOC 等转换类资产在转换前去重 */
+  originalBlake3?: string;
   /** 衍生数据映射表，key 为类型 (e.g., "transcription", "ocr") */
   derived?: Record<string, DerivedDataInfo>;
+  /** 图片 EXIF 信息 */
+  exif?: ImageExif;
+}
+
+/**
+ * 从图片 EXIF 中提取的信息
+ */
+export interface ImageExif {
+  /** 方向标签 (1-8) */
+  orientation?: number;
+  /** 拍摄时间 (RFC 3339, UTC) */
+  takenAt?: string;
+  /** 相机厂商 */
+  cameraMake?: string;
+  /** 相机型号 */
+  cameraModel?: string;
+  /** 纬度，南纬为负 */
+  gpsLatitude?: number;
+  /** 经度，西经为负 */
+  gpsLongitude?: number;
 }
 
 /**
@@ -91,6 +113,7 @@ export type AssetImportStatus = "pending" | "importing" | "complete" | "error";
 export type AssetImportPhase =
   | "queued" // 排队等待处理
   | "preparing" // 准备导入源
+  | "downloading" // 从网络 URL 下载
   | "converting" // 转换文档格式 (如 DOC → DOCX)
   | "hashing" // 计算文件哈希（校验/去重）
   | "copying" // 复制文件到资产库
@@ -173,6 +196,11 @@ export interface Asset {
    */
   origins: AssetOrigin[];
 
+  /**
+   * 用户标签
+   */
+  tags?: string[];
+
   /**
    * 可选的、特定于文件类型的元数据
    */
@@ -278,6 +306,25 @@ export interface AssetImportOptions {
    * 指定一个子目录来存储资产，而不是按类型和日期
    */
   subfolder?: string;
+
+  /**
+   * 缩略图输出格式（默认: "jpeg"）
+   *
+   * JPEG 会丢弃透明通道，带透明背景的图片建议使用 "webp"。
+   */
+  thumbnailFormat?: "jpeg" | "webp";
+
+  /**
+   * 缩略图质量 1-100（默认: 75），仅对 JPEG 生效
+   *
+   * WebP 缩略图使用无损编码，与 thumbnailFormat: "webp" 同时指定时导入会被拒绝。
+   */
+  thumbnailQuality?: number;
+
+  /**
+   * 缩略图边长（像素，默认: 400）
+   */
+  thumbnailSize?: number;
 }
 
 /**
@@ -304,6 +351,8 @@ export interface DuplicateFileInfo {
   hash: string;
   /** 重复文件的相对路径列表 */
   files: string[];
+  /** 重复文件对应的资产 ID，与 files 一一对应 */
+  assetIds: string[];
   /** 文件总大小（单个文件大小） */
   size: number;
   /** 重复文件数量 */
@@ -345,6 +394,10 @@ export interface ListAssetsPaginatedPayload {
   filterSourceModule?: string | "all";
   searchQuery?: string;
   showDuplicatesOnly?: boolean;
+  /** 按标签筛选，为空时不筛选 */
+  filterTags?: string[];
+  /** 多个标签的匹配方式："all" 需全部命中 (AND)，"any" 命中任一即可 (OR)，默认 "all" */
+  tagMatchMode?: "all" | "any";
 }
 
 export interface PaginatedAssetsResponse {
@@ -355,12 +408,55 @@ export interface PaginatedAssetsResponse {
   page: number;
 }
 
+/**
+ * 批量删除资产的结果
+ */
+export interface BatchAssetDeleteResult {
+  /** 删除失败的资产 ID */
+  failedIds: string[];
+  /** 实际释放的磁盘字节数（移入回收站时为 0） */
+  freedBytes: number;
+}
+
+/**
+ * 资产集合（相册），只保存成员资产的 ID
+ */
+export interface AssetCollection {
+  id: string;
+  name: string;
+  description?: string;
+  /** ISO 8601 */
+  createdAt: string;
+  /** ISO 8601 */
+  updatedAt: string;
+  /** 按加入顺序排列的资产 ID */
+  assetIds: string[];
+}
+
 export interface AssetStats {
   totalAssets: number;
   totalSize: number;
   typeCounts: Record<AssetType, number>;
   sourceModuleCounts: Record<string, number>;
   originCounts: Record<AssetOriginType, number>;
+  /** 按创建时间（UTC 年-月，如 "2025-10"）聚合的资产数量，按月份升序 */
+  monthlyCounts: Record<string, number>;
+}
+
+/**
+ * Catalog 索引校验结果
+ */
+export interface CatalogVerifyResult {
+  /** 非空行总数 */
+  totalLines: number;
+  /** 可正常解析的条目数 */
+  validEntries: number;
+  /** 损坏的行数 */
+  corruptedLines: number;
+  /** 损坏行的行号（从 1 开始） */
+  corruptedLineNumbers: number[];
+  /** 损坏行数超过阈值，建议重建目录索引 */
+  needsRebuild: boolean;
 }
 
 /**
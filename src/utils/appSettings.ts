@@ -55,7 +55,13 @@ export const BUILTIN_WALLPAPERS: BuiltinWallpaper[] = [
 ];
 
 // 窗口特效类型（根据不同操作系统支持）
-export type WindowEffect = "none" | "blur" | "acrylic" | "mica" | "vibrancy";
+export type WindowEffect =
+  | "none"
+  | "blur"
+  | "acrylic"
+  | "mica"
+  | "mica-alt"
+  | "vibrancy";
 
 // 定义颜色混合模式的类型
 export type BlendMode =
@@ -12,8 +12,12 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+import { invoke } from "@tauri-apps/api/core";
 import Base64Worker from "@/workers/base64.worker?worker";
 
+/** 分块读取的块大小，必须是 3 的倍数才能直接拼接 base64 片段 */
+const BASE64_CHUNK_SIZE = 3 * 1024 * 1024;
+
 /**
  * 使用 Worker 将 ArrayBuffer 转换为 Base64 字符串
  * 这种方法不会阻塞主线程，适合处理大文件
@@ -68,3 +72,38 @@ export const convertArrayBufferToBase64 = (
     worker.postMessage(actualBuffer, [actualBuffer]);
   });
 };
+
+/**
+ * 通过后端分块接口读取文件为 Base64，避免一次性读取大文件撑爆内存
+ *
+ * @param path 文件路径
+ * @returns Base64 字符串
+ */
+export const readFileAsBase64Chunked = async (
+  path: string
+): Promise<string> => {
+  const parts: string[] = [];
+  let offset = 0;
+
+  while (true) {
+    const chunk = await invoke<{
+      data: string;
+      bytesRead: number;
+      totalSize: number;
+    }>("read_file_base64_chunk", {
+      path,
+      offset,
+      length: BASE64_CHUNK_SIZE,
+    });
+
+    if (chunk.bytesRead > 0) {
+      parts.push(chunk.data);
+      offset += chunk.bytesRead;
+    }
+    if (chunk.bytesRead < BASE64_CHUNK_SIZE || offset >= chunk.totalSize) {
+      break;
+    }
+  }
+
+  return parts.join("");
+};
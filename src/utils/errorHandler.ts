@@ -67,6 +67,27 @@ export interface StandardError {
   originalError?: any;
 }
 
+/**
+ * 后端 AppError 序列化后的结构（见 src-tauri/src/error.rs）
+ *
+ * code 取值如 NOT_FOUND、PERMISSION_DENIED、TOO_LARGE、IO_ERROR 等，可用于 i18n 与重试决策
+ */
+export interface AppErrorPayload {
+  code: string;
+  message: string;
+  details?: string | null;
+}
+
+/** 判断 invoke 抛出的错误是否为后端结构化错误 */
+export function isAppError(error: unknown): error is AppErrorPayload {
+  return (
+    !!error &&
+    typeof error === "object" &&
+    typeof (error as AppErrorPayload).code === "string" &&
+    typeof (error as AppErrorPayload).message === "string"
+  );
+}
+
 /** HTML 转义映射表，替代 lodash.escape，提升为常量避免每次调用重建对象 */
 const HTML_ESCAPE_MAP: Record<string, string> = {
   "&": "&amp;",